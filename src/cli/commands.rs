@@ -1,4 +1,6 @@
-use clap::{Parser, ValueEnum};
+use clap::builder::{PossibleValue, StringValueParser, TypedValueParser};
+use clap::{ArgGroup, Parser, ValueEnum};
+use std::ffi::OsStr;
 use std::path::PathBuf;
 
 #[cfg(feature = "unstable-dynamic")]
@@ -32,21 +34,24 @@ treemd -s Installation doc.md # Extract section";
 #[command(version)]
 #[command(about = "A markdown navigator with tree-based structural navigation")]
 #[command(long_about = LONG_ABOUT)]
+#[command(group = ArgGroup::new("query_source").args(["query", "query_file"]))]
 pub struct Cli {
     /// Markdown file(s) to view (.md or .markdown), directory, URL, or '-' for stdin
     ///
     /// Path to the markdown file(s) to open. Use '-' to read from stdin.
-    /// If a directory is specified, opens file picker in that directory.
-    /// Multiple files can be specified for the file picker.
+    /// A directory lists its markdown files (recursively) to browse and preview,
+    /// skipping hidden files and plain names from its .gitignore (`target/`);
+    /// glob and `!` patterns there are not applied.
+    /// With several files the first is opened; `>` / `<` switch between them.
     /// If no file is specified and stdin is piped, input is read from stdin.
     /// http(s) URLs are fetched directly; `github:owner/repo` opens that
     /// repository's README.
     ///
     /// Examples:
     ///   treemd README.md         # Open file
-    ///   treemd .                 # Open file picker in current directory
-    ///   treemd docs/             # Open file picker in docs directory
-    ///   treemd *.md              # Open file picker with matched files
+    ///   treemd .                 # Browse markdown files under the current directory
+    ///   treemd docs/             # Browse markdown files under docs/
+    ///   treemd *.md              # Open matched files, `>` / `<` to switch
     ///   treemd -                 # Read from stdin
     ///   cat doc.md | treemd -l   # Pipe markdown
     ///   treemd https://raw.githubusercontent.com/rust-lang/rust/HEAD/README.md
@@ -54,6 +59,34 @@ pub struct Cli {
     #[arg(add = markdown_file_completer())]
     pub file: Vec<PathBuf>,
 
+    /// Print the effective keybindings (defaults plus config) and exit
+    ///
+    /// Lists every key of every mode with the action it runs:
+    ///   treemd --list-keys             # Readable table
+    ///   treemd --list-keys=toml        # [keybindings] section to paste into config.toml
+    #[arg(
+        long = "list-keys",
+        value_name = "FORMAT",
+        num_args = 0..=1,
+        require_equals = true,
+        default_missing_value = "table"
+    )]
+    pub list_keys: Option<KeyListFormat>,
+
+    /// Print a theme's colors as a [[theme.custom]] block and exit
+    ///
+    /// Dumps every color of a built-in or custom theme (with your [theme]
+    /// overrides applied) in the config.toml format, as a starting point for
+    /// a theme of your own:
+    ///   treemd --dump-theme Nord >> ~/.config/treemd/config.toml
+    #[arg(
+        long = "dump-theme",
+        value_name = "THEME",
+        value_parser = theme_names(),
+        hide_possible_values = true
+    )]
+    pub dump_theme: Option<String>,
+
     /// Print a man page to stdout (roff format)
     ///
     /// Generates the treemd(1) man page for packaging:
@@ -61,6 +94,15 @@ pub struct Cli {
     #[arg(long = "man-page")]
     pub man_page: bool,
 
+    /// Print a shell completion script to stdout and exit
+    ///
+    /// Writes static completions for bash, zsh, fish, elvish or PowerShell,
+    /// including the values of --output, --query-output and --theme:
+    ///   treemd --completions zsh > _treemd
+    ///   treemd --completions bash > /usr/share/bash-completion/completions/treemd
+    #[arg(long = "completions", value_name = "SHELL")]
+    pub completions: Option<clap_complete::Shell>,
+
     /// Show heading at or before a specific line number (non-interactive)
     ///
     /// Finds and prints the heading that appears at or immediately before the
@@ -71,6 +113,18 @@ pub struct Cli {
     #[arg(long = "at-line", value_name = "LINE")]
     pub at_line: Option<usize>,
 
+    /// Print the source position of the heading an anchor points to
+    ///
+    /// Resolves the anchor (a heading slug like `installation`, with or
+    /// without `#`) and prints the heading's 1-based line and byte offset as
+    /// `LINE OFFSET`; the offset counts bytes as stored in the file, BOM and
+    /// CRLF line endings included. Exits non-zero when no heading matches.
+    /// With `--format json` the section's end position is included as well.
+    ///
+    /// Example: --resolve-anchor installation
+    #[arg(long = "resolve-anchor", value_name = "ANCHOR")]
+    pub resolve_anchor: Option<String>,
+
     /// List all headings in the document (non-interactive)
     ///
     /// Displays all headings with their level indicators (# for h1, ## for h2, etc.).
@@ -85,6 +139,28 @@ pub struct Cli {
     #[arg(long = "tree")]
     pub tree: bool,
 
+    /// Print a table of contents as a nested markdown list (non-interactive)
+    ///
+    /// Each heading becomes a `[Text](#anchor)` link using GitHub's anchors,
+    /// ready to paste back into the document.
+    ///
+    /// Example: treemd --toc --max-level 3 README.md
+    #[arg(long = "toc")]
+    pub toc: bool,
+
+    /// Leave headings deeper than this level (1-6) out of --toc
+    #[arg(
+        long = "max-level",
+        value_name = "LEVEL",
+        requires = "toc",
+        value_parser = clap::value_parser!(u8).range(1..=6)
+    )]
+    pub max_level: Option<u8>,
+
+    /// Number --toc entries `1.`, `2.`, … instead of using bullets
+    #[arg(long = "numbered", requires = "toc")]
+    pub numbered: bool,
+
     /// Filter headings by text pattern (case-insensitive)
     ///
     /// Only shows headings containing the specified text.
@@ -103,13 +179,18 @@ pub struct Cli {
     #[arg(short = 'L', long = "level", value_name = "LEVEL")]
     pub level: Option<usize>,
 
-    /// Output format for --list and --tree modes
+    /// Output format for --list, --tree and --resolve-anchor modes
     ///
     /// Controls how headings are displayed:
     ///   plain - Human-readable text (default)
     ///   json  - JSON array for scripting/parsing
     ///   tree  - Box-drawing tree structure
-    #[arg(short = 'o', long = "output", default_value = "plain")]
+    #[arg(
+        short = 'o',
+        long = "output",
+        visible_alias = "format",
+        default_value = "plain"
+    )]
     pub output: OutputFormat,
 
     /// Extract specific section by heading name
@@ -128,6 +209,56 @@ pub struct Cli {
     #[arg(long = "count")]
     pub count: bool,
 
+    /// Print the document as plain sentences for text-to-speech
+    ///
+    /// Emits one sentence per line with no markup: headings are announced as
+    /// "Heading level N:", links are read as their text, list items become
+    /// "Item:" lines and code blocks are announced by language.
+    ///
+    /// Example: treemd --tts README.md | say
+    #[arg(long = "tts")]
+    pub tts: bool,
+
+    /// Leave code blocks out of --tts output instead of announcing them
+    #[arg(long = "tts-skip-code", requires = "tts")]
+    pub tts_skip_code: bool,
+
+    /// Print the whole parsed document as a nested JSON tree
+    ///
+    /// Unlike `-l -o json`, which follows the outline, this exports the full
+    /// structure: front matter, blocks before the first heading, and every
+    /// section with its typed blocks (paragraphs, code, lists, tables, ...)
+    /// and nested children, each with its source span.
+    ///
+    /// Example: treemd --json-tree README.md | jq '.sections[0].blocks'
+    #[arg(long = "json-tree")]
+    pub json_tree: bool,
+
+    /// Export the document to another format on stdout
+    ///
+    /// `html` writes a standalone page with semantic markup, heading anchors
+    /// matching `Y` (copy anchor) and an embedded stylesheet in the colors of
+    /// the configured theme (or --theme). Combine with -s to export a
+    /// single section.
+    ///
+    /// Example: treemd doc.md --export html > doc.html
+    #[arg(long = "export", value_name = "FORMAT")]
+    pub export: Option<ExportFormat>,
+
+    /// Print the rendered document to stdout instead of opening the TUI
+    ///
+    /// Formats the whole document (or the -s section) as the content pane
+    /// shows it, in the theme's colors when stdout is a terminal and as plain
+    /// text when piped, then exits. A quick look without leaving the shell:
+    ///   treemd --print README.md
+    ///   treemd --print README.md | less
+    #[arg(long = "print")]
+    pub print: bool,
+
+    /// Print without colors even on a terminal (also set by NO_COLOR)
+    #[arg(long = "no-color", requires = "print")]
+    pub no_color: bool,
+
     /// Set up shell completions interactively
     ///
     /// Interactive helper to configure tab completion for your shell (bash/zsh/fish).
@@ -140,10 +271,17 @@ pub struct Cli {
     /// Set theme for TUI mode
     ///
     /// Override the saved theme preference. Available themes:
-    /// OceanDark, Nord, Dracula, Solarized, Monokai, Gruvbox, TokyoNight, CatppuccinMocha
+    /// OceanDark, Nord, Dracula, Solarized, Monokai, Gruvbox, TokyoNight, CatppuccinMocha,
+    /// CatppuccinLatte, the name of a [[theme.custom]] theme from config.toml, or
+    /// auto (Catppuccin Latte on a light terminal background, Ocean Dark otherwise)
     ///
     /// Example: --theme Nord
-    #[arg(long = "theme", value_name = "THEME")]
+    #[arg(
+        long = "theme",
+        value_name = "THEME",
+        value_parser = theme_names(),
+        hide_possible_values = true
+    )]
     pub theme: Option<String>,
 
     /// Force color mode (auto, rgb, 256)
@@ -173,6 +311,25 @@ pub struct Cli {
     #[arg(long = "images", conflicts_with = "no_images")]
     pub images: bool,
 
+    /// Reload the open file whenever it changes on disk (override config)
+    ///
+    /// Saves from another editor show up in treemd right away, keeping the
+    /// selected heading. On by default; see `watch` under `[ui]`.
+    #[arg(long = "watch")]
+    pub watch: bool,
+
+    /// Do not watch the open file for changes (override config)
+    #[arg(long = "no-watch", conflicts_with = "watch")]
+    pub no_watch: bool,
+
+    /// Open at the top instead of where the file was last left
+    ///
+    /// treemd remembers the heading and scroll each file was left at (see
+    /// `restore_position` under `[ui]`). This run neither restores nor
+    /// records positions.
+    #[arg(long = "no-restore")]
+    pub no_restore: bool,
+
     /// Query expression for selecting/filtering document elements
     ///
     /// Uses a jq-like syntax for navigating and extracting markdown structure.
@@ -189,6 +346,15 @@ pub struct Cli {
     #[arg(short = 'q', long = "query", value_name = "EXPR")]
     pub query: Option<String>,
 
+    /// Read the query expression from a file
+    ///
+    /// Runs a saved query as if it were given with -q; the file may spread
+    /// the query over several lines.
+    ///
+    /// Example: treemd doc.md --query-file sections.tql
+    #[arg(long = "query-file", value_name = "FILE")]
+    pub query_file: Option<PathBuf>,
+
     /// Show query language documentation and examples
     ///
     /// Displays comprehensive help for the query language including:
@@ -209,10 +375,39 @@ pub struct Cli {
     ///   jsonl  - Line-delimited JSON
     ///   md     - Raw markdown
     ///   tree   - Tree structure
+    ///   csv    - Comma-separated values
     ///
     /// Example: -q '.h2' --query-output json
-    #[arg(long = "query-output", value_name = "FORMAT")]
+    #[arg(
+        long = "query-output",
+        value_name = "FORMAT",
+        value_parser = SuggestedValues(vec!["plain", "json", "jsonp", "jsonl", "md", "tree", "csv"]),
+        hide_possible_values = true
+    )]
     pub query_output: Option<String>,
+
+    /// Write string results without JSON quotes, one per line (like jq -r)
+    ///
+    /// Only the JSON query output formats quote strings, so this changes
+    /// json, jsonp and jsonl output; other results there stay JSON.
+    ///
+    /// Example: -q '.link | .url' --query-output json -r
+    #[arg(short = 'r', long = "raw-output", requires = "query_source")]
+    pub raw_output: bool,
+
+    /// Browse query results in the TUI instead of printing them
+    ///
+    /// Each result becomes an outline entry; its source link jumps back to
+    /// where the result came from in the queried file. The results themselves
+    /// are read-only.
+    ///
+    /// Example: treemd doc.md -q '.h2' --interactive
+    #[arg(
+        long,
+        requires = "query_source",
+        conflicts_with_all = ["query_output", "raw_output"]
+    )]
+    pub interactive: bool,
 }
 
 #[derive(Debug, Clone, ValueEnum)]
@@ -226,6 +421,20 @@ pub enum ColorModeArg {
     Color256,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum KeyListFormat {
+    /// Readable table grouped by mode and category
+    Table,
+    /// A `[keybindings]` section for config.toml
+    Toml,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    /// Standalone HTML page
+    Html,
+}
+
 #[derive(Debug, Clone, ValueEnum)]
 pub enum OutputFormat {
     /// Plain text output
@@ -236,9 +445,43 @@ pub enum OutputFormat {
     Tree,
 }
 
+/// Accepts any string, but offers `values` to shell completion. For options
+/// whose usual values are known but not exhaustive (custom theme names) or
+/// have aliases the parser accepts.
+#[derive(Debug, Clone)]
+struct SuggestedValues(Vec<&'static str>);
+
+impl TypedValueParser for SuggestedValues {
+    type Value = String;
+
+    fn parse_ref(
+        &self,
+        cmd: &clap::Command,
+        arg: Option<&clap::Arg>,
+        value: &OsStr,
+    ) -> Result<String, clap::Error> {
+        StringValueParser::new().parse_ref(cmd, arg, value)
+    }
+
+    fn possible_values(&self) -> Option<Box<dyn Iterator<Item = PossibleValue> + '_>> {
+        Some(Box::new(
+            self.0.iter().map(|&value| PossibleValue::new(value)),
+        ))
+    }
+}
+
+/// The built-in theme names and `auto`; custom themes are accepted too.
+fn theme_names() -> SuggestedValues {
+    let mut names: Vec<&'static str> = treemd::tui::ThemeName::BUILTIN
+        .iter()
+        .filter_map(|theme| theme.config_name())
+        .collect();
+    names.push(treemd::config::AUTO_THEME);
+    SuggestedValues(names)
+}
+
 #[cfg(feature = "unstable-dynamic")]
 fn markdown_file_completer() -> ArgValueCompleter {
-    use std::ffi::OsStr;
     use std::path::Path;
 
     struct MarkdownCompleter;
@@ -2,4 +2,4 @@ mod commands;
 #[cfg(feature = "unstable-dynamic")]
 pub mod setup;
 
-pub use commands::{Cli, ColorModeArg, OutputFormat};
+pub use commands::{Cli, ColorModeArg, ExportFormat, KeyListFormat, OutputFormat};
@@ -1,8 +1,10 @@
 use crate::keybindings::{Keybindings, KeybindingsConfig};
-use crate::tui::theme::ThemeName;
+use crate::tui::ColorMode;
+use crate::tui::theme::{Theme, ThemeName};
 use opensesame::EditorConfig;
 use ratatui::style::Color;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -31,9 +33,21 @@ pub struct Config {
     #[serde(default)]
     pub images: ImageConfig,
 
+    /// Heading link generation options
+    #[serde(default)]
+    pub links: LinksConfig,
+
+    /// Code block syntax options
+    #[serde(default)]
+    pub syntax: SyntaxConfig,
+
     /// Content filtering options
     #[serde(default)]
     pub content: ContentConfig,
+
+    /// CLI query options
+    #[serde(default)]
+    pub query: QueryConfig,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -41,11 +55,13 @@ pub struct UiConfig {
     #[serde(default = "default_theme")]
     pub theme: String,
 
-    #[serde(default = "default_code_theme")]
+    #[serde(default = "default_code_theme", alias = "syntax_theme")]
     pub code_theme: String,
 
+    /// Outline pane width: a share of the terminal (`30` or `"30%"`) or a
+    /// fixed number of columns (`"32 cols"`)
     #[serde(default = "default_outline_width")]
-    pub outline_width: u16,
+    pub outline_width: WidthSpec,
 
     /// Tree rendering style: "compact" (default, gapless) or "spaced"
     #[serde(default = "default_tree_style")]
@@ -54,6 +70,79 @@ pub struct UiConfig {
     /// Show heading level markers (e.g. ##, ###) in the outline sidebar (default: true)
     #[serde(default = "default_outline_heading_markers")]
     pub outline_heading_markers: bool,
+
+    /// Outline entries: "headings" (default) or "all" to also list code
+    /// blocks, tables and images under their heading
+    #[serde(default = "default_outline_content")]
+    pub outline_content: String,
+
+    /// Underline abbreviations defined with `*[ABBR]: expansion` and show
+    /// their expansion in interactive mode (default: false)
+    #[serde(default)]
+    pub abbreviations: bool,
+
+    /// How long the heading a followed link lands on stays highlighted, in
+    /// milliseconds (default: 800, 0 disables)
+    #[serde(default = "default_landing_flash_ms")]
+    pub landing_flash_ms: u64,
+
+    /// How long a status message stays, in milliseconds; errors and
+    /// warnings stay half as long again (default: 3000)
+    #[serde(default = "default_status_timeout_ms")]
+    pub status_timeout_ms: u64,
+
+    /// Soft-wrap long lines in the content pane; when off, long lines scroll
+    /// horizontally (default: true, toggle with W)
+    #[serde(default = "default_wrap")]
+    pub wrap: bool,
+
+    /// Show a gutter of source line numbers in the content pane
+    /// (default: false, toggle with Ctrl+g)
+    #[serde(default)]
+    pub line_numbers: bool,
+
+    /// How the gutter numbers rows: "absolute" (default), "relative" (distance
+    /// from the top row, like vim's `relativenumber`) or "hybrid" (relative,
+    /// with the top row's own line number). Cycle with Ctrl+r
+    #[serde(default = "default_line_number_style")]
+    pub line_number_style: String,
+
+    /// Reload the open file when it changes on disk (default: true;
+    /// `--watch` / `--no-watch` override it for one run)
+    #[serde(default = "default_watch")]
+    pub watch: bool,
+
+    /// Capture the mouse: click outline headings and links, scroll either
+    /// pane with the wheel (default: true; `M` releases it for one session)
+    #[serde(default = "default_mouse")]
+    pub mouse: bool,
+
+    /// Reopen each file at the heading and scroll it was last left at
+    /// (default: true; `--no-restore` turns it off for one run)
+    #[serde(default = "default_restore_position")]
+    pub restore_position: bool,
+
+    /// Tint the content line being read: the selected element in interactive
+    /// mode, otherwise the first line of the selected heading's section
+    /// (default: false)
+    #[serde(default = "default_highlight_current_line")]
+    pub highlight_current_line: bool,
+
+    /// How far `d`/`u` and PageDown/PageUp scroll the content: `"page"`,
+    /// `"half"` or a number of lines (default: "page"). `Ctrl+d`/`Ctrl+u`
+    /// always move half a page.
+    #[serde(default)]
+    pub scroll_step: ScrollStep,
+
+    /// Reading speed in words per minute, for the section stats popup's
+    /// reading time (default: 200)
+    #[serde(default = "default_reading_wpm")]
+    pub reading_wpm: usize,
+
+    /// Group the directory outline (`treemd docs/`) by this front-matter
+    /// field (e.g. "category") instead of by folder. Unset by default.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub group_by_frontmatter: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -61,6 +150,11 @@ pub struct TerminalConfig {
     #[serde(default = "default_color_mode")]
     pub color_mode: String,
 
+    /// How copies reach the clipboard: "auto" (OSC 52 over SSH, the system
+    /// clipboard otherwise), "native" or "osc52"
+    #[serde(default = "default_clipboard")]
+    pub clipboard: String,
+
     #[serde(default)]
     pub warned_terminal_app: bool,
 }
@@ -86,6 +180,79 @@ fn default_images_enabled() -> bool {
     true
 }
 
+/// Heading link configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LinksConfig {
+    /// Prefix copied heading links with the current filename
+    /// (`[Text](file.md#slug)` instead of `[Text](#slug)`) (default: false)
+    #[serde(default)]
+    pub include_filename: bool,
+}
+
+/// Code block syntax configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntaxConfig {
+    /// Parse yaml/toml/json fenced blocks and mark syntax errors (default: false)
+    #[serde(default)]
+    pub validate_config: bool,
+
+    /// Columns between tab stops in code blocks (default: 4)
+    #[serde(default = "default_tab_width")]
+    pub tab_width: usize,
+
+    /// Settings for code blocks of one fence language, e.g.
+    /// `[syntax.per_language.json]`
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub per_language: BTreeMap<String, LanguageConfig>,
+}
+
+impl Default for SyntaxConfig {
+    fn default() -> Self {
+        Self {
+            validate_config: false,
+            tab_width: default_tab_width(),
+            per_language: BTreeMap::new(),
+        }
+    }
+}
+
+fn default_tab_width() -> usize {
+    4
+}
+
+/// How code blocks of one language are laid out; unset fields follow the
+/// general settings.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageConfig {
+    /// Wrap long lines (`true`) or cut them at the pane edge (`false`),
+    /// whatever `ui.wrap` does for the rest of the content
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wrap: Option<bool>,
+    /// Columns between tab stops
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tab_width: Option<usize>,
+}
+
+/// CLI query configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryConfig {
+    /// Number of `-q` queries kept for `!!`/`!n` recall; 0 disables history (default: 100)
+    #[serde(default = "default_query_history_size")]
+    pub history_size: usize,
+}
+
+impl Default for QueryConfig {
+    fn default() -> Self {
+        Self {
+            history_size: default_query_history_size(),
+        }
+    }
+}
+
+fn default_query_history_size() -> usize {
+    100
+}
+
 /// Content filtering configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ContentConfig {
@@ -204,6 +371,195 @@ pub struct CustomThemeConfig {
     pub help_desc_fg: Option<ColorValue>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub footer_bg: Option<ColorValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub current_line_bg: Option<ColorValue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_fg: Option<ColorValue>,
+    /// User-defined themes (`[[theme.custom]]`), listed in the theme picker
+    /// after the built-in ones
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub custom: Vec<CustomTheme>,
+}
+
+/// A named palette from `[[theme.custom]]`: a built-in `base` theme with the
+/// given colors replaced.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomTheme {
+    /// Name shown in the theme picker and saved as `ui.theme`
+    pub name: String,
+    /// Built-in theme to start from (e.g. `"Nord"`); Ocean Dark if unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base: Option<String>,
+    #[serde(flatten)]
+    pub colors: CustomThemeConfig,
+}
+
+/// Width of a pane, fixed or relative to the terminal.
+///
+/// Written in config as `"32 cols"` or `"30%"`; a bare number is a
+/// percentage, as `outline_width` has always been. Percentages are saved as
+/// bare numbers so older versions can still read the config.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WidthSpec {
+    /// A fixed number of columns
+    Cells(u16),
+    /// A percentage of the terminal width (at most 100)
+    Percent(u8),
+}
+
+impl WidthSpec {
+    /// Columns wanted out of `total`, before the layout fits it in.
+    pub fn columns(self, total: u16) -> u16 {
+        match self {
+            WidthSpec::Cells(cells) => cells,
+            WidthSpec::Percent(percent) => (u32::from(total) * u32::from(percent) / 100) as u16,
+        }
+    }
+
+    /// The share of `total` columns this width is, rounded down.
+    pub fn percent_of(self, total: u16) -> u16 {
+        match self {
+            WidthSpec::Percent(percent) => u16::from(percent),
+            WidthSpec::Cells(_) if total == 0 => 0,
+            WidthSpec::Cells(cells) => (u32::from(cells) * 100 / u32::from(total)).min(100) as u16,
+        }
+    }
+}
+
+impl std::fmt::Display for WidthSpec {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WidthSpec::Cells(cells) => write!(f, "{} cols", cells),
+            WidthSpec::Percent(percent) => write!(f, "{}%", percent),
+        }
+    }
+}
+
+impl std::str::FromStr for WidthSpec {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let invalid = || {
+            format!(
+                "invalid width {:?} (expected e.g. \"30%\" or \"32 cols\")",
+                s
+            )
+        };
+        if let Some(percent) = s.strip_suffix('%') {
+            let percent: u16 = percent.trim().parse().map_err(|_| invalid())?;
+            Ok(WidthSpec::Percent(percent.min(100) as u8))
+        } else if let Some(cells) = s.strip_suffix("cols") {
+            cells
+                .trim()
+                .parse()
+                .map(WidthSpec::Cells)
+                .map_err(|_| invalid())
+        } else {
+            Err(invalid())
+        }
+    }
+}
+
+impl Serialize for WidthSpec {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            WidthSpec::Percent(percent) => serializer.serialize_u8(*percent),
+            WidthSpec::Cells(_) => serializer.collect_str(self),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for WidthSpec {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Percent(u64),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Percent(percent) => Ok(WidthSpec::Percent(percent.min(100) as u8)),
+            Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
+}
+
+/// How far a page scroll moves the content.
+///
+/// Written in config as `"page"`, `"half"` or a bare number of lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ScrollStep {
+    /// The viewport less two lines, which stay in view for context
+    #[default]
+    Page,
+    /// Half the viewport
+    HalfPage,
+    /// A fixed number of lines
+    Lines(u16),
+}
+
+impl ScrollStep {
+    /// Lines to move in a viewport `viewport` rows tall, at least one.
+    pub fn lines(self, viewport: u16) -> u16 {
+        match self {
+            ScrollStep::Page => viewport.saturating_sub(2),
+            ScrollStep::HalfPage => viewport / 2,
+            ScrollStep::Lines(lines) => lines,
+        }
+        .max(1)
+    }
+}
+
+impl std::fmt::Display for ScrollStep {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ScrollStep::Page => write!(f, "page"),
+            ScrollStep::HalfPage => write!(f, "half"),
+            ScrollStep::Lines(lines) => write!(f, "{}", lines),
+        }
+    }
+}
+
+impl std::str::FromStr for ScrollStep {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "page" | "full" => Ok(ScrollStep::Page),
+            "half" => Ok(ScrollStep::HalfPage),
+            other => other.parse().map(ScrollStep::Lines).map_err(|_| {
+                format!(
+                    "invalid scroll step {:?} (expected \"page\", \"half\" or a number of lines)",
+                    s
+                )
+            }),
+        }
+    }
+}
+
+impl Serialize for ScrollStep {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ScrollStep::Lines(lines) => serializer.serialize_u16(*lines),
+            _ => serializer.collect_str(self),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ScrollStep {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Raw {
+            Lines(u64),
+            Text(String),
+        }
+        match Raw::deserialize(deserializer)? {
+            Raw::Lines(lines) => Ok(ScrollStep::Lines(lines.min(u64::from(u16::MAX)) as u16)),
+            Raw::Text(text) => text.parse().map_err(serde::de::Error::custom),
+        }
+    }
 }
 
 /// Color value that can be specified in multiple formats
@@ -219,6 +575,41 @@ pub enum ColorValue {
 }
 
 impl ColorValue {
+    /// Config value for a ratatui Color; `None` for the terminal default
+    pub fn from_color(color: Color) -> Option<Self> {
+        let name = match color {
+            Color::Rgb(r, g, b) => return Some(ColorValue::Rgb { rgb: [r, g, b] }),
+            Color::Indexed(indexed) => return Some(ColorValue::Indexed { indexed }),
+            Color::Reset => return None,
+            Color::Black => "Black",
+            Color::Red => "Red",
+            Color::Green => "Green",
+            Color::Yellow => "Yellow",
+            Color::Blue => "Blue",
+            Color::Magenta => "Magenta",
+            Color::Cyan => "Cyan",
+            Color::Gray => "Gray",
+            Color::DarkGray => "DarkGray",
+            Color::LightRed => "LightRed",
+            Color::LightGreen => "LightGreen",
+            Color::LightYellow => "LightYellow",
+            Color::LightBlue => "LightBlue",
+            Color::LightMagenta => "LightMagenta",
+            Color::LightCyan => "LightCyan",
+            Color::White => "White",
+        };
+        Some(ColorValue::Named(name.to_string()))
+    }
+
+    /// The value as written in config.toml, e.g. `{ rgb = [25, 25, 35] }`
+    pub fn to_toml(&self) -> String {
+        match self {
+            ColorValue::Named(name) => format!("{:?}", name),
+            ColorValue::Rgb { rgb: [r, g, b] } => format!("{{ rgb = [{}, {}, {}] }}", r, g, b),
+            ColorValue::Indexed { indexed } => format!("{{ indexed = {} }}", indexed),
+        }
+    }
+
     /// Convert to ratatui Color
     pub fn to_color(&self) -> Option<Color> {
         match self {
@@ -255,6 +646,20 @@ impl Default for UiConfig {
             outline_width: default_outline_width(),
             tree_style: default_tree_style(),
             outline_heading_markers: default_outline_heading_markers(),
+            outline_content: default_outline_content(),
+            abbreviations: false,
+            landing_flash_ms: default_landing_flash_ms(),
+            status_timeout_ms: default_status_timeout_ms(),
+            wrap: default_wrap(),
+            line_numbers: false,
+            line_number_style: default_line_number_style(),
+            watch: default_watch(),
+            mouse: default_mouse(),
+            restore_position: default_restore_position(),
+            highlight_current_line: default_highlight_current_line(),
+            scroll_step: ScrollStep::default(),
+            reading_wpm: default_reading_wpm(),
+            group_by_frontmatter: None,
         }
     }
 }
@@ -263,19 +668,63 @@ fn default_tree_style() -> String {
     "compact".to_string()
 }
 
+fn default_landing_flash_ms() -> u64 {
+    800
+}
+
+fn default_status_timeout_ms() -> u64 {
+    3000
+}
+
+fn default_outline_content() -> String {
+    "headings".to_string()
+}
+
 fn default_outline_heading_markers() -> bool {
     true
 }
 
+fn default_watch() -> bool {
+    true
+}
+
+fn default_line_number_style() -> String {
+    "absolute".to_string()
+}
+
+fn default_mouse() -> bool {
+    true
+}
+
+fn default_restore_position() -> bool {
+    true
+}
+
+fn default_highlight_current_line() -> bool {
+    false
+}
+
+fn default_reading_wpm() -> usize {
+    200
+}
+
+fn default_wrap() -> bool {
+    true
+}
+
 impl Default for TerminalConfig {
     fn default() -> Self {
         Self {
             color_mode: default_color_mode(),
+            clipboard: default_clipboard(),
             warned_terminal_app: false,
         }
     }
 }
 
+/// `ui.theme` value that follows the terminal's background color
+pub const AUTO_THEME: &str = "auto";
+
 fn default_theme() -> String {
     "OceanDark".to_string()
 }
@@ -284,14 +733,18 @@ fn default_code_theme() -> String {
     "base16-ocean.dark".to_string()
 }
 
-fn default_outline_width() -> u16 {
-    30
+fn default_outline_width() -> WidthSpec {
+    WidthSpec::Percent(30)
 }
 
 fn default_color_mode() -> String {
     "auto".to_string()
 }
 
+fn default_clipboard() -> String {
+    "auto".to_string()
+}
+
 impl Config {
     /// Get the XDG-style config file path (~/.config/treemd/config.toml)
     /// This is preferred on macOS for CLI tools and cross-platform dotfiles
@@ -373,44 +826,98 @@ impl Config {
         Ok(())
     }
 
-    /// Parse theme name from string
+    /// Parse theme name from string: a built-in theme, or a `[[theme.custom]]`
+    /// entry with that name
     pub fn theme_name(&self) -> ThemeName {
-        match self.ui.theme.as_str() {
-            "OceanDark" => ThemeName::OceanDark,
-            "Nord" => ThemeName::Nord,
-            "Dracula" => ThemeName::Dracula,
-            "Solarized" => ThemeName::Solarized,
-            "Monokai" => ThemeName::Monokai,
-            "Gruvbox" => ThemeName::Gruvbox,
-            "TokyoNight" => ThemeName::TokyoNight,
-            "CatppuccinMocha" => ThemeName::CatppuccinMocha,
-            _ => ThemeName::OceanDark, // Default fallback
+        self.find_theme(&self.ui.theme)
+            .unwrap_or(ThemeName::OceanDark) // Default fallback
+    }
+
+    /// The built-in or `[[theme.custom]]` theme called `name`. `"auto"`
+    /// picks Catppuccin Latte when the terminal reported a light background
+    /// and Ocean Dark otherwise.
+    pub fn find_theme(&self, name: &str) -> Option<ThemeName> {
+        if name == AUTO_THEME {
+            return Some(match crate::tui::terminal_compat::background_is_light() {
+                Some(true) => ThemeName::CatppuccinLatte,
+                _ => ThemeName::OceanDark,
+            });
         }
+        ThemeName::builtin(name).or_else(|| {
+            self.theme
+                .custom
+                .iter()
+                .position(|theme| theme.name == name)
+                .map(ThemeName::Custom)
+        })
+    }
+
+    /// Name `theme` is saved under in `ui.theme`
+    pub fn theme_config_name(&self, theme: ThemeName) -> String {
+        match theme {
+            ThemeName::Custom(idx) => self.theme.custom.get(idx).map(|t| t.name.as_str()),
+            builtin => builtin.config_name(),
+        }
+        .unwrap_or("OceanDark")
+        .to_string()
+    }
+
+    /// Every color of `theme`, as configured, in a `[[theme.custom]]` block
+    /// to paste into config.toml and edit. Built-in themes are dumped as a
+    /// new `<Name>Custom` theme based on themselves.
+    pub fn theme_snippet(&self, theme: ThemeName) -> String {
+        let (name, base) = match theme {
+            ThemeName::Custom(idx) => match self.theme.custom.get(idx) {
+                Some(custom) => (custom.name.clone(), custom.base.clone()),
+                None => ("OceanDarkCustom".to_string(), Some("OceanDark".to_string())),
+            },
+            builtin => {
+                let name = self.theme_config_name(builtin);
+                (format!("{}Custom", name), Some(name))
+            }
+        };
+        Theme::from_config(self, theme, ColorMode::Rgb).to_custom_toml(&name, base.as_deref())
     }
 
     /// Update theme and save config
     pub fn set_theme(&mut self, theme: ThemeName) -> Result<(), Box<dyn std::error::Error>> {
-        self.ui.theme = match theme {
-            ThemeName::OceanDark => "OceanDark",
-            ThemeName::Nord => "Nord",
-            ThemeName::Dracula => "Dracula",
-            ThemeName::Solarized => "Solarized",
-            ThemeName::Monokai => "Monokai",
-            ThemeName::Gruvbox => "Gruvbox",
-            ThemeName::TokyoNight => "TokyoNight",
-            ThemeName::CatppuccinMocha => "CatppuccinMocha",
-        }
-        .to_string();
+        self.ui.theme = self.theme_config_name(theme);
+        self.save()
+    }
 
+    /// Update the code block highlighting theme and save config
+    pub fn set_code_theme(&mut self, theme: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.ui.code_theme = theme.to_string();
         self.save()
     }
 
     /// Update outline width and save config
-    pub fn set_outline_width(&mut self, width: u16) -> Result<(), Box<dyn std::error::Error>> {
+    pub fn set_outline_width(
+        &mut self,
+        width: WidthSpec,
+    ) -> Result<(), Box<dyn std::error::Error>> {
         self.ui.outline_width = width;
         self.save()
     }
 
+    /// Update content line wrapping and save config
+    pub fn set_wrap(&mut self, wrap: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.ui.wrap = wrap;
+        self.save()
+    }
+
+    /// Update the content line-number gutter and save config
+    pub fn set_line_numbers(&mut self, on: bool) -> Result<(), Box<dyn std::error::Error>> {
+        self.ui.line_numbers = on;
+        self.save()
+    }
+
+    /// Set the line-number style and save to disk
+    pub fn set_line_number_style(&mut self, style: &str) -> Result<(), Box<dyn std::error::Error>> {
+        self.ui.line_number_style = style.to_string();
+        self.save()
+    }
+
     /// Mark that we've warned the user about Terminal.app
     pub fn set_warned_terminal_app(&mut self) -> Result<(), Box<dyn std::error::Error>> {
         self.terminal.warned_terminal_app = true;
@@ -427,6 +934,11 @@ impl Config {
         self.ui.tree_style == "compact"
     }
 
+    /// Check if the outline should list code blocks, tables and images
+    pub fn outline_shows_all_blocks(&self) -> bool {
+        self.ui.outline_content == "all"
+    }
+
     /// Get the path of the directory that contains the user's sublime color schemes
     /// (used for syntax highlighting in code blocks)
     pub fn code_theme_dir_path(&self) -> Option<PathBuf> {
@@ -486,14 +998,27 @@ mod tests {
         let c = Config::default();
         assert_eq!(c.ui.theme, "OceanDark");
         assert_eq!(c.ui.code_theme, "base16-ocean.dark");
-        assert_eq!(c.ui.outline_width, 30);
+        assert_eq!(c.ui.outline_width, WidthSpec::Percent(30));
         assert_eq!(c.ui.tree_style, "compact");
         assert!(c.ui.outline_heading_markers);
+        assert!(c.ui.wrap);
+        assert!(!c.ui.line_numbers);
+        assert_eq!(c.ui.line_number_style, "absolute");
+        assert!(c.ui.watch);
+        assert!(c.ui.mouse);
+        assert!(c.ui.restore_position);
+        assert!(!c.ui.highlight_current_line);
+        assert_eq!(c.ui.reading_wpm, 200);
+        assert_eq!(c.ui.scroll_step, ScrollStep::Page);
+        assert_eq!(c.ui.status_timeout_ms, 3000);
         assert_eq!(c.terminal.color_mode, "auto");
+        assert_eq!(c.terminal.clipboard, "auto");
         assert!(!c.terminal.warned_terminal_app);
         assert!(c.images.enabled);
         assert!(c.content.hide_frontmatter);
         assert!(c.content.hide_latex);
+        assert_eq!(c.syntax.tab_width, 4);
+        assert!(c.syntax.per_language.is_empty());
         assert!(c.path.is_none());
     }
 
@@ -517,12 +1042,21 @@ mod tests {
             ("Gruvbox", ThemeName::Gruvbox),
             ("TokyoNight", ThemeName::TokyoNight),
             ("CatppuccinMocha", ThemeName::CatppuccinMocha),
+            ("CatppuccinLatte", ThemeName::CatppuccinLatte),
         ] {
             c.ui.theme = raw.into();
             assert_eq!(c.theme_name(), expected, "theme={raw}");
         }
     }
 
+    #[test]
+    fn auto_theme_is_dark_without_a_known_background() {
+        // The background is only queried when the TUI starts
+        let mut c = Config::default();
+        c.ui.theme = AUTO_THEME.into();
+        assert_eq!(c.theme_name(), ThemeName::OceanDark);
+    }
+
     #[test]
     fn theme_name_unknown_falls_back_to_oceandark() {
         let mut c = Config::default();
@@ -530,13 +1064,106 @@ mod tests {
         assert_eq!(c.theme_name(), ThemeName::OceanDark);
     }
 
+    #[test]
+    fn custom_themes_are_parsed_and_named() {
+        let s = r#"
+[ui]
+theme = "Paper"
+
+[theme]
+heading_2 = "Red"
+
+[[theme.custom]]
+name = "Midnight"
+background = { rgb = [10, 10, 20] }
+
+[[theme.custom]]
+name = "Paper"
+base = "Solarized"
+heading_1 = "Blue"
+"#;
+        let mut c: Config = toml::from_str(s).expect("parse");
+        assert_eq!(c.theme.custom.len(), 2);
+        assert_eq!(c.theme.custom[1].base.as_deref(), Some("Solarized"));
+        assert!(matches!(
+            c.theme.custom[0].colors.background,
+            Some(ColorValue::Rgb { rgb: [10, 10, 20] })
+        ));
+        assert!(matches!(c.theme.heading_2, Some(ColorValue::Named(ref n)) if n == "Red"));
+        assert_eq!(c.theme_name(), ThemeName::Custom(1));
+        assert_eq!(c.theme_config_name(ThemeName::Custom(0)), "Midnight");
+        assert_eq!(c.theme_config_name(ThemeName::Nord), "Nord");
+
+        // The custom palette sits on its base; [theme] overrides still win
+        let theme = Theme::from_config(&c, c.theme_name(), ColorMode::Rgb);
+        let solarized = Theme::from_name(ThemeName::Solarized);
+        assert_eq!(theme.name, "Paper");
+        assert_eq!(theme.heading_1, Color::Blue);
+        assert_eq!(theme.heading_2, Color::Red);
+        assert_eq!(theme.background, solarized.background);
+
+        // A saved custom theme that was since removed from the config
+        c.ui.theme = "Sepia".into();
+        assert_eq!(c.theme_name(), ThemeName::OceanDark);
+        assert_eq!(c.theme_config_name(ThemeName::Custom(5)), "OceanDark");
+
+        let round_trip: Config =
+            toml::from_str(&toml::to_string_pretty(&c).expect("serialize")).expect("parse back");
+        assert_eq!(round_trip.theme.custom.len(), 2);
+        assert_eq!(round_trip.theme.custom[1].name, "Paper");
+    }
+
+    #[test]
+    fn theme_snippet_recreates_the_theme() {
+        let c = Config::default();
+        let snippet = c.theme_snippet(ThemeName::Nord);
+        assert!(snippet.starts_with("[[theme.custom]]\nname = \"NordCustom\"\nbase = \"Nord\"\n"));
+        assert!(snippet.contains("\nfooter_bg = { rgb = ["));
+        // name, base and every color field
+        assert_eq!(snippet.lines().count(), 2 + 39 + 1);
+
+        let mut parsed: Config = toml::from_str(&snippet).expect("snippet parses");
+        parsed.ui.theme = "NordCustom".into();
+        let custom = Theme::from_config(&parsed, parsed.theme_name(), ColorMode::Rgb);
+        let nord = Theme::from_name(ThemeName::Nord);
+        assert_eq!(custom.name, "NordCustom");
+        assert_eq!(
+            custom.to_custom_toml("x", None),
+            nord.to_custom_toml("x", None)
+        );
+    }
+
+    #[test]
+    fn color_values_convert_back_from_colors() {
+        for value in [
+            ColorValue::Named("DarkGray".into()),
+            ColorValue::Rgb { rgb: [1, 2, 3] },
+            ColorValue::Indexed { indexed: 235 },
+        ] {
+            let color = value.to_color().unwrap();
+            assert_eq!(
+                ColorValue::from_color(color).unwrap().to_color(),
+                Some(color)
+            );
+        }
+        assert!(ColorValue::from_color(Color::Reset).is_none());
+        assert_eq!(
+            ColorValue::Rgb { rgb: [25, 25, 35] }.to_toml(),
+            "{ rgb = [25, 25, 35] }"
+        );
+        assert_eq!(ColorValue::Named("Cyan".into()).to_toml(), "\"Cyan\"");
+    }
+
     // ---------- TOML round-trip ----------
 
     #[test]
     fn config_round_trips_through_toml() {
         let mut c = Config::default();
         c.ui.theme = "Nord".into();
-        c.ui.outline_width = 42;
+        c.ui.outline_width = WidthSpec::Cells(42);
+        c.ui.wrap = false;
+        c.ui.watch = false;
+        c.ui.mouse = false;
         c.theme.heading_1 = Some(ColorValue::Named("Cyan".into()));
         c.theme.background = Some(ColorValue::Rgb { rgb: [1, 2, 3] });
         c.theme.foreground = Some(ColorValue::Indexed { indexed: 7 });
@@ -545,7 +1172,10 @@ mod tests {
         let parsed: Config = toml::from_str(&s).expect("parse back");
 
         assert_eq!(parsed.ui.theme, "Nord");
-        assert_eq!(parsed.ui.outline_width, 42);
+        assert_eq!(parsed.ui.outline_width, WidthSpec::Cells(42));
+        assert!(!parsed.ui.wrap);
+        assert!(!parsed.ui.watch);
+        assert!(!parsed.ui.mouse);
         assert!(matches!(
             parsed.theme.heading_1,
             Some(ColorValue::Named(ref n)) if n == "Cyan"
@@ -566,11 +1196,82 @@ mod tests {
         let s = "[ui]\ntheme = \"Dracula\"\n";
         let c: Config = toml::from_str(s).expect("parse");
         assert_eq!(c.ui.theme, "Dracula");
-        assert_eq!(c.ui.outline_width, 30); // default
+        assert_eq!(c.ui.outline_width, WidthSpec::Percent(30)); // default
+        assert!(c.ui.wrap); // default
+        assert!(c.ui.watch); // default
         assert_eq!(c.terminal.color_mode, "auto"); // default
+        assert_eq!(c.terminal.clipboard, "auto"); // default
         assert!(c.content.hide_frontmatter); // default
     }
 
+    #[test]
+    fn config_accepts_syntax_theme_alias() {
+        let s = "[ui]\nsyntax_theme = \"InspiredGitHub\"\n";
+        let c: Config = toml::from_str(s).expect("parse");
+        assert_eq!(c.ui.code_theme, "InspiredGitHub");
+    }
+
+    #[test]
+    fn scroll_step_parses_names_and_line_counts() {
+        let step = |value: &str| {
+            toml::from_str::<Config>(&format!("[ui]\nscroll_step = {}\n", value))
+                .map(|c| c.ui.scroll_step)
+        };
+        assert_eq!(step("\"page\"").unwrap(), ScrollStep::Page);
+        assert_eq!(step("\"Half\"").unwrap(), ScrollStep::HalfPage);
+        assert_eq!(step("10").unwrap(), ScrollStep::Lines(10));
+        assert_eq!(step("\"10\"").unwrap(), ScrollStep::Lines(10));
+        assert!(step("\"lots\"").is_err());
+
+        assert_eq!(ScrollStep::Page.lines(30), 28);
+        assert_eq!(ScrollStep::HalfPage.lines(30), 15);
+        assert_eq!(ScrollStep::Lines(5).lines(30), 5);
+        // Tiny viewports and zero still move
+        assert_eq!(ScrollStep::Page.lines(1), 1);
+        assert_eq!(ScrollStep::Lines(0).lines(30), 1);
+
+        let mut c = Config::default();
+        c.ui.scroll_step = ScrollStep::Lines(10);
+        assert!(toml::to_string(&c).unwrap().contains("scroll_step = 10\n"));
+        c.ui.scroll_step = ScrollStep::HalfPage;
+        assert!(
+            toml::to_string(&c)
+                .unwrap()
+                .contains("scroll_step = \"half\"\n")
+        );
+    }
+
+    #[test]
+    fn outline_width_parses_percentages_and_columns() {
+        let width = |value: &str| {
+            toml::from_str::<Config>(&format!("[ui]\noutline_width = {}\n", value))
+                .map(|c| c.ui.outline_width)
+        };
+        assert_eq!(width("25").unwrap(), WidthSpec::Percent(25));
+        assert_eq!(width("\"35%\"").unwrap(), WidthSpec::Percent(35));
+        assert_eq!(width("\"32 cols\"").unwrap(), WidthSpec::Cells(32));
+        assert_eq!(width("\"32cols\"").unwrap(), WidthSpec::Cells(32));
+        assert_eq!(width("250").unwrap(), WidthSpec::Percent(100));
+        assert!(width("\"wide\"").is_err());
+
+        // Percentages stay bare numbers, readable by older versions
+        let mut c = Config::default();
+        c.ui.outline_width = WidthSpec::Percent(25);
+        assert!(
+            toml::to_string(&c)
+                .unwrap()
+                .contains("outline_width = 25\n")
+        );
+        c.ui.outline_width = WidthSpec::Cells(32);
+        assert!(
+            toml::to_string(&c)
+                .unwrap()
+                .contains("outline_width = \"32 cols\"\n")
+        );
+        assert_eq!(WidthSpec::Cells(30).percent_of(120), 25);
+        assert_eq!(WidthSpec::Percent(25).columns(120), 30);
+    }
+
     #[test]
     fn config_color_value_untagged_parses_three_forms() {
         // Named
@@ -620,13 +1321,13 @@ heading_1 = { rgb = [10, 20, 30] }
 
         let mut c = Config::default();
         c.ui.theme = "Gruvbox".into();
-        c.ui.outline_width = 55;
+        c.ui.outline_width = WidthSpec::Percent(55);
         c.path = Some(path.clone());
         c.save().expect("save");
 
         let loaded = Config::load_from_path(&path);
         assert_eq!(loaded.ui.theme, "Gruvbox");
-        assert_eq!(loaded.ui.outline_width, 55);
+        assert_eq!(loaded.ui.outline_width, WidthSpec::Percent(55));
 
         // Cleanup
         let _ = std::fs::remove_file(&path);
@@ -2,9 +2,18 @@
 //!
 //! Provides robust stdin reading with UTF-8 validation and format detection.
 //! Includes security limits to prevent denial-of-service via large inputs.
+//!
+//! All content is normalized (leading BOM stripped, CRLF/CR converted to LF)
+//! before it reaches the parser, so byte offsets and line numbers are computed
+//! against a single canonical form. [`SourceFormat`] records what was removed
+//! so in-place edits can be written back in the file's original convention.
+//! The raw-source view shows the normalized text as well: line terminators
+//! and a BOM are invisible in a terminal, and keeping one form means byte
+//! offsets mean the same thing in both views.
 
 use std::io::{self, BufRead, IsTerminal};
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
 
 /// Maximum input size (100 MB) - prevents memory exhaustion attacks
 const MAX_INPUT_SIZE: usize = 100 * 1024 * 1024;
@@ -12,11 +21,112 @@ const MAX_INPUT_SIZE: usize = 100 * 1024 * 1024;
 /// Maximum line size (10 MB) - prevents single-line attacks
 const MAX_LINE_SIZE: usize = 10 * 1024 * 1024;
 
+/// Timeout for fetching a remote document, connection to last byte
+#[cfg(feature = "remote")]
+const FETCH_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(30);
+
+/// UTF-8 byte order mark, as emitted by some Windows editors
+const BOM: char = '\u{feff}';
+
+/// Line terminator convention of a source file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LineEnding {
+    /// Unix-style `\n`
+    #[default]
+    Lf,
+    /// Windows-style `\r\n`
+    CrLf,
+    /// Classic Mac-style bare `\r`
+    Cr,
+}
+
+impl LineEnding {
+    fn as_str(self) -> &'static str {
+        match self {
+            LineEnding::Lf => "\n",
+            LineEnding::CrLf => "\r\n",
+            LineEnding::Cr => "\r",
+        }
+    }
+}
+
+/// Encoding details of a source file that [`normalize`] strips away
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SourceFormat {
+    /// Whether the file started with a UTF-8 BOM
+    pub bom: bool,
+    /// Line terminator used by the file (taken from its first line break)
+    pub line_ending: LineEnding,
+}
+
+impl SourceFormat {
+    /// Detect the BOM and line ending convention of raw file content
+    pub fn detect(raw: &str) -> Self {
+        let line_ending = match raw.find(['\r', '\n']) {
+            Some(i) if raw[i..].starts_with("\r\n") => LineEnding::CrLf,
+            Some(i) if raw[i..].starts_with('\r') => LineEnding::Cr,
+            _ => LineEnding::Lf,
+        };
+        Self {
+            bom: raw.starts_with(BOM),
+            line_ending,
+        }
+    }
+
+    /// Convert normalized (LF-only, BOM-free) content back to this format
+    pub fn restore(&self, normalized: &str) -> String {
+        let mut out = String::with_capacity(normalized.len() + normalized.len() / 32 + 3);
+        if self.bom {
+            out.push(BOM);
+        }
+        match self.line_ending {
+            LineEnding::Lf => out.push_str(normalized),
+            ending => out.push_str(&normalized.replace('\n', ending.as_str())),
+        }
+        out
+    }
+}
+
+/// Strip a leading UTF-8 BOM and convert CRLF/CR line endings to LF
+pub fn normalize(content: &str) -> String {
+    let content = content.strip_prefix(BOM).unwrap_or(content);
+    if !content.contains('\r') {
+        return content.to_string();
+    }
+    content.replace("\r\n", "\n").replace('\r', "\n")
+}
+
+/// Byte offset in `raw` of the byte at `offset` in [`normalize`]`(raw)`,
+/// counting the BOM and each `\r\n` the way they are stored.
+pub fn raw_offset(raw: &str, offset: usize) -> usize {
+    let bytes = raw.as_bytes();
+    let mut pos = if raw.starts_with(BOM) {
+        BOM.len_utf8()
+    } else {
+        0
+    };
+    for _ in 0..offset {
+        if pos >= bytes.len() {
+            break;
+        }
+        pos += if bytes[pos..].starts_with(b"\r\n") {
+            2
+        } else {
+            1
+        };
+    }
+    pos
+}
+
 /// Input source for treemd
 #[derive(Debug)]
 pub enum InputSource {
     File(String),
     Stdin(String),
+    /// A document fetched from a URL; see [`fetch_remote`]
+    Remote(String),
+    /// A directory to browse; see [`markdown_files`]
+    Directory(PathBuf),
 }
 
 /// Errors that can occur during input reading
@@ -28,6 +138,18 @@ pub enum InputError {
     NoTty,
     InputTooLarge(usize),
     LineTooLong(usize),
+    /// The server answered a fetch with a non-success status
+    HttpStatus {
+        url: String,
+        status: u16,
+    },
+    /// A fetch failed before any response (DNS, TLS, timeout, ...)
+    Fetch {
+        url: String,
+        reason: String,
+    },
+    /// URLs were given but treemd was built without the `remote` feature
+    RemoteUnsupported,
 }
 
 impl std::fmt::Display for InputError {
@@ -55,6 +177,16 @@ impl std::fmt::Display for InputError {
                     MAX_LINE_SIZE / (1024 * 1024)
                 )
             }
+            InputError::HttpStatus { url, status } => {
+                write!(f, "Failed to fetch {}: HTTP status {}", url, status)
+            }
+            InputError::Fetch { url, reason } => write!(f, "Failed to fetch {}: {}", url, reason),
+            InputError::RemoteUnsupported => {
+                write!(
+                    f,
+                    "Opening URLs needs treemd built with the `remote` feature"
+                )
+            }
         }
     }
 }
@@ -83,7 +215,7 @@ pub fn read_stdin() -> Result<String, InputError> {
     let stdin = io::stdin();
     let mut handle = stdin.lock();
     let mut buffer = String::new();
-    let mut total_size = 0usize;
+    let mut limits = InputLimits::default();
     let mut line_buffer = String::new();
 
     loop {
@@ -95,17 +227,7 @@ pub fn read_stdin() -> Result<String, InputError> {
             break;
         }
 
-        // Check line size limit
-        if line_buffer.len() > MAX_LINE_SIZE {
-            return Err(InputError::LineTooLong(line_buffer.len()));
-        }
-
-        // Check total size limit
-        total_size = total_size.saturating_add(bytes_read);
-        if total_size > MAX_INPUT_SIZE {
-            return Err(InputError::InputTooLarge(total_size));
-        }
-
+        limits.accept(&line_buffer)?;
         buffer.push_str(&line_buffer);
     }
 
@@ -117,13 +239,172 @@ pub fn read_stdin() -> Result<String, InputError> {
     Ok(buffer)
 }
 
+/// Enforces [`MAX_LINE_SIZE`] and [`MAX_INPUT_SIZE`] one line at a time, so
+/// a stream is cut off as soon as it crosses either limit.
+#[derive(Debug, Default)]
+struct InputLimits {
+    total: usize,
+}
+
+impl InputLimits {
+    fn accept(&mut self, line: &str) -> Result<(), InputError> {
+        if line.len() > MAX_LINE_SIZE {
+            return Err(InputError::LineTooLong(line.len()));
+        }
+        self.total = self.total.saturating_add(line.len());
+        if self.total > MAX_INPUT_SIZE {
+            return Err(InputError::InputTooLarge(self.total));
+        }
+        Ok(())
+    }
+}
+
+/// Input read line by line on a background thread, for showing a document
+/// while it is still arriving (`slow-command | treemd -`).
+///
+/// The size limits apply as lines come in; the first violation ends the
+/// stream with that error.
+pub struct InputStream {
+    receiver: Receiver<Result<String, InputError>>,
+}
+
+impl InputStream {
+    /// Start reading `reader` on a worker thread.
+    pub fn spawn<R: BufRead + Send + 'static>(mut reader: R) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let mut limits = InputLimits::default();
+            loop {
+                let mut line = String::new();
+                let result = match reader.read_line(&mut line) {
+                    Ok(0) => break,
+                    Ok(_) => limits.accept(&line).map(|()| line),
+                    Err(e) => Err(InputError::Io(e)),
+                };
+                let failed = result.is_err();
+                if sender.send(result).is_err() || failed {
+                    break;
+                }
+            }
+        });
+        Self { receiver }
+    }
+
+    /// Append the lines that have arrived so far to `buffer`, without
+    /// blocking. Returns `Ok(true)` once the input has ended.
+    pub fn read_available(&self, buffer: &mut String) -> Result<bool, InputError> {
+        loop {
+            match self.receiver.try_recv() {
+                Ok(line) => buffer.push_str(&line?),
+                Err(TryRecvError::Empty) => return Ok(false),
+                Err(TryRecvError::Disconnected) => return Ok(true),
+            }
+        }
+    }
+}
+
+/// Stream stdin instead of reading it to the end; see [`InputStream`].
+///
+/// Call this before the TUI takes over the terminal: once stdin is redirected
+/// to `/dev/tty` for keyboard input, fd 0 is no longer the pipe. On Unix the
+/// stream reads from its own duplicate of the original descriptor.
+pub fn stream_stdin() -> InputStream {
+    #[cfg(unix)]
+    {
+        use std::os::fd::AsFd;
+        if let Ok(fd) = io::stdin().as_fd().try_clone_to_owned() {
+            return InputStream::spawn(io::BufReader::new(std::fs::File::from(fd)));
+        }
+    }
+    InputStream::spawn(io::BufReader::new(io::stdin()))
+}
+
+/// Whether `spec` names a remote document rather than a local path: an
+/// http(s) URL or `github:owner/repo`.
+pub fn is_remote(spec: &str) -> bool {
+    spec.starts_with("http://") || spec.starts_with("https://") || spec.starts_with("github:")
+}
+
+/// URL to fetch for a remote spec. `github:owner/repo` resolves to the
+/// repository's README on the default branch via raw.githubusercontent.com.
+pub fn remote_url(spec: &str) -> Result<String, InputError> {
+    let Some(repo) = spec.strip_prefix("github:") else {
+        return Ok(spec.to_string());
+    };
+    let repo = repo.trim_matches('/');
+    if repo.split('/').filter(|s| !s.is_empty()).count() != 2 {
+        return Err(InputError::Fetch {
+            url: spec.to_string(),
+            reason: "expected github:owner/repo".to_string(),
+        });
+    }
+    Ok(format!(
+        "https://raw.githubusercontent.com/{}/HEAD/README.md",
+        repo
+    ))
+}
+
+/// Fetch a remote document (see [`is_remote`]).
+///
+/// The request gives up after [`FETCH_TIMEOUT`], the body is held to the
+/// same size limits as stdin, and any non-success status is an
+/// [`InputError::HttpStatus`].
+#[cfg(feature = "remote")]
+pub fn fetch_remote(spec: &str) -> Result<String, InputError> {
+    let url = remote_url(spec)?;
+    let fetch_error = |e: ureq::Error| InputError::Fetch {
+        url: url.clone(),
+        reason: e.to_string(),
+    };
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .timeout_global(Some(FETCH_TIMEOUT))
+        .http_status_as_error(false)
+        .build()
+        .into();
+    let mut response = agent.get(&url).call().map_err(fetch_error)?;
+    let status = response.status();
+    if !status.is_success() {
+        return Err(InputError::HttpStatus {
+            url,
+            status: status.as_u16(),
+        });
+    }
+    let body = response
+        .body_mut()
+        .with_config()
+        .limit(MAX_INPUT_SIZE as u64)
+        .read_to_string()
+        .map_err(|e| match e {
+            ureq::Error::BodyExceedsLimit(_) => InputError::InputTooLarge(MAX_INPUT_SIZE + 1),
+            e => fetch_error(e),
+        })?;
+
+    let mut limits = InputLimits::default();
+    for line in body.split_inclusive('\n') {
+        limits.accept(line)?;
+    }
+    if body.is_empty() {
+        return Err(InputError::EmptyInput);
+    }
+    Ok(body)
+}
+
+/// Without the `remote` feature, URLs cannot be opened.
+#[cfg(not(feature = "remote"))]
+pub fn fetch_remote(_spec: &str) -> Result<String, InputError> {
+    Err(InputError::RemoteUnsupported)
+}
+
 /// Determine input source based on arguments and stdin state
 ///
 /// Priority:
 /// 1. If file path is exactly "-", read from stdin
-/// 2. If file path is provided, use file
-/// 3. If no file and stdin is piped, read from stdin
-/// 4. Otherwise, error (no input available)
+/// 2. If it is a URL or `github:owner/repo`, fetch it
+/// 3. If a directory is provided, browse it
+/// 4. If file path is provided, use file
+/// 5. If no file and stdin is piped, read from stdin
+/// 6. Otherwise, error (no input available)
 pub fn determine_input_source(file_path: Option<&Path>) -> Result<InputSource, InputError> {
     match file_path {
         Some(path) if path == Path::new("-") => {
@@ -131,6 +412,11 @@ pub fn determine_input_source(file_path: Option<&Path>) -> Result<InputSource, I
             let content = read_stdin()?;
             Ok(InputSource::Stdin(content))
         }
+        Some(path) if path.to_str().is_some_and(is_remote) => {
+            let content = fetch_remote(&path.to_string_lossy())?;
+            Ok(InputSource::Remote(content))
+        }
+        Some(path) if path.is_dir() => Ok(InputSource::Directory(path.to_path_buf())),
         Some(path) => {
             // File path provided
             let content = std::fs::read_to_string(path).map_err(InputError::Io)?;
@@ -148,31 +434,137 @@ pub fn determine_input_source(file_path: Option<&Path>) -> Result<InputSource, I
     }
 }
 
+/// Markdown files (`.md`, `.markdown`, `.mdown`) under `dir`, recursively,
+/// sorted by path.
+///
+/// Hidden files and directories are skipped, as are symlinks (no cycles).
+/// Plain names listed in `dir/.gitignore` (`target/`, `node_modules`) are
+/// skipped at any depth. Glob (`*.md`), negated (`!keep.md`) and nested
+/// (`docs/draft`) patterns are not interpreted and skip nothing.
+pub fn markdown_files(dir: &Path) -> Vec<PathBuf> {
+    let ignored: Vec<String> = std::fs::read_to_string(dir.join(".gitignore"))
+        .unwrap_or_default()
+        .lines()
+        .map(|line| line.trim().trim_end_matches('/').trim_start_matches('/'))
+        .filter(|name| !name.is_empty() && !name.contains(['*', '?', '[', '!', '#', '/']))
+        .map(str::to_string)
+        .collect();
+
+    let mut files = Vec::new();
+    let mut pending = vec![dir.to_path_buf()];
+    while let Some(current) = pending.pop() {
+        let Ok(entries) = std::fs::read_dir(&current) else {
+            continue;
+        };
+        for entry in entries.filter_map(|e| e.ok()) {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name.starts_with('.') || ignored.contains(&name) {
+                continue;
+            }
+            let Ok(kind) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if kind.is_dir() {
+                pending.push(path);
+            } else if kind.is_file() && is_markdown_path(&path) {
+                files.push(path);
+            }
+        }
+    }
+    files.sort();
+    files
+}
+
+fn is_markdown_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            let ext = ext.to_lowercase();
+            ext == "md" || ext == "markdown" || ext == "mdown"
+        })
+}
+
 /// Process input and return content ready for markdown parsing
 ///
 /// Supports:
 /// - Raw markdown (passed through)
 /// - Plain text (wrapped in markdown heading)
+///
+/// The content is [normalized](normalize) first, so a BOM or CRLF line
+/// endings never leak into rendering or offset computations. A directory is
+/// not content and is an error here.
 pub fn process_input(source: InputSource) -> Result<String, Box<dyn std::error::Error>> {
+    process_input_titled(source, None)
+}
+
+/// [`process_input`], naming the heading that wraps plain text `title`
+/// (typically the file name) instead of "Input".
+pub fn process_input_titled(
+    source: InputSource,
+    title: Option<&str>,
+) -> Result<String, Box<dyn std::error::Error>> {
     let content = match source {
-        InputSource::File(c) | InputSource::Stdin(c) => c,
+        InputSource::File(c) | InputSource::Stdin(c) | InputSource::Remote(c) => normalize(&c),
+        InputSource::Directory(dir) => {
+            return Err(format!("{} is a directory", dir.display()).into());
+        }
     };
 
-    // Decide whether to wrap based on whether the markdown *parser* finds any
-    // headings — not a naive `#` text scan. A `#` scan both misfires (e.g. a
-    // leading `#` inside a code fence) and misses setext headings
-    // (`Title\n=====`), which have no `#` at all but are real headings.
-    if turbovault_parser::parse_headings(&content).is_empty() {
-        // No headings at all: wrap in a phantom heading for basic viewing.
-        let mut markdown = String::from("# Input\n\n");
-        markdown.push_str(&content);
-        Ok(markdown)
-    } else {
+    if looks_like_markdown(&content) {
         // Document has structure already; pass through unchanged.
         Ok(content)
+    } else {
+        // Plain text: wrap in a phantom heading for basic viewing.
+        let title = title.map(str::trim).filter(|t| !t.is_empty());
+        let mut markdown = format!("# {}\n\n", title.unwrap_or("Input"));
+        markdown.push_str(&content);
+        Ok(markdown)
     }
 }
 
+/// Whether [`process_input`] wraps `source` in a heading of its own: it is
+/// plain text, which has no headings.
+pub fn is_plain_text(source: &InputSource) -> bool {
+    match source {
+        InputSource::File(c) | InputSource::Stdin(c) | InputSource::Remote(c) => {
+            !looks_like_markdown(&normalize(c))
+        }
+        InputSource::Directory(_) => false,
+    }
+}
+
+/// Whether `content` is markdown rather than plain text: it has a heading
+/// (ATX or setext), a fenced code block, or a list.
+///
+/// Headings come from the markdown *parser*, not a `#` text scan, which
+/// would misfire on `#hashtags` and on `#` inside code fences, and miss
+/// setext headings (`Title\n=====`) that have no `#` at all.
+fn looks_like_markdown(content: &str) -> bool {
+    if !turbovault_parser::parse_headings(content).is_empty() {
+        return true;
+    }
+    content.lines().any(|line| {
+        // Block syntax may be indented by up to three spaces
+        let indent = line.len() - line.trim_start_matches(' ').len();
+        if indent > 3 {
+            return false;
+        }
+        let line = &line[indent..];
+        if line.starts_with("```") || line.starts_with("~~~") {
+            return true;
+        }
+        let marker = line.strip_prefix(['-', '*', '+']).or_else(|| {
+            let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+            (1..=9)
+                .contains(&digits)
+                .then(|| line[digits..].strip_prefix(['.', ')']))
+                .flatten()
+        });
+        marker.is_some_and(|rest| rest.starts_with([' ', '\t']))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -207,12 +599,218 @@ mod tests {
     }
 
     #[test]
-    fn test_hash_inside_code_fence_only_is_wrapped() {
-        // A leading '#' that lives inside a fenced code block is not a heading;
-        // such content has no real headings and should be wrapped.
+    fn test_hash_inside_code_fence_is_not_a_heading() {
+        // A leading '#' that lives inside a fenced code block is not a heading,
+        // but the fence itself marks the content as markdown: no wrapping.
         let md = "```\n# not a heading\n```\n";
         let source = InputSource::Stdin(md.to_string());
         let result = process_input(source).unwrap();
-        assert!(result.starts_with("# Input\n\n"));
+        assert_eq!(result, md);
+        assert!(crate::parser::parse_markdown(&result).headings.is_empty());
+    }
+
+    #[test]
+    fn test_hashtags_are_plain_text() {
+        let text = "Loving this #rust #tui\n#hashtag at line start\n";
+        let result = process_input(InputSource::Stdin(text.to_string())).unwrap();
+        assert_eq!(result, format!("# Input\n\n{}", text));
+    }
+
+    #[test]
+    fn test_lists_and_fences_are_markdown_without_headings() {
+        for md in [
+            "Groceries:\n\n- milk\n- eggs\n",
+            "Steps\n  1. one\n  2) two\n",
+            "* starred\n",
+            "~~~sh\nls\n~~~\n",
+        ] {
+            let result = process_input(InputSource::Stdin(md.to_string())).unwrap();
+            assert_eq!(result, md, "{md:?} was wrapped");
+        }
+        // Dashes and numbers that are not list markers
+        for text in [
+            "-5 degrees\n",
+            "1.5 litres\n",
+            "    - indented code\n",
+            "2024 was a year\n",
+        ] {
+            let result = process_input(InputSource::Stdin(text.to_string())).unwrap();
+            assert!(
+                result.starts_with("# Input\n\n"),
+                "{text:?} was not wrapped"
+            );
+        }
+    }
+
+    #[test]
+    fn test_wrapped_plain_text_is_titled_after_the_file() {
+        let source = InputSource::File("just notes\n".to_string());
+        assert_eq!(
+            process_input_titled(source, Some("notes.txt")).unwrap(),
+            "# notes.txt\n\njust notes\n"
+        );
+        let source = InputSource::File("just notes\n".to_string());
+        assert_eq!(
+            process_input_titled(source, Some("  ")).unwrap(),
+            "# Input\n\njust notes\n"
+        );
+    }
+
+    #[test]
+    fn test_bom_is_stripped() {
+        let source = InputSource::File("\u{feff}# Title\n\nBody\n".to_string());
+        let result = process_input(source).unwrap();
+        assert_eq!(result, "# Title\n\nBody\n");
+    }
+
+    #[test]
+    fn test_crlf_and_cr_normalized_to_lf() {
+        let source = InputSource::File("# Title\r\n\r\nOne\rTwo\r\n".to_string());
+        let result = process_input(source).unwrap();
+        assert_eq!(result, "# Title\n\nOne\nTwo\n");
+    }
+
+    #[test]
+    fn test_bom_and_crlf_together_keep_headings_detectable() {
+        // Setext heading behind a BOM with Windows line endings: the parser
+        // must see a heading, so the content is not wrapped
+        let source = InputSource::File("\u{feff}Title\r\n=====\r\n\r\nBody\r\n".to_string());
+        assert_eq!(process_input(source).unwrap(), "Title\n=====\n\nBody\n");
+
+        let source = InputSource::File("\u{feff}# Title\r## Next\r".to_string());
+        let content = process_input(source).unwrap();
+        assert_eq!(content, "# Title\n## Next\n");
+        assert_eq!(crate::parser::parse_markdown(&content).headings.len(), 2);
+
+        // Plain text behind a BOM is wrapped, and the BOM does not survive
+        // into the body
+        let source = InputSource::Stdin("\u{feff}just text\r\n".to_string());
+        assert_eq!(process_input(source).unwrap(), "# Input\n\njust text\n");
+    }
+
+    #[test]
+    fn test_raw_offsets_count_the_bom_and_crlf() {
+        let raw = "\u{feff}# A\r\n\r\n## B\r\n";
+        let normalized = normalize(raw);
+        let b = normalized.find("## B").unwrap();
+        assert_eq!(raw_offset(raw, b), raw.find("## B").unwrap());
+        assert_eq!(raw_offset(raw, normalized.len()), raw.len());
+        assert_eq!(raw_offset("# A\n", 2), 2);
+    }
+
+    #[test]
+    fn test_remote_specs_resolve_to_urls() {
+        assert!(is_remote("https://example.com/README.md"));
+        assert!(is_remote("github:owner/repo"));
+        assert!(!is_remote("docs/https.md"));
+        assert_eq!(
+            remote_url("github:/owner/repo/").unwrap(),
+            "https://raw.githubusercontent.com/owner/repo/HEAD/README.md"
+        );
+        assert_eq!(
+            remote_url("http://localhost/a.md").unwrap(),
+            "http://localhost/a.md"
+        );
+        assert!(matches!(
+            remote_url("github:owner"),
+            Err(InputError::Fetch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_source_format_round_trip() {
+        let raw = "\u{feff}# Title\r\n\r\n- [ ] task\r\n";
+        let format = SourceFormat::detect(raw);
+        assert!(format.bom);
+        assert_eq!(format.line_ending, LineEnding::CrLf);
+
+        let normalized = normalize(raw);
+        assert_eq!(normalized, "# Title\n\n- [ ] task\n");
+        assert_eq!(format.restore(&normalized), raw);
+    }
+
+    #[test]
+    fn test_lf_content_untouched() {
+        let raw = "# Title\n\nBody\n";
+        assert_eq!(normalize(raw), raw);
+        assert_eq!(SourceFormat::detect(raw), SourceFormat::default());
+        assert_eq!(SourceFormat::default().restore(raw), raw);
+    }
+
+    #[test]
+    fn test_markdown_files_walks_the_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for path in [
+            "README.md",
+            "guide/install.markdown",
+            "guide/deep/notes.md",
+            "guide/image.png",
+            ".github/template.md",
+            "guide/.draft.md",
+            "target/doc/api.md",
+            "vendor/lib.md",
+        ] {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, "# Doc\n").unwrap();
+        }
+        std::fs::write(root.join(".gitignore"), "target/\n*.log\n").unwrap();
+
+        let found: Vec<_> = markdown_files(root)
+            .iter()
+            .map(|p| {
+                p.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .replace('\\', "/")
+            })
+            .collect();
+        assert_eq!(
+            found,
+            [
+                "README.md",
+                "guide/deep/notes.md",
+                "guide/install.markdown",
+                "vendor/lib.md"
+            ]
+        );
+        assert!(matches!(
+            determine_input_source(Some(root)),
+            Ok(InputSource::Directory(_))
+        ));
+        assert!(process_input(InputSource::Directory(root.to_path_buf())).is_err());
+    }
+
+    #[test]
+    fn test_input_limits_are_checked_per_line() {
+        let mut limits = InputLimits::default();
+        assert!(limits.accept("# Title\n").is_ok());
+        let long = "x".repeat(MAX_LINE_SIZE + 1);
+        assert!(matches!(
+            limits.accept(&long),
+            Err(InputError::LineTooLong(n)) if n == MAX_LINE_SIZE + 1
+        ));
+
+        let mut limits = InputLimits {
+            total: MAX_INPUT_SIZE - 4,
+        };
+        assert!(limits.accept("abc\n").is_ok());
+        assert!(matches!(
+            limits.accept("d\n"),
+            Err(InputError::InputTooLarge(n)) if n == MAX_INPUT_SIZE + 2
+        ));
+    }
+
+    #[test]
+    fn test_input_stream_delivers_lines_until_eof() {
+        let stream = InputStream::spawn(io::Cursor::new("# One\n\n## Two\nno newline"));
+        let mut buffer = String::new();
+        let deadline = std::time::Instant::now() + std::time::Duration::from_secs(10);
+        while !stream.read_available(&mut buffer).unwrap() {
+            assert!(std::time::Instant::now() < deadline, "stream never ended");
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+        assert_eq!(buffer, "# One\n\n## Two\nno newline");
     }
 }
@@ -13,7 +13,9 @@ use strum::{Display, EnumIter, EnumString};
 #[strum(serialize_all = "PascalCase")]
 pub enum Action {
     // === Miscellaneous ===
-    /// Do nothing
+    /// Do nothing. Binding a key to this (or its config aliases `None` and
+    /// `Unbind`) removes the key's default binding.
+    #[serde(alias = "None", alias = "Unbind")]
     Noop,
 
     // === Navigation ===
@@ -29,8 +31,20 @@ pub enum Action {
     PageDown,
     /// Scroll up by page
     PageUp,
+    /// Scroll down by half a page
+    HalfPageDown,
+    /// Scroll up by half a page
+    HalfPageUp,
     /// Jump to parent heading in outline
     JumpToParent,
+    /// Go back to the heading before the last jump (Ctrl+o)
+    JumpBack,
+    /// Go forward again after JumpBack (Ctrl+i)
+    JumpForward,
+    /// Scroll content left when line wrap is off
+    ScrollLeft,
+    /// Scroll content right when line wrap is off
+    ScrollRight,
 
     // === Outline ===
     /// Expand collapsed heading
@@ -39,6 +53,17 @@ pub enum Action {
     Collapse,
     /// Toggle expand/collapse state
     ToggleExpand,
+    /// Reveal one more outline level (progressive expand)
+    ExpandMoreLevels,
+    /// Hide the deepest revealed outline level (progressive collapse)
+    CollapseMoreLevels,
+    /// Collapse every heading that has children
+    CollapseAll,
+    /// Expand every heading
+    ExpandAll,
+    /// Show headings down to the level given by a count (`2zl`), or the
+    /// selected heading's level
+    FoldToLevel,
     /// Toggle focus between outline and content
     ToggleFocus,
     /// Toggle focus backwards (Shift+Tab)
@@ -49,10 +74,16 @@ pub enum Action {
     OutlineWidthIncrease,
     /// Decrease outline width
     OutlineWidthDecrease,
+    /// Widen the outline a little (2 columns or percentage points)
+    OutlineWidthGrow,
+    /// Narrow the outline a little (2 columns or percentage points)
+    OutlineWidthShrink,
     /// Toggle filtering outline by open todos
     ToggleTodoFilter,
     /// Toggle heading level markers (#, ##, ###) in outline
     ToggleHeadingMarkers,
+    /// Toggle outline between headings only and all block types
+    ToggleOutlineMode,
 
     // === Bookmarks ===
     /// Set bookmark at current position
@@ -73,6 +104,8 @@ pub enum Action {
     EnterDocSearch,
     /// Toggle between outline and document search modes
     ToggleSearchMode,
+    /// Toggle whether the outline filter also matches section text
+    ToggleOutlineSearchScope,
     /// Exit current mode (generic escape)
     ExitMode,
 
@@ -85,6 +118,8 @@ pub enum Action {
     FollowLink,
     /// Start link search/filter
     LinkSearch,
+    /// Jump between a footnote reference and its note
+    JumpToFootnote,
 
     // === Interactive Mode ===
     /// Move to next interactive element
@@ -107,32 +142,65 @@ pub enum Action {
     CopyTableRow,
     /// Copy the entire table as markdown
     CopyTableMarkdown,
+    /// Copy the entire table as CSV
+    CopyTableCsv,
+    /// Sort table rows by the selected column (repeat to reverse)
+    TableSort,
+    /// Insert a blank table row below the selected one and save
+    TableRowInsert,
+    /// Delete the selected table row and save
+    TableRowDelete,
 
     // === View ===
     /// Toggle raw markdown source view
     ToggleRawSource,
+    /// Toggle soft line-wrapping in the content pane
+    ToggleWrap,
+    /// Toggle the source line-number gutter in the content pane
+    ToggleLineNumbers,
+    /// Cycle line numbers between absolute, relative and hybrid
+    CycleLineNumberStyle,
     /// Toggle terminal mouse capture (off lets you select text natively)
     ToggleMouseCapture,
+    /// Show word count and reading time for the current section and its
+    /// subsections
+    ShowStats,
     /// Toggle help popup
     ToggleHelp,
+    /// Switch code blocks to the next syntax highlighting theme
+    CycleCodeTheme,
     /// Toggle theme picker
     ToggleThemePicker,
     /// Apply selected theme (in theme picker)
     ApplyTheme,
+    /// Copy the previewed theme's colors as a `[[theme.custom]]` config block
+    ExportTheme,
 
     // === Clipboard ===
     /// Copy current section content
     CopyContent,
     /// Copy anchor/heading text
     CopyAnchor,
+    /// Copy a markdown link to the current heading
+    CopyHeadingLink,
+    /// Copy the current section, heading and subsections included, as plain text
+    CopyPlainText,
 
     // === File Operations ===
     /// Navigate back in file history
     GoBack,
     /// Navigate forward in file history
     GoForward,
+    /// Switch to the next file given on the command line
+    NextFile,
+    /// Switch to the previous file given on the command line
+    PreviousFile,
     /// Open current file in external editor
     OpenInEditor,
+    /// Write the selected mermaid/plantuml block's source to a file
+    ExportDiagram,
+    /// Write the selected section and its subsections to a standalone HTML file
+    ExportHtml,
     /// Undo last table cell edit
     UndoEdit,
     /// Open file picker to switch between markdown files
@@ -191,6 +259,8 @@ pub enum Action {
     HelpScrollDown,
     /// Scroll help popup up
     HelpScrollUp,
+    /// Type a filter for the help popup, or stop typing and keep it
+    HelpSearch,
 
     // === Theme Picker Navigation ===
     /// Move to next theme in picker
@@ -224,7 +294,7 @@ impl Action {
     pub fn description(&self) -> &'static str {
         match self {
             // Miscellaneous
-            Action::Noop => "Do nothing",
+            Action::Noop => "Do nothing (unbinds the key)",
 
             // Navigation
             Action::Next => "Move to next item",
@@ -233,19 +303,33 @@ impl Action {
             Action::Last => "Jump to last item",
             Action::PageDown => "Page down",
             Action::PageUp => "Page up",
+            Action::HalfPageDown => "Half page down",
+            Action::HalfPageUp => "Half page up",
             Action::JumpToParent => "Jump to parent heading",
+            Action::JumpBack => "Jump back to previous position",
+            Action::JumpForward => "Jump forward to next position",
+            Action::ScrollLeft => "Scroll content left",
+            Action::ScrollRight => "Scroll content right",
 
             // Outline
             Action::Expand => "Expand heading",
             Action::Collapse => "Collapse heading",
             Action::ToggleExpand => "Toggle expand/collapse",
+            Action::ExpandMoreLevels => "Reveal one more outline level",
+            Action::CollapseMoreLevels => "Hide one outline level",
+            Action::CollapseAll => "Collapse all headings",
+            Action::ExpandAll => "Expand all headings",
+            Action::FoldToLevel => "Fold outline to heading level N",
             Action::ToggleFocus => "Switch focus (outline/content)",
             Action::ToggleFocusBack => "Switch focus backwards",
             Action::ToggleOutline => "Toggle outline visibility",
             Action::OutlineWidthIncrease => "Increase outline width",
             Action::OutlineWidthDecrease => "Decrease outline width",
+            Action::OutlineWidthGrow => "Widen outline slightly",
+            Action::OutlineWidthShrink => "Narrow outline slightly",
             Action::ToggleTodoFilter => "Filter by open todos",
             Action::ToggleHeadingMarkers => "Toggle heading markers",
+            Action::ToggleOutlineMode => "Toggle outline headings/all blocks",
 
             // Bookmarks
             Action::SetBookmark => "Set bookmark",
@@ -258,6 +342,7 @@ impl Action {
             Action::EnterSearchMode => "Search/filter headings",
             Action::EnterDocSearch => "Search document content",
             Action::ToggleSearchMode => "Toggle outline/content search",
+            Action::ToggleOutlineSearchScope => "Filter headings only / headings + text",
             Action::ExitMode => "Exit current mode",
 
             // Link navigation
@@ -265,6 +350,7 @@ impl Action {
             Action::PreviousLink => "Previous link",
             Action::FollowLink => "Follow link",
             Action::LinkSearch => "Search links",
+            Action::JumpToFootnote => "Jump between footnote and reference",
 
             // Interactive mode
             Action::InteractiveNext => "Next element",
@@ -277,22 +363,38 @@ impl Action {
             Action::CopyTableCell => "Copy cell",
             Action::CopyTableRow => "Copy row (tab-separated)",
             Action::CopyTableMarkdown => "Copy table as markdown",
+            Action::CopyTableCsv => "Copy table as CSV",
+            Action::TableSort => "Sort by column",
+            Action::TableRowInsert => "Insert row below",
+            Action::TableRowDelete => "Delete row",
 
             // View
             Action::ToggleRawSource => "Toggle raw source view",
+            Action::ToggleWrap => "Toggle line wrap",
+            Action::ToggleLineNumbers => "Toggle line numbers",
+            Action::CycleLineNumberStyle => "Cycle absolute/relative line numbers",
+            Action::ShowStats => "Show section word count",
             Action::ToggleMouseCapture => "Toggle mouse capture (text selection)",
             Action::ToggleHelp => "Toggle help",
+            Action::CycleCodeTheme => "Next code highlighting theme",
             Action::ToggleThemePicker => "Open theme picker",
             Action::ApplyTheme => "Apply selected theme",
+            Action::ExportTheme => "Copy theme as TOML",
 
             // Clipboard
             Action::CopyContent => "Copy content",
             Action::CopyAnchor => "Copy heading/anchor",
+            Action::CopyHeadingLink => "Copy markdown link to heading",
+            Action::CopyPlainText => "Copy section as plain text",
 
             // File operations
             Action::GoBack => "Go back",
             Action::GoForward => "Go forward",
+            Action::NextFile => "Next file",
+            Action::PreviousFile => "Previous file",
             Action::OpenInEditor => "Open in editor",
+            Action::ExportDiagram => "Export diagram source",
+            Action::ExportHtml => "Export section as HTML",
             Action::UndoEdit => "Undo last edit",
             Action::OpenFilePicker => "Open file picker",
             Action::ParentDirectory => "Go to parent directory",
@@ -337,6 +439,7 @@ impl Action {
             // Help navigation
             Action::HelpScrollDown => "Scroll help down",
             Action::HelpScrollUp => "Scroll help up",
+            Action::HelpSearch => "Search help",
 
             // Theme picker
             Action::ThemePickerNext => "Next theme",
@@ -368,18 +471,32 @@ impl Action {
             | Action::Last
             | Action::PageDown
             | Action::PageUp
-            | Action::JumpToParent => "Navigation",
+            | Action::HalfPageDown
+            | Action::HalfPageUp
+            | Action::JumpToParent
+            | Action::JumpBack
+            | Action::JumpForward
+            | Action::ScrollLeft
+            | Action::ScrollRight => "Navigation",
 
             Action::Expand
             | Action::Collapse
             | Action::ToggleExpand
+            | Action::ExpandMoreLevels
+            | Action::CollapseMoreLevels
+            | Action::CollapseAll
+            | Action::ExpandAll
+            | Action::FoldToLevel
             | Action::ToggleFocus
             | Action::ToggleFocusBack
             | Action::ToggleOutline
             | Action::OutlineWidthIncrease
             | Action::OutlineWidthDecrease
+            | Action::OutlineWidthGrow
+            | Action::OutlineWidthShrink
             | Action::ToggleTodoFilter
-            | Action::ToggleHeadingMarkers => "Outline",
+            | Action::ToggleHeadingMarkers
+            | Action::ToggleOutlineMode => "Outline",
 
             Action::SetBookmark | Action::JumpToBookmark => "Bookmarks",
 
@@ -389,11 +506,14 @@ impl Action {
             | Action::EnterSearchMode
             | Action::EnterDocSearch
             | Action::ToggleSearchMode
+            | Action::ToggleOutlineSearchScope
             | Action::ExitMode => "Modes",
 
-            Action::NextLink | Action::PreviousLink | Action::FollowLink | Action::LinkSearch => {
-                "Links"
-            }
+            Action::NextLink
+            | Action::PreviousLink
+            | Action::FollowLink
+            | Action::LinkSearch
+            | Action::JumpToFootnote => "Links",
 
             Action::InteractiveNext
             | Action::InteractivePrevious
@@ -404,19 +524,36 @@ impl Action {
             | Action::InteractiveRight
             | Action::CopyTableCell
             | Action::CopyTableRow
-            | Action::CopyTableMarkdown => "Interactive",
+            | Action::CopyTableMarkdown
+            | Action::CopyTableCsv
+            | Action::TableSort
+            | Action::TableRowInsert
+            | Action::TableRowDelete => "Interactive",
 
             Action::ToggleRawSource
+            | Action::ToggleWrap
+            | Action::ToggleLineNumbers
+            | Action::CycleLineNumberStyle
             | Action::ToggleMouseCapture
+            | Action::ShowStats
             | Action::ToggleHelp
+            | Action::CycleCodeTheme
             | Action::ToggleThemePicker
-            | Action::ApplyTheme => "View",
+            | Action::ApplyTheme
+            | Action::ExportTheme => "View",
 
-            Action::CopyContent | Action::CopyAnchor => "Clipboard",
+            Action::CopyContent
+            | Action::CopyAnchor
+            | Action::CopyHeadingLink
+            | Action::CopyPlainText => "Clipboard",
 
             Action::GoBack
             | Action::GoForward
+            | Action::NextFile
+            | Action::PreviousFile
             | Action::OpenInEditor
+            | Action::ExportDiagram
+            | Action::ExportHtml
             | Action::UndoEdit
             | Action::OpenFilePicker
             | Action::ParentDirectory
@@ -451,7 +588,7 @@ impl Action {
 
             Action::ScrollDown | Action::ScrollUp => "Scroll",
 
-            Action::HelpScrollDown | Action::HelpScrollUp => "Help",
+            Action::HelpScrollDown | Action::HelpScrollUp | Action::HelpSearch => "Help",
 
             Action::ThemePickerNext | Action::ThemePickerPrevious => "Theme Picker",
 
@@ -75,9 +75,17 @@ fn add_normal_mode(kb: &mut Keybindings) {
     bind(kb, Normal, "PageDown", PageDown);
     bind(kb, Normal, "u", PageUp);
     bind(kb, Normal, "PageUp", PageUp);
+    bind(kb, Normal, "Ctrl+d", HalfPageDown);
+    bind(kb, Normal, "Ctrl+u", HalfPageUp);
     bind(kb, Normal, "Home", First);
     bind(kb, Normal, "End", Last);
     bind(kb, Normal, "p", JumpToParent);
+    // Most terminals send Tab for Ctrl+i, so Ctrl+n is the portable forward key
+    bind(kb, Normal, "Ctrl+o", JumpBack);
+    bind(kb, Normal, "Ctrl+i", JumpForward);
+    bind(kb, Normal, "Ctrl+n", JumpForward);
+    bind(kb, Normal, "Shift+Left", ScrollLeft);
+    bind(kb, Normal, "Shift+Right", ScrollRight);
 
     // Outline
     bind(kb, Normal, "Enter", ToggleExpand);
@@ -88,11 +96,19 @@ fn add_normal_mode(kb: &mut Keybindings) {
     bind(kb, Normal, "Left", Collapse);
     bind(kb, Normal, "l", Expand);
     bind(kb, Normal, "Right", Expand);
+    bind(kb, Normal, "Plus", ExpandMoreLevels);
+    bind(kb, Normal, "-", CollapseMoreLevels);
+    bind(kb, Normal, "z M", CollapseAll);
+    bind(kb, Normal, "z R", ExpandAll);
+    bind(kb, Normal, "z l", FoldToLevel);
     bind(kb, Normal, "w", ToggleOutline);
     bind(kb, Normal, "[", OutlineWidthDecrease);
     bind(kb, Normal, "]", OutlineWidthIncrease);
+    bind(kb, Normal, "{", OutlineWidthShrink);
+    bind(kb, Normal, "}", OutlineWidthGrow);
     bind(kb, Normal, "T", ToggleTodoFilter);
     bind(kb, Normal, "#", ToggleHeadingMarkers);
+    bind(kb, Normal, "O", ToggleOutlineMode);
 
     // Bookmarks
     bind(kb, Normal, "m", SetBookmark);
@@ -107,20 +123,30 @@ fn add_normal_mode(kb: &mut Keybindings) {
 
     // View
     bind(kb, Normal, "r", ToggleRawSource);
+    bind(kb, Normal, "W", ToggleWrap);
+    bind(kb, Normal, "Ctrl+g", ToggleLineNumbers);
+    bind(kb, Normal, "Ctrl+r", CycleLineNumberStyle);
     bind(kb, Normal, "M", ToggleMouseCapture);
+    bind(kb, Normal, "I", ShowStats);
     bind(kb, Normal, "t", ToggleThemePicker);
+    bind(kb, Normal, "C", CycleCodeTheme);
     bind(kb, Normal, "?", ToggleHelp);
 
     // Clipboard
     bind(kb, Normal, "y", CopyContent);
     bind(kb, Normal, "Y", CopyAnchor);
+    bind(kb, Normal, "L", CopyHeadingLink);
+    bind(kb, Normal, "Ctrl+y", CopyPlainText);
 
     // File operations
     bind(kb, Normal, "b", GoBack);
     bind(kb, Normal, "Backspace", GoBack);
     bind(kb, Normal, "F", GoForward);
+    bind(kb, Normal, ">", NextFile);
+    bind(kb, Normal, "<", PreviousFile);
     bind(kb, Normal, "e", OpenInEditor);
-    bind(kb, Normal, "Ctrl+o", OpenFilePicker);
+    bind(kb, Normal, "E", ExportDiagram);
+    bind(kb, Normal, "H", ExportHtml);
     bind(kb, Normal, "o", OpenFilePicker);
 
     // Application
@@ -153,12 +179,19 @@ fn add_help_mode(kb: &mut Keybindings) {
     bind(kb, Help, "PageDown", PageDown);
     bind(kb, Help, "u", PageUp);
     bind(kb, Help, "PageUp", PageUp);
+    bind(kb, Help, "Ctrl+d", HalfPageDown);
+    bind(kb, Help, "Ctrl+u", HalfPageUp);
     bind(kb, Help, "g", First);
     bind(kb, Help, "G", Last);
     bind(kb, Help, "Home", First);
     bind(kb, Help, "End", Last);
 
-    // Close help
+    // Search (typed text goes to the filter; Enter stops typing)
+    bind(kb, Help, "/", HelpSearch);
+    bind(kb, Help, "Enter", HelpSearch);
+    bind(kb, Help, "Backspace", SearchBackspace);
+
+    // Close help (Esc clears a filter first)
     bind(kb, Help, "?", ToggleHelp);
     bind(kb, Help, "Escape", ToggleHelp);
 
@@ -182,6 +215,8 @@ fn add_theme_picker_mode(kb: &mut Keybindings) {
 
     // Actions
     bind(kb, ThemePicker, "Enter", ApplyTheme);
+    bind(kb, ThemePicker, "e", ExportTheme);
+    bind(kb, ThemePicker, "c", CycleCodeTheme);
     bind(kb, ThemePicker, "Escape", ToggleThemePicker);
 
     // Clipboard (available everywhere)
@@ -209,6 +244,7 @@ fn add_interactive_mode(kb: &mut Keybindings) {
     // Link navigation within element
     bind(kb, Interactive, "Tab", InteractiveNextLink);
     bind(kb, Interactive, "Shift+Tab", InteractivePreviousLink);
+    bind(kb, Interactive, "f", JumpToFootnote);
 
     // Activate element
     bind(kb, Interactive, "Enter", InteractiveActivate);
@@ -219,6 +255,8 @@ fn add_interactive_mode(kb: &mut Keybindings) {
     bind(kb, Interactive, "PageDown", PageDown);
     bind(kb, Interactive, "u", PageUp);
     bind(kb, Interactive, "PageUp", PageUp);
+    bind(kb, Interactive, "Ctrl+d", HalfPageDown);
+    bind(kb, Interactive, "Ctrl+u", HalfPageUp);
     bind(kb, Interactive, "g", First);
     bind(kb, Interactive, "G", Last);
     bind(kb, Interactive, "Home", First);
@@ -239,6 +277,7 @@ fn add_interactive_mode(kb: &mut Keybindings) {
 
     // Open in editor
     bind(kb, Interactive, "e", OpenInEditor);
+    bind(kb, Interactive, "E", ExportDiagram);
 
     // Quit
     bind(kb, Interactive, "q", Quit);
@@ -265,6 +304,10 @@ fn add_interactive_table_mode(kb: &mut Keybindings) {
     bind(kb, InteractiveTable, "y", CopyTableCell);
     bind(kb, InteractiveTable, "Y", CopyTableRow);
     bind(kb, InteractiveTable, "r", CopyTableMarkdown);
+    bind(kb, InteractiveTable, "c", CopyTableCsv);
+    bind(kb, InteractiveTable, "s", TableSort);
+    bind(kb, InteractiveTable, "o", TableRowInsert);
+    bind(kb, InteractiveTable, "D", TableRowDelete);
 
     // Activate (follow link or edit cell)
     bind(kb, InteractiveTable, "Enter", InteractiveActivate);
@@ -295,6 +338,7 @@ fn add_link_follow_mode(kb: &mut Keybindings) {
     bind(kb, LinkFollow, "Enter", FollowLink);
     bind(kb, LinkFollow, "/", LinkSearch);
     bind(kb, LinkFollow, "p", JumpToParent);
+    bind(kb, LinkFollow, "f", JumpToFootnote);
 
     // Jump to link by number
     bind(kb, LinkFollow, "1", JumpToLink1);
@@ -348,6 +392,9 @@ fn add_search_mode(kb: &mut Keybindings) {
 
     // Toggle to doc search
     bind(kb, Search, "Tab", ToggleSearchMode);
+
+    // Also match section text, not just headings
+    bind(kb, Search, "Ctrl+t", ToggleOutlineSearchScope);
 }
 
 fn add_doc_search_mode(kb: &mut Keybindings) {
@@ -562,6 +609,41 @@ mod tests {
             ),
             Some(Action::CopyTableMarkdown)
         );
+        assert_eq!(
+            kb.dispatch(
+                KeybindingMode::InteractiveTable,
+                make_key_event(KeyCode::Char('c'), KeyModifiers::NONE)
+            ),
+            Some(Action::CopyTableCsv)
+        );
+    }
+
+    #[test]
+    fn test_default_file_and_resize_bindings_act_at_once() {
+        // `[` and `]` resize the outline straight away: no default binding
+        // starts with them, so they never wait for a sequence
+        let mut kb = default_keybindings();
+        assert_eq!(
+            kb.dispatch(
+                KeybindingMode::Normal,
+                make_key_event(KeyCode::Char('['), KeyModifiers::NONE)
+            ),
+            Some(Action::OutlineWidthDecrease)
+        );
+        assert_eq!(
+            kb.dispatch(
+                KeybindingMode::Normal,
+                make_key_event(KeyCode::Char('>'), KeyModifiers::SHIFT)
+            ),
+            Some(Action::NextFile)
+        );
+        assert_eq!(
+            kb.dispatch(
+                KeybindingMode::Normal,
+                make_key_event(KeyCode::Char('<'), KeyModifiers::SHIFT)
+            ),
+            Some(Action::PreviousFile)
+        );
     }
 
     #[test]
@@ -628,4 +710,28 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_default_history_navigation_bindings() {
+        let mut kb = default_keybindings();
+
+        assert_eq!(
+            kb.dispatch(
+                KeybindingMode::Normal,
+                make_key_event(KeyCode::Char('b'), KeyModifiers::NONE)
+            ),
+            Some(Action::GoBack)
+        );
+        assert_eq!(
+            kb.dispatch(
+                KeybindingMode::Normal,
+                make_key_event(KeyCode::Char('F'), KeyModifiers::SHIFT)
+            ),
+            Some(Action::GoForward)
+        );
+        assert!(
+            !kb.keys_for_action(KeybindingMode::Normal, Action::GoForward)
+                .is_empty()
+        );
+    }
 }
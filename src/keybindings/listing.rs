@@ -0,0 +1,160 @@
+//! Effective keybindings as text (`--list-keys`).
+//!
+//! The table is for reading; the TOML form is a complete `[keybindings]`
+//! section that can be pasted into `config.toml` and edited.
+
+use super::{Action, KeybindingMode, Keybindings};
+
+impl Keybindings {
+    /// Every binding of every mode, grouped by action category, with the
+    /// action's description.
+    pub fn list_table(&self) -> String {
+        let mut out = String::new();
+        for mode in KeybindingMode::ALL {
+            let entries = self.help_entries(mode);
+            if entries.is_empty() {
+                continue;
+            }
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(mode.display_name());
+            out.push('\n');
+
+            let rows: Vec<_> = entries
+                .iter()
+                .map(|(action, keys)| (action, keys.join(", ")))
+                .collect();
+            let width = rows
+                .iter()
+                .map(|(_, keys)| keys.chars().count())
+                .max()
+                .unwrap_or(0);
+
+            let mut category = "";
+            for (action, keys) in rows {
+                if action.category() != category {
+                    category = action.category();
+                    out.push_str(&format!("  {}\n", category));
+                }
+                out.push_str(&format!(
+                    "    {:<width$}  {}\n",
+                    keys,
+                    action.description(),
+                    width = width
+                ));
+            }
+        }
+        out
+    }
+
+    /// The bindings as a `[keybindings]` config section, in the key syntax
+    /// the config file accepts.
+    pub fn to_config_toml(&self) -> String {
+        let mut out = format!(
+            "[keybindings]\nsequence_timeout_ms = {}\n",
+            self.sequence_timeout().as_millis()
+        );
+        for mode in KeybindingMode::ALL {
+            let Some(kb) = self.get_mode_keybinds(mode) else {
+                continue;
+            };
+            let mut binds: Vec<_> = kb
+                .as_slice()
+                .iter()
+                .filter(|bind| bind.action != Action::Noop)
+                .collect();
+            if binds.is_empty() {
+                continue;
+            }
+            binds.sort_by(|a, b| {
+                a.action
+                    .category()
+                    .cmp(b.action.category())
+                    .then(a.action.description().cmp(b.action.description()))
+            });
+
+            out.push_str(&format!("\n[keybindings.{:?}]\n", mode));
+            let mut category = "";
+            for bind in binds {
+                if bind.action.category() != category {
+                    category = bind.action.category();
+                    out.push_str(&format!("# {}\n", category));
+                }
+                out.push_str(&format!(
+                    "{} = \"{}\"  # {}\n",
+                    toml_string(&bind.seq.to_string()),
+                    bind.action,
+                    bind.action.description()
+                ));
+            }
+        }
+        out
+    }
+}
+
+/// `s` as a quoted TOML basic string.
+fn toml_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybindings::KeybindingsConfig;
+    use serde::Deserialize;
+
+    #[derive(Deserialize)]
+    struct ConfigFile {
+        keybindings: KeybindingsConfig,
+    }
+
+    #[test]
+    fn table_groups_bindings_by_mode_and_category() {
+        let table = Keybindings::default().list_table();
+        assert!(table.starts_with("Normal\n"));
+        assert!(table.contains("\n  Navigation\n"));
+        let next = table
+            .lines()
+            .find(|line| line.trim_end().ends_with(Action::Next.description()))
+            .expect("Next is listed");
+        assert!(next.trim_start().starts_with('j') || next.contains(", j"));
+        assert!(table.contains("\nHelp\n"));
+    }
+
+    #[test]
+    fn toml_round_trips_through_the_config_format() {
+        let mut kb = Keybindings::default();
+        kb.bind(KeybindingMode::Normal, "g d", Action::Last)
+            .unwrap();
+        let toml_text = kb.to_config_toml();
+        assert!(toml_text.contains("\"g d\" = \"Last\"  # Jump to last item\n"));
+
+        let parsed: ConfigFile = toml::from_str(&toml_text).unwrap();
+        assert!(parsed.keybindings.validate().is_empty());
+        let reloaded = parsed.keybindings.to_keybindings();
+        for mode in KeybindingMode::ALL {
+            let mut before = kb.help_entries(mode);
+            let mut after = reloaded.help_entries(mode);
+            before.iter_mut().for_each(|(_, keys)| keys.sort());
+            after.iter_mut().for_each(|(_, keys)| keys.sort());
+            assert_eq!(before, after, "{:?} changed", mode);
+        }
+    }
+
+    #[test]
+    fn toml_strings_are_escaped() {
+        assert_eq!(toml_string("\""), "\"\\\"\"");
+        assert_eq!(toml_string("\\"), "\"\\\\\"");
+    }
+}
@@ -23,17 +23,26 @@
 //! [keybindings.Interactive]
 //! "Escape" = "ExitInteractiveMode"
 //! ```
+//!
+//! `sequence_timeout_ms` under `[keybindings]` sets how long an incomplete
+//! sequence (a chord like `g g` or a count prefix like `5`) waits for the next
+//! key before it is abandoned.
 
 mod action;
 mod defaults;
+mod listing;
 
 pub use action::Action;
 
 use crossterm::event::KeyEvent;
-use keybinds::Keybinds;
+use keybinds::{KeyInput, Keybinds, Match};
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
 use std::{collections::HashMap, str::FromStr};
 
+/// Default time an incomplete key sequence waits for its next key.
+pub const DEFAULT_SEQUENCE_TIMEOUT_MS: u64 = 600;
+
 /// Application modes that have their own keybinding sets
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -69,6 +78,24 @@ pub enum KeybindingMode {
 }
 
 impl KeybindingMode {
+    /// Every mode, in the order they are listed to users
+    pub const ALL: [KeybindingMode; 14] = [
+        KeybindingMode::Normal,
+        KeybindingMode::Help,
+        KeybindingMode::ThemePicker,
+        KeybindingMode::Interactive,
+        KeybindingMode::InteractiveTable,
+        KeybindingMode::LinkFollow,
+        KeybindingMode::LinkSearch,
+        KeybindingMode::Search,
+        KeybindingMode::DocSearch,
+        KeybindingMode::CommandPalette,
+        KeybindingMode::CellEdit,
+        KeybindingMode::ConfirmDialog,
+        KeybindingMode::FilePicker,
+        KeybindingMode::FileSearch,
+    ];
+
     /// Get a display name for the mode
     pub fn display_name(&self) -> &'static str {
         match self {
@@ -97,6 +124,13 @@ impl KeybindingMode {
 pub struct Keybindings {
     /// Keybindings organized by mode
     bindings: HashMap<KeybindingMode, Keybinds<Action>>,
+    /// Time allowed between the keys of a multi-key sequence
+    sequence_timeout: Duration,
+    /// Keys of an incomplete sequence and the mode they were typed in
+    pending: Vec<KeyInput>,
+    pending_mode: Option<KeybindingMode>,
+    /// Second action produced by a single key press (see [`Self::take_queued`])
+    queued: Option<Action>,
 }
 
 impl Default for Keybindings {
@@ -109,6 +143,10 @@ impl Clone for Keybindings {
     fn clone(&self) -> Self {
         Self {
             bindings: self.bindings.clone(),
+            sequence_timeout: self.sequence_timeout,
+            pending: Vec::new(),
+            pending_mode: None,
+            queued: None,
         }
     }
 }
@@ -118,6 +156,10 @@ impl Keybindings {
     pub fn new() -> Self {
         Self {
             bindings: HashMap::new(),
+            sequence_timeout: Duration::from_millis(DEFAULT_SEQUENCE_TIMEOUT_MS),
+            pending: Vec::new(),
+            pending_mode: None,
+            queued: None,
         }
     }
 
@@ -134,7 +176,11 @@ impl Keybindings {
         for (mode, bindings) in &mut def.bindings {
             let mut binding_vec = std::mem::take(bindings).into_vec();
 
-            if let Some(config_bindings) = config.0.get(mode) {
+            if let Some(config_bindings) = config.modes.get(mode) {
+                // Sorted so that when two spellings name the same key, the
+                // winner does not depend on hash order (see `validate`)
+                let mut config_bindings: Vec<_> = config_bindings.iter().collect();
+                config_bindings.sort_by_key(|(key, _)| key.as_str());
                 for (config_key, config_action) in config_bindings {
                     let config_seq = keybinds::KeySeq::from_str(config_key)?;
 
@@ -151,31 +197,100 @@ impl Keybindings {
             *bindings = keybinds::Keybinds::new(binding_vec);
         }
 
+        def.set_sequence_timeout(Duration::from_millis(config.sequence_timeout_ms));
         Ok(def)
     }
 
     /// Get the action for a key event in a specific mode
     ///
     /// This is the main dispatch method - pass crossterm KeyEvents directly.
+    ///
+    /// Keys are buffered while they could still be the start of a longer
+    /// sequence. A key bound on its own resolves immediately unless some
+    /// sequence extends it (`g` alongside `g g`); then it waits for the next
+    /// key, and fires if that key (or [`finish_sequence`](Self::finish_sequence)
+    /// on timeout) ends the sequence. A key that cannot continue the buffered
+    /// sequence is dispatched again on its own rather than dropped.
     pub fn dispatch(&mut self, mode: KeybindingMode, event: KeyEvent) -> Option<Action> {
-        self.bindings
-            .get_mut(&mode)
-            .and_then(|kb| kb.dispatch(event).copied())
+        let input = KeyInput::from(event);
+        if input.key() == keybinds::Key::Ignored {
+            return None;
+        }
+        if self.pending_mode != Some(mode) {
+            self.pending.clear();
+            self.pending_mode = Some(mode);
+        }
+        self.pending.push(input);
+
+        let kb = self.bindings.get(&mode)?;
+        let exact = exact_action(kb, &self.pending);
+        if sequence_extends(kb, &self.pending) {
+            return None;
+        }
+        if exact.is_some() {
+            self.pending.clear();
+            return exact;
+        }
+
+        // The new key broke the sequence: finish whatever the keys before it
+        // formed, then start over from the new key alone.
+        let earlier = self.pending.len() - 1;
+        let fallback = exact_action(kb, &self.pending[..earlier]);
+        self.pending.clear();
+        if earlier == 0 {
+            return None;
+        }
+        let next = self.dispatch(mode, event);
+        match fallback {
+            Some(action) => {
+                self.queued = next;
+                Some(action)
+            }
+            None => next,
+        }
+    }
+
+    /// End an incomplete sequence (e.g. on timeout). The action bound to the
+    /// keys typed so far, if any, is queued for [`take_queued`](Self::take_queued).
+    /// Returns whether an action was queued.
+    pub fn finish_sequence(&mut self) -> bool {
+        let action = self
+            .pending_mode
+            .and_then(|mode| self.bindings.get(&mode))
+            .and_then(|kb| exact_action(kb, &self.pending));
+        self.pending.clear();
+        if action.is_some() {
+            self.queued = action;
+        }
+        action.is_some()
+    }
+
+    /// Take the action left waiting by [`finish_sequence`](Self::finish_sequence),
+    /// or by a key press that completed two bindings: an ambiguous prefix
+    /// (`g` of `g g`) followed by a key bound on its own.
+    pub fn take_queued(&mut self) -> Option<Action> {
+        self.queued.take()
     }
 
     /// Check if a multi-key sequence is in progress for this mode
     pub fn is_sequence_ongoing(&self, mode: KeybindingMode) -> bool {
-        self.bindings
-            .get(&mode)
-            .map(|kb| kb.is_ongoing())
-            .unwrap_or(false)
+        self.pending_mode == Some(mode) && !self.pending.is_empty()
     }
 
     /// Reset any in-progress key sequences (call when switching modes)
     pub fn reset_sequences(&mut self) {
-        for kb in self.bindings.values_mut() {
-            kb.reset();
-        }
+        self.pending.clear();
+        self.queued = None;
+    }
+
+    /// Time allowed between the keys of a multi-key sequence
+    pub fn sequence_timeout(&self) -> Duration {
+        self.sequence_timeout
+    }
+
+    /// Set the time allowed between the keys of a multi-key sequence
+    pub fn set_sequence_timeout(&mut self, timeout: Duration) {
+        self.sequence_timeout = timeout;
     }
 
     /// Get the keybinds for a specific mode
@@ -231,6 +346,21 @@ impl Keybindings {
     }
 }
 
+/// Action bound to exactly `keys`, if any.
+fn exact_action(kb: &Keybinds<Action>, keys: &[KeyInput]) -> Option<Action> {
+    kb.as_slice()
+        .iter()
+        .find(|bind| bind.seq.match_to(keys) == Match::Matched)
+        .map(|bind| bind.action)
+}
+
+/// Whether some binding is a longer sequence starting with `keys`.
+fn sequence_extends(kb: &Keybinds<Action>, keys: &[KeyInput]) -> bool {
+    kb.as_slice()
+        .iter()
+        .any(|bind| bind.seq.match_to(keys) == Match::Prefix)
+}
+
 /// Format a key sequence for display
 fn format_key_sequence(seq: &keybinds::KeySeq) -> String {
     seq.as_slice()
@@ -306,19 +436,155 @@ pub fn format_key_compact(key: &str) -> String {
 }
 
 /// Configuration format for keybindings (uses string keys for TOML compatibility)
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct KeybindingsConfig(pub HashMap<KeybindingMode, HashMap<String, Action>>);
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeybindingsConfig {
+    /// Milliseconds an incomplete key sequence waits for its next key
+    #[serde(default = "default_sequence_timeout_ms")]
+    pub sequence_timeout_ms: u64,
+
+    /// Key bindings by mode (`[keybindings.Normal]`, ...)
+    #[serde(flatten)]
+    pub modes: HashMap<KeybindingMode, HashMap<String, Action>>,
+}
+
+impl Default for KeybindingsConfig {
+    fn default() -> Self {
+        Self {
+            sequence_timeout_ms: default_sequence_timeout_ms(),
+            modes: HashMap::new(),
+        }
+    }
+}
+
+fn default_sequence_timeout_ms() -> u64 {
+    DEFAULT_SEQUENCE_TIMEOUT_MS
+}
 
 impl KeybindingsConfig {
     /// Convert to Keybindings, using defaults for any missing bindings
     pub fn to_keybindings(&self) -> Keybindings {
         // Falls back to pure defaults if any key is invalid
-        Keybindings::from_config(self).unwrap_or_default()
+        Keybindings::from_config(self).unwrap_or_else(|_| {
+            let mut kb = Keybindings::default();
+            kb.set_sequence_timeout(Duration::from_millis(self.sequence_timeout_ms));
+            kb
+        })
     }
 
-    /// Check if the config is empty
+    /// Check if the config has no custom bindings
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.modes.is_empty()
+    }
+
+    /// Human-readable problems with the configured bindings, for showing at
+    /// startup instead of silently resolving them:
+    ///
+    /// - keys that cannot be parsed (all custom bindings are then ignored)
+    /// - two spellings of the same key (`"Ctrl+x"`, `"ctrl+x"`) bound to
+    ///   different actions in one mode; the later one in sort order wins
+    /// - digits `1`-`9` bound in `Normal` or `Interactive`, where they start a
+    ///   count prefix (and a lone digit jumps to a heading), so the binding
+    ///   never runs
+    pub fn validate(&self) -> Vec<String> {
+        let mut modes: Vec<_> = self.modes.iter().collect();
+        modes.sort_by_key(|(mode, _)| mode.display_name());
+
+        let mut conflicts = Vec::new();
+        for (mode, bindings) in modes {
+            let mut keys: Vec<_> = bindings.iter().collect();
+            keys.sort_by_key(|(key, _)| key.as_str());
+
+            let mut seen: Vec<(keybinds::KeySeq, &str, Action)> = Vec::new();
+            for (key, &action) in keys {
+                let seq = match keybinds::KeySeq::from_str(key) {
+                    Ok(seq) => seq,
+                    Err(e) => {
+                        conflicts.push(format!(
+                            "{:?}: invalid key \"{}\" ({}); custom keybindings ignored",
+                            mode, key, e
+                        ));
+                        continue;
+                    }
+                };
+
+                if let Some((_, earlier, earlier_action)) =
+                    seen.iter().find(|(other, _, _)| *other == seq)
+                    && *earlier_action != action
+                {
+                    conflicts.push(format!(
+                        "{:?}: \"{}\" and \"{}\" are the same key, bound to {:?} and {:?}; using {:?}",
+                        mode, earlier, key, earlier_action, action, action
+                    ));
+                }
+
+                if matches!(mode, KeybindingMode::Normal | KeybindingMode::Interactive)
+                    && action != Action::Noop
+                    && let Some(digit) = count_digit(&seq)
+                {
+                    conflicts.push(format!(
+                        "{:?}: \"{}\" starts a count prefix (a lone {} jumps to heading {}), so {:?} never runs",
+                        mode, key, digit, digit, action
+                    ));
+                }
+
+                seen.push((seq, key, action));
+            }
+        }
+        conflicts
+    }
+}
+
+/// The digit of a single unmodified `1`-`9` key, which the event loop reads
+/// as a count prefix before dispatching bindings.
+fn count_digit(seq: &keybinds::KeySeq) -> Option<char> {
+    match seq.as_slice() {
+        [input] if input.mods().is_empty() => match input.key() {
+            keybinds::Key::Char(c @ '1'..='9') => Some(c),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Timer for incomplete key sequences.
+///
+/// Chords and count prefixes leave input waiting for another key. The event
+/// loop records every handled key here and asks [`expire`](Self::expire)
+/// while idle, so all pending states are abandoned after the same timeout.
+#[derive(Debug, Clone)]
+pub struct SequenceTimer {
+    timeout: Duration,
+    last_key: Option<Instant>,
+}
+
+impl SequenceTimer {
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            last_key: None,
+        }
+    }
+
+    /// Record a handled key; `pending` is whether input is still incomplete.
+    pub fn record_key(&mut self, pending: bool, now: Instant) {
+        self.last_key = pending.then_some(now);
+    }
+
+    /// Whether a pending sequence has waited longer than the timeout at
+    /// `now`. Returns true once per sequence; the caller cancels it.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        match self.last_key {
+            Some(last) if now.saturating_duration_since(last) > self.timeout => {
+                self.last_key = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether a sequence is waiting for its next key.
+    pub fn is_pending(&self) -> bool {
+        self.last_key.is_some()
     }
 }
 
@@ -396,7 +662,10 @@ mod tests {
         // Rebind 'j' from Next (default) to Last
         normal_bindings.insert("j".to_string(), Action::Last);
         config_map.insert(KeybindingMode::Normal, normal_bindings);
-        let config = KeybindingsConfig(config_map);
+        let config = KeybindingsConfig {
+            modes: config_map,
+            ..Default::default()
+        };
 
         let mut kb = config.to_keybindings();
         let action = kb.dispatch(
@@ -417,7 +686,10 @@ mod tests {
         // Unbind 'j' by mapping to Noop
         normal_bindings.insert("j".to_string(), Action::Noop);
         config_map.insert(KeybindingMode::Normal, normal_bindings);
-        let config = KeybindingsConfig(config_map);
+        let config = KeybindingsConfig {
+            modes: config_map,
+            ..Default::default()
+        };
 
         let mut kb = config.to_keybindings();
         let action = kb.dispatch(
@@ -430,13 +702,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_none_and_unbind_remove_default_bindings() {
+        let config: KeybindingsConfig =
+            toml::from_str("[Normal]\n\"q\" = \"None\"\n\"g\" = \"Unbind\"\n").unwrap();
+        assert!(config.validate().is_empty());
+
+        let mut kb = config.to_keybindings();
+        for c in ['q', 'g'] {
+            assert_eq!(
+                kb.dispatch(
+                    KeybindingMode::Normal,
+                    make_key_event(KeyCode::Char(c), KeyModifiers::NONE)
+                ),
+                None,
+                "{c} should be unbound"
+            );
+        }
+        // Other bindings for the same actions are kept
+        assert!(
+            kb.keys_for_action(KeybindingMode::Normal, Action::First)
+                .contains(&"Home".to_string())
+        );
+        // Only the configured mode is affected
+        assert!(
+            !kb.keys_for_action(KeybindingMode::Help, Action::First)
+                .is_empty()
+        );
+    }
+
     #[test]
     fn test_clone_preserves_user_config() {
         let mut config_map = HashMap::new();
         let mut normal_bindings = HashMap::new();
         normal_bindings.insert("j".to_string(), Action::Last);
         config_map.insert(KeybindingMode::Normal, normal_bindings);
-        let config = KeybindingsConfig(config_map);
+        let config = KeybindingsConfig {
+            modes: config_map,
+            ..Default::default()
+        };
 
         let kb = config.to_keybindings();
         let mut cloned = kb.clone();
@@ -457,7 +761,10 @@ mod tests {
         let mut normal_bindings = HashMap::new();
         normal_bindings.insert("j".to_string(), Action::Noop);
         config_map.insert(KeybindingMode::Normal, normal_bindings);
-        let config = KeybindingsConfig(config_map);
+        let config = KeybindingsConfig {
+            modes: config_map,
+            ..Default::default()
+        };
 
         let kb = config.to_keybindings();
         let entries = kb.help_entries(KeybindingMode::Normal);
@@ -499,4 +806,181 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_sequence_timeout_from_config() {
+        let config: KeybindingsConfig =
+            toml::from_str("sequence_timeout_ms = 250\n[Normal]\n\"z z\" = \"First\"\n").unwrap();
+        assert_eq!(config.sequence_timeout_ms, 250);
+        assert!(!config.is_empty());
+        let kb = config.to_keybindings();
+        assert_eq!(kb.sequence_timeout(), Duration::from_millis(250));
+
+        let default: KeybindingsConfig = toml::from_str("").unwrap();
+        assert_eq!(default.sequence_timeout_ms, DEFAULT_SEQUENCE_TIMEOUT_MS);
+    }
+
+    #[test]
+    fn test_partial_sequence_cancelled_after_timeout() {
+        let mut kb = Keybindings::default();
+        kb.bind(KeybindingMode::Normal, "z z", Action::First)
+            .unwrap();
+        let mut timer = SequenceTimer::new(kb.sequence_timeout());
+        let z = make_key_event(KeyCode::Char('z'), KeyModifiers::NONE);
+        let start = Instant::now();
+
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, z), None);
+        timer.record_key(kb.is_sequence_ongoing(KeybindingMode::Normal), start);
+        assert!(timer.is_pending());
+
+        // Idle past the timeout: the event loop cancels the sequence
+        assert!(timer.expire(start + kb.sequence_timeout() + Duration::from_millis(1)));
+        assert!(
+            !timer.expire(start + kb.sequence_timeout() * 2),
+            "fires once"
+        );
+        kb.reset_sequences();
+        assert!(!kb.is_sequence_ongoing(KeybindingMode::Normal));
+
+        // The next `z` starts a new sequence instead of completing the old one
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, z), None);
+    }
+
+    #[test]
+    fn test_sequence_completed_within_timeout_fires() {
+        let mut kb = Keybindings::default();
+        kb.bind(KeybindingMode::Normal, "z z", Action::First)
+            .unwrap();
+        let mut timer = SequenceTimer::new(kb.sequence_timeout());
+        let z = make_key_event(KeyCode::Char('z'), KeyModifiers::NONE);
+        let start = Instant::now();
+
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, z), None);
+        timer.record_key(kb.is_sequence_ongoing(KeybindingMode::Normal), start);
+        assert!(!timer.expire(start + kb.sequence_timeout() / 2));
+
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, z), Some(Action::First));
+        timer.record_key(kb.is_sequence_ongoing(KeybindingMode::Normal), start);
+        assert!(!timer.is_pending());
+        assert!(!timer.expire(start + kb.sequence_timeout() * 2));
+    }
+
+    #[test]
+    fn test_single_key_without_extension_resolves_immediately() {
+        let mut kb = Keybindings::default();
+        kb.bind(KeybindingMode::Normal, "z z", Action::First)
+            .unwrap();
+        let j = make_key_event(KeyCode::Char('j'), KeyModifiers::NONE);
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, j), Some(Action::Next));
+        assert!(!kb.is_sequence_ongoing(KeybindingMode::Normal));
+    }
+
+    #[test]
+    fn test_ambiguous_prefix_waits_for_next_key() {
+        let config: KeybindingsConfig = toml::from_str("[Normal]\n\"g d\" = \"Last\"\n").unwrap();
+        let mut kb = config.to_keybindings();
+        let g = make_key_event(KeyCode::Char('g'), KeyModifiers::NONE);
+        let d = make_key_event(KeyCode::Char('d'), KeyModifiers::NONE);
+
+        // `g` alone is First, but `g d` could follow, so it waits
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, g), None);
+        assert!(kb.is_sequence_ongoing(KeybindingMode::Normal));
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, d), Some(Action::Last));
+        assert!(!kb.is_sequence_ongoing(KeybindingMode::Normal));
+
+        // On timeout the keys typed so far fire their own binding
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, g), None);
+        assert!(kb.finish_sequence());
+        assert_eq!(kb.take_queued(), Some(Action::First));
+        assert!(!kb.is_sequence_ongoing(KeybindingMode::Normal));
+        assert!(!kb.finish_sequence(), "nothing left to finish");
+    }
+
+    #[test]
+    fn test_ambiguous_prefix_then_other_key_runs_both() {
+        let mut kb = Keybindings::default();
+        kb.bind(KeybindingMode::Normal, "g d", Action::Last)
+            .unwrap();
+        let g = make_key_event(KeyCode::Char('g'), KeyModifiers::NONE);
+        let j = make_key_event(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, g), None);
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, j), Some(Action::First));
+        assert_eq!(kb.take_queued(), Some(Action::Next));
+        assert_eq!(kb.take_queued(), None);
+    }
+
+    #[test]
+    fn test_key_breaking_a_sequence_is_not_dropped() {
+        let mut kb = Keybindings::default();
+        kb.bind(KeybindingMode::Normal, "z z", Action::First)
+            .unwrap();
+        let z = make_key_event(KeyCode::Char('z'), KeyModifiers::NONE);
+        let j = make_key_event(KeyCode::Char('j'), KeyModifiers::NONE);
+
+        // `z` alone is unbound, so `z j` is just `j`
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, z), None);
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, j), Some(Action::Next));
+        assert_eq!(kb.take_queued(), None);
+
+        // A timed-out `z` has nothing to run
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, z), None);
+        assert!(!kb.finish_sequence());
+        assert!(!kb.is_sequence_ongoing(KeybindingMode::Normal));
+    }
+
+    #[test]
+    fn test_sequence_is_per_mode() {
+        let mut kb = Keybindings::default();
+        kb.bind(KeybindingMode::Normal, "z z", Action::First)
+            .unwrap();
+        let z = make_key_event(KeyCode::Char('z'), KeyModifiers::NONE);
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, z), None);
+        assert!(kb.is_sequence_ongoing(KeybindingMode::Normal));
+        assert!(!kb.is_sequence_ongoing(KeybindingMode::Help));
+
+        // Keys typed in another mode do not complete it
+        kb.dispatch(KeybindingMode::Help, z);
+        assert_eq!(kb.dispatch(KeybindingMode::Normal, z), None);
+    }
+
+    #[test]
+    fn test_validate_reports_conflicts() {
+        // Digits are only counts in Normal and Interactive, not in Help
+        let config: KeybindingsConfig = toml::from_str(
+            "[Normal]\n\"Ctrl+x\" = \"Quit\"\n\"ctrl+x\" = \"Next\"\n\"3\" = \"Last\"\n\"0\" = \"First\"\n\"Ctrl+3\" = \"Last\"\n[Help]\n\"3\" = \"Last\"\n",
+        )
+        .unwrap();
+        assert_eq!(
+            config.validate(),
+            [
+                "Normal: \"3\" starts a count prefix (a lone 3 jumps to heading 3), so Last never runs"
+                    .to_string(),
+                "Normal: \"Ctrl+x\" and \"ctrl+x\" are the same key, bound to Quit and Next; using Next"
+                    .to_string(),
+            ]
+        );
+
+        // The reported winner is the one that is actually bound
+        let mut kb = config.to_keybindings();
+        assert_eq!(
+            kb.dispatch(
+                KeybindingMode::Normal,
+                make_key_event(KeyCode::Char('x'), KeyModifiers::CONTROL)
+            ),
+            Some(Action::Next)
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_invalid_keys_and_accepts_clean_config() {
+        let config: KeybindingsConfig = toml::from_str("[Normal]\n\"Ctrl+\" = \"Quit\"\n").unwrap();
+        let conflicts = config.validate();
+        assert_eq!(conflicts.len(), 1);
+        assert!(conflicts[0].starts_with("Normal: invalid key \"Ctrl+\""));
+
+        let config: KeybindingsConfig =
+            toml::from_str("[Normal]\n\"g d\" = \"Last\"\n\"x\" = \"Noop\"\n").unwrap();
+        assert!(config.validate().is_empty());
+    }
 }
@@ -31,7 +31,7 @@
 mod cli;
 
 use clap::Parser as ClapParser;
-use cli::{Cli, OutputFormat};
+use cli::{Cli, ExportFormat, KeyListFormat, OutputFormat};
 use color_eyre::Result;
 use std::collections::HashMap;
 use std::process;
@@ -48,7 +48,7 @@ fn main() -> Result<()> {
     })
     .complete();
 
-    let args = Cli::parse();
+    let mut args = Cli::parse();
 
     // Handle completion setup
     #[cfg(feature = "unstable-dynamic")]
@@ -69,6 +69,51 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // A saved query runs as if given with -q
+    if let Some(ref path) = args.query_file {
+        match std::fs::read_to_string(path) {
+            Ok(query) => args.query = Some(query.trim().to_string()),
+            Err(e) => {
+                eprintln!("Error: cannot read query file {}: {}", path.display(), e);
+                process::exit(1);
+            }
+        }
+    }
+
+    // Handle --list-keys (doesn't require input)
+    if let Some(format) = args.list_keys {
+        let config = treemd::Config::load();
+        for conflict in config.keybindings.validate() {
+            eprintln!("warning: {}", conflict);
+        }
+        let keybindings = config.keybindings();
+        match format {
+            KeyListFormat::Table => print!("{}", keybindings.list_table()),
+            KeyListFormat::Toml => print!("{}", keybindings.to_config_toml()),
+        }
+        return Ok(());
+    }
+
+    // Handle --dump-theme (doesn't require input)
+    if let Some(ref name) = args.dump_theme {
+        let config = treemd::Config::load();
+        let Some(theme) = config.find_theme(name) else {
+            let mut names: Vec<String> = treemd::tui::ThemeName::BUILTIN
+                .iter()
+                .map(|t| config.theme_config_name(*t))
+                .collect();
+            names.extend(config.theme.custom.iter().map(|t| t.name.clone()));
+            eprintln!(
+                "Error: unknown theme '{}'. Available: {}",
+                name,
+                names.join(", ")
+            );
+            process::exit(1);
+        };
+        print!("{}", config.theme_snippet(theme));
+        return Ok(());
+    }
+
     // Handle --man-page (doesn't require input)
     if args.man_page {
         use clap::CommandFactory;
@@ -80,6 +125,13 @@ fn main() -> Result<()> {
         return Ok(());
     }
 
+    // Handle --completions (doesn't require input)
+    if let Some(shell) = args.completions {
+        use clap::CommandFactory;
+        clap_complete::generate(shell, &mut Cli::command(), "treemd", &mut std::io::stdout());
+        return Ok(());
+    }
+
     // For TUI mode with piped stdin, we'll read stdin first, then open TUI
     // This allows elegant piping: tree | treemd
     //
@@ -90,6 +142,38 @@ fn main() -> Result<()> {
     // 4. TUI displays the processed content with full interactivity
     //
     // This is the standard pattern used by: less, fzf, bat, etc.
+    //
+    // `treemd -` opening the TUI streams instead: stdin is read on a worker
+    // thread and the outline fills in as lines arrive.
+
+    #[cfg(feature = "unstable-dynamic")]
+    let setup_completions_requested = args.setup_completions;
+    #[cfg(not(feature = "unstable-dynamic"))]
+    let setup_completions_requested = false;
+
+    // Without any CLI-mode flag, the TUI is launched
+    let launch_tui = !args.list
+        && !args.tree
+        && !args.toc
+        && !args.count
+        && !args.tts
+        && !args.json_tree
+        && !args.print
+        && args.export.is_none()
+        && args.section.is_none()
+        && args.at_line.is_none()
+        && args.resolve_anchor.is_none()
+        && !setup_completions_requested;
+
+    // `treemd -`, or no file with input piped in (`cmd | treemd`)
+    let reads_stdin = match args.file.as_slice() {
+        [] => treemd::input::is_stdin_piped(),
+        [file] => file == std::path::Path::new("-"),
+        _ => false,
+    };
+    let stream_stdin = launch_tui && args.query.is_none() && reads_stdin;
+    // Started now, before the TUI points stdin at the terminal
+    let mut stdin_stream = stream_stdin.then(treemd::input::stream_stdin);
 
     // Remote documents: http(s) URLs are fetched directly, `github:owner/repo`
     // opens that repository's README.
@@ -97,21 +181,47 @@ fn main() -> Result<()> {
         .file
         .first()
         .map(|p| p.to_string_lossy().to_string())
-        .filter(|s| {
-            s.starts_with("http://") || s.starts_with("https://") || s.starts_with("github:")
-        });
+        .filter(|s| treemd::input::is_remote(s));
+
+    // A query over several files, or a directory, runs against each of them
+    if let Some(ref query_str) = args.query
+        && !args.interactive
+        && remote_spec.is_none()
+        && (args.file.len() > 1 || args.file.first().is_some_and(|f| f.is_dir()))
+    {
+        return handle_batch_query(
+            &args.file,
+            query_str,
+            args.query_output.as_deref(),
+            args.raw_output,
+        );
+    }
 
     // Determine input source - check for remote and file picker cases first
-    let (input_source, needs_file_picker, file_picker_dir) = if let Some(ref spec) = remote_spec {
-        match fetch_remote(spec) {
-            Ok(content) => (treemd::input::InputSource::Stdin(content), false, None),
+    let (input_source, needs_file_picker, browse_dir) = if let Some(ref spec) = remote_spec {
+        match treemd::input::determine_input_source(Some(std::path::Path::new(spec))) {
+            Ok(source) => (source, false, None),
             Err(e) => {
                 eprintln!("Error: {}", e);
                 process::exit(1);
             }
         }
+    } else if stream_stdin {
+        // Read in the TUI; see `App::stream_input`
+        (
+            treemd::input::InputSource::Stdin(String::new()),
+            false,
+            None,
+        )
     } else {
         match args.file.len() {
+            0 if reads_stdin => match treemd::input::determine_input_source(None) {
+                Ok(source) => (source, false, None),
+                Err(e) => {
+                    eprintln!("Error reading input: {}", e);
+                    process::exit(1);
+                }
+            },
             0 => {
                 // No file provided - check for .md files in cwd
                 use std::fs;
@@ -136,7 +246,7 @@ fn main() -> Result<()> {
                     eprintln!("No markdown files found in current directory.");
                     eprintln!("\nUsage: treemd [OPTIONS] <FILE>");
                     eprintln!("       treemd [OPTIONS] -");
-                    eprintln!("       treemd [OPTIONS] .           # Open file picker");
+                    eprintln!("       treemd [OPTIONS] .           # Browse a directory");
                     eprintln!("       tree | treemd [OPTIONS]\n");
                     eprintln!(
                         "Tip: Navigate to a directory with .md files, or specify a file path."
@@ -156,65 +266,54 @@ fn main() -> Result<()> {
                 )
             }
             1 => {
-                let file_path = &args.file[0];
-                // Check if it's a directory
-                if file_path.is_dir() {
-                    // Directory provided - open file picker in that directory
-                    (
+                // A directory is browsed; a file (or '-') is read as usual
+                match treemd::input::determine_input_source(Some(args.file[0].as_path())) {
+                    Ok(treemd::input::InputSource::Directory(dir)) => (
                         treemd::input::InputSource::Stdin(
                             "# Select a file\n\nPress Enter to select a markdown file.".to_string(),
                         ),
-                        true,
-                        Some(file_path.clone()),
-                    )
-                } else {
-                    // Single file path was provided - use existing logic
-                    match treemd::input::determine_input_source(Some(file_path.as_path())) {
-                        Ok(source) => (source, false, None),
-                        Err(treemd::input::InputError::NoTty) => {
-                            eprintln!("Error: markdown file argument is required");
-                            eprintln!("\nUsage: treemd [OPTIONS] <FILE>");
-                            eprintln!("       treemd [OPTIONS] -");
-                            eprintln!("       treemd [OPTIONS] .           # Open file picker");
-                            eprintln!("       tree | treemd [OPTIONS]\n");
-                            eprintln!(
-                                "Use '-' to explicitly read from stdin, or pipe input with CLI flags."
-                            );
-                            eprintln!("\nFor shell completion setup, use:");
-                            eprintln!("  treemd --setup-completions");
-                            std::process::exit(1);
-                        }
-                        Err(e) => {
-                            eprintln!("Error reading input: {}", e);
-                            process::exit(1);
-                        }
+                        false,
+                        Some(dir),
+                    ),
+                    Ok(source) => (source, false, None),
+                    Err(treemd::input::InputError::NoTty) => {
+                        eprintln!("Error: markdown file argument is required");
+                        eprintln!("\nUsage: treemd [OPTIONS] <FILE>");
+                        eprintln!("       treemd [OPTIONS] -");
+                        eprintln!("       treemd [OPTIONS] .           # Browse a directory");
+                        eprintln!("       tree | treemd [OPTIONS]\n");
+                        eprintln!(
+                            "Use '-' to explicitly read from stdin, or pipe input with CLI flags."
+                        );
+                        eprintln!("\nFor shell completion setup, use:");
+                        eprintln!("  treemd --setup-completions");
+                        std::process::exit(1);
+                    }
+                    Err(e) => {
+                        eprintln!("Error reading input: {}", e);
+                        process::exit(1);
                     }
                 }
             }
             _ => {
-                // Multiple files provided - open first file, others available in file picker
-                // For now, just open the first file (file picker will show all files in same dir)
-                let file_path = &args.file[0];
-                if file_path.is_dir() {
-                    // If first arg is a directory, use it for file picker
-                    (
+                // Multiple files provided - open the first, `>` / `<` switch
+                // between them. A directory first is browsed.
+                match treemd::input::determine_input_source(Some(args.file[0].as_path())) {
+                    Ok(treemd::input::InputSource::Directory(dir)) => (
                         treemd::input::InputSource::Stdin(
                             "# Select a file\n\nPress Enter to select a markdown file.".to_string(),
                         ),
-                        true,
-                        Some(file_path.clone()),
-                    )
-                } else {
-                    match treemd::input::determine_input_source(Some(file_path.as_path())) {
-                        Ok(source) => (source, false, None),
-                        Err(treemd::input::InputError::NoTty) => {
-                            eprintln!("Error: markdown file argument is required");
-                            std::process::exit(1);
-                        }
-                        Err(e) => {
-                            eprintln!("Error reading input: {}", e);
-                            process::exit(1);
-                        }
+                        false,
+                        Some(dir),
+                    ),
+                    Ok(source) => (source, false, None),
+                    Err(treemd::input::InputError::NoTty) => {
+                        eprintln!("Error: markdown file argument is required");
+                        std::process::exit(1);
+                    }
+                    Err(e) => {
+                        eprintln!("Error reading input: {}", e);
+                        process::exit(1);
                     }
                 }
             }
@@ -223,37 +322,59 @@ fn main() -> Result<()> {
 
     // Check if stdin was piped (before consuming input_source)
     let stdin_was_piped = matches!(input_source, treemd::input::InputSource::Stdin(_));
+    let plain_text = treemd::input::is_plain_text(&input_source);
 
     // Process input (handles tree format conversion, markdown passthrough, etc.)
-    let markdown_content = match treemd::input::process_input(input_source) {
-        Ok(content) => content,
-        Err(e) => {
-            eprintln!("Error processing input: {}", e);
-            process::exit(1);
-        }
-    };
+    // Plain text from a file is titled after it
+    let wrap_title = matches!(input_source, treemd::input::InputSource::File(_))
+        .then(|| args.file.first().and_then(|f| f.file_name()))
+        .flatten()
+        .map(|name| name.to_string_lossy().into_owned());
+    let markdown_content =
+        match treemd::input::process_input_titled(input_source, wrap_title.as_deref()) {
+            Ok(content) => content,
+            Err(e) => {
+                eprintln!("Error processing input: {}", e);
+                process::exit(1);
+            }
+        };
+
+    // A large document opened straight into the TUI is parsed there in the
+    // background, so the window comes up without waiting for it
+    let parse_in_tui = launch_tui
+        && args.query.is_none()
+        && markdown_content.len() >= treemd::tui::BACKGROUND_LOAD_BYTES;
 
     // Parse the markdown content
-    let doc = parser::parse_markdown(&markdown_content);
+    let mut doc = if parse_in_tui || stream_stdin {
+        parser::parse_markdown("")
+    } else {
+        parser::parse_markdown(&markdown_content)
+    };
 
     // Handle query mode
     if let Some(ref query_str) = args.query {
-        return handle_query_mode(&doc, query_str, args.query_output.as_deref());
+        if !args.interactive {
+            return handle_query_mode(
+                &doc,
+                query_str,
+                args.query_output.as_deref(),
+                args.raw_output,
+            );
+        }
+        // Browse the results instead: they replace the document in the TUI
+        let (query_str, results) = run_query(&doc, query_str);
+        let source = args
+            .file
+            .first()
+            .filter(|f| f.is_file())
+            .and_then(|f| f.file_name())
+            .and_then(|n| n.to_str());
+        doc = treemd::query::results_document(&doc, &results, source, &query_str);
     }
 
-    #[cfg(feature = "unstable-dynamic")]
-    let setup_completions_requested = args.setup_completions;
-    #[cfg(not(feature = "unstable-dynamic"))]
-    let setup_completions_requested = false;
-
     // If no flags, launch TUI
-    if !args.list
-        && !args.tree
-        && !args.count
-        && args.section.is_none()
-        && args.at_line.is_none()
-        && !setup_completions_requested
-    {
+    if launch_tui {
         // Load configuration
         let mut config = treemd::Config::load();
 
@@ -263,27 +384,8 @@ fn main() -> Result<()> {
         }
 
         // Detect terminal capabilities and determine color mode
-        // Priority: CLI args > config file > auto-detection
         let caps = treemd::tui::TerminalCapabilities::detect();
-        let color_mode = if let Some(ref mode_arg) = args.color_mode {
-            // CLI flag takes highest priority
-            use cli::ColorModeArg;
-            use treemd::tui::ColorMode;
-            match mode_arg {
-                ColorModeArg::Auto => caps.recommended_color_mode,
-                ColorModeArg::Rgb => ColorMode::Rgb,
-                ColorModeArg::Color256 => ColorMode::Indexed256,
-            }
-        } else {
-            // Check config file setting before falling back to auto-detection
-            use treemd::tui::ColorMode;
-            match config.terminal.color_mode.as_str() {
-                "rgb" => ColorMode::Rgb,
-                "256" => ColorMode::Indexed256,
-                // "auto" or any other value falls back to detection
-                _ => caps.recommended_color_mode,
-            }
-        };
+        let color_mode = color_mode(&args, &config, &caps);
 
         // Show compatibility warning if needed (before TUI init)
         // Skip the warning prompt if stdin was piped (already consumed)
@@ -320,13 +422,21 @@ fn main() -> Result<()> {
             eprintln!("Note: When piping input, ensure you have a controlling terminal.");
         })?;
 
+        // `theme = "auto"` follows the terminal background; ask while in raw
+        // mode, before anything else reads input
+        if config.ui.theme == treemd::config::AUTO_THEME {
+            treemd::tui::terminal_compat::query_background(std::time::Duration::from_millis(200));
+        }
+
         stdout().execute(EnterAlternateScreen).inspect_err(|_| {
             treemd::tui::tty::disable_raw_mode().ok();
         })?;
 
-        // Mouse capture: best-effort. Some terminals don't support it; that's
-        // fine — keyboard navigation still works.
-        let _ = stdout().execute(EnableMouseCapture);
+        // Mouse capture (unless `ui.mouse` is off): best-effort. Some
+        // terminals don't support it; that's fine — keyboard navigation still works.
+        if config.ui.mouse {
+            let _ = stdout().execute(EnableMouseCapture);
+        }
 
         let backend = ratatui::backend::CrosstermBackend::new(stdout());
         let mut terminal = ratatui::Terminal::new(backend).inspect_err(|_| {
@@ -334,7 +444,10 @@ fn main() -> Result<()> {
         })?;
 
         // Get filename and path (use placeholders for stdin)
-        let (filename, file_path) = if !args.file.is_empty() && !args.file[0].is_dir() {
+        let (filename, file_path) = if !args.file.is_empty()
+            && !args.file[0].is_dir()
+            && args.file[0] != std::path::Path::new("-")
+        {
             let file = &args.file[0];
             let name = file
                 .file_name()
@@ -347,6 +460,15 @@ fn main() -> Result<()> {
             // Stdin input or directory
             ("stdin".to_string(), std::path::PathBuf::from("<stdin>"))
         };
+        // Query results are not a file on disk, but keep its directory so
+        // their source links resolve next to the queried file
+        let (filename, file_path) = if args.interactive {
+            let name = format!("{} (query)", filename);
+            let path = file_path.with_file_name(&name);
+            (name, path)
+        } else {
+            (filename, file_path)
+        };
 
         // Determine if images are enabled
         // Priority: CLI flags > config file > default (true)
@@ -358,13 +480,55 @@ fn main() -> Result<()> {
             config.images.enabled
         };
 
-        let mut app =
-            treemd::App::new(doc, filename, file_path, config, color_mode, images_enabled);
+        let mut app = treemd::App::new(
+            doc,
+            filename.clone(),
+            file_path.clone(),
+            config,
+            color_mode,
+            images_enabled,
+        );
+        if args.interactive {
+            app.query_results = Some(file_path.clone());
+        }
+        if parse_in_tui {
+            app.load_in_background(markdown_content, filename, file_path, None);
+        }
+        if let Some(spec) = &remote_spec {
+            app.remote_source =
+                Some(treemd::input::remote_url(spec).unwrap_or_else(|_| spec.clone()));
+        }
+        if let Some(stream) = stdin_stream.take() {
+            app.stream_input(stream);
+        }
+        // Live reload: CLI flags > config file
+        if args.watch || args.no_watch {
+            app.watch = args.watch;
+        }
+        if args.no_restore {
+            app.view_state_path = None;
+        }
+        app.return_to_remembered_position();
         if needs_file_picker {
             app.startup_needs_file_picker = true;
         }
-        if let Some(dir) = file_picker_dir {
-            app.file_picker_dir = Some(dir.canonicalize().unwrap_or(dir));
+        if remote_spec.is_none() && !args.interactive && args.file.len() > 1 {
+            let files = args
+                .file
+                .iter()
+                .filter(|f| f.is_file())
+                .map(|f| f.canonicalize().unwrap_or_else(|_| f.clone()))
+                .collect();
+            app.set_files(files);
+        }
+        if let Some(dir) = browse_dir {
+            // Browse the directory's markdown files; with none, fall back to
+            // the file picker there (it also lists subdirectories)
+            let dir = dir.canonicalize().unwrap_or(dir);
+            app.file_picker_dir = Some(dir.clone());
+            if !app.open_directory(dir) {
+                app.startup_needs_file_picker = true;
+            }
         }
         let result = treemd::tui::run(&mut terminal, app);
 
@@ -379,37 +543,40 @@ fn main() -> Result<()> {
     }
 
     // Handle CLI commands
-    handle_cli_mode(&args, &doc);
+    handle_cli_mode(&args, &doc, plain_text);
     Ok(())
 }
 
-/// Fetch a remote document. http(s) URLs are fetched directly;
-/// `github:owner/repo` resolves to the repository's README on the default
-/// branch via raw.githubusercontent.com.
-fn fetch_remote(spec: &str) -> Result<String, String> {
-    let url = if let Some(repo) = spec.strip_prefix("github:") {
-        let repo = repo.trim_matches('/');
-        if repo.split('/').filter(|s| !s.is_empty()).count() != 2 {
-            return Err(format!(
-                "Invalid GitHub spec '{}': expected github:owner/repo",
-                spec
-            ));
-        }
-        format!("https://raw.githubusercontent.com/{}/HEAD/README.md", repo)
+/// The color mode to draw in.
+/// Priority: CLI args > config file > auto-detection
+fn color_mode(
+    args: &Cli,
+    config: &treemd::Config,
+    caps: &treemd::tui::TerminalCapabilities,
+) -> treemd::tui::ColorMode {
+    use cli::ColorModeArg;
+    use treemd::tui::ColorMode;
+
+    if let Some(ref mode_arg) = args.color_mode {
+        // CLI flag takes highest priority
+        match mode_arg {
+            ColorModeArg::Auto => caps.recommended_color_mode,
+            ColorModeArg::Rgb => ColorMode::Rgb,
+            ColorModeArg::Color256 => ColorMode::Indexed256,
+        }
     } else {
-        spec.to_string()
-    };
-
-    let mut response = ureq::get(&url)
-        .call()
-        .map_err(|e| format!("Failed to fetch {}: {}", url, e))?;
-    response
-        .body_mut()
-        .read_to_string()
-        .map_err(|e| format!("Failed to read response from {}: {}", url, e))
+        // Check config file setting before falling back to auto-detection
+        match config.terminal.color_mode.as_str() {
+            "rgb" => ColorMode::Rgb,
+            "256" => ColorMode::Indexed256,
+            // "auto" or any other value falls back to detection
+            _ => caps.recommended_color_mode,
+        }
+    }
 }
 
-fn handle_cli_mode(args: &Cli, doc: &Document) {
+/// `plain_text` is set when `doc` is plain text wrapped in a heading.
+fn handle_cli_mode(args: &Cli, doc: &Document, plain_text: bool) {
     // Apply filters
     let headings: Vec<_> = if let Some(level) = args.level {
         doc.headings_at_level(level)
@@ -424,12 +591,46 @@ fn handle_cli_mode(args: &Cli, doc: &Document) {
         print_heading_at_line(doc, line);
         return;
     }
+    if let Some(ref anchor) = args.resolve_anchor {
+        // Offsets are reported in the file as stored, BOM and CRLF included
+        // (unless the document isn't the file's text, like wrapped plain text)
+        let raw = args
+            .file
+            .first()
+            .filter(|f| f.is_file())
+            .and_then(|f| std::fs::read_to_string(f).ok())
+            .filter(|raw| treemd::input::normalize(raw) == doc.content);
+        print_anchor_position(doc, raw.as_deref(), anchor, &args.output);
+        return;
+    }
 
     // Handle different modes
-    if args.count {
+    if let Some(ExportFormat::Html) = args.export {
+        export_html(args, doc);
+    } else if args.print {
+        print_rendered(args, doc);
+    } else if args.tts {
+        let options = parser::ReadAloudOptions {
+            skip_code: args.tts_skip_code,
+        };
+        print!("{}", parser::to_read_aloud(&doc.content, options));
+    } else if args.json_tree {
+        let json = serde_json::to_string_pretty(&parser::build_document_tree(doc))
+            .expect("JSON serialization of document tree should not fail");
+        println!("{}", json);
+    } else if args.count {
         print_heading_counts(doc);
     } else if args.tree {
         print_tree(doc, &args.output, &headings);
+    } else if args.toc {
+        // Plain text has no headings; the one it is wrapped in isn't in the file
+        if !plain_text {
+            let options = parser::TocOptions {
+                max_level: args.max_level.map(usize::from),
+                numbered: args.numbered,
+            };
+            print!("{}", parser::to_toc(doc, options));
+        }
     } else if let Some(ref section_name) = args.section {
         extract_section(doc, section_name);
     } else if args.list {
@@ -475,6 +676,43 @@ fn print_heading_at_line(doc: &Document, target_line: usize) {
     }
 }
 
+/// Print where the heading an anchor refers to starts in the source.
+///
+/// Used by `--resolve-anchor` for editor "go to anchor" integrations. Byte
+/// offsets are mapped back into `raw`, the file as read from disk, when
+/// there is one.
+fn print_anchor_position(doc: &Document, raw: Option<&str>, anchor: &str, format: &OutputFormat) {
+    let Some(idx) = doc.find_anchor(anchor) else {
+        eprintln!("No heading matches anchor '{}'", anchor);
+        process::exit(1);
+    };
+    let heading = &doc.headings[idx];
+    let line = doc.line_at(heading.offset);
+    let offset =
+        |normalized| raw.map_or(normalized, |raw| treemd::input::raw_offset(raw, normalized));
+
+    match format {
+        OutputFormat::Plain => println!("{} {}", line, offset(heading.offset)),
+        OutputFormat::Json => {
+            let end = doc.section_end(idx);
+            let json = serde_json::json!({
+                "anchor": doc.github_anchors()[idx],
+                "heading": heading.text,
+                "level": heading.level,
+                "line": line,
+                "offset": offset(heading.offset),
+                "end_line": doc.line_at(end),
+                "end_offset": offset(end),
+            });
+            println!("{}", json);
+        }
+        OutputFormat::Tree => {
+            eprintln!("--resolve-anchor supports plain or json output");
+            process::exit(1);
+        }
+    }
+}
+
 fn print_headings(headings: &[&parser::Heading], format: &OutputFormat, doc: &Document) {
     match format {
         OutputFormat::Plain => {
@@ -549,6 +787,17 @@ fn print_heading_counts(doc: &Document) {
 }
 
 fn extract_section(doc: &Document, section_name: &str) {
+    println!("{}", section_source(doc, section_name).trim());
+}
+
+/// Source of a section from its heading up to the next heading of the same
+/// or higher level. Exits if there is no such section.
+fn section_source<'a>(doc: &'a Document, section_name: &str) -> &'a str {
+    &doc.content[section_range(doc, section_name)]
+}
+
+/// Byte range of the section [`section_source`] returns.
+fn section_range(doc: &Document, section_name: &str) -> std::ops::Range<usize> {
     let heading = match doc.find_heading(section_name) {
         Some(h) => h,
         None => {
@@ -569,10 +818,81 @@ fn extract_section(doc: &Document, section_name: &str) {
         .map(|h| h.offset)
         .unwrap_or(doc.content.len());
 
-    println!("{}", doc.content[start..end].trim());
+    start..end
 }
 
-fn handle_query_mode(doc: &Document, query_str: &str, output_format: Option<&str>) -> Result<()> {
+/// Print the document (or the `-s` section) as a standalone HTML page
+/// styled with the configured theme.
+fn export_html(args: &Cli, doc: &Document) {
+    use treemd::tui::ColorMode;
+    use treemd::tui::theme::Theme;
+
+    let mut config = treemd::Config::load();
+    if let Some(ref theme_name) = args.theme {
+        config.ui.theme = theme_name.clone();
+    }
+    let style = Theme::from_config(&config, config.theme_name(), ColorMode::Rgb).html_style();
+
+    let (range, title) = match args.section {
+        Some(ref name) => (section_range(doc, name), name.clone()),
+        None => {
+            let title = doc
+                .headings
+                .iter()
+                .find(|h| h.level == 1)
+                .map(|h| h.text.clone())
+                .or_else(|| {
+                    args.file
+                        .first()
+                        .and_then(|f| f.file_stem())
+                        .map(|s| s.to_string_lossy().into_owned())
+                })
+                .unwrap_or_else(|| "treemd".to_string());
+            (0..doc.content.len(), title)
+        }
+    };
+    let anchors = doc.github_anchors_in(range.clone());
+    print!(
+        "{}",
+        parser::to_html(&doc.content[range], &title, &style, &anchors)
+    );
+}
+
+/// Print the document (or the `-s` section) rendered as in the content
+/// pane: in the theme's colors on a terminal, plain when piped, with
+/// --no-color or with `NO_COLOR` set.
+fn print_rendered(args: &Cli, doc: &Document) {
+    use std::io::{IsTerminal, Write};
+
+    let mut config = treemd::Config::load();
+    if let Some(ref theme_name) = args.theme {
+        config.ui.theme = theme_name.clone();
+    }
+    let terminal = std::io::stdout().is_terminal();
+    let no_color = args.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    let color = (terminal && !no_color)
+        .then(|| color_mode(args, &config, &treemd::tui::TerminalCapabilities::detect()));
+    let width = if terminal {
+        crossterm::terminal::size().map_or(80, |(columns, _)| columns)
+    } else {
+        80
+    };
+
+    let markdown = match args.section {
+        Some(ref name) => section_source(doc, name),
+        None => doc.content.as_str(),
+    };
+    let rendered = treemd::tui::print::render(markdown, &config, color, width);
+    // A pager quitting early closes the pipe; that is not an error
+    let _ = std::io::stdout().write_all(rendered.as_bytes());
+}
+
+fn handle_query_mode(
+    doc: &Document,
+    query_str: &str,
+    output_format: Option<&str>,
+    raw: bool,
+) -> Result<()> {
     use treemd::query::{self, OutputFormat};
 
     // Parse output format
@@ -585,24 +905,151 @@ fn handle_query_mode(doc: &Document, query_str: &str, output_format: Option<&str
         })?
         .unwrap_or(OutputFormat::Plain);
 
+    let (_, results) = run_query(doc, query_str);
+    if results.is_empty() {
+        // No results - exit silently like jq
+        return Ok(());
+    }
+    let output = if raw {
+        query::format_output_raw(&results, format)
+    } else {
+        query::format_output(&results, format)
+    };
+    println!("{}", output);
+    Ok(())
+}
+
+/// Expand history references in `query_str`, run it and record it in the
+/// history. Returns the expanded query and its results; exits on errors.
+fn run_query(doc: &Document, query_str: &str) -> (String, Vec<treemd::query::Value>) {
+    let query_str = recall_query(query_str);
+
     // Execute query
-    match query::execute(doc, query_str) {
+    match treemd::query::execute(doc, &query_str) {
         Ok(results) => {
-            if results.is_empty() {
-                // No results - exit silently like jq
-                return Ok(());
-            }
-            let output = query::format_output(&results, format);
-            println!("{}", output);
-            Ok(())
+            remember_query(&query_str);
+            (query_str, results)
+        }
+        Err(e) => {
+            eprint!("{}", e.render(&query_str));
+            process::exit(1);
         }
+    }
+}
+
+/// Substitute `!!` / `!n` in `query_str` from the persisted query history;
+/// exits if the reference does not exist.
+fn recall_query(query_str: &str) -> String {
+    use treemd::query::history::QueryHistory;
+
+    let history_size = treemd::Config::load().query.history_size;
+    let history = QueryHistory::path()
+        .filter(|_| history_size > 0)
+        .map(|path| QueryHistory::load(&path))
+        .unwrap_or_default();
+    match history.expand(query_str) {
+        Ok(Some(recalled)) => recalled.to_string(),
+        Ok(None) => query_str.to_string(),
         Err(e) => {
-            eprintln!("{}", e);
+            eprintln!("Error: {}", e);
             process::exit(1);
         }
     }
 }
 
+/// Add a query that ran to the persisted history. Only queries whose
+/// results go to a terminal are kept; scripts and pipelines leave the
+/// history alone.
+fn remember_query(query_str: &str) {
+    use std::io::IsTerminal;
+    use treemd::query::history::QueryHistory;
+
+    if !std::io::stdout().is_terminal() {
+        return;
+    }
+    let history_size = treemd::Config::load().query.history_size;
+    if let Some(path) = QueryHistory::path().filter(|_| history_size > 0) {
+        let mut history = QueryHistory::load(&path);
+        history.push(query_str);
+        // History is a convenience; failing to persist it is not an error
+        let _ = history.save(&path, history_size);
+    }
+}
+
+/// Run `--query` over several files (directories contribute their markdown
+/// files), printing each file's results under a `==> path <==` header. The
+/// JSON formats print one array of per-file objects, or one object per line
+/// with `jsonl`. Files without results are skipped; a file that cannot be
+/// read or queried is reported and makes the exit status 1 once the rest
+/// have printed.
+fn handle_batch_query(
+    paths: &[std::path::PathBuf],
+    query_str: &str,
+    output_format: Option<&str>,
+    raw: bool,
+) -> Result<()> {
+    use treemd::query::{self, OutputFormat};
+
+    let format = match output_format.map(|s| s.parse::<OutputFormat>()).transpose() {
+        Ok(format) => format.unwrap_or(OutputFormat::Plain),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            process::exit(1);
+        }
+    };
+    let files: Vec<std::path::PathBuf> = paths
+        .iter()
+        .flat_map(|path| {
+            if path.is_dir() {
+                treemd::input::markdown_files(path)
+            } else {
+                vec![path.clone()]
+            }
+        })
+        .collect();
+
+    let query_str = recall_query(query_str);
+    let results = match query::execute_many(&files, &query_str, format, raw) {
+        Ok(results) => results,
+        Err(e) => {
+            eprint!("{}", e.render(&query_str));
+            process::exit(1);
+        }
+    };
+    remember_query(&query_str);
+
+    let mut first = true;
+    let mut failed = false;
+    let mut objects = Vec::new();
+    for file in &results {
+        match &file.output {
+            Ok(output) if file.count > 0 && format.is_json() => objects.push(output.as_str()),
+            Ok(output) if file.count > 0 => {
+                if !first {
+                    println!();
+                }
+                first = false;
+                println!("==> {} <==", file.path.display());
+                println!("{}", output);
+            }
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                failed = true;
+            }
+        }
+    }
+    match format {
+        OutputFormat::JsonLines => objects.iter().for_each(|object| println!("{}", object)),
+        _ if format.is_json() => println!("{}", query::json_array(&objects, format)),
+        _ => {}
+    }
+    if failed {
+        process::exit(1);
+    }
+    Ok(())
+}
+
 fn print_query_help() {
     let help = r#"
 treemd Query Language (tql)
@@ -620,6 +1067,10 @@ ELEMENT SELECTORS
     .table          All tables
     .list           All lists
     .blockquote     All blockquotes
+    .callouts       GitHub alerts / Obsidian callouts (.kind, .title, .text)
+    .footnotes      Footnote definitions (.label, .text)
+    .definitions    Definition list entries (.term, .definition)
+    .outline        Heading tree (.text, .level, .depth, .children_count, .line)
 
 FILTERS & INDEXING
     .h2[Features]       Heading containing "Features" (fuzzy)
@@ -647,10 +1098,11 @@ COLLECTION FUNCTIONS
     nth(n)              Get element at index
     reverse             Reverse order
     sort                Sort alphabetically
-    sort_by(key)        Sort by property
-    unique              Remove duplicates
+    sort_by(key)        Sort by property (whole stream)
+    map(f)              Apply f to each element, collect results
+    unique              Remove duplicates (whole stream)
     flatten             Flatten nested arrays
-    group_by(key)       Group elements by key
+    group_by(key)       One array per key (whole stream)
     min, max            Min/max numeric value
     add                 Sum numbers or concat strings
 
@@ -658,17 +1110,20 @@ STRING FUNCTIONS
     text                Get text representation
     upper, lower        Case conversion
     trim                Strip whitespace
-    split(sep)          Split by separator
-    join(sep)           Join with separator
+    split(sep)          Split string into an array
+    join(sep)           Join an array into a string
     replace(a, b)       Replace substring
     slugify             URL-friendly slug
     lines, words, chars Count lines/words/chars
+    wordcount           Words in a section's plain text, code blocks left out
+                        (wordcount(true) counts them too)
 
 FILTER FUNCTIONS
     select(cond)        Keep if condition true (alias: where, filter)
     contains(s)         Contains substring (alias: includes)
     startswith(s)       Starts with prefix
     endswith(s)         Ends with suffix
+                        (case-sensitive; pass true as 2nd arg to ignore case)
     matches(regex)      Matches regex pattern
     any, all            Check if any/all truthy
     not                 Negate boolean
@@ -678,6 +1133,18 @@ CONTENT FUNCTIONS
     md                  Raw markdown
     url, href, src      Get URL/link/image source
     lang                Code block language
+    line                Source line of an element (empty if unknown)
+
+FORMAT FILTERS
+    @base64             Base64-encode text
+    @uri                Percent-encode text for URLs
+    @csv                Array as a CSV row
+
+OBJECT FUNCTIONS
+    keys                Sorted keys of an object (e.g. .frontmatter)
+    has(key)            Whether an object has a key
+    to_entries          Object to [{key, value}] sorted by key
+    from_entries        [{key, value}] back to an object
 
 AGGREGATION FUNCTIONS
     stats               Document statistics
@@ -713,7 +1180,10 @@ EXAMPLES
     treemd -q '.h1[Features] > .h2' doc.md
 
     # Group headings by level
-    treemd -q '[.h] | group_by("level")' doc.md
+    treemd -q '.h | group_by(.level)' doc.md
+
+    # Reshape results ({text} is short for {text: .text})
+    treemd -q '.h2 | {text, depth: .level}' --query-output csv doc.md
 
     # Document statistics
     treemd -q '. | stats' doc.md
@@ -721,6 +1191,12 @@ EXAMPLES
     # JSON output
     treemd -q '.h2' --query-output json doc.md
 
+    # Strings without JSON quotes (like jq -r)
+    treemd -q '.h2 | .text' --query-output jsonl -r doc.md
+
+    # Run a query saved in a file
+    treemd --query-file sections.tql doc.md
+
 OUTPUT FORMATS (--query-output)
     plain       Human-readable text (default)
     json        Compact JSON
@@ -728,6 +1204,7 @@ OUTPUT FORMATS (--query-output)
     jsonl       Line-delimited JSON (one per line)
     md          Raw markdown
     tree        Tree structure
+    csv         Comma-separated values (objects get a header row)
 
 For more details, see: https://github.com/epistates/treemd
 "#;
@@ -0,0 +1,189 @@
+//! Markdown Extra abbreviation definitions.
+//!
+//! A definition is a line of the form `*[HTML]: HyperText Markup Language`
+//! anywhere in the document (outside fenced code). Every whole-word
+//! occurrence of the abbreviation in the text can then be marked and its
+//! expansion looked up.
+
+use std::collections::BTreeMap;
+
+/// Abbreviations defined in a document, keyed by the abbreviation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Abbreviations {
+    map: BTreeMap<String, String>,
+}
+
+impl Abbreviations {
+    /// Collect the abbreviation definitions in `content`.
+    ///
+    /// A later definition of the same abbreviation replaces an earlier one.
+    pub fn parse(content: &str) -> Self {
+        let mut map = BTreeMap::new();
+        for line in definition_lines(content) {
+            if let Some((abbr, expansion)) = parse_definition(line) {
+                map.insert(abbr.to_string(), expansion.to_string());
+            }
+        }
+        Self { map }
+    }
+
+    /// Whether no abbreviations are defined.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Expansion of `abbr`, if defined (case-sensitive).
+    pub fn expansion(&self, abbr: &str) -> Option<&str> {
+        self.map.get(abbr).map(String::as_str)
+    }
+
+    /// Byte ranges of whole-word abbreviation occurrences in `text`, in order.
+    ///
+    /// Where abbreviations overlap (`HTML` and `HTML5`), the longest wins.
+    pub fn find_in(&self, text: &str) -> Vec<(usize, usize)> {
+        let mut found = Vec::new();
+        let mut pos = 0;
+        while pos < text.len() {
+            let rest = &text[pos..];
+            let at_word_start = text[..pos]
+                .chars()
+                .next_back()
+                .is_none_or(|c| !is_word_char(c));
+            let matched = at_word_start
+                .then(|| {
+                    self.map
+                        .keys()
+                        .filter(|abbr| rest.starts_with(abbr.as_str()))
+                        .filter(|abbr| {
+                            rest[abbr.len()..]
+                                .chars()
+                                .next()
+                                .is_none_or(|c| !is_word_char(c))
+                        })
+                        .map(String::len)
+                        .max()
+                })
+                .flatten();
+            match matched {
+                Some(len) => {
+                    found.push((pos, pos + len));
+                    pos += len;
+                }
+                None => pos += rest.chars().next().map_or(1, char::len_utf8),
+            }
+        }
+        found
+    }
+
+    /// `ABBR: expansion` for each abbreviation occurring in `text`, without
+    /// repeats, in order of first occurrence.
+    pub fn hints_for(&self, text: &str) -> Vec<String> {
+        let mut hints: Vec<String> = Vec::new();
+        for (start, end) in self.find_in(text) {
+            let abbr = &text[start..end];
+            if let Some(expansion) = self.expansion(abbr) {
+                let hint = format!("{}: {}", abbr, expansion);
+                if !hints.contains(&hint) {
+                    hints.push(hint);
+                }
+            }
+        }
+        hints
+    }
+}
+
+/// Remove abbreviation definition lines so they are not rendered as text.
+pub fn strip_definitions(content: &str) -> String {
+    let mut in_fence = false;
+    let mut result = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        if is_fence(line) {
+            in_fence = !in_fence;
+        }
+        if in_fence || parse_definition(line).is_none() {
+            result.push_str(line);
+        }
+    }
+    result
+}
+
+/// Lines outside fenced code blocks.
+fn definition_lines(content: &str) -> impl Iterator<Item = &str> {
+    let mut in_fence = false;
+    content.lines().filter(move |line| {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            return false;
+        }
+        !in_fence
+    })
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Parse `*[ABBR]: expansion`.
+fn parse_definition(line: &str) -> Option<(&str, &str)> {
+    let rest = line.trim_end_matches(['\n', '\r']).strip_prefix("*[")?;
+    let (abbr, rest) = rest.split_once("]:")?;
+    let expansion = rest.trim();
+    if abbr.trim().is_empty() || abbr != abbr.trim() || expansion.is_empty() {
+        return None;
+    }
+    Some((abbr, expansion))
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "\
+The HTML spec and HTML5 differ. XHTML is not HTML.
+
+```
+*[CODE]: not a definition
+```
+
+*[HTML]: HyperText Markup Language
+*[HTML5]: HTML version 5
+";
+
+    #[test]
+    fn parses_definitions_outside_code() {
+        let abbrs = Abbreviations::parse(DOC);
+        assert_eq!(abbrs.expansion("HTML"), Some("HyperText Markup Language"));
+        assert_eq!(abbrs.expansion("HTML5"), Some("HTML version 5"));
+        assert_eq!(abbrs.expansion("CODE"), None);
+        assert_eq!(abbrs.expansion("html"), None);
+    }
+
+    #[test]
+    fn finds_whole_word_occurrences() {
+        let abbrs = Abbreviations::parse(DOC);
+        let line = "The HTML spec and HTML5 differ. XHTML is not HTML.";
+        let words: Vec<&str> = abbrs
+            .find_in(line)
+            .into_iter()
+            .map(|(s, e)| &line[s..e])
+            .collect();
+        assert_eq!(words, ["HTML", "HTML5", "HTML"]);
+        assert_eq!(
+            abbrs.hints_for(line),
+            ["HTML: HyperText Markup Language", "HTML5: HTML version 5"]
+        );
+    }
+
+    #[test]
+    fn strips_definition_lines_only() {
+        let stripped = strip_definitions(DOC);
+        assert!(!stripped.contains("*[HTML]"));
+        assert!(stripped.contains("*[CODE]: not a definition"));
+        assert!(stripped.starts_with("The HTML spec"));
+    }
+}
@@ -0,0 +1,101 @@
+//! GitHub alerts and Obsidian callouts.
+//!
+//! A blockquote whose first line is a marker such as `[!NOTE]` or
+//! `[!warning]- Custom title` is a callout of that kind. The TUI draws them
+//! as styled boxes and the query language exposes them as `.callouts`.
+
+/// A callout marker at the start of a blockquote.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CalloutMarker {
+    /// Kind, upper-cased: `NOTE`, `WARNING`, or any custom word
+    pub kind: String,
+    /// Title given after the marker, or the kind in title case
+    pub title: String,
+}
+
+/// Parse the first line of a blockquote for a callout marker.
+pub fn parse_marker(first_line: &str) -> Option<CalloutMarker> {
+    let trimmed = first_line.trim();
+    let rest = trimmed.strip_prefix("[!")?;
+    let end = rest.find(']')?;
+    let kind_raw = &rest[..end];
+    if kind_raw.is_empty()
+        || !kind_raw
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return None;
+    }
+
+    // Obsidian fold markers (`[!note]+` / `[!note]-`) and optional title
+    let mut after = rest[end + 1..].trim_start();
+    if let Some(stripped) = after.strip_prefix(['+', '-']) {
+        after = stripped.trim_start();
+    }
+
+    let title = if after.is_empty() {
+        // Title-case the kind: NOTE -> Note
+        let lower = kind_raw.to_ascii_lowercase();
+        let mut chars = lower.chars();
+        let c = chars.next()?;
+        c.to_uppercase().collect::<String>() + chars.as_str()
+    } else {
+        after.to_string()
+    };
+
+    Some(CalloutMarker {
+        kind: kind_raw.to_ascii_uppercase(),
+        title,
+    })
+}
+
+/// Split blockquote `content` into its callout marker and body, or None when
+/// the blockquote is not a callout.
+pub fn split(content: &str) -> Option<(CalloutMarker, &str)> {
+    let (first, body) = content.split_once('\n').unwrap_or((content, ""));
+    Some((parse_marker(first)?, body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn basic_kinds() {
+        let m = parse_marker("[!NOTE]").unwrap();
+        assert_eq!(m.kind, "NOTE");
+        assert_eq!(m.title, "Note");
+
+        let m = parse_marker("[!warning]").unwrap();
+        assert_eq!(m.kind, "WARNING");
+        assert_eq!(m.title, "Warning");
+    }
+
+    #[test]
+    fn custom_title_and_fold() {
+        let m = parse_marker("[!tip] Read this first").unwrap();
+        assert_eq!(m.kind, "TIP");
+        assert_eq!(m.title, "Read this first");
+
+        let m = parse_marker("[!note]- Folded title").unwrap();
+        assert_eq!(m.kind, "NOTE");
+        assert_eq!(m.title, "Folded title");
+    }
+
+    #[test]
+    fn rejects_non_markers() {
+        assert!(parse_marker("plain quote").is_none());
+        assert!(parse_marker("[!]").is_none());
+        assert!(parse_marker("[! has space]").is_none());
+        assert!(parse_marker("[note]").is_none());
+    }
+
+    #[test]
+    fn split_separates_marker_and_body() {
+        let (marker, body) = split("[!CAUTION]\nHot\nsurface").unwrap();
+        assert_eq!(marker.kind, "CAUTION");
+        assert_eq!(body, "Hot\nsurface");
+        assert_eq!(split("[!TIP]").unwrap().1, "");
+        assert!(split("quote\n[!NOTE]").is_none());
+    }
+}
@@ -11,6 +11,8 @@
 //! - Full GFM support: tables, strikethrough, task lists
 
 use super::output::Block;
+use std::collections::HashMap;
+use std::ops::Range;
 
 /// Parse markdown content into structured blocks.
 ///
@@ -26,7 +28,316 @@ use super::output::Block;
 ///
 /// A vector of parsed content blocks.
 pub fn parse_content(markdown: &str, start_line: usize) -> Vec<Block> {
-    turbovault_parser::parse_blocks_from_line(markdown, start_line)
+    let mut blocks = turbovault_parser::parse_blocks_from_line(markdown, start_line);
+    restore_callout_lines(markdown, &mut blocks);
+    restore_definition_lines(markdown, &mut blocks);
+    blocks
+}
+
+/// The parser joins a blockquote's lines without any break, which glues a
+/// callout's `[!NOTE]` marker to its first line of body. Callouts get their
+/// source lines back instead, found by the offsets pulldown-cmark reports
+/// for each top-level blockquote.
+fn restore_callout_lines(markdown: &str, blocks: &mut [Block]) {
+    let ranges = block_ranges(blocks, &source_blocks(markdown));
+    for (block, range) in blocks.iter_mut().zip(ranges) {
+        if let Block::Blockquote { content, .. } = block
+            && let Some(range) = range
+        {
+            let source = unquote(&markdown[range]);
+            if super::callout::split(&source).is_some() {
+                *content = source;
+            }
+        }
+    }
+}
+
+/// A blockquote's source with the `>` marker (and the space after it)
+/// removed from each line; lazy continuation lines are kept as they are.
+pub(crate) fn unquote(quote: &str) -> String {
+    quote
+        .lines()
+        .map(|line| {
+            let trimmed = line.trim_start();
+            match trimmed.strip_prefix('>') {
+                Some(rest) => rest.strip_prefix(' ').unwrap_or(rest),
+                None => trimmed,
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// The parser joins a paragraph's lines with spaces, losing the layout of a
+/// definition list. Definition lists get their source lines back: each one
+/// is parsed on its own and the paragraph that produces is looked up among
+/// the top-level blocks.
+fn restore_definition_lines(markdown: &str, blocks: &mut [Block]) {
+    for source in paragraph_sources(markdown) {
+        if super::definition_list::split(&source).is_none() {
+            continue;
+        }
+        let parsed = turbovault_parser::parse_blocks_from_line(&source, 0);
+        let [
+            Block::Paragraph {
+                content: joined, ..
+            },
+        ] = parsed.as_slice()
+        else {
+            continue;
+        };
+        let paragraph = blocks.iter_mut().find_map(|block| match block {
+            Block::Paragraph { content, .. } if content == joined => Some(content),
+            _ => None,
+        });
+        if let Some(content) = paragraph {
+            *content = source;
+        }
+    }
+}
+
+/// Runs of non-blank lines outside fenced code, without leading ATX
+/// headings: the source of every top-level paragraph, among other blocks.
+fn paragraph_sources(markdown: &str) -> Vec<String> {
+    let mut sources = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    let mut fence: Option<(char, usize)> = None;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim_start();
+        if let Some((marker, len)) = fence {
+            if fence_marker(trimmed).is_some_and(|(m, l)| m == marker && l >= len)
+                && trimmed.trim_start_matches(marker).trim().is_empty()
+            {
+                fence = None;
+            }
+            continue;
+        }
+        fence = fence_marker(trimmed);
+        if fence.is_some() || trimmed.is_empty() || is_atx_heading(trimmed) {
+            if !current.is_empty() {
+                sources.push(current.join("\n"));
+                current.clear();
+            }
+            continue;
+        }
+        current.push(line);
+    }
+    if !current.is_empty() {
+        sources.push(current.join("\n"));
+    }
+    sources
+}
+
+/// Parse `content[start..end]` like [`parse_content`], pairing each
+/// top-level block with its byte range in `content`.
+///
+/// Ranges come from the offsets pulldown-cmark reports for the same source,
+/// matched to the blocks in order by kind. Images the parser lifts out of a
+/// list item, table or heading share the range of the block they came from.
+/// A block with no counterpart in the source (which preprocessing can
+/// produce) has no range; the others are unaffected.
+pub fn parse_content_with_ranges(
+    content: &str,
+    start: usize,
+    end: usize,
+) -> Vec<(Block, Option<Range<usize>>)> {
+    let blocks = parse_content(&content[start..end], super::utils::line_at(content, start));
+    let ranges = block_ranges(&blocks, &source_blocks(&content[start..end]));
+    blocks
+        .into_iter()
+        .zip(ranges)
+        .map(|(block, range)| (block, range.map(|r| start + r.start..start + r.end)))
+        .collect()
+}
+
+/// The range in `sources` of each of `blocks`, matched in order by kind;
+/// see [`parse_content_with_ranges`].
+fn block_ranges(
+    blocks: &[Block],
+    sources: &[(SourceKind, Range<usize>)],
+) -> Vec<Option<Range<usize>>> {
+    let mut next = 0;
+    blocks
+        .iter()
+        .map(|block| match source_kind(block) {
+            None => sources.get(next).map(|(_, range)| range.clone()),
+            Some(kind) => sources[next..]
+                .iter()
+                .position(|(k, _)| *k == kind)
+                .map(|found| {
+                    next += found + 1;
+                    sources[next - 1].1.clone()
+                }),
+        })
+        .collect()
+}
+
+/// Every code block in `content[start..end]`, nested ones included, in
+/// source order: its text as [`parse_content`] reports it and its byte
+/// range in `content`, fences included.
+pub fn code_block_ranges(content: &str, start: usize, end: usize) -> Vec<(String, Range<usize>)> {
+    use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+
+    let source = &content[start..end];
+    let mut blocks = Vec::new();
+    let mut current: Option<(String, Range<usize>)> = None;
+    for (event, range) in Parser::new_ext(source, parser_options()).into_offset_iter() {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => current = Some((String::new(), range)),
+            Event::Text(text) => {
+                if let Some((code, _)) = current.as_mut() {
+                    code.push_str(&text);
+                }
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                if let Some((code, range)) = current.take() {
+                    let range = start + range.start..start + trim_range(source, range).end;
+                    blocks.push((code.trim_end().to_string(), range));
+                }
+            }
+            _ => {}
+        }
+    }
+    blocks
+}
+
+/// Top-level block kinds as the event stream reports them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SourceKind {
+    Paragraph,
+    Heading,
+    Code,
+    List,
+    Blockquote,
+    Table,
+    Rule,
+    Html,
+}
+
+/// What `block` appears as in the event stream, or `None` for images the
+/// parser lifts out of their enclosing block. `<details>` is masked into a
+/// paragraph by [`source_blocks`], as the parser does with a placeholder.
+fn source_kind(block: &Block) -> Option<SourceKind> {
+    Some(match block {
+        Block::Paragraph { .. } | Block::Details { .. } => SourceKind::Paragraph,
+        Block::Heading { .. } => SourceKind::Heading,
+        Block::Code { .. } => SourceKind::Code,
+        Block::List { .. } => SourceKind::List,
+        Block::Blockquote { .. } => SourceKind::Blockquote,
+        Block::Table { .. } => SourceKind::Table,
+        Block::HorizontalRule => SourceKind::Rule,
+        Block::Image { .. } => return None,
+    })
+}
+
+/// Kind and byte range of each top-level block in `source`, trailing
+/// whitespace excluded. A `<details>` element counts as one paragraph
+/// spanning it, opening to closing tag.
+pub(crate) fn source_blocks(source: &str) -> Vec<(SourceKind, Range<usize>)> {
+    use pulldown_cmark::{Event, Parser, Tag};
+
+    let details = details_ranges(source);
+    let masked = mask_details(source, &details);
+    let mut blocks = Vec::new();
+    let mut depth = 0usize;
+    for (event, range) in Parser::new_ext(&masked, parser_options()).into_offset_iter() {
+        let kind = match &event {
+            Event::Start(tag) => {
+                depth += 1;
+                if depth > 1 {
+                    continue;
+                }
+                match tag {
+                    Tag::Paragraph => SourceKind::Paragraph,
+                    Tag::Heading { .. } => SourceKind::Heading,
+                    Tag::CodeBlock(_) => SourceKind::Code,
+                    Tag::List(_) => SourceKind::List,
+                    Tag::BlockQuote(_) => SourceKind::Blockquote,
+                    Tag::Table(_) => SourceKind::Table,
+                    _ => SourceKind::Html,
+                }
+            }
+            Event::End(_) => {
+                depth -= 1;
+                continue;
+            }
+            Event::Rule if depth == 0 => SourceKind::Rule,
+            _ => continue,
+        };
+        let range = details
+            .iter()
+            .find(|d| d.start <= range.start && range.start < d.end)
+            .cloned()
+            .unwrap_or_else(|| trim_range(source, range));
+        blocks.push((kind, range));
+    }
+    blocks
+}
+
+/// Byte ranges of the `<details>…</details>` elements in `source`, found
+/// the way the parser finds them.
+fn details_ranges(source: &str) -> Vec<Range<usize>> {
+    let mut ranges = Vec::new();
+    let mut pos = 0;
+    while let Some(found) = source[pos..].find("<details") {
+        let open = pos + found;
+        let Some(tag_end) = source[open..].find('>') else {
+            break;
+        };
+        let Some(close) = source[open + tag_end..].find("</details>") else {
+            break;
+        };
+        pos = open + tag_end + close + "</details>".len();
+        ranges.push(open..pos);
+    }
+    ranges
+}
+
+/// `source` with each of `details` replaced by a line of filler the same
+/// length, so offsets are kept and each element parses as a paragraph.
+fn mask_details<'a>(source: &'a str, details: &[Range<usize>]) -> std::borrow::Cow<'a, str> {
+    if details.is_empty() {
+        return std::borrow::Cow::Borrowed(source);
+    }
+    let mut masked = String::with_capacity(source.len());
+    let mut pos = 0;
+    for range in details {
+        masked.push_str(&source[pos..range.start]);
+        masked.push('\n');
+        masked.push_str(&"x".repeat(range.len() - 2));
+        masked.push('\n');
+        pos = range.end;
+    }
+    masked.push_str(&source[pos..]);
+    std::borrow::Cow::Owned(masked)
+}
+
+/// The extensions turbovault-parser enables.
+fn parser_options() -> pulldown_cmark::Options {
+    use pulldown_cmark::Options;
+    Options::ENABLE_TABLES | Options::ENABLE_STRIKETHROUGH | Options::ENABLE_TASKLISTS
+}
+
+/// `range` with trailing whitespace dropped.
+fn trim_range(source: &str, range: Range<usize>) -> Range<usize> {
+    range.start..range.start + source[range].trim_end().len()
+}
+
+/// The fence character and run length if `line` opens a code fence.
+fn fence_marker(line: &str) -> Option<(char, usize)> {
+    let marker = line.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let len = line.chars().take_while(|&c| c == marker).count();
+    (len >= 3).then_some((marker, len))
+}
+
+fn is_atx_heading(line: &str) -> bool {
+    let hashes = line.bytes().take_while(|&b| b == b'#').count();
+    (1..=6).contains(&hashes)
+        && line[hashes..]
+            .chars()
+            .next()
+            .is_none_or(char::is_whitespace)
 }
 
 /// Generate URL-friendly slug from heading text.
@@ -46,10 +357,223 @@ pub fn slugify(text: &str) -> String {
     turbovault_parser::slugify(text)
 }
 
+/// The anchor GitHub gives a heading.
+///
+/// Lowercased, with punctuation and symbols (emoji included) removed and
+/// each space turned into a hyphen. Unlike [`slugify`], `_` is kept and runs
+/// of spaces or hyphens are not collapsed. Repeated headings are told apart
+/// by [`GithubSlugger`].
+///
+/// # Examples
+///
+/// ```
+/// use treemd::parser::content::github_slug;
+///
+/// assert_eq!(github_slug("What's new in v2.0?"), "whats-new-in-v20");
+/// assert_eq!(github_slug("snake_case  option"), "snake_case--option");
+/// ```
+pub fn github_slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            '-' | '_' => Some(c),
+            c if c.is_alphanumeric() => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// GitHub anchors for the headings of one document, in order.
+///
+/// A repeated anchor gets `-1`, `-2`, … appended, skipping suffixes another
+/// heading already uses, as GitHub does.
+#[derive(Debug, Default)]
+pub struct GithubSlugger {
+    occurrences: HashMap<String, usize>,
+}
+
+impl GithubSlugger {
+    /// Anchor for the next heading, `text`.
+    pub fn slug(&mut self, text: &str) -> String {
+        let base = github_slug(text);
+        let mut slug = base.clone();
+        while self.occurrences.contains_key(&slug) {
+            let count = self.occurrences.entry(base.clone()).or_default();
+            *count += 1;
+            slug = format!("{}-{}", base, count);
+        }
+        self.occurrences.insert(slug.clone(), 0);
+        slug
+    }
+}
+
+/// GitHub anchors for a document's headings, in order (see [`GithubSlugger`]).
+pub fn github_slugs<'a>(texts: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut slugger = GithubSlugger::default();
+    texts.into_iter().map(|text| slugger.slug(text)).collect()
+}
+
+/// Whether an anchor (with or without its leading `#`) refers to a heading.
+///
+/// Matches the heading's slug in either form ([`slugify`] or
+/// [`github_slug`]), or its text case-insensitively since wikilinks like
+/// `[[#Mixed Links Test]]` keep the original heading text. Numbered anchors
+/// of repeated headings need the whole document; see
+/// [`Document::find_anchor`](super::Document::find_anchor).
+pub fn anchor_matches(heading_text: &str, anchor: &str) -> bool {
+    let anchor = anchor.strip_prefix('#').unwrap_or(anchor);
+    let lower = anchor.to_lowercase();
+    slugify(heading_text) == lower
+        || github_slug(heading_text) == lower
+        || heading_text.eq_ignore_ascii_case(anchor)
+}
+
+/// Line number named by a `L<line>` anchor (`#L12`), as used for links to
+/// a position rather than a heading.
+pub fn line_anchor(anchor: &str) -> Option<usize> {
+    let anchor = anchor.strip_prefix('#').unwrap_or(anchor);
+    anchor
+        .strip_prefix('L')?
+        .parse()
+        .ok()
+        .filter(|&line| line > 0)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn github_slugs_match_github() {
+        assert_eq!(github_slug("Getting Started"), "getting-started");
+        assert_eq!(github_slug("API & CLI Reference"), "api--cli-reference");
+        assert_eq!(github_slug("🎉 Release notes 🎉"), "-release-notes-");
+        assert_eq!(github_slug("Use `--no-restore`"), "use---no-restore");
+        assert_eq!(github_slug("C++ / Rust_FFI"), "c--rust_ffi");
+        assert_eq!(github_slug("Über café"), "über-café");
+        assert_eq!(github_slug("1.2.3 — notes"), "123--notes");
+    }
+
+    #[test]
+    fn repeated_headings_are_numbered() {
+        assert_eq!(
+            github_slugs(["Setup", "Usage", "Setup", "setup", "Setup-1", "Setup"]),
+            [
+                "setup",
+                "usage",
+                "setup-1",
+                "setup-2",
+                "setup-1-1",
+                "setup-3"
+            ]
+        );
+        assert_eq!(
+            github_slugs(["Foo-1", "Foo", "Foo"]),
+            ["foo-1", "foo", "foo-2"]
+        );
+    }
+
+    #[test]
+    fn anchors_match_either_slug_form() {
+        assert!(anchor_matches("API & CLI Reference", "#api--cli-reference"));
+        assert!(anchor_matches("API & CLI Reference", "api-cli-reference"));
+        assert!(anchor_matches("Mixed Links Test", "Mixed Links Test"));
+        assert!(!anchor_matches("Setup", "setup-1"));
+    }
+
+    #[test]
+    fn line_anchors() {
+        assert_eq!(line_anchor("L12"), Some(12));
+        assert_eq!(line_anchor("#L3"), Some(3));
+        assert_eq!(line_anchor("L0"), None);
+        assert_eq!(line_anchor("l12"), None);
+        assert_eq!(line_anchor("Lead"), None);
+    }
+
+    #[test]
+    fn callouts_keep_their_source_lines() {
+        let md = "> [!WARNING]\n> Hot **stuff**\nlazy\n\n```\n> [!TIP] in code\n```\n\n> plain\n> quote\n";
+        let blocks = parse_content(md, 0);
+        let quotes: Vec<&str> = blocks
+            .iter()
+            .filter_map(|block| match block {
+                Block::Blockquote { content, .. } => Some(content.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(quotes.len(), 2);
+        assert_eq!(quotes[0], "[!WARNING]\nHot **stuff**\nlazy");
+        // Ordinary quotes keep the parser's text
+        assert!(!quotes[1].contains('>'));
+        assert!(quotes[1].starts_with("plain"));
+    }
+
+    #[test]
+    fn indented_callouts_keep_their_source_lines() {
+        let md = "  > [!NOTE]\n  > Indented\n\n> [!TIP]\n> Flush\n";
+        let quotes: Vec<String> = parse_content(md, 0)
+            .into_iter()
+            .filter_map(|block| match block {
+                Block::Blockquote { content, .. } => Some(content),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(quotes, ["[!NOTE]\nIndented", "[!TIP]\nFlush"]);
+    }
+
+    #[test]
+    fn definition_lists_keep_their_source_lines() {
+        let md = "# Terms\nApple\n: A *fruit*\n\n```\nCode\n: not a list\n```\n\nPlain\nprose\n";
+        let paragraphs: Vec<String> = parse_content(md, 0)
+            .into_iter()
+            .filter_map(|block| match block {
+                Block::Paragraph { content, .. } => Some(content),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(paragraphs, ["Apple\n: A *fruit*", "Plain prose"]);
+    }
+
+    #[test]
+    fn block_ranges_follow_the_parser() {
+        let md = "Intro\n> quote\n\n<details>\n<summary>More</summary>\n\nHidden\n</details>\n\n- ![i](x.png)\n- two\n";
+        let ranges: Vec<(&str, &str)> = parse_content_with_ranges(md, 0, md.len())
+            .iter()
+            .map(|(block, range)| {
+                let kind = match block {
+                    Block::Paragraph { .. } => "para",
+                    Block::Blockquote { .. } => "quote",
+                    Block::Details { .. } => "details",
+                    Block::Image { .. } => "image",
+                    Block::List { .. } => "list",
+                    _ => "other",
+                };
+                (kind, &md[range.clone().unwrap()])
+            })
+            .collect();
+        assert_eq!(
+            ranges,
+            [
+                ("para", "Intro"),
+                ("quote", "> quote"),
+                (
+                    "details",
+                    "<details>\n<summary>More</summary>\n\nHidden\n</details>"
+                ),
+                ("image", "- ![i](x.png)\n- two"),
+                ("list", "- ![i](x.png)\n- two"),
+            ]
+        );
+
+        let md = "- a\n\n  ```sh\n  ls\n  ```\n";
+        let code = code_block_ranges(md, 0, md.len());
+        assert_eq!(code.len(), 1);
+        assert_eq!(code[0].0, "ls");
+        assert_eq!(&md[code[0].1.clone()], "```sh\n  ls\n  ```");
+    }
+
     #[test]
     fn test_parse_paragraph() {
         let markdown = "This is a simple paragraph.";
@@ -0,0 +1,106 @@
+//! Definition lists (PHP Markdown Extra / Pandoc style).
+//!
+//! A term on its own line followed by one or more definitions, each starting
+//! with `: `. Indented lines continue the definition above them, and a term
+//! may have several definitions:
+//!
+//! ```text
+//! Apple
+//! : A fruit
+//! : A company
+//! ```
+
+/// A term with its definitions, as written (inline markdown intact).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DefinitionItem {
+    pub term: String,
+    pub definitions: Vec<String>,
+    /// Line of the term within the paragraph (0-based)
+    pub line: usize,
+}
+
+/// Split a paragraph's lines into definition list items, or `None` when they
+/// are not a definition list: every term needs at least one definition and
+/// the text must start with a term.
+pub fn split(content: &str) -> Option<Vec<DefinitionItem>> {
+    let mut items: Vec<DefinitionItem> = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            return None;
+        }
+        if let Some(definition) = definition_text(line) {
+            items.last_mut()?.definitions.push(definition.to_string());
+        } else if line.starts_with([' ', '\t'])
+            && let Some(last) = items.last_mut()?.definitions.last_mut()
+        {
+            last.push(' ');
+            last.push_str(line.trim());
+        } else {
+            if items.last().is_some_and(|item| item.definitions.is_empty()) {
+                return None;
+            }
+            items.push(DefinitionItem {
+                term: line.trim().to_string(),
+                definitions: Vec::new(),
+                line: index,
+            });
+        }
+    }
+    let complete = !items.is_empty() && items.iter().all(|item| !item.definitions.is_empty());
+    complete.then_some(items)
+}
+
+/// Lines a definition list takes up when rendered: one per term and one per
+/// definition.
+pub fn line_count(items: &[DefinitionItem]) -> usize {
+    items.iter().map(|item| 1 + item.definitions.len()).sum()
+}
+
+/// The text of a `: definition` line (indented by at most three spaces).
+fn definition_text(line: &str) -> Option<&str> {
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = line[indent..].strip_prefix(':')?;
+    rest.starts_with([' ', '\t'])
+        .then(|| rest.trim())
+        .filter(|text| !text.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_terms_and_definitions() {
+        let items =
+            split("Apple\n: A fruit\n: A company\nOrange *x*\n:   Citrus\n    and more").unwrap();
+        assert_eq!(
+            items,
+            [
+                DefinitionItem {
+                    term: "Apple".to_string(),
+                    definitions: vec!["A fruit".to_string(), "A company".to_string()],
+                    line: 0,
+                },
+                DefinitionItem {
+                    term: "Orange *x*".to_string(),
+                    definitions: vec!["Citrus and more".to_string()],
+                    line: 3,
+                },
+            ]
+        );
+        assert_eq!(line_count(&items), 5);
+    }
+
+    #[test]
+    fn rejects_text_that_is_not_a_definition_list() {
+        assert_eq!(split("Just a paragraph"), None);
+        assert_eq!(split(": no term"), None);
+        assert_eq!(split("Term\n:no space"), None);
+        assert_eq!(split("Term\n: def\nDangling term"), None);
+        assert_eq!(split("One\nTwo\n: def"), None);
+        assert_eq!(split("Emoji\n:smile:"), None);
+    }
+}
@@ -128,6 +128,54 @@ impl Document {
             .map(|i| &self.headings[i])
     }
 
+    /// Index of the heading an anchor refers to.
+    ///
+    /// GitHub's anchors come first, so `#setup-1` finds the second "Setup";
+    /// otherwise the first heading that
+    /// [`anchor_matches`](super::content::anchor_matches). A line anchor
+    /// such as `#L12` that matches no heading refers to the section
+    /// containing that line.
+    pub fn find_anchor(&self, anchor: &str) -> Option<usize> {
+        let fragment = anchor.strip_prefix('#').unwrap_or(anchor).to_lowercase();
+        self.github_anchors()
+            .iter()
+            .position(|slug| *slug == fragment)
+            .or_else(|| {
+                self.headings
+                    .iter()
+                    .position(|h| super::content::anchor_matches(&h.text, anchor))
+            })
+            .or_else(|| self.heading_at_line(super::content::line_anchor(anchor)?))
+    }
+
+    /// GitHub's anchor for each heading, repeated headings numbered.
+    pub fn github_anchors(&self) -> Vec<String> {
+        super::content::github_slugs(self.headings.iter().map(|h| h.text.as_str()))
+    }
+
+    /// GitHub's anchors for the headings starting within the byte range
+    /// `range`, numbered as in the whole document.
+    pub fn github_anchors_in(&self, range: std::ops::Range<usize>) -> Vec<String> {
+        self.github_anchors()
+            .into_iter()
+            .zip(&self.headings)
+            .filter(|(_, h)| range.contains(&h.offset))
+            .map(|(anchor, _)| anchor)
+            .collect()
+    }
+
+    /// Index of the last heading starting at or before 1-based `line`.
+    pub fn heading_at_line(&self, line: usize) -> Option<usize> {
+        self.headings
+            .iter()
+            .rposition(|h| self.line_at(h.offset) <= line)
+    }
+
+    /// 1-based line number of a byte offset into the content.
+    pub fn line_at(&self, offset: usize) -> usize {
+        super::utils::line_at(&self.content, offset)
+    }
+
     /// Get all headings matching a filter
     pub fn filter_headings(&self, filter: &str) -> Vec<&Heading> {
         let search = filter.to_lowercase();
@@ -296,6 +344,37 @@ mod tests {
         Document::new(content.to_string(), headings)
     }
 
+    // ---------- find_anchor ----------
+
+    #[test]
+    fn find_anchor_by_slug_or_text() {
+        let content = "# Intro\n\n## Getting Started\nbody\n";
+        let d = doc(content, vec![h(1, "Intro", 0), h(2, "Getting Started", 9)]);
+        assert_eq!(d.find_anchor("getting-started"), Some(1));
+        assert_eq!(d.find_anchor("#getting-started"), Some(1));
+        assert_eq!(d.find_anchor("Getting Started"), Some(1));
+        assert_eq!(d.find_anchor("missing"), None);
+        assert_eq!(d.line_at(d.headings[1].offset), 3);
+        // Line anchors resolve to the enclosing section
+        assert_eq!(d.find_anchor("#L2"), Some(0));
+        assert_eq!(d.find_anchor("L4"), Some(1));
+    }
+
+    #[test]
+    fn find_anchor_follows_github_numbering() {
+        let d = crate::parser::parse_markdown(
+            "# Setup\n## Use `cargo` 🚀\n# Setup\n## Use `cargo` 🚀\n",
+        );
+        assert_eq!(
+            d.github_anchors(),
+            ["setup", "use-cargo-", "setup-1", "use-cargo--1"]
+        );
+        assert_eq!(d.find_anchor("#setup-1"), Some(2));
+        assert_eq!(d.find_anchor("use-cargo--1"), Some(3));
+        assert_eq!(d.find_anchor("Use-Cargo-"), Some(1));
+        assert_eq!(d.find_anchor("use-cargo"), Some(1));
+    }
+
     // ---------- build_tree ----------
 
     #[test]
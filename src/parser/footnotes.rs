@@ -0,0 +1,342 @@
+//! Footnotes: `[^label]` references and `[^label]: text` definitions.
+//!
+//! A definition starts at the beginning of a line (outside fenced code) and
+//! continues over the indented lines that follow it. Notes are numbered in
+//! definition order. For display, [`Footnotes::render`] turns references into
+//! superscript links to `#fn-label` and lists the notes a section cites at its
+//! end, each with a `↩` link back to `#fnref-label`.
+
+/// A footnote definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Footnote {
+    pub label: String,
+    /// Definition text, continuation lines joined with spaces
+    pub text: String,
+    /// Source line of the definition (1-indexed)
+    pub line: usize,
+}
+
+/// Footnotes defined in a document, in definition order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Footnotes {
+    notes: Vec<Footnote>,
+}
+
+/// Anchor of the note list entry for `label`.
+pub fn definition_anchor(label: &str) -> String {
+    format!("fn-{}", label)
+}
+
+/// Anchor of the first reference to `label`.
+pub fn reference_anchor(label: &str) -> String {
+    format!("fnref-{}", label)
+}
+
+/// The anchor a footnote link leads to: a reference (`fn-x`) leads to its
+/// definition's back link (`fnref-x`) and the back link to the reference.
+/// `None` for anchors that are not footnote links.
+pub fn counterpart(anchor: &str) -> Option<String> {
+    if let Some(label) = anchor.strip_prefix("fnref-") {
+        Some(definition_anchor(label))
+    } else {
+        anchor.strip_prefix("fn-").map(reference_anchor)
+    }
+}
+
+impl Footnotes {
+    /// Collect the footnote definitions in `content`.
+    ///
+    /// Only the first definition of a label counts.
+    pub fn parse(content: &str) -> Self {
+        let mut notes: Vec<Footnote> = Vec::new();
+        for definition in definitions(content) {
+            if notes.iter().all(|n| n.label != definition.label) {
+                notes.push(definition);
+            }
+        }
+        Self { notes }
+    }
+
+    /// Whether no footnotes are defined.
+    pub fn is_empty(&self) -> bool {
+        self.notes.is_empty()
+    }
+
+    /// All footnotes, in definition order.
+    pub fn iter(&self) -> impl Iterator<Item = &Footnote> {
+        self.notes.iter()
+    }
+
+    /// The footnote for `label`, if defined.
+    pub fn get(&self, label: &str) -> Option<&Footnote> {
+        self.notes.iter().find(|n| n.label == label)
+    }
+
+    /// Number of the footnote for `label` (1-based, in definition order).
+    pub fn number(&self, label: &str) -> Option<usize> {
+        self.notes
+            .iter()
+            .position(|n| n.label == label)
+            .map(|i| i + 1)
+    }
+
+    /// `content` ready for display: definitions removed, references to
+    /// defined notes replaced by superscript links, and the cited notes
+    /// listed after a rule at the end. References inside code are left alone.
+    pub fn render(&self, content: &str) -> String {
+        let mut cited: Vec<&Footnote> = Vec::new();
+        let mut result = String::with_capacity(content.len());
+        let mut in_fence = false;
+        for line in strip_definitions(content).split_inclusive('\n') {
+            if is_fence(line) {
+                in_fence = !in_fence;
+            }
+            if in_fence {
+                result.push_str(line);
+                continue;
+            }
+            result.push_str(&self.rewrite_references(line, &mut cited));
+        }
+        if cited.is_empty() {
+            return result;
+        }
+
+        if !result.ends_with('\n') {
+            result.push('\n');
+        }
+        result.push_str("\n---\n\n");
+        for note in cited {
+            let number = self.number(&note.label).unwrap_or_default();
+            result.push_str(&format!(
+                "- {} {} [↩](#{})\n",
+                superscript(number),
+                note.text,
+                reference_anchor(&note.label)
+            ));
+        }
+        result
+    }
+
+    /// Replace `[^label]` references in one line, noting each cited note.
+    fn rewrite_references<'a>(&'a self, line: &str, cited: &mut Vec<&'a Footnote>) -> String {
+        let mut out = String::with_capacity(line.len());
+        let mut rest = line;
+        let mut in_code = false;
+        while let Some(pos) = rest.find(['`', '[']) {
+            out.push_str(&rest[..pos]);
+            rest = &rest[pos..];
+            if rest.starts_with('`') {
+                in_code = !in_code;
+                out.push('`');
+                rest = &rest[1..];
+                continue;
+            }
+            let note = (!in_code)
+                .then(|| reference_label(rest))
+                .flatten()
+                .and_then(|label| self.get(label));
+            match note {
+                Some(note) => {
+                    let number = self.number(&note.label).unwrap_or_default();
+                    out.push_str(&format!(
+                        "[{}](#{})",
+                        superscript(number),
+                        definition_anchor(&note.label)
+                    ));
+                    rest = &rest[note.label.len() + 3..];
+                    if !cited.iter().any(|n| n.label == note.label) {
+                        cited.push(note);
+                    }
+                }
+                None => {
+                    out.push('[');
+                    rest = &rest[1..];
+                }
+            }
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Remove footnote definitions (with their continuation lines) so they are
+/// not rendered as text.
+pub fn strip_definitions(content: &str) -> String {
+    let mut in_fence = false;
+    let mut in_definition = false;
+    let mut result = String::with_capacity(content.len());
+    for line in content.split_inclusive('\n') {
+        if is_fence(line) {
+            in_fence = !in_fence;
+        }
+        if in_fence {
+            in_definition = false;
+        } else if parse_definition(line).is_some() {
+            in_definition = true;
+            continue;
+        } else if in_definition && is_continuation(line) {
+            continue;
+        } else {
+            in_definition = false;
+        }
+        result.push_str(line);
+    }
+    result
+}
+
+/// Definitions in `content`, in order.
+fn definitions(content: &str) -> Vec<Footnote> {
+    let mut found: Vec<Footnote> = Vec::new();
+    let mut in_fence = false;
+    let mut in_definition = false;
+    for (i, line) in content.lines().enumerate() {
+        if is_fence(line) {
+            in_fence = !in_fence;
+            in_definition = false;
+            continue;
+        }
+        if in_fence {
+            continue;
+        }
+        if let Some((label, text)) = parse_definition(line) {
+            found.push(Footnote {
+                label: label.to_string(),
+                text: text.to_string(),
+                line: i + 1,
+            });
+            in_definition = true;
+        } else if in_definition
+            && is_continuation(line)
+            && let Some(note) = found.last_mut()
+        {
+            let more = line.trim();
+            if !more.is_empty() {
+                if !note.text.is_empty() {
+                    note.text.push(' ');
+                }
+                note.text.push_str(more);
+            }
+        } else {
+            in_definition = false;
+        }
+    }
+    found
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// An indented line continuing a definition.
+fn is_continuation(line: &str) -> bool {
+    let line = line.trim_end_matches(['\n', '\r']);
+    line.starts_with("    ") || line.starts_with('\t')
+}
+
+/// Parse `[^label]: text` (indented by at most three spaces).
+fn parse_definition(line: &str) -> Option<(&str, &str)> {
+    let line = line.trim_end_matches(['\n', '\r']);
+    let indent = line.len() - line.trim_start_matches(' ').len();
+    if indent > 3 {
+        return None;
+    }
+    let rest = &line[indent..];
+    let label = reference_label(rest)?;
+    let text = rest[label.len() + 3..].strip_prefix(':')?;
+    Some((label, text.trim()))
+}
+
+/// The label of a `[^label]` at the start of `text`.
+fn reference_label(text: &str) -> Option<&str> {
+    let rest = text.strip_prefix("[^")?;
+    let end = rest.find(']')?;
+    let label = &rest[..end];
+    let valid = !label.is_empty() && !label.contains(|c: char| c.is_whitespace() || c == '[');
+    valid.then_some(label)
+}
+
+/// `n` written with superscript digits.
+pub fn superscript(n: usize) -> String {
+    const DIGITS: [char; 10] = ['⁰', '¹', '²', '³', '⁴', '⁵', '⁶', '⁷', '⁸', '⁹'];
+    n.to_string()
+        .chars()
+        .filter_map(|c| c.to_digit(10))
+        .map(|d| DIGITS[d as usize])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "\
+# Notes
+
+Plain claim[^1] and a longer one[^long]. Unknown [^nope] stays.
+
+```
+code [^1] stays
+```
+
+Inline `[^1]` stays too.
+
+[^1]: First note.
+[^long]: Second note
+    continued here.
+";
+
+    #[test]
+    fn parses_definitions_with_continuation_lines() {
+        let notes = Footnotes::parse(DOC);
+        let labels: Vec<&str> = notes.iter().map(|n| n.label.as_str()).collect();
+        assert_eq!(labels, ["1", "long"]);
+        assert_eq!(notes.get("1").unwrap().text, "First note.");
+        assert_eq!(notes.get("1").unwrap().line, 11);
+        assert_eq!(
+            notes.get("long").unwrap().text,
+            "Second note continued here."
+        );
+        assert_eq!(notes.number("long"), Some(2));
+        assert_eq!(notes.get("nope"), None);
+    }
+
+    #[test]
+    fn ignores_definitions_in_code() {
+        let notes = Footnotes::parse("```\n[^x]: not a note\n```\n");
+        assert!(notes.is_empty());
+    }
+
+    #[test]
+    fn renders_references_as_superscript_links() {
+        let rendered = Footnotes::parse(DOC).render(DOC);
+        assert!(rendered.contains("Plain claim[¹](#fn-1) and a longer one[²](#fn-long)."));
+        assert!(rendered.contains("Unknown [^nope] stays."));
+        assert!(rendered.contains("code [^1] stays"));
+        assert!(rendered.contains("Inline `[^1]` stays too."));
+        assert!(!rendered.contains("[^1]: First note."));
+        assert!(!rendered.contains("continued here.\n\n---"));
+        assert!(rendered.ends_with(
+            "\n---\n\n\
+             - ¹ First note. [↩](#fnref-1)\n\
+             - ² Second note continued here. [↩](#fnref-long)\n"
+        ));
+    }
+
+    #[test]
+    fn lists_only_notes_cited_in_the_content() {
+        let notes = Footnotes::parse(DOC);
+        assert_eq!(notes.render("No citations.\n"), "No citations.\n");
+        let rendered = notes.render("Just the second[^long], twice[^long].");
+        assert!(rendered.ends_with("\n---\n\n- ² Second note continued here. [↩](#fnref-long)\n"));
+        assert!(!rendered.contains("First note"));
+    }
+
+    #[test]
+    fn counterpart_links_reference_and_definition() {
+        assert_eq!(counterpart("fn-1").as_deref(), Some("fnref-1"));
+        assert_eq!(counterpart("fnref-long").as_deref(), Some("fn-long"));
+        assert_eq!(counterpart("features"), None);
+        assert_eq!(superscript(12), "¹²");
+    }
+}
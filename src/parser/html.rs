@@ -0,0 +1,441 @@
+//! Standalone HTML export.
+//!
+//! Renders the parsed blocks of a document as semantic HTML with a small
+//! embedded stylesheet. Headings get `id` attributes with GitHub's anchors,
+//! as `CopyAnchor` copies them, so `#anchor` links keep working in the
+//! export. Repeated headings are numbered as in the whole document, so an
+//! exported section keeps the ids its headings have in the full page.
+
+use super::content::{GithubSlugger, parse_content};
+use super::output::{Alignment, Block, InlineElement, ListItem};
+use super::utils::frontmatter_end;
+
+/// Colors for the embedded stylesheet, as CSS color values.
+///
+/// The TUI builds one from the active theme; the default is a neutral light
+/// palette.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HtmlStyle {
+    pub background: String,
+    pub foreground: String,
+    /// Colors for `h1` to `h5`; `h6` uses the last one
+    pub headings: [String; 5],
+    pub link: String,
+    pub code_fg: String,
+    pub code_bg: String,
+    pub blockquote_border: String,
+    pub blockquote_fg: String,
+    pub table_border: String,
+}
+
+impl Default for HtmlStyle {
+    fn default() -> Self {
+        Self {
+            background: "#ffffff".to_string(),
+            foreground: "#24292f".to_string(),
+            headings: ["#0550ae", "#0a3069", "#116329", "#953800", "#57606a"].map(String::from),
+            link: "#0969da".to_string(),
+            code_fg: "#953800".to_string(),
+            code_bg: "#f6f8fa".to_string(),
+            blockquote_border: "#d0d7de".to_string(),
+            blockquote_fg: "#57606a".to_string(),
+            table_border: "#d0d7de".to_string(),
+        }
+    }
+}
+
+/// Convert markdown to a standalone HTML page titled `title`.
+///
+/// `anchors` are the ids of the headings in `markdown`, in order: the
+/// matching part of [`Document::github_anchors`](super::Document::github_anchors).
+/// Headings past the end of it get GitHub anchors numbered within the page.
+/// Front matter is dropped. The page embeds its stylesheet, so it needs no
+/// other files.
+pub fn to_html(markdown: &str, title: &str, style: &HtmlStyle, anchors: &[String]) -> String {
+    let body = &markdown[frontmatter_end(markdown)..];
+
+    let mut ids = HeadingIds {
+        anchors: anchors.iter(),
+        slugger: GithubSlugger::default(),
+    };
+    let mut out = String::new();
+    for block in parse_content(body, 0) {
+        push_block(&block, &mut ids, &mut out);
+    }
+
+    format!(
+        "<!DOCTYPE html>\n\
+         <html lang=\"en\">\n\
+         <head>\n\
+         <meta charset=\"utf-8\">\n\
+         <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+         <title>{}</title>\n\
+         <style>\n{}</style>\n\
+         </head>\n\
+         <body>\n\
+         <main>\n{}</main>\n\
+         </body>\n\
+         </html>\n",
+        escape(title),
+        stylesheet(style),
+        out
+    )
+}
+
+fn stylesheet(style: &HtmlStyle) -> String {
+    let mut css = format!(
+        "body {{ margin: 0; background: {bg}; color: {fg}; font: 16px/1.6 system-ui, sans-serif; }}\n\
+         main {{ max-width: 48rem; margin: 0 auto; padding: 2rem 1rem; }}\n\
+         a {{ color: {link}; }}\n\
+         code {{ color: {code_fg}; background: {code_bg}; padding: 0.1em 0.3em; border-radius: 3px; font-family: ui-monospace, monospace; }}\n\
+         pre {{ background: {code_bg}; padding: 0.8rem 1rem; border-radius: 4px; overflow-x: auto; }}\n\
+         pre code {{ padding: 0; color: {fg}; }}\n\
+         blockquote {{ margin: 0; padding-left: 1rem; border-left: 3px solid {bq_border}; color: {bq_fg}; }}\n\
+         table {{ border-collapse: collapse; }}\n\
+         th, td {{ border: 1px solid {table_border}; padding: 0.3rem 0.6rem; }}\n\
+         hr {{ border: none; border-top: 1px solid {table_border}; }}\n\
+         img {{ max-width: 100%; }}\n",
+        bg = style.background,
+        fg = style.foreground,
+        link = style.link,
+        code_fg = style.code_fg,
+        code_bg = style.code_bg,
+        bq_border = style.blockquote_border,
+        bq_fg = style.blockquote_fg,
+        table_border = style.table_border,
+    );
+    for level in 1..=6 {
+        css.push_str(&format!(
+            "h{} {{ color: {}; }}\n",
+            level,
+            style.headings[level.min(5) - 1]
+        ));
+    }
+    css
+}
+
+/// Ids for the headings of a page, handed out in document order.
+struct HeadingIds<'a> {
+    anchors: std::slice::Iter<'a, String>,
+    /// Numbers the headings the given anchors don't cover
+    slugger: GithubSlugger,
+}
+
+impl HeadingIds<'_> {
+    fn next(&mut self, text: &str) -> String {
+        match self.anchors.next() {
+            Some(anchor) => anchor.clone(),
+            None => self.slugger.slug(text),
+        }
+    }
+}
+
+fn push_block(block: &Block, ids: &mut HeadingIds, out: &mut String) {
+    match block {
+        Block::Heading { level, inline, .. } => {
+            let level = (*level).clamp(1, 6);
+            let text: String = inline.iter().map(InlineElement::to_plain_text).collect();
+            out.push_str(&format!(
+                "<h{} id=\"{}\">{}</h{}>\n",
+                level,
+                escape(&ids.next(&text)),
+                inline_html(inline),
+                level
+            ));
+        }
+        Block::Paragraph { content, inline } => {
+            let html = if inline.is_empty() {
+                escape(content)
+            } else {
+                inline_html(inline)
+            };
+            out.push_str(&format!("<p>{}</p>\n", html));
+        }
+        Block::List { ordered, items } => push_list(*ordered, items, ids, out),
+        Block::Blockquote { content, blocks } => {
+            out.push_str("<blockquote>\n");
+            if blocks.is_empty() {
+                out.push_str(&format!("<p>{}</p>\n", escape(content)));
+            }
+            for block in blocks {
+                push_block(block, ids, out);
+            }
+            out.push_str("</blockquote>\n");
+        }
+        Block::Code {
+            language, content, ..
+        } => {
+            let class = language
+                .as_deref()
+                .filter(|l| !l.is_empty())
+                .map(|l| format!(" class=\"language-{}\"", escape(l)))
+                .unwrap_or_default();
+            let mut code = escape(content);
+            if !code.ends_with('\n') {
+                code.push('\n');
+            }
+            out.push_str(&format!("<pre><code{}>{}</code></pre>\n", class, code));
+        }
+        Block::Table {
+            headers,
+            alignments,
+            rows,
+        } => {
+            out.push_str("<table>\n<thead>\n");
+            push_row("th", headers, alignments, out);
+            out.push_str("</thead>\n<tbody>\n");
+            for row in rows {
+                push_row("td", row, alignments, out);
+            }
+            out.push_str("</tbody>\n</table>\n");
+        }
+        Block::Image { alt, src, title } => {
+            out.push_str(&format!("<p>{}</p>\n", image_html(alt, src, title)));
+        }
+        Block::HorizontalRule => out.push_str("<hr>\n"),
+        Block::Details {
+            summary,
+            content,
+            blocks,
+        } => {
+            out.push_str(&format!(
+                "<details>\n<summary>{}</summary>\n",
+                escape(summary.trim())
+            ));
+            if blocks.is_empty() && !content.trim().is_empty() {
+                out.push_str(&format!("<p>{}</p>\n", escape(content.trim())));
+            }
+            for block in blocks {
+                push_block(block, ids, out);
+            }
+            out.push_str("</details>\n");
+        }
+    }
+}
+
+fn push_list(ordered: bool, items: &[ListItem], ids: &mut HeadingIds, out: &mut String) {
+    let tag = if ordered { "ol" } else { "ul" };
+    out.push_str(&format!("<{}>\n", tag));
+    for item in items {
+        out.push_str("<li>");
+        match item.checked {
+            Some(true) => out.push_str("<input type=\"checkbox\" checked disabled> "),
+            Some(false) => out.push_str("<input type=\"checkbox\" disabled> "),
+            None => {}
+        }
+        if item.inline.is_empty() {
+            out.push_str(&escape(&item.content));
+        } else {
+            out.push_str(&inline_html(&item.inline));
+        }
+        if !item.blocks.is_empty() {
+            out.push('\n');
+            for block in &item.blocks {
+                push_block(block, ids, out);
+            }
+        }
+        out.push_str("</li>\n");
+    }
+    out.push_str(&format!("</{}>\n", tag));
+}
+
+fn push_row(tag: &str, cells: &[String], alignments: &[Alignment], out: &mut String) {
+    out.push_str("<tr>");
+    for (i, cell) in cells.iter().enumerate() {
+        let align = match alignments.get(i) {
+            Some(Alignment::Left) => " style=\"text-align: left\"",
+            Some(Alignment::Center) => " style=\"text-align: center\"",
+            Some(Alignment::Right) => " style=\"text-align: right\"",
+            _ => "",
+        };
+        out.push_str(&format!("<{}{}>{}</{}>", tag, align, cell_html(cell), tag));
+    }
+    out.push_str("</tr>\n");
+}
+
+/// Table cells are stored as text; parse them again so links survive.
+fn cell_html(cell: &str) -> String {
+    match parse_content(cell.trim(), 0).first() {
+        Some(Block::Paragraph { inline, .. }) if !inline.is_empty() => inline_html(inline),
+        _ => escape(cell.trim()),
+    }
+}
+
+fn inline_html(inline: &[InlineElement]) -> String {
+    inline
+        .iter()
+        .map(|element| match element {
+            InlineElement::Text { value } => escape(value),
+            InlineElement::Strong { value } => format!("<strong>{}</strong>", escape(value)),
+            InlineElement::Emphasis { value } => format!("<em>{}</em>", escape(value)),
+            InlineElement::Code { value } => format!("<code>{}</code>", escape(value)),
+            InlineElement::Strikethrough { value } => format!("<del>{}</del>", escape(value)),
+            InlineElement::Link {
+                text, url, title, ..
+            } => format!(
+                "<a href=\"{}\"{}>{}</a>",
+                escape(safe_url(url)),
+                title_attr(title),
+                escape(text)
+            ),
+            InlineElement::Image {
+                alt, src, title, ..
+            } => image_html(alt, src, title),
+        })
+        .collect()
+}
+
+fn image_html(alt: &str, src: &str, title: &Option<String>) -> String {
+    format!(
+        "<img src=\"{}\" alt=\"{}\"{}>",
+        escape(safe_url(src)),
+        escape(alt),
+        title_attr(title)
+    )
+}
+
+/// `url`, or `#` when opening it would run script: `javascript:` and
+/// `vbscript:` URLs, and `data:` URLs other than raster images. Browsers
+/// ignore case, whitespace and control characters in the scheme, so the
+/// check does too.
+fn safe_url(url: &str) -> &str {
+    let scheme: String = url
+        .chars()
+        .filter(|c| !c.is_ascii_whitespace() && !c.is_control())
+        .take(16)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    let runs_script = scheme.starts_with("javascript:")
+        || scheme.starts_with("vbscript:")
+        || (scheme.starts_with("data:")
+            && (!scheme.starts_with("data:image/") || scheme.starts_with("data:image/svg")));
+    if runs_script { "#" } else { url }
+}
+
+fn title_attr(title: &Option<String>) -> String {
+    title
+        .as_deref()
+        .filter(|t| !t.is_empty())
+        .map(|t| format!(" title=\"{}\"", escape(t)))
+        .unwrap_or_default()
+}
+
+/// Escape text for use in element content and quoted attributes.
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&#39;"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn body(markdown: &str) -> String {
+        let html = to_html(markdown, "Test", &HtmlStyle::default(), &[]);
+        let start = html.find("<main>\n").unwrap() + "<main>\n".len();
+        let end = html.find("</main>").unwrap();
+        html[start..end].to_string()
+    }
+
+    #[test]
+    fn headings_carry_copy_anchor_slugs() {
+        let out = body("# Getting Started\n\n## API & CLI Reference\n");
+        assert!(out.contains("<h1 id=\"getting-started\">Getting Started</h1>"));
+        assert!(out.contains("<h2 id=\"api--cli-reference\">API &amp; CLI Reference</h2>"));
+    }
+
+    #[test]
+    fn repeated_headings_get_the_documents_numbered_anchors() {
+        let markdown = "# Guide\n## Setup\n# Reference\n## Setup\n";
+        let out = body(markdown);
+        assert!(out.contains("<h2 id=\"setup\">Setup</h2>"));
+        assert!(out.contains("<h2 id=\"setup-1\">Setup</h2>"));
+
+        // A section keeps the ids its headings have in the whole document
+        let doc = crate::parser::parse_markdown(markdown);
+        let start = doc.headings[2].offset;
+        let section = &markdown[start..];
+        let html = to_html(
+            section,
+            "Reference",
+            &HtmlStyle::default(),
+            &doc.github_anchors_in(start..markdown.len()),
+        );
+        assert!(html.contains("<h1 id=\"reference\">Reference</h1>"));
+        assert!(html.contains("<h2 id=\"setup-1\">Setup</h2>"));
+    }
+
+    #[test]
+    fn lists_nest_and_show_task_state() {
+        let out = body("- [x] Ship it\n- [ ] Test it\n\n1. One\n2. Two\n");
+        assert!(out.contains("<ul>\n<li><input type=\"checkbox\" checked disabled> Ship it</li>"));
+        assert!(out.contains("<li><input type=\"checkbox\" disabled> Test it</li>"));
+        assert!(out.contains("<ol>\n<li>One</li>\n<li>Two</li>\n</ol>"));
+    }
+
+    #[test]
+    fn code_blocks_are_escaped_with_a_language_class() {
+        let out = body("```rust\nfn main() { let x = a < b; }\n```\n\n```\nplain\n```\n");
+        assert!(out.contains(
+            "<pre><code class=\"language-rust\">fn main() { let x = a &lt; b; }\n</code></pre>"
+        ));
+        assert!(out.contains("<pre><code>plain\n</code></pre>"));
+    }
+
+    #[test]
+    fn tables_keep_alignment_and_links() {
+        let out = body("| Name | Docs |\n|:---|---:|\n| Ada | [site](https://ada.dev) |\n");
+        assert!(out.contains(
+            "<thead>\n<tr><th style=\"text-align: left\">Name</th><th style=\"text-align: right\">Docs</th></tr>\n</thead>"
+        ));
+        assert!(out.contains(
+            "<td style=\"text-align: left\">Ada</td><td style=\"text-align: right\"><a href=\"https://ada.dev\">site</a></td>"
+        ));
+    }
+
+    #[test]
+    fn links_and_inline_markup_are_rendered() {
+        let out = body("See the [guide](docs/guide.md) and [pipes](#pipes), **now** `x<y`.\n");
+        assert!(out.contains("<a href=\"docs/guide.md\">guide</a>"));
+        assert!(out.contains("<strong>now</strong>"));
+        assert!(out.contains("<a href=\"#pipes\">pipes</a>"));
+        assert!(out.contains("<code>x&lt;y</code>"));
+    }
+
+    #[test]
+    fn script_urls_are_dropped() {
+        let out = body(
+            "[a](javascript:alert(1)) [b](JavaScript%3A) [c](<java\tscript:x>) \
+             [d](data:text/html,x) ![e](data:image/png;base64,AA) ![f](vbscript:x)\n",
+        );
+        assert!(out.contains("<a href=\"#\">a</a>"));
+        assert!(out.contains("<a href=\"JavaScript%3A\">b</a>"));
+        assert!(!out.to_lowercase().contains("script:"), "{out}");
+        assert!(out.contains("<a href=\"#\">d</a>"));
+        assert!(out.contains("<img src=\"data:image/png;base64,AA\""));
+        assert!(out.contains("<img src=\"#\" alt=\"f\">"));
+    }
+
+    #[test]
+    fn page_embeds_theme_colors_and_escapes_the_title() {
+        let style = HtmlStyle {
+            background: "#2b303b".to_string(),
+            ..HtmlStyle::default()
+        };
+        let html = to_html("---\ntitle: x\n---\n# Doc\n", "Q&A", &style, &[]);
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<title>Q&amp;A</title>"));
+        assert!(html.contains("background: #2b303b"));
+        assert!(!html.contains("title: x"), "front matter is dropped");
+    }
+}
@@ -6,18 +6,35 @@
 //! All parsing is delegated to `turbovault-parser` for unified, code-block-aware
 //! OFM (Obsidian Flavored Markdown) support.
 
+pub mod abbreviations;
 pub mod builder;
+pub mod callout;
 pub mod content;
+pub mod definition_list;
 mod document;
+pub mod footnotes;
+pub mod html;
 pub mod links;
 pub mod output;
+pub mod plain;
+pub mod toc;
+pub mod tree;
+pub mod tts;
 pub mod utils;
+pub mod validate;
 
+pub use abbreviations::Abbreviations;
 pub use builder::build_json_output;
 pub use document::{Document, Heading, HeadingNode};
+pub use footnotes::Footnotes;
+pub use html::{HtmlStyle, to_html};
 pub use links::{Link, LinkTarget, extract_links};
 pub use output::{Block, DocumentOutput, InlineElement, Section};
-pub use utils::{parse_inline_html, strip_markdown_inline};
+pub use plain::{TextStats, to_plain_text};
+pub use toc::{TocOptions, to_toc};
+pub use tree::{DocumentTree, build_document_tree};
+pub use tts::{ReadAloudOptions, to_read_aloud};
+pub use utils::{frontmatter_block, parse_inline_html, strip_markdown_inline};
 
 use std::path::Path;
 
@@ -36,7 +53,7 @@ use std::path::Path;
 /// Returns an error if the file cannot be read.
 pub fn parse_file(path: &Path) -> std::io::Result<Document> {
     let content = std::fs::read_to_string(path)?;
-    Ok(parse_markdown(&content))
+    Ok(parse_markdown(&crate::input::normalize(&content)))
 }
 
 /// Parse markdown content and extract headings with byte offsets.
@@ -0,0 +1,443 @@
+//! Plain-text export, for pasting where markup would show literally (chat,
+//! email, issue comments).
+//!
+//! Inline markup is dropped and links keep only their text. Lists keep their
+//! bullets, numbers and nesting, code blocks keep their lines verbatim and
+//! tables are laid out in aligned columns. Blocks are separated by a blank
+//! line.
+//!
+//! [`TextStats`] counts words over the same text, so counts match what a
+//! plain-text copy would contain.
+
+use super::content::{SourceKind, parse_content, source_blocks, unquote};
+use super::output::{Block, InlineElement, ListItem};
+use super::utils::frontmatter_end;
+use unicode_width::UnicodeWidthStr;
+
+/// Convert markdown to plain text.
+///
+/// Front matter is dropped. The result ends with a newline unless empty.
+///
+/// ```
+/// use treemd::parser::to_plain_text;
+///
+/// let text = to_plain_text("# Setup\n\nRun **this** [script](run.sh).\n");
+/// assert_eq!(text, "Setup\n\nRun this script.\n");
+/// ```
+pub fn to_plain_text(markdown: &str) -> String {
+    plain_text(markdown, true)
+}
+
+/// Word and character counts for a stretch of markdown, and the time it
+/// takes to read.
+///
+/// ```
+/// use treemd::parser::TextStats;
+///
+/// let stats = TextStats::of("# Setup\n\nRun **this** script.\n\n```sh\nmake all\n```\n", false);
+/// assert_eq!(stats.words, 4);
+/// assert_eq!(stats.reading_minutes(200), 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TextStats {
+    /// Whitespace-separated words; list bullets, checkboxes and table rules
+    /// don't count
+    pub words: usize,
+    /// Characters of the plain text, spaces included and line breaks not
+    pub chars: usize,
+}
+
+impl TextStats {
+    /// Count `markdown`'s plain text. Code blocks count only with
+    /// `include_code`; inline code is always part of the prose.
+    pub fn of(markdown: &str, include_code: bool) -> Self {
+        let text = plain_text(markdown, include_code);
+        Self {
+            words: text
+                .split_whitespace()
+                .filter(|token| is_word(token))
+                .count(),
+            chars: text.chars().filter(|&c| c != '\n').count(),
+        }
+    }
+
+    /// Minutes to read the words at `wpm` words a minute, rounded up; 0 for
+    /// no words.
+    pub fn reading_minutes(&self, wpm: usize) -> usize {
+        self.words.div_ceil(wpm.max(1))
+    }
+}
+
+/// Whether a token of plain text is a word rather than a list marker.
+fn is_word(token: &str) -> bool {
+    token != "[x]" && token.chars().any(char::is_alphanumeric)
+}
+
+fn plain_text(markdown: &str, include_code: bool) -> String {
+    let text = source_text(&markdown[frontmatter_end(markdown)..], include_code);
+    if text.is_empty() { text } else { text + "\n" }
+}
+
+/// Plain text of each top-level block of `source`, separated by blank lines.
+///
+/// Blocks are cut out of the source and converted one at a time. The
+/// parsed items lose the markers of nested lists, so lists are written from
+/// their source lines; the parser also runs a quote's lines together, so
+/// quotes are converted from their source with the `>` markers removed.
+fn source_text(source: &str, include_code: bool) -> String {
+    source_blocks(source)
+        .into_iter()
+        .map(|(kind, range)| {
+            let block = &source[range];
+            match kind {
+                SourceKind::Code if !include_code => String::new(),
+                SourceKind::List => list_source_text(block, include_code),
+                SourceKind::Blockquote => indent(&source_text(&unquote(block), include_code), "  "),
+                _ => blocks_text(&parse_content(block, 0)),
+            }
+        })
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+fn block_text(block: &Block) -> String {
+    match block {
+        Block::Heading { inline, .. } | Block::Paragraph { inline, .. } => inline_text(inline),
+        Block::Code { content, .. } => content.trim_end_matches('\n').to_string(),
+        Block::List { ordered, items } => list_text(items, *ordered),
+        Block::Blockquote { blocks, .. } => indent(&blocks_text(blocks), "  "),
+        Block::Table { headers, rows, .. } => table_text(headers, rows),
+        Block::Image { alt, .. } => alt.trim().to_string(),
+        Block::HorizontalRule => String::new(),
+        Block::Details {
+            summary, blocks, ..
+        } => {
+            let summary = summary.trim();
+            let body = blocks_text(blocks);
+            match (summary.is_empty(), body.is_empty()) {
+                (_, true) => summary.to_string(),
+                (true, false) => body,
+                (false, false) => format!("{}\n\n{}", summary, body),
+            }
+        }
+    }
+}
+
+/// Nested blocks, separated by blank lines like top-level ones.
+fn blocks_text(blocks: &[Block]) -> String {
+    blocks
+        .iter()
+        .map(block_text)
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// A list's source lines with their indentation, bullets normalized to `-`
+/// and inline markup removed. Fenced code inside items is kept verbatim
+/// without its fences, or left out without `include_code`.
+fn list_source_text(source: &str, include_code: bool) -> String {
+    let mut lines = Vec::new();
+    let mut fence: Option<&str> = None;
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            } else if include_code {
+                lines.push(line.trim_end().to_string());
+            }
+            continue;
+        }
+        if let Some(marker) = ["```", "~~~"].into_iter().find(|m| trimmed.starts_with(m)) {
+            fence = Some(marker);
+            continue;
+        }
+        if trimmed.is_empty() {
+            lines.push(String::new());
+            continue;
+        }
+        let indent = &line[..line.len() - trimmed.len()];
+        let (marker, text) = list_marker(trimmed);
+        lines.push(format!("{}{}{}", indent, marker, inline_source_text(text)));
+    }
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+/// The plain-text marker starting a list line (`- `, `3. `, `- [x] `) and
+/// the text after it. Continuation lines have no marker.
+fn list_marker(line: &str) -> (String, &str) {
+    let digits = line.len() - line.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let (mut marker, mut rest) = if let Some(rest) = ["- ", "* ", "+ "]
+        .into_iter()
+        .find_map(|bullet| line.strip_prefix(bullet))
+    {
+        ("- ".to_string(), rest)
+    } else if digits > 0
+        && let Some(rest) = line[digits..]
+            .strip_prefix(". ")
+            .or_else(|| line[digits..].strip_prefix(") "))
+    {
+        (format!("{}. ", &line[..digits]), rest)
+    } else {
+        return (String::new(), line);
+    };
+    for (source, checkbox) in [("[ ] ", "[ ] "), ("[x] ", "[x] "), ("[X] ", "[x] ")] {
+        if let Some(after) = rest.strip_prefix(source) {
+            marker.push_str(checkbox);
+            rest = after;
+        }
+    }
+    (marker, rest)
+}
+
+/// One line of markdown source as plain text.
+fn inline_source_text(text: &str) -> String {
+    parse_content(text, 0)
+        .iter()
+        .map(block_text)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Items as `- text` or `1. text` lines, with their nested blocks indented
+/// under the item text.
+fn list_text(items: &[ListItem], ordered: bool) -> String {
+    let mut lines = Vec::new();
+    for (number, item) in (1..).zip(items) {
+        let marker = if ordered {
+            format!("{}. ", number)
+        } else {
+            "- ".to_string()
+        };
+        let checkbox = match item.checked {
+            Some(true) => "[x] ",
+            Some(false) => "[ ] ",
+            None => "",
+        };
+        lines.push(format!(
+            "{}{}{}",
+            marker,
+            checkbox,
+            inline_text(&item.inline)
+        ));
+
+        let nested = blocks_text(&item.blocks);
+        if !nested.is_empty() {
+            lines.push(indent(&nested, &" ".repeat(marker.len())));
+        }
+    }
+    lines.join("\n")
+}
+
+/// Header and rows padded into columns, with a dashed line under the header.
+fn table_text(headers: &[String], rows: &[Vec<String>]) -> String {
+    let cells = |row: &[String]| -> Vec<String> {
+        row.iter()
+            .map(|cell| super::strip_markdown_inline(cell).trim().to_string())
+            .collect()
+    };
+    let headers = cells(headers);
+    let rows: Vec<Vec<String>> = rows.iter().map(|row| cells(row)).collect();
+
+    let columns = rows
+        .iter()
+        .map(Vec::len)
+        .chain([headers.len()])
+        .max()
+        .unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            rows.iter()
+                .chain([&headers])
+                .filter_map(|row| row.get(col))
+                .map(|cell| cell.width())
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let line = |row: &[String]| -> String {
+        let padded: Vec<String> = widths
+            .iter()
+            .enumerate()
+            .map(|(col, &width)| {
+                let cell = row.get(col).map(String::as_str).unwrap_or("");
+                format!("{}{}", cell, " ".repeat(width - cell.width()))
+            })
+            .collect();
+        padded.join("  ").trim_end().to_string()
+    };
+    let rule: Vec<String> = widths.iter().map(|&width| "-".repeat(width)).collect();
+
+    let mut lines = vec![line(&headers), rule.join("  ")];
+    lines.extend(rows.iter().map(|row| line(row)));
+    lines.join("\n")
+}
+
+/// Inline elements as text, with each line trimmed.
+fn inline_text(inline: &[InlineElement]) -> String {
+    let text: String = inline.iter().map(InlineElement::to_plain_text).collect();
+    text.lines()
+        .map(str::trim)
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
+/// `text` with `prefix` before each non-empty line.
+fn indent(text: &str, prefix: &str) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                String::new()
+            } else {
+                format!("{}{}", prefix, line)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_lists_keep_markers_and_indentation() {
+        let markdown = "\
+- Install
+  - from **crates.io**
+  - from [source](#source)
+- [x] Configure
+
+Then:
+
+1. First
+2. Second
+   - nested under second
+";
+        assert_eq!(
+            to_plain_text(markdown),
+            "\
+- Install
+  - from crates.io
+  - from source
+- [x] Configure
+
+Then:
+
+1. First
+2. Second
+   - nested under second
+"
+        );
+    }
+
+    #[test]
+    fn code_blocks_keep_their_lines() {
+        let markdown = "\
+## Build
+
+Run `cargo`:
+
+```rust
+fn main() {
+    println!(\"**not bold**\");
+}
+```
+
+- step
+  ```sh
+  make
+  ```
+";
+        assert_eq!(
+            to_plain_text(markdown),
+            "\
+Build
+
+Run cargo:
+
+fn main() {
+    println!(\"**not bold**\");
+}
+
+- step
+  make
+"
+        );
+    }
+
+    #[test]
+    fn tables_are_aligned_in_columns() {
+        let markdown = "| Key | Action |\n|---|---|\n| `j` | **Down** |\n| `gg` | Top |\n";
+        assert_eq!(
+            to_plain_text(markdown),
+            "Key  Action\n---  ------\nj    Down\ngg   Top\n"
+        );
+    }
+
+    #[test]
+    fn stats_count_prose_but_not_code_blocks() {
+        let markdown = "\
+---
+title: Not counted
+---
+# Install **now**
+
+Run `cargo install` and [see the docs](https://example.com).
+
+```sh
+cargo install treemd --locked
+```
+
+- one item
+  ```
+  nested code
+  ```
+- [x] done
+
+| Key | Action |
+|-----|--------|
+| j   | Down   |
+";
+        let prose = TextStats::of(markdown, false);
+        // Install now / Run cargo install and see the docs. / one item / done
+        // / the table's four cells
+        assert_eq!(prose.words, 2 + 7 + 2 + 1 + 4);
+        assert_eq!(TextStats::of(markdown, true).words, prose.words + 4 + 2);
+        assert_eq!(TextStats::of("café au lait", false).chars, 12);
+        assert_eq!(TextStats::of("> a b\n> c d\n", false).words, 4);
+        assert_eq!(TextStats::of("", false), TextStats::default());
+    }
+
+    #[test]
+    fn reading_time_rounds_up() {
+        let stats = |words| TextStats { words, chars: 0 };
+        assert_eq!(stats(0).reading_minutes(200), 0);
+        assert_eq!(stats(1).reading_minutes(200), 1);
+        assert_eq!(stats(400).reading_minutes(200), 2);
+        assert_eq!(stats(401).reading_minutes(200), 3);
+        assert_eq!(stats(10).reading_minutes(0), 10);
+    }
+
+    #[test]
+    fn quotes_are_indented_and_front_matter_dropped() {
+        let markdown = "---\ntitle: x\n---\n> Quoted *text*\n\n---\n\nAfter\n";
+        assert_eq!(to_plain_text(markdown), "  Quoted text\n\nAfter\n");
+        assert_eq!(to_plain_text(""), "");
+    }
+
+    #[test]
+    fn quote_lines_are_joined_with_spaces() {
+        assert_eq!(to_plain_text("> a b\n> c d\n"), "  a b c d\n");
+        let markdown = "> First line\nlazy line\n>\n> - item\n>   - nested\n>\n> > inner\n";
+        assert_eq!(
+            to_plain_text(markdown),
+            "  First line lazy line\n\n  - item\n    - nested\n\n    inner\n"
+        );
+    }
+}
@@ -0,0 +1,145 @@
+//! Table of contents generation.
+//!
+//! Writes the heading tree as a nested markdown list of links to GitHub
+//! anchors, ready to paste back into the document. Nesting follows the
+//! tree, so a skipped heading level doesn't add an extra indent.
+
+use super::document::{Document, HeadingNode};
+use super::utils::strip_markdown_inline;
+
+/// Options for [`to_toc`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TocOptions {
+    /// Leave out headings deeper than this level (`3` keeps `#` to `###`)
+    pub max_level: Option<usize>,
+    /// Number the entries `1.`, `2.`, … instead of using `-` bullets
+    pub numbered: bool,
+}
+
+/// A markdown table of contents for `doc`, one list item per heading.
+///
+/// Links use the anchors GitHub gives the headings, repeated headings
+/// numbered. The result ends with a newline unless empty.
+///
+/// ```
+/// use treemd::parser::{TocOptions, parse_markdown, to_toc};
+///
+/// let doc = parse_markdown("# Guide\n## Install\n## Usage\n");
+/// assert_eq!(
+///     to_toc(&doc, TocOptions::default()),
+///     "- [Guide](#guide)\n  - [Install](#install)\n  - [Usage](#usage)\n"
+/// );
+/// ```
+pub fn to_toc(doc: &Document, options: TocOptions) -> String {
+    let anchors = doc.github_anchors();
+    let mut lines = Vec::new();
+    push_entries(&doc.build_tree(), "", &anchors, options, &mut lines);
+    if lines.is_empty() {
+        String::new()
+    } else {
+        lines.join("\n") + "\n"
+    }
+}
+
+/// List items for `nodes` and their children, each indented under its
+/// parent's text.
+fn push_entries(
+    nodes: &[HeadingNode],
+    indent: &str,
+    anchors: &[String],
+    options: TocOptions,
+    lines: &mut Vec<String>,
+) {
+    let shown = nodes.iter().filter(|node| {
+        options
+            .max_level
+            .is_none_or(|max| node.heading.level <= max)
+    });
+    for (number, node) in (1..).zip(shown) {
+        let marker = if options.numbered {
+            format!("{}. ", number)
+        } else {
+            "- ".to_string()
+        };
+        let text = strip_markdown_inline(&node.heading.text);
+        lines.push(format!(
+            "{}{}[{}](#{})",
+            indent,
+            marker,
+            escape_brackets(text.trim()),
+            anchors[node.index]
+        ));
+
+        let child_indent = format!("{}{}", indent, " ".repeat(marker.len()));
+        push_entries(&node.children, &child_indent, anchors, options, lines);
+    }
+}
+
+/// `text` with `[` and `]` escaped, so it can't end the link text early.
+fn escape_brackets(text: &str) -> String {
+    text.replace('[', "\\[").replace(']', "\\]")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    #[test]
+    fn nests_by_tree_and_numbers_repeated_anchors() {
+        let doc = parse_markdown("# Guide\n### Deep\n## Setup\n# Reference\n## Setup\n");
+        assert_eq!(
+            to_toc(&doc, TocOptions::default()),
+            "\
+- [Guide](#guide)
+  - [Deep](#deep)
+  - [Setup](#setup)
+- [Reference](#reference)
+  - [Setup](#setup-1)
+"
+        );
+    }
+
+    #[test]
+    fn numbered_items_indent_under_their_text() {
+        let markdown: String = std::iter::once("# Top\n".to_string())
+            .chain((1..=10).map(|n| format!("## Part {}\n", n)))
+            .chain(["### Detail\n".to_string()])
+            .collect();
+        let toc = to_toc(
+            &parse_markdown(&markdown),
+            TocOptions {
+                numbered: true,
+                ..Default::default()
+            },
+        );
+        let lines: Vec<&str> = toc.lines().collect();
+        assert_eq!(lines[0], "1. [Top](#top)");
+        assert_eq!(lines[1], "   1. [Part 1](#part-1)");
+        assert_eq!(lines[10], "   10. [Part 10](#part-10)");
+        assert_eq!(lines[11], "       1. [Detail](#detail)");
+    }
+
+    #[test]
+    fn max_level_drops_deeper_headings() {
+        let doc = parse_markdown("# A\n## B\n### C\n#### D\n## E\n");
+        let options = TocOptions {
+            max_level: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(
+            to_toc(&doc, options),
+            "- [A](#a)\n  - [B](#b)\n  - [E](#e)\n"
+        );
+        assert_eq!(to_toc(&parse_markdown("No headings\n"), options), "");
+    }
+
+    #[test]
+    fn link_text_drops_markup() {
+        let doc = parse_markdown("# Using `treemd` [fast]\n");
+        assert_eq!(
+            to_toc(&doc, TocOptions::default()),
+            "- [Using treemd \\[fast\\]](#using-treemd-fast)\n"
+        );
+    }
+}
@@ -0,0 +1,223 @@
+//! Full structural export of a document (`--json-tree`).
+//!
+//! Unlike [`build_json_output`](super::build_json_output), which is organized
+//! around the outline, this serializes everything the parser sees: front
+//! matter, blocks before the first heading, and every section with its blocks
+//! and nested children, each carrying its source span.
+//!
+//! Schema (`treemd.json-tree/1`):
+//!
+//! ```text
+//! { schema, frontmatter: string|null, span, blocks: [block], sections: [section] }
+//! section = { type: "section", level, title, slug, span, heading_span,
+//!             blocks: [block], children: [section] }
+//! block   = { type: "paragraph"|"code"|"list"|"table"|..., ...fields, span: span|null }
+//! span    = { start_line, end_line, start_offset, end_offset }
+//! ```
+//!
+//! Lines are 1-based and inclusive; offsets are byte offsets with an exclusive
+//! end. A block's span is `null` when its source range cannot be determined.
+//! A section's `slug` is the anchor GitHub gives its heading, so repeated
+//! headings are numbered (`setup`, `setup-1`).
+
+use super::content::parse_content_with_ranges;
+use super::output::Block;
+use super::{Document, HeadingNode};
+use serde::Serialize;
+
+/// Identifier of the schema produced by [`build_document_tree`].
+pub const TREE_SCHEMA: &str = "treemd.json-tree/1";
+
+/// The whole document as a nested tree.
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentTree {
+    pub schema: &'static str,
+    /// Raw YAML front matter, without its `---` markers
+    pub frontmatter: Option<String>,
+    pub span: SourceSpan,
+    /// Blocks before the first heading
+    pub blocks: Vec<TreeBlock>,
+    pub sections: Vec<TreeSection>,
+}
+
+/// A heading, the blocks directly under it and its subsections.
+#[derive(Debug, Clone, Serialize)]
+pub struct TreeSection {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub level: usize,
+    pub title: String,
+    pub slug: String,
+    /// From the heading to the end of its last subsection
+    pub span: SourceSpan,
+    pub heading_span: SourceSpan,
+    /// Blocks between the heading and the next heading of any level
+    pub blocks: Vec<TreeBlock>,
+    pub children: Vec<TreeSection>,
+}
+
+/// A parsed block with its position in the source.
+#[derive(Debug, Clone, Serialize)]
+pub struct TreeBlock {
+    #[serde(flatten)]
+    pub block: Block,
+    pub span: Option<SourceSpan>,
+}
+
+/// A range of the source document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SourceSpan {
+    pub start_line: usize,
+    pub end_line: usize,
+    pub start_offset: usize,
+    pub end_offset: usize,
+}
+
+impl SourceSpan {
+    fn new(doc: &Document, start: usize, end: usize) -> Self {
+        let start_line = doc.line_at(start);
+        let end_line = if end > start {
+            doc.line_at(end - 1)
+        } else {
+            start_line
+        };
+        Self {
+            start_line,
+            end_line,
+            start_offset: start,
+            end_offset: end,
+        }
+    }
+}
+
+/// Build the full structural tree of `doc`.
+pub fn build_document_tree(doc: &Document) -> DocumentTree {
+    let frontmatter = super::frontmatter_block(&doc.content);
+    let preamble_start = super::utils::frontmatter_end(&doc.content);
+    let preamble_end = doc.headings.first().map_or(doc.content.len(), |h| h.offset);
+    let anchors = doc.github_anchors();
+
+    DocumentTree {
+        schema: TREE_SCHEMA,
+        frontmatter: frontmatter.map(str::to_string),
+        span: SourceSpan::new(doc, 0, doc.content.len()),
+        blocks: blocks_in(doc, preamble_start, preamble_end.max(preamble_start)),
+        sections: doc
+            .build_tree()
+            .iter()
+            .map(|node| build_section(doc, &anchors, node))
+            .collect(),
+    }
+}
+
+/// The section of `node`; `anchors` are the document's heading anchors.
+fn build_section(doc: &Document, anchors: &[String], node: &HeadingNode) -> TreeSection {
+    let heading = &node.heading;
+    let body_start = doc.body_start(node.index);
+    TreeSection {
+        kind: "section",
+        level: heading.level,
+        title: heading.text.clone(),
+        slug: anchors[node.index].clone(),
+        span: SourceSpan::new(
+            doc,
+            heading.offset,
+            trim_end(&doc.content, heading.offset, doc.section_end(node.index)),
+        ),
+        heading_span: SourceSpan::new(
+            doc,
+            heading.offset,
+            trim_end(&doc.content, heading.offset, body_start),
+        ),
+        blocks: blocks_in(doc, body_start, doc.section_end_any(node.index)),
+        children: node
+            .children
+            .iter()
+            .map(|child| build_section(doc, anchors, child))
+            .collect(),
+    }
+}
+
+/// Parse the top-level blocks of `doc.content[start..end]` and attach spans.
+fn blocks_in(doc: &Document, start: usize, end: usize) -> Vec<TreeBlock> {
+    parse_content_with_ranges(&doc.content, start, end)
+        .into_iter()
+        .map(|(block, range)| TreeBlock {
+            block,
+            span: range.map(|r| SourceSpan::new(doc, r.start, r.end)),
+        })
+        .collect()
+}
+
+/// `end` moved back past trailing whitespace, but not before `start`.
+fn trim_end(content: &str, start: usize, end: usize) -> usize {
+    start + content[start..end].trim_end().len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    const DOC: &str = "---\ntitle: Notes\n---\nIntro.\n\n# Guide\n\nRead this first.\nStill the same paragraph.\n\n```rust\nfn main() {\n\n}\n```\n\n## Steps\n\n- one\n\n- two\n";
+
+    #[test]
+    fn sections_carry_blocks_and_spans() {
+        let doc = parse_markdown(DOC);
+        let tree = build_document_tree(&doc);
+        assert_eq!(tree.frontmatter.as_deref(), Some("title: Notes\n"));
+        assert_eq!(tree.blocks.len(), 1);
+
+        let guide = &tree.sections[0];
+        assert_eq!((guide.level, guide.slug.as_str()), (1, "guide"));
+        assert_eq!(guide.heading_span.start_line, 6);
+        assert_eq!(guide.heading_span.end_line, 6);
+        assert_eq!(guide.span.end_line, 21);
+
+        let spans: Vec<_> = guide
+            .blocks
+            .iter()
+            .map(|b| {
+                let span = b.span.expect("span");
+                (span.start_line, span.end_line)
+            })
+            .collect();
+        assert_eq!(spans, [(8, 9), (11, 15)]);
+        let code = guide.blocks[1].span.unwrap();
+        assert!(DOC[code.start_offset..code.end_offset].starts_with("```rust"));
+        assert!(DOC[code.start_offset..code.end_offset].ends_with("```"));
+
+        let steps = &guide.children[0];
+        assert_eq!(steps.blocks.len(), 1);
+        assert_eq!(
+            steps.blocks[0].span.map(|s| (s.start_line, s.end_line)),
+            Some((19, 21))
+        );
+    }
+
+    #[test]
+    fn serializes_typed_blocks() {
+        let doc = parse_markdown("# A\n\nText.\n");
+        let json = serde_json::to_value(build_document_tree(&doc)).unwrap();
+        assert_eq!(json["schema"], TREE_SCHEMA);
+        assert_eq!(json["sections"][0]["type"], "section");
+        assert_eq!(json["sections"][0]["blocks"][0]["type"], "paragraph");
+        assert_eq!(json["sections"][0]["blocks"][0]["span"]["start_line"], 3);
+    }
+
+    #[test]
+    fn repeated_headings_get_their_own_slugs() {
+        let doc = parse_markdown("# Setup\n\nText\n> quote\n\n# Setup\n");
+        let tree = build_document_tree(&doc);
+        let slugs: Vec<&str> = tree.sections.iter().map(|s| s.slug.as_str()).collect();
+        assert_eq!(slugs, ["setup", "setup-1"]);
+
+        // A quote interrupting a paragraph leaves both their spans
+        let lines: Vec<_> = tree.sections[0]
+            .blocks
+            .iter()
+            .map(|b| b.span.map(|s| s.start_line))
+            .collect();
+        assert_eq!(lines, [Some(3), Some(4)]);
+    }
+}
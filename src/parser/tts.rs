@@ -0,0 +1,235 @@
+//! Read-aloud export for text-to-speech engines.
+//!
+//! Serializes the parsed blocks of a document as plain sentences, one per
+//! line, with no markup: headings are announced with their level, links are
+//! read as their text, list items become `Item:` lines and code blocks are
+//! announced by language (or skipped entirely).
+
+use super::content::parse_content;
+use super::output::{Block, InlineElement, ListItem};
+use super::utils::frontmatter_end;
+
+/// Options for [`to_read_aloud`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ReadAloudOptions {
+    /// Omit code blocks instead of announcing them
+    pub skip_code: bool,
+}
+
+/// Convert markdown to read-aloud text, one sentence per line.
+///
+/// Front matter is dropped. The result ends with a newline unless empty.
+pub fn to_read_aloud(markdown: &str, options: ReadAloudOptions) -> String {
+    let body = &markdown[frontmatter_end(markdown)..];
+
+    let mut lines = Vec::new();
+    for block in parse_content(body, 0) {
+        push_block(&block, options, &mut lines);
+    }
+
+    if lines.is_empty() {
+        String::new()
+    } else {
+        lines.join("\n") + "\n"
+    }
+}
+
+fn push_block(block: &Block, options: ReadAloudOptions, lines: &mut Vec<String>) {
+    match block {
+        Block::Heading { level, inline, .. } => {
+            let text = inline_text(inline);
+            if !text.is_empty() {
+                lines.push(format!("Heading level {}: {}", level, text));
+            }
+        }
+        Block::Paragraph { inline, .. } => push_sentences(&inline_text(inline), lines),
+        Block::List { items, .. } => push_list_items(items, options, lines),
+        Block::Blockquote { blocks, .. } => {
+            for block in blocks {
+                push_block(block, options, lines);
+            }
+        }
+        Block::Code { language, .. } => {
+            if options.skip_code {
+                return;
+            }
+            match language.as_deref().filter(|l| !l.is_empty()) {
+                Some(lang) => lines.push(format!("Code block in {}", language_name(lang))),
+                None => lines.push("Code block".to_string()),
+            }
+        }
+        Block::Table { headers, rows, .. } => {
+            let row_count = rows.len();
+            lines.push(format!(
+                "Table with {} row{}",
+                row_count,
+                if row_count == 1 { "" } else { "s" }
+            ));
+            let headers = spoken_cells(headers);
+            if !headers.is_empty() {
+                lines.push(format!("Columns: {}", headers));
+            }
+            for row in rows {
+                let cells = spoken_cells(row);
+                if !cells.is_empty() {
+                    lines.push(format!("Row: {}", cells));
+                }
+            }
+        }
+        Block::Image { alt, .. } => {
+            let alt = collapse_whitespace(alt);
+            if !alt.is_empty() {
+                lines.push(format!("Image: {}", alt));
+            }
+        }
+        Block::HorizontalRule => {}
+        Block::Details {
+            summary, blocks, ..
+        } => {
+            push_sentences(&collapse_whitespace(summary), lines);
+            for block in blocks {
+                push_block(block, options, lines);
+            }
+        }
+    }
+}
+
+fn push_list_items(items: &[ListItem], options: ReadAloudOptions, lines: &mut Vec<String>) {
+    for item in items {
+        let text = inline_text(&item.inline);
+        if !text.is_empty() {
+            let status = match item.checked {
+                Some(true) => "done, ",
+                Some(false) => "to do, ",
+                None => "",
+            };
+            lines.push(format!("Item: {}{}", status, text));
+        }
+        for block in &item.blocks {
+            push_block(block, options, lines);
+        }
+    }
+}
+
+/// Plain text of inline elements with whitespace collapsed.
+///
+/// Links contribute their text, images their alt text.
+fn inline_text(inline: &[InlineElement]) -> String {
+    let text: String = inline.iter().map(InlineElement::to_plain_text).collect();
+    collapse_whitespace(&text)
+}
+
+/// Table cells joined for speech, with inline markup removed.
+fn spoken_cells(cells: &[String]) -> String {
+    cells
+        .iter()
+        .map(|cell| collapse_whitespace(&super::strip_markdown_inline(cell)))
+        .filter(|cell| !cell.is_empty())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn collapse_whitespace(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Split text into sentences at `.`, `!` or `?` followed by whitespace.
+fn push_sentences(text: &str, lines: &mut Vec<String>) {
+    let mut start = 0;
+    let mut chars = text.char_indices().peekable();
+    while let Some((idx, c)) = chars.next() {
+        if matches!(c, '.' | '!' | '?')
+            && chars.peek().is_some_and(|(_, next)| next.is_whitespace())
+        {
+            let end = idx + c.len_utf8();
+            let sentence = text[start..end].trim();
+            if !sentence.is_empty() {
+                lines.push(sentence.to_string());
+            }
+            start = end;
+        }
+    }
+    let rest = text[start..].trim();
+    if !rest.is_empty() {
+        lines.push(rest.to_string());
+    }
+}
+
+/// Spoken name of a code block language tag.
+fn language_name(lang: &str) -> String {
+    let name = match lang.to_ascii_lowercase().as_str() {
+        "rs" | "rust" => "Rust",
+        "js" | "javascript" => "JavaScript",
+        "ts" | "typescript" => "TypeScript",
+        "py" | "python" => "Python",
+        "sh" | "bash" | "shell" | "zsh" => "shell",
+        "yml" | "yaml" => "YAML",
+        "json" => "JSON",
+        "toml" => "TOML",
+        "html" => "HTML",
+        "css" => "CSS",
+        "sql" => "SQL",
+        "cpp" | "c++" => "C++",
+        "cs" | "csharp" => "C#",
+        "go" | "golang" => "Go",
+        _ => {
+            let mut chars = lang.chars();
+            return match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => String::new(),
+            };
+        }
+    };
+    name.to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DOC: &str = "\
+# Getting Started
+
+Install the tool first. Then read the [usage guide](docs/usage.md)!
+
+- Fast **parsing**
+- Works with [pipes](#pipes)
+
+```rust
+fn main() {}
+```
+";
+
+    #[test]
+    fn reads_headings_lists_links_and_code() {
+        let out = to_read_aloud(DOC, ReadAloudOptions::default());
+        assert_eq!(
+            out,
+            "\
+Heading level 1: Getting Started
+Install the tool first.
+Then read the usage guide!
+Item: Fast parsing
+Item: Works with pipes
+Code block in Rust
+"
+        );
+    }
+
+    #[test]
+    fn skip_code_omits_code_blocks() {
+        let out = to_read_aloud(DOC, ReadAloudOptions { skip_code: true });
+        assert!(!out.contains("Code block"));
+        assert!(out.ends_with("Item: Works with pipes\n"));
+    }
+
+    #[test]
+    fn front_matter_and_markup_are_not_read() {
+        let doc = "---\ntitle: Notes\n---\n## Tasks\n\n- [x] Ship it\n\n| Name | Role |\n|---|---|\n| **Ada** | dev |\n";
+        let out = to_read_aloud(doc, ReadAloudOptions::default());
+        assert_eq!(
+            out,
+            "Heading level 2: Tasks\nItem: done, Ship it\nTable with 1 row\nColumns: Name, Role\nRow: Ada, dev\n"
+        );
+    }
+}
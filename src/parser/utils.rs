@@ -257,10 +257,78 @@ pub fn get_heading_level(line: &str) -> Option<usize> {
     None
 }
 
+/// Return the YAML body of a frontmatter block at the start of a document.
+///
+/// The document's first line must be exactly `---`; the block ends at the
+/// next line that is `---` or `...`. A `---` later in the document is a
+/// thematic break, not frontmatter.
+///
+/// # Examples
+///
+/// ```
+/// # use treemd::parser::utils::frontmatter_block;
+/// assert_eq!(frontmatter_block("---\ntitle: Hi\n---\n# Doc"), Some("title: Hi\n"));
+/// assert_eq!(frontmatter_block("# Doc\n\n---\ntitle: Hi\n---\n"), None);
+/// ```
+pub fn frontmatter_block(content: &str) -> Option<&str> {
+    let body = content
+        .strip_prefix("---\n")
+        .or_else(|| content.strip_prefix("---\r\n"))?;
+
+    let mut offset = 0;
+    for line in body.split_inclusive('\n') {
+        let marker = line.trim_end_matches(['\n', '\r']);
+        if marker == "---" || marker == "..." {
+            return Some(&body[..offset]);
+        }
+        offset += line.len();
+    }
+    None
+}
+
+/// 1-indexed line number of a byte offset into `content`.
+pub fn line_at(content: &str, offset: usize) -> usize {
+    let offset = offset.min(content.len());
+    content.as_bytes()[..offset]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count()
+        + 1
+}
+
+/// Byte offset just past the front matter's closing marker line, or 0 when
+/// the document has no front matter.
+pub fn frontmatter_end(content: &str) -> usize {
+    match frontmatter_block(content) {
+        Some(block) => {
+            let end = block.as_ptr() as usize - content.as_ptr() as usize + block.len();
+            content[end..]
+                .find('\n')
+                .map_or(content.len(), |i| end + i + 1)
+        }
+        None => 0,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_frontmatter_block() {
+        assert_eq!(
+            frontmatter_block("---\na: 1\nb:\n  c: 2\n...\nbody"),
+            Some("a: 1\nb:\n  c: 2\n")
+        );
+        assert_eq!(frontmatter_block("---\n---\n# Empty"), Some(""));
+        // Must be the very first line
+        assert_eq!(frontmatter_block("\n---\na: 1\n---\n"), None);
+        // Unterminated
+        assert_eq!(frontmatter_block("---\na: 1\n# Doc\n"), None);
+        // `----` is not an opening marker
+        assert_eq!(frontmatter_block("----\na: 1\n---\n"), None);
+    }
+
     #[test]
     fn test_strip_markdown_inline() {
         assert_eq!(strip_markdown_inline("**bold**"), "bold");
@@ -0,0 +1,147 @@
+//! Syntax validation for configuration code blocks.
+//!
+//! Fenced blocks tagged `yaml`/`yml`, `toml` or `json` are parsed with the
+//! matching deserializer so documentation examples can be checked in place.
+//! Other languages are never validated.
+
+use serde::Deserialize;
+
+/// A syntax error found in a config code block.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// 1-indexed line within the block's content
+    pub line: usize,
+    /// 1-indexed column, when the parser reports one
+    pub column: Option<usize>,
+    /// Parser error message (without location suffix)
+    pub message: String,
+}
+
+impl std::fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(col) => write!(f, "line {}:{}: {}", self.line, col, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+/// Whether blocks in `language` are validated by [`validate_config_block`].
+pub fn is_config_language(language: &str) -> bool {
+    matches!(
+        language.to_ascii_lowercase().as_str(),
+        "yaml" | "yml" | "toml" | "json"
+    )
+}
+
+/// Parse a config code block, returning the first syntax error.
+///
+/// Returns `None` for valid blocks and for languages that are not config
+/// formats (see [`is_config_language`]).
+pub fn validate_config_block(language: &str, content: &str) -> Option<ConfigError> {
+    match language.to_ascii_lowercase().as_str() {
+        "yaml" | "yml" => validate_yaml(content),
+        "toml" => validate_toml(content),
+        "json" => validate_json(content),
+        _ => None,
+    }
+}
+
+fn validate_yaml(content: &str) -> Option<ConfigError> {
+    // Iterate documents so `---`-separated multi-document examples are valid.
+    for document in yaml_serde::Deserializer::from_str(content) {
+        if let Err(e) = yaml_serde::Value::deserialize(document) {
+            let location = e.location();
+            let message = e.to_string();
+            // yaml_serde appends " at line N column M"; the location is
+            // reported separately.
+            let message = match message.find(" at line ") {
+                Some(idx) => message[..idx].to_string(),
+                None => message,
+            };
+            return Some(ConfigError {
+                line: location.as_ref().map_or(1, |l| l.line()),
+                column: location.map(|l| l.column()),
+                message,
+            });
+        }
+    }
+    None
+}
+
+fn validate_toml(content: &str) -> Option<ConfigError> {
+    let e = content.parse::<toml::Table>().err()?;
+    let (line, column) = match e.span() {
+        Some(span) => {
+            let before = &content[..span.start.min(content.len())];
+            let line = before.matches('\n').count() + 1;
+            let column = before.len() - before.rfind('\n').map_or(0, |i| i + 1) + 1;
+            (line, Some(column))
+        }
+        None => (1, None),
+    };
+    Some(ConfigError {
+        line,
+        column,
+        message: e.message().trim().to_string(),
+    })
+}
+
+fn validate_json(content: &str) -> Option<ConfigError> {
+    let e = serde_json::from_str::<serde_json::Value>(content).err()?;
+    let message = e.to_string();
+    let message = match message.find(" at line ") {
+        Some(idx) => message[..idx].to_string(),
+        None => message,
+    };
+    Some(ConfigError {
+        line: e.line().max(1),
+        column: Some(e.column()).filter(|&c| c > 0),
+        message,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_yaml_is_not_flagged() {
+        let yaml = "name: treemd\nfeatures:\n  - query\n  - tui\n";
+        assert_eq!(validate_config_block("yaml", yaml), None);
+    }
+
+    #[test]
+    fn malformed_yaml_reports_location() {
+        let yaml = "name: treemd\nfeatures: [query, tui\nother: 1\n";
+        let err = validate_config_block("yaml", yaml).expect("should be flagged");
+        assert!(err.line >= 2, "error should point past line 1: {err}");
+        assert!(err.column.is_some());
+        assert!(!err.message.contains(" at line "));
+    }
+
+    #[test]
+    fn multi_document_yaml_is_valid() {
+        assert_eq!(validate_config_block("yml", "a: 1\n---\nb: 2\n"), None);
+    }
+
+    #[test]
+    fn toml_error_line_from_span() {
+        let toml = "[ui]\ntheme = \"Nord\"\noutline_width = = 30\n";
+        let err = validate_config_block("toml", toml).expect("should be flagged");
+        assert_eq!(err.line, 3);
+        assert_eq!(
+            validate_config_block("TOML", "[ui]\ntheme = \"Nord\"\n"),
+            None
+        );
+    }
+
+    #[test]
+    fn json_error_and_unsupported_language() {
+        let err = validate_config_block("json", "{\n  \"a\": 1,\n}\n").expect("flagged");
+        assert_eq!(err.line, 3);
+        assert_eq!(validate_config_block("rust", "fn {"), None);
+        assert!(!is_config_language("rust"));
+        assert!(is_config_language("YAML"));
+    }
+}
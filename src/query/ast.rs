@@ -58,9 +58,10 @@ pub enum Expr {
         span: Span,
     },
 
-    /// Object construction: `{title: .h1.text}`
+    /// Object construction: `{title: .h1.text}`. A key without a value is
+    /// shorthand for the property of the same name: `{text}` is `{text: .text}`.
     Object {
-        pairs: Vec<(String, Expr)>,
+        pairs: Vec<(String, Option<Expr>)>,
         span: Span,
     },
 
@@ -103,6 +104,9 @@ pub enum Expr {
 
     /// Parenthesized expression for grouping
     Group { expr: Box<Expr>, span: Span },
+
+    /// String format filter: `@base64`, `@uri`, `@csv`
+    Format { format: StringFormat, span: Span },
 }
 
 impl Expr {
@@ -121,6 +125,7 @@ impl Expr {
             Expr::Binary { span, .. } => *span,
             Expr::Unary { span, .. } => *span,
             Expr::Group { span, .. } => *span,
+            Expr::Format { span, .. } => *span,
         }
     }
 }
@@ -144,8 +149,18 @@ pub enum ElementKind {
     Blockquote,
     /// Paragraph: `.para`
     Paragraph,
+    /// Task list item: `.tasks`
+    Task,
+    /// GitHub alert / Obsidian callout: `.callouts`
+    Callout,
+    /// Footnote definition: `.footnotes`
+    Footnote,
+    /// Definition list entry: `.definitions`
+    Definition,
     /// Front matter: `.frontmatter`
     FrontMatter,
+    /// Headings nested as the TUI outline shows them: `.outline`
+    Outline,
 }
 
 impl ElementKind {
@@ -185,9 +200,24 @@ impl ElementKind {
             // Paragraphs
             "para" | "paragraph" | "paragraphs" | "p" => Some(ElementKind::Paragraph),
 
+            // Task list items
+            "task" | "tasks" | "todo" | "todos" => Some(ElementKind::Task),
+
+            // Callouts / alerts
+            "callout" | "callouts" | "alert" | "alerts" => Some(ElementKind::Callout),
+
+            // Footnotes
+            "footnote" | "footnotes" | "fn" => Some(ElementKind::Footnote),
+
+            // Definition lists (`.definition` is the entry's property)
+            "definitions" | "dl" | "dt" => Some(ElementKind::Definition),
+
             // Front matter
             "frontmatter" | "fm" | "meta" | "yaml" => Some(ElementKind::FrontMatter),
 
+            // Outline (heading tree)
+            "outline" | "toc" => Some(ElementKind::Outline),
+
             _ => None,
         }
     }
@@ -210,7 +240,12 @@ impl ElementKind {
             ElementKind::List => "list",
             ElementKind::Blockquote => "blockquote",
             ElementKind::Paragraph => "para",
+            ElementKind::Task => "task",
+            ElementKind::Callout => "callout",
+            ElementKind::Footnote => "footnote",
+            ElementKind::Definition => "definition",
             ElementKind::FrontMatter => "frontmatter",
+            ElementKind::Outline => "outline",
         }
     }
 }
@@ -221,6 +256,35 @@ impl fmt::Display for ElementKind {
     }
 }
 
+/// Encoding applied by an `@name` format filter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringFormat {
+    /// `@base64`: standard base64 of the input text
+    Base64,
+    /// `@uri`: percent-encode everything but unreserved characters
+    Uri,
+    /// `@csv`: an array as one CSV row
+    Csv,
+}
+
+impl StringFormat {
+    /// All formats, for error messages.
+    pub const ALL: &[StringFormat] = &[StringFormat::Base64, StringFormat::Uri, StringFormat::Csv];
+
+    /// Look up a format by name (without the `@`).
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.iter().copied().find(|f| f.name() == name)
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            StringFormat::Base64 => "base64",
+            StringFormat::Uri => "uri",
+            StringFormat::Csv => "csv",
+        }
+    }
+}
+
 /// Filter for element selection.
 #[derive(Debug, Clone)]
 pub enum Filter {
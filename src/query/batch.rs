@@ -0,0 +1,141 @@
+//! Running one query over many files in parallel.
+
+use super::{Engine, OutputFormat, QueryError, Registry, Value};
+use crate::input::{InputSource, process_input_titled};
+use crate::parser::parse_markdown;
+use indexmap::IndexMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// One file's share of [`execute_many`](super::execute_many).
+#[derive(Debug)]
+pub struct FileResults {
+    pub path: PathBuf,
+    /// The results in the requested format, or why the file could not be
+    /// read or queried
+    pub output: Result<String, String>,
+    /// Number of results (0 on error)
+    pub count: usize,
+}
+
+pub(super) fn execute_many(
+    paths: &[PathBuf],
+    query_str: &str,
+    format: OutputFormat,
+    raw: bool,
+) -> Result<Vec<FileResults>, QueryError> {
+    let query = super::parse(query_str)?;
+    // File objects stay on one line except in pretty output; see `json_array`
+    let object_format = match format {
+        OutputFormat::JsonPretty => OutputFormat::JsonPretty,
+        _ => OutputFormat::JsonLines,
+    };
+    let registry = Arc::new(Registry::with_builtins());
+    let workers = std::thread::available_parallelism()
+        .map_or(1, usize::from)
+        .min(paths.len());
+
+    // Workers take the next unclaimed path; results are put back in order
+    let next = AtomicUsize::new(0);
+    let mut results: Vec<(usize, FileResults)> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| {
+                    let mut done = Vec::new();
+                    loop {
+                        let i = next.fetch_add(1, Ordering::Relaxed);
+                        let Some(path) = paths.get(i) else {
+                            break;
+                        };
+                        let results = run_file(path, |doc| {
+                            Engine::with_shared_registry(doc, Arc::clone(&registry))
+                                .execute(&query)
+                                .map_err(|e| e.render(query_str))
+                        });
+                        let file_results = match results {
+                            Ok(values) => FileResults {
+                                path: path.clone(),
+                                count: values.len(),
+                                output: Ok(if format.is_json() {
+                                    super::format_output(
+                                        &[file_object(path, values)],
+                                        object_format,
+                                    )
+                                } else if raw {
+                                    super::format_output_raw(&values, format)
+                                } else {
+                                    super::format_output(&values, format)
+                                }),
+                            },
+                            Err(e) => FileResults {
+                                path: path.clone(),
+                                count: 0,
+                                output: Err(e),
+                            },
+                        };
+                        done.push((i, file_results));
+                    }
+                    done
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|panic| std::panic::resume_unwind(panic))
+            })
+            .collect()
+    });
+    results.sort_by_key(|(i, _)| *i);
+    Ok(results.into_iter().map(|(_, r)| r).collect())
+}
+
+/// `{"file": path, "results": [...]}`, one file's share of JSON output.
+fn file_object(path: &Path, values: Vec<Value>) -> Value {
+    let mut object = IndexMap::new();
+    object.insert(
+        "file".to_string(),
+        Value::String(path.display().to_string()),
+    );
+    object.insert("results".to_string(), Value::Array(values));
+    Value::Object(object)
+}
+
+/// Join the file objects of a JSON batch into one array, laid out as
+/// `format` would lay out the array itself.
+pub fn json_array(objects: &[&str], format: OutputFormat) -> String {
+    if objects.is_empty() {
+        return "[]".to_string();
+    }
+    match format {
+        OutputFormat::JsonPretty => {
+            let indented: Vec<String> = objects
+                .iter()
+                .map(|object| {
+                    object
+                        .lines()
+                        .map(|line| format!("  {}", line))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                })
+                .collect();
+            format!("[\n{}\n]", indented.join(",\n"))
+        }
+        _ => format!("[{}]", objects.join(",")),
+    }
+}
+
+/// Read and parse `path` the way a single file is opened, then query it.
+fn run_file<T>(
+    path: &Path,
+    query: impl FnOnce(&crate::parser::Document) -> Result<T, String>,
+) -> Result<T, String> {
+    let raw = std::fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    let title = path.file_name().map(|name| name.to_string_lossy());
+    let content = process_input_titled(InputSource::File(raw), title.as_deref())
+        .map_err(|e| e.to_string())?;
+    query(&parse_markdown(&content))
+}
@@ -24,6 +24,8 @@ pub fn register_all(registry: &mut Registry) {
     registry.register_function("flatten", Function::new(fn_flatten, 0..=0));
     registry.register_function("keys", Function::new(fn_keys, 0..=0));
     registry.register_function("values", Function::new(fn_values, 0..=0));
+    registry.register_function("to_entries", Function::new(fn_to_entries, 0..=0));
+    registry.register_function("from_entries", Function::new(fn_from_entries, 0..=0));
     registry.register_function("empty", Function::new(fn_empty, 0..=0));
 
     // String functions
@@ -37,6 +39,7 @@ pub fn register_all(registry: &mut Registry) {
     registry.register_function("lines", Function::new(fn_lines, 0..=0));
     registry.register_function("words", Function::new(fn_words, 0..=0));
     registry.register_function("chars", Function::new(fn_chars, 0..=0));
+    registry.register_function("wordcount", Function::new(fn_wordcount, 0..=1));
     registry.register_function("slugify", Function::new(fn_slugify, 0..=0));
 
     // Boolean/filter functions
@@ -44,9 +47,9 @@ pub fn register_all(registry: &mut Registry) {
         "select",
         Function::new(fn_select, 1..=1).with_takes_input(true),
     );
-    registry.register_function("contains", Function::new(fn_contains, 1..=1));
-    registry.register_function("startswith", Function::new(fn_startswith, 1..=1));
-    registry.register_function("endswith", Function::new(fn_endswith, 1..=1));
+    registry.register_function("contains", Function::new(fn_contains, 1..=2));
+    registry.register_function("startswith", Function::new(fn_startswith, 1..=2));
+    registry.register_function("endswith", Function::new(fn_endswith, 1..=2));
     registry.register_function("matches", Function::new(fn_matches, 1..=1));
     registry.register_function("has", Function::new(fn_has, 1..=1));
     registry.register_function("type", Function::new(fn_type, 0..=0));
@@ -56,6 +59,11 @@ pub fn register_all(registry: &mut Registry) {
     registry.register_function("md", Function::new(fn_md, 0..=0));
     registry.register_function("url", Function::new(fn_url, 0..=0));
     registry.register_function("lang", Function::new(fn_lang, 0..=0));
+    registry.register_function("line", Function::new(fn_line, 0..=0));
+    registry.register_function(
+        "codeblocks",
+        Function::new(fn_codeblocks, 0..=1).with_takes_input(false),
+    );
 
     // Aggregation functions
     registry.register_function("stats", Function::new(fn_stats, 0..=0));
@@ -77,6 +85,7 @@ pub fn register_all(registry: &mut Registry) {
     registry.register_function("debug", Function::new(fn_debug, 0..=0));
     registry.register_function("group_by", Function::new(fn_group_by, 1..=1));
     registry.register_function("sort_by", Function::new(fn_sort_by, 1..=1));
+    registry.register_function("map", Function::new(fn_map, 1..=1));
 
     // Aliases - comprehensive for discoverability
     // Length/count
@@ -182,10 +191,11 @@ fn fn_unique(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryErro
     let input = args.first().unwrap_or(&Value::Null);
     match input {
         Value::Array(a) => {
+            // Key on kind as well as text so `1` and `"1"` stay distinct
             let mut seen = std::collections::HashSet::new();
             let unique: Vec<Value> = a
                 .iter()
-                .filter(|v| seen.insert(v.to_text()))
+                .filter(|v| seen.insert((v.kind(), v.to_text())))
                 .cloned()
                 .collect();
             Ok(vec![Value::Array(unique)])
@@ -214,11 +224,14 @@ fn fn_flatten(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryErr
 
 fn fn_keys(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
+    if let Some(keys) = input.sorted_keys() {
+        let keys = keys
+            .into_iter()
+            .map(|k| Value::String(k.to_string()))
+            .collect();
+        return Ok(vec![Value::Array(keys)]);
+    }
     match input {
-        Value::Object(o) => {
-            let keys: Vec<Value> = o.keys().map(|k| Value::String(k.clone())).collect();
-            Ok(vec![Value::Array(keys)])
-        }
         Value::Array(a) => {
             let keys: Vec<Value> = (0..a.len()).map(|i| Value::Number(i as f64)).collect();
             Ok(vec![Value::Array(keys)])
@@ -229,16 +242,84 @@ fn fn_keys(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
 
 fn fn_values(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
+    if let Some(o) = input.as_object() {
+        return Ok(vec![Value::Array(o.values().cloned().collect())]);
+    }
     match input {
-        Value::Object(o) => {
-            let values: Vec<Value> = o.values().cloned().collect();
-            Ok(vec![Value::Array(values)])
-        }
         Value::Array(a) => Ok(vec![Value::Array(a.clone())]),
         _ => Ok(vec![Value::Array(vec![])]),
     }
 }
 
+/// `{key, value}` objects for each pair of an object, sorted by key.
+fn fn_to_entries(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let input = args.first().unwrap_or(&Value::Null);
+    let (Some(object), Some(keys)) = (input.as_object(), input.sorted_keys()) else {
+        return Err(QueryError::new(
+            QueryErrorKind::TypeError {
+                expected: "an object for to_entries",
+                found: input.kind().to_string(),
+            },
+            Span::default(),
+            String::new(),
+        ));
+    };
+    let entries = keys
+        .into_iter()
+        .map(|key| {
+            let mut entry = indexmap::IndexMap::new();
+            entry.insert("key".to_string(), Value::String(key.to_string()));
+            entry.insert("value".to_string(), object[key].clone());
+            Value::Object(entry)
+        })
+        .collect();
+    Ok(vec![Value::Array(entries)])
+}
+
+/// Build an object from an array of `{key, value}` objects. A missing
+/// `value` is null; a missing or null `key` is an error. Later keys win.
+fn fn_from_entries(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let input = args.first().unwrap_or(&Value::Null);
+    let Value::Array(entries) = input else {
+        return Err(QueryError::new(
+            QueryErrorKind::TypeError {
+                expected: "an array of {key, value} objects for from_entries",
+                found: input.kind().to_string(),
+            },
+            Span::default(),
+            String::new(),
+        ));
+    };
+
+    let mut object = indexmap::IndexMap::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let key = match entry.as_object().and_then(|e| e.get("key")) {
+            Some(key @ (Value::String(_) | Value::Number(_) | Value::Bool(_))) => key.to_text(),
+            _ => {
+                let problem = match entry.as_object() {
+                    Some(_) => "has no 'key'".to_string(),
+                    None => format!("is a {}, not a {{key, value}} object", entry.kind()),
+                };
+                return Err(QueryError::new(
+                    QueryErrorKind::InvalidOperation(format!(
+                        "from_entries: entry {} {}",
+                        i, problem
+                    )),
+                    Span::default(),
+                    String::new(),
+                ));
+            }
+        };
+        let value = entry
+            .as_object()
+            .and_then(|e| e.get("value"))
+            .cloned()
+            .unwrap_or(Value::Null);
+        object.insert(key, value);
+    }
+    Ok(vec![Value::Object(object)])
+}
+
 fn fn_empty(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
     let is_empty = match input {
@@ -289,11 +370,20 @@ fn fn_split(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError
 fn fn_join(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
     let sep = args.get(1).map(|v| v.to_text()).unwrap_or_default();
-    let result = match input {
-        Value::Array(a) => a.iter().map(|v| v.to_text()).collect::<Vec<_>>().join(&sep),
-        _ => input.to_text(),
-    };
-    Ok(vec![Value::String(result)])
+    match input {
+        Value::Array(a) => {
+            let result = a.iter().map(|v| v.to_text()).collect::<Vec<_>>().join(&sep);
+            Ok(vec![Value::String(result)])
+        }
+        other => Err(QueryError::new(
+            QueryErrorKind::TypeError {
+                expected: "an array for join",
+                found: other.kind().to_string(),
+            },
+            Span::default(),
+            String::new(),
+        )),
+    }
 }
 
 fn fn_replace(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
@@ -321,6 +411,19 @@ fn fn_chars(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError
     Ok(vec![Value::Number(count as f64)])
 }
 
+/// Words in a section's plain text, subsections included, as the TUI's
+/// section stats count them: code blocks only with `wordcount(true)`.
+fn fn_wordcount(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let include_code = args.get(1).is_some_and(Value::is_truthy);
+    let markdown = match args.first().unwrap_or(&Value::Null) {
+        Value::Heading(h) => h.raw_md.clone(),
+        Value::Document(d) => d.content.clone(),
+        other => other.to_text(),
+    };
+    let stats = crate::parser::TextStats::of(&markdown, include_code);
+    Ok(vec![Value::Number(stats.words as f64)])
+}
+
 fn fn_slugify(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
     // Delegate to the canonical (turbovault) slugify for consistency with
@@ -346,33 +449,36 @@ fn fn_select(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryErro
 }
 
 fn fn_contains(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
-    let input = args.first().unwrap_or(&Value::Null);
-    let pattern = args.get(1).map(|v| v.to_text()).unwrap_or_default();
-    let result = input
-        .to_text()
-        .to_lowercase()
-        .contains(&pattern.to_lowercase());
-    Ok(vec![Value::Bool(result)])
+    Ok(vec![Value::Bool(match_text(args, |text, pattern| {
+        text.contains(pattern)
+    }))])
 }
 
 fn fn_startswith(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
-    let input = args.first().unwrap_or(&Value::Null);
-    let pattern = args.get(1).map(|v| v.to_text()).unwrap_or_default();
-    let result = input
-        .to_text()
-        .to_lowercase()
-        .starts_with(&pattern.to_lowercase());
-    Ok(vec![Value::Bool(result)])
+    Ok(vec![Value::Bool(match_text(args, |text, pattern| {
+        text.starts_with(pattern)
+    }))])
 }
 
 fn fn_endswith(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
-    let input = args.first().unwrap_or(&Value::Null);
+    Ok(vec![Value::Bool(match_text(args, |text, pattern| {
+        text.ends_with(pattern)
+    }))])
+}
+
+/// Apply a substring test to `(input, pattern[, ignore_case])`.
+///
+/// Matching is case-sensitive unless the optional third argument is truthy,
+/// in which case both sides are lowercased (Unicode-aware) first.
+fn match_text(args: &[Value], test: impl Fn(&str, &str) -> bool) -> bool {
+    let input = args.first().unwrap_or(&Value::Null).to_text();
     let pattern = args.get(1).map(|v| v.to_text()).unwrap_or_default();
-    let result = input
-        .to_text()
-        .to_lowercase()
-        .ends_with(&pattern.to_lowercase());
-    Ok(vec![Value::Bool(result)])
+    let ignore_case = args.get(2).is_some_and(Value::is_truthy);
+    if ignore_case {
+        test(&input.to_lowercase(), &pattern.to_lowercase())
+    } else {
+        test(&input, &pattern)
+    }
 }
 
 fn fn_matches(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
@@ -399,11 +505,7 @@ fn fn_has(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
     let input = args.first().unwrap_or(&Value::Null);
     let key = args.get(1).map(|v| v.to_text()).unwrap_or_default();
 
-    let result = match input {
-        Value::Object(o) => o.contains_key(&key),
-        _ => false,
-    };
-
+    let result = input.as_object().is_some_and(|o| o.contains_key(&key));
     Ok(vec![Value::Bool(result)])
 }
 
@@ -441,6 +543,12 @@ fn fn_url(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
     }
 }
 
+/// Source line of an element; nothing when it has none.
+fn fn_line(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let input = args.first().unwrap_or(&Value::Null);
+    Ok(input.get_property("line").into_iter().collect())
+}
+
 fn fn_lang(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
     match input {
@@ -451,6 +559,25 @@ fn fn_lang(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
     }
 }
 
+/// `codeblocks` emits every code block; `codeblocks("lang")` emits the exact
+/// source of each block whose language matches (case-insensitively).
+fn fn_codeblocks(args: &[Value], ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    let Some(lang) = args.first() else {
+        return Ok(ctx.code_blocks.iter().cloned().map(Value::Code).collect());
+    };
+    let lang = lang.to_text();
+    Ok(ctx
+        .code_blocks
+        .iter()
+        .filter(|c| {
+            c.language
+                .as_deref()
+                .is_some_and(|l| l.eq_ignore_ascii_case(&lang))
+        })
+        .map(|c| Value::String(c.content.clone()))
+        .collect())
+}
+
 // ============================================================================
 // Aggregation functions
 // ============================================================================
@@ -605,7 +732,7 @@ fn fn_nth(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError>
     }
 }
 
-// NOTE: `any`/`all`/`sort_by`/`group_by` are intercepted as special forms in
+// NOTE: `any`/`all`/`sort_by`/`group_by`/`map` are intercepted as special forms in
 // `Engine::eval_function` so their predicate/key argument is evaluated *per
 // element* (the registry path evaluates arguments only once). These registry
 // entries exist solely so calling them with the wrong number of arguments
@@ -745,16 +872,18 @@ fn fn_group_by(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryEr
                 groups.entry(key).or_default().push(item.clone());
             }
 
-            let result: indexmap::IndexMap<String, Value> = groups
-                .into_iter()
-                .map(|(k, v)| (k, Value::Array(v)))
-                .collect();
-            Ok(vec![Value::Object(result)])
+            Ok(groups.into_values().map(Value::Array).collect())
         }
         _ => Ok(vec![input.clone()]),
     }
 }
 
+fn fn_map(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
+    // `map(f)` is evaluated per element by the evaluator; this registration
+    // only provides arity checking and name suggestions.
+    Ok(vec![Value::Array(args.to_vec())])
+}
+
 fn fn_sort_by(args: &[Value], _ctx: &EvalContext) -> Result<Vec<Value>, QueryError> {
     let input = args.first().unwrap_or(&Value::Null);
     let key_name = args
@@ -48,34 +48,23 @@ impl QueryError {
         self
     }
 
-    /// Format the error for display.
+    /// Format the error for display, against the query it was parsed from.
     pub fn format(&self) -> String {
-        let inner = &self.0;
-        let mut output = String::new();
-
-        // Error header
-        output.push_str(&format!("error: {}\n", inner.kind));
-
-        // Source snippet with span indicator
-        if !inner.source.is_empty() {
-            output.push_str("  --> query\n");
-            output.push_str("  |\n");
+        self.render(&self.0.source)
+    }
 
-            // Show the line containing the error
-            let line = inner.source.lines().next().unwrap_or(&inner.source);
-            output.push_str(&format!("1 | {}\n", line));
+    /// Render the error like a rustc diagnostic: the message, then the line
+    /// of `source` containing the error with `^~~~` under the offending span.
+    ///
+    /// `source` is the query the error came from. Errors raised during
+    /// evaluation carry no position, so they are rendered without a snippet.
+    pub fn render(&self, source: &str) -> String {
+        let inner = &self.0;
+        let mut output = format!("error: {}\n", inner.kind);
 
-            // Underline the error span
-            let start = inner.span.start.min(line.len());
-            let end = inner.span.end.min(line.len()).max(start + 1);
-            let padding = " ".repeat(start + 4); // "1 | " = 4 chars
-            let underline = "^".repeat(end - start);
-            output.push_str(&format!(
-                "{}{}  {}\n",
-                padding,
-                underline,
-                inner.kind.short_message()
-            ));
+        let located = !inner.source.is_empty() || inner.span != Span::default();
+        if located && !source.is_empty() {
+            output.push_str(&snippet(source, inner.span, inner.kind.short_message()));
         }
 
         // Suggestions
@@ -106,6 +95,45 @@ impl QueryError {
     }
 }
 
+/// The line of `source` holding `span`, with a caret line underneath.
+///
+/// Spans are byte offsets; columns are counted in characters so the caret
+/// lines up under multibyte text. A span running past the end of its line is
+/// cut off there, and an empty span (e.g. at end of input) gets a single `^`.
+fn snippet(source: &str, span: Span, label: &str) -> String {
+    let start = floor_char_boundary(source, span.start.min(source.len()));
+    let end = floor_char_boundary(source, span.end.clamp(start, source.len()));
+
+    let line_start = source[..start].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = source[start..]
+        .find('\n')
+        .map_or(source.len(), |i| start + i);
+    let line = source[line_start..line_end].trim_end_matches('\r');
+    let line_no = source[..line_start].matches('\n').count() + 1;
+
+    let column = source[line_start..start].chars().count();
+    let width = source[start..end.min(line_end)].chars().count().max(1);
+
+    let gutter = " ".repeat(line_no.to_string().len());
+    format!(
+        "{gutter} --> query:{}:{}\n{gutter} |\n{} | {}\n{gutter} | {}^{}  {}\n",
+        line_no,
+        column + 1,
+        line_no,
+        line,
+        " ".repeat(column),
+        "~".repeat(width - 1),
+        label
+    )
+}
+
+fn floor_char_boundary(text: &str, mut index: usize) -> usize {
+    while !text.is_char_boundary(index) {
+        index -= 1;
+    }
+    index
+}
+
 impl fmt::Display for QueryError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.format())
@@ -152,6 +180,7 @@ pub enum QueryErrorKind {
     },
     UnknownFunction(String),
     UnknownElement(String),
+    UnknownFormat(String),
     InvalidArity {
         function: String,
         expected: String,
@@ -201,6 +230,7 @@ impl QueryErrorKind {
             QueryErrorKind::PropertyNotFound { .. } => "no such property",
             QueryErrorKind::UnknownFunction(_) => "unknown function",
             QueryErrorKind::UnknownElement(_) => "unknown element",
+            QueryErrorKind::UnknownFormat(_) => "unknown format",
             QueryErrorKind::InvalidArity { .. } => "wrong argument count",
             QueryErrorKind::NoMatch { .. } => "no match",
             QueryErrorKind::IndexOutOfBounds { .. } => "index out of bounds",
@@ -293,6 +323,9 @@ impl fmt::Display for QueryErrorKind {
             QueryErrorKind::UnknownElement(name) => {
                 write!(f, "Unknown element selector '{}'", name)
             }
+            QueryErrorKind::UnknownFormat(name) => {
+                write!(f, "Unknown format '@{}'", name)
+            }
             QueryErrorKind::InvalidArity {
                 function,
                 expected,
@@ -358,4 +391,52 @@ mod tests {
         assert!(formatted.contains("h1"));
         assert!(formatted.contains("heading levels"));
     }
+
+    #[test]
+    fn render_points_at_span_with_carets() {
+        let error = QueryError::new(
+            QueryErrorKind::UnknownFunction("lenght".to_string()),
+            Span::new(6, 12),
+            String::new(),
+        );
+        let rendered = error.render(".h2 | lenght");
+        assert_eq!(
+            rendered,
+            "error: Unknown function 'lenght'\n  --> query:1:7\n  |\n1 | .h2 | lenght\n  |       ^~~~~~  unknown function\n"
+        );
+    }
+
+    #[test]
+    fn render_finds_the_line_and_counts_characters() {
+        let source = ".h[\"café\"]\n| bogus";
+        let start = source.find("bogus").unwrap();
+        let error = QueryError::new(
+            QueryErrorKind::UnknownFunction("bogus".to_string()),
+            Span::new(start, start + 5),
+            source.to_string(),
+        );
+        let rendered = error.format();
+        assert!(rendered.contains("--> query:2:3\n"));
+        assert!(rendered.contains("2 | | bogus\n  |   ^~~~~  unknown function"));
+
+        // A span past the end of input still gets a caret
+        let eof = QueryError::new(
+            QueryErrorKind::UnexpectedEof {
+                expected: vec!["')'"],
+            },
+            Span::new(99, 99),
+            "(.h".to_string(),
+        );
+        assert!(eof.format().contains("1 | (.h\n  |    ^  unexpected end"));
+    }
+
+    #[test]
+    fn render_skips_snippet_for_unlocated_errors() {
+        let error = QueryError::new(
+            QueryErrorKind::DivisionByZero,
+            Span::default(),
+            String::new(),
+        );
+        assert_eq!(error.render("1 / 0"), "error: Division by zero\n");
+    }
 }
@@ -9,7 +9,7 @@ use super::ast::*;
 use super::error::{QueryError, QueryErrorKind};
 use super::registry::Registry;
 use super::value::*;
-use crate::parser::Document;
+use crate::parser::{Document, HeadingNode};
 
 /// Evaluation context passed to functions.
 pub struct EvalContext {
@@ -31,8 +31,18 @@ pub struct EvalContext {
     pub paragraphs: Vec<ParagraphValue>,
     /// All blockquotes
     pub blockquotes: Vec<BlockquoteValue>,
+    /// All task list items
+    pub tasks: Vec<TaskValue>,
+    /// All callouts (also present in `blockquotes`)
+    pub callouts: Vec<CalloutValue>,
+    /// All footnote definitions
+    pub footnotes: Vec<FootnoteValue>,
+    /// All definition list entries (also present in `paragraphs`)
+    pub definitions: Vec<DefinitionValue>,
     /// Parsed YAML frontmatter, if present (keys sorted for stable output)
     pub frontmatter: Option<IndexMap<String, Value>>,
+    /// Outline entries, one object per heading in document order
+    pub outline: Vec<Value>,
     /// Document metadata
     pub document: DocumentValue,
     /// Raw document content
@@ -43,8 +53,11 @@ impl EvalContext {
     /// Create a new context from a document.
     pub fn from_document(doc: &Document) -> Self {
         let headings = extract_headings(doc);
+        let outline = extract_outline(doc);
         let extracted = extract_blocks(doc);
         let frontmatter = extract_frontmatter(doc);
+        let tasks = extract_tasks(&doc.content);
+        let footnotes = extract_footnotes(&doc.content);
 
         let document = DocumentValue {
             content: doc.content.clone(),
@@ -62,7 +75,12 @@ impl EvalContext {
             lists: extracted.lists,
             paragraphs: extracted.paragraphs,
             blockquotes: extracted.blockquotes,
+            tasks,
+            callouts: extracted.callouts,
+            footnotes,
+            definitions: extracted.definitions,
             frontmatter,
+            outline,
             document,
             raw_content: doc.content.clone(),
         }
@@ -91,9 +109,15 @@ impl Engine {
 
     /// Create a new engine with a custom registry.
     pub fn with_registry(doc: &Document, registry: Registry) -> Self {
+        Self::with_shared_registry(doc, Arc::new(registry))
+    }
+
+    /// Create an engine using a registry shared with other engines, e.g. one
+    /// per thread when querying many documents.
+    pub fn with_shared_registry(doc: &Document, registry: Arc<Registry>) -> Self {
         let context = EvalContext::from_document(doc);
         Self {
-            registry: Arc::new(registry),
+            registry,
             context,
             depth: 0,
         }
@@ -120,18 +144,13 @@ impl Engine {
         let saved = self.context.current.clone();
 
         for stage in &piped.stages {
-            let mut next = Vec::new();
-            for input in current {
-                self.context.current = input;
-                match self.eval_expr(stage) {
-                    Ok(vals) => next.extend(vals),
-                    Err(e) => {
-                        self.context.current = saved;
-                        return Err(e);
-                    }
+            current = match self.eval_stage(stage, current) {
+                Ok(next) => next,
+                Err(e) => {
+                    self.context.current = saved;
+                    return Err(e);
                 }
-            }
-            current = next;
+            };
 
             // Short-circuit if no results
             if current.is_empty() {
@@ -143,6 +162,49 @@ impl Engine {
         Ok(current)
     }
 
+    /// Evaluate one pipeline stage against its input stream.
+    ///
+    /// Most stages run once per input value. Stream functions (`sort_by`,
+    /// `unique`, `group_by`) instead see the whole stream as one array, so
+    /// `.link | .url | unique` dedupes across links. `sort_by` and `unique`
+    /// results are re-emitted as a stream; `group_by` already emits one array
+    /// per group. A single array input is passed through unchanged
+    /// (`[.h] | sort_by(.level)`).
+    /// Leaves `current` set to the last input; callers restore it.
+    fn eval_stage(&mut self, stage: &Expr, inputs: Vec<Value>) -> Result<Vec<Value>, QueryError> {
+        let stream_function = match stage {
+            Expr::Function { name, .. } => match self.registry.resolve_name(name) {
+                f @ ("sort_by" | "unique" | "group_by") => Some(f == "group_by"),
+                _ => None,
+            },
+            _ => None,
+        };
+        let is_single_array = matches!(inputs.as_slice(), [Value::Array(_)]);
+        if let Some(emits_groups) = stream_function
+            && !is_single_array
+        {
+            self.context.current = Value::Array(inputs);
+            let results = self.eval_expr(stage)?;
+            if emits_groups {
+                return Ok(results);
+            }
+            return Ok(results
+                .into_iter()
+                .flat_map(|v| match v {
+                    Value::Array(items) => items,
+                    other => vec![other],
+                })
+                .collect());
+        }
+
+        let mut next = Vec::new();
+        for input in inputs {
+            self.context.current = input;
+            next.extend(self.eval_expr(stage)?);
+        }
+        Ok(next)
+    }
+
     fn eval_expr(&mut self, expr: &Expr) -> Result<Vec<Value>, QueryError> {
         self.depth += 1;
         if self.depth > MAX_EVAL_DEPTH {
@@ -193,6 +255,10 @@ impl Engine {
 
             Expr::Object { pairs, span } => self.eval_object(pairs, *span),
 
+            Expr::Format { format, .. } => {
+                format_value(*format, &self.context.current).map(|s| vec![Value::String(s)])
+            }
+
             Expr::Array { elements, span } => self.eval_array(elements, *span),
 
             Expr::Conditional {
@@ -213,6 +279,17 @@ impl Engine {
         index: Option<&IndexOp>,
         _span: Span,
     ) -> Result<Vec<Value>, QueryError> {
+        // On a code block (or an object with a "code" key) a bare `.code`
+        // reads its source rather than selecting the document's code again
+        if matches!(kind, ElementKind::Code)
+            && filters.is_empty()
+            && index.is_none()
+            && matches!(self.context.current, Value::Code(_) | Value::Object(_))
+            && let Some(code) = self.context.current.get_property("code")
+        {
+            return Ok(vec![code]);
+        }
+
         // Get all elements of the requested kind
         let mut elements: Vec<Value> = match kind {
             ElementKind::Heading(level) => self
@@ -272,10 +349,39 @@ impl Engine {
                 .cloned()
                 .map(Value::Paragraph)
                 .collect(),
+            ElementKind::Task => self
+                .context
+                .tasks
+                .iter()
+                .cloned()
+                .map(Value::Task)
+                .collect(),
+            ElementKind::Callout => self
+                .context
+                .callouts
+                .iter()
+                .cloned()
+                .map(Value::Callout)
+                .collect(),
+            ElementKind::Footnote => self
+                .context
+                .footnotes
+                .iter()
+                .cloned()
+                .map(Value::Footnote)
+                .collect(),
+            ElementKind::Definition => self
+                .context
+                .definitions
+                .iter()
+                .cloned()
+                .map(Value::Definition)
+                .collect(),
             ElementKind::FrontMatter => match &self.context.frontmatter {
                 Some(fm) => vec![Value::FrontMatter(fm.clone())],
                 None => Vec::new(),
             },
+            ElementKind::Outline => self.context.outline.clone(),
         };
 
         // Apply filters
@@ -331,6 +437,14 @@ impl Engine {
 
         if let Some(value) = current.get_property(name) {
             Ok(vec![value])
+        } else if current.as_object().is_some()
+            || matches!(current, Value::Null)
+            || matches!(name, "line" | "start_line" | "end_line")
+        {
+            // Like jq, a missing key on an object (or null) is simply absent;
+            // so is the line of a value without a known source position.
+            // Other unknown properties of elements are reported as typos.
+            Ok(vec![])
         } else {
             Err(QueryError::new(
                 QueryErrorKind::PropertyNotFound {
@@ -349,8 +463,10 @@ impl Engine {
         args: &[Expr],
         span: Span,
     ) -> Result<Vec<Value>, QueryError> {
-        // Handle special built-in functions
-        match name {
+        // Handle special built-in functions (under their canonical name, so
+        // aliases like `group` get the same per-element evaluation)
+        let registry = Arc::clone(&self.registry);
+        match registry.resolve_name(name) {
             "_pipe" => {
                 // Internal pipe handling. Save/restore `current` so the pipe's
                 // per-stage value doesn't leak into the surrounding expression
@@ -359,18 +475,13 @@ impl Engine {
                 let saved = self.context.current.clone();
                 let mut current = vec![self.context.current.clone()];
                 for arg in args {
-                    let mut next = Vec::new();
-                    for input in current {
-                        self.context.current = input;
-                        match self.eval_expr(arg) {
-                            Ok(vals) => next.extend(vals),
-                            Err(e) => {
-                                self.context.current = saved;
-                                return Err(e);
-                            }
+                    current = match self.eval_stage(arg, current) {
+                        Ok(next) => next,
+                        Err(e) => {
+                            self.context.current = saved;
+                            return Err(e);
                         }
-                    }
-                    current = next;
+                    };
                     if current.is_empty() {
                         break;
                     }
@@ -384,15 +495,18 @@ impl Engine {
             // Higher-order forms whose single argument is an expression to be
             // evaluated *per element* (with `current` bound to each element),
             // not once against the whole input.
-            "any" | "all" if args.len() == 1 => {
-                return self.eval_any_all(name == "all", &args[0]);
+            canonical @ ("any" | "all") if args.len() == 1 => {
+                return self.eval_any_all(canonical == "all", &args[0]);
             }
             "sort_by" if args.len() == 1 => {
-                return self.eval_sort_by(&args[0]);
+                return self.eval_sort_by(&args[0], span);
             }
             "group_by" if args.len() == 1 => {
                 return self.eval_group_by(&args[0]);
             }
+            "map" if args.len() == 1 => {
+                return self.eval_map(&args[0]);
+            }
             _ => {}
         }
 
@@ -525,21 +639,39 @@ impl Engine {
     }
 
     /// `sort_by(key)` — stable sort the current array by the evaluated key.
-    fn eval_sort_by(&mut self, key_expr: &Expr) -> Result<Vec<Value>, QueryError> {
+    ///
+    /// Keys must all be the same kind; numbers compare numerically and
+    /// everything else by text. Mixed kinds are an error rather than an
+    /// arbitrary order.
+    fn eval_sort_by(&mut self, key_expr: &Expr, span: Span) -> Result<Vec<Value>, QueryError> {
         let elements = self.current_elements();
         let mut keyed: Vec<(Value, Value)> = Vec::with_capacity(elements.len());
         for el in elements {
             let key = self.eval_key(&el, key_expr)?;
             keyed.push((key, el));
         }
+        if let Some((first, _)) = keyed.first()
+            && let Some((other, _)) = keyed.iter().find(|(k, _)| k.kind() != first.kind())
+        {
+            return Err(QueryError::new(
+                QueryErrorKind::InvalidOperation(format!(
+                    "sort_by cannot compare {} keys with {} keys",
+                    first.kind(),
+                    other.kind()
+                )),
+                span,
+                String::new(),
+            ));
+        }
         keyed.sort_by(|a, b| sort_key_cmp(&a.0, &b.0));
         Ok(vec![Value::Array(
             keyed.into_iter().map(|(_, v)| v).collect(),
         )])
     }
 
-    /// `group_by(key)` — group the current array into an object keyed by the
-    /// stringified evaluated key (per element), preserving first-seen order.
+    /// `group_by(key)` — partition the current array by the stringified
+    /// evaluated key (per element), emitting one array per group in
+    /// first-seen order. An empty input emits nothing.
     fn eval_group_by(&mut self, key_expr: &Expr) -> Result<Vec<Value>, QueryError> {
         let elements = self.current_elements();
         let mut groups: IndexMap<String, Vec<Value>> = IndexMap::new();
@@ -547,11 +679,22 @@ impl Engine {
             let key = self.eval_key(&el, key_expr)?.to_text();
             groups.entry(key).or_default().push(el);
         }
-        let obj: IndexMap<String, Value> = groups
-            .into_iter()
-            .map(|(k, v)| (k, Value::Array(v)))
-            .collect();
-        Ok(vec![Value::Object(obj)])
+        Ok(groups.into_values().map(Value::Array).collect())
+    }
+
+    /// `map(f)` — evaluate `f` per element and collect every output into one
+    /// array. An `f` that yields several values for an element contributes
+    /// all of them, in order; one that yields none drops the element.
+    fn eval_map(&mut self, f: &Expr) -> Result<Vec<Value>, QueryError> {
+        let elements = self.current_elements();
+        let mut mapped = Vec::with_capacity(elements.len());
+        for el in elements {
+            let saved = std::mem::replace(&mut self.context.current, el);
+            let result = self.eval_expr(f);
+            self.context.current = saved;
+            mapped.extend(result?);
+        }
+        Ok(vec![Value::Array(mapped)])
     }
 
     fn eval_hierarchy(
@@ -650,9 +793,14 @@ impl Engine {
                             // (it reports 1 for every fenced block), so scope by
                             // re-parsing the heading's byte range instead of
                             // filtering the global list by line number.
-                            let scope =
-                                &self.context.raw_content[scope_start_offset..scope_end_offset];
-                            results.extend(extract_code_blocks(scope).into_iter().map(Value::Code));
+                            results.extend(
+                                extract_code_blocks(
+                                    &self.context.raw_content,
+                                    scope_start_offset..scope_end_offset,
+                                )
+                                .into_iter()
+                                .map(Value::Code),
+                            );
                         }
                         ElementKind::Link => {
                             // Links carry byte offsets, so scope by byte range.
@@ -750,13 +898,16 @@ impl Engine {
 
     fn eval_object(
         &mut self,
-        pairs: &[(String, Expr)],
-        _span: Span,
+        pairs: &[(String, Option<Expr>)],
+        span: Span,
     ) -> Result<Vec<Value>, QueryError> {
         let mut obj = IndexMap::new();
 
         for (key, value_expr) in pairs {
-            let values = self.eval_expr(value_expr)?;
+            let values = match value_expr {
+                Some(expr) => self.eval_expr(expr)?,
+                None => self.eval_property(key, span)?,
+            };
             let value = if values.len() == 1 {
                 values.into_iter().next().unwrap()
             } else {
@@ -828,6 +979,108 @@ fn extract_headings(doc: &Document) -> Vec<HeadingValue> {
         .collect()
 }
 
+/// The heading tree the TUI outline shows, flattened in document order.
+///
+/// Each entry has the heading's `text`, `level` and `line`, its `depth` in
+/// the tree (0 for top-level entries; unlike `level` it doesn't count
+/// skipped levels) and `children_count`, its number of direct subheadings.
+fn extract_outline(doc: &Document) -> Vec<Value> {
+    fn walk(doc: &Document, nodes: &[HeadingNode], depth: usize, out: &mut Vec<Value>) {
+        for node in nodes {
+            let mut entry = IndexMap::new();
+            entry.insert("text".to_string(), Value::String(node.heading.text.clone()));
+            entry.insert(
+                "level".to_string(),
+                Value::Number(node.heading.level as f64),
+            );
+            entry.insert("depth".to_string(), Value::Number(depth as f64));
+            entry.insert(
+                "children_count".to_string(),
+                Value::Number(node.children.len() as f64),
+            );
+            entry.insert(
+                "line".to_string(),
+                Value::Number(doc.line_at(node.heading.offset) as f64),
+            );
+            out.push(Value::Object(entry));
+            walk(doc, &node.children, depth + 1, out);
+        }
+    }
+
+    let mut outline = Vec::with_capacity(doc.headings.len());
+    walk(doc, &doc.build_tree(), 0, &mut outline);
+    outline
+}
+
+/// Apply an `@name` format filter to `value`.
+fn format_value(format: StringFormat, value: &Value) -> Result<String, QueryError> {
+    match format {
+        StringFormat::Base64 => Ok(base64_encode(value.to_text().as_bytes())),
+        StringFormat::Uri => Ok(uri_encode(&value.to_text())),
+        StringFormat::Csv => {
+            let Value::Array(items) = value else {
+                return Err(QueryError::new(
+                    QueryErrorKind::TypeError {
+                        expected: "an array for @csv",
+                        found: value.kind().to_string(),
+                    },
+                    Span::default(),
+                    String::new(),
+                ));
+            };
+            let fields = items
+                .iter()
+                .map(|item| match item {
+                    Value::Null => Ok(String::new()),
+                    Value::Number(_) | Value::Bool(_) => Ok(item.to_text()),
+                    Value::Array(_) | Value::Object(_) | Value::FrontMatter(_) => {
+                        Err(QueryError::new(
+                            QueryErrorKind::TypeError {
+                                expected: "scalar values in @csv",
+                                found: item.kind().to_string(),
+                            },
+                            Span::default(),
+                            String::new(),
+                        ))
+                    }
+                    other => Ok(format!("\"{}\"", other.to_text().replace('"', "\"\""))),
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Ok(fields.join(","))
+        }
+    }
+}
+
+/// Standard base64 (RFC 4648) with padding.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk.iter().fold(0u32, |acc, &b| acc << 8 | b as u32) << (8 * (3 - chunk.len()));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Percent-encode every byte except the RFC 3986 unreserved characters.
+fn uri_encode(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || matches!(byte, b'-' | b'_' | b'.' | b'~') {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
 /// Block-level elements extracted from a document, grouped by kind.
 #[derive(Default)]
 struct ExtractedBlocks {
@@ -838,43 +1091,43 @@ struct ExtractedBlocks {
     lists: Vec<ListValue>,
     paragraphs: Vec<ParagraphValue>,
     blockquotes: Vec<BlockquoteValue>,
+    callouts: Vec<CalloutValue>,
+    definitions: Vec<DefinitionValue>,
 }
 
 fn extract_blocks(doc: &Document) -> ExtractedBlocks {
-    use crate::parser::content::parse_content;
+    use crate::parser::content::parse_content_with_ranges;
     use crate::parser::links::extract_links;
-    use crate::parser::output::Block;
+    use crate::parser::output::{Block, InlineElement};
 
-    let blocks = parse_content(&doc.content, 1);
+    let start = crate::parser::utils::frontmatter_end(&doc.content);
+    let blocks = parse_content_with_ranges(&doc.content, start, doc.content.len());
     let links = extract_links(&doc.content);
 
     let mut out = ExtractedBlocks::default();
 
     // Recursively extract blocks from nested structures (e.g., list items,
-    // blockquotes, details). Top-level paragraphs/blockquotes are collected by
-    // the outer loop; nested ones are collected here too.
-    fn walk(blocks: &[Block], out: &mut ExtractedBlocks) {
+    // blockquotes, details). `lines` is the (first, last) source line of a
+    // top-level block; other nested blocks have no position of their own,
+    // code blocks get theirs from `code`.
+    fn walk(
+        blocks: &[Block],
+        lines: Option<(usize, usize)>,
+        code: &mut CodeLines,
+        out: &mut ExtractedBlocks,
+    ) {
+        let line = lines.map(|(first, _)| first);
+        // Inline images are only pinned to a line when their block has one
+        let inline_line = lines.filter(|(first, last)| first == last).map(|(l, _)| l);
         for block in blocks {
             match block {
                 Block::Code {
-                    language,
-                    content,
-                    start_line,
-                    end_line,
+                    language, content, ..
                 } => {
-                    out.code_blocks.push(CodeValue {
-                        language: language.clone(),
-                        content: content.clone(),
-                        start_line: *start_line,
-                        end_line: *end_line,
-                    });
+                    out.code_blocks.push(code.value(language, content));
                 }
                 Block::Image { alt, src, title } => {
-                    out.images.push(ImageValue {
-                        alt: alt.clone(),
-                        src: src.clone(),
-                        title: title.clone(),
-                    });
+                    out.images.push(image_value(alt, src, title, line));
                 }
                 Block::Table {
                     headers,
@@ -882,22 +1135,44 @@ fn extract_blocks(doc: &Document) -> ExtractedBlocks {
                     alignments,
                 } => {
                     out.tables.push(TableValue {
-                        headers: headers.clone(),
-                        rows: rows.clone(),
+                        headers: headers.iter().map(|c| plain_cell_text(c)).collect(),
+                        rows: rows
+                            .iter()
+                            .map(|row| row.iter().map(|c| plain_cell_text(c)).collect())
+                            .collect(),
                         alignments: alignments
                             .iter()
                             .map(|a| format!("{:?}", a).to_lowercase())
                             .collect(),
+                        line,
                     });
                 }
-                Block::Paragraph { content, .. } => {
+                Block::Heading { inline, .. } => {
+                    push_inline_images(inline, inline_line, out);
+                }
+                Block::Paragraph { content, inline } => {
                     out.paragraphs.push(ParagraphValue {
                         content: content.clone(),
+                        line,
                     });
+                    let items = crate::parser::definition_list::split(content);
+                    for item in items.into_iter().flatten() {
+                        use crate::parser::strip_markdown_inline;
+                        let term = strip_markdown_inline(&item.term);
+                        for definition in &item.definitions {
+                            out.definitions.push(DefinitionValue {
+                                term: term.clone(),
+                                definition: strip_markdown_inline(definition),
+                                line: line.map(|l| l + item.line),
+                            });
+                        }
+                    }
+                    push_inline_images(inline, inline_line, out);
                 }
                 Block::List { ordered, items } => {
                     for item in items {
-                        walk(&item.blocks, out);
+                        push_inline_images(&item.inline, None, out);
+                        walk(&item.blocks, None, code, out);
                     }
                     out.lists.push(ListValue {
                         ordered: *ordered,
@@ -908,23 +1183,63 @@ fn extract_blocks(doc: &Document) -> ExtractedBlocks {
                                 checked: i.checked,
                             })
                             .collect(),
+                        line,
                     });
                 }
                 Block::Blockquote { content, blocks } => {
                     out.blockquotes.push(BlockquoteValue {
                         content: content.clone(),
+                        line,
                     });
-                    walk(blocks, out);
+                    if let Some((marker, body)) = crate::parser::callout::split(content) {
+                        use crate::parser::strip_markdown_inline;
+                        out.callouts.push(CalloutValue {
+                            kind: marker.kind.to_ascii_lowercase(),
+                            title: strip_markdown_inline(&marker.title),
+                            text: body
+                                .lines()
+                                .map(strip_markdown_inline)
+                                .collect::<Vec<_>>()
+                                .join("\n"),
+                            line,
+                        });
+                    }
+                    walk(blocks, None, code, out);
                 }
                 Block::Details { blocks, .. } => {
-                    walk(blocks, out);
+                    walk(blocks, None, code, out);
                 }
                 _ => {}
             }
         }
     }
 
-    walk(&blocks, &mut out);
+    // Images embedded in running text (as opposed to standalone image
+    // paragraphs, which arrive as `Block::Image`). Reference-style images are
+    // already resolved to their destination by the parser.
+    fn push_inline_images(
+        inline: &[InlineElement],
+        line: Option<usize>,
+        out: &mut ExtractedBlocks,
+    ) {
+        for element in inline {
+            if let InlineElement::Image {
+                alt, src, title, ..
+            } = element
+            {
+                out.images.push(image_value(alt, src, title, line));
+            }
+        }
+    }
+
+    let mut code = CodeLines::new(&doc.content, start..doc.content.len());
+    for (block, range) in &blocks {
+        let lines = range
+            .as_ref()
+            .map(|r| (doc.line_at(r.start), doc.line_at(r.end - 1)));
+        code.enter(lines);
+        walk(std::slice::from_ref(block), lines, &mut code, &mut out);
+    }
 
     out.links = links
         .into_iter()
@@ -943,11 +1258,16 @@ fn extract_blocks(doc: &Document) -> ExtractedBlocks {
                 }
                 LinkTarget::WikiLink { target, .. } => (target, LinkType::WikiLink),
             };
+            // pulldown-cmark reports autolinks as ordinary links; the only
+            // trace left is the `<` at the link's start offset.
+            let autolink = doc.content[l.offset..].starts_with('<');
             LinkValue {
                 text: l.text,
                 url,
                 link_type,
                 offset: l.offset,
+                line: doc.content[..l.offset].matches('\n').count() + 1,
+                autolink,
             }
         })
         .collect();
@@ -955,86 +1275,224 @@ fn extract_blocks(doc: &Document) -> ExtractedBlocks {
     out
 }
 
-/// Parse a markdown fragment and return only its code blocks (including those
+/// Build an [`ImageValue`], splitting off a title that the parser left
+/// attached to the source (`![alt](path "Title")` can arrive as a single
+/// destination after turbovault's space-in-URL preprocessing).
+fn image_value(alt: &str, src: &str, title: &Option<String>, line: Option<usize>) -> ImageValue {
+    let split = title
+        .is_none()
+        .then(|| {
+            ['"', '\''].into_iter().find_map(|q| {
+                let body = src.strip_suffix(q)?;
+                let (path, title) = body.rsplit_once(&format!(" {q}"))?;
+                Some((path.trim_end().to_string(), Some(title.to_string())))
+            })
+        })
+        .flatten();
+    let (src, title) = split.unwrap_or_else(|| (src.to_string(), title.clone()));
+    ImageValue {
+        alt: alt.to_string(),
+        src,
+        title,
+        line,
+    }
+}
+
+/// Scan the source for GitHub task list items.
+///
+/// Only list items whose body starts with a `[ ]`, `[x]` or `[X]` marker
+/// followed by whitespace count; `[link](url)` items, bare `[x] text` lines
+/// and anything inside fenced code blocks are ignored.
+fn extract_tasks(content: &str) -> Vec<TaskValue> {
+    use regex::Regex;
+    use std::sync::OnceLock;
+
+    static TASK: OnceLock<Regex> = OnceLock::new();
+    let task = TASK.get_or_init(|| {
+        Regex::new(r"^\s*(?:>\s*)*(?:[-*+]|\d{1,9}[.)])\s+\[([ xX])\](?:\s+(.*))?$").unwrap()
+    });
+
+    let mut tasks = Vec::new();
+    let mut fence: Option<&str> = None;
+    for (idx, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        if let Some(marker) = fence {
+            if trimmed.starts_with(marker) {
+                fence = None;
+            }
+            continue;
+        }
+        if trimmed.starts_with("```") {
+            fence = Some("```");
+            continue;
+        }
+        if trimmed.starts_with("~~~") {
+            fence = Some("~~~");
+            continue;
+        }
+        if let Some(caps) = task.captures(line) {
+            let text = caps.get(2).map_or("", |m| m.as_str());
+            tasks.push(TaskValue {
+                text: crate::parser::utils::strip_markdown_inline(text.trim()),
+                checked: &caps[1] != " ",
+                line: idx + 1,
+            });
+        }
+    }
+    tasks
+}
+
+/// Footnote definitions, in document order, with inline markdown stripped
+/// from their text.
+fn extract_footnotes(content: &str) -> Vec<FootnoteValue> {
+    crate::parser::Footnotes::parse(content)
+        .iter()
+        .map(|note| FootnoteValue {
+            label: note.label.clone(),
+            text: crate::parser::utils::strip_markdown_inline(&note.text),
+            line: note.line,
+        })
+        .collect()
+}
+
+/// Reduce a table cell to plain text: links and images keep their text,
+/// emphasis/code/strikethrough markers are dropped.
+fn plain_cell_text(cell: &str) -> String {
+    use regex::Regex;
+    use std::sync::OnceLock;
+
+    static LINK: OnceLock<Regex> = OnceLock::new();
+    let link = LINK.get_or_init(|| Regex::new(r"!?\[([^\]]*)\]\([^)]*\)").unwrap());
+    let text = link.replace_all(cell, "$1");
+    crate::parser::utils::strip_markdown_inline(&text)
+        .trim()
+        .to_string()
+}
+
+/// Parse `content[range]` and return only its code blocks (including those
 /// nested in lists/blockquotes/details). Used to scope code blocks to a
 /// heading's byte range, since turbovault's per-block line numbers are not
 /// reliable for line-range filtering.
-fn extract_code_blocks(fragment: &str) -> Vec<CodeValue> {
-    use crate::parser::content::parse_content;
+fn extract_code_blocks(content: &str, range: std::ops::Range<usize>) -> Vec<CodeValue> {
+    use crate::parser::content::parse_content_with_ranges;
     use crate::parser::output::Block;
+    use crate::parser::utils::line_at;
 
-    fn walk(blocks: &[Block], out: &mut Vec<CodeValue>) {
+    fn walk(blocks: &[Block], code: &mut CodeLines, out: &mut Vec<CodeValue>) {
         for block in blocks {
             match block {
                 Block::Code {
-                    language,
-                    content,
-                    start_line,
-                    end_line,
-                } => out.push(CodeValue {
-                    language: language.clone(),
-                    content: content.clone(),
-                    start_line: *start_line,
-                    end_line: *end_line,
-                }),
+                    language, content, ..
+                } => out.push(code.value(language, content)),
                 Block::List { items, .. } => {
                     for item in items {
-                        walk(&item.blocks, out);
+                        walk(&item.blocks, code, out);
                     }
                 }
                 Block::Blockquote { blocks, .. } | Block::Details { blocks, .. } => {
-                    walk(blocks, out);
+                    walk(blocks, code, out);
                 }
                 _ => {}
             }
         }
     }
 
-    let blocks = parse_content(fragment, 1);
+    let mut code = CodeLines::new(content, range.clone());
     let mut out = Vec::new();
-    walk(&blocks, &mut out);
+    for (block, block_range) in parse_content_with_ranges(content, range.start, range.end) {
+        code.enter(block_range.map(|r| (line_at(content, r.start), line_at(content, r.end - 1))));
+        walk(std::slice::from_ref(&block), &mut code, &mut out);
+    }
     out
 }
 
-/// Parse the document's YAML frontmatter into an ordered, sorted-key map of
-/// query [`Value`]s. Returns `None` when there is no frontmatter.
-fn extract_frontmatter(doc: &Document) -> Option<IndexMap<String, Value>> {
-    use turbovault_parser::{ParseOptions, ParsedContent};
+/// Fence lines for the parsed code blocks of one stretch of the source,
+/// taken from the parser's offsets in order, matched by the code's text.
+struct CodeLines<'a> {
+    content: &'a str,
+    sources: Vec<(String, std::ops::Range<usize>)>,
+    next: usize,
+    /// Lines of the top-level block being walked, for a code block the
+    /// source offsets don't account for
+    block: (usize, usize),
+}
 
-    let parsed =
-        ParsedContent::parse_with_options(&doc.content, ParseOptions::none().with_frontmatter());
-    let fm = parsed.frontmatter?;
+impl<'a> CodeLines<'a> {
+    fn new(content: &'a str, range: std::ops::Range<usize>) -> Self {
+        let line = crate::parser::utils::line_at(content, range.start);
+        Self {
+            content,
+            sources: crate::parser::content::code_block_ranges(content, range.start, range.end),
+            next: 0,
+            block: (line, line),
+        }
+    }
 
-    // Sort keys for stable, deterministic output.
-    let mut entries: Vec<(String, &serde_json::Value)> =
-        fm.data.iter().map(|(k, v)| (k.clone(), v)).collect();
-    entries.sort_by(|a, b| a.0.cmp(&b.0));
+    /// Start on the next top-level block, spanning `lines` when known.
+    fn enter(&mut self, lines: Option<(usize, usize)>) {
+        if let Some(lines) = lines {
+            self.block = lines;
+        }
+    }
 
-    let mut map = IndexMap::new();
-    for (k, v) in entries {
-        map.insert(k, json_to_value(v));
+    /// The [`CodeValue`] of the next code block, with its fence lines.
+    fn value(&mut self, language: &Option<String>, content: &str) -> CodeValue {
+        use crate::parser::utils::line_at;
+
+        let found = self.sources[self.next..]
+            .iter()
+            .position(|(code, _)| code == content);
+        let (start_line, end_line) = match found {
+            Some(found) => {
+                self.next += found + 1;
+                let range = &self.sources[self.next - 1].1;
+                (
+                    line_at(self.content, range.start),
+                    line_at(self.content, range.end - 1),
+                )
+            }
+            None => self.block,
+        };
+        CodeValue {
+            language: language.clone(),
+            content: content.to_string(),
+            start_line,
+            end_line,
+        }
     }
-    Some(map)
 }
 
-/// Convert a `serde_json::Value` (from parsed frontmatter) into a query
-/// [`Value`], sorting object keys for deterministic output.
-fn json_to_value(v: &serde_json::Value) -> Value {
+/// Parse the document's YAML frontmatter into an ordered, sorted-key map of
+/// query [`Value`]s. Returns `None` when there is no frontmatter (see
+/// [`crate::parser::frontmatter_block`]) or it is not a YAML mapping.
+fn extract_frontmatter(doc: &Document) -> Option<IndexMap<String, Value>> {
+    let yaml = crate::parser::frontmatter_block(&doc.content)?;
+    match yaml_to_value(&yaml_serde::from_str(yaml).ok()?) {
+        Value::Object(map) => Some(map),
+        _ => None,
+    }
+}
+
+/// Convert a parsed YAML value into a query [`Value`]: mappings become
+/// objects (keys sorted for deterministic output), sequences become arrays.
+fn yaml_to_value(v: &yaml_serde::Value) -> Value {
+    use yaml_serde::Value as Yaml;
+
     match v {
-        serde_json::Value::Null => Value::Null,
-        serde_json::Value::Bool(b) => Value::Bool(*b),
-        serde_json::Value::Number(n) => Value::Number(n.as_f64().unwrap_or(0.0)),
-        serde_json::Value::String(s) => Value::String(s.clone()),
-        serde_json::Value::Array(arr) => Value::Array(arr.iter().map(json_to_value).collect()),
-        serde_json::Value::Object(obj) => {
-            let mut keys: Vec<&String> = obj.keys().collect();
-            keys.sort();
-            let mut map = IndexMap::new();
-            for k in keys {
-                map.insert(k.clone(), json_to_value(&obj[k]));
-            }
-            Value::Object(map)
+        Yaml::Null => Value::Null,
+        Yaml::Bool(b) => Value::Bool(*b),
+        Yaml::Number(n) => Value::Number(n.as_f64().unwrap_or(0.0)),
+        Yaml::String(s) => Value::String(s.clone()),
+        Yaml::Sequence(seq) => Value::Array(seq.iter().map(yaml_to_value).collect()),
+        Yaml::Mapping(mapping) => {
+            let mut entries: Vec<(String, Value)> = mapping
+                .iter()
+                .map(|(k, v)| (yaml_to_value(k).to_text(), yaml_to_value(v)))
+                .collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().collect())
         }
+        Yaml::Tagged(tagged) => yaml_to_value(&tagged.value),
     }
 }
 
@@ -1120,15 +1578,16 @@ fn values_equal(a: &Value, b: &Value) -> bool {
     }
 }
 
-/// Total ordering used by `sort_by`. Numbers sort numerically, strings
-/// lexically; mixed/other types fall back to their text representation so the
-/// sort is always total and deterministic.
+/// Order two `sort_by` keys of the same kind (mixed kinds are rejected
+/// before sorting). Numbers compare numerically, strings and booleans
+/// directly, and other kinds by their text.
 fn sort_key_cmp(a: &Value, b: &Value) -> std::cmp::Ordering {
     match (a, b) {
         (Value::Number(x), Value::Number(y)) => {
             x.partial_cmp(y).unwrap_or(std::cmp::Ordering::Equal)
         }
         (Value::String(x), Value::String(y)) => x.cmp(y),
+        (Value::Bool(x), Value::Bool(y)) => x.cmp(y),
         _ => a.to_text().cmp(&b.to_text()),
     }
 }
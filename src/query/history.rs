@@ -0,0 +1,210 @@
+//! Persisted history of CLI queries.
+//!
+//! Each successful `-q` query run from a terminal is appended to a plain-text
+//! file (one query per line); queries in scripts and pipelines are not. `!!`
+//! reruns the most recent query and `!n` the nth most recent, so `!1` is the
+//! same as `!!`.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Error raised when a `!!`/`!n` reference cannot be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    /// No queries have been recorded yet
+    Empty,
+    /// `!n` refers past the oldest recorded query
+    OutOfRange { requested: usize, available: usize },
+}
+
+impl std::fmt::Display for HistoryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HistoryError::Empty => write!(f, "query history is empty"),
+            HistoryError::OutOfRange {
+                requested,
+                available,
+            } => write!(
+                f,
+                "query history has {} entr{}, cannot recall !{}",
+                available,
+                if *available == 1 { "y" } else { "ies" },
+                requested
+            ),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+/// Recently executed queries, oldest first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct QueryHistory {
+    entries: Vec<String>,
+}
+
+impl QueryHistory {
+    /// Create a history from a list of queries, oldest first.
+    pub fn new(entries: Vec<String>) -> Self {
+        Self { entries }
+    }
+
+    /// Default history file (`<data dir>/treemd/query_history`).
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::data_dir().map(|p| p.join("treemd").join("query_history"))
+    }
+
+    /// History file in use: `$TREEMD_QUERY_HISTORY` if set, otherwise
+    /// [`Self::default_path`].
+    pub fn path() -> Option<PathBuf> {
+        std::env::var_os("TREEMD_QUERY_HISTORY")
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .or_else(Self::default_path)
+    }
+
+    /// Load history from `path`; a missing or unreadable file is empty history.
+    pub fn load(path: &Path) -> Self {
+        let entries = fs::read_to_string(path)
+            .map(|content| {
+                content
+                    .lines()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self { entries }
+    }
+
+    /// Write the newest `limit` entries to `path`.
+    pub fn save(&self, path: &Path, limit: usize) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let start = self.entries.len().saturating_sub(limit);
+        let mut contents = self.entries[start..].join("\n");
+        contents.push('\n');
+        fs::write(path, contents)
+    }
+
+    /// Recorded queries, oldest first.
+    pub fn entries(&self) -> &[String] {
+        &self.entries
+    }
+
+    /// Record a query, skipping an immediate repeat of the last one.
+    ///
+    /// Multi-line queries are joined onto one line so the file stays
+    /// one-query-per-line.
+    pub fn push(&mut self, query: &str) {
+        let query = query.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+        if query.trim().is_empty() || self.entries.last() == Some(&query) {
+            return;
+        }
+        self.entries.push(query);
+    }
+
+    /// The `n`th most recent query (1-indexed).
+    pub fn recent(&self, n: usize) -> Result<&str, HistoryError> {
+        if self.entries.is_empty() {
+            return Err(HistoryError::Empty);
+        }
+        n.checked_sub(1)
+            .and_then(|back| self.entries.iter().rev().nth(back))
+            .map(String::as_str)
+            .ok_or(HistoryError::OutOfRange {
+                requested: n,
+                available: self.entries.len(),
+            })
+    }
+
+    /// Substitute a `!!` or `!n` reference with the query it recalls.
+    ///
+    /// Returns `Ok(None)` when `query` is not a history reference, so it
+    /// should be parsed as written.
+    pub fn expand(&self, query: &str) -> Result<Option<&str>, HistoryError> {
+        match history_reference(query) {
+            Some(n) => self.recent(n).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Parse `!!` (→ 1) or `!n` (→ n); anything else is not a history reference.
+fn history_reference(query: &str) -> Option<usize> {
+    let rest = query.trim().strip_prefix('!')?;
+    if rest == "!" {
+        return Some(1);
+    }
+    if rest.is_empty() || !rest.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    rest.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> QueryHistory {
+        QueryHistory::new(vec![
+            ".h1".to_string(),
+            ".code[rust]".to_string(),
+            ".h2 | text".to_string(),
+        ])
+    }
+
+    #[test]
+    fn bang_bang_recalls_last_query() {
+        assert_eq!(history().expand("!!"), Ok(Some(".h2 | text")));
+        assert_eq!(history().expand(" !! "), Ok(Some(".h2 | text")));
+    }
+
+    #[test]
+    fn bang_n_recalls_nth_most_recent() {
+        let h = history();
+        assert_eq!(h.expand("!1"), Ok(Some(".h2 | text")));
+        assert_eq!(h.expand("!2"), Ok(Some(".code[rust]")));
+        assert_eq!(h.expand("!3"), Ok(Some(".h1")));
+        assert_eq!(
+            h.expand("!4"),
+            Err(HistoryError::OutOfRange {
+                requested: 4,
+                available: 3
+            })
+        );
+        assert!(h.expand("!0").is_err());
+    }
+
+    #[test]
+    fn non_references_pass_through() {
+        let h = history();
+        assert_eq!(h.expand(".h1"), Ok(None));
+        assert_eq!(h.expand("!foo"), Ok(None));
+        assert_eq!(h.expand("!"), Ok(None));
+        assert_eq!(h.expand("not !!"), Ok(None));
+    }
+
+    #[test]
+    fn empty_history_errors() {
+        let h = QueryHistory::default();
+        assert_eq!(h.expand("!!"), Err(HistoryError::Empty));
+        assert_eq!(h.expand("!2"), Err(HistoryError::Empty));
+    }
+
+    #[test]
+    fn push_skips_repeats_and_save_truncates() {
+        let mut h = history();
+        h.push(".h2 | text");
+        h.push(".link\n| .url");
+        assert_eq!(h.entries().len(), 4);
+        assert_eq!(h.entries()[3], ".link | .url");
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("query_history");
+        h.save(&path, 2).unwrap();
+        let loaded = QueryHistory::load(&path);
+        assert_eq!(loaded.entries(), &[".h2 | text", ".link | .url"]);
+    }
+}
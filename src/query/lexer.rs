@@ -55,6 +55,8 @@ pub enum TokenKind {
 
     // Identifiers
     Ident(String),
+    /// `@name` format filter
+    Format(String),
 
     // End of input
     Eof,
@@ -101,6 +103,7 @@ impl TokenKind {
             TokenKind::String(_) => "string",
             TokenKind::Number(_) => "number",
             TokenKind::Ident(_) => "identifier",
+            TokenKind::Format(_) => "format",
             TokenKind::Eof => "end of input",
         }
     }
@@ -375,6 +378,26 @@ impl<'a> Lexer<'a> {
 
             c if c.is_alphabetic() || c == '_' => self.read_identifier(start, c),
 
+            '@' => match self.peek() {
+                Some(c) if c.is_alphabetic() || c == '_' => {
+                    self.advance();
+                    let name = match self.read_identifier(start, c).kind {
+                        TokenKind::Ident(name) => name,
+                        // Keywords are not formats, but let the parser say so
+                        other => other.name().trim_matches('\'').to_string(),
+                    };
+                    Token::new(TokenKind::Format(name), Span::new(start, self.pos))
+                }
+                _ => {
+                    return Err(QueryError::new(
+                        QueryErrorKind::UnexpectedChar('@'),
+                        Span::new(start, self.pos),
+                        self.input.to_string(),
+                    )
+                    .with_help("Formats are written '@name', e.g. '@uri'"));
+                }
+            },
+
             c => {
                 return Err(QueryError::new(
                     QueryErrorKind::UnexpectedChar(c),
@@ -21,6 +21,7 @@
 //! ```
 
 mod ast;
+mod batch;
 mod error;
 mod eval;
 mod lexer;
@@ -29,10 +30,12 @@ mod registry;
 mod value;
 
 pub mod builtins;
+pub mod history;
 
 // Re-exports for public API
 pub use ast::Span;
 pub use ast::{Expr, Query};
+pub use batch::{FileResults, json_array};
 pub use error::{QueryError, QueryErrorKind};
 pub use eval::{Engine, EvalContext};
 pub use registry::{Function, FunctionRegistry, Registry};
@@ -67,6 +70,27 @@ pub fn parse(query_str: &str) -> Result<Query, QueryError> {
     parser::parse(&tokens, query_str)
 }
 
+/// Run a query over many files, formatting each file's results.
+///
+/// The query is parsed once, then the files are read, parsed and queried on
+/// a pool of threads sharing one function [`Registry`]. Results come back in
+/// the order of `paths`; a file that cannot be read or whose query fails at
+/// runtime gets an error entry instead of stopping the batch. Only a query
+/// that does not parse is an error for the whole call. With `raw`, string
+/// results are formatted as by [`format_output_raw`]. In the JSON formats
+/// each file's output is one `{"file": ..., "results": [...]}` object, on a
+/// single line unless pretty-printed; [`json_array`] joins them.
+///
+/// A panic while querying a file is resumed on the calling thread.
+pub fn execute_many(
+    paths: &[std::path::PathBuf],
+    query_str: &str,
+    format: OutputFormat,
+    raw: bool,
+) -> Result<Vec<FileResults>, QueryError> {
+    batch::execute_many(paths, query_str, format, raw)
+}
+
 /// Create a new query engine with default configuration.
 pub fn engine(doc: &Document) -> Engine {
     Engine::new(doc)
@@ -104,7 +128,30 @@ pub fn format_output(values: &[Value], format: OutputFormat) -> String {
     output::format(values, format)
 }
 
+/// Format query results like [`format_output`], but with string results
+/// written without JSON quoting, one per line (`--raw-output`).
+pub fn format_output_raw(values: &[Value], format: OutputFormat) -> String {
+    output::format_raw(values, format)
+}
+
 mod output;
+mod results;
+
+/// Build a browsable document from query results, one section per result.
+///
+/// `source` is the name of the queried file `doc`; results with a known
+/// position link back to it (see [`parser::content::line_anchor`](crate::parser::content::line_anchor)).
+pub fn results_document(
+    doc: &Document,
+    values: &[Value],
+    source: Option<&str>,
+    query_str: &str,
+) -> Document {
+    let anchors = doc.github_anchors();
+    crate::parser::parse_markdown(&results::results_markdown(
+        values, source, &anchors, query_str,
+    ))
+}
 
 /// Output format for query results.
 #[derive(Debug, Clone, Copy, Default)]
@@ -122,6 +169,15 @@ pub enum OutputFormat {
     Markdown,
     /// Tree structure with box-drawing
     Tree,
+    /// Comma-separated values (RFC 4180)
+    Csv,
+}
+
+impl OutputFormat {
+    /// Whether this is one of the JSON formats.
+    pub fn is_json(self) -> bool {
+        matches!(self, Self::Json | Self::JsonPretty | Self::JsonLines)
+    }
 }
 
 impl std::str::FromStr for OutputFormat {
@@ -131,10 +187,11 @@ impl std::str::FromStr for OutputFormat {
         match s.to_lowercase().as_str() {
             "plain" | "text" => Ok(Self::Plain),
             "json" => Ok(Self::Json),
-            "json-pretty" | "jsonpretty" => Ok(Self::JsonPretty),
+            "json-pretty" | "jsonpretty" | "jsonp" => Ok(Self::JsonPretty),
             "jsonl" | "jsonlines" | "ndjson" => Ok(Self::JsonLines),
             "md" | "markdown" => Ok(Self::Markdown),
             "tree" => Ok(Self::Tree),
+            "csv" => Ok(Self::Csv),
             _ => Err(format!("Unknown output format: {}", s)),
         }
     }
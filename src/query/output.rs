@@ -12,9 +12,29 @@ pub fn format(values: &[Value], format: OutputFormat) -> String {
         OutputFormat::JsonLines => format_json_lines(values),
         OutputFormat::Markdown => format_markdown(values),
         OutputFormat::Tree => format_tree(values),
+        OutputFormat::Csv => format_csv(values),
     }
 }
 
+/// Format query results with string results written bare, one per line,
+/// like `jq -r`. Other results keep the JSON formats' encoding; formats
+/// other than JSON never quote strings and are unchanged.
+pub fn format_raw(values: &[Value], format: OutputFormat) -> String {
+    let pretty = match format {
+        OutputFormat::Json | OutputFormat::JsonLines => false,
+        OutputFormat::JsonPretty => true,
+        _ => return self::format(values, format),
+    };
+    values
+        .iter()
+        .map(|value| match value {
+            Value::String(s) => s.clone(),
+            _ => format_json(std::slice::from_ref(value), pretty),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 fn format_plain(values: &[Value]) -> String {
     values
         .iter()
@@ -100,6 +120,19 @@ fn format_plain_value(value: &Value) -> String {
             .collect::<Vec<_>>()
             .join("\n"),
         Value::Paragraph(p) => p.content.clone(),
+        Value::Task(t) => {
+            let checkbox = if t.checked { "[x]" } else { "[ ]" };
+            format!("- {} {}", checkbox, t.text)
+        }
+        Value::Callout(c) => {
+            std::iter::once(format!("[!{}] {}", c.kind.to_ascii_uppercase(), c.title))
+                .chain(c.text.lines().map(str::to_string))
+                .map(|line| format!("> {}", line).trim_end().to_string())
+                .collect::<Vec<_>>()
+                .join("\n")
+        }
+        Value::Footnote(f) => format!("[^{}]: {}", f.label, f.text),
+        Value::Definition(d) => format!("{}\n: {}", d.term, d.definition),
         Value::Document(d) => {
             format!(
                 "Document: {} headings, {} words",
@@ -135,6 +168,79 @@ fn format_json_lines(values: &[Value]) -> String {
         .join("\n")
 }
 
+/// Format results as CSV.
+///
+/// Object-like values (objects and elements) produce a header row from the
+/// union of their keys, in first-seen order, and one row per value. Arrays
+/// become one row each. Anything else is a single column, one value per row.
+fn format_csv(values: &[Value]) -> String {
+    let json_values: Vec<serde_json::Value> = values.iter().map(value_to_json).collect();
+
+    let rows: Vec<Vec<String>> =
+        if !json_values.is_empty() && json_values.iter().all(serde_json::Value::is_object) {
+            let mut header: Vec<String> = Vec::new();
+            for key in json_values
+                .iter()
+                .filter_map(|v| v.as_object())
+                .flat_map(|obj| obj.keys())
+            {
+                if !header.contains(key) {
+                    header.push(key.clone());
+                }
+            }
+            let data = json_values.iter().filter_map(|v| v.as_object()).map(|obj| {
+                header
+                    .iter()
+                    .map(|key| obj.get(key).map(csv_cell).unwrap_or_default())
+                    .collect()
+            });
+            std::iter::once(header.clone()).chain(data).collect()
+        } else if !json_values.is_empty() && json_values.iter().all(serde_json::Value::is_array) {
+            json_values
+                .iter()
+                .filter_map(|v| v.as_array())
+                .map(|items| items.iter().map(csv_cell).collect())
+                .collect()
+        } else {
+            values.iter().map(|v| vec![format_plain_value(v)]).collect()
+        };
+
+    rows.iter()
+        .map(|row| {
+            row.iter()
+                .map(|field| csv_quote(field))
+                .collect::<Vec<_>>()
+                .join(",")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render one CSV cell; nested arrays/objects are embedded as compact JSON.
+fn csv_cell(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Number(n) => match n.as_f64() {
+            Some(f) if f.fract() == 0.0 && f.abs() < 1e15 => (f as i64).to_string(),
+            _ => n.to_string(),
+        },
+        serde_json::Value::Bool(b) => b.to_string(),
+        serde_json::Value::Array(_) | serde_json::Value::Object(_) => {
+            serde_json::to_string(value).unwrap_or_default()
+        }
+    }
+}
+
+/// Quote a field per RFC 4180 when it contains a comma, quote, or line break.
+fn csv_quote(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
 fn value_to_json(value: &Value) -> serde_json::Value {
     match value {
         Value::Null => serde_json::Value::Null,
@@ -162,6 +268,7 @@ fn value_to_json(value: &Value) -> serde_json::Value {
                 "type": "code",
                 "language": c.language,
                 "content": c.content,
+                "code": c.content,
                 "start_line": c.start_line,
                 "end_line": c.end_line,
             })
@@ -172,6 +279,7 @@ fn value_to_json(value: &Value) -> serde_json::Value {
                 "text": l.text,
                 "url": l.url,
                 "link_type": l.link_type.as_str(),
+                "line": l.line,
             })
         }
         Value::Image(i) => {
@@ -180,6 +288,7 @@ fn value_to_json(value: &Value) -> serde_json::Value {
                 "alt": i.alt,
                 "src": i.src,
                 "title": i.title,
+                "line": i.line,
             })
         }
         Value::Table(t) => {
@@ -187,6 +296,8 @@ fn value_to_json(value: &Value) -> serde_json::Value {
                 "type": "table",
                 "headers": t.headers,
                 "rows": t.rows,
+                "align": t.alignments,
+                "line": t.line,
             })
         }
         Value::List(l) => {
@@ -199,18 +310,54 @@ fn value_to_json(value: &Value) -> serde_json::Value {
                         "checked": i.checked,
                     })
                 }).collect::<Vec<_>>(),
+                "line": l.line,
             })
         }
         Value::Blockquote(b) => {
             serde_json::json!({
                 "type": "blockquote",
                 "content": b.content,
+                "line": b.line,
             })
         }
         Value::Paragraph(p) => {
             serde_json::json!({
                 "type": "paragraph",
                 "content": p.content,
+                "line": p.line,
+            })
+        }
+        Value::Task(t) => {
+            serde_json::json!({
+                "type": "task",
+                "text": t.text,
+                "checked": t.checked,
+                "line": t.line,
+            })
+        }
+        Value::Callout(c) => {
+            serde_json::json!({
+                "type": "callout",
+                "kind": c.kind,
+                "title": c.title,
+                "text": c.text,
+                "line": c.line,
+            })
+        }
+        Value::Footnote(f) => {
+            serde_json::json!({
+                "type": "footnote",
+                "label": f.label,
+                "text": f.text,
+                "line": f.line,
+            })
+        }
+        Value::Definition(d) => {
+            serde_json::json!({
+                "type": "definition",
+                "term": d.term,
+                "definition": d.definition,
+                "line": d.line,
             })
         }
         Value::Document(d) => {
@@ -359,4 +506,29 @@ mod tests {
         assert!(output.contains("42"));
         assert!(output.contains("hello"));
     }
+
+    #[test]
+    fn raw_output_leaves_strings_unquoted() {
+        let values = vec![
+            Value::String("say \"hi\"".to_string()),
+            Value::Number(42.0),
+            Value::String("bye".to_string()),
+        ];
+        assert_eq!(
+            format(&values, OutputFormat::JsonLines),
+            "\"say \\\"hi\\\"\"\n42.0\n\"bye\""
+        );
+        assert_eq!(
+            format_raw(&values, OutputFormat::JsonLines),
+            "say \"hi\"\n42.0\nbye"
+        );
+        assert_eq!(
+            format_raw(&values, OutputFormat::Json),
+            format_raw(&values, OutputFormat::JsonLines)
+        );
+        assert_eq!(
+            format_raw(&values, OutputFormat::Plain),
+            format(&values, OutputFormat::Plain)
+        );
+    }
 }
@@ -402,6 +402,23 @@ fn parse_unary_expr_inner(p: &mut Parser) -> Result<Expr, QueryError> {
     let start_span = p.current_span();
 
     if p.matches(&[TokenKind::Not]) {
+        // A bare `not` with nothing to negate is jq's `not` filter, as in
+        // `select(.checked | not)`.
+        if matches!(
+            p.current_kind(),
+            TokenKind::RParen
+                | TokenKind::Pipe
+                | TokenKind::Comma
+                | TokenKind::RBracket
+                | TokenKind::RBrace
+                | TokenKind::Eof
+        ) {
+            return Ok(Expr::Function {
+                name: "not".to_string(),
+                args: Vec::new(),
+                span: start_span,
+            });
+        }
         let expr = parse_unary_expr(p)?;
         let end_span = expr.span();
         return Ok(Expr::Unary {
@@ -557,11 +574,33 @@ fn parse_primary_expr(p: &mut Parser) -> Result<Expr, QueryError> {
             return Ok(Expr::Identity);
         }
 
+        // Quoted property: ."code" reads a key that is also an element name
+        if let TokenKind::String(name) = p.current_kind().clone() {
+            let name_span = p.current_span();
+            p.advance();
+            return Ok(Expr::Property {
+                name,
+                span: span.merge(name_span),
+            });
+        }
+
         // Element or property selector
         if let TokenKind::Ident(name) = p.current_kind().clone() {
             let name_span = p.current_span();
             p.advance();
 
+            // Dotted function call: .codeblocks("rust")
+            if p.matches(&[TokenKind::LParen]) {
+                let args = parse_function_args(p)?;
+                let end_span = p.current_span();
+                p.expect(&TokenKind::RParen)?;
+                return Ok(Expr::Function {
+                    name,
+                    args,
+                    span: span.merge(end_span),
+                });
+            }
+
             // Check if it's an element type
             if let Some(kind) = ElementKind::from_str(&name) {
                 // Parse optional filters
@@ -673,6 +712,27 @@ fn parse_primary_expr(p: &mut Parser) -> Result<Expr, QueryError> {
         });
     }
 
+    // Format filter: @base64
+    if let TokenKind::Format(name) = p.current_kind().clone() {
+        p.advance();
+        return match StringFormat::from_name(&name) {
+            Some(format) => Ok(Expr::Format { format, span }),
+            None => Err(QueryError::new(
+                QueryErrorKind::UnknownFormat(name),
+                span,
+                p.source.to_string(),
+            )
+            .with_help(format!(
+                "available formats: {}",
+                StringFormat::ALL
+                    .iter()
+                    .map(|f| format!("@{}", f.name()))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ))),
+        };
+    }
+
     // Function call or identifier
     if let TokenKind::Ident(name) = p.current_kind().clone() {
         let name_span = p.current_span();
@@ -902,11 +962,13 @@ fn parse_object_literal(p: &mut Parser, start_span: Span) -> Result<Expr, QueryE
                 ));
             };
 
-            // Colon
-            p.expect(&TokenKind::Colon)?;
-
-            // Value
-            let value = parse_piped_expr(p).map(Expr::from)?;
+            // `key: value`, or a bare `key` as shorthand for `key: .key`
+            let value = if p.check(&TokenKind::Comma) || p.check(&TokenKind::RBrace) {
+                None
+            } else {
+                p.expect(&TokenKind::Colon)?;
+                Some(parse_piped_expr(p).map(Expr::from)?)
+            };
             pairs.push((key, value));
 
             if !p.matches(&[TokenKind::Comma]) {
@@ -1138,6 +1200,15 @@ mod tests {
         assert!(parse_str("if true then 1 elif true then 2 end end").is_err());
     }
 
+    #[test]
+    fn test_quoted_property_is_not_an_element() {
+        let query = parse_str(".\"code\"").unwrap();
+        assert!(matches!(
+            &query.expressions[0].stages[0],
+            Expr::Property { name, .. } if name == "code"
+        ));
+    }
+
     #[test]
     fn test_property_chain_is_pipe() {
         // `.h2.text` desugars to `.h2 | .text`.
@@ -1178,6 +1249,35 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_object_shorthand_keys() {
+        let query = parse_str("{text, depth: .level}").unwrap();
+        if let Expr::Object { pairs, .. } = &query.expressions[0].stages[0] {
+            assert_eq!(pairs[0].0, "text");
+            assert!(pairs[0].1.is_none());
+            assert_eq!(pairs[1].0, "depth");
+            assert!(matches!(pairs[1].1, Some(Expr::Property { .. })));
+        } else {
+            panic!("Expected Object, got {:?}", query.expressions[0].stages[0]);
+        }
+        assert!(parse_str("{text .level}").is_err());
+    }
+
+    #[test]
+    fn test_format_filters() {
+        let query = parse_str(".link | .url | @uri").unwrap();
+        assert!(matches!(
+            query.expressions[0].stages[2],
+            Expr::Format {
+                format: StringFormat::Uri,
+                ..
+            }
+        ));
+        let err = parse_str(".h | @nope").unwrap_err();
+        assert!(matches!(err.0.kind, QueryErrorKind::UnknownFormat(ref n) if n == "nope"));
+        assert_eq!(err.0.span, Span::new(5, 10));
+    }
+
     #[test]
     fn test_deeply_nested_parens_errors_not_overflow() {
         // 50k nested parens must error with RecursionLimit, not stack-overflow.
@@ -141,6 +141,11 @@ impl Registry {
         None
     }
 
+    /// Canonical name for `name`: the alias target, or `name` itself.
+    pub fn resolve_name<'a>(&'a self, name: &'a str) -> &'a str {
+        self.aliases.get(name).map_or(name, String::as_str)
+    }
+
     /// Check if a function exists.
     pub fn has_function(&self, name: &str) -> bool {
         self.functions.contains_key(name) || self.aliases.contains_key(name)
@@ -0,0 +1,206 @@
+//! Query results as a browsable document (`--query ... --interactive`).
+//!
+//! Each result becomes a `##` section under a `#` heading naming the query,
+//! so the normal outline lists one entry per match. Results with a known
+//! source position link back to it: headings by anchor, everything else by a
+//! `#L<line>` anchor that the TUI resolves to the enclosing section.
+
+use super::value::Value;
+
+/// Longest label shown in the outline before it is cut off.
+const MAX_LABEL_CHARS: usize = 60;
+
+/// Markdown for `values`, the results of `query` run against the file named
+/// `source` (`None` when the document came from stdin and cannot be linked),
+/// whose headings have the GitHub anchors `anchors`.
+pub(crate) fn results_markdown(
+    values: &[Value],
+    source: Option<&str>,
+    anchors: &[String],
+    query: &str,
+) -> String {
+    let mut out = format!("# {}\n\n", one_line(query));
+    out.push_str(&match values.len() {
+        1 => "1 result".to_string(),
+        n => format!("{} results", n),
+    });
+    if let Some(source) = source {
+        out.push_str(&format!(" in [{}]({})", source, link_target(source, None)));
+    }
+    out.push_str(".\n");
+
+    for (i, value) in values.iter().enumerate() {
+        out.push_str(&format!("\n## {}\n\n", label(value, i + 1)));
+        if let Some(source) = source
+            && let Some(link) = source_link(value, source, anchors)
+        {
+            out.push_str(&link);
+            out.push_str("\n\n");
+        }
+        let body = body(value);
+        if !body.is_empty() {
+            out.push_str(&body);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+/// Outline label for the `n`th result.
+fn label(value: &Value, n: usize) -> String {
+    let text = match value {
+        Value::Heading(h) => h.text.clone(),
+        Value::Code(c) => match c.language.as_deref().filter(|l| !l.is_empty()) {
+            Some(lang) => format!("Code: {}", lang),
+            None => "Code".to_string(),
+        },
+        Value::Link(l) if l.text.trim().is_empty() => l.url.clone(),
+        Value::Image(i) if i.alt.trim().is_empty() => i.src.clone(),
+        Value::Table(t) => format!("Table: {}", t.headers.join(", ")),
+        Value::Object(_) | Value::Array(_) | Value::Document(_) | Value::FrontMatter(_) => {
+            String::new()
+        }
+        other => other.to_text(),
+    };
+    let text = one_line(&text);
+    if text.is_empty() {
+        return format!("Result {}", n);
+    }
+    match text.char_indices().nth(MAX_LABEL_CHARS) {
+        Some((cut, _)) => format!("{}…", text[..cut].trim_end()),
+        None => text,
+    }
+}
+
+/// `[file:line](file#anchor)` pointing at where `value` came from, if known.
+fn source_link(value: &Value, source: &str, anchors: &[String]) -> Option<String> {
+    let line = match value.get_property("line")? {
+        Value::Number(n) => n as usize,
+        _ => return None,
+    };
+    let anchor = match value {
+        Value::Heading(h) if h.index < anchors.len() => anchors[h.index].clone(),
+        _ => format!("L{}", line),
+    };
+    Some(format!(
+        "[{}:{}]({})",
+        source,
+        line,
+        link_target(source, Some(&anchor))
+    ))
+}
+
+/// Markdown shown under a result's heading.
+fn body(value: &Value) -> String {
+    match value {
+        Value::Heading(h) => h.content.trim().to_string(),
+        Value::Code(c) => {
+            let fence = "`".repeat(longest_backtick_run(&c.content).max(2) + 1);
+            format!(
+                "{fence}{}\n{}\n{fence}",
+                c.language.as_deref().unwrap_or(""),
+                c.content.trim_end_matches('\n')
+            )
+        }
+        Value::Link(l) => format!("<{}>", l.url),
+        Value::Image(i) => format!("![{}]({})", i.alt, i.src),
+        Value::Table(t) => {
+            let row = |cells: &[String]| format!("| {} |", cells.join(" | "));
+            let mut lines = vec![
+                row(&t.headers),
+                row(&vec!["---".to_string(); t.headers.len()]),
+            ];
+            lines.extend(t.rows.iter().map(|r| row(r)));
+            lines.join("\n")
+        }
+        Value::List(l) => l
+            .items
+            .iter()
+            .map(|item| format!("- {}", item.content))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Value::Object(_) | Value::Array(_) | Value::FrontMatter(_) => format!(
+            "```json\n{}\n```",
+            serde_json::to_string_pretty(value).unwrap_or_default()
+        ),
+        Value::Document(_) => String::new(),
+        other => other.to_text(),
+    }
+}
+
+/// Link destination for `file`, in angle brackets when it contains spaces.
+fn link_target(file: &str, anchor: Option<&str>) -> String {
+    let target = match anchor {
+        Some(anchor) => format!("{}#{}", file, anchor),
+        None => file.to_string(),
+    };
+    if target.contains(char::is_whitespace) {
+        format!("<{}>", target)
+    } else {
+        target
+    }
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn longest_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`').map(str::len).max().unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    const DOC: &str = "# Guide\n\n## Install\n\nRun it.\n\n```sh\ncargo install treemd\n```\n\n## Usage\n\nOpen a file.\n";
+
+    fn browse(query: &str) -> String {
+        let doc = parse_markdown(DOC);
+        let values = crate::query::execute(&doc, query).unwrap();
+        results_markdown(&values, Some("guide.md"), &doc.github_anchors(), query)
+    }
+
+    #[test]
+    fn headings_become_sections_linking_back() {
+        let md = browse(".h2");
+        let doc = parse_markdown(&md);
+        let titles: Vec<_> = doc.headings.iter().map(|h| h.text.as_str()).collect();
+        assert_eq!(titles, [".h2", "Install", "Usage"]);
+        assert!(md.contains("2 results in [guide.md](guide.md)."));
+        assert!(md.contains("[guide.md:3](guide.md#install)\n\nRun it."));
+        assert!(md.contains("[guide.md:11](guide.md#usage)\n\nOpen a file."));
+    }
+
+    #[test]
+    fn repeated_headings_link_to_their_own_anchor() {
+        let doc = parse_markdown("# Log\n\n## Notes\n\na\n\n## Notes\n\nb\n");
+        let values = crate::query::execute(&doc, ".h2").unwrap();
+        let md = results_markdown(&values, Some("log.md"), &doc.github_anchors(), ".h2");
+        assert!(md.contains("[log.md:3](log.md#notes)\n\na"));
+        assert!(md.contains("[log.md:7](log.md#notes-1)\n\nb"));
+    }
+
+    #[test]
+    fn other_values_link_by_line_and_keep_their_markup() {
+        let md = browse(".code");
+        assert!(md.contains(
+            "## Code: sh\n\n[guide.md:7](guide.md#L7)\n\n```sh\ncargo install treemd\n```"
+        ));
+
+        let md = browse(".h2 | {text, level}");
+        assert!(md.contains("## Result 1\n\n```json"));
+        assert!(!md.contains("guide.md#"));
+    }
+
+    #[test]
+    fn labels_are_single_line_and_bounded() {
+        let long = Value::String(format!("first\nsecond {}", "x".repeat(100)));
+        let label = label(&long, 1);
+        assert!(label.starts_with("first second x"));
+        assert!(label.ends_with('…'));
+        assert_eq!(label.chars().count(), MAX_LABEL_CHARS + 1);
+        assert_eq!(link_target("my notes.md", Some("L3")), "<my notes.md#L3>");
+    }
+}
@@ -56,6 +56,18 @@ pub enum Value {
     /// Paragraph element
     Paragraph(ParagraphValue),
 
+    /// Task list item
+    Task(TaskValue),
+
+    /// GitHub alert / Obsidian callout
+    Callout(CalloutValue),
+
+    /// Footnote definition
+    Footnote(FootnoteValue),
+
+    /// Definition list entry
+    Definition(DefinitionValue),
+
     /// Full document reference
     Document(DocumentValue),
 
@@ -81,6 +93,10 @@ impl Value {
             Value::List(_) => ValueKind::List,
             Value::Blockquote(_) => ValueKind::Blockquote,
             Value::Paragraph(_) => ValueKind::Paragraph,
+            Value::Task(_) => ValueKind::Task,
+            Value::Callout(_) => ValueKind::Callout,
+            Value::Footnote(_) => ValueKind::Footnote,
+            Value::Definition(_) => ValueKind::Definition,
             Value::Document(_) => ValueKind::Document,
             Value::FrontMatter(_) => ValueKind::FrontMatter,
         }
@@ -131,14 +147,21 @@ impl Value {
         }
     }
 
-    /// Try to get this value as an object.
+    /// Try to get this value as an object (frontmatter included).
     pub fn as_object(&self) -> Option<&IndexMap<String, Value>> {
         match self {
-            Value::Object(o) => Some(o),
+            Value::Object(o) | Value::FrontMatter(o) => Some(o),
             _ => None,
         }
     }
 
+    /// Keys of an object value, sorted.
+    pub fn sorted_keys(&self) -> Option<Vec<&str>> {
+        let mut keys: Vec<&str> = self.as_object()?.keys().map(String::as_str).collect();
+        keys.sort_unstable();
+        Some(keys)
+    }
+
     /// Get a property from this value by name.
     ///
     /// This is the core property access mechanism used by `.property` syntax.
@@ -153,6 +176,10 @@ impl Value {
             Value::List(l) => l.get_property(name),
             Value::Blockquote(b) => b.get_property(name),
             Value::Paragraph(p) => p.get_property(name),
+            Value::Task(t) => t.get_property(name),
+            Value::Callout(c) => c.get_property(name),
+            Value::Footnote(f) => f.get_property(name),
+            Value::Definition(d) => d.get_property(name),
             Value::Document(d) => d.get_property(name),
             Value::FrontMatter(fm) => fm.get(name).cloned(),
             _ => None,
@@ -189,6 +216,10 @@ impl Value {
                 .join("\n"),
             Value::Blockquote(b) => b.content.clone(),
             Value::Paragraph(p) => p.content.clone(),
+            Value::Task(t) => t.text.clone(),
+            Value::Callout(c) => c.text.clone(),
+            Value::Footnote(f) => f.text.clone(),
+            Value::Definition(d) => d.definition.clone(),
             Value::Document(d) => d.content.clone(),
             Value::FrontMatter(fm) => serde_json::to_string(fm).unwrap_or_default(),
         }
@@ -271,7 +302,7 @@ impl<T: Into<Value>> From<Option<T>> for Value {
 }
 
 /// Value type enumeration for type checking and error messages.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ValueKind {
     Null,
     Bool,
@@ -287,6 +318,10 @@ pub enum ValueKind {
     List,
     Blockquote,
     Paragraph,
+    Task,
+    Callout,
+    Footnote,
+    Definition,
     Document,
     FrontMatter,
 }
@@ -308,6 +343,10 @@ impl fmt::Display for ValueKind {
             ValueKind::List => "list",
             ValueKind::Blockquote => "blockquote",
             ValueKind::Paragraph => "paragraph",
+            ValueKind::Task => "task",
+            ValueKind::Callout => "callout",
+            ValueKind::Footnote => "footnote",
+            ValueKind::Definition => "definition",
             ValueKind::Document => "document",
             ValueKind::FrontMatter => "frontmatter",
         };
@@ -358,6 +397,7 @@ pub struct CodeValue {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub language: Option<String>,
     pub content: String,
+    /// 1-indexed lines of the opening and closing fence
     pub start_line: usize,
     pub end_line: usize,
 }
@@ -370,8 +410,8 @@ impl CodeValue {
                 .clone()
                 .map(Value::String)
                 .or(Some(Value::Null)),
-            "text" | "content" => Some(Value::String(self.content.clone())),
-            "start_line" => Some(Value::Number(self.start_line as f64)),
+            "text" | "content" | "code" => Some(Value::String(self.content.clone())),
+            "line" | "start_line" => Some(Value::Number(self.start_line as f64)),
             "end_line" => Some(Value::Number(self.end_line as f64)),
             "lines" => Some(Value::Number(self.content.lines().count() as f64)),
             _ => None,
@@ -387,6 +427,10 @@ pub struct LinkValue {
     #[serde(rename = "type")]
     pub link_type: LinkType,
     pub offset: usize,
+    /// 1-indexed source line the link starts on
+    pub line: usize,
+    /// True for bare `<https://...>` autolinks
+    pub autolink: bool,
 }
 
 impl LinkValue {
@@ -396,6 +440,8 @@ impl LinkValue {
             "url" => Some(Value::String(self.url.clone())),
             "type" => Some(Value::String(self.link_type.as_str().to_string())),
             "offset" => Some(Value::Number(self.offset as f64)),
+            "line" => Some(Value::Number(self.line as f64)),
+            "autolink" => Some(Value::Bool(self.autolink)),
             _ => None,
         }
     }
@@ -429,6 +475,8 @@ pub struct ImageValue {
     pub src: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// 1-indexed source line, when known
+    pub line: Option<usize>,
 }
 
 impl ImageValue {
@@ -437,6 +485,7 @@ impl ImageValue {
             "alt" | "text" => Some(Value::String(self.alt.clone())),
             "src" | "url" => Some(Value::String(self.src.clone())),
             "title" => self.title.clone().map(Value::String).or(Some(Value::Null)),
+            "line" => self.line.map(|l| Value::Number(l as f64)),
             _ => None,
         }
     }
@@ -448,6 +497,8 @@ pub struct TableValue {
     pub headers: Vec<String>,
     pub rows: Vec<Vec<String>>,
     pub alignments: Vec<String>,
+    /// 1-indexed source line of the header row, when known
+    pub line: Option<usize>,
 }
 
 impl TableValue {
@@ -466,12 +517,13 @@ impl TableValue {
                     .collect(),
             )),
             "cols" | "columns" => Some(Value::Number(self.headers.len() as f64)),
-            "alignments" => Some(Value::Array(
+            "align" | "alignments" => Some(Value::Array(
                 self.alignments
                     .iter()
                     .map(|a| Value::String(a.clone()))
                     .collect(),
             )),
+            "line" => self.line.map(|l| Value::Number(l as f64)),
             _ => None,
         }
     }
@@ -482,6 +534,8 @@ impl TableValue {
 pub struct ListValue {
     pub ordered: bool,
     pub items: Vec<ListItemValue>,
+    /// 1-indexed source line of the first item, when known
+    pub line: Option<usize>,
 }
 
 impl ListValue {
@@ -495,6 +549,7 @@ impl ListValue {
                     .collect(),
             )),
             "length" | "count" => Some(Value::Number(self.items.len() as f64)),
+            "line" => self.line.map(|l| Value::Number(l as f64)),
             _ => None,
         }
     }
@@ -512,12 +567,15 @@ pub struct ListItemValue {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlockquoteValue {
     pub content: String,
+    /// 1-indexed source line, when known
+    pub line: Option<usize>,
 }
 
 impl BlockquoteValue {
     pub fn get_property(&self, name: &str) -> Option<Value> {
         match name {
             "text" | "content" => Some(Value::String(self.content.clone())),
+            "line" => self.line.map(|l| Value::Number(l as f64)),
             _ => None,
         }
     }
@@ -527,12 +585,99 @@ impl BlockquoteValue {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ParagraphValue {
     pub content: String,
+    /// 1-indexed source line, when known
+    pub line: Option<usize>,
 }
 
 impl ParagraphValue {
     pub fn get_property(&self, name: &str) -> Option<Value> {
         match name {
             "text" | "content" => Some(Value::String(self.content.clone())),
+            "line" => self.line.map(|l| Value::Number(l as f64)),
+            _ => None,
+        }
+    }
+}
+
+/// Task list item value (`- [ ]` / `- [x]`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TaskValue {
+    pub text: String,
+    pub checked: bool,
+    /// 1-indexed source line
+    pub line: usize,
+}
+
+impl TaskValue {
+    pub fn get_property(&self, name: &str) -> Option<Value> {
+        match name {
+            "text" | "content" => Some(Value::String(self.text.clone())),
+            "checked" | "done" => Some(Value::Bool(self.checked)),
+            "line" => Some(Value::Number(self.line as f64)),
+            _ => None,
+        }
+    }
+}
+
+/// Callout value (`> [!NOTE]` and friends).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CalloutValue {
+    /// Kind, lower-cased: `note`, `warning`, ...
+    pub kind: String,
+    pub title: String,
+    /// Body, without the marker line
+    pub text: String,
+    /// 1-indexed source line, when known
+    pub line: Option<usize>,
+}
+
+impl CalloutValue {
+    pub fn get_property(&self, name: &str) -> Option<Value> {
+        match name {
+            "kind" | "type" => Some(Value::String(self.kind.clone())),
+            "title" => Some(Value::String(self.title.clone())),
+            "text" | "content" => Some(Value::String(self.text.clone())),
+            "line" => self.line.map(|l| Value::Number(l as f64)),
+            _ => None,
+        }
+    }
+}
+
+/// Footnote definition value (`[^label]: text`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FootnoteValue {
+    pub label: String,
+    pub text: String,
+    /// 1-indexed source line of the definition
+    pub line: usize,
+}
+
+impl FootnoteValue {
+    pub fn get_property(&self, name: &str) -> Option<Value> {
+        match name {
+            "label" | "id" => Some(Value::String(self.label.clone())),
+            "text" | "content" => Some(Value::String(self.text.clone())),
+            "line" => Some(Value::Number(self.line as f64)),
+            _ => None,
+        }
+    }
+}
+
+/// Definition list entry value: one term and one of its definitions.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DefinitionValue {
+    pub term: String,
+    pub definition: String,
+    /// 1-indexed source line of the term, when known
+    pub line: Option<usize>,
+}
+
+impl DefinitionValue {
+    pub fn get_property(&self, name: &str) -> Option<Value> {
+        match name {
+            "term" => Some(Value::String(self.term.clone())),
+            "definition" | "text" | "content" => Some(Value::String(self.definition.clone())),
+            "line" => self.line.map(|l| Value::Number(l as f64)),
             _ => None,
         }
     }
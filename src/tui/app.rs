@@ -1,24 +1,49 @@
-use crate::config::Config;
-use crate::keybindings::{Action, KeybindingMode, Keybindings};
+use crate::config::{Config, CustomTheme, ScrollStep, WidthSpec};
+use crate::keybindings::{Action, KeybindingMode, Keybindings, SequenceTimer};
+use crate::parser::validate::ConfigError;
 use crate::parser::{Document, HeadingNode, Link, extract_links};
+use crate::tui::clipboard::Clipboard;
+use crate::tui::file_browser::{FileBrowser, group_files_by_frontmatter};
+use crate::tui::file_list::{FileList, FilePosition};
+use crate::tui::fuzzy::fuzzy_match;
 use crate::tui::help_text;
 use crate::tui::interactive::{ElementType, InteractiveState};
+use crate::tui::jumplist::JumpList;
 use crate::tui::kitty_animation::{self, KittyAnimation};
+use crate::tui::landing::LandingFlash;
+use crate::tui::marks::{MarkCommand, Marks};
+use crate::tui::mouse::{self, PaneAreas};
+use crate::tui::status::StatusQueue;
 use crate::tui::syntax::SyntaxHighlighter;
 use crate::tui::terminal_compat::ColorMode;
 use crate::tui::theme::{Theme, ThemeName};
+use crate::tui::view_state::{self, FileView, VIEW_STATE_LIMIT, ViewState};
 use crossterm::event::{KeyCode, KeyModifiers};
 use indexmap::IndexMap;
 use ratatui::widgets::{ListState, ScrollbarState};
 #[cfg(all(feature = "mermaid", unix))]
 use std::collections::HashMap;
 use std::collections::HashSet;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::time::{Duration, Instant};
 
 /// Special marker for the document overview entry (shows entire file content)
 pub const DOCUMENT_OVERVIEW: &str = "(Document)";
 
+/// Columns moved by one `h`/`l` when content scrolls sideways
+const HSCROLL_STEP: u16 = 4;
+
+/// Columns (or percentage points) one `{`/`}` resizes the outline by
+const OUTLINE_WIDTH_STEP: i16 = 2;
+
+/// Outline widths `[`/`]` cycle through; any other configured width is
+/// treated as a deliberate custom setting.
+const OUTLINE_WIDTH_PRESETS: [WidthSpec; 3] = [
+    WidthSpec::Percent(20),
+    WidthSpec::Percent(30),
+    WidthSpec::Percent(40),
+];
+
 /// Result of executing an action
 #[derive(Debug)]
 pub enum ActionResult {
@@ -38,6 +63,47 @@ pub enum Focus {
     Content,
 }
 
+/// How the line-number gutter numbers rows, after vim's `number` and
+/// `relativenumber` options.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineNumberStyle {
+    /// Source line numbers
+    Absolute,
+    /// Distance from the top row of the viewport, which shows 0
+    Relative,
+    /// Distance from the top row, which shows its own line number
+    Hybrid,
+}
+
+impl LineNumberStyle {
+    /// The style named in the config (unknown names mean absolute).
+    pub fn from_config(name: &str) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "relative" => Self::Relative,
+            "hybrid" => Self::Hybrid,
+            _ => Self::Absolute,
+        }
+    }
+
+    /// Config name of the style.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Absolute => "absolute",
+            Self::Relative => "relative",
+            Self::Hybrid => "hybrid",
+        }
+    }
+
+    /// The style after this one when cycling.
+    pub fn next(self) -> Self {
+        match self {
+            Self::Absolute => Self::Relative,
+            Self::Relative => Self::Hybrid,
+            Self::Hybrid => Self::Absolute,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum AppMode {
     Normal,
@@ -48,11 +114,13 @@ pub enum AppMode {
     Help,
     CellEdit,
     ConfirmFileCreate,
+    ConfirmOverwrite,      // Ask before an export replaces an existing file
     DocSearch,             // In-document search mode (n/N navigation)
     CommandPalette,        // Fuzzy-searchable command palette
     ConfirmSaveWidth,      // Modal confirmation for saving outline width
     ConfirmSaveBeforeQuit, // Prompt to save unsaved changes before quitting
     ConfirmSaveBeforeNav,  // Prompt to save unsaved changes before navigating
+    SectionStats,          // Word count and reading time popup
     FilePicker,            // File picker modal for switching files
     FileSearch,            // File picker search/filter mode
 }
@@ -66,6 +134,30 @@ pub enum PendingNavigation {
     Forward,
     /// Load a file (relative path, optional anchor)
     LoadFile(PathBuf, Option<String>),
+    /// Switch to the next (`true`) or previous command-line file
+    SwitchFile(bool),
+}
+
+/// An export waiting for the user to confirm replacing the file at its path
+#[derive(Debug, Clone)]
+pub enum PendingExport {
+    /// A section as a standalone HTML page
+    Html { path: PathBuf, html: String },
+    /// A mermaid or plantuml block's source
+    Diagram {
+        path: PathBuf,
+        kind: crate::tui::diagram::DiagramKind,
+        source: String,
+    },
+}
+
+impl PendingExport {
+    /// File the export is written to.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Html { path, .. } | Self::Diagram { path, .. } => path,
+        }
+    }
 }
 
 /// Available commands in the command palette
@@ -87,12 +179,39 @@ pub enum CommandAction {
     CollapseLevel,
     /// Expand headings at a specific level (parsed from command argument)
     ExpandLevel,
+    /// Jump to the outline heading numbered by the query (`:42`)
+    GoToHeading,
     Quit,
     /// Forward to the regular keybinding action dispatch — lets the palette
     /// cover every command without duplicating handlers
     Dispatch(Action),
 }
 
+impl CommandAction {
+    /// The keybinding action this command runs, for commands that have one
+    fn keybinding_action(self) -> Option<Action> {
+        match self {
+            CommandAction::Undo => Some(Action::UndoEdit),
+            CommandAction::ToggleOutline => Some(Action::ToggleOutline),
+            CommandAction::ToggleHeadingMarkers => Some(Action::ToggleHeadingMarkers),
+            CommandAction::ToggleHelp => Some(Action::ToggleHelp),
+            CommandAction::ToggleRawSource => Some(Action::ToggleRawSource),
+            CommandAction::ToggleMouseCapture => Some(Action::ToggleMouseCapture),
+            CommandAction::JumpToTop => Some(Action::First),
+            CommandAction::JumpToBottom => Some(Action::Last),
+            CommandAction::CollapseAll => Some(Action::CollapseAll),
+            CommandAction::ExpandAll => Some(Action::ExpandAll),
+            CommandAction::Quit => Some(Action::Quit),
+            CommandAction::Dispatch(action) => Some(action),
+            CommandAction::SaveWidth
+            | CommandAction::SaveFile
+            | CommandAction::CollapseLevel
+            | CommandAction::ExpandLevel
+            | CommandAction::GoToHeading => None,
+        }
+    }
+}
+
 /// A command in the palette
 #[derive(Debug, Clone)]
 pub struct PaletteCommand {
@@ -124,6 +243,9 @@ impl PaletteCommand {
         if query_lower.is_empty() {
             return true;
         }
+        if self.action == CommandAction::GoToHeading && heading_number(query_lower).is_some() {
+            return true;
+        }
 
         // Aliases and name are static ASCII — compare case-insensitively without
         // allocating a lowercase copy of each.
@@ -135,6 +257,9 @@ impl PaletteCommand {
                 return true;
             }
         }
+        if self.words_match(query_lower) {
+            return true;
+        }
 
         // Fuzzy: every char of query appears in order in name (ASCII fold).
         let mut name_chars = self.name.chars().map(|c| c.to_ascii_lowercase()).peekable();
@@ -156,6 +281,9 @@ impl PaletteCommand {
         if query_lower.is_empty() {
             return 100;
         }
+        if self.action == CommandAction::GoToHeading && heading_number(query_lower).is_some() {
+            return 1000;
+        }
 
         for alias in self.aliases {
             if alias.eq_ignore_ascii_case(query_lower) {
@@ -173,8 +301,97 @@ impl PaletteCommand {
         if contains_ignore_ascii_case(self.name, query_lower) {
             return 200;
         }
+        if self.words_match(query_lower) {
+            return 150;
+        }
         100
     }
+
+    /// Whether each word of the query starts a later word of the name or of
+    /// the action it runs (`ToggleThemePicker` reads as "toggle theme
+    /// picker"), so "tog theme" finds the theme picker.
+    fn words_match(&self, query_lower: &str) -> bool {
+        let action_name = self
+            .action
+            .keybinding_action()
+            .map(|action| split_camel_case(&action.to_string()))
+            .unwrap_or_default();
+        [self.name, action_name.as_str()]
+            .iter()
+            .any(|text| words_in_order(text, query_lower))
+    }
+}
+
+/// "ToggleThemePicker" → "Toggle Theme Picker"
+fn split_camel_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 4);
+    for (i, c) in name.chars().enumerate() {
+        if i > 0 && c.is_ascii_uppercase() {
+            out.push(' ');
+        }
+        out.push(c);
+    }
+    out
+}
+
+/// Whether every word of `query_lower` is a prefix of a word of `text`, with
+/// the matched words in the same order and none used twice.
+fn words_in_order(text: &str, query_lower: &str) -> bool {
+    let mut words = text.split_whitespace();
+    let mut query = query_lower.split_whitespace().peekable();
+    if query.peek().is_none() {
+        return false;
+    }
+    query.all(|q| words.any(|w| starts_with_ignore_ascii_case(w, q)))
+}
+
+/// Aliases of the "Go to heading" command, which may be followed by a number
+const GO_TO_HEADING_ALIASES: &[&str] = &["heading", "goto"];
+
+/// The 1-based heading number a palette query asks for: a bare number
+/// (`42`) or one after a go-to-heading alias (`goto 42`).
+fn heading_number(query: &str) -> Option<usize> {
+    let number = match query.trim().split_once(' ') {
+        Some((word, number)) if GO_TO_HEADING_ALIASES.contains(&word) => number.trim(),
+        Some(_) => return None,
+        None => query.trim(),
+    };
+    number.parse().ok().filter(|&n| n > 0)
+}
+
+/// The palette: the hand-written [`PALETTE_COMMANDS`] followed by every other
+/// action that has a default `Normal` mode key, named by its description.
+pub fn palette_commands() -> &'static [PaletteCommand] {
+    static COMMANDS: std::sync::OnceLock<Vec<PaletteCommand>> = std::sync::OnceLock::new();
+    COMMANDS.get_or_init(|| {
+        use strum::IntoEnumIterator;
+
+        let defaults = Keybindings::default();
+        let covered: HashSet<Action> = PALETTE_COMMANDS
+            .iter()
+            .filter_map(|cmd| cmd.action.keybinding_action())
+            .collect();
+        let mut commands = PALETTE_COMMANDS.to_vec();
+        commands.extend(
+            Action::iter()
+                .filter(|action| {
+                    !matches!(action, Action::Noop | Action::OpenCommandPalette)
+                        && !covered.contains(action)
+                        && !defaults
+                            .keys_for_action(KeybindingMode::Normal, *action)
+                            .is_empty()
+                })
+                .map(|action| {
+                    PaletteCommand::new(
+                        action.description(),
+                        &[],
+                        action.category(),
+                        CommandAction::Dispatch(action),
+                    )
+                }),
+        );
+        commands
+    })
 }
 
 fn starts_with_ignore_ascii_case(haystack: &str, needle_lower: &str) -> bool {
@@ -288,6 +505,12 @@ pub const PALETTE_COMMANDS: &[PaletteCommand] = &[
         "Expand headings at specific level",
         CommandAction::ExpandLevel,
     ),
+    PaletteCommand::new(
+        "Go to heading",
+        GO_TO_HEADING_ALIASES,
+        "Jump to outline heading N (type :N)",
+        CommandAction::GoToHeading,
+    ),
     PaletteCommand::new(
         "Theme picker",
         &["theme", "colors"],
@@ -354,12 +577,48 @@ pub const PALETTE_COMMANDS: &[PaletteCommand] = &[
         "Copy the current heading's anchor link",
         CommandAction::Dispatch(Action::CopyAnchor),
     ),
+    PaletteCommand::new(
+        "Export diagram",
+        &["diagram", "mmd", "puml"],
+        "Write a mermaid/plantuml block's source to a file",
+        CommandAction::Dispatch(Action::ExportDiagram),
+    ),
+    PaletteCommand::new(
+        "Export theme",
+        &["dumptheme", "themetoml"],
+        "Copy the current theme's colors as a [[theme.custom]] block",
+        CommandAction::Dispatch(Action::ExportTheme),
+    ),
+    PaletteCommand::new(
+        "Next code theme",
+        &["codetheme", "syntax"],
+        "Highlight code blocks with the next syntax theme",
+        CommandAction::Dispatch(Action::CycleCodeTheme),
+    ),
+    PaletteCommand::new(
+        "Export HTML",
+        &["html", "export"],
+        "Write the selected section and its subsections to an HTML file",
+        CommandAction::Dispatch(Action::ExportHtml),
+    ),
+    PaletteCommand::new(
+        "Copy heading link",
+        &["mdlink", "headinglink"],
+        "Copy a markdown link to the current heading",
+        CommandAction::Dispatch(Action::CopyHeadingLink),
+    ),
     PaletteCommand::new(
         "Toggle TODO filter",
         &["todo", "tasks"],
         "Show only sections containing open tasks",
         CommandAction::Dispatch(Action::ToggleTodoFilter),
     ),
+    PaletteCommand::new(
+        "Toggle outline blocks",
+        &["blocks", "elements"],
+        "List code blocks, tables and images in the outline",
+        CommandAction::Dispatch(Action::ToggleOutlineMode),
+    ),
     PaletteCommand::new(
         "Go back",
         &["back", "b"],
@@ -410,7 +669,7 @@ pub struct DocSearchState {
 #[derive(Debug)]
 pub struct CommandPaletteState {
     pub query: String,
-    /// Indices into `PALETTE_COMMANDS`, ordered by match score.
+    /// Indices into [`palette_commands`], ordered by match score.
     pub filtered: Vec<usize>,
     pub selected: usize,
 }
@@ -419,7 +678,7 @@ impl Default for CommandPaletteState {
     fn default() -> Self {
         Self {
             query: String::new(),
-            filtered: (0..PALETTE_COMMANDS.len()).collect(),
+            filtered: (0..palette_commands().len()).collect(),
             selected: 0,
         }
     }
@@ -491,8 +750,22 @@ pub struct PendingEdit {
     pub new_value: String,
 }
 
+/// Source line numbers for the content pane's gutter, kept while the
+/// section and its layout stay the same.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GutterLines {
+    /// Heading whose section was numbered (`None` for the whole document)
+    pub heading: Option<usize>,
+    /// Rendered line each block starts at
+    pub block_starts: Vec<usize>,
+    /// Source line of each rendered line that starts a block
+    pub numbers: Vec<Option<usize>>,
+}
+
 pub struct App {
     pub document: Document,
+    abbreviations: crate::parser::Abbreviations, // `*[ABBR]: ...` definitions in the document
+    footnotes: crate::parser::Footnotes,         // `[^label]: ...` definitions in the document
     pub filename: String,
     pub tree: Vec<HeadingNode>,
     pub outline_state: ListState,
@@ -505,31 +778,77 @@ pub struct App {
     pub content_viewport_height: u16, // Actual viewport height for scroll calculations
     pub show_help: bool,
     pub help_scroll: u16,
+    /// Filter for the help popup's lines
+    pub help_query: String,
+    /// Typing into `help_query` (`/` in help)
+    pub help_search_active: bool,
     pub show_search: bool,
     pub outline_search_active: bool, // Whether search input is active (cursor visible)
     pub search_query: String,
+    /// Whether the outline filter also matches each section's own text
+    pub outline_search_text: bool,
+    /// Headings the outline filter matched by their text only
+    pub outline_text_hits: HashSet<usize>,
+    /// Lowercased text of each section (up to the next heading), built the
+    /// first time the outline filter searches text
+    section_text_lc: Option<Vec<String>>,
+    /// Gutter line numbers of the section last drawn
+    pub gutter_lines: Option<GutterLines>,
+    /// Last accepted outline search, for `n`/`N` after the filter is closed
+    pub last_outline_search: Option<String>,
     pub highlighter: SyntaxHighlighter,
     pub show_outline: bool,
     pub show_heading_markers: bool, // Show # prefixes in outline sidebar
-    /// Whether terminal mouse capture is active. When on, the scroll wheel drives
-    /// navigation but the terminal's native click-drag text selection is disabled.
-    /// Toggling it off hands the mouse back to the terminal so text can be selected
-    /// and copied. Enabled at startup to match `main.rs`'s best-effort capture.
+    pub wrap: bool,                 // Soft-wrap content; off scrolls long lines sideways
+    pub content_hscroll: u16,       // Horizontal content scroll (columns) when wrap is off
+    /// How far the content last drawn scrolls sideways: its widest line,
+    /// or with wrapping on, its widest code that doesn't wrap
+    pub content_hscroll_max: u16,
+    pub line_numbers: bool, // Source line-number gutter in the content pane
+    pub line_number_style: LineNumberStyle, // Absolute, relative or hybrid gutter numbers
+    pub watch: bool,        // Reload the file when it changes on disk
+    /// File that remembers where each file was left, so it reopens there
+    /// (`None` when positions are neither restored nor saved)
+    pub view_state_path: Option<PathBuf>,
+    /// Whether terminal mouse capture is active. When on, clicks and the scroll
+    /// wheel drive navigation but the terminal's native click-drag text selection
+    /// is disabled. Toggling it off hands the mouse back to the terminal so text
+    /// can be selected and copied. Starts as `ui.mouse`, which `main.rs` follows.
     pub mouse_capture: bool,
-    pub outline_width: u16, // Percentage: 20, 30, or 40
+    pub outline_width: WidthSpec, // Percentage or columns, resolved per frame
+    pub terminal_width: u16,      // Columns of the last frame (or resize)
+    pub pane_areas: PaneAreas,    // Where the last frame drew the panes (mouse hit-testing)
     /// Whether the config file had a custom (non-standard) outline width at startup.
     /// Used to protect power users' custom config values from being overwritten.
-    /// Standard values are 20, 30, 40; anything else is considered custom.
+    /// Standard values are 20%, 30%, 40%; anything else is considered custom.
     config_has_custom_outline_width: bool,
     pub bookmark_position: Option<String>, // Bookmarked heading text (was: outline position)
+    pub marks: Marks,                      // Named marks (`m a` / `' a`)
+    pub pending_mark: Option<MarkCommand>, // `m` or `'` waiting for a mark name
+    jumps: JumpList,                       // Headings left by big jumps (Ctrl+o / Ctrl+i)
+    search_origin: Option<usize>,          // Heading selected when the outline search began
     collapsed_headings: HashSet<String>,   // Track which headings are collapsed by text
-    pub filter_by_todos: bool,             // Filter outline to show only headings with open todos
+    reveal_depth: Option<usize>, // Outline depth shown by ExpandMoreLevels/CollapseMoreLevels
+    pub filter_by_todos: bool,   // Filter outline to show only headings with open todos
+    outline_all_blocks: bool,    // Also list code blocks, tables and images in the outline
+    previous_outline_block: Option<usize>, // Block entry last scrolled to, for change detection
+    /// Block entry to scroll to once the content pane knows where its blocks start
+    pub pending_block_scroll: Option<usize>,
+    /// Block entries (block index, label) of each heading's body, parsed once
+    /// per document
+    outline_block_cache: HashMap<usize, Vec<(usize, String)>>,
+    /// Syntax errors of the yaml/toml/json blocks drawn for each section
+    /// (by heading index), by block index; validated once per document
+    pub config_error_cache: HashMap<Option<usize>, HashMap<usize, Option<ConfigError>>>,
     pub current_theme: ThemeName,
     pub theme: Theme,
     pub show_theme_picker: bool,
     pub theme_picker_selected: usize,
-    pub theme_picker_original: Option<ThemeName>, // Original theme before picker opened (for cancel)
-    previous_selection: Option<Option<usize>>,    // Heading index for change detection
+    /// The committed theme while the picker previews another one: restored
+    /// on Esc, and used to draw the picker so it stays readable whatever
+    /// palette is being previewed
+    pub theme_picker_committed: Option<(ThemeName, Theme)>,
+    previous_selection: Option<Option<usize>>, // Heading index for change detection
     /// True when the cached content_height/scrollbar may be stale and need
     /// recomputation on the next `update_content_metrics`. Set by file
     /// reload, raw-source toggle, etc.
@@ -541,8 +860,22 @@ pub struct App {
     pub count_prefix: Option<usize>,
     pub current_file_path: PathBuf, // Path to current file for resolving relative links
     pub file_path_changed: bool,    // Flag to signal file watcher needs update
-    pub suppress_file_watch: bool,  // Skip next file watch check (after internal save)
-    pub links_in_view: Vec<Link>,   // Links in currently displayed content
+    /// Directory relative links may climb up to with `..` (where treemd was
+    /// started, if the first file is inside it)
+    link_root: PathBuf,
+    /// A large document still being parsed on a worker thread
+    pub pending_load: Option<crate::tui::loader::BackgroundLoad>,
+    /// Stdin still arriving (`treemd -`), shown as it is read
+    pub stdin_load: Option<crate::tui::loader::StdinLoad>,
+    /// URL the current document was fetched from; its relative links point
+    /// at the server, not the local disk
+    pub remote_source: Option<String>,
+    /// Placeholder path of `--interactive` query output, which has no file
+    /// behind it: while it is shown, edits, saves and the external editor
+    /// are refused
+    pub query_results: Option<PathBuf>,
+    pub suppress_file_watch: bool, // Skip next file watch check (after internal save)
+    pub links_in_view: Vec<Link>,  // Links in currently displayed content
     pub link_picker: LinkPickerState,
 
     // File picker state
@@ -551,10 +884,16 @@ pub struct App {
     pub file_picker_dir: Option<PathBuf>, // Custom directory for file picker
     pub show_hidden: bool,               // Whether to show hidden (dot) files and directories
 
-    pub file_history: Vec<FileState>,   // Back navigation stack
-    pub file_future: Vec<FileState>,    // Forward navigation stack (for undo back)
+    pub file_history: Vec<FileState>,      // Back navigation stack
+    pub file_future: Vec<FileState>,       // Forward navigation stack (for undo back)
+    pub file_list: FileList,               // Files from the command line (`>` / `<`)
+    pub file_browser: Option<FileBrowser>, // Directory being browsed (`treemd docs/`)
+    /// Directory browser left by opening a file; going back past the start
+    /// of the file history returns to it
+    browser_return: Option<FileBrowser>,
     pub status_message: Option<String>, // Temporary status message to display
-    pub status_message_time: Option<Instant>, // When the status message was set
+    /// Expiry of the status message and messages waiting to be shown
+    status_queue: StatusQueue,
 
     // Interactive element navigation
     pub interactive_state: InteractiveState,
@@ -569,9 +908,8 @@ pub struct App {
     pub pending_edits: Vec<PendingEdit>, // Stack of uncommitted edits
     pub has_unsaved_changes: bool,       // True if pending_edits is non-empty
 
-    // Persistent clipboard for Linux X11 compatibility
-    // On Linux, the clipboard instance must stay alive to serve paste requests
-    clipboard: Option<arboard::Clipboard>,
+    // Persistent clipboard (system or OSC 52, per `[terminal] clipboard`)
+    clipboard: Clipboard,
 
     // Configuration persistence
     config: Config,
@@ -587,6 +925,12 @@ pub struct App {
     pub pending_file_create: Option<PathBuf>,
     pub pending_file_create_message: Option<String>,
 
+    /// Export waiting for the user to confirm replacing the file at its path
+    pub pending_overwrite: Option<PendingExport>,
+
+    /// Title and counts shown by the section stats popup
+    pub section_stats: Option<(String, crate::parser::TextStats)>,
+
     /// In-document search (/ + n/N).
     pub doc_search: DocSearchState,
 
@@ -595,6 +939,8 @@ pub struct App {
 
     // Customizable keybindings
     pub keybindings: Keybindings,
+    sequence_timer: SequenceTimer, // Abandons incomplete chords/count prefixes
+    landing_flash: LandingFlash,   // Highlights the heading a followed link landed on
 
     // Pending navigation (for confirm save dialog when navigating with unsaved changes)
     pub pending_navigation: Option<PendingNavigation>,
@@ -650,6 +996,7 @@ pub struct FileState {
     pub selected_heading: Option<String>,
     pub content_scroll: u16,
     pub outline_state_selected: Option<usize>,
+    pub collapsed_headings: HashSet<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -659,6 +1006,7 @@ pub struct OutlineItem {
     pub expanded: bool,
     pub has_children: bool, // Track if this heading has children in the tree
     pub heading_index: Option<usize>, // Index into Document::headings
+    pub block_idx: Option<usize>, // Block within the heading's section (non-heading entries)
 }
 
 impl App {
@@ -672,6 +1020,10 @@ impl App {
     ) -> Self {
         let tree = document.build_tree();
         let collapsed_headings = HashSet::new();
+        let outline_all_blocks = config.outline_shows_all_blocks();
+        let landing_flash = LandingFlash::new(Duration::from_millis(config.ui.landing_flash_ms));
+        // Block entries need the app's footnotes and filters; they are added
+        // once it is built
         let mut outline_items = Self::flatten_tree(&tree, &collapsed_headings);
 
         // Add document overview entry if there's preamble content or no headings
@@ -685,6 +1037,7 @@ impl App {
                     expanded: true,
                     has_children: !outline_items.is_empty(),
                     heading_index: None,
+                    block_idx: None,
                 },
             );
         }
@@ -698,9 +1051,7 @@ impl App {
 
         // Load theme from config, apply color mode, then apply custom colors
         let current_theme = config.theme_name();
-        let theme = Theme::from_name(current_theme)
-            .with_color_mode(color_mode, current_theme)
-            .with_custom_colors(&config.theme, color_mode);
+        let theme = Theme::from_config(&config, current_theme, color_mode);
 
         // Load sublime color scheme directory
         let code_theme_dir = config.code_theme_dir_path();
@@ -711,15 +1062,19 @@ impl App {
         let outline_width = config.ui.outline_width;
 
         // Detect if config has a custom (non-standard) outline width
-        // Standard values: 20, 30, 40 - anything else is a custom power-user setting
-        let config_has_custom_outline_width =
-            outline_width != 20 && outline_width != 30 && outline_width != 40;
+        // Standard values: 20%, 30%, 40% - anything else is a custom power-user setting
+        let config_has_custom_outline_width = !OUTLINE_WIDTH_PRESETS.contains(&outline_width);
 
         // Load keybindings from config (before config is moved)
         let keybindings = config.keybindings();
+        let keybinding_conflicts = config.keybindings.validate();
+        let abbreviations = crate::parser::Abbreviations::parse(&document.content);
+        let footnotes = crate::parser::Footnotes::parse(&document.content);
 
-        Self {
+        let mut app = Self {
             document,
+            abbreviations,
+            footnotes,
             filename,
             tree,
             outline_state,
@@ -732,31 +1087,72 @@ impl App {
             content_viewport_height: 20, // Default, will be updated by UI on first render
             show_help: false,
             help_scroll: 0,
+            help_query: String::new(),
+            help_search_active: false,
             show_search: false,
             outline_search_active: false,
             search_query: String::new(),
-            highlighter: SyntaxHighlighter::new(code_theme, code_theme_dir),
+            outline_search_text: false,
+            outline_text_hits: HashSet::new(),
+            section_text_lc: None,
+            gutter_lines: None,
+            last_outline_search: None,
+            highlighter: SyntaxHighlighter::new(code_theme, code_theme_dir)
+                .with_languages(&config.syntax),
             show_outline: true,
             show_heading_markers: config.ui.outline_heading_markers,
-            mouse_capture: true,
+            wrap: config.ui.wrap,
+            content_hscroll: 0,
+            content_hscroll_max: 0,
+            line_numbers: config.ui.line_numbers,
+            line_number_style: LineNumberStyle::from_config(&config.ui.line_number_style),
+            watch: config.ui.watch,
+            // Tests never touch the real state file
+            view_state_path: if cfg!(test) {
+                None
+            } else {
+                config
+                    .ui
+                    .restore_position
+                    .then(ViewState::default_path)
+                    .flatten()
+            },
+            mouse_capture: config.ui.mouse,
             outline_width,
+            terminal_width: 0,
+            pane_areas: PaneAreas::default(),
             config_has_custom_outline_width,
             bookmark_position: None,
+            marks: Marks::default(),
+            pending_mark: None,
+            jumps: JumpList::default(),
+            search_origin: None,
             collapsed_headings,
+            reveal_depth: None,
             filter_by_todos: false,
+            outline_all_blocks,
+            previous_outline_block: None,
+            pending_block_scroll: None,
+            outline_block_cache: HashMap::new(),
+            config_error_cache: HashMap::new(),
             current_theme,
             theme,
             show_theme_picker: false,
             theme_picker_selected: 0,
-            theme_picker_original: None,
+            theme_picker_committed: None,
             previous_selection: None,
             metrics_dirty: true,
 
             // Link following state
             mode: AppMode::Normal,
             count_prefix: None,
+            link_root: Self::link_root_for(&file_path),
             current_file_path: file_path,
             file_path_changed: false,
+            pending_load: None,
+            stdin_load: None,
+            remote_source: None,
+            query_results: None,
             suppress_file_watch: false,
             links_in_view: Vec::new(),
             link_picker: LinkPickerState::default(),
@@ -769,8 +1165,12 @@ impl App {
 
             file_history: Vec::new(),
             file_future: Vec::new(),
-            status_message: None,
-            status_message_time: None,
+            file_list: FileList::default(),
+            file_browser: None,
+            browser_return: None,
+            // A warning, so it stays for the longer error/warning timeout
+            status_message: Self::keybinding_conflict_message(&keybinding_conflicts),
+            status_queue: StatusQueue::new(Duration::from_millis(config.ui.status_timeout_ms)),
 
             // Interactive element navigation
             interactive_state: InteractiveState::new(),
@@ -785,8 +1185,8 @@ impl App {
             pending_edits: Vec::new(),
             has_unsaved_changes: false,
 
-            // Initialize persistent clipboard (None if unavailable)
-            clipboard: arboard::Clipboard::new().ok(),
+            // Initialize persistent clipboard
+            clipboard: Clipboard::new(&config.terminal.clipboard),
 
             // Configuration persistence
             config,
@@ -801,6 +1201,9 @@ impl App {
             // Pending file creation (for confirm dialog)
             pending_file_create: None,
             pending_file_create_message: None,
+            pending_overwrite: None,
+
+            section_stats: None,
 
             // Document search state
             doc_search: DocSearchState::default(),
@@ -810,7 +1213,9 @@ impl App {
 
             // Customizable keybindings (loaded from config)
             // Note: keybindings() called before config is moved into struct
+            sequence_timer: SequenceTimer::new(keybindings.sequence_timeout()),
             keybindings,
+            landing_flash,
 
             // Pending navigation (for confirm save dialog)
             pending_navigation: None,
@@ -853,7 +1258,11 @@ impl App {
             // LaTeX detection
             latex_detected: false,
             latex_hint_shown: false,
+        };
+        if app.outline_all_blocks {
+            app.rebuild_outline_items();
         }
+        app
     }
 
     /// Initialize graphics protocol picker with stdio protocol detection.
@@ -1038,7 +1447,7 @@ impl App {
             return false;
         }
         match crate::tui::mermaid::render_mermaid_to_image(source, target_px) {
-            Ok(img) => {
+            crate::tui::mermaid::Rendered::Image(img) => {
                 let dims = (img.width(), img.height());
                 if let Some(picker) = self.picker.as_mut() {
                     let font_h = {
@@ -1059,7 +1468,9 @@ impl App {
                 }
                 false
             }
-            Err(e) => {
+            // Not cached: asked again once mermaid-cli finishes
+            crate::tui::mermaid::Rendered::Pending => false,
+            crate::tui::mermaid::Rendered::Failed(e) => {
                 self.mermaid_render_errors.insert(hash, e);
                 false
             }
@@ -1077,7 +1488,7 @@ impl App {
             return;
         }
         self.mermaid_needs_reindex = false;
-        let content_text = self.current_section_content();
+        let content_text = self.current_section_view();
         use crate::parser::content::parse_content;
         let blocks = parse_content(&content_text, 0);
         let rows = self.mermaid_placeholder_rows.clone();
@@ -1288,6 +1699,32 @@ impl App {
         }
     }
 
+    /// Catch up with a terminal resize before the next frame: scroll offsets
+    /// are pulled back within the new viewport (keeping the interactive
+    /// selection in view) and content metrics are marked for recomputing.
+    pub fn handle_resize(&mut self, width: u16, height: u16) {
+        self.terminal_width = width;
+        // Title (2), status and footer rows, the search bar when open, and
+        // the content pane's borders; the frame itself sets the exact value
+        let search_rows = if self.show_search || self.mode == AppMode::DocSearch {
+            3
+        } else {
+            0
+        };
+        self.set_viewport_height(height.saturating_sub(6 + search_rows));
+
+        // Clamp against the last frame's rendered height; the next frame
+        // re-measures the wrapped lines at the new width
+        self.content_scroll = self.content_scroll.min(self.max_content_scroll());
+        if self.mode == AppMode::Interactive {
+            self.scroll_to_interactive_element(self.content_viewport_height);
+        }
+        self.content_scroll_state = self
+            .content_scroll_state
+            .position(self.content_scroll as usize);
+        self.mark_metrics_dirty();
+    }
+
     /// Update the content viewport height (called by UI when terminal size is known)
     pub fn set_viewport_height(&mut self, height: u16) {
         self.content_viewport_height = height.max(1); // Ensure at least 1 to avoid divide-by-zero
@@ -1336,9 +1773,11 @@ impl App {
             AppMode::Help => KeybindingMode::Help,
             AppMode::CellEdit => KeybindingMode::CellEdit,
             AppMode::ConfirmFileCreate
+            | AppMode::ConfirmOverwrite
             | AppMode::ConfirmSaveWidth
             | AppMode::ConfirmSaveBeforeQuit
-            | AppMode::ConfirmSaveBeforeNav => KeybindingMode::ConfirmDialog,
+            | AppMode::ConfirmSaveBeforeNav
+            | AppMode::SectionStats => KeybindingMode::ConfirmDialog,
             AppMode::DocSearch => KeybindingMode::DocSearch,
             AppMode::CommandPalette => KeybindingMode::CommandPalette,
             AppMode::FilePicker => {
@@ -1377,6 +1816,14 @@ impl App {
     pub fn execute_action(&mut self, action: Action) -> ActionResult {
         use Action::*;
 
+        if self.file_browser.is_some()
+            && self.mode == AppMode::Normal
+            && !self.show_help
+            && let Some(result) = self.browser_action(action)
+        {
+            return result;
+        }
+
         match action {
             // === Miscellaneous ===
             Noop => {}
@@ -1407,9 +1854,7 @@ impl App {
                         self.next_file();
                     }
                 } else {
-                    for _ in 0..count {
-                        self.next();
-                    }
+                    self.next_n(count);
                 }
             }
             Previous => {
@@ -1419,23 +1864,29 @@ impl App {
                         self.previous_file();
                     }
                 } else {
-                    for _ in 0..count {
-                        self.previous();
-                    }
+                    self.previous_n(count);
                 }
             }
             First => {
-                self.clear_count();
-                self.first();
+                self.record_jump();
+                match self.count_prefix.take() {
+                    Some(n) => self.go_to_nth(n),
+                    None => self.first(),
+                }
             }
             Last => {
-                self.clear_count();
-                self.last();
+                self.record_jump();
+                match self.count_prefix.take() {
+                    Some(n) => self.go_to_nth(n),
+                    None => self.last(),
+                }
             }
             PageDown => {
                 self.clear_count();
                 if self.show_help {
                     self.scroll_help_page_down();
+                } else if self.mode == AppMode::Interactive {
+                    self.scroll_content_by(self.page_lines(), true);
                 } else {
                     self.scroll_page_down();
                 }
@@ -1444,30 +1895,105 @@ impl App {
                 self.clear_count();
                 if self.show_help {
                     self.scroll_help_page_up();
+                } else if self.mode == AppMode::Interactive {
+                    self.scroll_content_by(self.page_lines(), false);
                 } else {
                     self.scroll_page_up();
                 }
             }
+            HalfPageDown => {
+                self.clear_count();
+                if self.show_help {
+                    self.scroll_help_by(self.half_page_lines(), true);
+                } else if self.mode == AppMode::Interactive {
+                    self.scroll_content_by(self.half_page_lines(), true);
+                } else {
+                    self.scroll_half_page_down();
+                }
+            }
+            HalfPageUp => {
+                self.clear_count();
+                if self.show_help {
+                    self.scroll_help_by(self.half_page_lines(), false);
+                } else if self.mode == AppMode::Interactive {
+                    self.scroll_content_by(self.half_page_lines(), false);
+                } else {
+                    self.scroll_half_page_up();
+                }
+            }
             JumpToParent => {
                 self.clear_count();
                 self.jump_to_parent();
             }
+            ScrollLeft | ScrollRight if !self.scroll_keys_apply() => {
+                self.clear_count();
+                if self.wrap {
+                    self.set_status_message("Line wrap is on (W turns it off)");
+                }
+            }
+            ScrollLeft => {
+                let count = self.take_count();
+                for _ in 0..count {
+                    self.scroll_content_left();
+                }
+            }
+            ScrollRight => {
+                let count = self.take_count();
+                for _ in 0..count {
+                    self.scroll_content_right();
+                }
+            }
+            JumpBack => {
+                self.clear_count();
+                self.jump_back();
+            }
+            JumpForward => {
+                self.clear_count();
+                self.jump_forward();
+            }
 
             // === Outline ===
+            Expand if self.scrolls_sideways() => self.scroll_content_right(),
+            Collapse if self.scrolls_sideways() => self.scroll_content_left(),
             Expand => self.expand(),
             Collapse => self.collapse(),
             ToggleExpand => self.toggle_expand(),
+            ExpandMoreLevels => self.expand_more_levels(),
+            CollapseMoreLevels => self.collapse_more_levels(),
+            CollapseAll => self.collapse_all(),
+            ExpandAll => self.expand_all(),
+            FoldToLevel => {
+                // A count picks the level (`2zl`); otherwise the selected heading's
+                let level = self
+                    .count_prefix
+                    .take()
+                    .or_else(|| {
+                        let heading = self.selected_heading_index()?;
+                        self.document.headings.get(heading).map(|h| h.level)
+                    })
+                    .unwrap_or(1);
+                self.fold_to_level(level.min(6));
+            }
             ToggleFocus => self.toggle_focus(),
             ToggleFocusBack => self.toggle_focus_back(),
             ToggleOutline => self.toggle_outline(),
             OutlineWidthIncrease => self.cycle_outline_width(true),
             OutlineWidthDecrease => self.cycle_outline_width(false),
+            OutlineWidthGrow => self.resize_outline_width(OUTLINE_WIDTH_STEP),
+            OutlineWidthShrink => self.resize_outline_width(-OUTLINE_WIDTH_STEP),
             ToggleTodoFilter => self.toggle_todo_filter(),
             ToggleHeadingMarkers => self.toggle_heading_markers(),
+            ToggleOutlineMode => self.toggle_outline_mode(),
 
             // === Bookmarks ===
-            SetBookmark => self.set_bookmark(),
-            JumpToBookmark => self.jump_to_bookmark(),
+            SetBookmark => {
+                self.pending_mark = Some(MarkCommand::Set);
+                self.set_status_message("m: a-z names the mark, any other key sets the bookmark");
+            }
+            JumpToBookmark => {
+                self.pending_mark = Some(MarkCommand::Jump);
+                self.set_status_message("': a-z jumps to that mark, any other key to the bookmark");
+            }
 
             // === Mode Transitions ===
             EnterInteractiveMode => self.enter_interactive_mode(),
@@ -1476,6 +2002,7 @@ impl App {
             EnterSearchMode => self.toggle_search(),
             EnterDocSearch => self.enter_doc_search(),
             ToggleSearchMode => self.toggle_search_mode(),
+            ToggleOutlineSearchScope => self.toggle_outline_search_scope(),
             ExitMode => self.exit_current_mode(),
             OpenCommandPalette => self.open_command_palette(),
 
@@ -1497,6 +2024,7 @@ impl App {
                 }
                 _ => {}
             },
+            JumpToFootnote => self.footnote_jump(),
             LinkSearch => match self.mode {
                 AppMode::LinkFollow => self.start_link_search(),
                 AppMode::FilePicker => {
@@ -1522,7 +2050,7 @@ impl App {
                         self.interactive_state.next();
                     }
                     self.scroll_to_interactive_element(self.content_viewport_height);
-                    self.status_message = Some(self.interactive_state.status_text());
+                    self.status_message = Some(self.interactive_status_text());
                 }
             }
             InteractivePrevious => {
@@ -1541,7 +2069,7 @@ impl App {
                         self.interactive_state.previous();
                     }
                     self.scroll_to_interactive_element(self.content_viewport_height);
-                    self.status_message = Some(self.interactive_state.status_text());
+                    self.status_message = Some(self.interactive_status_text());
                 }
             }
             InteractiveActivate => {
@@ -1562,7 +2090,7 @@ impl App {
                     self.interactive_state.next();
                 }
                 self.scroll_to_interactive_element(self.content_viewport_height);
-                self.status_message = Some(self.interactive_state.status_text());
+                self.status_message = Some(self.interactive_status_text());
             }
             InteractivePreviousLink => {
                 let count = self.take_count();
@@ -1570,7 +2098,7 @@ impl App {
                     self.interactive_state.previous();
                 }
                 self.scroll_to_interactive_element(self.content_viewport_height);
-                self.status_message = Some(self.interactive_state.status_text());
+                self.status_message = Some(self.interactive_status_text());
             }
             InteractiveLeft => {
                 let count = self.take_count();
@@ -1599,22 +2127,49 @@ impl App {
                     self.set_status_message(&format!("✗ {}", e));
                 }
             }
+            CopyTableCsv => {
+                if let Err(e) = self.copy_table_csv() {
+                    self.set_status_message(&format!("✗ {}", e));
+                }
+            }
+            TableSort => self.sort_table(),
+            TableRowInsert => {
+                if let Err(e) = self.edit_table_row(crate::tui::edits::RowEdit::InsertBelow) {
+                    self.set_status_message(&format!("✗ {}", e));
+                }
+            }
+            TableRowDelete => {
+                if let Err(e) = self.edit_table_row(crate::tui::edits::RowEdit::Delete) {
+                    self.set_status_message(&format!("✗ {}", e));
+                }
+            }
 
             // === View ===
             ToggleRawSource => self.toggle_raw_source(),
+            ToggleWrap => self.toggle_wrap(),
+            ToggleLineNumbers => self.toggle_line_numbers(),
+            CycleLineNumberStyle => self.cycle_line_number_style(),
             ToggleMouseCapture => self.toggle_mouse_capture(),
             ToggleHelp => self.toggle_help(),
             ToggleThemePicker => self.toggle_theme_picker(),
             ApplyTheme => self.apply_selected_theme(),
+            ExportTheme => self.export_theme(),
+            CycleCodeTheme => self.cycle_code_theme(),
 
             // === Clipboard ===
             CopyContent => self.copy_content(),
             CopyAnchor => self.copy_anchor(),
+            CopyHeadingLink => self.copy_heading_link(),
+            CopyPlainText => self.copy_plain_text(),
+            ShowStats => self.show_section_stats(),
 
             // === File Operations ===
             GoBack => {
                 // Check if there's anything to go back to
                 if self.file_history.is_empty() {
+                    if self.browser_return.is_some() {
+                        self.return_to_browser();
+                    }
                     return ActionResult::Continue;
                 }
                 // Check for unsaved changes
@@ -1638,7 +2193,13 @@ impl App {
                     self.update_content_metrics();
                 }
             }
+            NextFile => self.switch_file(true),
+            PreviousFile => self.switch_file(false),
             OpenInEditor => {
+                if let Err(e) = self.ensure_backing_file() {
+                    self.status_message = Some(format!("✗ {}", e));
+                    return ActionResult::Continue;
+                }
                 let line = if self.mode == AppMode::Interactive {
                     // In interactive mode, jump to the current element's source line
                     self.interactive_element_source_line()
@@ -1648,6 +2209,8 @@ impl App {
                 };
                 return ActionResult::RunEditor(self.current_file_path.clone(), line);
             }
+            ExportDiagram => self.export_diagram(),
+            ExportHtml => self.export_html(),
             UndoEdit => {
                 self.clear_count();
                 if let Err(e) = self.undo_last_edit() {
@@ -1726,6 +2289,7 @@ impl App {
                 self.clear_count();
                 self.scroll_help_up();
             }
+            HelpSearch => self.toggle_help_search(),
 
             // === Theme Picker Navigation ===
             ThemePickerNext => self.theme_picker_next(),
@@ -1770,7 +2334,7 @@ impl App {
                 // If in table mode, exit table mode first (stay in interactive)
                 if self.interactive_state.is_in_table_mode() {
                     self.interactive_state.exit_table_mode();
-                    self.status_message = Some(self.interactive_state.status_text());
+                    self.status_message = Some(self.interactive_status_text());
                 } else {
                     self.exit_interactive_mode();
                 }
@@ -1827,9 +2391,11 @@ impl App {
             }
             AppMode::Normal
             | AppMode::ConfirmFileCreate
+            | AppMode::ConfirmOverwrite
             | AppMode::ConfirmSaveWidth
             | AppMode::ConfirmSaveBeforeQuit
-            | AppMode::ConfirmSaveBeforeNav => {
+            | AppMode::ConfirmSaveBeforeNav
+            | AppMode::SectionStats => {
                 // In normal mode, show hint for quitting
                 self.set_status_message("Press q to quit • : for commands • ? for help");
             }
@@ -1852,6 +2418,12 @@ impl App {
             if has_matches {
                 // Accept search - deactivate input but keep filter visible
                 self.outline_search_active = false;
+                if !self.search_query.is_empty() {
+                    self.last_outline_search = Some(self.search_query.clone());
+                }
+                if let Some(origin) = self.search_origin.take() {
+                    self.jumps.record(origin);
+                }
                 // show_search stays true to keep highlights visible
                 // User can now navigate with j/k, n/N, or press 's' to start new search
             } else {
@@ -1871,7 +2443,9 @@ impl App {
                     self.status_message = Some(format!("✗ Error: {}", e));
                 }
             }
+            AppMode::ConfirmOverwrite => self.confirm_overwrite(),
             AppMode::ConfirmSaveWidth => self.confirm_save_outline_width(),
+            AppMode::SectionStats => self.close_section_stats(),
             AppMode::ConfirmSaveBeforeQuit => {
                 // Save pending changes and quit
                 if let Err(e) = self.save_pending_edits_to_file() {
@@ -1918,7 +2492,9 @@ impl App {
     fn handle_cancel_action(&mut self) {
         match self.mode {
             AppMode::ConfirmFileCreate => self.cancel_file_create(),
+            AppMode::ConfirmOverwrite => self.cancel_overwrite(),
             AppMode::ConfirmSaveWidth => self.cancel_save_width_confirmation(),
+            AppMode::SectionStats => self.close_section_stats(),
             AppMode::ConfirmSaveBeforeQuit => {
                 // Cancel quit - go back to normal mode
                 self.mode = AppMode::Normal;
@@ -1999,12 +2575,26 @@ impl App {
                     self.status_message = Some(format!("✗ {}", e));
                 }
             }
+            Some(PendingNavigation::SwitchFile(forward)) => {
+                if let Err(e) = self.switch_file_internal(forward) {
+                    self.status_message = Some(format!("✗ {}", e));
+                }
+            }
             None => {}
         }
     }
 
     /// Handle backspace in search contexts
     fn handle_search_backspace(&mut self) {
+        if self.show_help {
+            if self.help_search_active {
+                let mut query = std::mem::take(&mut self.help_query);
+                query.pop();
+                self.set_help_query(query);
+            }
+            return;
+        }
+
         // Handle outline search - only if active
         if self.show_search && self.outline_search_active {
             self.search_backspace();
@@ -2093,6 +2683,33 @@ impl App {
         self.content_scroll_state = self.content_scroll_state.position(new_scroll as usize);
     }
 
+    /// Whether `h`/`l` scroll the content sideways instead of collapsing
+    /// and expanding: content focused with wrapping off.
+    fn scrolls_sideways(&self) -> bool {
+        !self.wrap && self.focus == Focus::Content && self.mode == AppMode::Normal
+    }
+
+    /// Whether `Shift+←`/`Shift+→` scroll: like `h`/`l` with wrapping off,
+    /// or through code in languages that don't wrap with it on.
+    fn scroll_keys_apply(&self) -> bool {
+        self.scrolls_sideways()
+            || (self.wrap
+                && self.content_hscroll_max > 0
+                && self.focus == Focus::Content
+                && self.mode == AppMode::Normal)
+    }
+
+    /// Scroll content right by a few columns (clamped when rendered). With
+    /// wrapping on, this scrolls code in languages set not to wrap.
+    fn scroll_content_right(&mut self) {
+        self.content_hscroll = self.content_hscroll.saturating_add(HSCROLL_STEP);
+    }
+
+    /// Scroll content left by a few columns
+    fn scroll_content_left(&mut self) {
+        self.content_hscroll = self.content_hscroll.saturating_sub(HSCROLL_STEP);
+    }
+
     /// Jump to link by index in filtered list
     fn jump_to_link(&mut self, idx: usize) {
         if let Some(display_idx) = self
@@ -2118,33 +2735,25 @@ impl App {
         self.set_status_message(msg);
     }
 
-    /// Set an informational status message that auto-expires after a few
-    /// seconds. Messages assigned directly to `status_message` (without a
-    /// timestamp) are treated as sticky and dismissed on the next keypress.
+    /// Show a status message now, for the full `status_timeout_ms` even if
+    /// the same text is already showing. Assigning `status_message` directly
+    /// also works; its time starts on the next [`Self::tick_status`].
     pub fn set_status_message(&mut self, msg: &str) {
         self.status_message = Some(msg.to_string());
-        self.status_message_time = Some(Instant::now());
+        self.status_queue.restart(msg, Instant::now());
     }
 
-    /// Clear status message if it has expired
-    pub fn clear_expired_status_message(&mut self) {
-        const STATUS_MESSAGE_TIMEOUT: Duration = Duration::from_secs(3);
-
-        if let Some(time) = self.status_message_time
-            && time.elapsed() >= STATUS_MESSAGE_TIMEOUT
-        {
-            self.status_message = None;
-            self.status_message_time = None;
-        }
+    /// Show a status message after the current one (and any already
+    /// waiting) expires, rather than replacing it.
+    pub fn queue_status_message(&mut self, msg: String) {
+        self.status_queue
+            .push(&mut self.status_message, msg, Instant::now());
     }
 
-    /// Dismiss a sticky status message (one set without a timestamp, e.g.
-    /// errors). Called on the next keypress so errors stay visible until the
-    /// user acts, while timed info messages expire on their own.
-    pub fn dismiss_sticky_status_message(&mut self) {
-        if self.status_message.is_some() && self.status_message_time.is_none() {
-            self.status_message = None;
-        }
+    /// Expire the status message and show the next queued one. Returns true
+    /// if the status line changed.
+    pub fn tick_status(&mut self, now: Instant) -> bool {
+        self.status_queue.tick(&mut self.status_message, now)
     }
 
     /// Delete the trailing whitespace-delimited word from a string (Ctrl+W).
@@ -2163,6 +2772,18 @@ impl App {
     pub fn apply_text_input_edit(&mut self, edit: TextInputEdit) -> bool {
         use TextInputEdit::*;
 
+        // Help search (help shows over every mode)
+        if self.show_help && self.help_search_active {
+            let mut query = std::mem::take(&mut self.help_query);
+            match edit {
+                Insert(c) => query.push(c),
+                Clear => query.clear(),
+                DeleteWord => Self::delete_last_word(&mut query),
+            }
+            self.set_help_query(query);
+            return true;
+        }
+
         // Outline search
         if self.show_search && self.outline_search_active {
             match edit {
@@ -2290,6 +2911,214 @@ impl App {
         self.count_prefix.is_some()
     }
 
+    /// Whether input is waiting for more keys (count prefix or partial chord)
+    pub fn has_pending_keys(&self) -> bool {
+        self.count_prefix.is_some() || self.pending_mark.is_some() || self.in_key_sequence()
+    }
+
+    /// Whether the keys pressed so far start a longer binding (`z` of `z M`)
+    pub fn in_key_sequence(&self) -> bool {
+        self.keybindings
+            .is_sequence_ongoing(self.current_keybinding_mode())
+    }
+
+    /// Restart the sequence timeout after a handled key press
+    pub fn record_key_for_sequence(&mut self, now: Instant) {
+        let pending = self.has_pending_keys();
+        self.sequence_timer.record_key(pending, now);
+    }
+
+    /// End a pending count prefix, mark or chord once it has waited longer
+    /// than `[keybindings] sequence_timeout_ms`. A mark with no name yet
+    /// uses the unnamed bookmark. A chord whose keys are already
+    /// bound on their own (`g` while `g g` is possible) queues that action for
+    /// [`take_queued_action`](Self::take_queued_action); a lone digit jumps to
+    /// that heading; anything else is cancelled. Returns true if something
+    /// expired.
+    pub fn expire_pending_keys(&mut self, now: Instant) -> bool {
+        if !self.sequence_timer.expire(now) {
+            return false;
+        }
+        if self.pending_mark.is_some() {
+            self.finish_mark(None);
+            return true;
+        }
+        let chord = self
+            .keybindings
+            .is_sequence_ongoing(self.current_keybinding_mode());
+        if self.keybindings.finish_sequence() {
+            return true;
+        }
+        let count = self.count_prefix.take();
+        match Self::lone_digit_heading(count, chord) {
+            Some(index) if self.mode == AppMode::Normal => self.jump_to_heading(index),
+            _ => self.set_status_message("Sequence cancelled"),
+        }
+        true
+    }
+
+    /// Startup warning for problems found in the configured keybindings.
+    fn keybinding_conflict_message(conflicts: &[String]) -> Option<String> {
+        match conflicts {
+            [] => None,
+            [only] => Some(format!("⚠ Keybinding conflict: {}", only)),
+            [first, rest @ ..] => Some(format!(
+                "⚠ {} keybinding conflicts: {} (+{} more)",
+                conflicts.len(),
+                first,
+                rest.len()
+            )),
+        }
+    }
+
+    /// Heading index for a count that timed out with no motion after it.
+    ///
+    /// Digits start a count first; only a single digit `1`-`9` left on its own
+    /// falls back to jumping to that heading. Longer counts, or a count
+    /// followed by an unfinished chord, are cancelled.
+    fn lone_digit_heading(count: Option<usize>, chord: bool) -> Option<usize> {
+        match count {
+            Some(n @ 1..=9) if !chord => Some(n - 1),
+            _ => None,
+        }
+    }
+
+    /// Action still to run after the last key press or sequence timeout
+    pub fn take_queued_action(&mut self) -> Option<Action> {
+        self.keybindings.take_queued()
+    }
+
+    /// Strength of the landing highlight on the selected heading (1.0 fading
+    /// to 0.0), or `None` when no followed link landed recently.
+    pub fn landing_flash_strength(&self) -> Option<f32> {
+        self.landing_flash.strength(Instant::now())
+    }
+
+    /// Whether the landing highlight is fading and needs redraws.
+    pub fn landing_flash_active(&self) -> bool {
+        self.landing_flash.is_active(Instant::now())
+    }
+
+    /// Drop a finished landing highlight. Returns true if it just ended.
+    pub fn expire_landing_flash(&mut self, now: Instant) -> bool {
+        self.landing_flash.expire(now)
+    }
+
+    /// Parse `content` on a worker thread and show it once ready (see
+    /// [`poll_pending_load`](Self::poll_pending_load)), jumping to `anchor`
+    /// if given. The current document stays up meanwhile.
+    pub fn load_in_background(
+        &mut self,
+        content: String,
+        filename: String,
+        path: PathBuf,
+        anchor: Option<String>,
+    ) {
+        self.pending_load = Some(crate::tui::loader::BackgroundLoad::spawn(
+            content, filename, path, anchor,
+        ));
+    }
+
+    /// Swap in a background-parsed document if it has arrived. Returns true
+    /// when something changed and the screen needs redrawing.
+    pub fn poll_pending_load(&mut self) -> bool {
+        let Some(load) = &self.pending_load else {
+            return false;
+        };
+        match load.try_take() {
+            Ok(None) => false,
+            Ok(Some(document)) => {
+                let Some(load) = self.pending_load.take() else {
+                    return false;
+                };
+                self.load_document(document, load.filename, load.path);
+                match load.anchor {
+                    Some(anchor) => {
+                        let _ = self.jump_to_anchor(&anchor);
+                    }
+                    // The file opened at startup shows up here once parsed
+                    None => self.return_to_remembered_position(),
+                }
+                true
+            }
+            Err(e) => {
+                self.pending_load = None;
+                self.status_message = Some(format!("✗ {}", e));
+                true
+            }
+        }
+    }
+
+    /// Show stdin as it arrives: the outline fills in while the stream is
+    /// still open (see [`poll_stdin_load`](Self::poll_stdin_load)).
+    pub fn stream_input(&mut self, stream: crate::input::InputStream) {
+        self.stdin_load = Some(crate::tui::loader::StdinLoad::new(stream));
+    }
+
+    /// Re-parse streamed stdin if more has arrived. Returns true when
+    /// something changed and the screen needs redrawing.
+    pub fn poll_stdin_load(&mut self) -> bool {
+        use crate::tui::loader::StdinUpdate;
+
+        let Some(load) = &mut self.stdin_load else {
+            return false;
+        };
+        match load.poll(Instant::now()) {
+            StdinUpdate::Waiting => false,
+            StdinUpdate::Content { content, finished } => {
+                if finished {
+                    self.stdin_load = None;
+                }
+                if finished && content.len() >= crate::tui::BACKGROUND_LOAD_BYTES {
+                    let (filename, path) = (self.filename.clone(), self.current_file_path.clone());
+                    self.load_in_background(content, filename, path, None);
+                } else {
+                    self.show_streamed_content(&content);
+                }
+                true
+            }
+            StdinUpdate::Failed(e) => {
+                self.stdin_load = None;
+                self.status_message = Some(format!("✗ {}", e));
+                true
+            }
+        }
+    }
+
+    /// Swap in a newer parse of streamed stdin, keeping the selected heading
+    /// and scroll position.
+    fn show_streamed_content(&mut self, content: &str) {
+        let current_selection = self.selected_heading_text().map(|s| s.to_string());
+        let current_scroll = self.content_scroll;
+        let document = crate::parser::parse_markdown(content);
+        let (filename, path) = (self.filename.clone(), self.current_file_path.clone());
+        self.load_document(document, filename, path);
+        if let Some(heading) = current_selection {
+            self.select_by_text(&heading);
+        }
+        if (current_scroll as usize) < self.content_height {
+            self.content_scroll = current_scroll;
+            self.content_scroll_state = self.content_scroll_state.position(current_scroll as usize);
+        }
+    }
+
+    /// Whether a document is still loading, so the spinner needs redraws.
+    pub fn is_loading(&self) -> bool {
+        self.pending_load.is_some() || self.stdin_load.is_some()
+    }
+
+    /// Spinner and file name while a document is parsing in the background
+    /// or still streaming in.
+    pub fn loading_indicator(&self) -> Option<(char, &str)> {
+        let now = Instant::now();
+        if let Some(load) = &self.pending_load {
+            return Some((load.spinner(now), load.filename.as_str()));
+        }
+        self.stdin_load
+            .as_ref()
+            .map(|load| (load.spinner(now), self.filename.as_str()))
+    }
+
     /// Check if the document has non-whitespace content before the first heading
     fn has_preamble_content(document: &Document) -> bool {
         if document.headings.is_empty() {
@@ -2364,6 +3193,10 @@ impl App {
             items.retain(|item| headings_with_todos.contains(&item.text));
         }
 
+        if self.outline_all_blocks {
+            items = self.with_block_entries(items);
+        }
+
         self.outline_items = items;
 
         // Add document overview entry if there's preamble content or no headings
@@ -2391,14 +3224,32 @@ impl App {
                     expanded: true,
                     has_children: !self.outline_items.is_empty(),
                     heading_index: None,
+                    block_idx: None,
                 },
             );
         }
     }
 
-    fn flatten_tree(
-        tree: &[HeadingNode],
-        collapsed_headings: &HashSet<String>,
+    /// The folds in `collapsed` that still apply to `tree`: headings are
+    /// matched by text, and those that are gone or no longer have children
+    /// to hide drop out, so headings added later start expanded.
+    fn surviving_folds(collapsed: &HashSet<String>, tree: &[HeadingNode]) -> HashSet<String> {
+        fn collect(nodes: &[HeadingNode], collapsed: &HashSet<String>, kept: &mut HashSet<String>) {
+            for node in nodes {
+                if !node.children.is_empty() && collapsed.contains(&node.heading.text) {
+                    kept.insert(node.heading.text.clone());
+                }
+                collect(&node.children, collapsed, kept);
+            }
+        }
+        let mut kept = HashSet::new();
+        collect(tree, collapsed, &mut kept);
+        kept
+    }
+
+    fn flatten_tree(
+        tree: &[HeadingNode],
+        collapsed_headings: &HashSet<String>,
     ) -> Vec<OutlineItem> {
         let mut items = Vec::new();
 
@@ -2417,6 +3268,7 @@ impl App {
                 expanded,
                 has_children,
                 heading_index: Some(node.index),
+                block_idx: None,
             });
 
             // Only show children if this node is expanded
@@ -2434,6 +3286,86 @@ impl App {
         items
     }
 
+    /// Insert code block, table and image entries after each expanded heading.
+    ///
+    /// Entries are nested one level below their heading and only cover the
+    /// heading's own body, not its subsections.
+    fn with_block_entries(&mut self, items: Vec<OutlineItem>) -> Vec<OutlineItem> {
+        let mut result = Vec::with_capacity(items.len());
+        for item in items {
+            let blocks = match item.heading_index {
+                Some(idx) if item.expanded && item.block_idx.is_none() => {
+                    self.section_block_entries(idx, item.level)
+                }
+                _ => Vec::new(),
+            };
+            result.push(item);
+            result.extend(blocks);
+        }
+        result
+    }
+
+    /// Outline entries for the code blocks, tables and images in a heading's body.
+    ///
+    /// `block_idx` counts the blocks the content pane draws, after footnotes,
+    /// hidden front matter and LaTeX are applied. Sections are parsed on first
+    /// use and kept until another document loads.
+    fn section_block_entries(&mut self, heading_index: usize, level: usize) -> Vec<OutlineItem> {
+        use crate::parser::content::parse_content;
+        use crate::parser::output::Block;
+
+        if !self.outline_block_cache.contains_key(&heading_index) {
+            let entries = self
+                .document
+                .extract_section_at_index(heading_index)
+                .map(|section| {
+                    parse_content(&self.rendered_markdown(section), 0)
+                        .iter()
+                        .enumerate()
+                        .take_while(|(_, block)| !matches!(block, Block::Heading { .. }))
+                        .filter_map(|(idx, block)| Some((idx, Self::block_entry_label(block)?)))
+                        .collect()
+                })
+                .unwrap_or_default();
+            self.outline_block_cache.insert(heading_index, entries);
+        }
+
+        self.outline_block_cache[&heading_index]
+            .iter()
+            .map(|(idx, text)| OutlineItem {
+                level: level + 1,
+                text: text.clone(),
+                expanded: true,
+                has_children: false,
+                heading_index: Some(heading_index),
+                block_idx: Some(*idx),
+            })
+            .collect()
+    }
+
+    /// Outline text for a code block, table or image; `None` for other blocks.
+    fn block_entry_label(block: &crate::parser::output::Block) -> Option<String> {
+        use crate::parser::output::Block;
+
+        Some(match block {
+            Block::Code { language, .. } => match language.as_deref() {
+                Some(lang) if !lang.is_empty() => format!("code: {}", lang),
+                _ => "code".to_string(),
+            },
+            Block::Table { headers, .. } => {
+                let columns = headers.join(" | ");
+                if columns.trim().is_empty() {
+                    "table".to_string()
+                } else {
+                    format!("table: {}", columns)
+                }
+            }
+            Block::Image { alt, .. } if !alt.is_empty() => format!("image: {}", alt),
+            Block::Image { .. } => "image".to_string(),
+            _ => return None,
+        })
+    }
+
     /// Select an outline item by index, updating both selection and scroll state.
     fn select_outline_index(&mut self, idx: usize) {
         self.outline_state.select(Some(idx));
@@ -2443,7 +3375,7 @@ impl App {
     /// Select a heading by its text. Returns true if found and selected.
     fn select_by_text(&mut self, text: &str) -> bool {
         for (idx, item) in self.outline_items.iter().enumerate() {
-            if item.text == text {
+            if item.text == text && item.block_idx.is_none() {
                 self.select_outline_index(idx);
                 return true;
             }
@@ -2453,7 +3385,7 @@ impl App {
 
     fn select_by_heading_index(&mut self, heading_index: usize) -> bool {
         for (idx, item) in self.outline_items.iter().enumerate() {
-            if item.heading_index == Some(heading_index) {
+            if item.heading_index == Some(heading_index) && item.block_idx.is_none() {
                 self.select_outline_index(idx);
                 return true;
             }
@@ -2478,10 +3410,11 @@ impl App {
         if selection_changed {
             // Reset content scroll when selection changes
             self.content_scroll = 0;
+            self.content_hscroll = 0;
             self.previous_selection = current_selection;
 
             // Reindex interactive elements for the new section
-            let content_text = self.current_section_content();
+            let content_text = self.current_section_view();
 
             use crate::parser::content::parse_content;
             let blocks = parse_content(&content_text, 0);
@@ -2489,8 +3422,23 @@ impl App {
             self.populate_image_cache();
         }
 
+        // Selecting a block entry scrolls to that block within its section,
+        // once the content pane has laid the section out
+        let selected_block = self
+            .outline_state
+            .selected()
+            .and_then(|i| self.outline_items.get(i))
+            .and_then(|item| item.block_idx);
+        if selected_block != self.previous_outline_block {
+            self.previous_outline_block = selected_block;
+            self.pending_block_scroll = selected_block;
+            if selected_block.is_none() {
+                self.content_scroll = 0;
+            }
+        }
+
         if selection_changed || self.metrics_dirty {
-            let content_text = self.current_section_content();
+            let content_text = self.current_section_view();
             let content_lines = content_text.lines().count();
             self.content_height = content_lines;
             self.content_scroll_state =
@@ -2512,34 +3460,73 @@ impl App {
     }
 
     pub fn next(&mut self) {
+        self.next_n(1);
+    }
+
+    /// Move `n` items down the outline, or scroll the content `n` lines.
+    pub fn next_n(&mut self, n: usize) {
         if self.focus == Focus::Outline {
-            let i = match self.outline_state.selected() {
-                Some(i) => {
-                    if i >= self.outline_items.len().saturating_sub(1) {
-                        i
-                    } else {
-                        i + 1
-                    }
-                }
-                None => 0,
-            };
+            let i = Self::step_index(
+                self.outline_state.selected(),
+                self.outline_items.len(),
+                n,
+                true,
+            );
             self.select_outline_index(i);
         } else {
             // Scroll content - stop when last line is at viewport bottom
-            self.scroll_content_down();
+            for _ in 0..n {
+                self.scroll_content_down();
+            }
         }
     }
 
     pub fn previous(&mut self) {
+        self.previous_n(1);
+    }
+
+    /// Move `n` items up the outline, or scroll the content `n` lines.
+    pub fn previous_n(&mut self, n: usize) {
         if self.focus == Focus::Outline {
-            let i = match self.outline_state.selected() {
-                Some(i) => i.saturating_sub(1),
-                None => 0,
-            };
+            let i = Self::step_index(
+                self.outline_state.selected(),
+                self.outline_items.len(),
+                n,
+                false,
+            );
             self.select_outline_index(i);
         } else {
-            // Scroll content
-            self.scroll_content_up();
+            for _ in 0..n {
+                self.scroll_content_up();
+            }
+        }
+    }
+
+    /// Index `n` items after (or before) `current` in a list of `len`,
+    /// clamped to the list. Nothing selected starts at the top.
+    fn step_index(current: Option<usize>, len: usize, n: usize, forward: bool) -> usize {
+        match current {
+            Some(i) if forward => i.saturating_add(n).min(len.saturating_sub(1)).max(i),
+            Some(i) => i.saturating_sub(n),
+            None => 0,
+        }
+    }
+
+    /// Go to the `n`th (1-based) outline item, file, or content line: the
+    /// target of a count before `g`/`G` (`10G`).
+    pub fn go_to_nth(&mut self, n: usize) {
+        let index = n.saturating_sub(1);
+        if self.mode == AppMode::FilePicker {
+            let total = self.file_picker_item_count();
+            if total > 0 {
+                self.file_picker.selected = Some(index.min(total - 1));
+            }
+        } else if self.focus == Focus::Outline && !self.outline_items.is_empty() {
+            self.select_outline_index(index.min(self.outline_items.len() - 1));
+        } else {
+            let line = (index.min(u16::MAX as usize) as u16).min(self.max_content_scroll());
+            self.content_scroll = line;
+            self.content_scroll_state = self.content_scroll_state.position(line as usize);
         }
     }
 
@@ -2594,16 +3581,40 @@ impl App {
         }
     }
 
+    /// Open or close help. With a help search going, Esc stops typing and
+    /// clears the filter first; so does `?`, once typing has stopped (while
+    /// typing, `?` goes into the filter).
     pub fn toggle_help(&mut self) {
+        if self.show_help && (self.help_search_active || !self.help_query.is_empty()) {
+            self.help_search_active = false;
+            self.set_help_query(String::new());
+            return;
+        }
         self.show_help = !self.show_help;
         if self.show_help {
             self.help_scroll = 0; // Reset scroll when opening help
         }
     }
 
+    /// Start typing a help filter, or stop typing and keep it (`/`, Enter).
+    fn toggle_help_search(&mut self) {
+        self.help_search_active = !self.help_search_active;
+    }
+
+    /// Filter help to lines matching `query`, from the top.
+    fn set_help_query(&mut self, query: String) {
+        self.help_query = query;
+        self.help_scroll = 0;
+    }
+
+    /// Number of help lines the current filter shows.
+    fn help_line_count(&self) -> usize {
+        help_text::matching_help_lines(&self.keybindings, &self.help_query).len()
+    }
+
     pub fn scroll_help_down(&mut self) {
         let new_scroll = self.help_scroll.saturating_add(1);
-        let max_scroll = help_text::HELP_LINES.len() as u16;
+        let max_scroll = self.help_line_count() as u16;
         if new_scroll < max_scroll {
             self.help_scroll = new_scroll;
         }
@@ -2615,18 +3626,24 @@ impl App {
 
     /// Scroll help popup down by a page
     pub fn scroll_help_page_down(&mut self) {
-        let page_size = self.content_viewport_height.saturating_sub(2).max(1);
-        let new_scroll = self.help_scroll.saturating_add(page_size);
-        let max_scroll = help_text::HELP_LINES.len() as u16;
-        if new_scroll < max_scroll {
-            self.help_scroll = new_scroll;
-        }
+        self.scroll_help_by(self.page_lines(), true);
     }
 
     /// Scroll help popup up by a page
     pub fn scroll_help_page_up(&mut self) {
-        let page_size = self.content_viewport_height.saturating_sub(2).max(1);
-        self.help_scroll = self.help_scroll.saturating_sub(page_size);
+        self.scroll_help_by(self.page_lines(), false);
+    }
+
+    /// Move the help popup `lines` down or up; a move past the end is ignored
+    fn scroll_help_by(&mut self, lines: u16, down: bool) {
+        if down {
+            let new_scroll = self.help_scroll.saturating_add(lines);
+            if new_scroll < self.help_line_count() as u16 {
+                self.help_scroll = new_scroll;
+            }
+        } else {
+            self.help_scroll = self.help_scroll.saturating_sub(lines);
+        }
     }
 
     pub fn toggle_search(&mut self) {
@@ -2638,11 +3655,13 @@ impl App {
             self.filter_outline();
         } else if self.show_search {
             // In accepted search state (showing filtered results) - start fresh search
+            self.search_origin = self.selected_heading_index();
             self.search_query.clear();
             self.filter_outline(); // Restore full outline for new search
             self.outline_search_active = true; // Re-enter input mode
         } else {
             // Enter search mode from normal state
+            self.search_origin = self.selected_heading_index();
             self.show_search = true;
             self.outline_search_active = true;
             self.search_query.clear();
@@ -2718,22 +3737,45 @@ impl App {
         let current_idx = self.selected_heading_index();
         let current_selection = self.selected_heading_text().map(|s| s.to_string());
 
+        self.outline_text_hits.clear();
         if self.search_query.is_empty() {
             // Reset to full tree with overview entry
             self.rebuild_outline_items();
         } else {
-            // Filter by search query, but always include overview entry if applicable
-            let query_lower = self.search_query.to_lowercase();
+            // Fuzzy filter ranked by score (substring hits first, ties in
+            // document order), but always include overview entry if applicable.
+            // Sections matched only by their text follow, in document order.
             let has_preamble = Self::has_preamble_content(&self.document);
-
-            self.outline_items = Self::flatten_tree(&self.tree, &self.collapsed_headings)
-                .into_iter()
-                .filter(|item| item.text.to_lowercase().contains(&query_lower))
-                .collect();
+            if self.outline_search_text && self.section_text_lc.is_none() {
+                self.section_text_lc = Some(Self::section_texts_lc(&self.document));
+            }
+            let section_text = self
+                .section_text_lc
+                .as_deref()
+                .filter(|_| self.outline_search_text);
+            let query_lc = self.search_query.to_lowercase();
+
+            let mut ranked: Vec<((bool, i64), OutlineItem)> =
+                Self::flatten_tree(&self.tree, &self.collapsed_headings)
+                    .into_iter()
+                    .filter_map(|item| {
+                        if let Some(m) = fuzzy_match(&item.text, &self.search_query) {
+                            return Some(((true, m.score), item));
+                        }
+                        let heading = item.heading_index.filter(|_| item.block_idx.is_none())?;
+                        let text = section_text?.get(heading)?;
+                        text.contains(&query_lc).then(|| {
+                            self.outline_text_hits.insert(heading);
+                            ((false, 0), item)
+                        })
+                    })
+                    .collect();
+            ranked.sort_by_key(|(rank, _)| std::cmp::Reverse(*rank));
+            self.outline_items = ranked.into_iter().map(|(_, item)| item).collect();
 
             // Add overview entry if it matches the search or if document has preamble
             if (has_preamble || self.document.headings.is_empty())
-                && DOCUMENT_OVERVIEW.to_lowercase().contains(&query_lower)
+                && fuzzy_match(DOCUMENT_OVERVIEW, &self.search_query).is_some()
             {
                 self.outline_items.insert(
                     0,
@@ -2743,6 +3785,7 @@ impl App {
                         expanded: true,
                         has_children: !self.tree.is_empty(),
                         heading_index: None,
+                        block_idx: None,
                     },
                 );
             }
@@ -2761,6 +3804,30 @@ impl App {
         }
     }
 
+    /// Switch the outline filter between matching headings only and also
+    /// matching the text of each section.
+    pub fn toggle_outline_search_scope(&mut self) {
+        self.outline_search_text = !self.outline_search_text;
+        self.filter_outline();
+        self.set_status_message(if self.outline_search_text {
+            "Outline filter: headings + text"
+        } else {
+            "Outline filter: headings only"
+        });
+    }
+
+    /// Lowercased text of each section, from below its heading to the next
+    /// heading of any level, indexed like `document.headings`.
+    fn section_texts_lc(document: &Document) -> Vec<String> {
+        (0..document.headings.len())
+            .map(|idx| {
+                let start = document.body_start(idx);
+                let end = document.section_end_any(idx).max(start);
+                document.content[start..end].to_lowercase()
+            })
+            .collect()
+    }
+
     // ========== Document Search Methods ==========
 
     /// Enter document search mode (activated by / when content is focused or in interactive mode)
@@ -2820,25 +3887,25 @@ impl App {
 
         // Get current section content
         let content = self.current_section_content();
+        if content.trim().is_empty() {
+            self.doc_search.current_idx = None;
+            return;
+        }
 
         // Convert to plain text using parser (strips links, formatting, etc.)
         // This ensures search matches what's visible when rendered
         let plain_content = turbovault_parser::to_plain_text(&content);
-        let query = self.doc_search.query.to_lowercase();
 
-        // Find all exact substring matches (case-insensitive)
+        // Find all substring matches (case-insensitive), as byte ranges of
+        // the original line so they can be sliced safely
         for (line_num, line) in plain_content.lines().enumerate() {
-            let line_lower = line.to_lowercase();
-
-            let mut search_start = 0;
-            while let Some(pos) = line_lower[search_start..].find(&query) {
-                let col_start = search_start + pos;
+            for (start, end) in crate::tui::ui::util::find_ignore_case(line, &self.doc_search.query)
+            {
                 self.doc_search.matches.push(SearchMatch {
                     line: line_num,
-                    col_start,
-                    len: query.len(),
+                    col_start: start,
+                    len: end - start,
                 });
-                search_start = col_start + query.len();
             }
         }
 
@@ -2861,7 +3928,7 @@ impl App {
         if let Some(idx) = self.doc_search.current_idx
             && let Some(m) = self.doc_search.matches.get(idx)
         {
-            let match_line = m.line as u16;
+            let match_line = m.line.min(u16::MAX as usize) as u16;
 
             // Scroll to bring match line into view (center it if possible)
             let half_viewport = self.content_viewport_height / 2;
@@ -2975,10 +4042,12 @@ impl App {
             let current = self.outline_state.selected().unwrap_or(0);
             let next = (current + 1) % self.outline_items.len();
             self.select_outline_index(next);
+            self.set_status_message(&format!("match {}/{}", next + 1, self.outline_items.len()));
             return;
         }
 
         if self.doc_search.matches.is_empty() {
+            self.cycle_last_outline_search(true);
             return;
         }
 
@@ -2990,6 +4059,47 @@ impl App {
         self.scroll_to_doc_search_match();
     }
 
+    /// Select the next (or previous) outline heading matching the last
+    /// accepted outline search, wrapping around. Used by `n`/`N` once the
+    /// search filter has been closed.
+    fn cycle_last_outline_search(&mut self, forward: bool) {
+        let Some(query) = self.last_outline_search.clone() else {
+            return;
+        };
+        let matches: Vec<usize> = self
+            .outline_items
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| fuzzy_match(&item.text, &query).is_some())
+            .map(|(i, _)| i)
+            .collect();
+        match Self::cycle_match(&matches, self.outline_state.selected(), forward) {
+            Some(pos) => {
+                self.select_outline_index(matches[pos]);
+                self.set_status_message(&format!("match {}/{}: {}", pos + 1, matches.len(), query));
+            }
+            None => self.set_status_message(&format!("Pattern not found: {}", query)),
+        }
+    }
+
+    /// Position in `matches` (sorted item indices) of the first match after
+    /// (or before) `current`, wrapping around.
+    fn cycle_match(matches: &[usize], current: Option<usize>, forward: bool) -> Option<usize> {
+        if matches.is_empty() {
+            return None;
+        }
+        let pos = match (current, forward) {
+            (None, true) => 0,
+            (None, false) => matches.len() - 1,
+            (Some(cur), true) => matches.iter().position(|&m| m > cur).unwrap_or(0),
+            (Some(cur), false) => matches
+                .iter()
+                .rposition(|&m| m < cur)
+                .unwrap_or(matches.len() - 1),
+        };
+        Some(pos)
+    }
+
     /// Navigate to previous search match
     /// Handles both doc search matches and accepted outline search navigation
     pub fn prev_doc_match(&mut self) {
@@ -3000,10 +4110,12 @@ impl App {
             let len = self.outline_items.len();
             let prev = (current + len - 1) % len;
             self.select_outline_index(prev);
+            self.set_status_message(&format!("match {}/{}", prev + 1, len));
             return;
         }
 
         if self.doc_search.matches.is_empty() {
+            self.cycle_last_outline_search(false);
             return;
         }
 
@@ -3042,41 +4154,54 @@ impl App {
         }
     }
 
+    /// Lines a page scroll moves, from `ui.scroll_step`
+    fn page_lines(&self) -> u16 {
+        self.config
+            .ui
+            .scroll_step
+            .lines(self.content_viewport_height)
+    }
+
+    /// Lines a half-page scroll moves
+    fn half_page_lines(&self) -> u16 {
+        ScrollStep::HalfPage.lines(self.content_viewport_height)
+    }
+
     pub fn scroll_page_down(&mut self) {
         if self.focus == Focus::Content {
-            let page = self.content_viewport_height.saturating_sub(2).max(1);
-            let new_scroll = self.content_scroll.saturating_add(page);
-            self.content_scroll = new_scroll.min(self.max_content_scroll());
-            self.content_scroll_state = self
-                .content_scroll_state
-                .position(self.content_scroll as usize);
+            self.scroll_content_by(self.page_lines(), true);
         }
     }
 
     pub fn scroll_page_up(&mut self) {
         if self.focus == Focus::Content {
-            let page = self.content_viewport_height.saturating_sub(2).max(1);
-            self.content_scroll = self.content_scroll.saturating_sub(page);
-            self.content_scroll_state = self
-                .content_scroll_state
-                .position(self.content_scroll as usize);
+            self.scroll_content_by(self.page_lines(), false);
         }
     }
 
-    /// Scroll page down in interactive mode (bypasses focus check)
-    pub fn scroll_page_down_interactive(&mut self) {
-        let page = self.content_viewport_height.saturating_sub(2).max(1);
-        let new_scroll = self.content_scroll.saturating_add(page);
-        self.content_scroll = new_scroll.min(self.max_content_scroll());
-        self.content_scroll_state = self
-            .content_scroll_state
-            .position(self.content_scroll as usize);
+    /// Scroll the content down half a page (`Ctrl+d`)
+    pub fn scroll_half_page_down(&mut self) {
+        if self.focus == Focus::Content {
+            self.scroll_content_by(self.half_page_lines(), true);
+        }
+    }
+
+    /// Scroll the content up half a page (`Ctrl+u`)
+    pub fn scroll_half_page_up(&mut self) {
+        if self.focus == Focus::Content {
+            self.scroll_content_by(self.half_page_lines(), false);
+        }
     }
 
-    /// Scroll page up in interactive mode (bypasses focus check)
-    pub fn scroll_page_up_interactive(&mut self) {
-        let page = self.content_viewport_height.saturating_sub(2).max(1);
-        self.content_scroll = self.content_scroll.saturating_sub(page);
+    /// Move the content `lines` down or up, staying within the content.
+    /// Interactive mode pages with this directly, whichever pane has focus.
+    fn scroll_content_by(&mut self, lines: u16, down: bool) {
+        let scroll = if down {
+            self.content_scroll.saturating_add(lines)
+        } else {
+            self.content_scroll.saturating_sub(lines)
+        };
+        self.content_scroll = scroll.min(self.max_content_scroll());
         self.content_scroll_state = self
             .content_scroll_state
             .position(self.content_scroll as usize);
@@ -3243,21 +4368,63 @@ impl App {
             self.collapsed_headings.insert(text);
         }
 
-        // Rebuild outline and preserve selection
-        let selected_offset = self.selected_heading_index();
-        let selected_text = self.selected_heading_text().map(|s| s.to_string());
+        // Rebuild outline, keeping the selection or its nearest visible ancestor
+        let selected = self.selected_heading_index();
         self.rebuild_outline_items();
+        self.reselect_after_fold(selected);
 
-        // Try to restore selection, or select first item
-        let restored = selected_offset.is_some_and(|o| self.select_by_heading_index(o))
-            || selected_text.is_some_and(|t| self.select_by_text(&t));
-        if !restored && !self.outline_items.is_empty() {
+        let count = self.collapsed_headings.len();
+        self.set_status_message(&format!("Collapsed {} headings", count));
+    }
+
+    /// Select `heading` again after a bulk fold, or its nearest ancestor
+    /// still visible in the outline, or else the first item.
+    fn reselect_after_fold(&mut self, heading: Option<usize>) {
+        let ancestry: Vec<usize> = heading
+            .map(|heading| {
+                Self::tree_path(&self.tree, heading)
+                    .iter()
+                    .map(|node| node.index)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let visible = ancestry
+            .into_iter()
+            .rev()
+            .any(|index| self.select_by_heading_index(index));
+        if !visible && !self.outline_items.is_empty() {
             self.outline_state.select(Some(0));
             self.outline_scroll_state = ScrollbarState::new(self.outline_items.len()).position(0);
         }
+    }
 
-        let count = self.collapsed_headings.len();
-        self.set_status_message(&format!("Collapsed {} headings", count));
+    /// Show headings down to heading level `level` (`2` shows H1 and H2):
+    /// headings with deeper subheadings collapse, all others expand.
+    pub fn fold_to_level(&mut self, level: usize) {
+        self.reveal_depth = None;
+        self.collapsed_headings = Self::headings_deeper_than(&self.tree, level);
+
+        let selected = self.selected_heading_index();
+        self.rebuild_outline_items();
+        self.reselect_after_fold(selected);
+
+        self.set_status_message(&format!("Folded to H{}", level));
+    }
+
+    /// Texts of headings in `nodes` that have subheadings deeper than `level`
+    fn headings_deeper_than(nodes: &[HeadingNode], level: usize) -> HashSet<String> {
+        let mut result = HashSet::new();
+        for node in nodes {
+            if node
+                .children
+                .iter()
+                .any(|child| child.heading.level > level)
+            {
+                result.insert(node.heading.text.clone());
+            }
+            result.extend(Self::headings_deeper_than(&node.children, level));
+        }
+        result
     }
 
     /// Recursively collect all heading texts that have children
@@ -3291,6 +4458,83 @@ impl App {
         self.set_status_message(&format!("Expanded {} headings", count));
     }
 
+    /// Reveal one more outline level than currently shown
+    pub fn expand_more_levels(&mut self) {
+        let max = Self::tree_depth(&self.tree);
+        let depth = Self::step_reveal_depth(self.reveal_depth, max, true);
+        self.reveal_to_depth(depth, max);
+    }
+
+    /// Hide the deepest outline level currently shown
+    pub fn collapse_more_levels(&mut self) {
+        let max = Self::tree_depth(&self.tree);
+        let depth = Self::step_reveal_depth(self.reveal_depth, max, false);
+        self.reveal_to_depth(depth, max);
+    }
+
+    /// Next reveal depth for a progressive expand/collapse step.
+    ///
+    /// The first expand starts at depth 1; the first collapse starts one
+    /// level above the fully expanded tree. The result stays within `1..=max`.
+    fn step_reveal_depth(current: Option<usize>, max: usize, expand: bool) -> usize {
+        let next = match (current, expand) {
+            (Some(d), true) => d + 1,
+            (Some(d), false) => d.saturating_sub(1),
+            (None, true) => 1,
+            (None, false) => max.saturating_sub(1),
+        };
+        next.clamp(1, max.max(1))
+    }
+
+    /// Collapse every heading at `depth` or deeper so exactly `depth` outline
+    /// levels are visible.
+    fn reveal_to_depth(&mut self, depth: usize, max: usize) {
+        self.reveal_depth = Some(depth);
+        self.collapsed_headings = self
+            .tree
+            .iter()
+            .flat_map(|node| Self::collect_collapsible_from_depth(node, 1, depth))
+            .collect();
+
+        // Rebuild outline, keeping the selection or its nearest visible ancestor
+        let selected = self.selected_heading_index();
+        self.rebuild_outline_items();
+        self.reselect_after_fold(selected);
+
+        self.set_status_message(&format!("Outline depth {}/{}", depth, max.max(1)));
+    }
+
+    /// Number of nesting levels in the heading tree (0 for an empty tree)
+    fn tree_depth(nodes: &[HeadingNode]) -> usize {
+        nodes
+            .iter()
+            .map(|node| 1 + Self::tree_depth(&node.children))
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Collect headings with children whose tree depth is at least `min_depth`
+    fn collect_collapsible_from_depth(
+        node: &HeadingNode,
+        depth: usize,
+        min_depth: usize,
+    ) -> Vec<String> {
+        let mut result = Vec::new();
+        if !node.children.is_empty() {
+            if depth >= min_depth {
+                result.push(node.heading.text.clone());
+            }
+            for child in &node.children {
+                result.extend(Self::collect_collapsible_from_depth(
+                    child,
+                    depth + 1,
+                    min_depth,
+                ));
+            }
+        }
+        result
+    }
+
     /// Collapse all headings at a specific level (1-6)
     pub fn collapse_level(&mut self, level: usize) {
         // Collect all headings at the target level that have children
@@ -3305,17 +4549,10 @@ impl App {
             self.collapsed_headings.insert(text);
         }
 
-        // Rebuild outline and preserve selection
-        let selected_offset = self.selected_heading_index();
-        let selected_text = self.selected_heading_text().map(|s| s.to_string());
+        // Rebuild outline, keeping the selection or its nearest visible ancestor
+        let selected = self.selected_heading_index();
         self.rebuild_outline_items();
-
-        let restored = selected_offset.is_some_and(|o| self.select_by_heading_index(o))
-            || selected_text.is_some_and(|t| self.select_by_text(&t));
-        if !restored && !self.outline_items.is_empty() {
-            self.outline_state.select(Some(0));
-            self.outline_scroll_state = ScrollbarState::new(self.outline_items.len()).position(0);
-        }
+        self.reselect_after_fold(selected);
 
         self.set_status_message(&format!("Collapsed {} h{} headings", count, level));
     }
@@ -3436,6 +4673,44 @@ impl App {
         }
     }
 
+    /// Toggle soft line-wrapping in the content pane and save the choice
+    pub fn toggle_wrap(&mut self) {
+        self.wrap = !self.wrap;
+        self.content_hscroll = 0;
+        let _ = self.config.set_wrap(self.wrap);
+        let state = if self.wrap {
+            "ON"
+        } else {
+            "OFF (h/l scroll sideways in content)"
+        };
+        self.set_status_message(&format!("Line wrap: {}", state));
+    }
+
+    /// Toggle the source line-number gutter and save the choice
+    pub fn toggle_line_numbers(&mut self) {
+        self.line_numbers = !self.line_numbers;
+        let _ = self.config.set_line_numbers(self.line_numbers);
+        let state = if self.line_numbers { "ON" } else { "OFF" };
+        self.set_status_message(&format!("Line numbers: {}", state));
+    }
+
+    /// Switch to the next line-number style and save the choice. The gutter
+    /// is turned on so the change shows.
+    pub fn cycle_line_number_style(&mut self) {
+        self.line_number_style = self.line_number_style.next();
+        let _ = self
+            .config
+            .set_line_number_style(self.line_number_style.as_str());
+        if !self.line_numbers {
+            self.line_numbers = true;
+            let _ = self.config.set_line_numbers(true);
+        }
+        self.set_status_message(&format!(
+            "Line numbers: {}",
+            self.line_number_style.as_str()
+        ));
+    }
+
     /// Toggle heading level markers (#, ##, ###) in the outline sidebar
     pub fn toggle_heading_markers(&mut self) {
         self.show_heading_markers = !self.show_heading_markers;
@@ -3469,6 +4744,84 @@ impl App {
         }
     }
 
+    /// Mouse wheel at a screen position: the pane under the pointer takes
+    /// focus and moves (the outline one heading, the content three lines).
+    /// Outside the panes, or in other modes, the wheel acts like `j` / `k`.
+    pub fn mouse_scroll(&mut self, column: u16, row: u16, down: bool) {
+        let pane = self.pane_areas.pane_at(column, row);
+        if let Some(pane) = pane.filter(|_| self.mode == AppMode::Normal && !self.show_help) {
+            self.focus = pane;
+            let lines = if pane == Focus::Content { 3 } else { 1 };
+            if down {
+                self.next_n(lines);
+            } else {
+                self.previous_n(lines);
+            }
+        } else {
+            let _ = self.execute_action(if down { Action::Next } else { Action::Previous });
+        }
+    }
+
+    /// Left click at a screen position. In the outline it selects the heading
+    /// under the pointer; in the content it follows the link under it. Either
+    /// way the clicked pane takes focus. Popups and other modes ignore clicks.
+    pub fn mouse_click(&mut self, column: u16, row: u16) {
+        if self.show_help || self.show_theme_picker || self.is_image_modal_open() {
+            return;
+        }
+        match self.pane_areas.pane_at(column, row) {
+            Some(Focus::Outline) if self.mode == AppMode::Normal => {
+                self.focus = Focus::Outline;
+                if self.file_browser.is_some() {
+                    return;
+                }
+                let Some(area) = self.pane_areas.outline else {
+                    return;
+                };
+                if let Some(idx) =
+                    mouse::outline_row(area, self.outline_state.offset(), column, row)
+                        .filter(|&idx| idx < self.outline_items.len())
+                {
+                    self.select_outline_index(idx);
+                }
+            }
+            Some(Focus::Content) if matches!(self.mode, AppMode::Normal | AppMode::LinkFollow) => {
+                self.focus = Focus::Content;
+                self.follow_link_at(column, row);
+            }
+            _ => {}
+        }
+    }
+
+    /// Follow the link drawn at a screen position in the content pane, as if
+    /// it had been picked in link follow mode.
+    fn follow_link_at(&mut self, column: u16, row: u16) {
+        let text = self.pane_areas.content_text;
+        if !text.contains(ratatui::layout::Position::new(column, row)) {
+            return;
+        }
+        let links = extract_links(&self.current_section_view());
+        let texts: Vec<&str> = links.iter().map(|link| link.text.as_str()).collect();
+        let Some(idx) = mouse::link_at(&self.pane_areas.content_links, column, row, &texts) else {
+            return;
+        };
+
+        self.link_picker.filtered_indices = (0..links.len()).collect();
+        self.link_picker.query.clear();
+        self.link_picker.active = false;
+        self.link_picker.selected = Some(idx);
+        self.links_in_view = links;
+        let was_following = self.mode == AppMode::LinkFollow;
+        self.mode = AppMode::LinkFollow;
+        if let Err(e) = self.follow_selected_link() {
+            self.status_message = Some(format!("✗ Error: {}", e));
+            if !was_following {
+                self.exit_link_follow_mode();
+            }
+        }
+        self.update_content_metrics();
+    }
+
     /// Toggle filtering outline by open todos
     pub fn toggle_todo_filter(&mut self) {
         self.filter_by_todos = !self.filter_by_todos;
@@ -3499,29 +4852,53 @@ impl App {
         }
     }
 
-    /// Cycle outline width between 20%, 30%, and 40%.
-    ///
-    /// Behavior depends on user's config:
-    /// - **New users** (default or standard width in config): Changes are persisted
-    ///   to config file for a seamless experience.
-    /// - **Power users** (custom width like 25% in config): Changes are session-only
-    ///   to protect their carefully crafted config from accidental overwrites.
-    ///   They can explicitly save with `S` key.
-    ///
-    /// This respects the principle that user config should always take precedence.
-    pub fn cycle_outline_width(&mut self, increase: bool) {
-        if increase {
-            self.outline_width = match self.outline_width {
-                20 => 30,
-                30 => 40,
+    /// Toggle the outline between headings only and all block types.
+    pub fn toggle_outline_mode(&mut self) {
+        self.outline_all_blocks = !self.outline_all_blocks;
+
+        let selected_offset = self.selected_heading_index();
+        let selected_text = self.selected_heading_text().map(|s| s.to_string());
+        self.rebuild_outline_items();
+
+        let restored = selected_offset.is_some_and(|o| self.select_by_heading_index(o))
+            || selected_text.is_some_and(|t| self.select_by_text(&t));
+        if !restored && !self.outline_items.is_empty() {
+            self.outline_state.select(Some(0));
+            self.outline_scroll_state = ScrollbarState::new(self.outline_items.len()).position(0);
+        }
+
+        if self.outline_all_blocks {
+            self.set_status_message("Outline: all blocks");
+        } else {
+            self.set_status_message("Outline: headings only");
+        }
+    }
+
+    /// Cycle outline width between 20%, 30%, and 40%.
+    ///
+    /// Behavior depends on user's config:
+    /// - **New users** (default or standard width in config): Changes are persisted
+    ///   to config file for a seamless experience.
+    /// - **Power users** (custom width like 25% in config): Changes are session-only
+    ///   to protect their carefully crafted config from accidental overwrites.
+    ///   They can explicitly save with `S` key.
+    ///
+    /// This respects the principle that user config should always take precedence.
+    pub fn cycle_outline_width(&mut self, increase: bool) {
+        // A width in columns cycles from the share of the terminal it takes
+        let percent = self.outline_width.percent_of(self.terminal_width);
+        let percent = if increase {
+            match percent {
+                20 => 30,
+                30 => 40,
                 40 => 20, // Wrap around
                 // For custom widths, snap to nearest standard value going up
                 w if w < 25 => 30,
                 w if w < 35 => 40,
                 _ => 20,
-            };
+            }
         } else {
-            self.outline_width = match self.outline_width {
+            match percent {
                 40 => 30,
                 30 => 20,
                 20 => 40, // Wrap around
@@ -3529,17 +4906,48 @@ impl App {
                 w if w > 35 => 30,
                 w if w > 25 => 20,
                 _ => 40,
-            };
-        }
+            }
+        };
+        self.outline_width = WidthSpec::Percent(percent);
+        self.outline_width_changed();
+    }
+
+    /// Widen (positive `delta`) or narrow the outline by `delta` columns, or
+    /// percentage points when the width is a percentage. Stops where either
+    /// pane would get narrower than it can be used at.
+    pub fn resize_outline_width(&mut self, delta: i16) {
+        self.outline_width =
+            crate::tui::ui::layout::resize_outline(self.outline_width, delta, self.terminal_width);
+        self.outline_width_changed();
+    }
+
+    /// Report a new outline width and persist it, following the same rules
+    /// as [`cycle_outline_width`](Self::cycle_outline_width).
+    fn outline_width_changed(&mut self) {
+        // The layout clamps the outline so both panes stay usable; say so
+        // when this terminal can't give it the full width
+        let fit = match crate::tui::ui::layout::outline_columns(
+            self.terminal_width,
+            self.outline_width,
+        ) {
+            None if self.terminal_width > 0 => " (hidden: terminal too narrow)".to_string(),
+            Some(columns) if columns != self.outline_width.columns(self.terminal_width) => {
+                format!(" ({} columns fit)", columns)
+            }
+            _ => String::new(),
+        };
 
         // Decide whether to persist based on user's config type
         if self.config_has_custom_outline_width {
             // Power user: protect their custom config value, offer explicit save
-            self.set_status_message(&format!("Width: {}% | :w to save", self.outline_width));
+            self.set_status_message(&format!(
+                "Width: {}{} | :w to save",
+                self.outline_width, fit
+            ));
         } else {
             // New user or standard config: safe to persist for better UX
             let _ = self.config.set_outline_width(self.outline_width);
-            self.set_status_message(&format!("Width: {}%", self.outline_width));
+            self.set_status_message(&format!("Width: {}{}", self.outline_width, fit));
         }
     }
 
@@ -3554,13 +4962,9 @@ impl App {
         match self.config.set_outline_width(self.outline_width) {
             Ok(_) => {
                 // Update the flag since user explicitly chose to save
-                self.config_has_custom_outline_width = self.outline_width != 20
-                    && self.outline_width != 30
-                    && self.outline_width != 40;
-                self.set_status_message(&format!(
-                    "✓ Width {}% saved to config",
-                    self.outline_width
-                ));
+                self.config_has_custom_outline_width =
+                    !OUTLINE_WIDTH_PRESETS.contains(&self.outline_width);
+                self.set_status_message(&format!("✓ Width {} saved to config", self.outline_width));
             }
             Err(e) => {
                 self.set_status_message(&format!("✗ Failed to save: {}", e));
@@ -3581,7 +4985,7 @@ impl App {
     pub fn open_command_palette(&mut self) {
         self.mode = AppMode::CommandPalette;
         self.command_palette.query.clear();
-        self.command_palette.filtered = (0..PALETTE_COMMANDS.len()).collect();
+        self.command_palette.filtered = (0..palette_commands().len()).collect();
         self.command_palette.selected = 0;
     }
 
@@ -3604,7 +5008,7 @@ impl App {
         // Lowercase once, pass to each command — saves N allocations per
         // keystroke where N = number of palette commands.
         let query_lower = self.command_palette.query.to_lowercase();
-        let mut matches: Vec<(usize, usize)> = PALETTE_COMMANDS
+        let mut matches: Vec<(usize, usize)> = palette_commands()
             .iter()
             .enumerate()
             .filter(|(_, cmd)| cmd.matches(&query_lower))
@@ -3648,7 +5052,7 @@ impl App {
             .filtered
             .get(self.command_palette.selected)
         {
-            let cmd = &PALETTE_COMMANDS[cmd_idx];
+            let cmd = &palette_commands()[cmd_idx];
             // Use the first alias (typically the shortest canonical form)
             if let Some(&alias) = cmd.aliases.first() {
                 self.command_palette.query = alias.to_string();
@@ -3671,7 +5075,7 @@ impl App {
             .filtered
             .get(self.command_palette.selected)
         {
-            let action = PALETTE_COMMANDS[cmd_idx].action;
+            let action = palette_commands()[cmd_idx].action;
             let query = self.command_palette.query.clone(); // Capture query for argument parsing
             self.mode = AppMode::Normal;
             self.command_palette.query.clear();
@@ -3688,11 +5092,10 @@ impl App {
             CommandAction::SaveWidth => {
                 match self.config.set_outline_width(self.outline_width) {
                     Ok(_) => {
-                        self.config_has_custom_outline_width = self.outline_width != 20
-                            && self.outline_width != 30
-                            && self.outline_width != 40;
+                        self.config_has_custom_outline_width =
+                            !OUTLINE_WIDTH_PRESETS.contains(&self.outline_width);
                         self.set_status_message(&format!(
-                            "\u{2713} Width {}% saved to config",
+                            "\u{2713} Width {} saved to config",
                             self.outline_width
                         ));
                     }
@@ -3756,6 +5159,18 @@ impl App {
                 }
                 ActionResult::Continue
             }
+            CommandAction::GoToHeading => {
+                let total = self.outline_items.len();
+                match heading_number(&query.to_lowercase()) {
+                    Some(n) if n <= total => self.jump_to_heading(n - 1),
+                    Some(n) => self
+                        .set_status_message(&format!("No heading {} (outline has {})", n, total)),
+                    None => {
+                        self.set_status_message(&format!("Type :N to go to heading 1-{}", total))
+                    }
+                }
+                ActionResult::Continue
+            }
             CommandAction::SaveFile => {
                 if let Err(e) = self.save_pending_edits_to_file() {
                     self.set_status_message(&format!("\u{2717} Save failed: {}", e));
@@ -3792,20 +5207,71 @@ impl App {
             .filter(|&n| (1..=6).contains(&n))
     }
 
+    /// Description shown under the selected palette command. Going to a
+    /// heading names the target and how many headings there are.
+    pub fn palette_description(&self, command: &PaletteCommand) -> String {
+        if command.action != CommandAction::GoToHeading {
+            return command.description.to_string();
+        }
+        let total = self.outline_items.len();
+        match heading_number(&self.command_palette.query.to_lowercase()) {
+            Some(n) => match self.outline_items.get(n - 1) {
+                Some(item) => format!("Heading {} of {}: {}", n, total, item.text),
+                None => format!("No heading {} (outline has {})", n, total),
+            },
+            None => format!("Jump to heading N of {} (type :N)", total),
+        }
+    }
+
     /// Get selected command for display
     pub fn selected_command(&self) -> Option<&'static PaletteCommand> {
         self.command_palette
             .filtered
             .get(self.command_palette.selected)
-            .map(|&idx| &PALETTE_COMMANDS[idx])
+            .map(|&idx| &palette_commands()[idx])
     }
 
     pub fn jump_to_heading(&mut self, index: usize) {
         if index < self.outline_items.len() {
+            self.record_jump();
             self.select_outline_index(index);
         }
     }
 
+    /// Remember the selected heading before a big jump, for `Ctrl+o`.
+    fn record_jump(&mut self) {
+        if self.mode != AppMode::FilePicker
+            && let Some(heading) = self.selected_heading_index()
+        {
+            self.jumps.record(heading);
+        }
+    }
+
+    /// Go back to the heading selected before the last jump.
+    pub fn jump_back(&mut self) {
+        let Some(current) = self.selected_heading_index() else {
+            return;
+        };
+        match self.jumps.back(current) {
+            Some(heading) => self.select_jump_target(heading),
+            None => self.set_status_message("Already at the oldest jump"),
+        }
+    }
+
+    /// Go forward again after [`jump_back`](Self::jump_back).
+    pub fn jump_forward(&mut self) {
+        match self.jumps.forward() {
+            Some(heading) => self.select_jump_target(heading),
+            None => self.set_status_message("Already at the newest jump"),
+        }
+    }
+
+    fn select_jump_target(&mut self, heading: usize) {
+        if !self.select_by_heading_index(heading) {
+            self.set_status_message("Jump target is hidden in the outline");
+        }
+    }
+
     pub fn set_bookmark(&mut self) {
         // Store bookmark as heading text instead of index
         self.bookmark_position = self.selected_heading_text().map(|s| s.to_string());
@@ -3818,11 +5284,81 @@ impl App {
         }
     }
 
+    /// Complete a pending `m` or `'` with the next key: a letter names the
+    /// mark; anything else (or `None`, on timeout) uses the unnamed bookmark.
+    pub fn finish_mark(&mut self, key: Option<char>) {
+        let Some(command) = self.pending_mark.take() else {
+            return;
+        };
+        let name = key.filter(|&c| Marks::is_name(c));
+        self.status_message = None;
+        match (command, name) {
+            (MarkCommand::Set, None) => self.set_bookmark(),
+            (MarkCommand::Jump, None) => self.jump_to_bookmark(),
+            (MarkCommand::Set, Some(name)) => {
+                if let Some(text) = self.selected_heading_text().map(str::to_string) {
+                    self.marks.set(name, &text);
+                    self.set_status_message(&format!("✓ Mark '{}' set: {}", name, text));
+                }
+            }
+            (MarkCommand::Jump, Some(name)) => match self.marks.get(name).map(str::to_string) {
+                Some(text) => {
+                    if !self.select_by_text(&text) {
+                        self.set_status_message(&format!(
+                            "Mark '{}': \"{}\" is not in the outline",
+                            name, text
+                        ));
+                    }
+                }
+                None => self.set_status_message(&format!("Mark '{}' is not set", name)),
+            },
+        }
+    }
+
+    /// Forget a pending `m` or `'` without setting or jumping.
+    pub fn cancel_pending_mark(&mut self) {
+        self.pending_mark = None;
+        self.status_message = None;
+    }
+
+    /// Text of the selected heading. Block entries resolve to their heading.
     pub fn selected_heading_text(&self) -> Option<&str> {
-        self.outline_state
+        let item = self
+            .outline_state
             .selected()
-            .and_then(|i| self.outline_items.get(i))
-            .map(|item| item.text.as_str())
+            .and_then(|i| self.outline_items.get(i))?;
+        match (item.block_idx, item.heading_index) {
+            (Some(_), Some(idx)) => self.document.headings.get(idx).map(|h| h.text.as_str()),
+            _ => Some(item.text.as_str()),
+        }
+    }
+
+    /// Texts of the selected heading and its ancestors, outermost first, for
+    /// the breadcrumb trail. Empty on the document overview.
+    pub fn heading_path(&self) -> Vec<&str> {
+        self.selected_heading_index()
+            .map(|index| {
+                Self::tree_path(&self.tree, index)
+                    .into_iter()
+                    .map(|node| node.heading.text.as_str())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Nodes from a root of `tree` down to the heading at `index`.
+    fn tree_path(tree: &[HeadingNode], index: usize) -> Vec<&HeadingNode> {
+        for node in tree {
+            if node.index == index {
+                return vec![node];
+            }
+            let mut path = Self::tree_path(&node.children, index);
+            if !path.is_empty() {
+                path.insert(0, node);
+                return path;
+            }
+        }
+        Vec::new()
     }
 
     pub fn selected_heading_index(&self) -> Option<usize> {
@@ -3839,6 +5375,55 @@ impl App {
             .unwrap_or_else(|| self.document.content.clone())
     }
 
+    /// The current section as the content pane shows it, footnotes included.
+    ///
+    /// Links and interactive elements are indexed from this text so that they
+    /// line up with what is drawn.
+    fn current_section_view(&self) -> String {
+        self.with_footnotes(self.current_section_content())
+    }
+
+    /// Drop what was worked out from the document's text, after it changed.
+    fn forget_content_caches(&mut self) {
+        self.section_text_lc = None;
+        self.outline_block_cache.clear();
+        self.config_error_cache.clear();
+        self.gutter_lines = None;
+    }
+
+    /// `content` as the content pane renders it outside raw source view:
+    /// footnotes listed, front matter and LaTeX hidden as configured, and
+    /// abbreviation definitions dropped.
+    pub fn rendered_markdown(&self, content: String) -> String {
+        // Footnotes first, before LaTeX filtering turns `[^1]` into `[¹]`
+        let content = if self.footnotes.is_empty() {
+            content
+        } else {
+            self.footnotes.render(&content)
+        };
+        let content = crate::tui::ui::util::filter_content(
+            &content,
+            self.should_hide_frontmatter(),
+            self.should_hide_latex(),
+            self.should_latex_aggressive(),
+        );
+        if self.abbreviations().is_some() {
+            crate::parser::abbreviations::strip_definitions(&content)
+        } else {
+            content
+        }
+    }
+
+    /// `content` with footnote references turned into links and the cited
+    /// notes listed at the end. Raw source view shows the markdown as is.
+    pub fn with_footnotes(&self, content: String) -> String {
+        if self.show_raw_source || self.footnotes.is_empty() {
+            content
+        } else {
+            self.footnotes.render(&content)
+        }
+    }
+
     /// Get the source line number (1-indexed) for the currently selected heading.
     ///
     /// Returns None if no heading is selected or if the selection is the document overview.
@@ -3888,53 +5473,58 @@ impl App {
 
     pub fn toggle_theme_picker(&mut self) {
         if self.show_theme_picker {
-            // Closing picker - restore original theme if set (user pressed Esc)
-            if let Some(original) = self.theme_picker_original.take() {
-                self.apply_theme_preview(original);
+            // Closing picker - restore the committed theme (user pressed Esc)
+            if let Some((name, theme)) = self.theme_picker_committed.take() {
+                self.current_theme = name;
+                self.theme = theme;
             }
             self.show_theme_picker = false;
         } else {
-            // Opening picker - store current theme and set selection
-            self.theme_picker_original = Some(self.current_theme);
-            self.theme_picker_selected = match self.current_theme {
-                ThemeName::OceanDark => 0,
-                ThemeName::Nord => 1,
-                ThemeName::Dracula => 2,
-                ThemeName::Solarized => 3,
-                ThemeName::Monokai => 4,
-                ThemeName::Gruvbox => 5,
-                ThemeName::TokyoNight => 6,
-                ThemeName::CatppuccinMocha => 7,
-            };
+            // Opening picker - remember the committed theme and set selection
+            self.theme_picker_committed = Some((self.current_theme, self.theme.clone()));
+            self.theme_picker_selected = self.theme_picker_index(self.current_theme);
             self.show_theme_picker = true;
         }
     }
 
+    /// Themes defined in `[[theme.custom]]`
+    pub fn custom_themes(&self) -> &[CustomTheme] {
+        &self.config.theme.custom
+    }
+
+    /// Number of themes in the picker: the built-in ones, then custom ones
+    pub fn theme_picker_len(&self) -> usize {
+        ThemeName::BUILTIN.len() + self.custom_themes().len()
+    }
+
+    /// Position of a theme in the picker
+    fn theme_picker_index(&self, theme: ThemeName) -> usize {
+        match theme {
+            ThemeName::Custom(idx) => ThemeName::BUILTIN.len() + idx,
+            builtin => ThemeName::BUILTIN
+                .iter()
+                .position(|t| *t == builtin)
+                .unwrap_or(0),
+        }
+    }
+
     /// Convert theme picker selection index to ThemeName
     fn theme_name_from_index(idx: usize) -> ThemeName {
-        match idx {
-            0 => ThemeName::OceanDark,
-            1 => ThemeName::Nord,
-            2 => ThemeName::Dracula,
-            3 => ThemeName::Solarized,
-            4 => ThemeName::Monokai,
-            5 => ThemeName::Gruvbox,
-            6 => ThemeName::TokyoNight,
-            7 => ThemeName::CatppuccinMocha,
-            _ => ThemeName::OceanDark,
-        }
+        ThemeName::BUILTIN
+            .get(idx)
+            .copied()
+            .unwrap_or_else(|| ThemeName::Custom(idx - ThemeName::BUILTIN.len()))
     }
 
-    /// Apply a theme preview (doesn't save to config)
+    /// Show the content in another theme while the picker is open (doesn't
+    /// save to config)
     fn apply_theme_preview(&mut self, theme_name: ThemeName) {
         self.current_theme = theme_name;
-        self.theme = Theme::from_name(theme_name)
-            .with_color_mode(self.color_mode, theme_name)
-            .with_custom_colors(&self.config.theme, self.color_mode);
+        self.theme = Theme::from_config(&self.config, theme_name, self.color_mode);
     }
 
     pub fn theme_picker_next(&mut self) {
-        if self.theme_picker_selected < 7 {
+        if self.theme_picker_selected + 1 < self.theme_picker_len() {
             self.theme_picker_selected += 1;
             // Apply theme preview immediately
             let theme_name = Self::theme_name_from_index(self.theme_picker_selected);
@@ -3952,14 +5542,31 @@ impl App {
     }
 
     pub fn apply_selected_theme(&mut self) {
-        // Theme is already applied via preview, just save to config and close
-        self.theme_picker_original = None; // Clear so toggle doesn't restore
+        // Theme is already applied via preview, just commit it and close
+        self.theme_picker_committed = None; // Clear so toggle doesn't restore
         self.show_theme_picker = false;
 
         // Save to config (silently ignore errors)
         let _ = self.config.set_theme(self.current_theme);
     }
 
+    /// Copy the current (or previewed) theme's colors to the clipboard as a
+    /// `[[theme.custom]]` block to paste into config.toml.
+    pub fn export_theme(&mut self) {
+        let snippet = self.config.theme_snippet(self.current_theme);
+        self.status_message = Some(match self.copy_to_clipboard(&snippet) {
+            Ok(()) => format!("✓ Theme {} copied as TOML", self.theme.name),
+            Err(e) => format!("✗ {}", e),
+        });
+    }
+
+    /// Highlight code blocks with the next syntax theme and save the choice
+    pub fn cycle_code_theme(&mut self) {
+        let name = self.highlighter.cycle_theme().to_string();
+        let _ = self.config.set_code_theme(&name);
+        self.set_status_message(&format!("Code theme: {}", name));
+    }
+
     /// Get the editor configuration for external file editing
     pub fn editor_config(&self) -> opensesame::EditorConfig {
         self.config.editor.clone()
@@ -3967,43 +5574,297 @@ impl App {
 
     pub fn copy_content(&mut self) {
         let content = self.current_section_content();
-        if let Some(clipboard) = &mut self.clipboard {
-            match clipboard.set_text(content) {
-                Ok(_) => {
-                    self.status_message = Some("✓ Section copied to clipboard".to_string());
-                }
-                Err(e) => {
-                    self.status_message = Some(format!("✗ Clipboard error: {}", e));
-                }
+        self.status_message = Some(match self.copy_to_clipboard(&content) {
+            Ok(()) => "✓ Section copied to clipboard".to_string(),
+            Err(e) => format!("✗ {}", e),
+        });
+    }
+
+    /// Copy a link to the selected heading as `file.md#slug`, with the
+    /// anchor GitHub gives it. Documents not read from a file get `#slug`.
+    pub fn copy_anchor(&mut self) {
+        let Some(anchor) = self.selected_github_anchor() else {
+            self.status_message = Some("✗ No heading selected".to_string());
+            return;
+        };
+        let filename = if self.current_file_path.is_file() {
+            self.filename.as_str()
+        } else {
+            ""
+        };
+        let anchor_link = format!("{}#{}", filename, anchor);
+
+        self.status_message = Some(match self.copy_to_clipboard(&anchor_link) {
+            Ok(()) => format!("✓ Anchor link copied: {}", anchor_link),
+            Err(e) => format!("✗ {}", e),
+        });
+    }
+
+    /// GitHub's anchor for the selected heading, numbered if an earlier
+    /// heading has the same text.
+    fn selected_github_anchor(&self) -> Option<String> {
+        let idx = self.selected_heading_index()?;
+        self.document.github_anchors().into_iter().nth(idx)
+    }
+
+    /// Copy a ready-to-paste markdown link (`[Text](#slug)`) to the selected heading
+    pub fn copy_heading_link(&mut self) {
+        let (Some(heading_text), Some(anchor)) =
+            (self.selected_heading_text(), self.selected_github_anchor())
+        else {
+            self.status_message = Some("✗ No heading selected".to_string());
+            return;
+        };
+
+        let filename = self
+            .config
+            .links
+            .include_filename
+            .then_some(self.filename.as_str());
+        let link = Self::heading_markdown_link(heading_text, &anchor, filename);
+
+        self.status_message = Some(match self.copy_to_clipboard(&link) {
+            Ok(()) => format!("✓ Heading link copied: {}", link),
+            Err(e) => format!("✗ {}", e),
+        });
+    }
+
+    /// Copy the selected section as plain text, with its heading and
+    /// subsections, or the whole document from the overview. Unlike
+    /// [`Self::copy_content`] the markup is removed, so it pastes cleanly
+    /// where markdown isn't rendered.
+    pub fn copy_plain_text(&mut self) {
+        let text = crate::parser::to_plain_text(self.selected_section_markdown());
+        self.status_message = Some(match self.copy_to_clipboard(&text) {
+            Ok(()) => "✓ Section copied as plain text".to_string(),
+            Err(e) => format!("✗ {}", e),
+        });
+    }
+
+    /// Markdown of the selected heading's section with its subsections, or
+    /// the whole document when no heading is selected.
+    fn selected_section_markdown(&self) -> &str {
+        match self.selected_heading_index() {
+            Some(idx) => {
+                let start = self.document.headings[idx].offset;
+                &self.document.content[start..self.document.section_end(idx)]
             }
+            None => self.document.content.as_str(),
+        }
+    }
+
+    /// Show word and character counts and reading time for the selected
+    /// section and its subsections, code blocks left out.
+    pub fn show_section_stats(&mut self) {
+        let stats = crate::parser::TextStats::of(self.selected_section_markdown(), false);
+        let title = match self.selected_heading_index() {
+            Some(idx) => self.document.headings[idx].text.clone(),
+            None => self.filename.clone(),
+        };
+        self.section_stats = Some((title, stats));
+        self.mode = AppMode::SectionStats;
+    }
+
+    /// Close the section stats popup.
+    pub fn close_section_stats(&mut self) {
+        self.section_stats = None;
+        self.mode = AppMode::Normal;
+    }
+
+    /// Words per minute for reading times (from config)
+    pub fn reading_wpm(&self) -> usize {
+        self.config.ui.reading_wpm
+    }
+
+    /// Export a mermaid/plantuml block's source to a file next to the document.
+    ///
+    /// Uses the selected block in interactive mode and the section's first
+    /// diagram otherwise. Falls back to the clipboard if the file cannot be
+    /// written (e.g. a read-only directory or stdin input).
+    fn export_diagram(&mut self) {
+        use crate::parser::content::parse_content;
+        use crate::parser::output::Block;
+        use crate::tui::diagram::{self, DiagramKind};
+
+        let blocks = parse_content(&self.current_section_content(), 0);
+        let diagrams: Vec<(usize, DiagramKind, &str)> = blocks
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, block)| match block {
+                Block::Code {
+                    language, content, ..
+                } => DiagramKind::from_language(language.as_deref())
+                    .map(|kind| (idx, kind, content.as_str())),
+                _ => None,
+            })
+            .collect();
+
+        let target = if self.mode == AppMode::Interactive {
+            self.interactive_state
+                .current_element()
+                .and_then(|e| diagrams.iter().position(|(idx, ..)| *idx == e.id.block_idx))
+        } else {
+            (!diagrams.is_empty()).then_some(0)
+        };
+        let Some(position) = target else {
+            self.status_message = Some("✗ No mermaid/plantuml diagram selected".to_string());
+            return;
+        };
+
+        let (_, kind, source) = diagrams[position];
+        let section = self
+            .selected_heading_text()
+            .filter(|text| *text != DOCUMENT_OVERVIEW)
+            .map(Self::heading_to_anchor);
+        let path =
+            diagram::export_path(&self.export_base(), section.as_deref(), position + 1, kind);
+
+        self.export_unless_exists(PendingExport::Diagram {
+            path,
+            kind,
+            source: source.to_string(),
+        });
+    }
+
+    /// Export the selected section and its subsections as a standalone HTML
+    /// page next to the document (`<stem>-<slug>.html`), styled with the
+    /// current theme. The document overview exports the whole file.
+    fn export_html(&mut self) {
+        use crate::parser::to_html;
+        use crate::tui::terminal_compat::ColorMode;
+
+        let base = self.export_base();
+        let stem = base
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "document".to_string());
+        let (range, title, name) = match self.selected_heading_index() {
+            Some(idx) => {
+                let heading = &self.document.headings[idx];
+                (
+                    heading.offset..self.document.section_end(idx),
+                    heading.text.clone(),
+                    format!("{}-{}.html", stem, Self::heading_to_anchor(&heading.text)),
+                )
+            }
+            None => (
+                0..self.document.content.len(),
+                self.filename.clone(),
+                format!("{}.html", stem),
+            ),
+        };
+        let anchors = self.document.github_anchors_in(range.clone());
+        let markdown = &self.document.content[range];
+
+        // Terminal colors may be reduced to 256; the page gets the full theme
+        let style =
+            Theme::from_config(&self.config, self.current_theme, ColorMode::Rgb).html_style();
+        let html = to_html(markdown, &title, &style, &anchors);
+        let path = base.with_file_name(name);
+        self.export_unless_exists(PendingExport::Html { path, html });
+    }
+
+    /// The file exports are named after and written next to: the open file,
+    /// or `document` for stdin and other input that isn't a file on disk.
+    fn export_base(&self) -> PathBuf {
+        if self.current_file_path.is_file() {
+            self.current_file_path.clone()
         } else {
-            self.status_message = Some("✗ Clipboard not available".to_string());
+            self.current_file_path.with_file_name("document")
         }
     }
 
-    pub fn copy_anchor(&mut self) {
-        // Copy the anchor link for the currently selected heading
-        if let Some(heading_text) = self.selected_heading_text() {
-            // Convert heading to anchor format (lowercase, replace spaces with dashes)
-            let anchor = Self::heading_to_anchor(heading_text);
-            let anchor_link = format!("#{}", anchor);
-
-            // Use persistent clipboard for Linux X11 compatibility
-            if let Some(clipboard) = &mut self.clipboard {
-                match clipboard.set_text(anchor_link) {
-                    Ok(_) => {
-                        self.status_message = Some(format!("✓ Anchor link copied: #{}", anchor));
-                    }
-                    Err(e) => {
-                        self.status_message = Some(format!("✗ Clipboard error: {}", e));
-                    }
+    /// Write `export`, or ask first when a file (an earlier export or any
+    /// other) is already at its path.
+    fn export_unless_exists(&mut self, export: PendingExport) {
+        if export.path().exists() {
+            self.pending_overwrite = Some(export);
+            self.mode = AppMode::ConfirmOverwrite;
+            return;
+        }
+        self.write_export(export);
+    }
+
+    /// Write an export to its path, reporting the outcome. A diagram that
+    /// can't be written (e.g. a read-only directory or stdin input) is
+    /// copied to the clipboard instead.
+    fn write_export(&mut self, export: PendingExport) {
+        let message = match export {
+            PendingExport::Html { path, html } => match std::fs::write(&path, html) {
+                Ok(()) => format!("✓ Exported HTML to {}", path.display()),
+                Err(e) => format!("✗ Export failed: {}", e),
+            },
+            PendingExport::Diagram { path, kind, source } => {
+                match crate::tui::diagram::export_source(&path, &source) {
+                    Ok(()) => format!("✓ Exported {} to {}", kind.label(), path.display()),
+                    Err(e) => match self.copy_to_clipboard(&source) {
+                        Ok(()) => format!(
+                            "✓ {} source copied (could not write {}: {})",
+                            kind.label(),
+                            path.display(),
+                            e
+                        ),
+                        Err(_) => format!("✗ Export failed: {}", e),
+                    },
                 }
-            } else {
-                self.status_message = Some("✗ Clipboard not available".to_string());
             }
-        } else {
-            self.status_message = Some("✗ No heading selected".to_string());
+        };
+        self.status_message = Some(message);
+    }
+
+    /// Replace the existing file with the pending export.
+    pub fn confirm_overwrite(&mut self) {
+        self.mode = AppMode::Normal;
+        if let Some(export) = self.pending_overwrite.take() {
+            self.write_export(export);
+        }
+    }
+
+    /// Drop the pending export, leaving the existing file alone.
+    pub fn cancel_overwrite(&mut self) {
+        self.pending_overwrite = None;
+        self.mode = AppMode::Normal;
+        self.status_message = Some("Export cancelled".to_string());
+    }
+
+    /// Interactive-mode status line, extended with the syntax error of a
+    /// selected yaml/toml/json block when `[syntax] validate_config` is on.
+    fn interactive_status_text(&self) -> String {
+        let mut status = self.interactive_state.status_text();
+        let element = self.interactive_state.current_element();
+
+        if self.should_validate_config()
+            && let Some(element) = element
+            && let ElementType::CodeBlock {
+                language: Some(lang),
+                content,
+                ..
+            } = &element.element_type
+            && let Some(err) = self
+                .config_error_cache
+                .get(&self.selected_heading_index())
+                .and_then(|errors| errors.get(&element.id.block_idx).cloned())
+                .unwrap_or_else(|| crate::parser::validate::validate_config_block(lang, content))
+        {
+            status = format!("{} ✗ {}", status, err);
+        }
+
+        // Expand abbreviations in the selected element's text
+        if let Some(abbreviations) = self.abbreviations() {
+            let text = match element.map(|e| &e.element_type) {
+                Some(ElementType::Link { link, .. }) => Some(link.text.as_str()),
+                Some(ElementType::Checkbox { content, .. }) => Some(content.as_str()),
+                Some(ElementType::Details { summary, .. }) => Some(summary.as_str()),
+                Some(ElementType::Image { alt, .. }) => Some(alt.as_str()),
+                _ => None,
+            };
+            let hints = text.map(|t| abbreviations.hints_for(t)).unwrap_or_default();
+            if !hints.is_empty() {
+                status = format!("{} | {}", status, hints.join("; "));
+            }
         }
+
+        status
     }
 
     /// Convert heading text to anchor format using the parser's slugify for consistency
@@ -4011,10 +5872,18 @@ impl App {
         crate::parser::content::slugify(heading)
     }
 
+    /// Build `[Heading](#anchor)`, or `[Heading](file.md#anchor)` when a
+    /// filename is given. Brackets in the heading text are escaped so the
+    /// link stays valid.
+    fn heading_markdown_link(heading: &str, anchor: &str, filename: Option<&str>) -> String {
+        let text = heading.replace('[', "\\[").replace(']', "\\]");
+        format!("[{}]({}#{})", text, filename.unwrap_or_default(), anchor)
+    }
+
     /// Enter link follow mode - extract links from current section and highlight them
     pub fn enter_link_follow_mode(&mut self) {
         // Extract content for current section
-        let content = self.current_section_content();
+        let content = self.current_section_view();
 
         // Extract all links from the content
         self.links_in_view = extract_links(&content);
@@ -4158,7 +6027,7 @@ impl App {
                         self.select_outline_index(i);
 
                         // Now extract links from parent's content
-                        let content = self.current_section_content();
+                        let content = self.current_section_view();
                         self.links_in_view = extract_links(&content);
 
                         // Reset link selection
@@ -4241,6 +6110,7 @@ impl App {
 
         files.sort();
         dirs.sort();
+
         self.file_picker.files = files;
         self.file_picker.dirs = dirs;
 
@@ -4373,6 +6243,7 @@ impl App {
             selected_heading: self.selected_heading_text().map(|s| s.to_string()),
             content_scroll: self.content_scroll,
             outline_state_selected: self.outline_state.selected(),
+            collapsed_headings: self.collapsed_headings.clone(),
         };
         self.file_history.push(current_state);
         self.file_future.clear(); // Clear forward history when navigating to new file
@@ -4380,14 +6251,19 @@ impl App {
         // Load new file
         let content = std::fs::read_to_string(&file_path)
             .map_err(|e| format!("Failed to read file: {}", e))?;
-        let document = crate::parser::parse_markdown(&content);
+        let content = crate::input::normalize(&content);
         let filename = file_path
             .file_name()
             .and_then(|n| n.to_str())
             .unwrap_or("unknown.md")
             .to_string();
 
-        self.load_document(document, filename, file_path);
+        if content.len() >= crate::tui::loader::BACKGROUND_LOAD_BYTES {
+            self.load_in_background(content, filename, file_path, None);
+        } else {
+            let document = crate::parser::parse_markdown(&content);
+            self.load_document(document, filename, file_path);
+        }
 
         // Exit picker mode
         self.mode = AppMode::Normal;
@@ -4454,21 +6330,45 @@ impl App {
         self.config.content.latex_aggressive
     }
 
+    /// Check if yaml/toml/json code blocks should be validated (from config)
+    pub fn should_validate_config(&self) -> bool {
+        self.config.syntax.validate_config
+    }
+
+    /// Content line to tint as the one being read, in `content_scroll`
+    /// units: the selected element in interactive mode, otherwise the first
+    /// line of the selected heading's section. `None` when
+    /// `ui.highlight_current_line` is off or nothing is selected.
+    pub fn current_content_line(&self) -> Option<usize> {
+        if !self.config.ui.highlight_current_line {
+            return None;
+        }
+        if self.mode == AppMode::Interactive {
+            return self
+                .interactive_state
+                .current_element_line_range()
+                .map(|(start, _)| start);
+        }
+        self.selected_heading_index().map(|_| 0)
+    }
+
+    /// Abbreviations to mark in the content, when enabled and any are defined
+    pub fn abbreviations(&self) -> Option<&crate::parser::Abbreviations> {
+        (self.config.ui.abbreviations && !self.abbreviations.is_empty())
+            .then_some(&self.abbreviations)
+    }
+
     /// Handle loading a relative file link, resolving markdown extensions and fallbacks.
     ///
     /// Returns `true` if the caller should exit its current mode (link-follow or interactive).
     fn resolve_relative_file_link(
         &mut self,
-        path: &PathBuf,
+        path: &Path,
         anchor: &Option<String>,
     ) -> Result<bool, String> {
+        self.ensure_local_links(&path.display().to_string())?;
         let has_md_extension = Self::is_markdown_extension(path);
 
-        let current_dir = self
-            .current_file_path
-            .parent()
-            .ok_or("Cannot determine current directory")?;
-
         if has_md_extension {
             self.load_file(path, anchor.as_deref())?;
             // Only signal exit if we're not prompting for file creation
@@ -4476,13 +6376,13 @@ impl App {
         } else {
             // No markdown extension — try .md, then as-is, then prompt to create
             let md_path = PathBuf::from(format!("{}.md", path.display()));
-            let absolute_md_path = current_dir.join(&md_path);
+            let absolute_md_path = self.resolve_link(&md_path)?;
 
             if absolute_md_path.exists() && !absolute_md_path.is_symlink() {
                 self.load_file(&md_path, anchor.as_deref())?;
                 Ok(true)
             } else {
-                let absolute_path = current_dir.join(path);
+                let absolute_path = self.resolve_link(path)?;
 
                 if absolute_path.exists() && !absolute_path.is_symlink() {
                     // Non-markdown file — open in editor
@@ -4493,7 +6393,7 @@ impl App {
                     let relative_path = if path.extension().is_none() {
                         PathBuf::from(format!("{}.md", path.display()))
                     } else {
-                        path.clone()
+                        path.to_path_buf()
                     };
                     self.load_file(&relative_path, anchor.as_deref())?;
                     Ok(self.mode != AppMode::ConfirmFileCreate)
@@ -4511,9 +6411,10 @@ impl App {
 
         match link.target {
             crate::parser::LinkTarget::Anchor(anchor) => {
-                // Jump to heading in current document
-                self.jump_to_anchor(&anchor)?;
-                self.status_message = Some(format!("✓ Jumped to #{}", anchor));
+                if self.jump_to_footnote(&anchor) {
+                    return Ok(());
+                }
+                self.follow_anchor(&anchor)?;
                 self.exit_link_follow_mode();
                 Ok(())
             }
@@ -4540,10 +6441,7 @@ impl App {
                 let open_result = open::that(&url);
 
                 // Also copy to clipboard as backup
-                let mut clipboard_success = false;
-                if let Ok(mut clipboard) = arboard::Clipboard::new() {
-                    clipboard_success = clipboard.set_text(url.clone()).is_ok();
-                }
+                let clipboard_success = self.copy_to_clipboard(&url).is_ok();
 
                 // Set status message
                 self.status_message = match (open_result, clipboard_success) {
@@ -4565,45 +6463,176 @@ impl App {
         }
     }
 
+    /// Follow a `#fragment` link to a heading in this document, recording
+    /// the jump so `Ctrl+o` returns to the heading holding the link.
+    fn follow_anchor(&mut self, anchor: &str) -> Result<(), String> {
+        let origin = self.selected_heading_index();
+        self.jump_to_anchor(anchor)?;
+        if let Some(origin) = origin
+            && self.selected_heading_index() != Some(origin)
+        {
+            self.jumps.record(origin);
+        }
+        self.status_message = Some(format!("✓ Jumped to #{}", anchor));
+        Ok(())
+    }
+
     /// Jump to a heading by anchor name or heading text.
     ///
-    /// Supports two matching strategies (checked per-item, Strategy 1 takes priority):
-    /// 1. **Normalized anchor match** - compares `heading_to_anchor(item)` with lowercased anchor.
-    ///    Handles markdown links (`#features`, `#mixed-links-test`) and simple wikilinks (`[[#Features]]`).
-    /// 2. **Heading text match** - case-insensitive comparison of raw heading text.
-    ///    Handles wikilinks preserving spaces (`[[#Mixed Links Test]]`).
+    /// The heading is found by [`Document::find_anchor`]: GitHub's anchors
+    /// first (`#setup-1` is the second "Setup"), then a looser slug or
+    /// heading text match for wikilinks like `[[#Mixed Links Test]]`. A line
+    /// anchor (`#L12`) that matches no heading selects the section
+    /// containing that line.
     fn jump_to_anchor(&mut self, anchor: &str) -> Result<(), String> {
-        let anchor_lower = anchor.to_lowercase();
-
-        for (idx, item) in self.outline_items.iter().enumerate() {
-            // Strategy 1: Normalized anchor match
-            // The anchor from markdown links is already normalized (lowercase, dashes),
-            // so we just lowercase the query and compare with the item's normalized form.
-            if Self::heading_to_anchor(&item.text) == anchor_lower {
-                self.select_outline_index(idx);
-                return Ok(());
-            }
-
-            // Strategy 2: Direct heading text match (case-insensitive)
-            // Wikilinks like [[#Mixed Links Test]] preserve the original heading text.
-            if item.text.eq_ignore_ascii_case(anchor) {
-                self.select_outline_index(idx);
-                return Ok(());
-            }
+        let Some(heading) = self.document.find_anchor(anchor) else {
+            return Err(format!("No heading matches #{}", anchor));
+        };
+        self.expand_ancestors(heading);
+        if !self.select_by_heading_index(heading) {
+            return Err(format!("#{} is hidden by the outline filter", anchor));
         }
+        self.landing_flash.start(Instant::now());
+        Ok(())
+    }
 
-        Err(format!("Heading '{}' not found", anchor))
+    /// Unfold the headings above `heading_index`, so it shows in the outline.
+    fn expand_ancestors(&mut self, heading_index: usize) {
+        let ancestors: Vec<String> = Self::tree_path(&self.tree, heading_index)
+            .iter()
+            .rev()
+            .skip(1)
+            .map(|node| node.heading.text.clone())
+            .collect();
+        let mut unfolded = false;
+        for text in &ancestors {
+            unfolded |= self.collapsed_headings.remove(text);
+        }
+        if unfolded {
+            self.rebuild_outline_items();
+        }
     }
 
-    /// Load a file by relative path (checks for unsaved changes first)
+    /// Move from a footnote link to its counterpart in the current view: from
+    /// a reference to its note at the end of the section, or from the note's
+    /// `↩` back to the reference. Stays in link-follow or interactive mode.
     ///
-    /// Security: Validates path to prevent directory traversal attacks.
-    /// Files must be within the current file's directory or its subdirectories.
-    fn load_file(&mut self, relative_path: &PathBuf, anchor: Option<&str>) -> Result<(), String> {
-        // Check for unsaved changes before navigating to a different file
+    /// Returns false when `anchor` is not a footnote link or its counterpart
+    /// is not on screen.
+    fn jump_to_footnote(&mut self, anchor: &str) -> bool {
+        use crate::parser::LinkTarget;
+        use crate::parser::footnotes::{counterpart, superscript};
+
+        let Some(target) = counterpart(anchor) else {
+            return false;
+        };
+        let is_target = |link: &Link| matches!(&link.target, LinkTarget::Anchor(a) if *a == target);
+        let element = self.interactive_state.elements.iter().position(
+            |e| matches!(&e.element_type, ElementType::Link { link, .. } if is_target(link)),
+        );
+        match self.mode {
+            AppMode::LinkFollow => {
+                let Some(idx) = self.links_in_view.iter().position(is_target) else {
+                    return false;
+                };
+                self.link_picker.query.clear();
+                self.link_picker.active = false;
+                self.update_link_filter();
+                self.link_picker.selected = Some(idx);
+            }
+            AppMode::Interactive if element.is_some() => {}
+            _ => return false,
+        }
+        // Link-follow mode has no cursor of its own; scrolling to the matching
+        // interactive element brings the link into view
+        if element.is_some() {
+            self.interactive_state.current_index = element;
+            self.scroll_to_interactive_element(self.content_viewport_height);
+        }
+
+        self.status_message = Some(match target.strip_prefix("fnref-") {
+            Some(label) => {
+                let number = self.footnotes.number(label).unwrap_or_default();
+                let text = self.footnotes.get(label).map_or("", |n| n.text.as_str());
+                format!("{} {}", superscript(number), text)
+            }
+            None => "↩ Back to the reference".to_string(),
+        });
+        true
+    }
+
+    /// Jump between the selected footnote reference and its note, or select
+    /// the next footnote reference when the selection is not a footnote link.
+    pub fn footnote_jump(&mut self) {
+        use crate::parser::LinkTarget;
+        use crate::parser::footnotes::counterpart;
+
+        let selected = match self.mode {
+            AppMode::LinkFollow => self.get_selected_link(),
+            AppMode::Interactive => match self.interactive_state.current_element() {
+                Some(element) => match &element.element_type {
+                    ElementType::Link { link, .. } => Some(link),
+                    _ => None,
+                },
+                None => None,
+            },
+            _ => return,
+        };
+        let selected_anchor = selected.and_then(|link| match &link.target {
+            LinkTarget::Anchor(a) if counterpart(a).is_some() => Some(a.clone()),
+            _ => None,
+        });
+        if let Some(anchor) = selected_anchor {
+            self.jump_to_footnote(&anchor);
+            return;
+        }
+
+        // Otherwise select the next reference, wrapping around
+        let is_reference =
+            |link: &Link| matches!(&link.target, LinkTarget::Anchor(a) if a.starts_with("fn-"));
+        match self.mode {
+            AppMode::LinkFollow => {
+                self.link_picker.query.clear();
+                self.link_picker.active = false;
+                self.update_link_filter();
+                let current = self.link_picker.selected.unwrap_or(0);
+                let count = self.links_in_view.len();
+                let next = (1..=count)
+                    .map(|step| (current + step) % count)
+                    .find(|&i| is_reference(&self.links_in_view[i]));
+                match next {
+                    Some(idx) => self.link_picker.selected = Some(idx),
+                    None => self.status_message = Some("No footnotes here".to_string()),
+                }
+            }
+            _ => {
+                let elements = &self.interactive_state.elements;
+                let current = self.interactive_state.current_index.unwrap_or(0);
+                let count = elements.len();
+                let next = (1..=count).map(|step| (current + step) % count).find(|&i| {
+                    matches!(&elements[i].element_type, ElementType::Link { link, .. } if is_reference(link))
+                });
+                match next {
+                    Some(idx) => {
+                        self.interactive_state.current_index = Some(idx);
+                        self.scroll_to_interactive_element(self.content_viewport_height);
+                        self.status_message = Some(self.interactive_status_text());
+                    }
+                    None => self.status_message = Some("No footnotes here".to_string()),
+                }
+            }
+        }
+    }
+
+    /// Load a file by relative path (checks for unsaved changes first)
+    ///
+    /// Security: Validates path to prevent directory traversal attacks.
+    /// Files must be within the current file's directory or its subdirectories.
+    fn load_file(&mut self, relative_path: &Path, anchor: Option<&str>) -> Result<(), String> {
+        // Check for unsaved changes before navigating to a different file
         if self.has_unsaved_changes {
             self.pending_navigation = Some(PendingNavigation::LoadFile(
-                relative_path.clone(),
+                relative_path.to_path_buf(),
                 anchor.map(|s| s.to_string()),
             ));
             self.mode = AppMode::ConfirmSaveBeforeNav;
@@ -4616,36 +6645,19 @@ impl App {
     /// Internal file loading - skips unsaved changes check
     ///
     /// Security: Validates path to prevent directory traversal attacks.
-    /// Files must be within the current file's directory or its subdirectories.
+    /// Files must be within the link root (see [`Self::resolve_link_path`]).
     fn load_file_internal(
         &mut self,
-        relative_path: &PathBuf,
+        relative_path: &Path,
         anchor: Option<&str>,
     ) -> Result<(), String> {
-        // Reject absolute paths
-        if relative_path.is_absolute() {
-            return Err("Absolute paths are not allowed for security reasons".to_string());
-        }
-
-        // Reject paths containing .. components (path traversal)
-        if relative_path
-            .components()
-            .any(|c| matches!(c, std::path::Component::ParentDir))
-        {
-            return Err("Path traversal (..) is not allowed for security reasons".to_string());
-        }
-
-        // Resolve path relative to current file
-        let current_dir = self
-            .current_file_path
-            .parent()
-            .ok_or("Cannot determine current directory")?;
-        let absolute_path = current_dir.join(relative_path);
+        let absolute_path = self.resolve_link(relative_path)?;
 
         // Verify the resolved path is within allowed boundaries
-        // (defense in depth - even though we rejected .., canonicalize to be sure)
+        // (defense in depth - `..` was resolved lexically, canonicalize to be sure)
+        let boundary = Self::link_boundary(self.current_dir()?, &self.link_root);
         if let (Ok(canonical_path), Ok(canonical_base)) =
-            (absolute_path.canonicalize(), current_dir.canonicalize())
+            (absolute_path.canonicalize(), boundary.canonicalize())
             && !canonical_path.starts_with(&canonical_base)
         {
             return Err("Path escapes document directory boundary".to_string());
@@ -4667,9 +6679,9 @@ impl App {
             return Ok(()); // Not an error - we're asking user to confirm
         }
 
-        // Parse the new file
-        let new_document = crate::parser::parse_file(&absolute_path)
+        let content = std::fs::read_to_string(&absolute_path)
             .map_err(|e| format!("Failed to load file: {}", e))?;
+        let content = crate::input::normalize(&content);
 
         let new_filename = absolute_path
             .file_name()
@@ -4680,6 +6692,18 @@ impl App {
         // Save current state to history
         self.save_to_history();
 
+        // Large files parse in the background and jump to the anchor once ready
+        if content.len() >= crate::tui::loader::BACKGROUND_LOAD_BYTES {
+            self.load_in_background(
+                content,
+                new_filename,
+                absolute_path,
+                anchor.map(str::to_string),
+            );
+            return Ok(());
+        }
+        let new_document = crate::parser::parse_markdown(&content);
+
         // Load new document
         self.load_document(new_document, new_filename, absolute_path);
 
@@ -4691,6 +6715,81 @@ impl App {
         Ok(())
     }
 
+    /// Directory of the current file, which relative links start from.
+    fn current_dir(&self) -> Result<&Path, String> {
+        self.current_file_path
+            .parent()
+            .ok_or_else(|| "Cannot determine current directory".to_string())
+    }
+
+    /// Absolute path a relative link in the current file points to.
+    fn resolve_link(&self, link: &Path) -> Result<PathBuf, String> {
+        Self::resolve_link_path(self.current_dir()?, &self.link_root, link)
+    }
+
+    /// Where `..` in links may climb up to: the directory treemd was started
+    /// in when the first file is inside it, else that file's own directory.
+    fn link_root_for(file: &Path) -> PathBuf {
+        let dir = file.parent().unwrap_or(Path::new(""));
+        match std::env::current_dir().and_then(|cwd| cwd.canonicalize()) {
+            Ok(cwd) if dir.starts_with(&cwd) => cwd,
+            _ => dir.to_path_buf(),
+        }
+    }
+
+    /// Directory links from `dir` must stay inside: `root`, unless `dir` is
+    /// outside it (a file opened from elsewhere), in which case `dir` itself.
+    fn link_boundary<'a>(dir: &'a Path, root: &'a Path) -> &'a Path {
+        if dir.starts_with(root) { root } else { dir }
+    }
+
+    /// Resolve `link`, a relative link in a file in `dir`, to an absolute
+    /// path with `.` and `..` worked out.
+    ///
+    /// Links may go up out of `dir` (`../README.md`) but not out of the
+    /// [boundary](Self::link_boundary), so a document can't point treemd
+    /// at arbitrary files. Absolute links are refused.
+    fn resolve_link_path(dir: &Path, root: &Path, link: &Path) -> Result<PathBuf, String> {
+        use std::path::Component;
+
+        if link.has_root() || link.is_absolute() {
+            return Err("Absolute paths are not allowed for security reasons".to_string());
+        }
+        let mut resolved = dir.to_path_buf();
+        for component in link.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    resolved.pop();
+                }
+                Component::Normal(part) => resolved.push(part),
+                Component::RootDir | Component::Prefix(_) => {
+                    return Err("Absolute paths are not allowed for security reasons".to_string());
+                }
+            }
+        }
+        if !resolved.starts_with(Self::link_boundary(dir, root)) {
+            return Err(format!(
+                "{} is outside {}; links can't leave it",
+                link.display(),
+                Self::link_boundary(dir, root).display()
+            ));
+        }
+        Ok(resolved)
+    }
+
+    /// Refuse to follow `link` as a local file when the document was fetched
+    /// from a URL: the file it names lives next to that URL, not on disk.
+    fn ensure_local_links(&self, link: &str) -> Result<(), String> {
+        match &self.remote_source {
+            Some(url) => Err(format!(
+                "{} is relative to {}; relative links in a fetched document can't be followed",
+                link, url
+            )),
+            None => Ok(()),
+        }
+    }
+
     /// Find and load a wikilinked file
     ///
     /// Supports formats:
@@ -4710,6 +6809,8 @@ impl App {
             return Ok(());
         }
 
+        self.ensure_local_links(target)?;
+
         // Split target into file and optional anchor (e.g., "file#section" -> ("file", Some("section")))
         let (file_target, anchor) = if let Some((file, anchor)) = target.split_once('#') {
             (file, Some(anchor))
@@ -4797,6 +6898,7 @@ impl App {
             selected_heading: self.selected_heading_text().map(|s| s.to_string()),
             content_scroll: self.content_scroll,
             outline_state_selected: self.outline_state.selected(),
+            collapsed_headings: self.collapsed_headings.clone(),
         };
         self.file_history.push(state);
 
@@ -4806,17 +6908,39 @@ impl App {
 
     /// Load a new document and update all related state
     fn load_document(&mut self, document: Document, filename: String, path: PathBuf) {
+        let same_file = self.current_file_path == path;
         // Signal file watcher if path changed
-        if self.current_file_path != path {
+        if !same_file {
+            self.remember_position();
             self.file_path_changed = true;
+            // Streamed stdin stops updating once another file is open
+            self.stdin_load = None;
+            self.remote_source = None;
+            // Another file replaces the browser; `b` can bring it back
+            if let Some(browser) = self.file_browser.take() {
+                self.browser_return = Some(browser);
+            }
         }
 
+        // A newer document supersedes one still parsing
+        self.pending_load = None;
         self.document = document;
+        self.forget_content_caches();
+        self.highlighter.clear_cache();
+        self.abbreviations = crate::parser::Abbreviations::parse(&self.document.content);
+        self.footnotes = crate::parser::Footnotes::parse(&self.document.content);
         self.filename = filename;
         self.current_file_path = path;
 
-        // Rebuild tree and outline (with overview entry if applicable)
+        // Rebuild tree and outline (with overview entry if applicable). Folds
+        // survive a reload of the same file; another file opens unfolded.
         self.tree = self.document.build_tree();
+        if same_file {
+            self.collapsed_headings = Self::surviving_folds(&self.collapsed_headings, &self.tree);
+        } else {
+            self.collapsed_headings.clear();
+            self.reveal_depth = None;
+        }
         self.rebuild_outline_items();
 
         // Reset selection to first item
@@ -4835,6 +6959,9 @@ impl App {
 
         // Clear previous selection tracking
         self.previous_selection = None;
+        // Jump list entries index the old document's headings
+        self.jumps.clear();
+        self.search_origin = None;
         // Document changed — force a metrics recompute on the next render.
         self.metrics_dirty = true;
 
@@ -4860,6 +6987,70 @@ impl App {
             self.latex_hint_shown = true;
             self.set_status_message("LaTeX detected · filtered via hide_latex in config");
         }
+
+        if !same_file {
+            self.return_to_remembered_position();
+        }
+    }
+
+    /// Record where the current file is being viewed in the view state file,
+    /// so it reopens there. Only files on disk are remembered, and not while
+    /// the document shown is still loading.
+    pub fn remember_position(&self) {
+        let Some(state_path) = &self.view_state_path else {
+            return;
+        };
+        if self.pending_load.is_some()
+            || self.remote_source.is_some()
+            || !self.current_file_path.is_file()
+        {
+            return;
+        }
+        let heading_path = self
+            .selected_heading_index()
+            .map(|idx| {
+                Self::tree_path(&self.tree, idx)
+                    .iter()
+                    .map(|node| node.heading.text.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let mut state = ViewState::load(state_path);
+        state.remember(
+            FileView {
+                path: self.current_file_path.clone(),
+                heading_path,
+                content_scroll: self.content_scroll,
+            },
+            VIEW_STATE_LIMIT,
+        );
+        let _ = state.save(state_path);
+    }
+
+    /// Select the heading the current file was last left at. The scroll is
+    /// restored too, unless the heading was only found by a fallback.
+    pub fn return_to_remembered_position(&mut self) {
+        let Some(state_path) = &self.view_state_path else {
+            return;
+        };
+        if !self.current_file_path.is_file() {
+            return;
+        }
+        let state = ViewState::load(state_path);
+        let Some(view) = state.get(&self.current_file_path) else {
+            return;
+        };
+        let Some((index, exact)) = view_state::locate(&self.tree, &view.heading_path) else {
+            return;
+        };
+        if self.select_by_heading_index(index) && exact {
+            // Metrics first: a selection change resets the scroll
+            self.update_content_metrics();
+            self.content_scroll = view.content_scroll;
+            self.content_scroll_state = self
+                .content_scroll_state
+                .position(view.content_scroll as usize);
+        }
     }
 
     /// Navigate back in file history
@@ -4877,6 +7068,7 @@ impl App {
             selected_heading: self.selected_heading_text().map(|s| s.to_string()),
             content_scroll: self.content_scroll,
             outline_state_selected: self.outline_state.selected(),
+            collapsed_headings: self.collapsed_headings.clone(),
         };
         self.file_future.push(current_state);
 
@@ -4898,6 +7090,7 @@ impl App {
             selected_heading: self.selected_heading_text().map(|s| s.to_string()),
             content_scroll: self.content_scroll,
             outline_state_selected: self.outline_state.selected(),
+            collapsed_headings: self.collapsed_headings.clone(),
         };
         self.file_history.push(current_state);
 
@@ -4907,9 +7100,298 @@ impl App {
         Ok(())
     }
 
+    /// Browse the markdown files under `dir`, previewing the first. Returns
+    /// `false` (and changes nothing) when there are none.
+    pub fn open_directory(&mut self, dir: PathBuf) -> bool {
+        let files = crate::input::markdown_files(&dir);
+        let browser = match self.config.ui.group_by_frontmatter.as_deref() {
+            Some(field) => {
+                let contents = files.into_iter().map(|path| {
+                    let content = std::fs::read_to_string(&path).unwrap_or_default();
+                    (path, content)
+                });
+                FileBrowser::grouped(dir, group_files_by_frontmatter(contents, field))
+            }
+            None => FileBrowser::new(dir, &files),
+        };
+        let Some(browser) = browser else {
+            return false;
+        };
+        self.file_browser = Some(browser);
+        self.focus = Focus::Outline;
+        self.preview_browser_file();
+        true
+    }
+
+    /// Handle `action` while browsing a directory, or `None` to run it as
+    /// usual. With the file tree focused, movement keys pick a file and
+    /// Enter opens it; outline commands have nothing to act on.
+    fn browser_action(&mut self, action: Action) -> Option<ActionResult> {
+        use Action::*;
+
+        let on_tree = self.focus == Focus::Outline;
+        match action {
+            Next | Previous | First | Last | PageDown | PageUp | HalfPageDown | HalfPageUp
+                if on_tree =>
+            {
+                let count = self.take_count() as isize;
+                let page = self.content_viewport_height.max(1) as isize;
+                let browser = self.file_browser.as_mut()?;
+                let moved = match action {
+                    Next => browser.move_by(count),
+                    Previous => browser.move_by(-count),
+                    PageDown => browser.move_by(page),
+                    PageUp => browser.move_by(-page),
+                    HalfPageDown => browser.move_by(page / 2),
+                    HalfPageUp => browser.move_by(-page / 2),
+                    First => browser.move_to_end(false),
+                    _ => browser.move_to_end(true),
+                };
+                if moved {
+                    self.preview_browser_file();
+                }
+            }
+            ToggleExpand | Expand if on_tree => self.open_browser_file(),
+            // Views, scrolling and search of the previewed file
+            Noop | Redraw | Quit | ToggleHelp | ToggleFocus | ToggleFocusBack | ToggleOutline
+            | OutlineWidthIncrease | OutlineWidthDecrease | OutlineWidthGrow
+            | OutlineWidthShrink | Next | Previous | First | Last | PageDown | PageUp
+            | HalfPageDown | HalfPageUp | ScrollDown | ScrollUp | ScrollLeft | ScrollRight
+            | ToggleWrap | ToggleLineNumbers | CycleLineNumberStyle | ToggleRawSource
+            | ToggleMouseCapture | ToggleThemePicker | CycleCodeTheme | OpenFilePicker
+            | OpenInEditor | OpenCommandPalette | EnterDocSearch | NextMatch | PrevMatch
+            | CopyContent | CopyPlainText | ShowStats => return None,
+            // Actions of other modes, only here if bound in normal mode
+            HelpScrollDown
+            | HelpScrollUp
+            | HelpSearch
+            | ThemePickerNext
+            | ThemePickerPrevious
+            | SearchBackspace
+            | CommandPaletteNext
+            | CommandPalettePrev
+            | CommandPaletteAutocomplete
+            | ConfirmAction
+            | CancelAction
+            | DiscardAndQuit
+            | DiscardAndContinue
+            | ParentDirectory
+            | ToggleHidden
+            | ApplyTheme
+            | ExportTheme => {
+                return None;
+            }
+            // Outline, link and edit commands need the file opened first
+            Expand
+            | ToggleExpand
+            | Collapse
+            | ExpandMoreLevels
+            | CollapseMoreLevels
+            | CollapseAll
+            | ExpandAll
+            | FoldToLevel
+            | JumpToParent
+            | JumpBack
+            | JumpForward
+            | ToggleTodoFilter
+            | ToggleHeadingMarkers
+            | ToggleOutlineMode
+            | SetBookmark
+            | JumpToBookmark
+            | EnterInteractiveMode
+            | ExitInteractiveMode
+            | EnterLinkFollowMode
+            | EnterSearchMode
+            | ToggleSearchMode
+            | ToggleOutlineSearchScope
+            | ExitMode
+            | NextLink
+            | PreviousLink
+            | FollowLink
+            | LinkSearch
+            | JumpToFootnote
+            | InteractiveNext
+            | InteractivePrevious
+            | InteractiveActivate
+            | InteractiveNextLink
+            | InteractivePreviousLink
+            | InteractiveLeft
+            | InteractiveRight
+            | CopyTableCell
+            | CopyTableRow
+            | CopyTableMarkdown
+            | CopyTableCsv
+            | TableSort
+            | TableRowInsert
+            | TableRowDelete
+            | CopyAnchor
+            | CopyHeadingLink
+            | GoBack
+            | GoForward
+            | NextFile
+            | PreviousFile
+            | ExportDiagram
+            | ExportHtml
+            | UndoEdit
+            | JumpToHeading1
+            | JumpToHeading2
+            | JumpToHeading3
+            | JumpToHeading4
+            | JumpToHeading5
+            | JumpToHeading6
+            | JumpToHeading7
+            | JumpToHeading8
+            | JumpToHeading9
+            | JumpToLink1
+            | JumpToLink2
+            | JumpToLink3
+            | JumpToLink4
+            | JumpToLink5
+            | JumpToLink6
+            | JumpToLink7
+            | JumpToLink8
+            | JumpToLink9 => {
+                self.clear_count();
+                let name = self.filename.clone();
+                self.set_status_message(&format!("Enter opens {}", name));
+            }
+        }
+        Some(ActionResult::Continue)
+    }
+
+    /// Load the file selected in the directory browser and show all of it.
+    fn preview_browser_file(&mut self) {
+        let Some(path) = self
+            .file_browser
+            .as_ref()
+            .map(|b| b.selected_file().to_path_buf())
+        else {
+            return;
+        };
+        match crate::parser::parse_file(&path) {
+            Ok(document) => {
+                let filename = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("unknown")
+                    .to_string();
+                // The preview replaces the document but not the browser
+                let browser = self.file_browser.take();
+                self.load_document(document, filename, path);
+                self.file_browser = browser;
+                // No heading selected: the content pane shows the whole file
+                self.outline_state.select(None);
+                self.update_content_metrics();
+            }
+            Err(e) => self.set_status_message(&format!("✗ Failed to load file: {}", e)),
+        }
+    }
+
+    /// Leave the directory browser for the previewed file's outline.
+    fn open_browser_file(&mut self) {
+        let Some(browser) = self.file_browser.take() else {
+            return;
+        };
+        self.browser_return = Some(browser);
+        if !self.outline_items.is_empty() {
+            self.select_outline_index(0);
+        }
+        self.update_content_metrics();
+    }
+
+    /// Go back to the directory browser a file was opened from, previewing
+    /// the file selected there.
+    fn return_to_browser(&mut self) {
+        if self.has_unsaved_changes {
+            self.set_status_message("⚠ Save or undo your changes before going back");
+            return;
+        }
+        let Some(browser) = self.browser_return.take() else {
+            return;
+        };
+        self.file_browser = Some(browser);
+        self.focus = Focus::Outline;
+        self.preview_browser_file();
+    }
+
+    /// Set the files given on the command line, in order.
+    pub fn set_files(&mut self, files: Vec<PathBuf>) {
+        self.file_list = FileList::new(files);
+    }
+
+    /// Switch to the next (or previous) command-line file, wrapping around
+    /// (checks for unsaved changes first)
+    fn switch_file(&mut self, forward: bool) {
+        if !self.file_list.can_switch() {
+            self.set_status_message("No other files (open several: treemd a.md b.md)");
+            return;
+        }
+        if self.has_unsaved_changes {
+            self.pending_navigation = Some(PendingNavigation::SwitchFile(forward));
+            self.mode = AppMode::ConfirmSaveBeforeNav;
+            return;
+        }
+        if let Err(e) = self.switch_file_internal(forward) {
+            self.status_message = Some(format!("✗ {}", e));
+        }
+    }
+
+    /// Switch files without the unsaved changes check. The file left behind
+    /// remembers its position; the one switched to gets its own back. The
+    /// switch is also recorded in the back/forward history.
+    fn switch_file_internal(&mut self, forward: bool) -> Result<(), String> {
+        let current = self.current_file_path.clone();
+        self.file_list.save(
+            &current,
+            FilePosition {
+                selected_heading: self.selected_heading_text().map(|s| s.to_string()),
+                content_scroll: self.content_scroll,
+            },
+        );
+        let (path, position) = self
+            .file_list
+            .step(&current, forward)
+            .ok_or("No files to switch to")?;
+
+        let document =
+            crate::parser::parse_file(&path).map_err(|e| format!("Failed to load file: {}", e))?;
+        let filename = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        self.save_to_history();
+        // A file not yet visited this session opens where it was last left
+        self.load_document(document, filename, path);
+        if let Some(position) = position {
+            if let Some(heading) = &position.selected_heading {
+                self.select_by_text(heading);
+            }
+            // Metrics first: a selection change resets the scroll
+            self.update_content_metrics();
+            self.content_scroll = position.content_scroll;
+            self.content_scroll_state = self
+                .content_scroll_state
+                .position(position.content_scroll as usize);
+        }
+
+        if let Some(i) = self.file_list.index_of(&self.current_file_path) {
+            self.set_status_message(&format!(
+                "File {}/{}: {}",
+                i + 1,
+                self.file_list.len(),
+                self.filename
+            ));
+        }
+        Ok(())
+    }
+
     /// Restore a file state from history
     fn restore_file_state(&mut self, state: FileState) {
         self.load_document(state.document, state.filename, state.path);
+        self.collapsed_headings = state.collapsed_headings;
+        self.rebuild_outline_items();
 
         // Restore selection and scroll position
         if let Some(selected_idx) = state.outline_state_selected
@@ -4936,6 +7418,7 @@ impl App {
 
         // Reload the file
         let content = std::fs::read_to_string(&self.current_file_path)
+            .map(|raw| crate::input::normalize(&raw))
             .map_err(|e| format!("Failed to reload file: {}", e))?;
 
         if content == self.document.content {
@@ -4974,7 +7457,7 @@ impl App {
         }
 
         // Get current section content to index
-        let content = self.current_section_content();
+        let content = self.current_section_view();
 
         // Parse content into blocks
         use crate::parser::content::parse_content;
@@ -5105,6 +7588,22 @@ impl App {
                 self.follow_link_from_interactive(&link.clone())?;
                 Ok(())
             }
+            ElementType::CodeBlock { language, .. }
+                if crate::tui::diagram::placeholder_kind(language.as_deref()).is_some() =>
+            {
+                // Swap the diagram placeholder for its source (or back)
+                self.interactive_state.toggle_diagram_source(element.id);
+                self.reindex_interactive_elements();
+                self.status_message = Some(
+                    if self.interactive_state.is_diagram_source_shown(element.id) {
+                        "✓ Showing diagram source"
+                    } else {
+                        "✓ Showing diagram placeholder"
+                    }
+                    .to_string(),
+                );
+                Ok(())
+            }
             ElementType::CodeBlock { content, .. } => {
                 // Copy code to clipboard
                 self.copy_to_clipboard(content)?;
@@ -5134,7 +7633,7 @@ impl App {
 
     /// Re-index interactive elements after state changes
     pub fn reindex_interactive_elements(&mut self) {
-        let content = self.current_section_content();
+        let content = self.current_section_view();
 
         use crate::parser::content::parse_content;
         let blocks = parse_content(&content, 0);
@@ -5142,6 +7641,15 @@ impl App {
         self.populate_image_cache();
     }
 
+    /// Refuse to change a document that has no file behind it.
+    fn ensure_backing_file(&self) -> Result<(), String> {
+        if self.query_results.as_ref() == Some(&self.current_file_path) {
+            Err("Query results are read-only; open the file to edit it".to_string())
+        } else {
+            Ok(())
+        }
+    }
+
     /// Toggle a checkbox and save changes to the file
     fn toggle_checkbox_and_save(
         &mut self,
@@ -5149,6 +7657,7 @@ impl App {
         item_idx: usize,
         checked: bool,
     ) -> Result<(), String> {
+        self.ensure_backing_file()?;
         use crate::parser::content::parse_content;
         use crate::parser::output::Block;
 
@@ -5164,8 +7673,10 @@ impl App {
         // Identify the checkbox text and its occurrence index among identical
         // checkboxes (same text, same state) in this section, so duplicates
         // toggle the right line.
-        let content = self.current_section_content();
-        let blocks = parse_content(&content, 0);
+        let blocks = parse_content(&self.current_section_content(), 0);
+        let block_idx = self
+            .source_block_index(block_idx)
+            .ok_or_else(|| "Could not find checkbox content".to_string())?;
 
         let target_text = if let Some(Block::List { items, .. }) = blocks.get(block_idx) {
             items.get(item_idx).map(|item| clean(&item.content))
@@ -5202,31 +7713,33 @@ impl App {
             crate::parser::utils::strip_markdown_inline,
         )?;
 
-        // Atomic write: write to temp file, then rename (prevents data corruption)
-        use std::io::Write;
-        let parent_dir = self
-            .current_file_path
-            .parent()
-            .ok_or("Cannot determine parent directory")?;
-
-        let mut temp_file = tempfile::NamedTempFile::new_in(parent_dir)
-            .map_err(|e| format!("Failed to create temp file: {}", e))?;
+        self.save_content_and_reload(&new_content)?;
 
-        temp_file
-            .write_all(new_content.as_bytes())
-            .map_err(|e| format!("Failed to write temp file: {}", e))?;
+        let new_state = if checked { "unchecked" } else { "checked" };
+        self.status_message = Some(if had_pending {
+            format!(
+                "✓ Checkbox {} and saved (pending edits included)",
+                new_state
+            )
+        } else {
+            format!("✓ Checkbox {} and saved", new_state)
+        });
 
-        temp_file
-            .flush()
-            .map_err(|e| format!("Failed to flush temp file: {}", e))?;
+        Ok(())
+    }
 
-        // Atomic rename (same filesystem guarantees atomicity)
-        temp_file
-            .persist(&self.current_file_path)
-            .map_err(|e| format!("Failed to save file: {}", e))?;
+    /// Write `new_content` (the whole document, including any buffered
+    /// table edits) to the file and reload it, keeping the scroll position
+    /// and interactive selection.
+    fn save_content_and_reload(&mut self, new_content: &str) -> Result<(), String> {
+        self.ensure_backing_file()?;
+        crate::tui::edits::write_atomically(
+            &self.current_file_path,
+            &self.on_disk_format().restore(new_content),
+        )?;
 
-        // Any buffered table edits were persisted along with the toggle
-        if had_pending {
+        // Any buffered table edits were persisted along with the change
+        if self.has_unsaved_changes {
             self.pending_edits.clear();
             self.has_unsaved_changes = false;
         }
@@ -5267,16 +7780,6 @@ impl App {
         // Without this, file watcher detects our save and triggers a second reload
         self.suppress_file_watch = true;
 
-        let new_state = if checked { "unchecked" } else { "checked" };
-        self.status_message = Some(if had_pending {
-            format!(
-                "✓ Checkbox {} and saved (pending edits included)",
-                new_state
-            )
-        } else {
-            format!("✓ Checkbox {} and saved", new_state)
-        });
-
         Ok(())
     }
 
@@ -5314,10 +7817,11 @@ impl App {
 
         match &link.target {
             LinkTarget::Anchor(anchor) => {
-                // Jump to heading in current document
-                self.jump_to_anchor(anchor)?;
+                if self.jump_to_footnote(anchor) {
+                    return Ok(());
+                }
+                self.follow_anchor(anchor)?;
                 self.exit_interactive_mode();
-                self.status_message = Some(format!("✓ Jumped to #{}", anchor));
                 Ok(())
             }
             LinkTarget::RelativeFile { path, anchor } => {
@@ -5355,37 +7859,73 @@ impl App {
 
     /// Copy text to clipboard
     fn copy_to_clipboard(&mut self, text: &str) -> Result<(), String> {
-        if let Some(clipboard) = &mut self.clipboard {
-            clipboard
-                .set_text(text.to_string())
-                .map_err(|e| format!("Clipboard error: {}", e))?;
-            Ok(())
-        } else {
-            Err("Clipboard not available".to_string())
+        self.clipboard.set_text(text)
+    }
+
+    /// Get table data for current interactive element, rows in the order
+    /// they are displayed
+    pub fn get_current_table_data(&self) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+        let id = self.interactive_state.current_element()?.id;
+        match self.current_table_block()? {
+            crate::parser::output::Block::Table { headers, rows, .. } => Some((
+                headers,
+                self.interactive_state.displayed_table_rows(id, &rows),
+            )),
+            _ => None,
         }
     }
 
-    /// Get table data for current interactive element
-    fn get_current_table_data(&self) -> Option<(Vec<String>, Vec<Vec<String>>)> {
+    /// Sort the table by the selected column, flipping direction on repeat
+    pub fn sort_table(&mut self) {
+        let Some(sort) = self.interactive_state.sort_table_by_selected_column() else {
+            return;
+        };
+        let column = self
+            .get_current_table_data()
+            .and_then(|(headers, _)| headers.get(sort.col).cloned())
+            .unwrap_or_default();
+        let direction = if sort.descending { "↓" } else { "↑" };
+        self.set_status_message(&format!("Sorted by {} {}", column, direction));
+    }
+
+    /// The `Block::Table` of the selected table element
+    fn current_table_block(&self) -> Option<crate::parser::output::Block> {
         if let Some(element) = self.interactive_state.current_element()
             && let crate::tui::interactive::ElementType::Table { block_idx, .. } =
                 &element.element_type
         {
             // Parse current section to get table data
-            let content = self.current_section_content();
-
             use crate::parser::content::parse_content;
-            let blocks = parse_content(&content, 0);
-
-            if let Some(crate::parser::output::Block::Table { headers, rows, .. }) =
-                blocks.get(*block_idx)
-            {
-                return Some((headers.clone(), rows.clone()));
-            }
+            return parse_content(&self.current_section_content(), 0)
+                .into_iter()
+                .nth(self.source_block_index(*block_idx)?)
+                .filter(|block| matches!(block, crate::parser::output::Block::Table { .. }));
         }
         None
     }
 
+    /// Index among the section's own blocks of block `view_idx` of the
+    /// content pane. The pane renders footnotes, dropping their definitions,
+    /// so edits read the section source and find the block there by its
+    /// position among blocks of the same kind.
+    fn source_block_index(&self, view_idx: usize) -> Option<usize> {
+        use crate::parser::content::parse_content;
+        use std::mem::discriminant;
+
+        let view = parse_content(&self.current_section_view(), 0);
+        let kind = discriminant(view.get(view_idx)?);
+        let rank = view[..view_idx]
+            .iter()
+            .filter(|block| discriminant(*block) == kind)
+            .count();
+        parse_content(&self.current_section_content(), 0)
+            .iter()
+            .enumerate()
+            .filter(|(_, block)| discriminant(*block) == kind)
+            .nth(rank)
+            .map(|(i, _)| i)
+    }
+
     /// Copy table cell to clipboard
     pub fn copy_table_cell(&mut self) -> Result<(), String> {
         if let Some((headers, rows)) = self.get_current_table_data()
@@ -5411,38 +7951,39 @@ impl App {
         Err("No row selected".to_string())
     }
 
-    /// Copy entire table as markdown
+    /// Copy entire table as markdown, columns padded to line up
     pub fn copy_table_markdown(&mut self) -> Result<(), String> {
-        if let Some((headers, rows)) = self.get_current_table_data() {
-            let mut table_md = String::new();
-
-            // Header row
-            table_md.push_str("| ");
-            table_md.push_str(&headers.join(" | "));
-            table_md.push_str(" |\n");
-
-            // Separator row
-            table_md.push_str("| ");
-            table_md.push_str(&vec!["---"; headers.len()].join(" | "));
-            table_md.push_str(" |\n");
-
-            // Data rows
-            for row in &rows {
-                table_md.push_str("| ");
-                table_md.push_str(&row.join(" | "));
-                table_md.push_str(" |\n");
-            }
+        let Some(crate::parser::output::Block::Table {
+            headers,
+            alignments,
+            ..
+        }) = self.current_table_block()
+        else {
+            return Err("No table data available".to_string());
+        };
+        let (_, rows) = self
+            .get_current_table_data()
+            .ok_or("No table data available")?;
+        let table_md = crate::tui::table_export::to_markdown(&headers, &alignments, &rows);
+        self.copy_to_clipboard(&table_md)?;
+        self.status_message = Some("✓ Table copied as markdown".to_string());
+        Ok(())
+    }
 
-            self.copy_to_clipboard(&table_md)?;
-            self.status_message = Some("✓ Table copied as markdown".to_string());
-            Ok(())
-        } else {
-            Err("No table data available".to_string())
-        }
+    /// Copy entire table as CSV
+    pub fn copy_table_csv(&mut self) -> Result<(), String> {
+        let (headers, rows) = self
+            .get_current_table_data()
+            .ok_or("No table data available")?;
+        let csv = crate::tui::table_export::to_csv(&headers, &rows);
+        self.copy_to_clipboard(&csv)?;
+        self.status_message = Some("✓ Table copied as CSV".to_string());
+        Ok(())
     }
 
     /// Enter cell edit mode for the currently selected table cell
     pub fn enter_cell_edit_mode(&mut self) -> Result<(), String> {
+        self.ensure_backing_file()?;
         if let Some((headers, rows)) = self.get_current_table_data()
             && let Some((row, col)) = self.interactive_state.get_table_position()
         {
@@ -5460,7 +8001,7 @@ impl App {
 
             self.cell_edit_value = cell_value.clone();
             self.cell_edit_original_value = cell_value; // Store original for undo
-            self.cell_edit_row = row;
+            self.cell_edit_row = self.table_source_row(row);
             self.cell_edit_col = col;
             self.mode = AppMode::CellEdit;
             return Ok(());
@@ -5468,7 +8009,62 @@ impl App {
         Err("No cell selected for editing".to_string())
     }
 
-    /// Sanitize table cell content to prevent markdown injection
+    /// The file's row (0 = header) behind displayed table row `row`; they
+    /// differ while the table is sorted.
+    fn table_source_row(&self, row: usize) -> usize {
+        match (
+            self.interactive_state.current_element(),
+            self.current_table_block(),
+        ) {
+            (Some(element), Some(crate::parser::output::Block::Table { rows, .. })) if row > 0 => {
+                self.interactive_state
+                    .table_row_order(element.id, &rows)
+                    .get(row - 1)
+                    .map_or(row, |source| source + 1)
+            }
+            _ => row,
+        }
+    }
+
+    /// Insert a blank row below the selected one, or delete the selected
+    /// row, and save the file
+    pub fn edit_table_row(&mut self, edit: crate::tui::edits::RowEdit) -> Result<(), String> {
+        use crate::tui::edits::RowEdit;
+
+        let (row, _) = self
+            .interactive_state
+            .get_table_position()
+            .ok_or("No row selected")?;
+        let source_row = self.table_source_row(row);
+        let table_index = self.calculate_current_table_index()?;
+        let section_start_line = self.current_section_line_range().0;
+        let new_content = crate::tui::edits::edit_table_row(
+            &self.document.content,
+            section_start_line,
+            table_index,
+            source_row,
+            edit,
+        )?;
+
+        // Rows now appear in file order, so the selection can follow the edit
+        self.interactive_state.table_sort = None;
+        self.save_content_and_reload(&new_content)?;
+        let rows = self
+            .get_current_table_data()
+            .map_or(0, |(_, rows)| rows.len());
+        let selected = match edit {
+            RowEdit::InsertBelow => source_row + 1,
+            RowEdit::Delete => source_row.min(rows),
+        };
+        self.interactive_state.select_table_row(selected);
+        self.status_message = Some(match edit {
+            RowEdit::InsertBelow => "✓ Row inserted and saved".to_string(),
+            RowEdit::Delete => "✓ Row deleted and saved".to_string(),
+        });
+        Ok(())
+    }
+
+    /// Sanitize table cell content to prevent markdown injection
     fn sanitize_table_cell(value: &str) -> String {
         value
             .replace('|', "\\|") // Escape pipe characters (table delimiters)
@@ -5478,6 +8074,7 @@ impl App {
     /// Buffer the edited cell value in memory (does not write to file)
     /// Use save_pending_edits_to_file() to write changes to disk
     pub fn save_edited_cell(&mut self) -> Result<(), String> {
+        self.ensure_backing_file()?;
         // Sanitize the cell value to prevent table structure corruption
         let sanitized_value = Self::sanitize_table_cell(&self.cell_edit_value);
 
@@ -5502,6 +8099,7 @@ impl App {
             &sanitized_value,
         )?;
         self.document.content = new_content;
+        self.forget_content_caches();
 
         // Store the edit in the pending buffer for undo capability
         let pending_edit = PendingEdit {
@@ -5541,56 +8139,47 @@ impl App {
         let blocks = parse_content(&section_content, 0);
 
         // Find the block index of the current table element
-        if let Some(element) = self.interactive_state.current_element() {
-            let block_idx = element.id.block_idx;
-
-            if let Some(Block::Table { .. }) = blocks.get(block_idx) {
-                // Count tables before this one in the section
-                let tables_before_in_section: usize = blocks[..block_idx]
-                    .iter()
-                    .filter(|b| matches!(b, Block::Table { .. }))
-                    .count();
+        if let Some(element) = self.interactive_state.current_element()
+            && let Some(block_idx) = self.source_block_index(element.id.block_idx)
+            && let Some(Block::Table { .. }) = blocks.get(block_idx)
+        {
+            // Count tables before this one in the section
+            let tables_before_in_section: usize = blocks[..block_idx]
+                .iter()
+                .filter(|b| matches!(b, Block::Table { .. }))
+                .count();
 
-                return Ok(tables_before_in_section);
-            }
+            return Ok(tables_before_in_section);
         }
 
         Err("Could not locate table".to_string())
     }
 
+    /// BOM and line-ending convention of the file currently on disk.
+    ///
+    /// The in-memory document is always normalized to LF, so saves convert
+    /// back to this format to avoid rewriting every line of a CRLF file.
+    fn on_disk_format(&self) -> crate::input::SourceFormat {
+        std::fs::read_to_string(&self.current_file_path)
+            .map(|raw| crate::input::SourceFormat::detect(&raw))
+            .unwrap_or_default()
+    }
+
     /// Write all pending edits to the file
     pub fn save_pending_edits_to_file(&mut self) -> Result<(), String> {
-        use std::io::Write;
-
+        self.ensure_backing_file()?;
         if !self.has_unsaved_changes {
             self.status_message = Some("No changes to save".to_string());
             return Ok(());
         }
 
-        // Atomic write: write to temp file, then rename (prevents data corruption)
-        let parent_dir = self
-            .current_file_path
-            .parent()
-            .ok_or("Cannot determine parent directory")?;
-
-        let mut temp_file = tempfile::NamedTempFile::new_in(parent_dir)
-            .map_err(|e| format!("Failed to create temp file: {}", e))?;
-
-        temp_file
-            .write_all(self.document.content.as_bytes())
-            .map_err(|e| format!("Failed to write temp file: {}", e))?;
-
-        temp_file
-            .flush()
-            .map_err(|e| format!("Failed to flush temp file: {}", e))?;
-
         // Suppress file watcher for our own save
         self.suppress_file_watch = true;
 
-        // Atomic rename
-        temp_file
-            .persist(&self.current_file_path)
-            .map_err(|e| format!("Failed to save file: {}", e))?;
+        crate::tui::edits::write_atomically(
+            &self.current_file_path,
+            &self.on_disk_format().restore(&self.document.content),
+        )?;
 
         // Clear the pending edits buffer
         let edit_count = self.pending_edits.len();
@@ -5620,6 +8209,7 @@ impl App {
             )?;
 
             self.document.content = new_content;
+            self.forget_content_caches();
             self.has_unsaved_changes = !self.pending_edits.is_empty();
 
             if self.pending_edits.is_empty() {
@@ -5668,6 +8258,269 @@ impl App {
     }
 }
 
+#[cfg(test)]
+mod link_path_tests {
+    use super::*;
+
+    fn resolve(dir: &str, link: &str) -> Result<PathBuf, String> {
+        App::resolve_link_path(Path::new(dir), Path::new("/repo"), Path::new(link))
+    }
+
+    #[test]
+    fn links_resolve_against_the_current_file() {
+        assert_eq!(
+            resolve("/repo/docs", "guide.md"),
+            Ok(PathBuf::from("/repo/docs/guide.md"))
+        );
+        assert_eq!(
+            resolve("/repo/docs", "./api/index.md"),
+            Ok(PathBuf::from("/repo/docs/api/index.md"))
+        );
+    }
+
+    #[test]
+    fn parent_links_stay_inside_the_root() {
+        assert_eq!(
+            resolve("/repo/docs/api", "../../README.md"),
+            Ok(PathBuf::from("/repo/README.md"))
+        );
+        assert_eq!(
+            resolve("/repo/docs", "api/../guide.md"),
+            Ok(PathBuf::from("/repo/docs/guide.md"))
+        );
+        assert!(resolve("/repo/docs", "../../etc/passwd").is_err());
+        assert!(resolve("/repo", "../repo-other/x.md").is_err());
+        assert!(resolve("/repo/docs", "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn files_outside_the_root_keep_to_their_directory() {
+        assert_eq!(
+            resolve("/elsewhere/notes", "todo.md"),
+            Ok(PathBuf::from("/elsewhere/notes/todo.md"))
+        );
+        assert!(resolve("/elsewhere/notes", "../secret.md").is_err());
+    }
+}
+
+#[cfg(test)]
+mod heading_link_tests {
+    use super::*;
+
+    #[test]
+    fn heading_link_without_filename() {
+        assert_eq!(
+            App::heading_markdown_link("Getting Started", "getting-started", None),
+            "[Getting Started](#getting-started)"
+        );
+    }
+
+    #[test]
+    fn heading_link_with_filename() {
+        assert_eq!(
+            App::heading_markdown_link("Getting Started", "getting-started", Some("guide.md")),
+            "[Getting Started](guide.md#getting-started)"
+        );
+    }
+
+    #[test]
+    fn heading_link_escapes_brackets() {
+        assert_eq!(
+            App::heading_markdown_link("Use [x] flags", "use-x-flags", None),
+            "[Use \\[x\\] flags](#use-x-flags)"
+        );
+    }
+}
+
+#[cfg(test)]
+mod count_prefix_tests {
+    use super::*;
+
+    #[test]
+    fn counted_steps_clamp_to_the_list() {
+        assert_eq!(App::step_index(Some(2), 10, 5, true), 7);
+        assert_eq!(App::step_index(Some(8), 10, 5, true), 9);
+        assert_eq!(App::step_index(Some(3), 10, 5, false), 0);
+        assert_eq!(App::step_index(None, 10, 5, true), 0);
+        assert_eq!(App::step_index(Some(0), 0, 1, true), 0);
+    }
+
+    #[test]
+    fn lone_digit_falls_back_to_heading_jump() {
+        // `3` with nothing after it jumps to the third heading
+        assert_eq!(App::lone_digit_heading(Some(3), false), Some(2));
+        assert_eq!(App::lone_digit_heading(Some(1), false), Some(0));
+        // A longer count, or one followed by a partial chord, is a count
+        assert_eq!(App::lone_digit_heading(Some(12), false), None);
+        assert_eq!(App::lone_digit_heading(Some(3), true), None);
+        assert_eq!(App::lone_digit_heading(None, false), None);
+    }
+
+    #[test]
+    fn keybinding_conflicts_summarized_at_startup() {
+        assert_eq!(App::keybinding_conflict_message(&[]), None);
+        let conflicts = ["Normal: a".to_string(), "Normal: b".to_string()];
+        assert_eq!(
+            App::keybinding_conflict_message(&conflicts[..1]).as_deref(),
+            Some("⚠ Keybinding conflict: Normal: a")
+        );
+        assert_eq!(
+            App::keybinding_conflict_message(&conflicts).as_deref(),
+            Some("⚠ 2 keybinding conflicts: Normal: a (+1 more)")
+        );
+    }
+}
+
+#[cfg(test)]
+mod outline_search_tests {
+    use super::*;
+
+    #[test]
+    fn cycle_match_wraps_in_both_directions() {
+        let matches = [1, 4, 7];
+        assert_eq!(App::cycle_match(&matches, Some(1), true), Some(1));
+        assert_eq!(App::cycle_match(&matches, Some(5), true), Some(2));
+        assert_eq!(App::cycle_match(&matches, Some(7), true), Some(0));
+        assert_eq!(App::cycle_match(&matches, Some(4), false), Some(0));
+        assert_eq!(App::cycle_match(&matches, Some(0), false), Some(2));
+        assert_eq!(App::cycle_match(&matches, None, true), Some(0));
+        assert_eq!(App::cycle_match(&matches, None, false), Some(2));
+        assert_eq!(App::cycle_match(&[], Some(3), true), None);
+    }
+
+    #[test]
+    fn section_text_stops_at_the_next_heading() {
+        let doc = crate::parser::parse_markdown(
+            "# Guide\nIntro with SETUP notes.\n## Setup\nRun it.\n## Empty\n",
+        );
+        assert_eq!(
+            App::section_texts_lc(&doc),
+            ["intro with setup notes.\n", "run it.\n", ""]
+        );
+    }
+}
+
+#[cfg(test)]
+mod heading_path_tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    #[test]
+    fn path_runs_from_the_root_to_the_heading() {
+        let doc = parse_markdown("# Intro\n## Setup\n### Config\n## Usage\n# Next\n");
+        let tree = doc.build_tree();
+        let texts = |index| -> Vec<&str> {
+            App::tree_path(&tree, index)
+                .iter()
+                .map(|node| node.heading.text.as_str())
+                .collect()
+        };
+        assert_eq!(texts(2), ["Intro", "Setup", "Config"]);
+        assert_eq!(texts(3), ["Intro", "Usage"]);
+        assert_eq!(texts(4), ["Next"]);
+        assert!(texts(9).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod fold_tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    #[test]
+    fn folds_survive_a_reload() {
+        let before = parse_markdown("# Guide\n## Setup\n### Linux\n### macOS\n## Usage\n### CLI\n");
+        let collapsed: HashSet<String> = ["Setup", "Usage"].map(String::from).into();
+        let visible = |tree: &[HeadingNode], collapsed: &HashSet<String>| -> Vec<String> {
+            App::flatten_tree(tree, collapsed)
+                .into_iter()
+                .map(|item| item.text)
+                .collect()
+        };
+        assert_eq!(
+            visible(&before.build_tree(), &collapsed),
+            ["Guide", "Setup", "Usage"]
+        );
+
+        // Edited: Setup gains a subsection, Usage loses its only one, and a
+        // new section with children appears
+        let after = parse_markdown(
+            "# Guide\n## Setup\n### Linux\n### macOS\n### Windows\n## Usage\n## FAQ\n### Why\n",
+        );
+        let tree = after.build_tree();
+        let kept = App::surviving_folds(&collapsed, &tree);
+        assert_eq!(kept, ["Setup".to_string()].into());
+        assert_eq!(
+            visible(&tree, &kept),
+            ["Guide", "Setup", "Usage", "FAQ", "Why"]
+        );
+    }
+
+    #[test]
+    fn fold_to_level_hides_deeper_headings() {
+        let doc = parse_markdown(
+            "# Guide
+## Setup
+### Linux
+## Usage
+# Notes
+### Deep
+",
+        );
+        let tree = doc.build_tree();
+        let visible = |level| -> Vec<String> {
+            App::flatten_tree(&tree, &App::headings_deeper_than(&tree, level))
+                .into_iter()
+                .map(|item| item.text)
+                .collect()
+        };
+        assert_eq!(visible(1), ["Guide", "Notes"]);
+        assert_eq!(visible(2), ["Guide", "Setup", "Usage", "Notes"]);
+        assert_eq!(
+            visible(3),
+            ["Guide", "Setup", "Linux", "Usage", "Notes", "Deep"]
+        );
+    }
+
+    fn app(markdown: &str) -> App {
+        App::new(
+            parse_markdown(markdown),
+            "doc.md".to_string(),
+            PathBuf::from("doc.md"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        )
+    }
+
+    #[test]
+    fn folding_away_the_selection_selects_its_visible_ancestor() {
+        let mut app = app("# Guide\n## Setup\n### Linux\n## Usage\n");
+        assert!(app.select_by_text("Linux"));
+
+        app.fold_to_level(2);
+        assert_eq!(app.selected_heading_text(), Some("Setup"));
+        app.fold_to_level(1);
+        assert_eq!(app.selected_heading_text(), Some("Guide"));
+
+        // Unfolding keeps the selection where it is
+        app.fold_to_level(3);
+        assert_eq!(app.selected_heading_text(), Some("Guide"));
+    }
+
+    #[test]
+    fn anchors_unfold_the_headings_above_their_target() {
+        let mut app = app("# Guide\n## Setup\n### Linux\n## Usage\n");
+        app.collapsed_headings.insert("Setup".to_string());
+        app.rebuild_outline_items();
+        assert!(!app.outline_items.iter().any(|item| item.text == "Linux"));
+
+        app.follow_anchor("linux").unwrap();
+        assert_eq!(app.selected_heading_text(), Some("Linux"));
+        assert!(!app.collapsed_headings.contains("Setup"));
+    }
+}
+
 #[cfg(test)]
 mod palette_tests {
     use super::*;
@@ -5824,6 +8677,72 @@ mod palette_tests {
         }
     }
 
+    #[test]
+    fn words_match_prefixes_in_order() {
+        let c = PaletteCommand::new(
+            "Theme picker",
+            &["theme"],
+            "desc",
+            CommandAction::Dispatch(Action::ToggleThemePicker),
+        );
+        // "tog" only appears in the action name, ToggleThemePicker
+        assert!(c.matches("tog theme"));
+        assert_eq!(c.match_score("tog theme"), 150);
+        assert!(c.matches("theme pick"));
+        assert!(!c.matches("theme tog"));
+        assert!(!words_in_order("Theme picker", ""));
+        assert_eq!(split_camel_case("ToggleThemePicker"), "Toggle Theme Picker");
+    }
+
+    #[test]
+    fn palette_offers_every_normal_mode_action_once() {
+        let defaults = Keybindings::default();
+        let commands = palette_commands();
+        for action in defaults
+            .help_entries(KeybindingMode::Normal)
+            .into_iter()
+            .map(|(action, _)| action)
+            .filter(|action| *action != Action::OpenCommandPalette)
+        {
+            let count = commands
+                .iter()
+                .filter(|cmd| cmd.action.keybinding_action() == Some(action))
+                .count();
+            assert_eq!(count, 1, "{:?} should have exactly one command", action);
+        }
+    }
+
+    #[test]
+    fn registered_tog_theme_ranks_theme_picker_first() {
+        let query = "tog theme";
+        let best = palette_commands()
+            .iter()
+            .filter(|c| c.matches(query))
+            .max_by_key(|c| c.match_score(query))
+            .expect("something matches");
+        assert_eq!(
+            best.action,
+            CommandAction::Dispatch(Action::ToggleThemePicker)
+        );
+    }
+
+    #[test]
+    fn numbers_go_to_a_heading() {
+        assert_eq!(heading_number("42"), Some(42));
+        assert_eq!(heading_number("goto 7"), Some(7));
+        assert_eq!(heading_number(" heading 120 "), Some(120));
+        assert_eq!(heading_number("0"), None);
+        assert_eq!(heading_number("collapse 2"), None);
+        assert_eq!(heading_number("goto"), None);
+
+        let best = palette_commands()
+            .iter()
+            .filter(|c| c.matches("42"))
+            .max_by_key(|c| c.match_score("42"))
+            .expect("something matches");
+        assert_eq!(best.action, CommandAction::GoToHeading);
+    }
+
     #[test]
     fn registered_quit_command_resolves() {
         // Typing "q" should pick the Quit command as the top score.
@@ -5937,4 +8856,548 @@ sub b content
         assert!(sub_b.contains("sub b content"));
         assert!(!sub_b.contains("sub a content"));
     }
+
+    #[test]
+    fn expand_more_levels_reveals_one_level_per_step() {
+        let content = "\
+# A
+## A.1
+### A.1.a
+#### A.1.a.i
+# B
+## B.1
+";
+        let tree = make_doc(content).build_tree();
+        let max = App::tree_depth(&tree);
+        assert_eq!(max, 4);
+
+        let visible_depth = |depth: usize| {
+            let collapsed: HashSet<String> = tree
+                .iter()
+                .flat_map(|node| App::collect_collapsible_from_depth(node, 1, depth))
+                .collect();
+            let items = App::flatten_tree(&tree, &collapsed);
+            items.iter().map(|i| i.level).max().unwrap()
+        };
+
+        let mut depth = None;
+        for expected in [1, 2, 3, 4, 4, 4] {
+            let next = App::step_reveal_depth(depth, max, true);
+            assert_eq!(next, expected);
+            assert_eq!(visible_depth(next), expected);
+            depth = Some(next);
+        }
+
+        // Collapsing steps back down and stops at the top level
+        for expected in [3, 2, 1, 1] {
+            let next = App::step_reveal_depth(depth, max, false);
+            assert_eq!(next, expected);
+            assert_eq!(visible_depth(next), expected);
+            depth = Some(next);
+        }
+    }
+
+    #[test]
+    fn all_blocks_outline_lists_code_block_under_its_heading() {
+        let content = "\
+# Setup
+Install first.
+
+```rust
+fn main() {}
+```
+
+## Usage
+| Flag | Meaning |
+|------|---------|
+| -q   | query   |
+
+# Notes
+plain text
+";
+        let mut app = all_blocks_app(content);
+        let items = app.outline_items.clone();
+
+        let texts: Vec<&str> = items.iter().map(|i| i.text.as_str()).collect();
+        assert_eq!(
+            texts,
+            [
+                "Setup",
+                "code: rust",
+                "Usage",
+                "table: Flag | Meaning",
+                "Notes"
+            ]
+        );
+
+        let code = &items[1];
+        assert_eq!(code.level, items[0].level + 1);
+        assert_eq!(code.heading_index, items[0].heading_index);
+        assert!(code.block_idx.is_some());
+        assert!(!code.has_children);
+
+        // Collapsed headings hide their block entries too
+        app.collapsed_headings.insert("Setup".to_string());
+        app.rebuild_outline_items();
+        assert!(app.outline_items.iter().all(|i| i.text != "code: rust"));
+    }
+
+    #[test]
+    fn block_entries_count_the_blocks_left_after_filtering() {
+        use crate::parser::content::parse_content;
+        use crate::parser::output::Block;
+
+        let content = "\
+---
+title: Notes
+---
+
+# Math
+$$
+x^2
+$$
+
+```rust
+fn main() {}
+```
+";
+        let app = all_blocks_app(content);
+        let code = app
+            .outline_items
+            .iter()
+            .find(|i| i.text == "code: rust")
+            .expect("code block entry");
+
+        // The hidden LaTeX block doesn't count, so the index is the one the
+        // content pane draws the code block at
+        let section = app.document.extract_section_at_index(0).unwrap();
+        let shown = parse_content(&app.rendered_markdown(section.clone()), 0);
+        assert!(matches!(shown[code.block_idx.unwrap()], Block::Code { .. }));
+        let unfiltered = parse_content(&section, 0);
+        assert!(!matches!(
+            unfiltered[code.block_idx.unwrap()],
+            Block::Code { .. }
+        ));
+    }
+
+    fn all_blocks_app(content: &str) -> App {
+        let mut config = Config::default();
+        config.ui.outline_content = "all".to_string();
+        App::new(
+            make_doc(content),
+            "doc.md".to_string(),
+            PathBuf::from("doc.md"),
+            config,
+            ColorMode::Rgb,
+            false,
+        )
+    }
+}
+
+#[cfg(test)]
+mod interactive_scroll_tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    #[test]
+    fn half_page_scrolls_content_while_outline_has_focus() {
+        let body: String = (1..=60).map(|n| format!("line {}\n\n", n)).collect();
+        let mut app = App::new(
+            parse_markdown(&format!("# Doc\n{}", body)),
+            "doc.md".to_string(),
+            PathBuf::from("doc.md"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+        app.set_viewport_height(10);
+        app.update_content_metrics();
+        app.enter_interactive_mode();
+        app.focus = Focus::Outline;
+
+        app.execute_action(Action::HalfPageDown);
+        assert_eq!(app.content_scroll, app.half_page_lines());
+        app.execute_action(Action::HalfPageUp);
+        assert_eq!(app.content_scroll, 0);
+    }
+
+    #[test]
+    fn sideways_scroll_needs_focused_content_that_scrolls() {
+        let mut app = App::new(
+            parse_markdown("# Doc\n\ntext\n"),
+            "doc.md".to_string(),
+            PathBuf::from("doc.md"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+        app.content_hscroll_max = 20;
+        app.focus = Focus::Outline;
+        app.execute_action(Action::ScrollRight);
+        assert_eq!(app.content_hscroll, 0);
+
+        app.focus = Focus::Content;
+        app.execute_action(Action::ScrollRight);
+        assert_eq!(app.content_hscroll, HSCROLL_STEP);
+
+        // Nothing to scroll with wrapping on: say how to turn it off
+        app.content_hscroll_max = 0;
+        app.execute_action(Action::ScrollLeft);
+        assert_eq!(app.content_hscroll, HSCROLL_STEP);
+        assert_eq!(
+            app.status_message.as_deref(),
+            Some("Line wrap is on (W turns it off)")
+        );
+    }
+
+    #[test]
+    fn highlighted_line_follows_the_selected_element() {
+        let doc = parse_markdown("# Doc\n\n[a](#a)\n\ntext\n\n[b](#b)\n");
+        let app = App::new(
+            doc.clone(),
+            "doc.md".to_string(),
+            PathBuf::from("doc.md"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+        assert_eq!(app.current_content_line(), None);
+
+        let mut config = Config::default();
+        config.ui.highlight_current_line = true;
+        let mut app = App::new(
+            doc,
+            "doc.md".to_string(),
+            PathBuf::from("doc.md"),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        app.set_viewport_height(10);
+        app.update_content_metrics();
+        assert_eq!(app.current_content_line(), Some(0));
+
+        app.enter_interactive_mode();
+        let first = app.current_content_line().unwrap();
+        app.interactive_state.next();
+        assert!(app.current_content_line().unwrap() > first);
+    }
+}
+
+#[cfg(test)]
+mod query_results_tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    fn app(path: PathBuf, query_results: bool) -> App {
+        let results = query_results.then(|| path.clone());
+        let content = std::fs::read_to_string(&path).unwrap_or_else(|_| "- [ ] task\n".into());
+        let mut app = App::new(
+            parse_markdown(&content),
+            "doc.md".to_string(),
+            path,
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+        app.query_results = results;
+        app.enter_interactive_mode();
+        app
+    }
+
+    #[test]
+    fn toggling_a_checkbox_in_query_results_writes_nothing() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut results = app(dir.path().join("doc.md (query)"), true);
+        assert!(results.activate_interactive_element().is_err());
+        assert!(results.enter_cell_edit_mode().is_err());
+        assert!(results.save_pending_edits_to_file().is_err());
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+
+        // The same toggle on a real file saves it
+        let file = dir.path().join("doc.md");
+        std::fs::write(&file, "- [ ] task\n").unwrap();
+        app(file.clone(), false)
+            .activate_interactive_element()
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "- [x] task\n");
+    }
+
+    #[test]
+    fn query_results_refuse_the_editor() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut results = app(dir.path().join("doc.md (query)"), true);
+        results.mode = AppMode::Normal;
+        assert!(matches!(
+            results.execute_action(Action::OpenInEditor),
+            ActionResult::Continue
+        ));
+    }
+}
+
+#[cfg(test)]
+mod footnote_edit_tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+    use crate::tui::interactive::ElementType;
+
+    const DOC: &str = "# Doc\n\n[^1]: A note.\n\n| Name |\n|------|\n| x[^1] |\n\n- [ ] task[^1]\n";
+
+    fn app(dir: &Path) -> App {
+        let path = dir.join("doc.md");
+        std::fs::write(&path, DOC).unwrap();
+        let mut app = App::new(
+            parse_markdown(DOC),
+            "doc.md".to_string(),
+            path,
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+        app.enter_interactive_mode();
+        app
+    }
+
+    fn select(app: &mut App, wanted: impl Fn(&ElementType) -> bool) {
+        while !app
+            .interactive_state
+            .current_element()
+            .is_some_and(|e| wanted(&e.element_type))
+        {
+            app.interactive_state.next();
+        }
+    }
+
+    #[test]
+    fn cell_edits_start_from_the_source_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app(dir.path());
+        select(&mut app, |e| matches!(e, ElementType::Table { .. }));
+        app.interactive_state.enter_table_mode().unwrap();
+        app.interactive_state.table_move_down(1);
+        app.enter_cell_edit_mode().unwrap();
+        assert_eq!(app.cell_edit_value, "x[^1]");
+    }
+
+    #[test]
+    fn checkbox_toggles_keep_footnote_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = app(dir.path());
+        select(&mut app, |e| matches!(e, ElementType::Checkbox { .. }));
+        app.activate_interactive_element().unwrap();
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("doc.md")).unwrap(),
+            DOC.replace("- [ ]", "- [x]")
+        );
+    }
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    #[test]
+    fn html_export_asks_before_replacing_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("doc.md");
+        std::fs::write(&file, "# Guide\n\nbody\n").unwrap();
+        let mut app = App::new(
+            parse_markdown("# Guide\n\nbody\n"),
+            "doc.md".to_string(),
+            file,
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+
+        app.execute_action(Action::ExportHtml);
+        assert_eq!(app.mode, AppMode::Normal);
+        let exported = std::fs::read_dir(dir.path())
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|path| path.extension().is_some_and(|ext| ext == "html"))
+            .unwrap();
+        std::fs::write(&exported, "mine").unwrap();
+
+        app.execute_action(Action::ExportHtml);
+        assert_eq!(app.mode, AppMode::ConfirmOverwrite);
+        app.cancel_overwrite();
+        assert_eq!(std::fs::read_to_string(&exported).unwrap(), "mine");
+
+        app.execute_action(Action::ExportHtml);
+        app.confirm_overwrite();
+        assert_eq!(app.mode, AppMode::Normal);
+        assert!(
+            std::fs::read_to_string(&exported)
+                .unwrap()
+                .starts_with("<!DOCTYPE html>")
+        );
+    }
+
+    #[test]
+    fn diagram_export_asks_before_replacing_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("doc.md");
+        let doc = "# Flow\n\n```mermaid\ngraph TD\n```\n";
+        std::fs::write(&file, doc).unwrap();
+        let mut app = App::new(
+            parse_markdown(doc),
+            "doc.md".to_string(),
+            file,
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+        let exported = dir.path().join("doc-flow-1.mmd");
+        std::fs::write(&exported, "mine").unwrap();
+
+        app.execute_action(Action::ExportDiagram);
+        assert_eq!(app.mode, AppMode::ConfirmOverwrite);
+        app.cancel_overwrite();
+        assert_eq!(std::fs::read_to_string(&exported).unwrap(), "mine");
+
+        app.execute_action(Action::ExportDiagram);
+        app.confirm_overwrite();
+        assert_eq!(std::fs::read_to_string(&exported).unwrap(), "graph TD\n");
+    }
+
+    #[test]
+    fn exports_of_input_that_is_no_file_are_named_document() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = App::new(
+            parse_markdown("# Guide\n\nbody\n"),
+            "stdin".to_string(),
+            dir.path().join("<stdin>"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+
+        app.execute_action(Action::ExportHtml);
+        assert!(dir.path().join("document-guide.html").is_file());
+    }
+}
+
+#[cfg(test)]
+mod browser_tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    fn browsing(dir: &Path) -> App {
+        std::fs::write(dir.join("a.md"), "# A\n").unwrap();
+        std::fs::write(dir.join("b.md"), "# B\n## B2\n").unwrap();
+        let mut app = App::new(
+            parse_markdown(""),
+            "a.md".to_string(),
+            dir.join("a.md"),
+            Config::default(),
+            ColorMode::Rgb,
+            false,
+        );
+        assert!(app.open_directory(dir.to_path_buf()));
+        app
+    }
+
+    #[test]
+    fn loading_another_file_leaves_the_browser() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = browsing(dir.path());
+        app.load_document(
+            parse_markdown("# Other\n## Part\n"),
+            "other.md".to_string(),
+            dir.path().join("other.md"),
+        );
+        assert!(app.file_browser.is_none());
+
+        // Movement acts on the new outline instead of the file tree
+        app.select_outline_index(0);
+        app.execute_action(Action::Next);
+        assert_eq!(app.selected_heading_text(), Some("Part"));
+    }
+
+    #[test]
+    fn going_back_returns_to_the_browser() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut app = browsing(dir.path());
+        app.execute_action(Action::Next);
+        assert_eq!(app.current_file_path, dir.path().join("b.md"));
+        app.execute_action(Action::ToggleExpand);
+        assert!(app.file_browser.is_none());
+
+        app.execute_action(Action::GoBack);
+        let browser = app.file_browser.as_ref().expect("browser is back");
+        assert_eq!(browser.selected_file(), dir.path().join("b.md"));
+        assert_eq!(app.focus, Focus::Outline);
+    }
+
+    #[test]
+    fn frontmatter_field_groups_the_file_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let write = |name: &str, content: &str| std::fs::write(dir.path().join(name), content);
+        write("a.md", "---\ncategory: recipes\n---\n# A\n").unwrap();
+        write("b.md", "---\ncategory: journal\n---\n# B\n").unwrap();
+        write("c.md", "---\ncategory: recipes\n---\n# C\n").unwrap();
+        let mut config = Config::default();
+        config.ui.group_by_frontmatter = Some("category".to_string());
+        let mut app = App::new(
+            parse_markdown(""),
+            "a.md".to_string(),
+            dir.path().join("a.md"),
+            config,
+            ColorMode::Rgb,
+            false,
+        );
+        assert!(app.open_directory(dir.path().to_path_buf()));
+
+        let browser = app.file_browser.as_ref().unwrap();
+        let rows: Vec<_> = browser
+            .entries
+            .iter()
+            .map(|e| format!("{}{}", "  ".repeat(e.depth), e.name()))
+            .collect();
+        assert_eq!(rows, ["journal", "  b.md", "recipes", "  a.md", "  c.md"]);
+        assert_eq!(browser.selected_file(), dir.path().join("b.md"));
+    }
+}
+
+#[cfg(test)]
+mod view_state_tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    #[test]
+    fn positions_are_kept_in_the_given_state_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let doc = dir.path().join("doc.md");
+        let content = "# A\n\n## B\n\ntext\n\n## C\n";
+        std::fs::write(&doc, content).unwrap();
+        let open = |state: Option<PathBuf>| {
+            let mut app = App::new(
+                parse_markdown(content),
+                "doc.md".to_string(),
+                doc.clone(),
+                Config::default(),
+                ColorMode::Rgb,
+                false,
+            );
+            if state.is_some() {
+                app.view_state_path = state;
+                app.return_to_remembered_position();
+            }
+            app
+        };
+
+        // Tests start with persistence off
+        assert_eq!(open(None).view_state_path, None);
+
+        let state = dir.path().join("state").join("view_state.json");
+        let mut app = open(Some(state.clone()));
+        assert!(app.select_by_heading_index(2));
+        app.remember_position();
+        assert!(state.is_file());
+
+        let app = open(Some(state));
+        assert_eq!(app.selected_heading_text(), Some("C"));
+    }
 }
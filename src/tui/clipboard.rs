@@ -0,0 +1,142 @@
+//! Clipboard access: the system clipboard, or OSC 52 escape sequences.
+//!
+//! Over SSH the system clipboard belongs to the remote machine, so copies
+//! never reach the user. OSC 52 asks the terminal itself to set its
+//! clipboard, which works across SSH and (with passthrough) inside tmux.
+//! `[terminal] clipboard` picks the method; `"auto"` uses OSC 52 when
+//! `$SSH_TTY` is set.
+
+use std::io::Write;
+
+/// How copied text reaches the clipboard.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClipboardMethod {
+    /// The system clipboard via arboard
+    Native,
+    /// OSC 52 escape sequences written to the terminal
+    Osc52,
+}
+
+impl ClipboardMethod {
+    /// Method for a `[terminal] clipboard` setting (`"auto"`, `"native"` or
+    /// `"osc52"`), with `ssh` telling whether this is an SSH session.
+    pub fn from_config(setting: &str, ssh: bool) -> Self {
+        match setting {
+            "osc52" => ClipboardMethod::Osc52,
+            "native" => ClipboardMethod::Native,
+            _ if ssh => ClipboardMethod::Osc52,
+            _ => ClipboardMethod::Native,
+        }
+    }
+}
+
+/// Clipboard used by the copy actions.
+pub struct Clipboard {
+    method: ClipboardMethod,
+    /// Kept alive for the whole session: on Linux the clipboard contents are
+    /// served by this instance, so dropping it would lose them
+    native: Option<arboard::Clipboard>,
+    tmux: bool,
+}
+
+impl Clipboard {
+    /// Clipboard for a `[terminal] clipboard` setting, detecting SSH and tmux
+    /// from the environment.
+    pub fn new(setting: &str) -> Self {
+        let ssh = std::env::var_os("SSH_TTY").is_some();
+        let method = ClipboardMethod::from_config(setting, ssh);
+        Self {
+            method,
+            // Created even for OSC 52, as the fallback
+            native: arboard::Clipboard::new().ok(),
+            tmux: std::env::var_os("TMUX").is_some(),
+        }
+    }
+
+    /// Copy `text`. OSC 52 falls back to the system clipboard if the escape
+    /// sequence cannot be written.
+    pub fn set_text(&mut self, text: &str) -> Result<(), String> {
+        if self.method == ClipboardMethod::Osc52 {
+            let sequence = osc52_sequence(text, self.tmux);
+            let mut out = std::io::stdout();
+            if out
+                .write_all(sequence.as_bytes())
+                .and_then(|()| out.flush())
+                .is_ok()
+            {
+                return Ok(());
+            }
+        }
+        match &mut self.native {
+            Some(clipboard) => clipboard
+                .set_text(text.to_string())
+                .map_err(|e| format!("Clipboard error: {}", e)),
+            None => Err("Clipboard not available".to_string()),
+        }
+    }
+}
+
+/// The OSC 52 sequence that sets the clipboard to `text`, wrapped in tmux's
+/// DCS passthrough (with its escape characters doubled) when `tmux` is set.
+pub fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let osc = format!("\x1b]52;c;{}\x07", base64(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;{}\x1b\\", osc.replace('\x1b', "\x1b\x1b"))
+    } else {
+        osc
+    }
+}
+
+/// Standard base64 with padding.
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (u32::from(b[0]) << 16) | (u32::from(b[1]) << 8) | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn base64_pads_partial_groups() {
+        assert_eq!(base64(b""), "");
+        assert_eq!(base64(b"f"), "Zg==");
+        assert_eq!(base64(b"fo"), "Zm8=");
+        assert_eq!(base64(b"foo"), "Zm9v");
+        assert_eq!(base64("héllo\n".as_bytes()), "aMOpbGxvCg==");
+    }
+
+    #[test]
+    fn osc52_sequences_are_wrapped_for_tmux() {
+        assert_eq!(osc52_sequence("hi", false), "\x1b]52;c;aGk=\x07");
+        assert_eq!(
+            osc52_sequence("hi", true),
+            "\x1bPtmux;\x1b\x1b]52;c;aGk=\x07\x1b\\"
+        );
+    }
+
+    #[test]
+    fn method_follows_config_then_ssh() {
+        use ClipboardMethod::*;
+        assert_eq!(ClipboardMethod::from_config("osc52", false), Osc52);
+        assert_eq!(ClipboardMethod::from_config("native", true), Native);
+        assert_eq!(ClipboardMethod::from_config("auto", true), Osc52);
+        assert_eq!(ClipboardMethod::from_config("auto", false), Native);
+    }
+}
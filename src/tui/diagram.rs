@@ -0,0 +1,177 @@
+//! Diagram code blocks (mermaid, plantuml) shown as placeholders.
+//!
+//! Diagram languages that treemd cannot draw in the terminal are rendered as
+//! a small bordered placeholder instead of their source. The source stays
+//! reachable: interactive mode toggles it back in, and `ExportDiagram` writes
+//! it to a `.mmd`/`.puml` file for rendering with external tools.
+
+use ratatui::style::Style;
+use ratatui::text::{Line, Span};
+use std::path::{Path, PathBuf};
+
+/// Rendered height of a diagram placeholder (top border, summary, bottom border).
+pub const DIAGRAM_PLACEHOLDER_LINES: usize = 3;
+
+/// Width of the placeholder box, in columns.
+const PLACEHOLDER_WIDTH: usize = 48;
+
+/// A diagram language recognized in fenced code blocks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramKind {
+    Mermaid,
+    PlantUml,
+}
+
+impl DiagramKind {
+    /// Detect a diagram from a code block's language tag.
+    pub fn from_language(language: Option<&str>) -> Option<Self> {
+        match language?.to_ascii_lowercase().as_str() {
+            "mermaid" => Some(Self::Mermaid),
+            "plantuml" | "puml" => Some(Self::PlantUml),
+            _ => None,
+        }
+    }
+
+    /// Placeholder label, e.g. "Mermaid diagram".
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Mermaid => "Mermaid diagram",
+            Self::PlantUml => "PlantUML diagram",
+        }
+    }
+
+    /// File extension used when exporting the source.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Mermaid => "mmd",
+            Self::PlantUml => "puml",
+        }
+    }
+
+    /// Whether this build draws the diagram as an image instead of a placeholder.
+    pub fn renders_graphically(self) -> bool {
+        matches!(self, Self::Mermaid) && cfg!(all(feature = "mermaid", unix))
+    }
+}
+
+/// The diagram kind of a code block that should be shown as a placeholder.
+pub fn placeholder_kind(language: Option<&str>) -> Option<DiagramKind> {
+    DiagramKind::from_language(language).filter(|kind| !kind.renders_graphically())
+}
+
+/// Build the placeholder lines for a diagram block.
+pub fn placeholder_lines(kind: DiagramKind, source: &str, style: Style) -> Vec<Line<'static>> {
+    let title = format!("┌─ [{}] ", kind.label());
+    let top = format!(
+        "{}{}┐",
+        title,
+        "─".repeat(PLACEHOLDER_WIDTH.saturating_sub(title.chars().count() + 1))
+    );
+
+    let line_count = source.lines().count();
+    let summary = format!(
+        "{} line{} · Enter: source · E: export",
+        line_count,
+        if line_count == 1 { "" } else { "s" }
+    );
+    let padding = PLACEHOLDER_WIDTH.saturating_sub(summary.chars().count() + 4);
+    let middle = format!("│ {}{} │", summary, " ".repeat(padding));
+
+    let bottom = format!("└{}┘", "─".repeat(PLACEHOLDER_WIDTH.saturating_sub(2)));
+
+    vec![
+        Line::from(Span::styled(top, style)),
+        Line::from(Span::styled(middle, style)),
+        Line::from(Span::styled(bottom, style)),
+    ]
+}
+
+/// Path a diagram is exported to: next to the document, named after the
+/// document, the section and the diagram's position within it
+/// (`guide-architecture-1.mmd`).
+pub fn export_path(
+    document: &Path,
+    section: Option<&str>,
+    index: usize,
+    kind: DiagramKind,
+) -> PathBuf {
+    let stem = document
+        .file_stem()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| "diagram".to_string());
+    let name = match section.filter(|s| !s.is_empty()) {
+        Some(slug) => format!("{}-{}-{}.{}", stem, slug, index, kind.extension()),
+        None => format!("{}-{}.{}", stem, index, kind.extension()),
+    };
+    document.with_file_name(name)
+}
+
+/// Write a diagram's source to `path`, ending it with a newline.
+pub fn export_source(path: &Path, source: &str) -> std::io::Result<()> {
+    let mut contents = source.to_string();
+    if !contents.ends_with('\n') {
+        contents.push('\n');
+    }
+    std::fs::write(path, contents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::content::parse_content;
+    use crate::parser::output::Block;
+
+    const DOC: &str = "# Flow\n\n```mermaid\ngraph TD\n  A --> B\n```\n\n```puml\n@startuml\nA -> B\n@enduml\n```\n";
+
+    #[test]
+    fn detects_diagram_languages() {
+        assert_eq!(
+            DiagramKind::from_language(Some("mermaid")),
+            Some(DiagramKind::Mermaid)
+        );
+        assert_eq!(
+            DiagramKind::from_language(Some("PlantUML")),
+            Some(DiagramKind::PlantUml)
+        );
+        assert_eq!(DiagramKind::from_language(Some("rust")), None);
+        assert_eq!(DiagramKind::from_language(None), None);
+        // PlantUML never has a graphical renderer
+        assert_eq!(placeholder_kind(Some("puml")), Some(DiagramKind::PlantUml));
+    }
+
+    #[test]
+    fn mermaid_block_renders_as_placeholder_and_exports() {
+        let blocks = parse_content(DOC, 0);
+        let (language, source) = blocks
+            .iter()
+            .find_map(|b| match b {
+                Block::Code {
+                    language, content, ..
+                } => Some((language.as_deref(), content.as_str())),
+                _ => None,
+            })
+            .unwrap();
+        let kind = DiagramKind::from_language(language).expect("mermaid detected");
+
+        let lines = placeholder_lines(kind, source, Style::default());
+        assert_eq!(lines.len(), DIAGRAM_PLACEHOLDER_LINES);
+        let text: Vec<String> = lines.iter().map(|l| l.to_string()).collect();
+        assert!(text[0].contains("[Mermaid diagram]"));
+        assert!(text[1].contains("2 lines"));
+        assert!(text.iter().all(|l| !l.contains("A --> B")));
+        assert_eq!(
+            text[0].chars().count(),
+            text[2].chars().count(),
+            "borders line up"
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = export_path(&dir.path().join("guide.md"), Some("flow"), 1, kind);
+        assert_eq!(path.file_name().unwrap(), "guide-flow-1.mmd");
+        export_source(&path, source).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap(),
+            "graph TD\n  A --> B\n"
+        );
+    }
+}
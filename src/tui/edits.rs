@@ -1,8 +1,9 @@
 //! Pure text transformations for in-place file edits (checkbox toggling,
 //! table cell editing).
 //!
-//! These functions are kept free of I/O and `App` state so the file-mutation
-//! logic can be unit-tested directly. They preserve the document's original
+//! These functions are kept free of `App` state so the file-mutation logic
+//! can be unit-tested directly; only [`write_atomically`] touches the disk.
+//! They preserve the document's original
 //! line endings (LF or CRLF, per line) and the presence/absence of a trailing
 //! newline, and they are code-fence aware so edits never land inside fenced
 //! code blocks that merely look like tables or task lists.
@@ -124,6 +125,80 @@ fn replace_cell_in_row(line: &str, col: usize, new_value: &str) -> Option<String
     Some(segments.join("|"))
 }
 
+/// Line indices of one table's rows: header first, then the body rows
+/// (separator excluded), plus the separator line.
+struct TableLines {
+    rows: Vec<usize>,
+    separator: usize,
+}
+
+/// Find the `table_index`-th table (0-based) from `section_start_line` on,
+/// skipping fenced code blocks anywhere in the document.
+fn locate_table(
+    lines: &[(&str, &str)],
+    section_start_line: usize,
+    table_index: usize,
+) -> Option<TableLines> {
+    let mut fences = FenceTracker::default();
+    let mut found: Option<TableLines> = None;
+    // Counts tables seen from section_start_line onward; starts "one before"
+    // so the first table found becomes index 0.
+    let mut current_table: Option<usize> = None;
+    let mut in_table = false;
+
+    for (idx, (text, _)) in lines.iter().enumerate() {
+        let in_fence = fences.feed(text);
+        let pipe_line = !in_fence && idx >= section_start_line && is_table_line(text);
+
+        if !pipe_line {
+            in_table = false;
+            if found.is_some() {
+                break;
+            }
+            continue;
+        }
+        if !in_table {
+            // A pipe-delimited block is only a GFM table when its header
+            // row is immediately followed by a delimiter row. This mirrors
+            // the parser used to compute `table_index`, so the two never
+            // disagree on what counts as a table; bare `|a|b|` blocks are
+            // emitted as plain content.
+            let is_real_table = lines
+                .get(idx + 1)
+                .map(|(next, _)| is_table_line(next) && is_separator_row(next))
+                .unwrap_or(false);
+            if !is_real_table {
+                continue;
+            }
+            in_table = true;
+            current_table = Some(current_table.map_or(0, |t| t + 1));
+            if current_table == Some(table_index) {
+                found = Some(TableLines {
+                    rows: Vec::new(),
+                    separator: idx + 1,
+                });
+            } else if found.is_some() {
+                break;
+            }
+        }
+        if let Some(table) = found.as_mut()
+            && current_table == Some(table_index)
+            && idx != table.separator
+        {
+            table.rows.push(idx);
+        }
+    }
+    found
+}
+
+/// Reassemble `lines` into a string.
+fn join_lines(lines: &[(&str, &str)]) -> String {
+    lines
+        .iter()
+        .flat_map(|(text, ending)| [*text, *ending])
+        .collect()
+}
+
 /// Replace a table cell in `content`.
 ///
 /// Tables are counted starting at `section_start_line` (0-indexed); fenced
@@ -138,79 +213,92 @@ pub fn replace_table_cell(
     col: usize,
     new_value: &str,
 ) -> Result<String, String> {
-    let lines = split_lines_keep_endings(content);
-    let mut out = String::with_capacity(content.len() + new_value.len());
-    let mut fences = FenceTracker::default();
-    let mut in_table = false;
-    let mut table_row_idx = 0usize;
-    // Counts tables seen from section_start_line onward; starts "one before"
-    // so the first table found becomes index 0.
-    let mut current_table: Option<usize> = None;
-    let mut modified = false;
-
-    for (idx, (text, ending)) in lines.iter().enumerate() {
-        let in_fence = fences.feed(text);
-        let pipe_line = !in_fence && idx >= section_start_line && is_table_line(text);
-
-        if pipe_line {
-            if !in_table {
-                // A pipe-delimited block is only a GFM table when its header
-                // row is immediately followed by a delimiter row. This mirrors
-                // the parser used to compute `table_index`, so the two never
-                // disagree on what counts as a table; bare `|a|b|` blocks are
-                // emitted as plain content.
-                let is_real_table = lines
-                    .get(idx + 1)
-                    .map(|(next, _)| is_table_line(next) && is_separator_row(next))
-                    .unwrap_or(false);
-                if !is_real_table {
-                    out.push_str(text);
-                    out.push_str(ending);
-                    continue;
-                }
-                in_table = true;
-                table_row_idx = 0;
-                current_table = Some(current_table.map_or(0, |t| t + 1));
-            }
+    let mut lines = split_lines_keep_endings(content);
+    let line_idx = locate_table(&lines, section_start_line, table_index)
+        .and_then(|table| table.rows.get(row).copied())
+        .ok_or_else(|| format!("Table {} not found or row {} not found", table_index, row))?;
+    let new_line = replace_cell_in_row(lines[line_idx].0, col, new_value).ok_or_else(|| {
+        format!(
+            "Column {} out of range in table {} row {}",
+            col, table_index, row
+        )
+    })?;
+    lines[line_idx].0 = &new_line;
+    Ok(join_lines(&lines))
+}
 
-            if is_separator_row(text) {
-                out.push_str(text);
-                out.push_str(ending);
-                continue;
-            }
+/// A structural change to a table's rows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowEdit {
+    /// Add a blank row below the given one (below the separator for the header)
+    InsertBelow,
+    /// Remove the given row; the header row cannot be deleted
+    Delete,
+}
 
-            if !modified && current_table == Some(table_index) && table_row_idx == row {
-                match replace_cell_in_row(text, col, new_value) {
-                    Some(new_line) => {
-                        out.push_str(&new_line);
-                        modified = true;
-                    }
-                    None => {
-                        return Err(format!(
-                            "Column {} out of range in table {} row {}",
-                            col, table_index, row
-                        ));
+/// Insert or delete a table row in `content`. Tables and rows are addressed
+/// as in [`replace_table_cell`]. An inserted row has as many cells as the
+/// row it follows, each blank and padded to the same width so aligned
+/// tables stay aligned.
+pub fn edit_table_row(
+    content: &str,
+    section_start_line: usize,
+    table_index: usize,
+    row: usize,
+    edit: RowEdit,
+) -> Result<String, String> {
+    if edit == RowEdit::Delete && row == 0 {
+        return Err("The header row can't be deleted".to_string());
+    }
+    let mut lines = split_lines_keep_endings(content);
+    let table = locate_table(&lines, section_start_line, table_index)
+        .ok_or_else(|| format!("Table {} not found", table_index))?;
+    let line_idx = *table
+        .rows
+        .get(row)
+        .ok_or_else(|| format!("Row {} not found in table {}", row, table_index))?;
+
+    match edit {
+        RowEdit::InsertBelow => {
+            let segments = split_row_segments(lines[line_idx].0);
+            let last = segments.len() - 1;
+            let blank: Vec<String> = segments
+                .iter()
+                .enumerate()
+                .map(|(i, seg)| {
+                    if i == 0 || i == last {
+                        seg.clone()
+                    } else {
+                        " ".repeat(seg.chars().count().max(2))
                     }
-                }
-            } else {
-                out.push_str(text);
+                })
+                .collect();
+            let blank = blank.join("|");
+            let after = if row == 0 { table.separator } else { line_idx };
+            let ending = match lines[table.separator].1 {
+                "" => "\n",
+                ending => ending,
+            };
+            // The row before the new one may have ended the file without a
+            // newline; the new row takes that place instead
+            let trailing = lines[after].1;
+            if trailing.is_empty() {
+                lines[after].1 = ending;
             }
-            out.push_str(ending);
-            table_row_idx += 1;
-        } else {
-            in_table = false;
-            out.push_str(text);
-            out.push_str(ending);
+            lines.insert(after + 1, (&blank, trailing));
+            Ok(join_lines(&lines))
+        }
+        RowEdit::Delete => {
+            let removed = lines.remove(line_idx);
+            // Keep a missing final newline missing
+            if removed.1.is_empty()
+                && line_idx == lines.len()
+                && let Some(prev) = lines.last_mut()
+            {
+                prev.1 = "";
+            }
+            Ok(join_lines(&lines))
         }
-    }
-
-    if modified {
-        Ok(out)
-    } else {
-        Err(format!(
-            "Table {} not found or row {} not found",
-            table_index, row
-        ))
     }
 }
 
@@ -331,6 +419,28 @@ pub fn toggle_checkbox(
     }
 }
 
+/// Replace the file at `path` with `contents` by writing a temporary file
+/// beside it and renaming it over the original, so a failed write never
+/// leaves a half-written document.
+pub fn write_atomically(path: &std::path::Path, contents: &str) -> Result<(), String> {
+    use std::io::Write;
+
+    let parent_dir = path.parent().ok_or("Cannot determine parent directory")?;
+    let mut temp_file = tempfile::NamedTempFile::new_in(parent_dir)
+        .map_err(|e| format!("Failed to create temp file: {}", e))?;
+    temp_file
+        .write_all(contents.as_bytes())
+        .map_err(|e| format!("Failed to write temp file: {}", e))?;
+    temp_file
+        .flush()
+        .map_err(|e| format!("Failed to flush temp file: {}", e))?;
+    // Atomic rename (same filesystem guarantees atomicity)
+    temp_file
+        .persist(path)
+        .map_err(|e| format!("Failed to save file: {}", e))?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -405,6 +515,26 @@ mod tests {
         assert_eq!(out, "- [ ] a\r\n- [x] b");
     }
 
+    #[test]
+    fn toggle_writes_exact_bytes_to_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tasks.md");
+        let original = "# Tasks\r\n\n- [ ] top\n  - [X] nested\n\t* [ ] tabbed";
+        std::fs::write(&path, original).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let out = toggle_checkbox(&content, (0, 5), "nested", true, 0, no_strip).unwrap();
+        write_atomically(&path, &out).unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        let out = toggle_checkbox(&content, (0, 5), "tabbed", false, 0, no_strip).unwrap();
+        write_atomically(&path, &out).unwrap();
+
+        assert_eq!(
+            std::fs::read(&path).unwrap(),
+            b"# Tasks\r\n\n- [ ] top\n  - [ ] nested\n\t* [x] tabbed"
+        );
+    }
+
     #[test]
     fn errors_when_not_found() {
         assert!(toggle_checkbox("- [ ] a\n", (0, 1), "zzz", false, 0, no_strip).is_err());
@@ -509,4 +639,68 @@ mod tests {
         assert!(!is_separator_row("| a --- b | c |"));
         assert!(!is_separator_row("| |"));
     }
+
+    // --- table row insertion and deletion ---
+
+    fn parsed_rows(content: &str) -> Vec<Vec<String>> {
+        use crate::parser::content::parse_content;
+        use crate::parser::output::Block;
+        parse_content(content, 0)
+            .into_iter()
+            .find_map(|block| match block {
+                Block::Table { rows, .. } => Some(rows),
+                _ => None,
+            })
+            .expect("still a table")
+    }
+
+    #[test]
+    fn inserts_blank_row_padded_like_its_neighbour() {
+        let content =
+            "# T\n\n| Name | Qty |\n| ---- | --- |\n| a    | 1   |\n| b    | 2   |\n\nafter\n";
+        let out = edit_table_row(content, 0, 0, 1, RowEdit::InsertBelow).unwrap();
+        assert_eq!(
+            out,
+            "# T\n\n| Name | Qty |\n| ---- | --- |\n| a    | 1   |\n|      |     |\n| b    | 2   |\n\nafter\n"
+        );
+        assert_eq!(parsed_rows(&out).len(), 3);
+    }
+
+    #[test]
+    fn inserting_below_the_header_goes_after_the_separator() {
+        let content = "| A | B |\n|---|---|\n| 1 | 2 |\n";
+        let out = edit_table_row(content, 0, 0, 0, RowEdit::InsertBelow).unwrap();
+        assert_eq!(out, "| A | B |\n|---|---|\n|   |   |\n| 1 | 2 |\n");
+        assert_eq!(parsed_rows(&out)[1], ["1", "2"]);
+    }
+
+    #[test]
+    fn inserting_at_the_end_of_file_keeps_line_endings() {
+        let content = "| A |\r\n| --- |\r\n| 1 |";
+        let out = edit_table_row(content, 0, 0, 1, RowEdit::InsertBelow).unwrap();
+        assert_eq!(out, "| A |\r\n| --- |\r\n| 1 |\r\n|   |");
+    }
+
+    #[test]
+    fn deletes_body_rows_but_not_the_header() {
+        let content = "| A |\n| --- |\n| 1 |\n| 2 |";
+        let out = edit_table_row(content, 0, 0, 1, RowEdit::Delete).unwrap();
+        assert_eq!(out, "| A |\n| --- |\n| 2 |");
+        let out = edit_table_row(content, 0, 0, 2, RowEdit::Delete).unwrap();
+        assert_eq!(out, "| A |\n| --- |\n| 1 |", "no trailing newline added");
+        assert_eq!(parsed_rows(&out), [["1"]]);
+
+        assert!(edit_table_row(content, 0, 0, 0, RowEdit::Delete).is_err());
+        assert!(edit_table_row(content, 0, 0, 3, RowEdit::Delete).is_err());
+    }
+
+    #[test]
+    fn row_edits_address_the_right_table_in_the_section() {
+        let content = "# A\n| X |\n|---|\n| a |\n# B\n| X |\n|---|\n| b |\n\n| Y |\n|---|\n| c |\n";
+        let out = edit_table_row(content, 4, 1, 1, RowEdit::Delete).unwrap();
+        assert_eq!(
+            out,
+            "# A\n| X |\n|---|\n| a |\n# B\n| X |\n|---|\n| b |\n\n| Y |\n|---|\n"
+        );
+    }
 }
@@ -0,0 +1,269 @@
+//! Directory browsing (`treemd docs/`).
+//!
+//! The left pane lists the directory's markdown files as a tree and the
+//! content pane previews the selected file in full. Enter opens the file in
+//! the normal outline view. With `ui.group_by_frontmatter` set, the tree
+//! groups files under the value of that front-matter field instead.
+
+use std::path::{Path, PathBuf};
+
+/// One row of the file tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BrowserEntry {
+    pub path: PathBuf,
+    /// Nesting below the browsed directory (0 for its direct children)
+    pub depth: usize,
+    /// Directory or front-matter group row; never selected
+    pub is_dir: bool,
+    /// Shown instead of the file name: the category of a group row, or a
+    /// grouped file's path relative to the browsed directory
+    pub label: Option<String>,
+}
+
+impl BrowserEntry {
+    /// Name shown in the tree.
+    pub fn name(&self) -> String {
+        if let Some(label) = &self.label {
+            return label.clone();
+        }
+        self.path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default()
+    }
+}
+
+/// Markdown files under a directory, laid out as a tree.
+#[derive(Debug, Clone)]
+pub struct FileBrowser {
+    pub root: PathBuf,
+    pub entries: Vec<BrowserEntry>,
+    /// Index into `entries`; always a file
+    pub selected: usize,
+}
+
+impl FileBrowser {
+    /// Tree of `files` (sorted paths under `root`), with directory rows
+    /// before their contents and the first file selected. `None` when there
+    /// are no files.
+    pub fn new(root: PathBuf, files: &[PathBuf]) -> Option<Self> {
+        let mut entries: Vec<BrowserEntry> = Vec::new();
+        for file in files {
+            let relative = file.strip_prefix(&root).unwrap_or(file);
+            let mut dir = root.clone();
+            let parents: Vec<_> = relative
+                .parent()
+                .into_iter()
+                .flat_map(Path::components)
+                .collect();
+            for (depth, part) in parents.iter().enumerate() {
+                dir.push(part);
+                if !entries.iter().any(|e| e.is_dir && e.path == dir) {
+                    entries.push(BrowserEntry {
+                        path: dir.clone(),
+                        depth,
+                        is_dir: true,
+                        label: None,
+                    });
+                }
+            }
+            entries.push(BrowserEntry {
+                path: file.clone(),
+                depth: parents.len(),
+                is_dir: false,
+                label: None,
+            });
+        }
+        let selected = entries.iter().position(|e| !e.is_dir)?;
+        Some(Self {
+            root,
+            entries,
+            selected,
+        })
+    }
+
+    /// Tree with one row per group from [`group_files_by_frontmatter`] and
+    /// its files listed under it. `None` when there are no files.
+    pub fn grouped(root: PathBuf, groups: Vec<(String, Vec<PathBuf>)>) -> Option<Self> {
+        let mut entries = Vec::new();
+        for (name, files) in groups {
+            entries.push(BrowserEntry {
+                path: root.clone(),
+                depth: 0,
+                is_dir: true,
+                label: Some(name),
+            });
+            for file in files {
+                let relative = file.strip_prefix(&root).unwrap_or(&file);
+                let label = relative.to_string_lossy().to_string();
+                entries.push(BrowserEntry {
+                    path: file,
+                    depth: 1,
+                    is_dir: false,
+                    label: Some(label),
+                });
+            }
+        }
+        let selected = entries.iter().position(|e| !e.is_dir)?;
+        Some(Self {
+            root,
+            entries,
+            selected,
+        })
+    }
+
+    /// The selected file.
+    pub fn selected_file(&self) -> &Path {
+        &self.entries[self.selected].path
+    }
+
+    /// Move the selection `delta` files down (negative: up), skipping
+    /// directory rows and stopping at either end. Returns whether it moved.
+    pub fn move_by(&mut self, delta: isize) -> bool {
+        let files: Vec<usize> = (0..self.entries.len())
+            .filter(|&i| !self.entries[i].is_dir)
+            .collect();
+        let Some(pos) = files.iter().position(|&i| i == self.selected) else {
+            return false;
+        };
+        let target = pos.saturating_add_signed(delta).min(files.len() - 1);
+        let moved = files[target] != self.selected;
+        self.selected = files[target];
+        moved
+    }
+
+    /// Select the first (or last) file. Returns whether it moved.
+    pub fn move_to_end(&mut self, last: bool) -> bool {
+        self.move_by(if last { isize::MAX } else { isize::MIN })
+    }
+}
+
+/// Group name for files whose front matter lacks the grouping field.
+pub const UNCATEGORIZED_GROUP: &str = "Uncategorized";
+
+/// Group `(path, content)` pairs by the value of a front-matter `field`.
+///
+/// Groups are sorted by name, with [`UNCATEGORIZED_GROUP`] always last; files
+/// keep their input order within a group.
+pub fn group_files_by_frontmatter(
+    files: impl IntoIterator<Item = (PathBuf, String)>,
+    field: &str,
+) -> Vec<(String, Vec<PathBuf>)> {
+    use turbovault_parser::{ParseOptions, ParsedContent};
+
+    let mut groups: std::collections::BTreeMap<String, Vec<PathBuf>> = Default::default();
+    let mut uncategorized = Vec::new();
+
+    for (path, content) in files {
+        let parsed = ParsedContent::parse_with_options(
+            &crate::input::normalize(&content),
+            ParseOptions::none().with_frontmatter(),
+        );
+        let value = parsed
+            .frontmatter
+            .and_then(|fm| fm.data.get(field).cloned())
+            .and_then(|v| match v {
+                serde_json::Value::Null => None,
+                serde_json::Value::String(s) => Some(s.trim().to_string()),
+                other => Some(other.to_string()),
+            })
+            .filter(|s| !s.is_empty());
+
+        match value {
+            Some(name) => groups.entry(name).or_default().push(path),
+            None => uncategorized.push(path),
+        }
+    }
+
+    let mut out: Vec<_> = groups.into_iter().collect();
+    if !uncategorized.is_empty() {
+        out.push((UNCATEGORIZED_GROUP.to_string(), uncategorized));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn browser() -> FileBrowser {
+        let root = PathBuf::from("/docs");
+        let files: Vec<PathBuf> = ["README.md", "guide/deep/notes.md", "guide/install.md"]
+            .iter()
+            .map(|f| root.join(f))
+            .collect();
+        FileBrowser::new(root, &files).unwrap()
+    }
+
+    #[test]
+    fn directories_are_listed_before_their_files() {
+        let rows: Vec<_> = browser()
+            .entries
+            .iter()
+            .map(|e| {
+                format!(
+                    "{}{}{}",
+                    "  ".repeat(e.depth),
+                    e.name(),
+                    if e.is_dir { "/" } else { "" }
+                )
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                "README.md",
+                "guide/",
+                "  deep/",
+                "    notes.md",
+                "  install.md"
+            ]
+        );
+    }
+
+    #[test]
+    fn selection_moves_over_files_only() {
+        let mut files = browser();
+        assert_eq!(files.selected_file(), Path::new("/docs/README.md"));
+        assert!(files.move_by(1));
+        assert_eq!(
+            files.selected_file(),
+            Path::new("/docs/guide/deep/notes.md")
+        );
+        assert!(files.move_to_end(true));
+        assert_eq!(files.selected_file(), Path::new("/docs/guide/install.md"));
+        assert!(!files.move_by(1), "stops at the last file");
+        assert!(files.move_by(-5));
+        assert_eq!(files.selected_file(), Path::new("/docs/README.md"));
+        assert!(FileBrowser::new(PathBuf::from("/empty"), &[]).is_none());
+    }
+
+    #[test]
+    fn groups_files_by_category_with_uncategorized_last() {
+        let files = vec![
+            (
+                PathBuf::from("a.md"),
+                "---\ncategory: recipes\n---\n# A\n".to_string(),
+            ),
+            (
+                PathBuf::from("b.md"),
+                "---\ncategory: journal\n---\n# B\n".to_string(),
+            ),
+            (
+                PathBuf::from("c.md"),
+                "---\ncategory: recipes\n---\n# C\n".to_string(),
+            ),
+            (PathBuf::from("d.md"), "# D\n".to_string()),
+        ];
+
+        let groups = group_files_by_frontmatter(files, "category");
+        let names: Vec<&str> = groups.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, vec!["journal", "recipes", UNCATEGORIZED_GROUP]);
+        assert_eq!(groups[0].1, vec![PathBuf::from("b.md")]);
+        assert_eq!(
+            groups[1].1,
+            vec![PathBuf::from("a.md"), PathBuf::from("c.md")]
+        );
+        assert_eq!(groups[2].1, vec![PathBuf::from("d.md")]);
+    }
+}
@@ -0,0 +1,127 @@
+//! Files named on the command line (`treemd a.md b.md c.md`).
+//!
+//! `>` and `<` step through them in order, wrapping around. Each file
+//! remembers where it was left so switching back restores the selection and
+//! scroll. This is separate from the `b`/`F` history, which records every
+//! file visited, however it was opened.
+
+use std::path::{Path, PathBuf};
+
+/// Where a file was left when switching away from it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FilePosition {
+    pub selected_heading: Option<String>,
+    pub content_scroll: u16,
+}
+
+/// The command-line files, with the position each was last left at.
+#[derive(Debug, Clone, Default)]
+pub struct FileList {
+    files: Vec<PathBuf>,
+    positions: Vec<Option<FilePosition>>,
+    /// Index of the file last switched to
+    current: usize,
+}
+
+impl FileList {
+    pub fn new(files: Vec<PathBuf>) -> Self {
+        let positions = vec![None; files.len()];
+        Self {
+            files,
+            positions,
+            current: 0,
+        }
+    }
+
+    /// Number of files in the list.
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Whether there is another file to switch to.
+    pub fn can_switch(&self) -> bool {
+        self.files.len() > 1
+    }
+
+    /// Position of `path` in the list.
+    pub fn index_of(&self, path: &Path) -> Option<usize> {
+        self.files.iter().position(|file| file == path)
+    }
+
+    /// Remember `position` for `path`, if it is in the list.
+    pub fn save(&mut self, path: &Path, position: FilePosition) {
+        if let Some(i) = self.index_of(path) {
+            self.positions[i] = Some(position);
+        }
+    }
+
+    /// The file after (or before) `current`, wrapping around, with the
+    /// position it was left at. When `current` is not in the list (a link
+    /// led elsewhere), steps from the file last switched to.
+    pub fn step(
+        &mut self,
+        current: &Path,
+        forward: bool,
+    ) -> Option<(PathBuf, Option<FilePosition>)> {
+        if self.files.is_empty() {
+            return None;
+        }
+        let from = self.index_of(current).unwrap_or(self.current);
+        let len = self.files.len();
+        self.current = if forward {
+            (from + 1) % len
+        } else {
+            (from + len - 1) % len
+        };
+        Some((
+            self.files[self.current].clone(),
+            self.positions[self.current].clone(),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn list() -> FileList {
+        FileList::new(vec!["a.md".into(), "b.md".into(), "c.md".into()])
+    }
+
+    fn step(files: &mut FileList, current: &str, forward: bool) -> PathBuf {
+        files.step(Path::new(current), forward).unwrap().0
+    }
+
+    #[test]
+    fn steps_wrap_around_both_ways() {
+        let mut files = list();
+        assert_eq!(step(&mut files, "a.md", true), Path::new("b.md"));
+        assert_eq!(step(&mut files, "c.md", true), Path::new("a.md"));
+        assert_eq!(step(&mut files, "a.md", false), Path::new("c.md"));
+        assert_eq!(step(&mut files, "b.md", false), Path::new("a.md"));
+    }
+
+    #[test]
+    fn a_file_outside_the_list_steps_from_the_last_one_switched_to() {
+        let mut files = list();
+        assert_eq!(step(&mut files, "a.md", true), Path::new("b.md"));
+        // A link from b.md opened notes.md
+        assert_eq!(step(&mut files, "notes.md", true), Path::new("c.md"));
+        assert_eq!(step(&mut files, "notes.md", false), Path::new("b.md"));
+    }
+
+    #[test]
+    fn positions_are_kept_per_file() {
+        let mut files = list();
+        let left = FilePosition {
+            selected_heading: Some("Usage".to_string()),
+            content_scroll: 12,
+        };
+        files.save(Path::new("a.md"), left.clone());
+        files.save(Path::new("other.md"), FilePosition::default());
+
+        assert_eq!(files.step(Path::new("a.md"), true).unwrap().1, None);
+        assert_eq!(files.step(Path::new("b.md"), false).unwrap().1, Some(left));
+        assert!(files.can_switch() && !FileList::new(vec!["a.md".into()]).can_switch());
+    }
+}
@@ -0,0 +1,206 @@
+//! Fuzzy matching for the outline filter.
+//!
+//! A query matches when its characters appear in order in the heading,
+//! ignoring case (`cfg` matches "Configuration"). Runs of consecutive
+//! characters and matches at word starts score higher, gaps score lower, and
+//! a heading that contains the query as a substring always outranks one that
+//! only matches loosely.
+
+use crate::tui::ui::util::find_ignore_case;
+
+/// Score given to every substring match on top of its own bonuses.
+const SUBSTRING_SCORE: i64 = 1_000;
+const CHAR_SCORE: i64 = 1;
+const CONSECUTIVE_BONUS: i64 = 5;
+const WORD_START_BONUS: i64 = 5;
+/// Cost of each skipped character between two matched ones, up to
+/// `MAX_GAP_PENALTY` per gap.
+const GAP_PENALTY: i64 = 1;
+const MAX_GAP_PENALTY: i64 = 5;
+
+/// How well a query matched and which parts of the text it matched.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// Byte ranges of the matched characters, merged where adjacent
+    pub ranges: Vec<(usize, usize)>,
+}
+
+/// Match `query` against `text`, or `None` if its characters do not all
+/// appear in order. An empty query matches everything with score 0.
+pub fn fuzzy_match(text: &str, query: &str) -> Option<FuzzyMatch> {
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            ranges: Vec::new(),
+        });
+    }
+
+    if let Some(&(start, end)) = find_ignore_case(text, query).first() {
+        let boundary = if is_word_start(text, start) {
+            WORD_START_BONUS
+        } else {
+            0
+        };
+        // Earlier and tighter matches first
+        let score = SUBSTRING_SCORE + boundary * 10 - text[..start].chars().count() as i64;
+        return Some(FuzzyMatch {
+            score,
+            ranges: vec![(start, end)],
+        });
+    }
+
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let query: Vec<char> = query.chars().collect();
+    let positions = best_alignment(text, &chars, &query)?;
+
+    let mut score = 0;
+    let mut ranges: Vec<(usize, usize)> = Vec::new();
+    for (n, &i) in positions.iter().enumerate() {
+        let previous = n.checked_sub(1).map(|p| positions[p]);
+        score += char_score(text, chars[i].0) + link_score(previous, i);
+        let (offset, c) = chars[i];
+        let end = offset + c.len_utf8();
+        match ranges.last_mut() {
+            Some(last) if last.1 == offset => last.1 = end,
+            _ => ranges.push((offset, end)),
+        }
+    }
+    Some(FuzzyMatch { score, ranges })
+}
+
+/// Indices into `chars` where each query character is matched, choosing the
+/// highest-scoring placement (earliest on ties).
+fn best_alignment(text: &str, chars: &[(usize, char)], query: &[char]) -> Option<Vec<usize>> {
+    // best[j][i]: best score with query[..=j] matched and query[j] at chars[i],
+    // plus where query[j - 1] was matched
+    let mut best: Vec<Vec<Option<(i64, usize)>>> = vec![vec![None; chars.len()]; query.len()];
+    for (j, &q) in query.iter().enumerate() {
+        for i in j..chars.len() {
+            if !same_char(chars[i].1, q) {
+                continue;
+            }
+            let own = char_score(text, chars[i].0);
+            if j == 0 {
+                best[j][i] = Some((own, 0));
+                continue;
+            }
+            let mut found: Option<(i64, usize)> = None;
+            for (k, cell) in best[j - 1].iter().enumerate().take(i).skip(j - 1) {
+                if let Some((prev, _)) = *cell {
+                    let total = prev + own + link_score(Some(k), i);
+                    if found.is_none_or(|(score, _)| total > score) {
+                        found = Some((total, k));
+                    }
+                }
+            }
+            best[j][i] = found;
+        }
+    }
+
+    let last = query.len() - 1;
+    let mut end: Option<(i64, usize)> = None;
+    for (i, cell) in best[last].iter().enumerate() {
+        if let Some((score, _)) = *cell
+            && end.is_none_or(|(top, _)| score > top)
+        {
+            end = Some((score, i));
+        }
+    }
+    let mut i = end?.1;
+    let mut positions = vec![i];
+    for j in (1..=last).rev() {
+        i = best[j][i]?.1;
+        positions.push(i);
+    }
+    positions.reverse();
+    Some(positions)
+}
+
+/// Score for matching the character at byte `offset`.
+fn char_score(text: &str, offset: usize) -> i64 {
+    if is_word_start(text, offset) {
+        CHAR_SCORE + WORD_START_BONUS
+    } else {
+        CHAR_SCORE
+    }
+}
+
+/// Bonus or penalty for matching at char index `i` after `previous`.
+fn link_score(previous: Option<usize>, i: usize) -> i64 {
+    match previous {
+        Some(p) if p + 1 == i => CONSECUTIVE_BONUS,
+        Some(p) => -((i - p - 1) as i64 * GAP_PENALTY).min(MAX_GAP_PENALTY),
+        None => 0,
+    }
+}
+
+fn same_char(a: char, b: char) -> bool {
+    a == b || a.to_lowercase().eq(b.to_lowercase())
+}
+
+/// Whether the character at byte `offset` starts a word: the start of the
+/// text, after a non-alphanumeric character, or a capital after a lowercase
+/// letter (`camelCase`).
+fn is_word_start(text: &str, offset: usize) -> bool {
+    let Some(before) = text[..offset].chars().next_back() else {
+        return true;
+    };
+    let current = text[offset..].chars().next().unwrap_or(' ');
+    !before.is_alphanumeric() || (before.is_lowercase() && current.is_uppercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn score(text: &str, query: &str) -> Option<i64> {
+        fuzzy_match(text, query).map(|m| m.score)
+    }
+
+    fn matched<'a>(text: &'a str, query: &str) -> Vec<&'a str> {
+        fuzzy_match(text, query)
+            .unwrap()
+            .ranges
+            .iter()
+            .map(|&(start, end)| &text[start..end])
+            .collect()
+    }
+
+    #[test]
+    fn subsequences_match_ignoring_case() {
+        assert!(score("Configuration", "cfg").is_some());
+        assert!(score("Getting Started", "gs").is_some());
+        assert!(score("API Reference", "apiref").is_some());
+        assert!(score("Configuration", "gfc").is_none());
+        assert!(score("Install", "installer").is_none());
+    }
+
+    #[test]
+    fn substrings_outrank_fuzzy_hits() {
+        let substring = score("Setup and config", "config").unwrap();
+        let fuzzy = score("Configuration guide", "confg").unwrap();
+        assert!(substring > fuzzy);
+        assert!(score("Config", "config") > score("Reconfigure", "config"));
+    }
+
+    #[test]
+    fn runs_and_word_starts_beat_scattered_letters() {
+        assert!(score("Release Notes", "rn") > score("Reorganizing", "rn"));
+        assert!(score("Setup Guide", "setgu") > score("Server Teardown Guide", "setgu"));
+        assert!(score("getStarted", "gs") > score("Guides", "gs"));
+    }
+
+    #[test]
+    fn ranges_cover_the_matched_characters() {
+        assert_eq!(matched("Configuration", "cfg"), ["C", "f", "g"]);
+        assert_eq!(matched("Config Files", "cofi"), ["Co", "Fi"]);
+        assert_eq!(matched("Über Café", "übca"), ["Üb", "Ca"]);
+        assert_eq!(matched("Setup and config", "CONFIG"), ["config"]);
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        assert_eq!(score("Anything", ""), Some(0));
+    }
+}
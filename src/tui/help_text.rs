@@ -43,6 +43,27 @@ impl HelpLine {
             .join("/")
     }
 
+    /// Lowercased text a help search looks in: the keys and description of a
+    /// binding, or a note's text.
+    fn search_text(&self, keybindings: &Keybindings) -> String {
+        match *self {
+            HelpLine::KeyBinding {
+                prefix,
+                mode,
+                actions,
+                desc,
+            } => format!(
+                "{}{} {}",
+                prefix,
+                Self::format_action_keys(keybindings, mode, actions),
+                desc
+            )
+            .to_lowercase(),
+            HelpLine::Note(text) => text.to_lowercase(),
+            _ => String::new(),
+        }
+    }
+
     /// Convert this help line to a styled ratatui Line
     pub fn to_line(self, keybindings: &Keybindings, theme: &Theme) -> Line<'static> {
         match self {
@@ -143,7 +164,7 @@ const fn blank() -> HelpLine {
 pub const HELP_LINES: &[HelpLine] = &[
     // Title and instructions
     title("treemd - Keyboard Shortcuts"),
-    description("Use j/k or ↓/↑ to scroll | Press Esc or ? to close"),
+    description("Use j/k or ↓/↑ to scroll | / to search | Press Esc or ? to close"),
     blank(),
     // Navigation section
     section("Navigation"),
@@ -152,14 +173,39 @@ pub const HELP_LINES: &[HelpLine] = &[
     keybinding(Normal, &[First], "Jump to top"),
     keybinding(Normal, &[Last], "Jump to bottom"),
     keybinding(Normal, &[JumpToParent], "Jump to parent heading"),
+    keybinding(Normal, &[JumpBack], "Back to position before last jump"),
+    keybinding(Normal, &[JumpForward], "Forward again in jump list"),
     keybinding(Normal, &[PageDown], "Page down (content)"),
     keybinding(Normal, &[PageUp], "Page up (content)"),
+    keybinding(
+        Normal,
+        &[HalfPageDown, HalfPageUp],
+        "Half page down/up (content)",
+    ),
+    keybinding(
+        Normal,
+        &[ScrollLeft, ScrollRight],
+        "Scroll sideways (wrap off; else unwrapped code)",
+    ),
     blank(),
     // Tree Operations
     section("Tree Operations"),
     keybinding(Normal, &[ToggleExpand], "Toggle expand/collapse"),
     keybinding(Normal, &[Expand], "Expand heading"),
     keybinding(Normal, &[Collapse], "Collapse (or parent if no children)"),
+    keybinding(Normal, &[ExpandMoreLevels], "Reveal one more outline level"),
+    keybinding(Normal, &[CollapseMoreLevels], "Hide deepest outline level"),
+    keybinding(
+        Normal,
+        &[CollapseAll, ExpandAll],
+        "Collapse / expand all headings",
+    ),
+    prefixed_keybinding(
+        "[N]",
+        Normal,
+        &[FoldToLevel],
+        "Fold to heading level N (default: selected heading's)",
+    ),
     blank(),
     // General
     section("General"),
@@ -168,6 +214,11 @@ pub const HELP_LINES: &[HelpLine] = &[
     keybinding(Normal, &[EnterSearchMode], "Filter outline headings"),
     keybinding(Search, &[ExitMode], "Clear search"),
     keybinding(Search, &[ConfirmAction], "Confirm search"),
+    keybinding(
+        Search,
+        &[ToggleOutlineSearchScope],
+        "Filter headings only / headings + text",
+    ),
     keybinding(
         Normal,
         &[NextMatch, PrevMatch],
@@ -175,12 +226,29 @@ pub const HELP_LINES: &[HelpLine] = &[
     ),
     keybinding(Normal, &[OpenFilePicker], "Open file picker"),
     keybinding(Normal, &[ToggleRawSource], "Toggle raw source view"),
+    keybinding(
+        Normal,
+        &[ToggleWrap],
+        "Toggle line wrap (h/l scroll when off)",
+    ),
+    keybinding(Normal, &[ToggleLineNumbers], "Toggle source line numbers"),
+    keybinding(
+        Normal,
+        &[CycleLineNumberStyle],
+        "Line numbers: absolute / relative / hybrid",
+    ),
     keybinding(
         Normal,
         &[ToggleMouseCapture],
         "Toggle mouse capture (off to select/copy text)",
     ),
+    keybinding(Normal, &[ShowStats], "Section word count and reading time"),
     keybinding(Normal, &[ToggleHelp], "Toggle this help"),
+    keybinding(
+        Help,
+        &[HelpSearch],
+        "Search this help by key or description (in help)",
+    ),
     keybinding(Normal, &[Quit], "Quit"),
     blank(),
     // UX Features
@@ -195,6 +263,11 @@ pub const HELP_LINES: &[HelpLine] = &[
         &[OutlineWidthDecrease, OutlineWidthIncrease],
         "Decrease/increase outline width (20%, 30%, 40%)",
     ),
+    keybinding(
+        Normal,
+        &[OutlineWidthShrink, OutlineWidthGrow],
+        "Narrow/widen outline in small steps",
+    ),
     keybinding(
         Normal,
         &[OpenCommandPalette],
@@ -206,13 +279,34 @@ pub const HELP_LINES: &[HelpLine] = &[
         &[Next, Previous],
         "Move N items (vim count prefix, e.g., 5j)",
     ),
+    prefixed_keybinding(
+        "[N]",
+        Normal,
+        &[First, Last],
+        "Go to item N (e.g., 10G); a lone digit jumps to heading N",
+    ),
+    prefixed_keybinding(
+        ":N Enter",
+        Normal,
+        &[],
+        "Go to heading N through the command palette (e.g., :120)",
+    ),
     keybinding(
         Normal,
         &[ToggleHeadingMarkers],
         "Toggle heading markers in outline",
     ),
-    keybinding(Normal, &[SetBookmark], "Set bookmark (shows ⚑ indicator)"),
-    keybinding(Normal, &[JumpToBookmark], "Jump to bookmarked position"),
+    keybinding(
+        Normal,
+        &[ToggleOutlineMode],
+        "Outline: headings only / all blocks",
+    ),
+    keybinding(
+        Normal,
+        &[SetBookmark],
+        "Set mark a-z, or bookmark (shows ⚑)",
+    ),
+    keybinding(Normal, &[JumpToBookmark], "Jump to mark a-z, or bookmark"),
     blank(),
     // Link Following
     section("Link Following"),
@@ -238,8 +332,22 @@ pub const HELP_LINES: &[HelpLine] = &[
         &[JumpToParent],
         "Jump to parent's links (stay in link mode)",
     ),
-    keybinding(Normal, &[GoBack], "Go back to previous file"),
+    keybinding(
+        LinkFollow,
+        &[JumpToFootnote],
+        "Jump between footnote reference and note",
+    ),
+    keybinding(
+        Normal,
+        &[GoBack],
+        "Go back to previous file (or the directory browser)",
+    ),
     keybinding(Normal, &[GoForward], "Go forward in navigation history"),
+    keybinding(
+        Normal,
+        &[NextFile, PreviousFile],
+        "Next/previous file from the command line",
+    ),
     blank(),
     // Interactive Mode
     section("Interactive Mode"),
@@ -254,12 +362,22 @@ pub const HELP_LINES: &[HelpLine] = &[
         "Next/previous element",
     ),
     keybinding(Interactive, &[PageUp, PageDown], "Page up/down"),
+    keybinding(
+        Interactive,
+        &[HalfPageUp, HalfPageDown],
+        "Half page up/down",
+    ),
     keybinding(
         Interactive,
         &[InteractiveActivate],
         "Activate element (toggle/follow/edit)",
     ),
     keybinding(Interactive, &[CopyContent], "Copy element (code/cell/link)"),
+    keybinding(
+        Interactive,
+        &[JumpToFootnote],
+        "Jump between footnote reference and note",
+    ),
     keybinding(
         InteractiveTable,
         &[
@@ -277,14 +395,34 @@ pub const HELP_LINES: &[HelpLine] = &[
     ),
     keybinding(
         InteractiveTable,
-        &[CopyTableCell, CopyTableRow, CopyTableMarkdown],
-        "Copy cell / row / table as markdown",
+        &[CopyTableCell, CopyTableRow, CopyTableMarkdown, CopyTableCsv],
+        "Copy cell / row / table as markdown / table as CSV",
+    ),
+    keybinding(
+        InteractiveTable,
+        &[TableRowInsert, TableRowDelete],
+        "Insert a row below / delete the row (saves the file)",
+    ),
+    keybinding(
+        InteractiveTable,
+        &[TableSort],
+        "Sort rows by column (again to reverse; view only)",
     ),
     keybinding(InteractiveTable, &[ExitMode], "Exit table navigation"),
     blank(),
     // Themes & Clipboard
     section("Themes & Clipboard"),
     keybinding(Normal, &[ToggleThemePicker], "Toggle theme picker"),
+    keybinding(
+        ThemePicker,
+        &[ExportTheme],
+        "In the picker: copy the theme as a [[theme.custom]] block",
+    ),
+    keybinding(
+        Normal,
+        &[CycleCodeTheme],
+        "Next code highlighting theme (c in the picker)",
+    ),
     keybinding(
         Normal,
         &[CopyContent],
@@ -293,25 +431,147 @@ pub const HELP_LINES: &[HelpLine] = &[
     keybinding(
         Normal,
         &[CopyAnchor],
-        "Copy anchor link (works in all modes)",
+        "Copy file.md#anchor link, GitHub style (works in all modes)",
+    ),
+    keybinding(
+        Normal,
+        &[CopyHeadingLink],
+        "Copy markdown link to heading ([Text](#slug))",
+    ),
+    keybinding(
+        Normal,
+        &[CopyPlainText],
+        "Copy section as plain text (no markup, for chat or email)",
     ),
     keybinding(
         Normal,
         &[OpenInEditor],
         "Edit file in default editor ($VISUAL or $EDITOR)",
     ),
+    keybinding(
+        Normal,
+        &[ExportDiagram],
+        "Export mermaid/plantuml source to .mmd/.puml",
+    ),
+    keybinding(
+        Normal,
+        &[ExportHtml],
+        "Export section (with subsections) to HTML",
+    ),
     blank(),
     // Note
     note("On Linux, install a clipboard manager (clipit, parcellite, xclip) for best results"),
+    note("Over SSH, copies go to your local terminal via OSC 52 ([terminal] clipboard)"),
     blank(),
     // Footer
-    description("Use j/k or ↓/↑ to scroll | Press Esc or ? to close"),
+    description("Use j/k or ↓/↑ to scroll | / to search | Press Esc or ? to close"),
 ];
 
-/// Build the help text with theme colors applied
-pub fn build_help_text(keybindings: &Keybindings, theme: &Theme) -> Vec<Line<'static>> {
-    HELP_LINES
-        .iter()
+/// The help lines whose keys or description contain `query` (ignoring
+/// case), each group under its section header. A section whose header
+/// matches is kept whole. An empty query keeps every line.
+pub fn matching_help_lines(keybindings: &Keybindings, query: &str) -> Vec<HelpLine> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return HELP_LINES.to_vec();
+    }
+
+    let mut lines = Vec::new();
+    let mut header = None;
+    let mut header_matches = false;
+    for line in HELP_LINES {
+        match line {
+            HelpLine::SectionHeader(text) => {
+                header = Some(*line);
+                header_matches = text.to_lowercase().contains(&query);
+            }
+            HelpLine::KeyBinding { .. } | HelpLine::Note(_) => {
+                if !header_matches && !line.search_text(keybindings).contains(&query) {
+                    continue;
+                }
+                if let Some(header) = header.take() {
+                    if !lines.is_empty() {
+                        lines.push(HelpLine::Blank);
+                    }
+                    lines.push(header);
+                }
+                lines.push(*line);
+            }
+            HelpLine::Title(_) | HelpLine::Description(_) | HelpLine::Blank => {}
+        }
+    }
+    lines
+}
+
+/// Build the help text with theme colors applied, keeping only the lines
+/// that match `query` (see [`matching_help_lines`])
+pub fn build_help_text(
+    keybindings: &Keybindings,
+    theme: &Theme,
+    query: &str,
+) -> Vec<Line<'static>> {
+    matching_help_lines(keybindings, query)
+        .into_iter()
         .map(|line| line.to_line(keybindings, theme))
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn help_search_matches_keys_and_descriptions() {
+        let kb = Keybindings::default();
+        let texts = |query: &str| -> Vec<String> {
+            matching_help_lines(&kb, query)
+                .iter()
+                .map(|line| match line {
+                    HelpLine::SectionHeader(text) => format!("[{}]", text),
+                    HelpLine::KeyBinding { desc, .. } => desc.to_string(),
+                    HelpLine::Blank => String::new(),
+                    other => panic!("unexpected line {:?}", other),
+                })
+                .collect()
+        };
+
+        let found = texts("PARENT heading");
+        assert_eq!(found[0], "[Navigation]");
+        assert!(found.contains(&"Jump to parent heading".to_string()));
+        assert!(found.iter().all(|t| t.starts_with('[')
+            || t.is_empty()
+            || t.to_lowercase().contains("parent heading")));
+
+        // Key names match too: `C-o` (Ctrl+o) is the jump-back key
+        assert!(texts("c-o").contains(&"Back to position before last jump".to_string()));
+
+        assert!(texts("no such binding").is_empty());
+        assert_eq!(matching_help_lines(&kb, " ").len(), HELP_LINES.len());
+    }
+
+    #[test]
+    fn documented_actions_have_default_keys() {
+        // A help line whose action has no default key renders with an empty
+        // key column and documents a feature nobody can reach.
+        let kb = Keybindings::default();
+        for line in HELP_LINES {
+            if let HelpLine::KeyBinding {
+                mode,
+                actions,
+                desc,
+                ..
+            } = line
+            {
+                for action in *actions {
+                    assert!(
+                        !kb.keys_for_action(*mode, *action).is_empty(),
+                        "{:?} ({}) has no default key in {:?} mode",
+                        action,
+                        desc,
+                        mode
+                    );
+                }
+            }
+        }
+    }
+}
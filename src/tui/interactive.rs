@@ -5,11 +5,12 @@
 //! - Links (follow/copy)
 //! - Checkboxes (toggle/save)
 //! - Code blocks (copy)
-//! - Tables (navigate cells)
+//! - Tables (navigate cells, sort by column)
 //! - Images (view info)
 
 use crate::parser::output::{Block, InlineElement};
 use crate::parser::{Link, LinkTarget};
+use crate::tui::diagram;
 use std::collections::HashMap;
 
 // Sub-index encoding constants for nested elements within list items
@@ -106,6 +107,17 @@ pub struct InteractiveState {
     pub element_states: HashMap<ElementId, ElementState>,
     /// Current detail navigation mode (for tables/lists)
     pub detail_mode: Option<DetailMode>,
+    /// View-only row order of the table being navigated; cleared when table
+    /// mode ends
+    pub table_sort: Option<TableSort>,
+}
+
+/// Sort applied to a table's rows in the rendered view.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSort {
+    pub id: ElementId,
+    pub col: usize,
+    pub descending: bool,
 }
 
 /// Unique identifier for an element
@@ -181,6 +193,9 @@ pub enum ElementState {
     List {
         selected_item: usize,
     },
+    Diagram {
+        show_source: bool,
+    },
 }
 
 /// Fine-grained navigation mode for complex elements
@@ -197,6 +212,7 @@ impl InteractiveState {
             current_index: None,
             element_states: HashMap::new(),
             detail_mode: None,
+            table_sort: None,
         }
     }
 
@@ -430,7 +446,7 @@ impl InteractiveState {
                         }
                     }
                 }
-                Block::Paragraph { inline, .. } => {
+                Block::Paragraph { content, inline } => {
                     // Extract links and images from inline elements
                     let mut paragraph_has_image = false;
                     for (inline_idx, inline_elem) in inline.iter().enumerate() {
@@ -501,7 +517,7 @@ impl InteractiveState {
                     if paragraph_has_image {
                         current_line += PARAGRAPH_WITH_IMAGE_TOTAL_LINES;
                     } else {
-                        current_line += 1;
+                        current_line += paragraph_lines(content);
                     }
                 }
                 Block::List { items, .. } => {
@@ -708,6 +724,15 @@ impl InteractiveState {
                     #[cfg(not(all(feature = "mermaid", unix)))]
                     let lines = 2 + content.lines().count();
 
+                    // Undrawable diagrams collapse to a fixed-size placeholder
+                    let lines = if diagram::placeholder_kind(language.as_deref()).is_some()
+                        && !self.is_diagram_source_shown(id)
+                    {
+                        diagram::DIAGRAM_PLACEHOLDER_LINES
+                    } else {
+                        lines
+                    };
+
                     self.elements.push(InteractiveElement {
                         id,
                         element_type: ElementType::CodeBlock {
@@ -840,6 +865,21 @@ impl InteractiveState {
         )
     }
 
+    /// Check if a diagram placeholder has been toggled to show its source
+    pub fn is_diagram_source_shown(&self, id: ElementId) -> bool {
+        matches!(
+            self.element_states.get(&id),
+            Some(ElementState::Diagram { show_source: true })
+        )
+    }
+
+    /// Toggle between a diagram's placeholder and its source
+    pub fn toggle_diagram_source(&mut self, id: ElementId) {
+        let show_source = !self.is_diagram_source_shown(id);
+        self.element_states
+            .insert(id, ElementState::Diagram { show_source });
+    }
+
     /// Toggle details block expansion
     pub fn toggle_details(&mut self, id: ElementId) {
         if let Some(ElementState::Details { expanded }) = self.element_states.get_mut(&id) {
@@ -1040,6 +1080,7 @@ impl InteractiveState {
     pub fn exit(&mut self) {
         self.current_index = None;
         self.detail_mode = None;
+        self.table_sort = None;
     }
 
     /// Check if in interactive mode
@@ -1062,6 +1103,47 @@ impl InteractiveState {
     /// Exit table navigation mode
     pub fn exit_table_mode(&mut self) {
         self.detail_mode = None;
+        self.table_sort = None;
+    }
+
+    /// Sort the table being navigated by its selected column: ascending
+    /// first, then flipping direction when repeated on the same column.
+    /// Returns the new sort, or `None` outside table mode.
+    pub fn sort_table_by_selected_column(&mut self) -> Option<TableSort> {
+        let Some(DetailMode::Table { element_idx }) = self.detail_mode else {
+            return None;
+        };
+        let id = self.elements.get(element_idx)?.id;
+        let Some(ElementState::Table { selected_col, .. }) = self.element_states.get(&id) else {
+            return None;
+        };
+        let col = *selected_col;
+        let descending = self
+            .table_sort
+            .is_some_and(|sort| sort.id == id && sort.col == col && !sort.descending);
+        let sort = TableSort {
+            id,
+            col,
+            descending,
+        };
+        self.table_sort = Some(sort);
+        Some(sort)
+    }
+
+    /// Source indices of the table `id`'s data rows in display order.
+    pub fn table_row_order(&self, id: ElementId, rows: &[Vec<String>]) -> Vec<usize> {
+        match self.table_sort {
+            Some(sort) if sort.id == id => sort_order(rows, sort.col, sort.descending),
+            _ => (0..rows.len()).collect(),
+        }
+    }
+
+    /// The table `id`'s data rows as displayed.
+    pub fn displayed_table_rows(&self, id: ElementId, rows: &[Vec<String>]) -> Vec<Vec<String>> {
+        self.table_row_order(id, rows)
+            .into_iter()
+            .map(|i| rows[i].clone())
+            .collect()
     }
 
     /// Check if in table navigation mode
@@ -1081,7 +1163,7 @@ impl InteractiveState {
             }) = self.element_states.get(&id)
             {
                 return format!(
-                    "[TABLE] Cell({},{}) | hjkl:Move y:Copy Y:CopyRow r:CopyTable Esc:Exit",
+                    "[TABLE] Cell({},{}) | hjkl:Move y:Copy Y:CopyRow r:CopyTable s:Sort Esc:Exit",
                     selected_row + 1,
                     selected_col + 1
                 );
@@ -1151,6 +1233,19 @@ impl InteractiveState {
         }
     }
 
+    /// Select row `row` (0 = header) of the table being navigated
+    pub fn select_table_row(&mut self, row: usize) {
+        if let Some(DetailMode::Table { element_idx }) = &self.detail_mode
+            && let Some(element) = self.elements.get(*element_idx)
+        {
+            let id = element.id;
+            if let Some(ElementState::Table { selected_row, .. }) = self.element_states.get_mut(&id)
+            {
+                *selected_row = row;
+            }
+        }
+    }
+
     /// Get the currently selected table cell content
     pub fn get_table_cell(&self, headers: &[String], rows: &[Vec<String>]) -> Option<String> {
         if let Some(DetailMode::Table { element_idx }) = &self.detail_mode
@@ -1215,12 +1310,53 @@ impl InteractiveState {
     }
 }
 
+/// Order of `rows` sorted by column `col`: numerically when every non-empty
+/// cell parses as a number, otherwise case-insensitively. Empty cells go
+/// last in either direction, and ties keep their original order.
+pub fn sort_order(rows: &[Vec<String>], col: usize, descending: bool) -> Vec<usize> {
+    let cell = |i: usize| rows[i].get(col).map_or("", |c| c.trim());
+    let numeric = (0..rows.len())
+        .map(cell)
+        .filter(|c| !c.is_empty())
+        .all(|c| c.parse::<f64>().is_ok());
+
+    let mut order: Vec<usize> = (0..rows.len()).collect();
+    order.sort_by(|&a, &b| {
+        let (x, y) = (cell(a), cell(b));
+        match (x.is_empty(), y.is_empty()) {
+            (true, true) => return std::cmp::Ordering::Equal,
+            (true, false) => return std::cmp::Ordering::Greater,
+            (false, true) => return std::cmp::Ordering::Less,
+            _ => {}
+        }
+        let ord = if numeric {
+            let (x, y) = (
+                x.parse::<f64>().unwrap_or(0.0),
+                y.parse::<f64>().unwrap_or(0.0),
+            );
+            x.total_cmp(&y)
+        } else {
+            x.to_lowercase().cmp(&y.to_lowercase())
+        };
+        if descending { ord.reverse() } else { ord }
+    });
+    order
+}
+
 impl Default for InteractiveState {
     fn default() -> Self {
         Self::new()
     }
 }
 
+/// Rendered lines of a paragraph without images: one, or a line per term and
+/// definition for a definition list.
+fn paragraph_lines(content: &str) -> usize {
+    crate::parser::definition_list::split(content).map_or(1, |items| {
+        crate::parser::definition_list::line_count(&items)
+    })
+}
+
 /// Count lines for nested blocks
 fn count_block_lines(
     blocks: &[Block],
@@ -1239,14 +1375,14 @@ fn count_single_block_lines(
 ) -> usize {
     match block {
         Block::Heading { .. } => 1,
-        Block::Paragraph { inline, .. } => {
+        Block::Paragraph { content, inline } => {
             let has_image = inline
                 .iter()
                 .any(|e| matches!(e, InlineElement::Image { .. }));
             if has_image {
                 PARAGRAPH_WITH_IMAGE_TOTAL_LINES
             } else {
-                1
+                paragraph_lines(content)
             }
         }
         Block::Code {
@@ -1432,4 +1568,61 @@ fn main() {}
             "toggle_details should flip the freshly-inserted Details state"
         );
     }
+
+    fn rows(cells: &[&str]) -> Vec<Vec<String>> {
+        cells
+            .iter()
+            .map(|c| vec!["x".to_string(), c.to_string()])
+            .collect()
+    }
+
+    #[test]
+    fn sort_order_is_numeric_only_when_every_cell_is_a_number() {
+        let numbers = rows(&["10", "9", "", "-1.5"]);
+        assert_eq!(sort_order(&numbers, 1, false), [3, 1, 0, 2]);
+        assert_eq!(
+            sort_order(&numbers, 1, true),
+            [0, 1, 3, 2],
+            "empty stays last"
+        );
+
+        let words = rows(&["10", "banana", "Apple"]);
+        assert_eq!(sort_order(&words, 1, false), [0, 2, 1]);
+        // Missing column counts as empty
+        assert_eq!(sort_order(&words, 5, false), [0, 1, 2]);
+    }
+
+    #[test]
+    fn sorting_the_selected_column_toggles_direction() {
+        let mut state = InteractiveState::new();
+        let table_md = "| n | v |\n|---|---|\n| a | 2 |\n| b | 10 |\n| c | 1 |\n";
+        state.index_elements(
+            &parse_content(table_md, 0),
+            &std::collections::HashMap::new(),
+        );
+        assert_eq!(
+            state.sort_table_by_selected_column(),
+            None,
+            "not in table mode"
+        );
+
+        state.current_index = Some(0);
+        state.enter_table_mode().unwrap();
+        state.table_move_right(2);
+        let id = state.elements[0].id;
+        let data = vec![
+            vec!["a".to_string(), "2".to_string()],
+            vec!["b".to_string(), "10".to_string()],
+            vec!["c".to_string(), "1".to_string()],
+        ];
+
+        let sort = state.sort_table_by_selected_column().unwrap();
+        assert_eq!((sort.col, sort.descending), (1, false));
+        assert_eq!(state.table_row_order(id, &data), [2, 0, 1]);
+        assert!(state.sort_table_by_selected_column().unwrap().descending);
+        assert_eq!(state.displayed_table_rows(id, &data)[0][0], "b");
+
+        state.exit_table_mode();
+        assert_eq!(state.table_row_order(id, &data), [0, 1, 2]);
+    }
 }
@@ -0,0 +1,129 @@
+//! Vim-style jump list for positions within the current file.
+//!
+//! Big jumps (`g`, `G`, `10G`, heading jumps, an accepted outline search, a
+//! followed `#anchor` link) record the heading they left; `Ctrl+o` walks back
+//! through those headings and `Ctrl+i` forward again. Entries are document
+//! heading indices, so they stay valid while the outline is filtered or
+//! collapsed. File history (`b`/`F`) is separate.
+
+/// Most positions kept; the oldest is dropped first.
+pub const JUMP_LIST_CAPACITY: usize = 100;
+
+/// Positions left by jumps, oldest first, with a cursor for `Ctrl+o`/`Ctrl+i`.
+#[derive(Debug, Clone, Default)]
+pub struct JumpList {
+    entries: Vec<usize>,
+    /// Index of the entry last jumped to; `entries.len()` when not walking
+    pos: usize,
+}
+
+impl JumpList {
+    /// Record `position` as the place a jump is leaving. Anything ahead of
+    /// the cursor (from earlier `Ctrl+o`) is dropped.
+    pub fn record(&mut self, position: usize) {
+        self.entries.truncate(self.pos);
+        self.push(position);
+    }
+
+    /// Position to go back to from `current`, if any. The first step back
+    /// remembers `current` so `Ctrl+i` can return to it.
+    pub fn back(&mut self, current: usize) -> Option<usize> {
+        if self.pos >= self.entries.len() {
+            if self.entries.last() != Some(&current) {
+                self.push(current);
+            }
+            self.pos = self.entries.len() - 1;
+        }
+        if self.pos == 0 {
+            return None;
+        }
+        self.pos -= 1;
+        Some(self.entries[self.pos])
+    }
+
+    /// Position to go forward to after [`back`](Self::back), if any.
+    pub fn forward(&mut self) -> Option<usize> {
+        if self.pos + 1 >= self.entries.len() {
+            return None;
+        }
+        self.pos += 1;
+        Some(self.entries[self.pos])
+    }
+
+    /// Forget every position (the document changed).
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.pos = 0;
+    }
+
+    fn push(&mut self, position: usize) {
+        if self.entries.last() != Some(&position) {
+            self.entries.push(position);
+            if self.entries.len() > JUMP_LIST_CAPACITY {
+                self.entries.remove(0);
+            }
+        }
+        self.pos = self.entries.len();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn back_returns_to_prior_positions_in_order() {
+        let mut jumps = JumpList::default();
+        // 0 -> 5 -> 9 -> 2
+        jumps.record(0);
+        jumps.record(5);
+        jumps.record(9);
+        assert_eq!(jumps.back(2), Some(9));
+        assert_eq!(jumps.back(9), Some(5));
+        assert_eq!(jumps.back(5), Some(0));
+        assert_eq!(jumps.back(0), None);
+    }
+
+    #[test]
+    fn forward_retraces_back_steps() {
+        let mut jumps = JumpList::default();
+        jumps.record(0);
+        jumps.record(5);
+        assert_eq!(jumps.forward(), None);
+        assert_eq!(jumps.back(7), Some(5));
+        assert_eq!(jumps.back(5), Some(0));
+        assert_eq!(jumps.forward(), Some(5));
+        assert_eq!(jumps.forward(), Some(7));
+        assert_eq!(jumps.forward(), None);
+    }
+
+    #[test]
+    fn a_new_jump_drops_positions_ahead_of_the_cursor() {
+        let mut jumps = JumpList::default();
+        jumps.record(0);
+        jumps.record(5);
+        assert_eq!(jumps.back(7), Some(5));
+        jumps.record(5);
+        assert_eq!(jumps.forward(), None);
+        assert_eq!(jumps.back(3), Some(5));
+        assert_eq!(jumps.back(5), Some(0));
+    }
+
+    #[test]
+    fn repeated_positions_are_kept_once_and_the_list_is_capped() {
+        let mut jumps = JumpList::default();
+        jumps.record(4);
+        jumps.record(4);
+        assert_eq!(jumps.back(4), None);
+
+        let mut jumps = JumpList::default();
+        for i in 0..JUMP_LIST_CAPACITY + 20 {
+            jumps.record(i);
+        }
+        let mut oldest = None;
+        while let Some(i) = jumps.back(usize::MAX) {
+            oldest = Some(i);
+        }
+        assert_eq!(oldest, Some(21));
+    }
+}
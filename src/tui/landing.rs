@@ -0,0 +1,113 @@
+//! Brief highlight of the section a followed link landed on.
+//!
+//! After jumping to an anchor (in the current document or another file) the
+//! selected heading flashes with a themed background that fades out over
+//! `[ui] landing_flash_ms`, so the eye can find where navigation ended up.
+
+use ratatui::style::Color;
+use std::time::{Duration, Instant};
+
+/// Timestamp of the last navigation target and how long it stays highlighted.
+#[derive(Debug, Clone)]
+pub struct LandingFlash {
+    duration: Duration,
+    started: Option<Instant>,
+}
+
+impl LandingFlash {
+    /// A flash lasting `duration`; a zero duration disables it.
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration,
+            started: None,
+        }
+    }
+
+    /// Start (or restart) the flash at `now`.
+    pub fn start(&mut self, now: Instant) {
+        if !self.duration.is_zero() {
+            self.started = Some(now);
+        }
+    }
+
+    /// Highlight strength at `now`: 1.0 when the flash starts, falling to 0.0
+    /// as it ends. `None` once it has finished (or never started).
+    pub fn strength(&self, now: Instant) -> Option<f32> {
+        let elapsed = now.saturating_duration_since(self.started?);
+        if elapsed >= self.duration {
+            return None;
+        }
+        Some(1.0 - elapsed.as_secs_f32() / self.duration.as_secs_f32())
+    }
+
+    /// Whether the flash is still showing at `now`.
+    pub fn is_active(&self, now: Instant) -> bool {
+        self.strength(now).is_some()
+    }
+
+    /// Forget a finished flash. Returns true if it just ended, so the caller
+    /// can redraw once more without the highlight.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        if self.started.is_some() && !self.is_active(now) {
+            self.started = None;
+            return true;
+        }
+        false
+    }
+}
+
+/// Blend `from` into `to`: `strength` 1.0 is `from`, 0.0 is `to`.
+///
+/// Only RGB colors can be mixed; other colors switch over halfway.
+pub fn fade(from: Color, to: Color, strength: f32) -> Color {
+    let strength = strength.clamp(0.0, 1.0);
+    match (from, to) {
+        (Color::Rgb(r1, g1, b1), Color::Rgb(r2, g2, b2)) => {
+            let mix = |a: u8, b: u8| (b as f32 + (a as f32 - b as f32) * strength).round() as u8;
+            Color::Rgb(mix(r1, r2), mix(g1, g2), mix(b1, b2))
+        }
+        _ if strength >= 0.5 => from,
+        _ => to,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flash_is_active_until_duration_elapses() {
+        let start = Instant::now();
+        let mut flash = LandingFlash::new(Duration::from_millis(800));
+        assert!(!flash.is_active(start));
+
+        flash.start(start);
+        assert_eq!(flash.strength(start), Some(1.0));
+        let halfway = flash.strength(start + Duration::from_millis(400)).unwrap();
+        assert!((halfway - 0.5).abs() < 1e-3);
+        assert!(flash.is_active(start + Duration::from_millis(799)));
+        assert!(!flash.is_active(start + Duration::from_millis(800)));
+
+        assert!(!flash.expire(start + Duration::from_millis(500)));
+        assert!(flash.expire(start + Duration::from_millis(900)));
+        assert!(!flash.expire(start + Duration::from_millis(1000)));
+    }
+
+    #[test]
+    fn zero_duration_disables_flash() {
+        let now = Instant::now();
+        let mut flash = LandingFlash::new(Duration::ZERO);
+        flash.start(now);
+        assert!(!flash.is_active(now));
+    }
+
+    #[test]
+    fn fade_blends_rgb_colors() {
+        let from = Color::Rgb(200, 100, 0);
+        let to = Color::Rgb(0, 0, 0);
+        assert_eq!(fade(from, to, 1.0), from);
+        assert_eq!(fade(from, to, 0.5), Color::Rgb(100, 50, 0));
+        assert_eq!(fade(from, to, 0.0), to);
+        assert_eq!(fade(Color::Yellow, to, 0.2), to);
+    }
+}
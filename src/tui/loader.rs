@@ -0,0 +1,202 @@
+//! Parsing large documents off the UI thread.
+//!
+//! Files of [`BACKGROUND_LOAD_BYTES`] or more are parsed on a worker thread
+//! while the TUI keeps drawing, with a spinner in the title bar; the parsed
+//! document is swapped in once it arrives. Smaller files parse faster than a
+//! frame, so they stay synchronous.
+
+use crate::input::{InputSource, InputStream, process_input};
+use crate::parser::{Document, parse_markdown};
+use std::path::PathBuf;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
+
+/// Documents at least this large (in bytes) are parsed in the background.
+pub const BACKGROUND_LOAD_BYTES: usize = 1024 * 1024;
+
+/// Minimum time between re-parses of streamed input.
+pub const STREAM_REPARSE: Duration = Duration::from_millis(250);
+
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+const SPINNER_STEP: Duration = Duration::from_millis(80);
+
+/// A document being parsed on a worker thread.
+///
+/// Dropping it abandons the parse: the worker finishes on its own and its
+/// result is discarded, so quitting mid-load never waits for it.
+pub struct BackgroundLoad {
+    pub filename: String,
+    pub path: PathBuf,
+    /// Heading to jump to once loaded
+    pub anchor: Option<String>,
+    started: Instant,
+    receiver: Receiver<Document>,
+}
+
+impl BackgroundLoad {
+    /// Start parsing `content` (already normalized) on a worker thread.
+    pub fn spawn(content: String, filename: String, path: PathBuf, anchor: Option<String>) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::spawn(move || {
+            let _ = sender.send(parse_markdown(&content));
+        });
+        Self {
+            filename,
+            path,
+            anchor,
+            started: Instant::now(),
+            receiver,
+        }
+    }
+
+    /// The parsed document if it is ready. An error means the worker died
+    /// without producing one.
+    pub fn try_take(&self) -> Result<Option<Document>, String> {
+        match self.receiver.try_recv() {
+            Ok(document) => Ok(Some(document)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err(format!("Failed to parse {}", self.filename)),
+        }
+    }
+
+    /// Spinner character for the current moment.
+    pub fn spinner(&self, now: Instant) -> char {
+        spinner_frame(now.saturating_duration_since(self.started))
+    }
+}
+
+/// Stdin being read while the TUI is up.
+pub struct StdinLoad {
+    stream: InputStream,
+    received: String,
+    /// Length of `received` when it was last parsed
+    parsed_len: usize,
+    parsed_at: Instant,
+    started: Instant,
+}
+
+/// What [`StdinLoad::poll`] found.
+pub enum StdinUpdate {
+    /// Nothing new worth re-parsing yet
+    Waiting,
+    /// The input so far, processed and ready to parse; `finished` once the
+    /// stream has closed
+    Content { content: String, finished: bool },
+    /// Reading failed (or the input was empty); the stream is over
+    Failed(String),
+}
+
+impl StdinLoad {
+    pub fn new(stream: InputStream) -> Self {
+        let now = Instant::now();
+        Self {
+            stream,
+            received: String::new(),
+            parsed_len: 0,
+            parsed_at: now,
+            started: now,
+        }
+    }
+
+    /// Take in what has arrived. New content is handed back at most every
+    /// [`STREAM_REPARSE`], and only while it is small enough to parse on the
+    /// UI thread; the complete input always comes back once the stream ends.
+    pub fn poll(&mut self, now: Instant) -> StdinUpdate {
+        let finished = match self.stream.read_available(&mut self.received) {
+            Ok(finished) => finished,
+            Err(e) => return StdinUpdate::Failed(e.to_string()),
+        };
+        if finished && self.received.is_empty() {
+            return StdinUpdate::Failed(crate::input::InputError::EmptyInput.to_string());
+        }
+        let due = self.received.len() > self.parsed_len
+            && self.received.len() < BACKGROUND_LOAD_BYTES
+            && now.saturating_duration_since(self.parsed_at) >= STREAM_REPARSE;
+        if !finished && !due {
+            return StdinUpdate::Waiting;
+        }
+        self.parsed_len = self.received.len();
+        self.parsed_at = now;
+        match process_input(InputSource::Stdin(self.received.clone())) {
+            Ok(content) => StdinUpdate::Content { content, finished },
+            Err(e) => StdinUpdate::Failed(e.to_string()),
+        }
+    }
+
+    /// Spinner character for the current moment.
+    pub fn spinner(&self, now: Instant) -> char {
+        spinner_frame(now.saturating_duration_since(self.started))
+    }
+}
+
+/// Spinner character `elapsed` into a load.
+fn spinner_frame(elapsed: Duration) -> char {
+    let step = (elapsed.as_millis() / SPINNER_STEP.as_millis()) as usize;
+    SPINNER[step % SPINNER.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn spinner_advances_and_wraps() {
+        assert_eq!(spinner_frame(Duration::ZERO), '⠋');
+        assert_eq!(spinner_frame(Duration::from_millis(85)), '⠙');
+        assert_eq!(spinner_frame(SPINNER_STEP * 10), '⠋');
+    }
+
+    #[test]
+    fn delivers_the_parsed_document() {
+        let load = BackgroundLoad::spawn(
+            "# One\n\n## Two\n".to_string(),
+            "big.md".to_string(),
+            PathBuf::from("big.md"),
+            None,
+        );
+        let deadline = Instant::now() + Duration::from_secs(10);
+        let document = loop {
+            if let Some(document) = load.try_take().unwrap() {
+                break document;
+            }
+            assert!(Instant::now() < deadline, "parse never finished");
+            std::thread::sleep(Duration::from_millis(5));
+        };
+        assert_eq!(document.headings.len(), 2);
+    }
+
+    #[test]
+    fn stdin_load_hands_back_processed_input_when_the_stream_ends() {
+        let mut load = StdinLoad::new(InputStream::spawn(std::io::Cursor::new("plain text\n")));
+        let deadline = Instant::now() + Duration::from_secs(10);
+        loop {
+            match load.poll(Instant::now()) {
+                StdinUpdate::Content {
+                    content,
+                    finished: true,
+                } => {
+                    assert_eq!(content, "# Input\n\nplain text\n");
+                    break;
+                }
+                StdinUpdate::Failed(e) => panic!("{}", e),
+                _ => {}
+            }
+            assert!(Instant::now() < deadline, "stream never ended");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        let mut empty = StdinLoad::new(InputStream::spawn(std::io::Cursor::new("")));
+        loop {
+            match empty.poll(Instant::now()) {
+                StdinUpdate::Failed(e) => {
+                    assert_eq!(e, "Empty input provided");
+                    break;
+                }
+                StdinUpdate::Content { .. } => panic!("empty input parsed"),
+                StdinUpdate::Waiting => {}
+            }
+            assert!(Instant::now() < deadline, "stream never ended");
+            std::thread::sleep(Duration::from_millis(5));
+        }
+    }
+}
@@ -0,0 +1,84 @@
+//! Vim-style named marks.
+//!
+//! `m` followed by a letter remembers the selected heading under that letter
+//! and `'` followed by the letter jumps back to it. Marks hold heading text
+//! (like the unnamed bookmark) so they survive outline filtering and
+//! collapsing, and last for the session only.
+
+use std::collections::HashMap;
+
+/// What the key after `m` or `'` completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarkCommand {
+    Set,
+    Jump,
+}
+
+/// Headings remembered under a letter.
+#[derive(Debug, Clone, Default)]
+pub struct Marks {
+    named: HashMap<char, String>,
+}
+
+impl Marks {
+    /// Whether `c` can name a mark (`a`-`z`, `A`-`Z`).
+    pub fn is_name(c: char) -> bool {
+        c.is_ascii_alphabetic()
+    }
+
+    /// Remember `heading` under `name`, replacing any earlier mark.
+    pub fn set(&mut self, name: char, heading: &str) {
+        self.named.insert(name, heading.to_string());
+    }
+
+    /// Heading remembered under `name`.
+    pub fn get(&self, name: char) -> Option<&str> {
+        self.named.get(&name).map(String::as_str)
+    }
+
+    /// Names of the marks on `heading`, in alphabetical order.
+    pub fn names_for(&self, heading: &str) -> Vec<char> {
+        let mut names: Vec<char> = self
+            .named
+            .iter()
+            .filter(|(_, text)| text.as_str() == heading)
+            .map(|(&name, _)| name)
+            .collect();
+        names.sort_unstable();
+        names
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn set_and_jump_by_name() {
+        let mut marks = Marks::default();
+        marks.set('a', "Install");
+        marks.set('b', "Usage");
+        assert_eq!(marks.get('a'), Some("Install"));
+        assert_eq!(marks.get('b'), Some("Usage"));
+        assert_eq!(marks.get('c'), None);
+        assert_eq!(marks.get('A'), None, "names are case-sensitive");
+    }
+
+    #[test]
+    fn setting_a_name_again_overwrites_it() {
+        let mut marks = Marks::default();
+        marks.set('a', "Install");
+        marks.set('a', "Usage");
+        assert_eq!(marks.get('a'), Some("Usage"));
+        assert!(marks.names_for("Install").is_empty());
+
+        marks.set('c', "Usage");
+        assert_eq!(marks.names_for("Usage"), ['a', 'c']);
+    }
+
+    #[test]
+    fn only_letters_name_marks() {
+        assert!(Marks::is_name('a') && Marks::is_name('Z'));
+        assert!(!Marks::is_name('1') && !Marks::is_name('\'') && !Marks::is_name('é'));
+    }
+}
@@ -1,13 +1,158 @@
 use image::DynamicImage;
-use std::sync::Arc;
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
+
+/// How long mermaid-cli may take over one diagram before it is stopped.
+const MMDC_TIMEOUT: Duration = Duration::from_secs(20);
+
+/// Outcome of asking for a diagram image.
+pub enum Rendered {
+    Image(DynamicImage),
+    /// mermaid-cli is still working on it; ask again after
+    /// [`take_finished_renders`] reports progress
+    Pending,
+    Failed(String),
+}
 
 /// Render mermaid source text to a raster image.
 ///
-/// Pipeline: mermaid source → SVG (via mermaid-rs-renderer) → raster (via resvg)
-pub fn render_mermaid_to_image(source: &str, target_width: u32) -> Result<DynamicImage, String> {
-    let svg = render_to_svg(source)?;
-    rasterize_svg(&svg, target_width)
+/// Pipeline: mermaid source → SVG (via mermaid-rs-renderer) → raster (via resvg).
+/// Diagrams the built-in renderer rejects are handed to mermaid-cli (`mmdc`)
+/// when it is on `PATH`. That runs on a worker thread, so the first call
+/// returns [`Rendered::Pending`]; later calls for the same source and width
+/// return the cached result. Without `mmdc` the original error is returned
+/// and the block stays as highlighted source.
+pub fn render_mermaid_to_image(source: &str, target_width: u32) -> Rendered {
+    match render_to_svg(source).and_then(|svg| rasterize_svg(&svg, target_width)) {
+        Ok(img) => Rendered::Image(img),
+        Err(e) if !mmdc_available() => Rendered::Failed(e),
+        Err(e) => match mmdc_render(source, target_width) {
+            Some(Ok(img)) => Rendered::Image(img),
+            Some(Err(_)) => Rendered::Failed(e),
+            None => Rendered::Pending,
+        },
+    }
+}
+
+/// Whether a mermaid-cli render has finished since the last call, so the
+/// screen should be redrawn to pick it up.
+pub fn take_finished_renders() -> bool {
+    MMDC_FINISHED.swap(false, Ordering::Relaxed)
+}
+
+static MMDC_FINISHED: AtomicBool = AtomicBool::new(false);
+
+/// mermaid-cli results by source hash and width; `None` while rendering.
+type MmdcCache = HashMap<(u64, u32), Option<Result<DynamicImage, String>>>;
+
+fn mmdc_cache() -> &'static Mutex<MmdcCache> {
+    static CACHE: OnceLock<Mutex<MmdcCache>> = OnceLock::new();
+    CACHE.get_or_init(Default::default)
+}
+
+/// Whether `mmdc` is on `PATH`, looked up once.
+fn mmdc_available() -> bool {
+    static AVAILABLE: OnceLock<bool> = OnceLock::new();
+    *AVAILABLE.get_or_init(|| {
+        std::env::var_os("PATH").is_some_and(|paths| {
+            std::env::split_paths(&paths).any(|dir| dir.join("mmdc").is_file())
+        })
+    })
+}
+
+/// The mermaid-cli result for `source` at `target_width`, starting a render
+/// on a worker thread the first time. `None` until it is done.
+fn mmdc_render(source: &str, target_width: u32) -> Option<Result<DynamicImage, String>> {
+    use std::hash::{Hash, Hasher};
+
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    let key = (hasher.finish(), target_width);
+
+    let mut cache = mmdc_cache().lock().unwrap_or_else(|e| e.into_inner());
+    if let Some(entry) = cache.get(&key) {
+        return entry.clone();
+    }
+    cache.insert(key, None);
+    drop(cache);
+
+    let source = source.to_string();
+    std::thread::spawn(move || {
+        let result = render_with_mmdc(&source, target_width);
+        mmdc_cache()
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .insert(key, Some(result));
+        MMDC_FINISHED.store(true, Ordering::Relaxed);
+    });
+    None
+}
+
+/// Render a diagram to PNG with mermaid-cli and load it.
+fn render_with_mmdc(source: &str, target_width: u32) -> Result<DynamicImage, String> {
+    let temp = |suffix: &str| {
+        tempfile::Builder::new()
+            .prefix("treemd-mermaid-")
+            .suffix(suffix)
+            .tempfile()
+            .map_err(|e| format!("mmdc temp file: {e}"))
+    };
+    let input = temp(".mmd")?;
+    let output = temp(".png")?;
+    std::fs::write(input.path(), source).map_err(|e| format!("mmdc input: {e}"))?;
+
+    let mut command = Command::new("mmdc");
+    command
+        .args(mmdc_args(input.path(), output.path(), target_width))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+    match run_with_timeout(&mut command, MMDC_TIMEOUT)? {
+        status if status.success() => {
+            image::open(output.path()).map_err(|e| format!("mmdc output: {e}"))
+        }
+        status => Err(format!("mmdc exited with {status}")),
+    }
+}
+
+/// Run `command`, killing it if it is still going after `timeout`.
+fn run_with_timeout(command: &mut Command, timeout: Duration) -> Result<ExitStatus, String> {
+    let mut child = command
+        .spawn()
+        .map_err(|e| format!("mmdc not available: {e}"))?;
+    let deadline = Instant::now() + timeout;
+    loop {
+        match child.try_wait() {
+            Ok(Some(status)) => return Ok(status),
+            Ok(None) if Instant::now() < deadline => std::thread::sleep(Duration::from_millis(50)),
+            Ok(None) => {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("mmdc timed out after {}s", timeout.as_secs()));
+            }
+            Err(e) => return Err(format!("mmdc: {e}")),
+        }
+    }
+}
+
+/// Arguments for `mmdc` to render `input` to a transparent PNG `width` pixels wide.
+fn mmdc_args(input: &Path, output: &Path, width: u32) -> Vec<OsString> {
+    vec![
+        "--quiet".into(),
+        "--input".into(),
+        input.into(),
+        "--output".into(),
+        output.into(),
+        "--width".into(),
+        width.to_string().into(),
+        "--backgroundColor".into(),
+        "transparent".into(),
+    ]
 }
 
 /// Generate SVG from mermaid diagram source.
@@ -120,12 +265,18 @@ fn rasterize_svg(svg: &str, target_width: u32) -> Result<DynamicImage, String> {
 mod tests {
     use super::*;
 
+    fn image(rendered: Rendered) -> DynamicImage {
+        match rendered {
+            Rendered::Image(img) => img,
+            Rendered::Pending => panic!("render pending"),
+            Rendered::Failed(e) => panic!("render failed: {e}"),
+        }
+    }
+
     #[test]
     fn test_basic_flowchart() {
         let source = "graph TD\n    A --> B";
-        let result = render_mermaid_to_image(source, 400);
-        assert!(result.is_ok(), "flowchart failed: {:?}", result.err());
-        let img = result.unwrap();
+        let img = image(render_mermaid_to_image(source, 400));
         assert!(img.width() > 0);
         assert!(img.height() > 0);
     }
@@ -133,13 +284,7 @@ mod tests {
     #[test]
     fn test_flowchart_keyword() {
         let source = "flowchart TD\n    A[Start] --> B{Decision?}\n    B -->|Yes| C[Ok]\n    B -->|No| D[Fix]";
-        let result = render_mermaid_to_image(source, 800);
-        assert!(
-            result.is_ok(),
-            "flowchart keyword failed: {:?}",
-            result.err()
-        );
-        let img = result.unwrap();
+        let img = image(render_mermaid_to_image(source, 800));
         assert!(img.width() == 800);
         assert!(img.height() > 100);
     }
@@ -153,6 +298,37 @@ mod tests {
         let _ = result;
     }
 
+    #[test]
+    fn slow_commands_are_stopped_at_the_timeout() {
+        let start = Instant::now();
+        let result = run_with_timeout(Command::new("sleep").arg("5"), Duration::from_millis(100));
+        assert!(result.unwrap_err().contains("timed out"));
+        assert!(start.elapsed() < Duration::from_secs(2));
+
+        let status = run_with_timeout(&mut Command::new("true"), Duration::from_secs(5));
+        assert!(status.unwrap().success());
+    }
+
+    #[test]
+    fn test_mmdc_args() {
+        let args = mmdc_args(Path::new("in.mmd"), Path::new("out.png"), 640);
+        let args: Vec<_> = args.iter().map(|a| a.to_string_lossy()).collect();
+        assert_eq!(
+            args,
+            [
+                "--quiet",
+                "--input",
+                "in.mmd",
+                "--output",
+                "out.png",
+                "--width",
+                "640",
+                "--backgroundColor",
+                "transparent"
+            ]
+        );
+    }
+
     #[test]
     fn test_fix_font_families() {
         let input = r#"<text font-family="Inter, "Segoe UI", sans-serif">hello</text>"#;
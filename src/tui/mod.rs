@@ -1,27 +1,42 @@
 mod app;
+mod clipboard;
+mod diagram;
 mod edits;
+mod file_browser;
+mod file_list;
+mod fuzzy;
 mod help_text;
 mod image_cache;
 mod interactive;
+mod jumplist;
 mod kitty_animation;
+mod landing;
+mod loader;
+mod marks;
 #[cfg(all(feature = "mermaid", unix))]
 mod mermaid;
+mod mouse;
+pub mod print;
+mod status;
 mod syntax;
+mod table_export;
 pub mod terminal_compat;
 pub mod theme;
 pub mod tty; // Public module for TTY handling
 mod ui;
+mod view_state;
 mod watcher;
 
 pub use app::{ActionResult, App};
 pub use interactive::InteractiveState;
+pub use loader::BACKGROUND_LOAD_BYTES;
 pub use terminal_compat::{ColorMode, TerminalCapabilities};
 pub use theme::ThemeName;
 
 use crate::keybindings::Action;
 use color_eyre::Result;
 use crossterm::ExecutableCommand;
-use crossterm::event::{KeyCode, MouseEvent, MouseEventKind};
+use crossterm::event::{Event, KeyCode, KeyModifiers};
 use crossterm::terminal::{
     BeginSynchronizedUpdate, EndSynchronizedUpdate, EnterAlternateScreen, LeaveAlternateScreen,
 };
@@ -29,7 +44,7 @@ use opensesame::{Editor, EditorConfig};
 use ratatui::DefaultTerminal;
 use std::io::stdout;
 use std::path::Path;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 /// Suspend the TUI, run an external editor, then restore the TUI.
 ///
@@ -96,8 +111,11 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
         app.enter_file_picker();
     }
 
-    // Create file watcher for live reload
-    let mut file_watcher = watcher::FileWatcher::new().ok();
+    // Create file watcher for live reload (unless turned off)
+    let mut file_watcher = app
+        .watch
+        .then(watcher::FileWatcher::new)
+        .and_then(Result::ok);
     if let Some(ref mut watcher) = file_watcher {
         let _ = watcher.watch(&app.current_file_path);
     }
@@ -133,6 +151,20 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
             needs_redraw = false;
         }
 
+        // Swap in a document that finished parsing in the background
+        if app.poll_pending_load() {
+            needs_redraw = true;
+        }
+        // Pick up streamed stdin as it arrives
+        if app.poll_stdin_load() {
+            needs_redraw = true;
+        }
+        // Show diagrams mermaid-cli finished rendering in the background
+        #[cfg(all(feature = "mermaid", unix))]
+        if mermaid::take_finished_renders() {
+            needs_redraw = true;
+        }
+
         // Update file watcher if the current file changed (e.g., via navigation)
         if app.file_path_changed {
             app.file_path_changed = false;
@@ -168,15 +200,28 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
 
         // Poll for events with dynamic timeout:
         // - When GIF is animating: use time until next frame (for smooth playback)
+        // - While a landing highlight fades: ~30fps so the fade looks smooth
+        // - While a document parses in the background or streams in: spinner speed
         // - Otherwise: 100ms for responsive UI updates
         let poll_timeout = app
             .time_until_next_frame()
-            .unwrap_or(Duration::from_millis(100));
+            .unwrap_or(if app.landing_flash_active() {
+                Duration::from_millis(33)
+            } else if app.is_loading() {
+                Duration::from_millis(80)
+            } else {
+                Duration::from_millis(100)
+            });
         let event_ready = tty::poll_event(poll_timeout)?;
+        if app.is_loading() {
+            // Keep the spinner turning
+            needs_redraw = true;
+        }
 
         // Check the file watcher every iteration, regardless of whether there
         // was a keyboard event. Otherwise a debounced reload could stall
-        // behind a stream of keypresses indefinitely.
+        // behind a stream of keypresses indefinitely. Its messages wait for
+        // whatever the status line is showing, such as the result of a save.
         if app.suppress_file_watch {
             app.suppress_file_watch = false;
             if let Some(ref mut watcher) = file_watcher {
@@ -188,7 +233,7 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
             if app.has_unsaved_changes {
                 // Never clobber buffered edits with an external reload; the
                 // user must save or undo first, then the file can be reloaded.
-                app.status_message = Some(
+                app.queue_status_message(
                     "⚠ File changed on disk — reload skipped to protect unsaved edits (save or undo first)"
                         .to_string(),
                 );
@@ -202,7 +247,7 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
                 // File changed externally - reload with state preservation
                 match app.reload_current_file() {
                     Err(e) => {
-                        app.status_message = Some(format!("✗ Reload failed: {}", e));
+                        app.queue_status_message(format!("✗ Reload failed: {}", e));
                         needs_redraw = true;
                     }
                     // Disk content matches what we have (e.g. the echo of our
@@ -227,13 +272,37 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
                         // Sync previous_selection to prevent update_content_metrics() from resetting scroll
                         app.sync_previous_selection();
 
-                        app.status_message = Some("↻ File reloaded (external change)".to_string());
+                        app.queue_status_message("↻ File reloaded (external change)".to_string());
                         needs_redraw = true;
                     }
                 }
             }
         }
 
+        // Abandon an incomplete chord or count prefix that has waited too
+        // long, whether the loop woke for a key or just timed out. A chord
+        // that is already a complete binding (`g` while `g g` is possible)
+        // runs instead, and a lone count digit jumps to that heading.
+        if app.expire_pending_keys(Instant::now()) {
+            needs_redraw = true;
+            while let Some(action) = app.take_queued_action() {
+                if run_action(terminal, &mut app, action)? {
+                    return Ok(());
+                }
+            }
+        }
+
+        // Time out the status message and bring in the next queued one
+        if app.tick_status(Instant::now()) {
+            needs_redraw = true;
+        }
+
+        // Keep repainting while the landing highlight fades, plus once after
+        // it ends to clear it.
+        if app.landing_flash_active() || app.expire_landing_flash(Instant::now()) {
+            needs_redraw = true;
+        }
+
         if !event_ready {
             // GIF animation needs redraw on timeout
             if app.is_image_modal_open() && app.image_modal.gif_frames.len() > 1 {
@@ -247,9 +316,15 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
 
         let event = tty::read_event()?;
 
-        // Mouse handling — keep simple: scroll wheel scrolls content / outline.
+        // Re-fit scroll offsets to the new size; the redraw follows at once
+        if let Event::Resize(width, height) = event {
+            app.handle_resize(width, height);
+            continue;
+        }
+
+        // Clicks select headings and follow links; the wheel scrolls a pane
         if let Some(mouse) = event.as_mouse_event() {
-            handle_mouse(&mut app, mouse);
+            mouse::handle(&mut app, mouse);
             continue;
         }
 
@@ -276,13 +351,18 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
                 continue;
             }
 
-            // A sticky message (error set without a timestamp) stays visible
-            // until the user presses a key; dismiss it now. Track the message
-            // state so anything set directly during this keypress is marked
-            // sticky for the next one.
-            app.dismiss_sticky_status_message();
-            let prev_status = app.status_message.clone();
-            let prev_status_time = app.status_message_time;
+            // The key after `m` or `'` names the mark
+            if app.pending_mark.is_some() {
+                match key.code {
+                    KeyCode::Esc => app.cancel_pending_mark(),
+                    KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                        app.finish_mark(Some(c))
+                    }
+                    _ => app.finish_mark(None),
+                }
+                app.record_key_for_sequence(Instant::now());
+                continue;
+            }
 
             // Handle text input modes separately - these need raw character input
             let handled = handle_text_input(&mut app, key.code, key.modifiers);
@@ -312,100 +392,78 @@ pub fn run(terminal: &mut DefaultTerminal, app: App) -> Result<()> {
                 if !digit_handled {
                     // Try to get an action from the keybinding system
                     if let Some(action) = app.get_action_for_key(key.code, key.modifiers) {
-                        {
-                            // CommandPalette confirm routes through the palette
-                            // executor; everything else through the action dispatch.
-                            // Both produce an ActionResult handled uniformly below.
-                            let result = if action == Action::ConfirmAction
-                                && app.mode == app::AppMode::CommandPalette
-                            {
-                                app.execute_selected_command()
-                            } else {
-                                app.execute_action(action)
-                            };
-                            match result {
-                                ActionResult::Quit => return Ok(()),
-                                ActionResult::RunEditor(path, line) => {
-                                    let editor_config = app.editor_config();
-                                    match run_editor(
-                                        terminal,
-                                        &path,
-                                        line,
-                                        &editor_config,
-                                        app.mouse_capture,
-                                    ) {
-                                        Ok(_) => {
-                                            // The user deliberately edited the file externally,
-                                            // so the on-disk version wins over any buffered edits.
-                                            let had_pending = app.has_unsaved_changes;
-                                            match app.reload_current_file() {
-                                                Err(e) => {
-                                                    app.status_message =
-                                                        Some(format!("✗ Failed to reload: {}", e));
-                                                }
-                                                Ok(reloaded) => {
-                                                    if reloaded && had_pending {
-                                                        app.discard_pending_edits();
-                                                        app.status_message = Some(
-                                                            "✓ File reloaded after editing (buffered edits discarded)"
-                                                                .to_string(),
-                                                        );
-                                                    } else if reloaded {
-                                                        app.status_message = Some(
-                                                            "✓ File reloaded after editing"
-                                                                .to_string(),
-                                                        );
-                                                    }
-                                                }
-                                            }
-                                            app.update_content_metrics();
-                                        }
-                                        Err(e) => {
-                                            app.status_message =
-                                                Some(format!("✗ Editor failed: {}", e));
-                                        }
-                                    }
-                                }
-                                ActionResult::Redraw => {
-                                    terminal.clear()?;
-                                }
-                                ActionResult::Continue => {}
+                        if run_action(terminal, &mut app, action)? {
+                            return Ok(());
+                        }
+                        while let Some(action) = app.take_queued_action() {
+                            if run_action(terminal, &mut app, action)? {
+                                return Ok(());
                             }
                         }
-                    } else {
-                        // No action found - clear count prefix (invalid key cancels count)
+                    } else if !app.in_key_sequence() {
+                        // No action found - clear count prefix (invalid key cancels
+                        // count). A chord still in progress keeps it (`2zl`).
                         app.clear_count();
                     }
                 }
             }
 
-            // Anything assigned directly to status_message during this
-            // keypress (without refreshing the timestamp) is treated as
-            // sticky: it stays visible until the next keypress.
-            if app.status_message != prev_status && app.status_message_time == prev_status_time {
-                app.status_message_time = None;
-            }
+            app.record_key_for_sequence(Instant::now());
         }
     }
 }
 
-/// Handle a mouse event. Currently scroll-only — pointer-click routing into
-/// outline/content panes would require knowing the laid-out rects, which the
-/// renderer hasn't published yet.
-fn handle_mouse(app: &mut App, mouse: MouseEvent) {
-    use crate::keybindings::Action;
-
-    // Scroll wheel maps to existing actions so keybindings stay authoritative.
-    let action = match mouse.kind {
-        MouseEventKind::ScrollDown => Some(Action::Next),
-        MouseEventKind::ScrollUp => Some(Action::Previous),
-        _ => None,
+/// Run `action` and handle its result. Returns true if the app should quit.
+fn run_action(terminal: &mut DefaultTerminal, app: &mut App, action: Action) -> Result<bool> {
+    // CommandPalette confirm routes through the palette executor; everything
+    // else through the action dispatch. Both produce an ActionResult.
+    let result = if action == Action::ConfirmAction && app.mode == app::AppMode::CommandPalette {
+        app.execute_selected_command()
+    } else {
+        app.execute_action(action)
     };
-
-    if let Some(a) = action {
-        // Bypass quit/editor/redraw return paths — scroll never produces them.
-        let _ = app.execute_action(a);
+    match result {
+        ActionResult::Quit => {
+            app.remember_position();
+            return Ok(true);
+        }
+        ActionResult::RunEditor(path, line) => {
+            let editor_config = app.editor_config();
+            match run_editor(terminal, &path, line, &editor_config, app.mouse_capture) {
+                Ok(_) => {
+                    // The user deliberately edited the file externally,
+                    // so the on-disk version wins over any buffered edits.
+                    let had_pending = app.has_unsaved_changes;
+                    match app.reload_current_file() {
+                        Err(e) => {
+                            app.status_message = Some(format!("✗ Failed to reload: {}", e));
+                        }
+                        Ok(reloaded) => {
+                            if reloaded && had_pending {
+                                app.discard_pending_edits();
+                                app.status_message = Some(
+                                    "✓ File reloaded after editing (buffered edits discarded)"
+                                        .to_string(),
+                                );
+                            } else if reloaded {
+                                app.status_message =
+                                    Some("✓ File reloaded after editing".to_string());
+                            }
+                        }
+                    }
+                    app.update_content_metrics();
+                }
+                Err(e) => {
+                    app.status_message = Some(format!("✗ Editor failed: {}", e));
+                }
+            }
+        }
+        ActionResult::Redraw => {
+            terminal.clear()?;
+        }
+        ActionResult::Continue => {}
     }
+    Ok(false)
 }
 
 /// Handle text input for search/edit modes.
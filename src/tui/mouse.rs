@@ -0,0 +1,128 @@
+//! Mouse input: hit-testing clicks and wheel events against the panes laid
+//! out in the last frame.
+
+use crate::tui::app::{App, Focus};
+use crossterm::event::{MouseButton, MouseEvent, MouseEventKind};
+use ratatui::layout::{Position, Rect};
+use std::ops::Range;
+
+/// Where the panes were drawn in the last frame.
+#[derive(Debug, Clone, Default)]
+pub struct PaneAreas {
+    /// Outline pane, borders included (`None` while hidden)
+    pub outline: Option<Rect>,
+    /// Content pane, borders included
+    pub content: Rect,
+    /// Content text inside the borders and line-number gutter
+    pub content_text: Rect,
+    /// Links drawn in `content_text`, one entry per row a link covers
+    pub content_links: Vec<DrawnLink>,
+}
+
+/// Where a link was drawn on screen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DrawnLink {
+    pub row: u16,
+    pub columns: Range<u16>,
+    /// Link text as drawn
+    pub text: String,
+    /// How many links with the same text were drawn before this one
+    pub occurrence: usize,
+}
+
+impl PaneAreas {
+    /// The pane under a screen position.
+    pub fn pane_at(&self, column: u16, row: u16) -> Option<Focus> {
+        let position = Position::new(column, row);
+        if self.outline.is_some_and(|area| area.contains(position)) {
+            Some(Focus::Outline)
+        } else if self.content.contains(position) {
+            Some(Focus::Content)
+        } else {
+            None
+        }
+    }
+}
+
+/// Handle a mouse event: the wheel scrolls the pane under the pointer, a
+/// left click selects the outline heading or follows the link under it.
+pub fn handle(app: &mut App, mouse: MouseEvent) {
+    match mouse.kind {
+        MouseEventKind::ScrollDown => app.mouse_scroll(mouse.column, mouse.row, true),
+        MouseEventKind::ScrollUp => app.mouse_scroll(mouse.column, mouse.row, false),
+        MouseEventKind::Down(MouseButton::Left) => app.mouse_click(mouse.column, mouse.row),
+        _ => {}
+    }
+}
+
+/// Outline row under a screen position, for a list drawn in `area` (with
+/// borders) whose first visible row is `offset`.
+pub fn outline_row(area: Rect, offset: usize, column: u16, row: u16) -> Option<usize> {
+    let inner = area.inner(ratatui::layout::Margin {
+        vertical: 1,
+        horizontal: 1,
+    });
+    inner
+        .contains(Position::new(column, row))
+        .then(|| offset + (row - inner.y) as usize)
+}
+
+/// Which of `texts` belongs to the link drawn at a screen position: the
+/// one with the same text and as many same-text links before it.
+pub fn link_at(drawn: &[DrawnLink], column: u16, row: u16, texts: &[&str]) -> Option<usize> {
+    let link = drawn
+        .iter()
+        .find(|link| link.row == row && link.columns.contains(&column))?;
+    texts
+        .iter()
+        .enumerate()
+        .filter(|(_, text)| **text == link.text)
+        .nth(link.occurrence)
+        .map(|(idx, _)| idx)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outline_rows_skip_the_border_and_follow_the_offset() {
+        let area = Rect::new(0, 2, 20, 10);
+        assert_eq!(outline_row(area, 0, 5, 3), Some(0));
+        assert_eq!(outline_row(area, 7, 5, 5), Some(9));
+        assert_eq!(outline_row(area, 0, 5, 2), None); // top border
+        assert_eq!(outline_row(area, 0, 0, 4), None); // left border
+        assert_eq!(outline_row(area, 0, 25, 4), None); // other pane
+    }
+
+    #[test]
+    fn links_are_found_by_where_they_were_drawn() {
+        // "See docs, [docs](a) and [docs](b)": the plain "docs" isn't drawn
+        // as a link, and the second link is the second "docs" link
+        let drawn = |columns, occurrence| DrawnLink {
+            row: 3,
+            columns,
+            text: "docs".to_string(),
+            occurrence,
+        };
+        let drawn = [drawn(10..14, 0), drawn(19..23, 1)];
+        let texts = ["intro", "docs", "docs"];
+        assert_eq!(link_at(&drawn, 4, 3, &texts), None);
+        assert_eq!(link_at(&drawn, 10, 3, &texts), Some(1));
+        assert_eq!(link_at(&drawn, 22, 3, &texts), Some(2));
+        assert_eq!(link_at(&drawn, 23, 3, &texts), None);
+        assert_eq!(link_at(&drawn, 12, 4, &texts), None);
+    }
+
+    #[test]
+    fn pane_at_prefers_the_outline() {
+        let panes = PaneAreas {
+            outline: Some(Rect::new(0, 0, 20, 10)),
+            content: Rect::new(20, 0, 60, 10),
+            ..Default::default()
+        };
+        assert_eq!(panes.pane_at(5, 5), Some(Focus::Outline));
+        assert_eq!(panes.pane_at(30, 5), Some(Focus::Content));
+        assert_eq!(panes.pane_at(30, 12), None);
+    }
+}
@@ -0,0 +1,102 @@
+//! Rendering a document to text for stdout (`--print`), outside the TUI.
+
+use crate::Config;
+use crate::tui::syntax::SyntaxHighlighter;
+use crate::tui::terminal_compat::ColorMode;
+use crate::tui::theme::Theme;
+use crate::tui::ui;
+use ratatui::backend::IntoCrossterm;
+use ratatui::style::Style;
+use ratatui::text::Text;
+use std::fmt::Write;
+
+/// `content` rendered as the content pane shows it, for a terminal `width`
+/// columns wide. With a color mode the text is styled with ANSI escapes in
+/// the configured theme; without one it is plain.
+pub fn render(content: &str, config: &Config, color: Option<ColorMode>, width: u16) -> String {
+    let theme = Theme::from_config(config, config.theme_name(), color.unwrap_or(ColorMode::Rgb));
+    let highlighter = SyntaxHighlighter::new(&config.ui.code_theme, config.code_theme_dir_path())
+        .with_languages(&config.syntax);
+    let text = ui::render_document(content, config, &theme, &highlighter, width);
+    to_string(&text, color.is_some())
+}
+
+/// `text` as lines of output, styled with ANSI escapes when `styled`.
+/// Plain lines lose the trailing padding that fills code blocks and tables.
+/// Runs of empty lines (such as the room kept for images) print as one.
+fn to_string(text: &Text, styled: bool) -> String {
+    let mut out = String::new();
+    let mut after_empty = false;
+    for line in &text.lines {
+        let empty = line.width() == 0;
+        if empty && after_empty {
+            continue;
+        }
+        after_empty = empty;
+        if styled {
+            for span in &line.spans {
+                let style: Style = text.style.patch(line.style).patch(span.style);
+                let _ = write!(out, "{}", style.into_crossterm().apply(&span.content));
+            }
+        } else {
+            let plain: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            out.push_str(plain.trim_end());
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::style::{Color, Modifier};
+    use ratatui::text::{Line, Span};
+
+    #[test]
+    fn plain_output_has_no_escapes() {
+        let config = Config::default();
+        let out = render(
+            "# Title\n\nSome **bold** text.\n\n```rust\nlet x = 1;\n```\n",
+            &config,
+            None,
+            80,
+        );
+        assert!(!out.contains('\x1b'));
+        assert!(out.contains("Title"));
+        assert!(out.contains("Some bold text."));
+        assert!(out.contains("let x = 1;"));
+        assert!(out.lines().all(|line| line == line.trim_end()));
+    }
+
+    #[test]
+    fn styled_output_uses_ansi_escapes() {
+        let text = Text::from(Line::from(vec![
+            Span::raw("plain "),
+            Span::styled(
+                "bold",
+                Style::default()
+                    .fg(Color::Rgb(1, 2, 3))
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]));
+        let out = to_string(&text, true);
+        assert!(out.starts_with("plain "));
+        assert!(out.contains("\x1b[38;2;1;2;3m"));
+        assert!(out.contains("\x1b[1m"));
+        assert_eq!(to_string(&text, false), "plain bold\n");
+    }
+
+    #[test]
+    fn empty_lines_are_squeezed() {
+        let text = Text::from(vec![
+            Line::from("a"),
+            Line::default(),
+            Line::from(""),
+            Line::default(),
+            Line::from("  "),
+            Line::from("b"),
+        ]);
+        assert_eq!(to_string(&text, false), "a\n\n\nb\n");
+    }
+}
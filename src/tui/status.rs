@@ -0,0 +1,150 @@
+//! Timing for status line messages.
+//!
+//! A message stays for `[ui] status_timeout_ms`, errors (`✗`) and warnings
+//! (`⚠`) half as long again, then the next queued message takes its place.
+//! Messages are mostly set by assigning `App::status_message`; the queue
+//! notices a new one on its next tick and starts its clock then.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// Messages waiting their turn, and when the shown one appeared.
+#[derive(Debug, Clone)]
+pub struct StatusQueue {
+    timeout: Duration,
+    /// The message being timed and when it was first shown
+    shown: Option<(String, Instant)>,
+    pending: VecDeque<String>,
+}
+
+impl StatusQueue {
+    /// A queue showing info messages for `timeout`.
+    pub fn new(timeout: Duration) -> Self {
+        Self {
+            timeout,
+            shown: None,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// How long `message` stays: longer for errors and warnings, so there
+    /// is time to read what went wrong.
+    pub fn lifetime(&self, message: &str) -> Duration {
+        if message.starts_with(['✗', '⚠']) {
+            self.timeout * 3 / 2
+        } else {
+            self.timeout
+        }
+    }
+
+    /// Show `message` in `current` if the status line is free, else after
+    /// the messages already waiting. A repeat of the last message is dropped.
+    pub fn push(&mut self, current: &mut Option<String>, message: String, now: Instant) {
+        if self.pending.back().or(current.as_ref()) == Some(&message) {
+            return;
+        }
+        if current.is_none() {
+            self.shown = Some((message.clone(), now));
+            *current = Some(message);
+        } else {
+            self.pending.push_back(message);
+        }
+    }
+
+    /// Start `message`'s time again even if it is already shown.
+    pub fn restart(&mut self, message: &str, now: Instant) {
+        self.shown = Some((message.to_string(), now));
+    }
+
+    /// Bring `current` up to date at `now`: time a newly set message, clear
+    /// one that has been up long enough and show the next waiting one.
+    /// Returns true if the status line changed.
+    pub fn tick(&mut self, current: &mut Option<String>, now: Instant) -> bool {
+        let expired = match (&*current, &self.shown) {
+            (None, _) => true,
+            (Some(message), Some((timed, since))) if message == timed => {
+                now.saturating_duration_since(*since) >= self.lifetime(message)
+            }
+            (Some(message), _) => {
+                self.shown = Some((message.clone(), now));
+                false
+            }
+        };
+        if !expired {
+            return false;
+        }
+
+        let had_message = current.is_some();
+        *current = self.pending.pop_front();
+        self.shown = current.clone().map(|message| (message, now));
+        had_message || current.is_some()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TIMEOUT: Duration = Duration::from_secs(2);
+
+    #[test]
+    fn messages_expire_after_the_timeout() {
+        let mut queue = StatusQueue::new(TIMEOUT);
+        let start = Instant::now();
+        let mut current = Some("✓ Copied".to_string());
+        assert!(!queue.tick(&mut current, start));
+        assert!(!queue.tick(&mut current, start + TIMEOUT / 2));
+        assert!(queue.tick(&mut current, start + TIMEOUT));
+        assert_eq!(current, None);
+        assert!(!queue.tick(&mut current, start + TIMEOUT * 2));
+    }
+
+    #[test]
+    fn errors_stay_longer() {
+        let mut queue = StatusQueue::new(TIMEOUT);
+        let start = Instant::now();
+        let mut current = Some("✗ Failed to load file".to_string());
+        queue.tick(&mut current, start);
+        assert!(!queue.tick(&mut current, start + TIMEOUT));
+        assert!(queue.tick(&mut current, start + TIMEOUT * 3 / 2));
+        assert_eq!(queue.lifetime("⚠ Parent has no links"), TIMEOUT * 3 / 2);
+        assert_eq!(queue.lifetime("Sequence cancelled"), TIMEOUT);
+    }
+
+    #[test]
+    fn a_replaced_message_gets_its_own_time() {
+        let mut queue = StatusQueue::new(TIMEOUT);
+        let start = Instant::now();
+        let mut current = Some("first".to_string());
+        queue.tick(&mut current, start);
+        current = Some("second".to_string());
+        assert!(!queue.tick(&mut current, start + TIMEOUT));
+        assert_eq!(current.as_deref(), Some("second"));
+        assert!(queue.tick(&mut current, start + TIMEOUT * 2));
+
+        current = Some("again".to_string());
+        queue.tick(&mut current, start);
+        queue.restart("again", start + TIMEOUT);
+        assert!(!queue.tick(&mut current, start + TIMEOUT * 3 / 2));
+    }
+
+    #[test]
+    fn queued_messages_show_in_turn() {
+        let mut queue = StatusQueue::new(TIMEOUT);
+        let start = Instant::now();
+        let mut current = None;
+        queue.push(&mut current, "one".to_string(), start);
+        queue.push(&mut current, "two".to_string(), start);
+        queue.push(&mut current, "three".to_string(), start);
+        queue.push(&mut current, "three".to_string(), start);
+        assert_eq!(current.as_deref(), Some("one"));
+
+        assert!(queue.tick(&mut current, start + TIMEOUT));
+        assert_eq!(current.as_deref(), Some("two"));
+        assert!(!queue.tick(&mut current, start + TIMEOUT * 3 / 2));
+        assert!(queue.tick(&mut current, start + TIMEOUT * 2));
+        assert_eq!(current.as_deref(), Some("three"));
+        assert!(queue.tick(&mut current, start + TIMEOUT * 3));
+        assert_eq!(current, None);
+    }
+}
@@ -1,27 +1,40 @@
 use std::cell::RefCell;
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::hash::{Hash, Hasher};
 use std::path::PathBuf;
 
+use indexmap::IndexMap;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span};
 use syntect::easy::HighlightLines;
 use syntect::highlighting::{Theme, ThemeSet};
 use syntect::parsing::SyntaxSet;
 use syntect::util::LinesWithEndings;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::config::{LanguageConfig, SyntaxConfig};
 
 const DEFAULT_CODE_THEME: &str = "base16-ocean.dark";
 
-/// Soft cap on cached entries before the cache resets. Each entry is a small
-/// `Vec<Line>` so 256 covers virtually any document while bounding memory.
+/// Cap on cached code blocks; the least recently used is evicted beyond it.
+/// Each entry is a small `Vec<Line>` so 256 covers virtually any document
+/// while bounding memory.
 const CACHE_LIMIT: usize = 256;
 
 pub struct SyntaxHighlighter {
     syntax_set: SyntaxSet,
+    /// Built-in themes plus any loaded from the code theme directory
+    themes: BTreeMap<String, Theme>,
+    theme_name: String,
     theme: Theme,
-    /// Cached highlight results keyed by `hash((content, language))`.
+    /// Cached highlight results keyed by `hash((content, language))`, least
+    /// recently used first.
     /// `RefCell` because highlight_code takes `&self` and is called from render.
-    cache: RefCell<HashMap<u64, Vec<Line<'static>>>>,
+    cache: RefCell<IndexMap<u64, Vec<Line<'static>>>>,
+    /// Columns between tab stops, unless the language sets its own
+    tab_width: usize,
+    /// `[syntax.per_language]`, keyed by lowercase fence language
+    languages: BTreeMap<String, LanguageConfig>,
 }
 
 impl SyntaxHighlighter {
@@ -45,26 +58,124 @@ impl SyntaxHighlighter {
             }
         }
 
+        let theme_name = if theme_set.themes.contains_key(theme) {
+            theme.to_string()
+        } else {
+            if theme != DEFAULT_CODE_THEME {
+                eprintln!(
+                    "warning: code theme '{}' not found, using '{}'",
+                    theme, DEFAULT_CODE_THEME
+                );
+            }
+            DEFAULT_CODE_THEME.to_string()
+        };
         let theme = theme_set
             .themes
-            .get(theme)
-            .or_else(|| {
-                if theme != DEFAULT_CODE_THEME {
-                    eprintln!(
-                        "warning: code theme '{}' not found, using '{}'",
-                        theme, DEFAULT_CODE_THEME
-                    );
-                }
-                theme_set.themes.get(DEFAULT_CODE_THEME)
-            })
+            .get(&theme_name)
             .cloned()
             .expect("syntect default themes must contain base16-ocean.dark");
 
         Self {
             syntax_set,
+            themes: theme_set.themes,
+            theme_name,
             theme,
-            cache: RefCell::new(HashMap::new()),
+            cache: RefCell::new(IndexMap::new()),
+            tab_width: SyntaxConfig::default().tab_width,
+            languages: BTreeMap::new(),
+        }
+    }
+
+    /// Lay code out with `syntax`'s tab width and per-language settings.
+    pub fn with_languages(mut self, syntax: &SyntaxConfig) -> Self {
+        self.tab_width = syntax.tab_width.max(1);
+        self.languages = syntax
+            .per_language
+            .iter()
+            .map(|(language, config)| (language.to_lowercase(), config.clone()))
+            .collect();
+        self.cache.borrow_mut().clear();
+        self
+    }
+
+    fn language_config(&self, language: &str) -> Option<&LanguageConfig> {
+        self.languages.get(&language.to_lowercase())
+    }
+
+    /// Columns between tab stops in `language` code.
+    pub fn tab_width(&self, language: &str) -> usize {
+        self.language_config(language)
+            .and_then(|config| config.tab_width)
+            .map_or(self.tab_width, |width| width.max(1))
+    }
+
+    /// Lay highlighted `language` code out in a pane `width` columns wide
+    /// whose other lines wrap if `wrap`. A language set to wrap is broken
+    /// into rows even when the pane scrolls sideways. One set not to shows
+    /// `width` columns from `scroll.offset` on, with `‹` / `›` where text
+    /// is cut off, and raises `scroll.max_offset` to how far it can scroll.
+    pub fn fit_to_width(
+        &self,
+        lines: Vec<Line<'static>>,
+        language: &str,
+        wrap: bool,
+        width: usize,
+        scroll: &mut CodeScroll,
+    ) -> Vec<Line<'static>> {
+        let language_wrap = self
+            .language_config(language)
+            .and_then(|config| config.wrap);
+        if width < 3 || language_wrap.is_none_or(|language_wrap| language_wrap == wrap) {
+            return lines;
+        }
+        if wrap {
+            let widest = lines.iter().map(line_width).max().unwrap_or(0);
+            let max_offset = if widest > width {
+                widest - (width - 1)
+            } else {
+                0
+            };
+            scroll.max_offset = scroll.max_offset.max(max_offset);
+            let offset = scroll.offset.min(max_offset);
+            lines
+                .into_iter()
+                .map(|line| clip_line(line, width, offset))
+                .collect()
+        } else {
+            lines
+                .into_iter()
+                .flat_map(|line| split_line(line, width))
+                .collect()
+        }
+    }
+
+    /// Name of the theme code blocks are highlighted with.
+    pub fn theme_name(&self) -> &str {
+        &self.theme_name
+    }
+
+    /// Switch to the next available theme (alphabetically, wrapping around)
+    /// and return its name.
+    pub fn cycle_theme(&mut self) -> &str {
+        let next = self
+            .themes
+            .range::<str, _>((
+                std::ops::Bound::Excluded(self.theme_name.as_str()),
+                std::ops::Bound::Unbounded,
+            ))
+            .next()
+            .or_else(|| self.themes.iter().next());
+        if let Some((name, theme)) = next {
+            self.theme_name = name.clone();
+            self.theme = theme.clone();
+            self.cache.borrow_mut().clear();
         }
+        &self.theme_name
+    }
+
+    /// Drop all cached highlighting, e.g. when a new document is loaded.
+    pub fn clear_cache(&self) {
+        self.cache.borrow_mut().clear();
     }
 
     /// Highlight `code` as `language`. Result is memoized — repeat calls with
@@ -72,13 +183,20 @@ impl SyntaxHighlighter {
     /// re-invoking syntect.
     pub fn highlight_code(&self, code: &str, language: &str) -> Vec<Line<'static>> {
         let key = cache_key(code, language);
+        let tab_width = self.tab_width(language);
 
-        if let Some(cached) = self.cache.borrow().get(&key) {
-            return cached.clone();
+        {
+            let mut cache = self.cache.borrow_mut();
+            if let Some(idx) = cache.get_index_of(&key) {
+                // Most recently used moves to the back
+                let last = cache.len() - 1;
+                cache.move_index(idx, last);
+                return cache[last].clone();
+            }
         }
 
         // Replace tabs with spaces once at cache-miss time, not every render.
-        let code_owned = code.replace('\t', "    ");
+        let code_owned = expand_tabs(code, tab_width);
 
         let syntax = self
             .syntax_set
@@ -126,20 +244,296 @@ impl SyntaxHighlighter {
             lines.push(Line::from(spans));
         }
 
-        // Bounded cache: clear when full. Simpler than LRU and adequate here
-        // because highlighting is the cold path; cache hits dominate.
         let mut cache = self.cache.borrow_mut();
         if cache.len() >= CACHE_LIMIT {
-            cache.clear();
+            cache.shift_remove_index(0);
         }
         cache.insert(key, lines.clone());
         lines
     }
 }
 
+/// `code` with each tab replaced by spaces up to the next multiple of
+/// `tab_width` columns.
+fn expand_tabs(code: &str, tab_width: usize) -> String {
+    let mut expanded = String::with_capacity(code.len());
+    let mut column = 0;
+    for c in code.chars() {
+        match c {
+            '\t' => {
+                let spaces = tab_width - column % tab_width;
+                expanded.extend(std::iter::repeat_n(' ', spaces));
+                column += spaces;
+            }
+            '\n' => {
+                expanded.push(c);
+                column = 0;
+            }
+            _ => {
+                expanded.push(c);
+                column += c.width().unwrap_or(0);
+            }
+        }
+    }
+    expanded
+}
+
+/// Sideways scroll of code blocks left unwrapped in a wrapping pane.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CodeScroll {
+    /// Columns scrolled past on the left
+    pub offset: usize,
+    /// Furthest the widest block laid out so far can scroll
+    pub max_offset: usize,
+}
+
+/// `line` broken into rows at most `width` columns wide.
+fn split_line(line: Line<'static>, width: usize) -> Vec<Line<'static>> {
+    let mut rows = vec![Vec::new()];
+    let mut column = 0;
+    for span in line.spans {
+        let mut text = String::new();
+        for c in span.content.chars().filter(|&c| c != '\n') {
+            let c_width = c.width().unwrap_or(0);
+            if column + c_width > width && column > 0 {
+                if !text.is_empty() {
+                    let row = rows.last_mut().expect("rows start non-empty");
+                    row.push(Span::styled(std::mem::take(&mut text), span.style));
+                }
+                rows.push(Vec::new());
+                column = 0;
+            }
+            text.push(c);
+            column += c_width;
+        }
+        if !text.is_empty() {
+            let row = rows.last_mut().expect("rows start non-empty");
+            row.push(Span::styled(text, span.style));
+        }
+    }
+    rows.into_iter().map(Line::from).collect()
+}
+
+/// Columns `line` takes, not counting a trailing newline.
+fn line_width(line: &Line) -> usize {
+    line.spans
+        .iter()
+        .map(|span| span.content.trim_end_matches('\n').width())
+        .sum()
+}
+
+/// `line` from column `offset` on, cut to `width` columns, with `‹` and `›`
+/// where text was cut off on either side.
+fn clip_line(line: Line<'static>, width: usize, offset: usize) -> Line<'static> {
+    if offset == 0 && line_width(&line) <= width {
+        return line;
+    }
+    let marker = |text| Span::styled(text, Style::default().add_modifier(Modifier::DIM));
+
+    let mut spans = Vec::new();
+    let mut rest = line;
+    let mut room = width;
+    if offset > 0 {
+        spans.push(marker("‹"));
+        let shown: Vec<Span> = split_line(rest, offset)
+            .into_iter()
+            .skip(1)
+            .flat_map(|row| row.spans)
+            .collect();
+        rest = Line::from(shown);
+        room -= 1;
+    }
+    if line_width(&rest) > room {
+        spans.extend(split_line(rest, room - 1).swap_remove(0).spans);
+        spans.push(marker("›"));
+    } else {
+        spans.extend(rest.spans);
+    }
+    Line::from(spans)
+}
+
 fn cache_key(code: &str, language: &str) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     code.hash(&mut hasher);
     language.hash(&mut hasher);
     hasher.finish()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unknown_theme_falls_back_to_default() {
+        let highlighter = SyntaxHighlighter::new("no-such-theme", None);
+        assert_eq!(highlighter.theme_name(), DEFAULT_CODE_THEME);
+    }
+
+    #[test]
+    fn cycling_visits_every_theme_and_wraps() {
+        let mut highlighter = SyntaxHighlighter::new("InspiredGitHub", None);
+        let count = highlighter.themes.len();
+        let mut seen = vec![highlighter.theme_name().to_string()];
+        for _ in 0..count - 1 {
+            seen.push(highlighter.cycle_theme().to_string());
+        }
+        let mut sorted = seen.clone();
+        sorted.sort();
+        sorted.dedup();
+        assert_eq!(sorted.len(), count);
+        assert_eq!(highlighter.cycle_theme(), "InspiredGitHub");
+    }
+
+    #[test]
+    fn identical_blocks_hit_the_cache() {
+        let highlighter = SyntaxHighlighter::new(DEFAULT_CODE_THEME, None);
+        let first = highlighter.highlight_code("let x = 1;\n", "rust");
+        let again = highlighter.highlight_code("let x = 1;\n", "rust");
+        assert_eq!(first, again);
+        assert_eq!(highlighter.cache.borrow().len(), 1);
+
+        // Same source in another language is a separate entry
+        highlighter.highlight_code("let x = 1;\n", "js");
+        assert_eq!(highlighter.cache.borrow().len(), 2);
+
+        highlighter.clear_cache();
+        assert!(highlighter.cache.borrow().is_empty());
+    }
+
+    #[test]
+    fn cache_evicts_the_least_recently_used_block() {
+        let highlighter = SyntaxHighlighter::new(DEFAULT_CODE_THEME, None);
+        for i in 0..CACHE_LIMIT {
+            highlighter.highlight_code(&format!("{i}\n"), "txt");
+        }
+        // Touch the oldest so the second oldest is evicted instead
+        highlighter.highlight_code("0\n", "txt");
+        highlighter.highlight_code("new\n", "txt");
+
+        let cache = highlighter.cache.borrow();
+        assert_eq!(cache.len(), CACHE_LIMIT);
+        assert!(cache.contains_key(&cache_key("0\n", "txt")));
+        assert!(!cache.contains_key(&cache_key("1\n", "txt")));
+    }
+
+    fn plain(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn configured(per_language: &[(&str, LanguageConfig)]) -> SyntaxHighlighter {
+        let syntax = SyntaxConfig {
+            per_language: per_language
+                .iter()
+                .map(|(language, config)| (language.to_string(), config.clone()))
+                .collect(),
+            ..SyntaxConfig::default()
+        };
+        SyntaxHighlighter::new(DEFAULT_CODE_THEME, None).with_languages(&syntax)
+    }
+
+    #[test]
+    fn tabs_expand_to_the_language_tab_width() {
+        let highlighter = configured(&[(
+            "json",
+            LanguageConfig {
+                tab_width: Some(2),
+                ..LanguageConfig::default()
+            },
+        )]);
+        let code = "{\n\t\"a\": 1,\n\t\t\"b\": 2\n}\n";
+        assert_eq!(
+            plain(&highlighter.highlight_code(code, "json")),
+            ["{\n", "  \"a\": 1,\n", "    \"b\": 2\n", "}\n"]
+        );
+        assert_eq!(highlighter.tab_width("JSON"), 2);
+        // Other languages keep the default, and tabs stop at tab stops
+        assert_eq!(
+            plain(&highlighter.highlight_code("ab\tc\n", "txt")),
+            ["ab  c\n"]
+        );
+    }
+
+    #[test]
+    fn languages_can_wrap_apart_from_the_content() {
+        let highlighter = configured(&[
+            (
+                "log",
+                LanguageConfig {
+                    wrap: Some(false),
+                    ..LanguageConfig::default()
+                },
+            ),
+            (
+                "txt",
+                LanguageConfig {
+                    wrap: Some(true),
+                    ..LanguageConfig::default()
+                },
+            ),
+        ]);
+        let long = || vec![Line::from("0123456789")];
+        let mut scroll = CodeScroll::default();
+        assert_eq!(
+            plain(&highlighter.fit_to_width(long(), "log", true, 6, &mut scroll)),
+            ["01234›"]
+        );
+        assert_eq!(scroll.max_offset, 5);
+        assert_eq!(
+            plain(&highlighter.fit_to_width(long(), "txt", false, 4, &mut scroll)),
+            ["0123", "4567", "89"]
+        );
+        // Unconfigured, or matching the content, lines are left alone
+        assert_eq!(
+            plain(&highlighter.fit_to_width(long(), "log", false, 6, &mut scroll)),
+            ["0123456789"]
+        );
+        assert_eq!(
+            plain(&highlighter.fit_to_width(long(), "rust", true, 6, &mut scroll)),
+            ["0123456789"]
+        );
+    }
+
+    #[test]
+    fn unwrapped_languages_scroll_sideways_in_a_wrapping_pane() {
+        let highlighter = configured(&[(
+            "log",
+            LanguageConfig {
+                wrap: Some(false),
+                ..LanguageConfig::default()
+            },
+        )]);
+        let lines = || vec![Line::from("0123456789"), Line::from("ab")];
+        let mut scroll = CodeScroll {
+            offset: 3,
+            max_offset: 0,
+        };
+        assert_eq!(
+            plain(&highlighter.fit_to_width(lines(), "log", true, 6, &mut scroll)),
+            ["‹3456›", "‹"]
+        );
+        // Past the end, the offset stops where the longest line ends
+        scroll.offset = 9;
+        assert_eq!(
+            plain(&highlighter.fit_to_width(lines(), "log", true, 6, &mut scroll)),
+            ["‹56789", "‹"]
+        );
+        assert_eq!(scroll.max_offset, 5);
+    }
+
+    #[test]
+    fn unknown_language_renders_as_plain_text() {
+        let highlighter = SyntaxHighlighter::new(DEFAULT_CODE_THEME, None);
+        let lines = highlighter.highlight_code("fn main() {}\nsecond\n", "no-such-lang");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].spans.len(), 1, "plain text is a single span");
+        assert_eq!(lines[0].spans[0].content, "fn main() {}\n");
+    }
+}
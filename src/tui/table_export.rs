@@ -0,0 +1,141 @@
+//! Serialize a table's cells for the clipboard, as CSV or as markdown with
+//! the columns padded to line up.
+
+use crate::parser::output::Alignment;
+use unicode_width::UnicodeWidthStr;
+
+/// The table as RFC 4180 CSV. Cells containing a comma, quote or line break
+/// are quoted, with inner quotes doubled; rows end in CRLF.
+pub fn to_csv(headers: &[String], rows: &[Vec<String>]) -> String {
+    let mut out = String::new();
+    for row in std::iter::once(headers).chain(rows.iter().map(Vec::as_slice)) {
+        let cells: Vec<String> = row.iter().map(|cell| csv_field(cell)).collect();
+        out.push_str(&cells.join(","));
+        out.push_str("\r\n");
+    }
+    out
+}
+
+fn csv_field(cell: &str) -> String {
+    if cell.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+/// The table as markdown, every column padded to its widest cell and the
+/// delimiter row carrying each column's alignment. Short rows are filled
+/// with empty cells.
+pub fn to_markdown(headers: &[String], alignments: &[Alignment], rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).fold(headers.len(), usize::max);
+    let escape = |row: &[String]| -> Vec<String> {
+        (0..columns)
+            .map(|i| row.get(i).map_or(String::new(), |c| c.replace('|', "\\|")))
+            .collect()
+    };
+    let header = escape(headers);
+    let body: Vec<Vec<String>> = rows.iter().map(|row| escape(row)).collect();
+
+    let widths: Vec<usize> = (0..columns)
+        .map(|i| {
+            std::iter::once(&header)
+                .chain(&body)
+                .map(|row| row[i].width())
+                .fold(3, usize::max)
+        })
+        .collect();
+    let align = |i: usize| alignments.get(i).copied().unwrap_or(Alignment::None);
+
+    let mut out = String::new();
+    let mut push_row = |cells: Vec<String>| {
+        out.push_str("| ");
+        out.push_str(&cells.join(" | "));
+        out.push_str(" |\n");
+    };
+    let pad = |row: &[String]| -> Vec<String> {
+        row.iter()
+            .enumerate()
+            .map(|(i, cell)| pad_cell(cell, widths[i], align(i)))
+            .collect()
+    };
+
+    push_row(pad(&header));
+    push_row(
+        (0..columns)
+            .map(|i| {
+                let w = widths[i];
+                match align(i) {
+                    Alignment::Left => format!(":{}", "-".repeat(w - 1)),
+                    Alignment::Center => format!(":{}:", "-".repeat(w - 2)),
+                    Alignment::Right => format!("{}:", "-".repeat(w - 1)),
+                    Alignment::None => "-".repeat(w),
+                }
+            })
+            .collect(),
+    );
+    for row in &body {
+        push_row(pad(row));
+    }
+    out
+}
+
+fn pad_cell(cell: &str, width: usize, align: Alignment) -> String {
+    let gap = width.saturating_sub(cell.width());
+    let (left, right) = match align {
+        Alignment::Right => (gap, 0),
+        Alignment::Center => (gap / 2, gap - gap / 2),
+        _ => (0, gap),
+    };
+    format!("{}{}{}", " ".repeat(left), cell, " ".repeat(right))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cells(row: &[&str]) -> Vec<String> {
+        row.iter().map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn csv_quotes_only_cells_that_need_it() {
+        let headers = cells(&["Name", "Note"]);
+        let rows = vec![
+            cells(&["plain", ""]),
+            cells(&["a, b", "say \"hi\""]),
+            cells(&["", "two\nlines"]),
+        ];
+        assert_eq!(
+            to_csv(&headers, &rows),
+            "Name,Note\r\nplain,\r\n\"a, b\",\"say \"\"hi\"\"\"\r\n,\"two\nlines\"\r\n"
+        );
+    }
+
+    #[test]
+    fn markdown_aligns_columns_and_keeps_alignment_markers() {
+        let headers = cells(&["Item", "Qty", "Status"]);
+        let alignments = [Alignment::Left, Alignment::Right, Alignment::Center];
+        let rows = vec![
+            cells(&["Apples", "3", "ok"]),
+            cells(&["Kiwi", "", "sold out"]),
+        ];
+        assert_eq!(
+            to_markdown(&headers, &alignments, &rows),
+            "| Item   | Qty |  Status  |\n\
+             | :----- | --: | :------: |\n\
+             | Apples |   3 |    ok    |\n\
+             | Kiwi   |     | sold out |\n"
+        );
+    }
+
+    #[test]
+    fn markdown_fills_short_rows_and_escapes_pipes() {
+        let headers = cells(&["A", "B"]);
+        let rows = vec![cells(&["x|y"])];
+        assert_eq!(
+            to_markdown(&headers, &[], &rows),
+            "| A    | B   |\n| ---- | --- |\n| x\\|y |     |\n"
+        );
+    }
+}
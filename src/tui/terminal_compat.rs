@@ -1,3 +1,5 @@
+use std::sync::OnceLock;
+use std::time::Duration;
 use supports_color::{Stream, on};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -156,6 +158,108 @@ impl TerminalCapabilities {
     }
 }
 
+/// Terminal background color, queried at most once per session.
+static BACKGROUND: OnceLock<Option<(u8, u8, u8)>> = OnceLock::new();
+
+/// Ask the terminal for its background color (OSC 11) and cache the answer.
+///
+/// Must run in raw mode before the event loop starts reading input. The query
+/// is followed by a device attributes request (DA1), which every terminal
+/// answers, so terminals without OSC 11 support don't cost the full timeout.
+/// Returns `None` if the terminal doesn't answer in time.
+pub fn query_background(timeout: Duration) -> Option<(u8, u8, u8)> {
+    *BACKGROUND
+        .get_or_init(|| read_background_reply(timeout).and_then(|reply| parse_osc11_reply(&reply)))
+}
+
+/// Whether the cached terminal background is light. `None` if it was never
+/// queried or the terminal didn't say.
+pub fn background_is_light() -> Option<bool> {
+    BACKGROUND.get().copied().flatten().map(is_light)
+}
+
+#[cfg(unix)]
+fn read_background_reply(timeout: Duration) -> Option<Vec<u8>> {
+    use std::io::Write;
+    use std::time::Instant;
+
+    let mut out = std::io::stdout();
+    out.write_all(b"\x1b]11;?\x1b\\\x1b[c").ok()?;
+    out.flush().ok()?;
+
+    let deadline = Instant::now() + timeout;
+    let mut reply = Vec::new();
+    let mut buf = [0u8; 256];
+    while !ends_with_da1(&reply) {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            break;
+        }
+        let mut fds = libc::pollfd {
+            fd: 0,
+            events: libc::POLLIN,
+            revents: 0,
+        };
+        // SAFETY: polling and reading fd 0 (the terminal in raw mode) into a
+        // local buffer; return values are checked.
+        let ready = unsafe { libc::poll(&mut fds, 1, remaining.as_millis() as libc::c_int) };
+        if ready <= 0 {
+            break;
+        }
+        let n = unsafe { libc::read(0, buf.as_mut_ptr().cast(), buf.len()) };
+        if n <= 0 {
+            break;
+        }
+        reply.extend_from_slice(&buf[..n as usize]);
+    }
+    Some(reply)
+}
+
+#[cfg(not(unix))]
+fn read_background_reply(_timeout: Duration) -> Option<Vec<u8>> {
+    None
+}
+
+/// Whether `reply` ends with a DA1 response (`ESC [ ? ... c`).
+fn ends_with_da1(reply: &[u8]) -> bool {
+    reply.ends_with(b"c")
+        && reply
+            .windows(3)
+            .rposition(|w| w == b"\x1b[?")
+            .is_some_and(|start| {
+                reply[start + 3..reply.len() - 1]
+                    .iter()
+                    .all(|b| b.is_ascii_digit() || *b == b';')
+            })
+}
+
+/// Parse the color out of an OSC 11 reply such as
+/// `ESC ] 11 ; rgb:ffff/ffff/dddd ESC \`. Each channel has 1 to 4 hex digits.
+fn parse_osc11_reply(reply: &[u8]) -> Option<(u8, u8, u8)> {
+    let text = String::from_utf8_lossy(reply);
+    let start = text.find("]11;")? + 4;
+    let spec = text[start..].strip_prefix("rgb:")?;
+    let end = spec
+        .find(|c: char| !(c.is_ascii_hexdigit() || c == '/'))
+        .unwrap_or(spec.len());
+    let mut channels = spec[..end].split('/').map(|hex| {
+        if hex.is_empty() || hex.len() > 4 {
+            return None;
+        }
+        let value = u32::from_str_radix(hex, 16).ok()?;
+        let max = (1u32 << (4 * hex.len())) - 1;
+        Some((value * 255 / max) as u8)
+    });
+    let color = (channels.next()??, channels.next()??, channels.next()??);
+    channels.next().is_none().then_some(color)
+}
+
+/// Whether a background color is light, by its relative luminance.
+fn is_light((r, g, b): (u8, u8, u8)) -> bool {
+    let luminance = 0.2126 * f64::from(r) + 0.7152 * f64::from(g) + 0.0722 * f64::from(b);
+    luminance > 127.5
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,6 +285,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn osc11_replies_are_parsed() {
+        assert_eq!(
+            parse_osc11_reply(b"\x1b]11;rgb:ffff/ffff/dddd\x1b\\\x1b[?62;22c"),
+            Some((255, 255, 221))
+        );
+        // BEL-terminated, two hex digits per channel
+        assert_eq!(
+            parse_osc11_reply(b"\x1b]11;rgb:2b/30/3b\x07"),
+            Some((43, 48, 59))
+        );
+        assert_eq!(parse_osc11_reply(b"\x1b[?1;2c"), None);
+        assert_eq!(parse_osc11_reply(b"\x1b]11;rgb:ff/ff\x07"), None);
+    }
+
+    #[test]
+    fn da1_marks_the_end_of_the_reply() {
+        assert!(ends_with_da1(b"\x1b]11;rgb:0/0/0\x07\x1b[?64;1;9c"));
+        assert!(!ends_with_da1(b"\x1b]11;rgb:0/0/0\x07"));
+        assert!(!ends_with_da1(b"\x1b[?64;1"));
+    }
+
+    #[test]
+    fn luminance_splits_light_and_dark() {
+        assert!(is_light((239, 241, 245)));
+        assert!(is_light((253, 246, 227)));
+        assert!(!is_light((43, 48, 59)));
+        assert!(!is_light((0, 43, 54)));
+    }
+
     #[test]
     fn test_color_mode_enum() {
         // Verify ColorMode variants are distinct
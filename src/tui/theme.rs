@@ -1,3 +1,4 @@
+use crate::parser::HtmlStyle;
 use crate::tui::terminal_compat::ColorMode;
 use ratatui::style::{Color, Modifier, Style};
 
@@ -11,11 +12,53 @@ pub enum ThemeName {
     Gruvbox,
     TokyoNight,
     CatppuccinMocha,
+    CatppuccinLatte,
+    /// A `[[theme.custom]]` entry, by its position in the config
+    Custom(usize),
+}
+
+impl ThemeName {
+    /// The built-in themes, in theme picker order.
+    pub const BUILTIN: [ThemeName; 9] = [
+        ThemeName::OceanDark,
+        ThemeName::Nord,
+        ThemeName::Dracula,
+        ThemeName::Solarized,
+        ThemeName::Monokai,
+        ThemeName::Gruvbox,
+        ThemeName::TokyoNight,
+        ThemeName::CatppuccinMocha,
+        ThemeName::CatppuccinLatte,
+    ];
+
+    /// The built-in theme saved in config as `name` (e.g. `"TokyoNight"`).
+    pub fn builtin(name: &str) -> Option<Self> {
+        Self::BUILTIN
+            .into_iter()
+            .find(|theme| theme.config_name() == Some(name))
+    }
+
+    /// Name a built-in theme is saved under in config; `None` for custom
+    /// themes, which are saved under their own name.
+    pub fn config_name(self) -> Option<&'static str> {
+        Some(match self {
+            ThemeName::OceanDark => "OceanDark",
+            ThemeName::Nord => "Nord",
+            ThemeName::Dracula => "Dracula",
+            ThemeName::Solarized => "Solarized",
+            ThemeName::Monokai => "Monokai",
+            ThemeName::Gruvbox => "Gruvbox",
+            ThemeName::TokyoNight => "TokyoNight",
+            ThemeName::CatppuccinMocha => "CatppuccinMocha",
+            ThemeName::CatppuccinLatte => "CatppuccinLatte",
+            ThemeName::Custom(_) => return None,
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct Theme {
-    pub name: &'static str,
+    pub name: String,
     pub background: Color,
     pub foreground: Color,
     pub heading_1: Color,
@@ -55,6 +98,10 @@ pub struct Theme {
     pub help_key_fg: Color,
     pub help_desc_fg: Color,
     pub footer_bg: Color,
+    /// Background of the content line being read (`ui.highlight_current_line`)
+    pub current_line_bg: Color,
+    /// Syntax errors in yaml/toml/json blocks (`[syntax] validate_config`)
+    pub error_fg: Color,
 }
 
 impl Theme {
@@ -68,6 +115,9 @@ impl Theme {
             ThemeName::Gruvbox => Self::gruvbox(),
             ThemeName::TokyoNight => Self::tokyo_night(),
             ThemeName::CatppuccinMocha => Self::catppuccin_mocha(),
+            ThemeName::CatppuccinLatte => Self::catppuccin_latte(),
+            // Resolved against the config by `from_config`
+            ThemeName::Custom(_) => Self::ocean_dark(),
         }
     }
 
@@ -81,13 +131,42 @@ impl Theme {
             ThemeName::Gruvbox => Self::gruvbox_256(),
             ThemeName::TokyoNight => Self::tokyo_night_256(),
             ThemeName::CatppuccinMocha => Self::catppuccin_mocha_256(),
+            ThemeName::CatppuccinLatte => Self::catppuccin_latte_256(),
+            ThemeName::Custom(_) => Self::ocean_dark_256(),
         }
     }
 
+    /// The theme `name` as configured for `mode`: a built-in palette, or a
+    /// `[[theme.custom]]` one layered on its `base`, with the `[theme]`
+    /// overrides applied last. A custom theme that is no longer in the config
+    /// falls back to Ocean Dark.
+    pub fn from_config(config: &crate::config::Config, name: ThemeName, mode: ColorMode) -> Self {
+        let theme = match name {
+            ThemeName::Custom(idx) => match config.theme.custom.get(idx) {
+                Some(custom) => {
+                    let base = custom
+                        .base
+                        .as_deref()
+                        .and_then(ThemeName::builtin)
+                        .unwrap_or(ThemeName::OceanDark);
+                    let mut theme = Self::from_name(base)
+                        .with_color_mode(mode, base)
+                        .with_custom_colors(&custom.colors, mode);
+                    theme.name = custom.name.clone();
+                    theme
+                }
+                None => Self::from_name(ThemeName::OceanDark)
+                    .with_color_mode(mode, ThemeName::OceanDark),
+            },
+            _ => Self::from_name(name).with_color_mode(mode, name),
+        };
+        theme.with_custom_colors(&config.theme, mode)
+    }
+
     /// Base16 Ocean Dark - Default theme
     pub fn ocean_dark() -> Self {
         Self {
-            name: "Ocean Dark",
+            name: "Ocean Dark".to_string(),
             background: Color::Rgb(43, 48, 59),
             foreground: Color::Rgb(192, 197, 206),
             heading_1: Color::Rgb(100, 200, 255),
@@ -126,13 +205,15 @@ impl Theme {
             help_key_fg: Color::Rgb(100, 200, 255),
             help_desc_fg: Color::Rgb(150, 155, 165),
             footer_bg: Color::Rgb(35, 40, 50),
+            current_line_bg: Color::Rgb(52, 58, 71),
+            error_fg: Color::Rgb(235, 100, 100),
         }
     }
 
     /// Nord theme - Arctic, north-bluish color palette
     pub fn nord() -> Self {
         Self {
-            name: "Nord",
+            name: "Nord".to_string(),
             background: Color::Rgb(46, 52, 64),
             foreground: Color::Rgb(216, 222, 233),
             heading_1: Color::Rgb(136, 192, 208), // Nord Frost
@@ -170,13 +251,15 @@ impl Theme {
             help_key_fg: Color::Rgb(136, 192, 208),
             help_desc_fg: Color::Rgb(147, 155, 170),
             footer_bg: Color::Rgb(46, 52, 64),
+            current_line_bg: Color::Rgb(53, 60, 74),
+            error_fg: Color::Rgb(191, 97, 106),
         }
     }
 
     /// Dracula theme - Dark theme with vibrant colors
     pub fn dracula() -> Self {
         Self {
-            name: "Dracula",
+            name: "Dracula".to_string(),
             background: Color::Rgb(40, 42, 54),
             foreground: Color::Rgb(248, 248, 242),
             heading_1: Color::Rgb(139, 233, 253), // Cyan
@@ -214,13 +297,15 @@ impl Theme {
             help_key_fg: Color::Rgb(139, 233, 253),
             help_desc_fg: Color::Rgb(98, 114, 164),
             footer_bg: Color::Rgb(40, 42, 54),
+            current_line_bg: Color::Rgb(52, 54, 70),
+            error_fg: Color::Rgb(255, 85, 85),
         }
     }
 
     /// Solarized Dark - Precision colors for machines and people
     pub fn solarized() -> Self {
         Self {
-            name: "Solarized",
+            name: "Solarized".to_string(),
             background: Color::Rgb(0, 43, 54),
             foreground: Color::Rgb(131, 148, 150),
             heading_1: Color::Rgb(38, 139, 210), // Blue
@@ -258,13 +343,15 @@ impl Theme {
             help_key_fg: Color::Rgb(38, 139, 210),
             help_desc_fg: Color::Rgb(88, 110, 117),
             footer_bg: Color::Rgb(0, 43, 54),
+            current_line_bg: Color::Rgb(7, 54, 66),
+            error_fg: Color::Rgb(220, 50, 47),
         }
     }
 
     /// Monokai - Sublime Text's iconic color scheme
     pub fn monokai() -> Self {
         Self {
-            name: "Monokai",
+            name: "Monokai".to_string(),
             background: Color::Rgb(39, 40, 34),
             foreground: Color::Rgb(248, 248, 242),
             heading_1: Color::Rgb(102, 217, 239), // Cyan
@@ -302,13 +389,15 @@ impl Theme {
             help_key_fg: Color::Rgb(102, 217, 239),
             help_desc_fg: Color::Rgb(117, 113, 94),
             footer_bg: Color::Rgb(39, 40, 34),
+            current_line_bg: Color::Rgb(52, 53, 45),
+            error_fg: Color::Rgb(249, 38, 114),
         }
     }
 
     /// Gruvbox Dark - Retro groove color scheme
     pub fn gruvbox() -> Self {
         Self {
-            name: "Gruvbox",
+            name: "Gruvbox".to_string(),
             background: Color::Rgb(40, 40, 40),
             foreground: Color::Rgb(235, 219, 178),
             heading_1: Color::Rgb(131, 165, 152), // Aqua
@@ -346,13 +435,15 @@ impl Theme {
             help_key_fg: Color::Rgb(131, 165, 152),
             help_desc_fg: Color::Rgb(146, 131, 116),
             footer_bg: Color::Rgb(40, 40, 40),
+            current_line_bg: Color::Rgb(50, 48, 47),
+            error_fg: Color::Rgb(251, 73, 52),
         }
     }
 
     /// Tokyo Night - Modern dark theme celebrating Tokyo's neon lights at night
     pub fn tokyo_night() -> Self {
         Self {
-            name: "Tokyo Night",
+            name: "Tokyo Night".to_string(),
             background: Color::Rgb(26, 27, 38), // Very dark blue-black
             foreground: Color::Rgb(192, 202, 245), // Soft blue-white
             heading_1: Color::Rgb(122, 162, 247), // Blue
@@ -390,13 +481,15 @@ impl Theme {
             help_key_fg: Color::Rgb(122, 162, 247),
             help_desc_fg: Color::Rgb(86, 95, 137),
             footer_bg: Color::Rgb(26, 27, 38),
+            current_line_bg: Color::Rgb(36, 40, 59),
+            error_fg: Color::Rgb(247, 118, 142),
         }
     }
 
     /// Catppuccin Mocha - Soothing pastel theme for cozy night coding
     pub fn catppuccin_mocha() -> Self {
         Self {
-            name: "Catppuccin Mocha",
+            name: "Catppuccin Mocha".to_string(),
             background: Color::Rgb(30, 30, 46),    // Base
             foreground: Color::Rgb(205, 214, 244), // Text
             heading_1: Color::Rgb(137, 180, 250),  // Blue
@@ -434,6 +527,54 @@ impl Theme {
             help_key_fg: Color::Rgb(137, 180, 250),
             help_desc_fg: Color::Rgb(108, 112, 134),
             footer_bg: Color::Rgb(30, 30, 46),
+            current_line_bg: Color::Rgb(49, 50, 68),
+            error_fg: Color::Rgb(243, 139, 168),
+        }
+    }
+
+    /// Catppuccin Latte - The light Catppuccin flavor, for light terminals
+    pub fn catppuccin_latte() -> Self {
+        Self {
+            name: "Catppuccin Latte".to_string(),
+            background: Color::Rgb(239, 241, 245), // Base
+            foreground: Color::Rgb(76, 79, 105),   // Text
+            heading_1: Color::Rgb(30, 102, 245),   // Blue
+            heading_2: Color::Rgb(32, 159, 181),   // Sapphire
+            heading_3: Color::Rgb(64, 160, 43),    // Green
+            heading_4: Color::Rgb(223, 142, 29),   // Yellow
+            heading_5: Color::Rgb(136, 57, 239),   // Mauve
+            border_focused: Color::Rgb(30, 102, 245),
+            border_unfocused: Color::Rgb(188, 192, 204), // Surface 1
+            selection_bg: Color::Rgb(204, 208, 218),     // Surface 0
+            selection_fg: Color::Rgb(76, 79, 105),       // Text
+            status_bar_bg: Color::Rgb(230, 233, 239),    // Mantle
+            status_bar_fg: Color::Rgb(76, 79, 105),      // Text
+            inline_code_fg: Color::Rgb(254, 100, 11),    // Peach
+            inline_code_bg: Color::Rgb(220, 224, 232),   // Crust
+            bold_fg: Color::Rgb(76, 79, 105),            // Text
+            italic_fg: Color::Rgb(234, 118, 203),        // Pink
+            list_bullet: Color::Rgb(23, 146, 153),       // Teal
+            blockquote_border: Color::Rgb(156, 160, 176), // Overlay 0
+            blockquote_fg: Color::Rgb(108, 111, 133),    // Subtext 0
+            code_fence: Color::Rgb(32, 159, 181),        // Sapphire
+            title_bar_fg: Color::Rgb(30, 102, 245),      // Blue
+            scrollbar_fg: Color::Rgb(156, 160, 176),     // Overlay 0
+            selection_indicator_fg: Color::Rgb(239, 241, 245), // Base
+            selection_indicator_bg: Color::Rgb(30, 102, 245), // Blue
+            link_fg: Color::Rgb(30, 102, 245),           // Blue
+            link_selected_bg: Color::Rgb(30, 102, 245),  // Blue
+            link_selected_fg: Color::Rgb(239, 241, 245), // Base
+            table_border: Color::Rgb(156, 160, 176),     // Overlay 0
+            search_match_bg: Color::Rgb(245, 215, 160),  // Pale yellow
+            search_match_fg: Color::Rgb(76, 79, 105),    // Text
+            search_current_bg: Color::Rgb(223, 142, 29), // Yellow
+            search_current_fg: Color::Rgb(239, 241, 245), // Base
+            help_key_bg: Color::Rgb(204, 208, 218),
+            help_key_fg: Color::Rgb(30, 102, 245),
+            help_desc_fg: Color::Rgb(108, 111, 133),
+            footer_bg: Color::Rgb(230, 233, 239),
+            current_line_bg: Color::Rgb(230, 233, 239),
+            error_fg: Color::Rgb(210, 15, 57),
         }
     }
 
@@ -442,7 +583,7 @@ impl Theme {
     /// Ocean Dark - 256-color optimized variant
     pub fn ocean_dark_256() -> Self {
         Self {
-            name: "Ocean Dark",
+            name: "Ocean Dark".to_string(),
             background: Color::Indexed(236), // ~(43, 48, 59)
             foreground: Color::Indexed(188), // ~(192, 197, 206)
             heading_1: Color::Indexed(117),  // Bright blue
@@ -480,13 +621,15 @@ impl Theme {
             help_key_fg: Color::Indexed(117),
             help_desc_fg: Color::Indexed(246),
             footer_bg: Color::Indexed(236),
+            current_line_bg: Color::Indexed(237),
+            error_fg: Color::Indexed(203),
         }
     }
 
     /// Nord - 256-color optimized variant based on official Nord palette
     pub fn nord_256() -> Self {
         Self {
-            name: "Nord",
+            name: "Nord".to_string(),
             background: Color::Indexed(236), // nord0 approximation
             foreground: Color::Indexed(252), // nord4 approximation
             heading_1: Color::Indexed(109),  // nord8 Frost cyan
@@ -524,13 +667,15 @@ impl Theme {
             help_key_fg: Color::Indexed(109),
             help_desc_fg: Color::Indexed(240),
             footer_bg: Color::Indexed(236),
+            current_line_bg: Color::Indexed(237),
+            error_fg: Color::Indexed(131),
         }
     }
 
     /// Dracula - 256-color optimized variant based on official palette
     pub fn dracula_256() -> Self {
         Self {
-            name: "Dracula",
+            name: "Dracula".to_string(),
             background: Color::Indexed(236),     // Background
             foreground: Color::Indexed(231),     // Foreground
             heading_1: Color::Indexed(117),      // Cyan
@@ -568,13 +713,15 @@ impl Theme {
             help_key_fg: Color::Indexed(117),
             help_desc_fg: Color::Indexed(61),
             footer_bg: Color::Indexed(236),
+            current_line_bg: Color::Indexed(237),
+            error_fg: Color::Indexed(203),
         }
     }
 
     /// Solarized - 256-color degraded variant
     pub fn solarized_256() -> Self {
         Self {
-            name: "Solarized",
+            name: "Solarized".to_string(),
             background: Color::Indexed(234),    // Base03
             foreground: Color::Indexed(244),    // Base0
             heading_1: Color::Indexed(33),      // Blue
@@ -612,13 +759,15 @@ impl Theme {
             help_key_fg: Color::Indexed(33),
             help_desc_fg: Color::Indexed(240),
             footer_bg: Color::Indexed(234),
+            current_line_bg: Color::Indexed(235),
+            error_fg: Color::Indexed(160),
         }
     }
 
     /// Monokai - 256-color optimized variant
     pub fn monokai_256() -> Self {
         Self {
-            name: "Monokai",
+            name: "Monokai".to_string(),
             background: Color::Indexed(235),    // ~(39, 40, 34)
             foreground: Color::Indexed(231),    // ~(248, 248, 242)
             heading_1: Color::Indexed(81),      // Cyan
@@ -656,13 +805,15 @@ impl Theme {
             help_key_fg: Color::Indexed(81),
             help_desc_fg: Color::Indexed(241),
             footer_bg: Color::Indexed(235),
+            current_line_bg: Color::Indexed(236),
+            error_fg: Color::Indexed(197),
         }
     }
 
     /// Gruvbox - 256-color optimized variant (already looks good, refined further)
     pub fn gruvbox_256() -> Self {
         Self {
-            name: "Gruvbox",
+            name: "Gruvbox".to_string(),
             background: Color::Indexed(235),     // Dark background
             foreground: Color::Indexed(223),     // ~(235, 219, 178)
             heading_1: Color::Indexed(108),      // Aqua
@@ -701,13 +852,15 @@ impl Theme {
             help_key_fg: Color::Indexed(108),
             help_desc_fg: Color::Indexed(243),
             footer_bg: Color::Indexed(235),
+            current_line_bg: Color::Indexed(236),
+            error_fg: Color::Indexed(167),
         }
     }
 
     /// Tokyo Night - 256-color optimized variant
     pub fn tokyo_night_256() -> Self {
         Self {
-            name: "Tokyo Night",
+            name: "Tokyo Night".to_string(),
             background: Color::Indexed(234), // Very dark blue-black
             foreground: Color::Indexed(189), // Soft blue-white
             heading_1: Color::Indexed(110),  // Blue
@@ -746,13 +899,15 @@ impl Theme {
             help_key_fg: Color::Indexed(110),
             help_desc_fg: Color::Indexed(243),
             footer_bg: Color::Indexed(234),
+            current_line_bg: Color::Indexed(235),
+            error_fg: Color::Indexed(210),
         }
     }
 
     /// Catppuccin Mocha - 256-color optimized variant
     pub fn catppuccin_mocha_256() -> Self {
         Self {
-            name: "Catppuccin Mocha",
+            name: "Catppuccin Mocha".to_string(),
             background: Color::Indexed(235),     // Base
             foreground: Color::Indexed(189),     // Text
             heading_1: Color::Indexed(117),      // Blue
@@ -791,9 +946,16 @@ impl Theme {
             help_key_fg: Color::Indexed(117),
             help_desc_fg: Color::Indexed(242),
             footer_bg: Color::Indexed(235),
+            current_line_bg: Color::Indexed(236),
+            error_fg: Color::Indexed(211),
         }
     }
 
+    /// Catppuccin Latte - 256-color variant, quantized from the RGB palette
+    pub fn catppuccin_latte_256() -> Self {
+        Self::catppuccin_latte().with_color_mode_custom(ColorMode::Indexed256)
+    }
+
     pub fn heading_color(&self, level: usize) -> Color {
         match level {
             1 => self.heading_1,
@@ -804,6 +966,43 @@ impl Theme {
         }
     }
 
+    /// Accent color for a callout of `kind` (upper-cased, as in `[!NOTE]`).
+    /// GitHub's five alert kinds and their Obsidian aliases each get their
+    /// own color; other kinds share the link color.
+    pub fn callout_color(&self, kind: &str) -> Color {
+        match kind {
+            "NOTE" | "INFO" => self.link_fg,
+            "TIP" | "HINT" | "SUCCESS" | "CHECK" | "DONE" => self.heading_3,
+            "IMPORTANT" => self.heading_1,
+            "WARNING" | "ATTENTION" => self.search_match_bg,
+            "CAUTION" | "DANGER" | "ERROR" | "BUG" | "FAILURE" | "FAIL" => self.search_current_bg,
+            "QUESTION" | "FAQ" | "HELP" => self.heading_4,
+            "EXAMPLE" => self.heading_5,
+            "QUOTE" | "CITE" => self.blockquote_fg,
+            "TODO" | "ABSTRACT" | "SUMMARY" | "TLDR" => self.heading_2,
+            _ => self.link_fg,
+        }
+    }
+
+    /// Colors for an HTML export, taken from an RGB theme. Colors without a
+    /// fixed RGB value (indexed or the terminal default) keep the export's
+    /// default.
+    pub fn html_style(&self) -> HtmlStyle {
+        let default = HtmlStyle::default();
+        let css = |color: Color, fallback: &String| css_color(color).unwrap_or(fallback.clone());
+        HtmlStyle {
+            background: css(self.background, &default.background),
+            foreground: css(self.foreground, &default.foreground),
+            headings: std::array::from_fn(|i| css(self.heading_color(i + 1), &default.headings[i])),
+            link: css(self.link_fg, &default.link),
+            code_fg: css(self.inline_code_fg, &default.code_fg),
+            code_bg: css(self.inline_code_bg, &default.code_bg),
+            blockquote_border: css(self.blockquote_border, &default.blockquote_border),
+            blockquote_fg: css(self.blockquote_fg, &default.blockquote_fg),
+            table_border: css(self.table_border, &default.table_border),
+        }
+    }
+
     pub fn border_style(&self, focused: bool) -> Style {
         if focused {
             Style::default().fg(self.border_focused)
@@ -921,6 +1120,17 @@ impl Theme {
         Style::default().bg(self.footer_bg)
     }
 
+    /// Style for the current line in the content pane
+    pub fn current_line_style(&self) -> Style {
+        Style::default().bg(self.current_line_bg)
+    }
+
+    /// Style for an error shown inside the content, such as a code block's
+    /// syntax error
+    pub fn error_style(&self) -> Style {
+        Style::default().fg(self.error_fg)
+    }
+
     /// Apply custom color overrides from config
     pub fn with_custom_colors(
         mut self,
@@ -982,10 +1192,75 @@ impl Theme {
         apply_color!(help_key_fg);
         apply_color!(help_desc_fg);
         apply_color!(footer_bg);
+        apply_color!(current_line_bg);
+        apply_color!(error_fg);
 
         self
     }
 
+    /// Every color of the theme as a `[[theme.custom]]` block for
+    /// config.toml, named `name` and based on the built-in `base`.
+    pub fn to_custom_toml(&self, name: &str, base: Option<&str>) -> String {
+        use crate::config::ColorValue;
+
+        let mut out = format!("[[theme.custom]]\nname = {:?}\n", name);
+        if let Some(base) = base {
+            out.push_str(&format!("base = {:?}\n", base));
+        }
+
+        macro_rules! push_color {
+            ($field:ident) => {
+                if let Some(value) = ColorValue::from_color(self.$field) {
+                    out.push_str(&format!("{} = {}\n", stringify!($field), value.to_toml()));
+                }
+            };
+        }
+
+        push_color!(background);
+        push_color!(foreground);
+        push_color!(heading_1);
+        push_color!(heading_2);
+        push_color!(heading_3);
+        push_color!(heading_4);
+        push_color!(heading_5);
+        push_color!(border_focused);
+        push_color!(border_unfocused);
+        push_color!(selection_bg);
+        push_color!(selection_fg);
+        push_color!(status_bar_bg);
+        push_color!(status_bar_fg);
+        push_color!(inline_code_fg);
+        push_color!(inline_code_bg);
+        push_color!(bold_fg);
+        push_color!(italic_fg);
+        push_color!(list_bullet);
+        push_color!(blockquote_border);
+        push_color!(blockquote_fg);
+        push_color!(code_fence);
+        push_color!(title_bar_fg);
+        push_color!(scrollbar_fg);
+        push_color!(selection_indicator_fg);
+        push_color!(selection_indicator_bg);
+        push_color!(link_fg);
+        push_color!(link_selected_bg);
+        push_color!(link_selected_fg);
+        push_color!(table_border);
+        // Search highlighting
+        push_color!(search_match_bg);
+        push_color!(search_match_fg);
+        push_color!(search_current_bg);
+        push_color!(search_current_fg);
+        // Footer keybinding hints
+        push_color!(help_key_bg);
+        push_color!(help_key_fg);
+        push_color!(help_desc_fg);
+        push_color!(footer_bg);
+        push_color!(current_line_bg);
+        push_color!(error_fg);
+
+        out
+    }
+
     /// Apply color mode to theme (use optimized 256-color variants or convert RGB)
     pub fn with_color_mode(self, mode: ColorMode, theme_name: ThemeName) -> Self {
         match mode {
@@ -1042,12 +1317,39 @@ impl Theme {
                 self.help_key_fg = rgb_to_256(self.help_key_fg);
                 self.help_desc_fg = rgb_to_256(self.help_desc_fg);
                 self.footer_bg = rgb_to_256(self.footer_bg);
+                self.current_line_bg = rgb_to_256(self.current_line_bg);
+                self.error_fg = rgb_to_256(self.error_fg);
                 self
             }
         }
     }
 }
 
+/// CSS hex value of an RGB or named terminal color.
+fn css_color(color: Color) -> Option<String> {
+    let (r, g, b) = match color {
+        Color::Rgb(r, g, b) => (r, g, b),
+        Color::Black => (0, 0, 0),
+        Color::Red => (205, 49, 49),
+        Color::Green => (13, 188, 121),
+        Color::Yellow => (229, 229, 16),
+        Color::Blue => (36, 114, 200),
+        Color::Magenta => (188, 63, 188),
+        Color::Cyan => (17, 168, 205),
+        Color::Gray => (204, 204, 204),
+        Color::DarkGray => (118, 118, 118),
+        Color::LightRed => (241, 76, 76),
+        Color::LightGreen => (35, 209, 139),
+        Color::LightYellow => (245, 245, 67),
+        Color::LightBlue => (59, 142, 234),
+        Color::LightMagenta => (214, 112, 214),
+        Color::LightCyan => (41, 184, 219),
+        Color::White => (255, 255, 255),
+        _ => return None,
+    };
+    Some(format!("#{:02x}{:02x}{:02x}", r, g, b))
+}
+
 /// Convert RGB color to nearest 256-color palette entry
 fn rgb_to_256(color: Color) -> Color {
     match color {
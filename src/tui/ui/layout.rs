@@ -3,9 +3,75 @@
 //! Provides a builder pattern for creating layouts that can show/hide sections
 //! dynamically based on application state.
 
+use crate::config::WidthSpec;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use std::collections::HashMap;
 
+/// Smallest terminal the panes are laid out in; anything smaller shows a
+/// notice instead.
+pub const MIN_TERMINAL_WIDTH: u16 = 30;
+pub const MIN_TERMINAL_HEIGHT: u16 = 8;
+
+/// Narrowest usable outline pane, in columns.
+pub const MIN_OUTLINE_WIDTH: u16 = 20;
+/// Narrowest usable content pane, in columns.
+pub const MIN_CONTENT_WIDTH: u16 = 40;
+
+/// Whether `area` is too small to lay out.
+pub fn is_too_small(area: Rect) -> bool {
+    area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT
+}
+
+/// Columns for an outline pane `width` wide out of `total` columns, clamped
+/// so that both panes stay usable. `None` when `total` cannot fit both.
+pub fn outline_columns(total: u16, width: WidthSpec) -> Option<u16> {
+    if total < MIN_OUTLINE_WIDTH + MIN_CONTENT_WIDTH {
+        return None;
+    }
+    Some(
+        width
+            .columns(total)
+            .clamp(MIN_OUTLINE_WIDTH, total - MIN_CONTENT_WIDTH),
+    )
+}
+
+/// `width` changed by `delta` (percentage points or columns, keeping its
+/// kind), or unchanged when the outline is already as narrow or as wide as
+/// `total` columns allow.
+pub fn resize_outline(width: WidthSpec, delta: i16, total: u16) -> WidthSpec {
+    let Some(columns) = outline_columns(total, width) else {
+        return width;
+    };
+    let limit = if delta < 0 {
+        MIN_OUTLINE_WIDTH
+    } else {
+        total - MIN_CONTENT_WIDTH
+    };
+    if columns == limit {
+        return width;
+    }
+    // Step from the width actually shown, so a config value beyond the
+    // limits doesn't take several presses to move
+    match width {
+        WidthSpec::Cells(_) => WidthSpec::Cells(
+            columns
+                .saturating_add_signed(delta)
+                .clamp(MIN_OUTLINE_WIDTH, total - MIN_CONTENT_WIDTH),
+        ),
+        WidthSpec::Percent(_) => {
+            // Round the shown share against the step, so it moves at least
+            // a column
+            let shown = u32::from(columns) * 100;
+            let percent = if delta < 0 {
+                shown / u32::from(total)
+            } else {
+                shown.div_ceil(u32::from(total))
+            };
+            WidthSpec::Percent((percent as i16 + delta).clamp(1, 100) as u8)
+        }
+    }
+}
+
 /// Section identifiers for layout areas
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum Section {
@@ -14,6 +80,7 @@ pub enum Section {
     Content,
     Status,
     Footer,
+    Position,
 }
 
 /// A dynamic layout that maps section IDs to their rendered areas
@@ -117,6 +184,70 @@ impl DynamicLayoutBuilder {
 mod tests {
     use super::*;
 
+    #[test]
+    fn outline_columns_keep_both_panes_usable() {
+        let percent = WidthSpec::Percent;
+        assert_eq!(outline_columns(100, percent(30)), Some(30));
+        assert_eq!(outline_columns(70, percent(40)), Some(28));
+        // 40% of 65 would leave the content pane a column short
+        assert_eq!(outline_columns(65, percent(40)), Some(25));
+        // 20% of 60 is below the outline minimum
+        assert_eq!(outline_columns(60, percent(20)), Some(20));
+        assert_eq!(outline_columns(59, percent(30)), None);
+        assert_eq!(outline_columns(100, WidthSpec::Cells(32)), Some(32));
+        assert_eq!(outline_columns(100, WidthSpec::Cells(0)), Some(20));
+        assert_eq!(outline_columns(100, WidthSpec::Cells(90)), Some(60));
+        assert!(is_too_small(Rect::new(0, 0, 29, 40)));
+        assert!(is_too_small(Rect::new(0, 0, 120, 7)));
+        assert!(!is_too_small(Rect::new(0, 0, 30, 8)));
+    }
+
+    #[test]
+    fn resizing_stops_at_the_pane_limits() {
+        assert_eq!(
+            resize_outline(WidthSpec::Percent(30), 2, 100),
+            WidthSpec::Percent(32)
+        );
+        assert_eq!(
+            resize_outline(WidthSpec::Percent(30), -2, 100),
+            WidthSpec::Percent(28)
+        );
+        assert_eq!(
+            resize_outline(WidthSpec::Percent(20), -2, 100),
+            WidthSpec::Percent(20)
+        );
+        assert_eq!(
+            resize_outline(WidthSpec::Cells(32), -2, 100),
+            WidthSpec::Cells(30)
+        );
+        // Columns step from what is shown, and never past either pane's minimum
+        assert_eq!(
+            resize_outline(WidthSpec::Cells(5), 2, 100),
+            WidthSpec::Cells(22)
+        );
+        assert_eq!(
+            resize_outline(WidthSpec::Cells(21), -2, 100),
+            WidthSpec::Cells(20)
+        );
+        assert_eq!(
+            resize_outline(WidthSpec::Cells(59), 2, 100),
+            WidthSpec::Cells(60)
+        );
+        assert_eq!(
+            resize_outline(WidthSpec::Cells(60), 2, 100),
+            WidthSpec::Cells(60)
+        );
+        // Percentages too: 5% of 150 is shown as 20 columns, 13.3%
+        assert_eq!(
+            resize_outline(WidthSpec::Percent(5), 2, 150),
+            WidthSpec::Percent(16)
+        );
+        assert_eq!(
+            resize_outline(WidthSpec::Percent(90), -2, 100),
+            WidthSpec::Percent(58)
+        );
+    }
+
     #[test]
     fn test_all_sections_visible() {
         let area = Rect::new(0, 0, 100, 50);
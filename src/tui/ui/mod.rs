@@ -1,27 +1,33 @@
-mod layout;
+pub mod layout;
 mod popups;
 mod table;
 pub mod util;
 
 use layout::{DynamicLayout, Section};
 
-use crate::tui::app::{App, AppMode, Focus};
+use crate::tui::app::{App, AppMode, Focus, GutterLines, LineNumberStyle};
+use crate::tui::landing;
+use crate::tui::mouse::DrawnLink;
 use crate::tui::theme::Theme;
 use popups::{
     render_cell_edit_overlay, render_command_palette, render_file_create_confirm,
-    render_file_picker, render_help_popup, render_link_picker, render_save_before_nav_confirm,
-    render_save_before_quit_confirm, render_save_width_confirm, render_theme_picker,
+    render_file_picker, render_help_popup, render_link_picker, render_overwrite_confirm,
+    render_save_before_nav_confirm, render_save_before_quit_confirm, render_save_width_confirm,
+    render_section_stats, render_theme_picker,
 };
 use ratatui::Frame;
 use ratatui::layout::{Constraint, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::{Line, Span, Text};
 use ratatui::widgets::{
-    Block, Borders, Clear, List, ListItem, Paragraph, Scrollbar, ScrollbarOrientation,
-    ScrollbarState, Wrap,
+    Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Scrollbar,
+    ScrollbarOrientation, ScrollbarState, Wrap,
 };
 use table::render_table;
-use util::{detect_checkbox_in_text, filter_content};
+use util::{
+    breadcrumb, detect_checkbox_in_text, filter_content, scroll_position_label, scroll_to_reveal,
+    source_line_map, terminal_width,
+};
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     // Re-index interactive elements if mermaid image dimensions arrived last frame.
@@ -31,20 +37,28 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     // Update content metrics before rendering to ensure content height and scroll are correct
     app.update_content_metrics();
 
-    // Clear expired status messages (auto-dismiss after timeout)
-    app.clear_expired_status_message();
-
     let area = frame.area();
+    app.terminal_width = area.width;
+    if layout::is_too_small(area) {
+        app.pane_areas = Default::default();
+        render_too_small(frame, app, area);
+        return;
+    }
 
     // Create dynamic main layout
     // Show search bar if: outline search is active OR in document search mode (typing or viewing results)
     let show_search_bar = app.show_search || app.mode == AppMode::DocSearch;
+    // The scroll position shares the footer row when the hints leave room
+    // for it, and gets its own row below them otherwise
+    let hints = footer_hints(app);
+    let position_row = hints.width() + terminal_width(&scroll_position(app)) > area.width as usize;
     let main_layout = DynamicLayout::vertical(area)
         .section(Section::Title, Constraint::Length(2))
         .section_if(show_search_bar, Section::Search, Constraint::Length(3))
         .section(Section::Content, Constraint::Min(0))
         .section(Section::Status, Constraint::Length(1))
         .section(Section::Footer, Constraint::Length(1))
+        .section_if(position_row, Section::Position, Constraint::Length(1))
         .build();
 
     // Render title bar
@@ -61,26 +75,23 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     // Update viewport height for scroll calculations (subtract 2 for block borders)
     app.set_viewport_height(content_area.height.saturating_sub(2));
 
-    // Minimum widths: outline needs at least 20 cols to be usable, content needs at least 40
-    const MIN_OUTLINE_WIDTH: u16 = 20;
-    const MIN_CONTENT_WIDTH: u16 = 40;
-    const MIN_TOTAL_WIDTH: u16 = MIN_OUTLINE_WIDTH + MIN_CONTENT_WIDTH;
-
-    // Decide whether to show outline based on terminal width
-    let effective_show_outline = app.show_outline && content_area.width >= MIN_TOTAL_WIDTH;
-
-    let content_chunks = if effective_show_outline {
-        let content_width = 100 - app.outline_width;
-        Layout::horizontal([
-            Constraint::Percentage(app.outline_width),
-            Constraint::Percentage(content_width),
-        ])
-        .split(content_area)
+    // The outline is shown only when both panes fit at their minimum widths
+    let outline_columns = app
+        .show_outline
+        .then(|| layout::outline_columns(content_area.width, app.outline_width))
+        .flatten();
+    let effective_show_outline = outline_columns.is_some();
+
+    let content_chunks = if let Some(columns) = outline_columns {
+        Layout::horizontal([Constraint::Length(columns), Constraint::Min(0)]).split(content_area)
     } else {
         // Full-width content when outline is hidden
         Layout::horizontal([Constraint::Percentage(100)]).split(content_area)
     };
 
+    app.pane_areas.outline = effective_show_outline.then(|| content_chunks[0]);
+    app.pane_areas.content = content_chunks[content_chunks.len() - 1];
+
     // Render outline (left pane) only if effectively visible (user toggle AND enough width)
     if effective_show_outline {
         render_outline(frame, app, content_chunks[0]);
@@ -95,7 +106,13 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     render_status_bar(frame, app, main_layout.require(Section::Status));
 
     // Render keybinding hints footer
-    render_footer(frame, app, main_layout.require(Section::Footer));
+    render_footer(
+        frame,
+        app,
+        hints,
+        main_layout.require(Section::Footer),
+        main_layout.get(Section::Position),
+    );
 
     // Render help popup if shown
     if app.show_help {
@@ -132,11 +149,25 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         render_file_create_confirm(frame, message, &app.theme);
     }
 
+    // Render overwrite confirmation dialog
+    if matches!(app.mode, AppMode::ConfirmOverwrite)
+        && let Some(export) = &app.pending_overwrite
+    {
+        render_overwrite_confirm(frame, export.path(), &app.theme);
+    }
+
     // Render save width confirmation dialog
     if matches!(app.mode, AppMode::ConfirmSaveWidth) {
         render_save_width_confirm(frame, app.outline_width, &app.theme);
     }
 
+    // Render section stats popup
+    if matches!(app.mode, AppMode::SectionStats)
+        && let Some((title, stats)) = &app.section_stats
+    {
+        render_section_stats(frame, title, *stats, app.reading_wpm(), &app.theme);
+    }
+
     // Render save before quit confirmation dialog
     if matches!(app.mode, AppMode::ConfirmSaveBeforeQuit) {
         render_save_before_quit_confirm(frame, app.pending_edits.len(), &app.theme);
@@ -155,14 +186,31 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 
 fn render_title_bar(frame: &mut Frame, app: &App, area: Rect) {
     let heading_count = app.document.headings.len();
-    let title_text = format!("treemd - {} - {} headings", app.filename, heading_count);
+    let title_text = match app.loading_indicator() {
+        Some((spinner, filename)) => format!("treemd - {} Loading {}…", spinner, filename),
+        None => format!("treemd - {} - {} headings", app.filename, heading_count),
+    };
 
-    let title = Paragraph::new(title_text)
-        .style(
-            Style::default()
-                .fg(app.theme.title_bar_fg)
-                .add_modifier(Modifier::BOLD),
-        )
+    // Breadcrumb of the selected heading's ancestors after the title
+    const GAP: &str = "  │  ";
+    let room =
+        (area.width as usize).saturating_sub(terminal_width(&title_text) + terminal_width(GAP));
+    let path = app.heading_path();
+    let trail = breadcrumb(&path, room);
+    let mut spans = vec![Span::styled(
+        title_text,
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    if !trail.is_empty() {
+        spans.push(Span::styled(
+            GAP,
+            Style::default().fg(app.theme.border_unfocused),
+        ));
+        spans.push(Span::raw(trail));
+    }
+
+    let title = Paragraph::new(Line::from(spans))
+        .style(Style::default().fg(app.theme.title_bar_fg))
         .block(Block::default().borders(Borders::BOTTOM));
     frame.render_widget(title, area);
 }
@@ -189,7 +237,7 @@ fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
         let shown = app
             .outline_items
             .iter()
-            .filter(|item| item.heading_index.is_some())
+            .filter(|item| item.heading_index.is_some() && item.block_idx.is_none())
             .count();
         let info = if app.search_query.is_empty() {
             String::new()
@@ -211,10 +259,16 @@ fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
             " Content Search (Tab: switch to Outline) ",
             theme.link_fg,
         )
+    } else if app.outline_search_text {
+        (
+            "Filter (+text)",
+            " Outline Search (Tab: switch to Content, Ctrl+T: headings only) ",
+            theme.search_match_bg,
+        )
     } else {
         (
             "Filter",
-            " Outline Search (Tab: switch to Content) ",
+            " Outline Search (Tab: switch to Content, Ctrl+T: include text) ",
             theme.search_match_bg,
         )
     };
@@ -265,7 +319,13 @@ fn render_search_bar(frame: &mut Frame, app: &App, area: Rect) {
 
 fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
     use crate::tui::app::DOCUMENT_OVERVIEW;
-    use util::build_highlighted_line;
+    use crate::tui::fuzzy::fuzzy_match;
+    use util::highlight_ranges;
+
+    if app.file_browser.is_some() {
+        render_file_browser(frame, app, area);
+        return;
+    }
 
     let theme = &app.theme;
     let search_query = if app.show_search && !app.search_query.is_empty() {
@@ -274,8 +334,17 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
         None
     };
 
-    let items: Vec<ListItem> = app
-        .outline_items
+    // Only the rows the pane can show are built; a generated reference with
+    // tens of thousands of headings would otherwise style every one per frame
+    let viewport = area.height.saturating_sub(2) as usize;
+    let (window, offset) = outline_window(
+        app.outline_items.len(),
+        app.outline_state.selected(),
+        app.outline_state.offset(),
+        viewport,
+    );
+
+    let items: Vec<ListItem> = app.outline_items[window.clone()]
         .iter()
         .map(|item| {
             let indent = "  ".repeat(item.level.saturating_sub(1));
@@ -287,19 +356,32 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
                 "  "
             };
 
-            // Show bookmark indicator if this item's text matches the bookmark
-            let bookmark_indicator = if app.bookmark_position.as_deref() == Some(&item.text) {
-                "⚑ "
+            // Show bookmark indicator if this item's text matches the bookmark,
+            // followed by the names of any marks on it
+            let mut bookmark_indicator = if app.bookmark_position.as_deref() == Some(&item.text) {
+                "⚑ ".to_string()
             } else {
-                ""
+                String::new()
             };
+            let mark_names = app.marks.names_for(&item.text);
+            if !mark_names.is_empty() {
+                bookmark_indicator.push('\'');
+                bookmark_indicator.extend(mark_names);
+                bookmark_indicator.push(' ');
+            }
 
-            // Color headings by level using theme
-            let color = theme.heading_color(item.level);
+            // Color headings by level using theme; block entries are dimmed
+            let color = if item.block_idx.is_some() {
+                theme.help_desc_fg
+            } else {
+                theme.heading_color(item.level)
+            };
             let base_style = Style::default().fg(color);
 
             // Build prefix (indent + indicators + optional #'s)
-            let prefix_text = if item.text == DOCUMENT_OVERVIEW {
+            let prefix_text = if item.block_idx.is_some() {
+                format!("{}{}· ", indent, expand_indicator)
+            } else if item.text == DOCUMENT_OVERVIEW {
                 format!("{}{}{}📄 ", indent, expand_indicator, bookmark_indicator)
             } else if app.show_heading_markers {
                 let hashes = "#".repeat(item.level);
@@ -311,14 +393,24 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
                 format!("{}{}{}", indent, expand_indicator, bookmark_indicator)
             };
 
-            // Build line with search highlighting using shared utility
-            let line = build_highlighted_line(
-                vec![Span::styled(prefix_text, base_style)],
+            // Highlight the characters the fuzzy filter matched
+            let mut spans = vec![Span::styled(prefix_text, base_style)];
+            let matched = search_query.and_then(|q| fuzzy_match(&item.text, q));
+            spans.extend(highlight_ranges(
                 &item.text,
-                search_query,
+                matched.map(|m| m.ranges).as_deref().unwrap_or_default(),
                 base_style,
                 theme.search_match_style(),
-            );
+            ));
+            // Sections the filter found by their text rather than heading
+            if item.block_idx.is_none()
+                && item
+                    .heading_index
+                    .is_some_and(|i| app.outline_text_hits.contains(&i))
+            {
+                spans.push(Span::styled(" ≡", theme.search_match_style()));
+            }
+            let line = Line::from(spans);
 
             ListItem::new(line)
         })
@@ -326,6 +418,24 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
 
     let block_style = theme.border_style(app.focus == Focus::Outline);
 
+    // A heading a followed link just landed on flashes, fading back to the
+    // normal selection color
+    let highlight_style = match app.landing_flash_strength() {
+        Some(strength) => theme
+            .selection_style()
+            .bg(landing::fade(
+                theme.search_current_bg,
+                theme.selection_bg,
+                strength,
+            ))
+            .fg(landing::fade(
+                theme.search_current_fg,
+                theme.selection_fg,
+                strength,
+            )),
+        None => theme.selection_style(),
+    };
+
     let list = List::new(items)
         .block(
             Block::default()
@@ -334,10 +444,18 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
                 .title(" Outline "),
         )
         .style(theme.content_style())
-        .highlight_style(theme.selection_style())
+        .highlight_style(highlight_style)
         .highlight_symbol("► ");
 
-    frame.render_stateful_widget(list, area, &mut app.outline_state);
+    let mut window_state = ListState::default()
+        .with_offset(offset - window.start)
+        .with_selected(
+            app.outline_state
+                .selected()
+                .map(|selected| selected.saturating_sub(window.start)),
+        );
+    frame.render_stateful_widget(list, area, &mut window_state);
+    *app.outline_state.offset_mut() = window.start + window_state.offset();
 
     // Render scrollbar
     let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
@@ -357,6 +475,85 @@ fn render_outline(frame: &mut Frame, app: &mut App, area: Rect) {
     );
 }
 
+/// Outline rows built beyond each edge of the visible window.
+const OUTLINE_OVERSCAN: usize = 4;
+
+/// Which of `len` outline rows to build for a pane `height` rows tall: the
+/// scroll offset that keeps `selected` visible while moving as little as
+/// possible from `offset` (as the list widget would), and the visible rows
+/// around it widened by [`OUTLINE_OVERSCAN`].
+fn outline_window(
+    len: usize,
+    selected: Option<usize>,
+    offset: usize,
+    height: usize,
+) -> (std::ops::Range<usize>, usize) {
+    let height = height.max(1);
+    let mut offset = offset.min(len.saturating_sub(1));
+    if let Some(selected) = selected.map(|s| s.min(len.saturating_sub(1))) {
+        if selected < offset {
+            offset = selected;
+        } else if selected >= offset + height {
+            offset = selected + 1 - height;
+        }
+    }
+    let start = offset.saturating_sub(OUTLINE_OVERSCAN);
+    let end = (offset + height + OUTLINE_OVERSCAN).min(len);
+    (start..end.max(start), offset)
+}
+
+/// The directory being browsed, as a tree of its markdown files.
+fn render_file_browser(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(browser) = &app.file_browser else {
+        return;
+    };
+    let theme = &app.theme;
+
+    let items: Vec<ListItem> = browser
+        .entries
+        .iter()
+        .map(|entry| {
+            let indent = "  ".repeat(entry.depth);
+            let line = if entry.is_dir {
+                Line::from(Span::styled(
+                    format!(
+                        "{}▼ {}{}",
+                        indent,
+                        entry.name(),
+                        if entry.label.is_none() { "/" } else { "" }
+                    ),
+                    Style::default().fg(theme.help_desc_fg),
+                ))
+            } else {
+                Line::from(Span::styled(
+                    format!("{}  {}", indent, entry.name()),
+                    Style::default().fg(theme.heading_color(1)),
+                ))
+            };
+            ListItem::new(line)
+        })
+        .collect();
+
+    let root = browser
+        .root
+        .file_name()
+        .map(|n| n.to_string_lossy().to_string())
+        .unwrap_or_else(|| browser.root.display().to_string());
+    let list = List::new(items)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(theme.border_style(app.focus == Focus::Outline))
+                .title(format!(" {}/ ", root)),
+        )
+        .style(theme.content_style())
+        .highlight_style(theme.selection_style())
+        .highlight_symbol("► ");
+
+    let mut state = ListState::default().with_selected(Some(browser.selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
 fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
     use crate::tui::app::AppMode;
 
@@ -393,26 +590,33 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
                 raw_indicator,
                 app.links_in_view.len()
             )
+        } else if app.file_browser.is_some() {
+            format!(" {}{} ", raw_indicator, app.filename)
         } else {
             format!(" {}Content ", raw_indicator)
         };
         (app.document.content.clone(), title)
     };
 
-    // Apply content filtering (frontmatter, LaTeX) based on config
-    // Only filter when not showing raw source - raw view shows everything
-    let content_text = if !app.show_raw_source {
-        filter_content(
-            &content_text,
-            app.should_hide_frontmatter(),
-            app.should_hide_latex(),
-            app.should_latex_aggressive(),
-        )
-    } else {
+    // Footnotes, hidden front matter and LaTeX, abbreviations: only the raw
+    // source view shows everything
+    let content_text = if app.show_raw_source {
         content_text
+    } else {
+        app.rendered_markdown(content_text)
     };
 
     // Check if we should render raw source or enhanced markdown
+    let mut block_starts = Vec::new();
+    // With wrapping on, sideways scrolling moves code blocks that don't wrap
+    let mut code_scroll = CodeScroll {
+        offset: if app.wrap {
+            app.content_hscroll as usize
+        } else {
+            0
+        },
+        max_offset: 0,
+    };
     let mut rendered_text = if app.show_raw_source {
         // Raw source view - show unprocessed markdown
         render_raw_markdown(&content_text, &theme)
@@ -430,6 +634,9 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
         // Calculate available width for tables (content area minus borders and padding)
         let content_width = area.width.saturating_sub(2); // 2 for left/right borders
 
+        let validate_config = app.should_validate_config();
+        let section = app.selected_heading_index();
+
         #[cfg(all(feature = "mermaid", unix))]
         let mermaid_rows_ref = &app.mermaid_placeholder_rows;
         #[cfg(not(all(feature = "mermaid", unix)))]
@@ -443,9 +650,28 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
             Some(&interactive_state), // Pass cloned copy to release borrow
             Some(content_width),
             mermaid_rows_ref,
+            validate_config.then(|| app.config_error_cache.entry(section).or_default()),
+            app.wrap,
+            &mut block_starts,
+            &mut code_scroll,
         )
     };
 
+    // A copy of the text with only the links marked, drawn off screen below
+    // to find where each link lands after wrapping and scrolling
+    let link_marks = mark_links(&rendered_text, &theme);
+
+    // A block entry picked in the outline scrolls to where its block starts
+    if let Some(block) = app.pending_block_scroll.take() {
+        app.content_scroll = block_starts.get(block).map_or(0, |&line| line as u16);
+    }
+
+    if !app.show_raw_source
+        && let Some(abbreviations) = app.abbreviations()
+    {
+        rendered_text = apply_abbreviation_styling(rendered_text, abbreviations);
+    }
+
     // Apply search highlighting only for document/content search mode
     // Outline search (s) only filters headings, it doesn't highlight content
     if app.mode == AppMode::DocSearch && !app.doc_search.query.is_empty() {
@@ -458,20 +684,76 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
         );
     }
 
+    // The section title shares the outline's landing highlight
+    let title = match app.landing_flash_strength() {
+        Some(strength) => Span::styled(
+            title,
+            Style::default()
+                .bg(landing::fade(
+                    theme.search_current_bg,
+                    theme.background,
+                    strength,
+                ))
+                .fg(theme.search_current_fg),
+        ),
+        None => Span::raw(title),
+    };
+
+    // Source line numbers sit in a gutter inside the left border; raw source
+    // view numbers its own lines. Relative numbers count rows in view.
+    let gutter_width = if app.line_numbers && !app.show_raw_source {
+        let mut max_number = app.document.content.lines().count().max(1);
+        if app.line_number_style != LineNumberStyle::Absolute {
+            max_number = max_number.max(area.height as usize);
+        }
+        max_number.to_string().len() as u16 + 1
+    } else {
+        0
+    };
+    let inner_width = area.width.saturating_sub(2); // subtract block borders
+    let text_width = inner_width.saturating_sub(gutter_width);
+    let gutter_rows = (gutter_width > 0).then(|| {
+        let numbers = cached_gutter_numbers(app, &content_text, &block_starts, &rendered_text);
+        visual_line_numbers(&numbers, &rendered_text, text_width, app.wrap)
+    });
+
     // Build paragraph with wrapping to get accurate visual line count
     let block = Block::default()
         .borders(Borders::ALL)
         .border_style(block_style)
+        .padding(Padding::left(gutter_width))
         .title(title);
-    let paragraph = Paragraph::new(rendered_text)
+    let marks_block = block.clone();
+    let mut paragraph = Paragraph::new(rendered_text)
         .block(block)
-        .style(theme.content_style())
-        .wrap(Wrap { trim: false });
+        .style(theme.content_style());
+    if app.wrap {
+        paragraph = paragraph.wrap(Wrap { trim: false });
+    }
 
     // Use line_count() for accurate visual line count after wrapping
     // (requires ratatui "unstable-rendered-line-info" feature)
-    let inner_width = area.width.saturating_sub(2); // subtract block borders
-    let visual_line_count = paragraph.line_count(inner_width);
+    let visual_line_count = paragraph.line_count(text_width);
+
+    // Without wrapping, stop scrolling sideways once the widest line's end
+    // is in view (line_width() includes the block borders); with it, once
+    // the widest unwrapped code line's is
+    let max_hscroll = if app.wrap {
+        code_scroll.max_offset as u16
+    } else {
+        paragraph.line_width().saturating_sub(area.width as usize) as u16
+    };
+    // Keep the selected table cell in view while navigating a wide table
+    if !app.wrap
+        && app.interactive_state.is_in_table_mode()
+        && let Some((_, col)) = app.interactive_state.get_table_position()
+        && let Some((headers, rows)) = app.get_current_table_data()
+        && let Some((start, end)) = table::table_column_span(&headers, &rows, col, None)
+    {
+        app.content_hscroll = scroll_to_reveal(app.content_hscroll, start, end, text_width);
+    }
+    app.content_hscroll_max = max_hscroll;
+    app.content_hscroll = app.content_hscroll.min(max_hscroll);
     if app.content_height != visual_line_count {
         app.content_height = visual_line_count;
     }
@@ -485,8 +767,71 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
         ScrollbarState::new(max_scroll as usize).position(app.content_scroll as usize);
 
     // Apply scroll and render
-    let paragraph = paragraph.scroll((app.content_scroll, 0));
+    let hscroll = if app.wrap { 0 } else { app.content_hscroll };
+    let paragraph = paragraph.scroll((app.content_scroll, hscroll));
     frame.render_widget(paragraph, area);
+    if let Some(rows) = gutter_rows {
+        let gutter = line_number_gutter(
+            &rows,
+            app.line_number_style,
+            app.content_scroll as usize,
+            gutter_width,
+        );
+        let gutter_area = Rect {
+            x: area.x + 1,
+            y: area.y + 1,
+            width: gutter_width.min(inner_width),
+            height: area.height.saturating_sub(2),
+        };
+        frame.render_widget(
+            Paragraph::new(gutter)
+                .style(Style::default().fg(theme.border_unfocused))
+                .scroll((app.content_scroll, 0)),
+            gutter_area,
+        );
+    }
+
+    let text_area = Rect {
+        x: area.x + 1 + gutter_width.min(inner_width),
+        y: area.y + 1,
+        width: text_width,
+        height: area.height.saturating_sub(2),
+    };
+
+    // The line being read, while it is in view
+    if let Some(row) = app
+        .current_content_line()
+        .and_then(|line| line.checked_sub(app.content_scroll as usize))
+        .filter(|&row| row < text_area.height as usize)
+    {
+        tint_row(
+            frame.buffer_mut(),
+            Rect {
+                y: text_area.y + row as u16,
+                height: 1,
+                ..text_area
+            },
+            theme.background,
+            theme.current_line_style(),
+        );
+    }
+    app.pane_areas.content_text = text_area;
+    // Remember where the links were drawn so a click can find the one
+    // under it
+    app.pane_areas.content_links = match link_marks {
+        Some((marks, links)) => {
+            let mut marks = Paragraph::new(marks)
+                .block(marks_block)
+                .scroll((app.content_scroll, hscroll));
+            if app.wrap {
+                marks = marks.wrap(Wrap { trim: false });
+            }
+            let mut buffer = ratatui::buffer::Buffer::empty(area);
+            ratatui::widgets::Widget::render(marks, area, &mut buffer);
+            drawn_links(&buffer, text_area, &links)
+        }
+        None => Vec::new(),
+    };
 
     // Render inline images (first image in content)
     render_inline_images(frame, app, area);
@@ -513,6 +858,259 @@ fn render_content(frame: &mut Frame, app: &mut App, area: Rect) {
     );
 }
 
+/// A whole document rendered as the content pane shows it (footnotes,
+/// filtering and abbreviations as configured), for printing outside the TUI.
+pub fn render_document(
+    content: &str,
+    config: &crate::Config,
+    theme: &Theme,
+    highlighter: &SyntaxHighlighter,
+    width: u16,
+) -> Text<'static> {
+    let footnotes = crate::parser::Footnotes::parse(content);
+    let content = footnotes.render(content);
+    let content = filter_content(
+        &content,
+        config.content.hide_frontmatter,
+        config.content.hide_latex,
+        config.content.latex_aggressive,
+    );
+    let abbreviations = crate::parser::Abbreviations::parse(&content);
+    let show_abbreviations = config.ui.abbreviations && !abbreviations.is_empty();
+    let content = if show_abbreviations {
+        crate::parser::abbreviations::strip_definitions(&content)
+    } else {
+        content
+    };
+
+    let text = render_markdown_enhanced(
+        &content,
+        highlighter,
+        theme,
+        None,
+        None,
+        Some(width),
+        &std::collections::HashMap::new(),
+        config
+            .syntax
+            .validate_config
+            .then_some(&mut std::collections::HashMap::new()),
+        true,
+        &mut Vec::new(),
+        &mut CodeScroll::default(),
+    );
+    if show_abbreviations {
+        apply_abbreviation_styling(text, &abbreviations)
+    } else {
+        text
+    }
+}
+
+/// `text` with every link span marked by the colour of its number and
+/// everything else unstyled, plus each link's text and how many links with
+/// the same text come before it. `None` when there are no links.
+fn mark_links(text: &Text, theme: &Theme) -> Option<(Text<'static>, Vec<(String, usize)>)> {
+    let is_link = |style: Style| {
+        style.add_modifier.contains(Modifier::UNDERLINED)
+            && (style.fg == Some(theme.link_fg)
+                || (style.fg == Some(theme.link_selected_fg)
+                    && style.bg == Some(theme.link_selected_bg)))
+    };
+    let mut links: Vec<(String, usize)> = Vec::new();
+    let lines = text
+        .lines
+        .iter()
+        .map(|line| {
+            let spans = line.spans.iter().map(|span| {
+                let content = span.content.to_string();
+                if !is_link(line.style.patch(span.style)) {
+                    return Span::raw(content);
+                }
+                let number = links.len() as u32;
+                let [_, r, g, b] = number.to_be_bytes();
+                let occurrence = links.iter().filter(|(text, _)| *text == content).count();
+                links.push((content.clone(), occurrence));
+                Span::styled(content, Style::default().fg(Color::Rgb(r, g, b)))
+            });
+            Line {
+                spans: spans.collect(),
+                style: Style::default(),
+                alignment: line.alignment,
+            }
+        })
+        .collect::<Vec<_>>();
+    (!links.is_empty()).then(|| (Text::from(lines), links))
+}
+
+/// Where the links marked by [`mark_links`] landed in `area` of `buffer`.
+fn drawn_links(
+    buffer: &ratatui::buffer::Buffer,
+    area: Rect,
+    links: &[(String, usize)],
+) -> Vec<DrawnLink> {
+    let area = area.intersection(buffer.area);
+    let mut drawn = Vec::new();
+    for y in area.top()..area.bottom() {
+        let mut current: Option<(usize, u16)> = None;
+        let mut covered = 0;
+        for x in area.left()..=area.right() {
+            // The cells a wide character covers belong to it
+            if covered > 0 {
+                covered -= 1;
+                continue;
+            }
+            let number = match buffer.cell((x, y)) {
+                Some(cell) if x < area.right() => {
+                    covered = terminal_width(cell.symbol()).saturating_sub(1);
+                    match cell.fg {
+                        Color::Rgb(r, g, b) => Some(u32::from_be_bytes([0, r, g, b]) as usize),
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            if current.map(|(n, _)| n) == number {
+                continue;
+            }
+            if let Some((n, start)) = current
+                && let Some((text, occurrence)) = links.get(n)
+            {
+                drawn.push(DrawnLink {
+                    row: y,
+                    columns: start..x,
+                    text: text.clone(),
+                    occurrence: *occurrence,
+                });
+            }
+            current = number.map(|n| (n, x));
+        }
+    }
+    drawn
+}
+
+/// [`gutter_line_numbers`], reused from the last frame while the same
+/// section is drawn with the same layout.
+fn cached_gutter_numbers(
+    app: &mut App,
+    content: &str,
+    block_starts: &[usize],
+    rendered: &Text,
+) -> Vec<Option<usize>> {
+    let heading = app.selected_heading_index();
+    if let Some(cached) = &app.gutter_lines
+        && cached.heading == heading
+        && cached.block_starts == block_starts
+        && cached.numbers.len() == rendered.lines.len()
+    {
+        return cached.numbers.clone();
+    }
+    let numbers = gutter_line_numbers(app, content, block_starts, rendered);
+    app.gutter_lines = Some(GutterLines {
+        heading,
+        block_starts: block_starts.to_vec(),
+        numbers: numbers.clone(),
+    });
+    numbers
+}
+
+/// Source line of the first rendered line of each block in `content`, by
+/// rendered line (`None` for lines that continue a block).
+fn gutter_line_numbers(
+    app: &App,
+    content: &str,
+    block_starts: &[usize],
+    rendered: &Text,
+) -> Vec<Option<usize>> {
+    let first_line = app
+        .selected_heading_index()
+        .map_or(1, |idx| app.document.line_at(app.document.body_start(idx)));
+    let source_lines = source_line_map(&app.document.content, first_line, content);
+    let mut numbers = vec![None; rendered.lines.len()];
+    let blocks = crate::parser::content::parse_content_with_ranges(content, 0, content.len());
+    for ((_, range), &start) in blocks.iter().zip(block_starts) {
+        if let Some(range) = range
+            && let Some(slot) = numbers.get_mut(start)
+        {
+            let text_line = content[..range.start].matches('\n').count();
+            *slot = source_lines.get(text_line).copied();
+        }
+    }
+    numbers
+}
+
+/// `numbers` by screen row: an unnumbered row follows each row a wrapped
+/// line takes beyond its first, so the gutter scrolls with the content.
+fn visual_line_numbers(
+    numbers: &[Option<usize>],
+    rendered: &Text,
+    text_width: u16,
+    wrap: bool,
+) -> Vec<Option<usize>> {
+    let mut rows = Vec::new();
+    for (&number, line) in numbers.iter().zip(&rendered.lines) {
+        rows.push(number);
+        let height = if wrap {
+            Paragraph::new(line.clone())
+                .wrap(Wrap { trim: false })
+                .line_count(text_width)
+                .max(1)
+        } else {
+            1
+        };
+        rows.extend((1..height).map(|_| None));
+    }
+    rows
+}
+
+/// Give the cells of `row` that show the plain `background` the
+/// `highlight` background instead, leaving search matches, inline code and
+/// other cells with a background of their own as they are.
+fn tint_row(buffer: &mut ratatui::buffer::Buffer, row: Rect, background: Color, highlight: Style) {
+    for x in row.left()..row.right() {
+        for y in row.top()..row.bottom() {
+            if let Some(cell) = buffer.cell_mut((x, y))
+                && cell.bg == background
+            {
+                cell.set_style(highlight);
+            }
+        }
+    }
+}
+
+/// The gutter for screen `rows`, right-aligned. Absolute numbers label the
+/// rows that start a block; relative styles label every row with its
+/// distance from `current` (the top row in view), which shows 0 or, in the
+/// hybrid style, the line number of the block it belongs to.
+fn line_number_gutter(
+    rows: &[Option<usize>],
+    style: LineNumberStyle,
+    current: usize,
+    gutter_width: u16,
+) -> Text<'static> {
+    let digits = gutter_width.saturating_sub(1) as usize;
+    let label = |row: usize, number: Option<usize>| match style {
+        LineNumberStyle::Absolute => number.map(|n| n.to_string()),
+        _ if row != current => Some(row.abs_diff(current).to_string()),
+        LineNumberStyle::Relative => Some("0".to_string()),
+        LineNumberStyle::Hybrid => rows[..=row]
+            .iter()
+            .rev()
+            .find_map(|&n| n)
+            .map(|n| n.to_string()),
+    };
+    rows.iter()
+        .enumerate()
+        .map(|(row, &number)| {
+            Line::from(
+                label(row, number)
+                    .map(|text| format!("{:>digits$}", text))
+                    .unwrap_or_default(),
+            )
+        })
+        .collect::<Vec<_>>()
+        .into()
+}
+
 fn render_inline_images(frame: &mut Frame, app: &mut App, area: Rect) {
     use crate::tui::interactive::ElementType;
     use ratatui_image::{FilterType, Resize, StatefulImage};
@@ -1341,7 +1939,7 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
         };
 
         let outline_status = if app.show_outline {
-            format!("Outline:{}%", app.outline_width)
+            format!("Outline:{}", app.outline_width)
         } else {
             "Outline:Hidden".to_string()
         };
@@ -1382,11 +1980,46 @@ fn render_status_bar(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(status, area);
 }
 
-/// Render the footer with context-aware keybinding hints.
+/// Render the footer: keybinding `hints` on the left, the scroll position on
+/// the right, or on `position_row` when the layout gave it a row of its own.
+fn render_footer(
+    frame: &mut Frame,
+    app: &App,
+    hints: Line<'static>,
+    area: Rect,
+    position_row: Option<Rect>,
+) {
+    let theme = &app.theme;
+    let position = scroll_position(app);
+    let (hints_area, position_area) = match position_row {
+        Some(row) => (area, row),
+        None => {
+            let [hints_area, position_area] = Layout::horizontal([
+                Constraint::Min(0),
+                Constraint::Length(terminal_width(&position) as u16),
+            ])
+            .areas(area);
+            (hints_area, position_area)
+        }
+    };
+
+    frame.render_widget(
+        Paragraph::new(hints).style(theme.footer_style()),
+        hints_area,
+    );
+    frame.render_widget(
+        Paragraph::new(Span::styled(position, theme.help_desc_style()))
+            .style(theme.footer_style())
+            .alignment(ratatui::layout::Alignment::Right),
+        position_area,
+    );
+}
+
+/// Context-aware keybinding hints for the footer.
 ///
 /// Key labels are derived from the live keybindings (like the help overlay)
 /// so the footer stays accurate when the user remaps keys.
-fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
+fn footer_hints(app: &App) -> Line<'static> {
     use crate::keybindings::{Action, KeybindingMode};
     use crate::tui::app::AppMode;
 
@@ -1512,6 +2145,15 @@ fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
             use KeybindingMode::Normal;
             // Normal mode - show based on focus
             match app.focus {
+                Focus::Outline if app.file_browser.is_some() => {
+                    vec![
+                        (Normal, &[Next, Previous], "Files"),
+                        (Normal, &[ToggleExpand], "Open"),
+                        (Normal, &[ToggleFocus], "Preview"),
+                        (Normal, &[OpenFilePicker], "Picker"),
+                        (Normal, &[ToggleHelp], "Help"),
+                    ]
+                }
                 Focus::Outline => {
                     vec![
                         (Normal, &[Next, Previous], "Navigate"),
@@ -1552,16 +2194,49 @@ fn render_footer(frame: &mut Frame, app: &App, area: Rect) {
         .flatten()
         .collect();
 
-    let line = Line::from(spans);
-    let footer = Paragraph::new(line).style(theme.footer_style());
+    Line::from(spans)
+}
 
-    frame.render_widget(footer, area);
+/// How far through the content pane the view is, like less and vim, and
+/// which of the command-line files is open.
+///
+/// Numbers are padded to the width of the largest they can reach, so the
+/// panes beside the position keep still while scrolling.
+fn scroll_position(app: &App) -> String {
+    let max_scroll = app.max_content_scroll();
+    let label = scroll_position_label(app.content_scroll, max_scroll);
+    let mut position = if max_scroll == 0 {
+        format!(" {} ", label)
+    } else {
+        let total = max_scroll as usize + app.content_viewport_height as usize;
+        format!(
+            " line {:>width$}/{}  {:>3} ",
+            app.content_scroll as usize + 1,
+            total,
+            label,
+            width = total.to_string().len()
+        )
+    };
+    // Which of the command-line files is open
+    if let Some(i) = app.file_list.index_of(&app.current_file_path) {
+        let count = app.file_list.len();
+        position.insert_str(
+            0,
+            &format!(
+                " file {:>width$}/{} │",
+                i + 1,
+                count,
+                width = count.to_string().len()
+            ),
+        );
+    }
+    position
 }
 
 use crate::parser::content::parse_content;
 use crate::parser::output::{Block as ContentBlock, InlineElement};
 use crate::parser::utils::parse_inline_html;
-use crate::tui::syntax::SyntaxHighlighter;
+use crate::tui::syntax::{CodeScroll, SyntaxHighlighter};
 
 /// Render raw markdown source with line numbers
 fn render_raw_markdown(content: &str, theme: &Theme) -> Text<'static> {
@@ -1585,6 +2260,7 @@ fn render_raw_markdown(content: &str, theme: &Theme) -> Text<'static> {
     Text::from(lines)
 }
 
+#[allow(clippy::too_many_arguments)]
 fn render_markdown_enhanced(
     content: &str,
     highlighter: &SyntaxHighlighter,
@@ -1593,6 +2269,12 @@ fn render_markdown_enhanced(
     interactive_state: Option<&crate::tui::interactive::InteractiveState>,
     available_width: Option<u16>,
     _mermaid_placeholder_rows: &std::collections::HashMap<u64, usize>,
+    mut config_errors: Option<
+        &mut std::collections::HashMap<usize, Option<crate::parser::validate::ConfigError>>,
+    >,
+    wrap: bool,
+    block_starts: &mut Vec<usize>,
+    code_scroll: &mut CodeScroll,
 ) -> Text<'static> {
     let mut lines = Vec::new();
 
@@ -1600,6 +2282,8 @@ fn render_markdown_enhanced(
     let blocks = parse_content(content, 0);
 
     for (block_idx, block) in blocks.iter().enumerate() {
+        block_starts.push(lines.len());
+
         // Check if any element in this block is selected (block-level or inline)
         let is_block_selected = selected_element_id
             .map(|id| id.block_idx == block_idx)
@@ -1650,38 +2334,55 @@ fn render_markdown_enhanced(
                 lines.push(Line::from(formatted));
             }
             ContentBlock::Paragraph { content, inline } => {
-                let mut formatted = if !inline.is_empty() {
-                    render_inline_elements(inline, theme, selected_inline_idx)
+                // Definition lists: bold terms, indented definitions
+                if let Some(mut definition_lines) = render_definition_lines(content, theme) {
+                    if is_block_selected {
+                        definition_lines[0].spans.insert(
+                            0,
+                            Span::styled(
+                                "→ ",
+                                Style::default()
+                                    .fg(theme.selection_indicator_fg)
+                                    .bg(theme.selection_indicator_bg)
+                                    .add_modifier(Modifier::BOLD),
+                            ),
+                        );
+                    }
+                    lines.extend(definition_lines);
                 } else {
-                    format_inline_markdown(content, theme)
-                };
-
-                // Add selection indicator (with background for visibility)
-                if is_block_selected {
-                    formatted.insert(
-                        0,
-                        Span::styled(
-                            "→ ",
-                            Style::default()
-                                .fg(theme.selection_indicator_fg)
-                                .bg(theme.selection_indicator_bg)
-                                .add_modifier(Modifier::BOLD),
-                        ),
-                    );
-                }
+                    let mut formatted = if !inline.is_empty() {
+                        render_inline_elements(inline, theme, selected_inline_idx)
+                    } else {
+                        format_inline_markdown(content, theme)
+                    };
 
-                lines.push(Line::from(formatted));
+                    // Add selection indicator (with background for visibility)
+                    if is_block_selected {
+                        formatted.insert(
+                            0,
+                            Span::styled(
+                                "→ ",
+                                Style::default()
+                                    .fg(theme.selection_indicator_fg)
+                                    .bg(theme.selection_indicator_bg)
+                                    .add_modifier(Modifier::BOLD),
+                            ),
+                        );
+                    }
 
-                // If paragraph contains images, add blank lines to reserve space for them
-                // Images will be rendered on top at this position, so we need to push text below down
-                let has_images = inline
-                    .iter()
-                    .any(|elem| matches!(elem, InlineElement::Image { .. }));
-                if has_images {
-                    // Reserve space for image rendering overlay
-                    use crate::tui::interactive::PARAGRAPH_IMAGE_PLACEHOLDER_LINES;
-                    for _ in 0..PARAGRAPH_IMAGE_PLACEHOLDER_LINES {
-                        lines.push(Line::from(vec![]));
+                    lines.push(Line::from(formatted));
+
+                    // If paragraph contains images, add blank lines to reserve space for them
+                    // Images will be rendered on top at this position, so we need to push text below down
+                    let has_images = inline
+                        .iter()
+                        .any(|elem| matches!(elem, InlineElement::Image { .. }));
+                    if has_images {
+                        // Reserve space for image rendering overlay
+                        use crate::tui::interactive::PARAGRAPH_IMAGE_PLACEHOLDER_LINES;
+                        for _ in 0..PARAGRAPH_IMAGE_PLACEHOLDER_LINES {
+                            lines.push(Line::from(vec![]));
+                        }
                     }
                 }
             }
@@ -1695,7 +2396,35 @@ fn render_markdown_enhanced(
                 #[cfg(not(all(feature = "mermaid", unix)))]
                 let is_mermaid = false;
 
-                if is_mermaid {
+                let diagram_placeholder =
+                    crate::tui::diagram::placeholder_kind(language.as_deref()).filter(|_| {
+                        !interactive_state.is_some_and(|state| {
+                            state.is_diagram_source_shown(crate::tui::interactive::ElementId::new(
+                                block_idx, None,
+                            ))
+                        })
+                    });
+
+                if let Some(kind) = diagram_placeholder {
+                    let mut placeholder = crate::tui::diagram::placeholder_lines(
+                        kind,
+                        content,
+                        theme.code_fence_style(),
+                    );
+                    if is_block_selected {
+                        placeholder[0].spans.insert(
+                            0,
+                            Span::styled(
+                                "→ ",
+                                Style::default()
+                                    .fg(theme.selection_indicator_fg)
+                                    .bg(theme.selection_indicator_bg)
+                                    .add_modifier(Modifier::BOLD),
+                            ),
+                        );
+                    }
+                    lines.extend(placeholder);
+                } else if is_mermaid {
                     // Mermaid diagram: render header + placeholder lines for image overlay
                     let mut header_spans = vec![];
                     if is_block_selected {
@@ -1759,14 +2488,46 @@ fn render_markdown_enhanced(
                     lines.push(Line::from(fence_spans));
 
                     // Highlighted code
-                    let highlighted = highlighter.highlight_code(content, lang_str);
+                    let mut highlighted = highlighter.highlight_code(content, lang_str);
+
+                    // Mark the offending line of an invalid yaml/toml/json block
+                    let config_error = config_errors.as_mut().and_then(|errors| {
+                        errors
+                            .entry(block_idx)
+                            .or_insert_with(|| {
+                                crate::parser::validate::validate_config_block(lang_str, content)
+                            })
+                            .clone()
+                    });
+                    if let Some(err) = &config_error
+                        && let Some(line) = highlighted.get_mut(err.line - 1)
+                    {
+                        for span in &mut line.spans {
+                            span.style = span
+                                .style
+                                .fg(theme.error_fg)
+                                .add_modifier(Modifier::UNDERLINED);
+                        }
+                    }
+                    if let Some(width) = available_width {
+                        highlighted = highlighter.fit_to_width(
+                            highlighted,
+                            lang_str,
+                            wrap,
+                            width as usize,
+                            code_scroll,
+                        );
+                    }
                     lines.extend(highlighted);
 
                     // Closing fence
-                    lines.push(Line::from(vec![Span::styled(
-                        "```".to_string(),
-                        theme.code_fence_style(),
-                    )]));
+                    let mut closing_spans =
+                        vec![Span::styled("```".to_string(), theme.code_fence_style())];
+                    if let Some(err) = config_error {
+                        closing_spans
+                            .push(Span::styled(format!(" ✗ {}", err), theme.error_style()));
+                    }
+                    lines.push(Line::from(closing_spans));
                 }
             }
             ContentBlock::List { ordered, items } => {
@@ -2073,16 +2834,28 @@ fn render_markdown_enhanced(
                     (false, None)
                 };
 
-                // Use available_width for smart table collapsing
+                // Rows in the order table mode sorted them, if it did
+                let rows = match interactive_state {
+                    Some(state) if is_block_selected => {
+                        std::borrow::Cow::Owned(state.displayed_table_rows(
+                            crate::tui::interactive::ElementId::new(block_idx, None),
+                            rows,
+                        ))
+                    }
+                    _ => std::borrow::Cow::Borrowed(rows),
+                };
+
+                // Use available_width for smart table collapsing; without
+                // wrapping, tables keep their natural width and scroll sideways
                 let table_lines = render_table(
                     headers,
                     alignments,
-                    rows,
+                    &rows,
                     theme,
                     is_block_selected,
                     in_table_mode,
                     selected_cell,
-                    available_width,
+                    available_width.filter(|_| wrap),
                 );
                 lines.extend(table_lines);
             }
@@ -2300,7 +3073,6 @@ fn apply_search_highlighting(
         return text;
     }
 
-    let query_lower = query.to_lowercase();
     let mut new_lines = Vec::new();
     let mut match_counter = 0usize;
 
@@ -2316,27 +3088,7 @@ fn apply_search_highlighting(
 
         // Join all spans to get the full line text for searching
         let full_text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
-        let full_text_lower = full_text.to_lowercase();
-
-        // Find all occurrences of the query in this line (non-overlapping)
-        let mut matches_in_line: Vec<(usize, usize)> = Vec::new();
-        let mut search_start = 0;
-        let query_len = query.len();
-
-        while let Some(rel_pos) = full_text_lower[search_start..].find(&query_lower) {
-            let byte_start = search_start + rel_pos;
-            let byte_end = byte_start + query_len;
-
-            // Verify we're on valid char boundaries
-            if full_text.is_char_boundary(byte_start) && full_text.is_char_boundary(byte_end) {
-                matches_in_line.push((byte_start, byte_end));
-            }
-
-            search_start = byte_end;
-            if search_start >= full_text_lower.len() {
-                break;
-            }
-        }
+        let matches_in_line = util::find_ignore_case(&full_text, query);
 
         if matches_in_line.is_empty() {
             // No matches in this line - keep original
@@ -2417,6 +3169,70 @@ fn apply_search_highlighting(
     Text::from(new_lines)
 }
 
+/// Underline whole-word occurrences of defined abbreviations.
+///
+/// Spans with a background color (code blocks, inline code) are left alone:
+/// text in code is never an abbreviation.
+fn apply_abbreviation_styling(
+    text: Text<'static>,
+    abbreviations: &crate::parser::Abbreviations,
+) -> Text<'static> {
+    let lines = text
+        .lines
+        .into_iter()
+        .map(|line| {
+            let full_text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            let matches = abbreviations.find_in(&full_text);
+            if matches.is_empty() {
+                return line;
+            }
+
+            let mut spans: Vec<Span<'static>> = Vec::new();
+            let mut span_start = 0;
+            for span in line.spans {
+                let content = span.content.as_ref();
+                let span_end = span_start + content.len();
+                if span.style.bg.is_some() {
+                    span_start = span_end;
+                    spans.push(span);
+                    continue;
+                }
+
+                // Split the span at every abbreviation boundary inside it
+                let mut pos = 0;
+                for &(start, end) in &matches {
+                    if end <= span_start || start >= span_end {
+                        continue;
+                    }
+                    let rel_start = start.saturating_sub(span_start).max(pos);
+                    let rel_end = end.min(span_end) - span_start;
+                    if let Some(before) = safe_slice(content, pos, rel_start)
+                        && !before.is_empty()
+                    {
+                        spans.push(Span::styled(before.to_string(), span.style));
+                    }
+                    if let Some(word) = safe_slice(content, rel_start, rel_end) {
+                        spans.push(Span::styled(
+                            word.to_string(),
+                            span.style.add_modifier(Modifier::UNDERLINED),
+                        ));
+                    }
+                    pos = rel_end;
+                }
+                if let Some(after) = safe_slice(content, pos, content.len())
+                    && !after.is_empty()
+                {
+                    spans.push(Span::styled(after.to_string(), span.style));
+                }
+                span_start = span_end;
+            }
+            Line::from(spans).style(line.style)
+        })
+        .collect::<Vec<_>>();
+
+    Text::from(lines)
+}
+
 /// Safely slice a string at byte boundaries, returning None if boundaries are invalid
 fn safe_slice(s: &str, start: usize, end: usize) -> Option<&str> {
     if start > end || end > s.len() {
@@ -2428,66 +3244,22 @@ fn safe_slice(s: &str, start: usize, end: usize) -> Option<&str> {
     Some(&s[start..end])
 }
 
-/// A recognized GFM alert / Obsidian callout marker like `[!NOTE]` or
-/// `[!warning] Custom title` at the start of a blockquote.
-struct CalloutMarker {
-    kind: String,
-    title: String,
-}
-
-/// Parse the first line of a blockquote for a callout marker.
-fn parse_callout_marker(first_line: &str) -> Option<CalloutMarker> {
-    let trimmed = first_line.trim();
-    let rest = trimmed.strip_prefix("[!")?;
-    let end = rest.find(']')?;
-    let kind_raw = &rest[..end];
-    if kind_raw.is_empty()
-        || !kind_raw
-            .chars()
-            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
-    {
-        return None;
-    }
-
-    // Obsidian fold markers (`[!note]+` / `[!note]-`) and optional title
-    let mut after = rest[end + 1..].trim_start();
-    if let Some(stripped) = after.strip_prefix(['+', '-']) {
-        after = stripped.trim_start();
-    }
-
-    let title = if after.is_empty() {
-        // Title-case the kind: NOTE -> Note
-        let lower = kind_raw.to_ascii_lowercase();
-        let mut chars = lower.chars();
-        let c = chars.next()?;
-        c.to_uppercase().collect::<String>() + chars.as_str()
-    } else {
-        after.to_string()
-    };
-
-    Some(CalloutMarker {
-        kind: kind_raw.to_ascii_uppercase(),
-        title,
-    })
-}
-
-/// Icon and accent color for a callout kind (GFM alerts + common Obsidian
-/// callout kinds; unknown kinds get a neutral default).
-fn callout_decoration(kind: &str, theme: &Theme) -> (&'static str, Color) {
+/// Icon for a callout kind (GFM alerts + common Obsidian callout kinds;
+/// unknown kinds get a neutral default). The color is
+/// [`Theme::callout_color`].
+fn callout_icon(kind: &str) -> &'static str {
     match kind {
-        "NOTE" | "INFO" => ("ℹ", theme.link_fg),
-        "TIP" | "HINT" | "SUCCESS" | "CHECK" | "DONE" => ("💡", theme.heading_3),
-        "IMPORTANT" => ("❗", theme.heading_1),
-        "WARNING" | "ATTENTION" => ("⚠", theme.search_match_bg),
-        "CAUTION" | "DANGER" | "ERROR" | "BUG" | "FAILURE" | "FAIL" => {
-            ("🛑", theme.search_current_bg)
-        }
-        "QUESTION" | "FAQ" | "HELP" => ("❓", theme.heading_4),
-        "EXAMPLE" => ("✏", theme.heading_5),
-        "QUOTE" | "CITE" => ("❝", theme.blockquote_fg),
-        "TODO" => ("☐", theme.heading_2),
-        "ABSTRACT" | "SUMMARY" | "TLDR" => ("📋", theme.heading_2),
-        _ => ("❯", theme.link_fg),
+        "NOTE" | "INFO" => "ℹ",
+        "TIP" | "HINT" | "SUCCESS" | "CHECK" | "DONE" => "💡",
+        "IMPORTANT" => "❗",
+        "WARNING" | "ATTENTION" => "⚠",
+        "CAUTION" | "DANGER" | "ERROR" | "BUG" | "FAILURE" | "FAIL" => "🛑",
+        "QUESTION" | "FAQ" | "HELP" => "❓",
+        "EXAMPLE" => "✏",
+        "QUOTE" | "CITE" => "❝",
+        "TODO" => "☐",
+        "ABSTRACT" | "SUMMARY" | "TLDR" => "📋",
+        _ => "❯",
     }
 }
 
@@ -2495,8 +3267,9 @@ fn callout_decoration(kind: &str, theme: &Theme) -> (&'static str, Color) {
 /// callout marker. Returns None when the blockquote is not a callout.
 fn render_callout_lines(content: &str, theme: &Theme) -> Option<Vec<Line<'static>>> {
     let mut content_lines = content.lines();
-    let marker = parse_callout_marker(content_lines.next()?)?;
-    let (icon, accent) = callout_decoration(&marker.kind, theme);
+    let marker = crate::parser::callout::parse_marker(content_lines.next()?)?;
+    let icon = callout_icon(&marker.kind);
+    let accent = theme.callout_color(&marker.kind);
 
     let bar = || Span::styled("▌ ", Style::default().fg(accent));
     let mut lines = vec![Line::from(vec![
@@ -2516,6 +3289,54 @@ fn render_callout_lines(content: &str, theme: &Theme) -> Option<Vec<Line<'static
     Some(lines)
 }
 
+/// Shown instead of the panes when the terminal is below the minimum size.
+fn render_too_small(frame: &mut Frame, app: &App, area: Rect) {
+    let message = format!(
+        "Terminal too small ({}x{}, need {}x{})",
+        area.width,
+        area.height,
+        layout::MIN_TERMINAL_WIDTH,
+        layout::MIN_TERMINAL_HEIGHT
+    );
+    let paragraph = Paragraph::new(message)
+        .style(
+            Style::default()
+                .fg(app.theme.foreground)
+                .bg(app.theme.background),
+        )
+        .alignment(ratatui::layout::Alignment::Center)
+        .wrap(Wrap { trim: true });
+    let top = area.height.saturating_sub(1) / 2;
+    frame.render_widget(
+        paragraph,
+        Rect {
+            y: area.y + top,
+            height: area.height - top,
+            ..area
+        },
+    );
+}
+
+/// Lines for a definition list paragraph: each term in bold, its
+/// definitions below it indented. `None` if `content` is not one.
+fn render_definition_lines(content: &str, theme: &Theme) -> Option<Vec<Line<'static>>> {
+    let items = crate::parser::definition_list::split(content)?;
+    let mut lines = Vec::with_capacity(crate::parser::definition_list::line_count(&items));
+    for item in items {
+        let mut term = format_inline_markdown(&item.term, theme);
+        for span in &mut term {
+            span.style = span.style.add_modifier(Modifier::BOLD);
+        }
+        lines.push(Line::from(term));
+        for definition in &item.definitions {
+            let mut spans = vec![Span::raw("    ")];
+            spans.extend(format_inline_markdown(definition, theme));
+            lines.push(Line::from(spans));
+        }
+    }
+    Some(lines)
+}
+
 fn render_block_to_lines(
     block: &ContentBlock,
     highlighter: &SyntaxHighlighter,
@@ -2550,6 +3371,10 @@ fn render_block_to_lines(
             lines.push(Line::from(formatted));
         }
         ContentBlock::Paragraph { content, inline } => {
+            if let Some(definition_lines) = render_definition_lines(content, theme) {
+                lines.extend(definition_lines);
+                return lines;
+            }
             let formatted = if !inline.is_empty() {
                 render_inline_elements(inline, theme, None)
             } else {
@@ -2900,33 +3725,129 @@ mod tests {
     use super::*;
 
     #[test]
-    fn callout_marker_basic_kinds() {
-        let m = parse_callout_marker("[!NOTE]").unwrap();
-        assert_eq!(m.kind, "NOTE");
-        assert_eq!(m.title, "Note");
+    fn links_are_found_where_they_wrap_to() {
+        let theme = Theme::ocean_dark();
+        let link = Style::default()
+            .fg(theme.link_fg)
+            .add_modifier(Modifier::UNDERLINED);
+        let text = Text::from(Line::from(vec![
+            Span::raw("日本 docs, "),
+            Span::styled("docs", link),
+            Span::raw(" and "),
+            Span::styled("the docs", link),
+        ]));
+        let (marks, links) = mark_links(&text, &theme).unwrap();
+        assert_eq!(
+            links,
+            [("docs".to_string(), 0), ("the docs".to_string(), 0)]
+        );
 
-        let m = parse_callout_marker("[!warning]").unwrap();
-        assert_eq!(m.kind, "WARNING");
-        assert_eq!(m.title, "Warning");
+        let area = Rect::new(0, 0, 12, 3);
+        let mut buffer = ratatui::buffer::Buffer::empty(area);
+        ratatui::widgets::Widget::render(
+            Paragraph::new(marks).wrap(Wrap { trim: false }),
+            area,
+            &mut buffer,
+        );
+        let at = |row, columns, text: &str| DrawnLink {
+            row,
+            columns,
+            text: text.to_string(),
+            occurrence: 0,
+        };
+        let drawn = drawn_links(&buffer, area, &links);
+        // Wide characters take two cells; "the docs" wraps onto the next row
+        assert_eq!(
+            drawn,
+            [
+                at(1, 0..4, "docs"),
+                at(1, 9..12, "the docs"),
+                at(2, 0..4, "the docs")
+            ]
+        );
     }
 
     #[test]
-    fn callout_marker_custom_title_and_fold() {
-        let m = parse_callout_marker("[!tip] Read this first").unwrap();
-        assert_eq!(m.kind, "TIP");
-        assert_eq!(m.title, "Read this first");
+    fn config_blocks_are_validated_once() {
+        let content = "```json\n{\"a\": 1,}\n```\n";
+        let render = |errors: &mut std::collections::HashMap<_, _>| {
+            render_markdown_enhanced(
+                content,
+                &SyntaxHighlighter::new("InspiredGitHub", None),
+                &Theme::ocean_dark(),
+                None,
+                None,
+                Some(80),
+                &std::collections::HashMap::new(),
+                Some(errors),
+                true,
+                &mut Vec::new(),
+                &mut CodeScroll::default(),
+            )
+            .to_string()
+        };
+
+        let mut errors = std::collections::HashMap::new();
+        assert!(render(&mut errors).contains("✗ line 1"));
+        assert!(errors[&0].is_some());
 
-        let m = parse_callout_marker("[!note]- Folded title").unwrap();
-        assert_eq!(m.kind, "NOTE");
-        assert_eq!(m.title, "Folded title");
+        // A block already checked is not parsed again
+        errors.insert(0, None);
+        assert!(!render(&mut errors).contains('✗'));
     }
 
     #[test]
-    fn callout_marker_rejects_non_markers() {
-        assert!(parse_callout_marker("plain quote").is_none());
-        assert!(parse_callout_marker("[!]").is_none());
-        assert!(parse_callout_marker("[! has space]").is_none());
-        assert!(parse_callout_marker("[note]").is_none());
+    fn scroll_position_keeps_its_width_while_scrolling() {
+        let mut app = App::new(
+            crate::parser::parse_markdown("# Doc\n"),
+            "doc.md".to_string(),
+            std::path::PathBuf::from("doc.md"),
+            crate::Config::default(),
+            crate::tui::terminal_compat::ColorMode::Rgb,
+            false,
+        );
+        app.content_viewport_height = 20;
+        app.content_height = 1_020;
+        let widths: Vec<usize> = [0, 5, 99, 500, 1_000]
+            .into_iter()
+            .map(|scroll| {
+                app.content_scroll = scroll;
+                terminal_width(&scroll_position(&app))
+            })
+            .collect();
+        assert_eq!(widths, [widths[0]; 5]);
+        app.content_scroll = 5;
+        assert_eq!(scroll_position(&app), " line    6/1020   0% ");
+    }
+
+    #[test]
+    fn outline_window_is_bounded_by_the_viewport() {
+        let height = 40;
+        for len in [10, 1_000, 20_000] {
+            for selected in [0, len / 2, len - 1] {
+                let (window, offset) = outline_window(len, Some(selected), 0, height);
+                assert!(window.len() <= height + 2 * OUTLINE_OVERSCAN);
+                assert!(window.contains(&selected) || len == 0);
+                assert!((offset..offset + height).contains(&selected));
+            }
+        }
+
+        // Rendering the window of a 20k-row outline builds no more rows than
+        // a small document does
+        let (big, _) = outline_window(20_000, Some(12_345), 12_300, height);
+        let (small, _) = outline_window(100, Some(50), 30, height);
+        assert_eq!(big.len(), small.len());
+    }
+
+    #[test]
+    fn outline_window_scrolls_like_the_list_widget() {
+        // Selection inside the view leaves the offset alone
+        assert_eq!(outline_window(100, Some(15), 10, 20).1, 10);
+        // Below the view: scroll just enough to show it on the last row
+        assert_eq!(outline_window(100, Some(40), 10, 20).1, 21);
+        // Above the view: it becomes the first row
+        assert_eq!(outline_window(100, Some(3), 10, 20).1, 3);
+        assert_eq!(outline_window(0, None, 5, 20), (0..0, 0));
     }
 
     #[test]
@@ -2940,9 +3861,127 @@ mod tests {
         assert!(body.contains("body text"));
     }
 
+    #[test]
+    fn abbreviations_are_underlined_outside_code() {
+        let abbrs = crate::parser::Abbreviations::parse(
+            "Uses HTML.\n\n*[HTML]: HyperText Markup Language\n",
+        );
+        assert_eq!(abbrs.expansion("HTML"), Some("HyperText Markup Language"));
+
+        let code = Style::default().bg(Color::Black);
+        let text = Text::from(vec![
+            Line::from(vec![Span::raw("Uses HT"), Span::raw("ML and XHTML.")]),
+            Line::from(Span::styled("HTML", code)),
+        ]);
+        let styled = apply_abbreviation_styling(text, &abbrs);
+
+        let underlined: Vec<String> = styled.lines[0]
+            .spans
+            .iter()
+            .filter(|s| s.style.add_modifier.contains(Modifier::UNDERLINED))
+            .map(|s| s.content.to_string())
+            .collect();
+        // The word spans two source spans; XHTML is not a whole-word match
+        assert_eq!(underlined, ["HT", "ML"]);
+        assert_eq!(styled.lines[0].to_string(), "Uses HTML and XHTML.");
+        assert!(
+            !styled.lines[1].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::UNDERLINED)
+        );
+    }
+
+    #[test]
+    fn definition_list_renders_bold_terms_and_indented_definitions() {
+        let theme = Theme::ocean_dark();
+        let lines = render_definition_lines("Apple\n: A fruit\n: A company\nKiwi\n: Green", &theme)
+            .unwrap();
+        let rendered: Vec<String> = lines.iter().map(|line| line.to_string()).collect();
+        assert_eq!(
+            rendered,
+            ["Apple", "    A fruit", "    A company", "Kiwi", "    Green"]
+        );
+        assert!(
+            lines[0].spans[0]
+                .style
+                .add_modifier
+                .contains(Modifier::BOLD)
+        );
+        assert!(render_definition_lines("Plain text", &theme).is_none());
+    }
+
     #[test]
     fn non_callout_blockquote_is_untouched() {
         let theme = Theme::ocean_dark();
         assert!(render_callout_lines("just a quote", &theme).is_none());
     }
+
+    #[test]
+    fn line_number_gutter_leaves_room_for_wrapped_rows() {
+        let rendered = Text::from(vec![
+            Line::from("short"),
+            Line::from("a line long enough to wrap"),
+            Line::from("end"),
+        ]);
+        let numbers = [Some(3), Some(10), None];
+        let rows = |wrap| -> Vec<String> {
+            let rows = visual_line_numbers(&numbers, &rendered, 10, wrap);
+            line_number_gutter(&rows, LineNumberStyle::Absolute, 0, 3)
+                .lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect()
+        };
+        assert_eq!(rows(true), [" 3", "10", "", "", "", ""]);
+        assert_eq!(rows(false), [" 3", "10", ""]);
+    }
+
+    #[test]
+    fn current_line_tint_keeps_other_backgrounds() {
+        let area = Rect::new(0, 0, 6, 2);
+        let mut buffer = ratatui::buffer::Buffer::empty(area);
+        buffer.set_style(area, Style::default().bg(Color::Black));
+        buffer.set_string(2, 0, "ab", Style::default().bg(Color::Yellow));
+        tint_row(
+            &mut buffer,
+            Rect::new(0, 0, 6, 1),
+            Color::Black,
+            Style::default().bg(Color::Blue),
+        );
+        let row: Vec<Color> = (0..6).map(|x| buffer[(x, 0)].bg).collect();
+        assert_eq!(
+            row,
+            [
+                Color::Blue,
+                Color::Blue,
+                Color::Yellow,
+                Color::Yellow,
+                Color::Blue,
+                Color::Blue
+            ]
+        );
+        assert_eq!(buffer[(0, 1)].bg, Color::Black);
+    }
+
+    #[test]
+    fn relative_line_numbers_count_rows_from_the_top() {
+        let rows = [Some(3), None, None, Some(7), None];
+        let gutter = |style| -> Vec<String> {
+            line_number_gutter(&rows, style, 2, 3)
+                .lines
+                .iter()
+                .map(|line| line.to_string())
+                .collect()
+        };
+        assert_eq!(
+            gutter(LineNumberStyle::Relative),
+            [" 2", " 1", " 0", " 1", " 2"]
+        );
+        assert_eq!(
+            gutter(LineNumberStyle::Hybrid),
+            [" 2", " 1", " 3", " 1", " 2"]
+        );
+        assert_eq!(gutter(LineNumberStyle::Absolute), [" 3", "", "", " 7", ""]);
+    }
 }
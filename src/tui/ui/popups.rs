@@ -3,6 +3,8 @@
 //! Handles modal dialogs including help, link picker, search, theme selector,
 //! and cell edit overlays.
 
+use crate::config::WidthSpec;
+use crate::parser::TextStats;
 use crate::tui::app::App;
 use crate::tui::help_text;
 use crate::tui::theme::Theme;
@@ -14,7 +16,8 @@ use ratatui::widgets::{
     Block, Borders, Clear, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState, Wrap,
 };
 
-use super::util::popup_area;
+use super::util::{highlight_ranges, popup_area};
+use std::path::Path;
 
 /// Render the help popup with keyboard shortcuts
 pub fn render_help_popup(frame: &mut Frame, app: &App, area: Rect) {
@@ -26,15 +29,32 @@ pub fn render_help_popup(frame: &mut Frame, app: &App, area: Rect) {
     // Clear the area
     frame.render_widget(Clear, popup_area);
 
-    let help_lines = help_text::build_help_text(keybindings, theme);
+    let query = app.help_query.trim();
+    let mut help_lines: Vec<Line> = help_text::build_help_text(keybindings, theme, query)
+        .into_iter()
+        .map(|line| highlight_query(line, query, theme.search_match_style()))
+        .collect();
+    if help_lines.is_empty() {
+        help_lines.push(Line::from(Span::styled(
+            format!("No keys or descriptions match \"{}\"", query),
+            Style::default().fg(theme.modal_description()),
+        )));
+    }
     let help_text_len = help_lines.len();
 
+    let title = if app.help_search_active || !app.help_query.is_empty() {
+        let cursor = if app.help_search_active { "▌" } else { "" };
+        format!(" Help  /{}{} ", app.help_query, cursor)
+    } else {
+        " Help ".to_string()
+    };
+
     let paragraph = Paragraph::new(help_lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .border_style(Style::default().fg(theme.modal_border()))
-                .title(" Help ")
+                .title(title)
                 .style(Style::default().bg(theme.modal_bg())),
         )
         .wrap(Wrap { trim: false })
@@ -62,6 +82,32 @@ pub fn render_help_popup(frame: &mut Frame, app: &App, area: Rect) {
     );
 }
 
+/// `line` with each case-insensitive occurrence of `query` in `style`.
+fn highlight_query(line: Line<'static>, query: &str, style: Style) -> Line<'static> {
+    if query.is_empty() {
+        return line;
+    }
+    let query = query.to_lowercase();
+    let spans = line
+        .spans
+        .into_iter()
+        .flat_map(|span| {
+            let lower = span.content.to_lowercase();
+            // Offsets only carry over when lowercasing kept the byte length
+            let ranges: Vec<(usize, usize)> = if lower.len() == span.content.len() {
+                lower
+                    .match_indices(&query)
+                    .map(|(start, m)| (start, start + m.len()))
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            highlight_ranges(&span.content, &ranges, span.style, span.style.patch(style))
+        })
+        .collect::<Vec<_>>();
+    Line::from(spans).style(line.style)
+}
+
 /// Render the link picker popup
 pub fn render_link_picker(frame: &mut Frame, app: &App, area: Rect) {
     use crate::parser::LinkTarget;
@@ -289,10 +335,15 @@ pub fn render_link_picker(frame: &mut Frame, app: &App, area: Rect) {
 pub fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
     use crate::tui::theme::ThemeName;
 
-    let theme = &app.theme;
+    // The content behind previews the selection; the picker keeps the
+    // committed palette so it stays legible under any preview
+    let (saved_theme, theme) = match &app.theme_picker_committed {
+        Some((name, theme)) => (*name, theme),
+        None => (app.current_theme, &app.theme),
+    };
 
-    // All available themes
-    let themes = [
+    // Built-in themes, then the ones from [[theme.custom]]
+    let builtin = [
         (
             ThemeName::OceanDark,
             "Ocean Dark",
@@ -325,7 +376,24 @@ pub fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
             "Catppuccin Mocha",
             "Soothing pastel theme for night coding",
         ),
+        (
+            ThemeName::CatppuccinLatte,
+            "Catppuccin Latte",
+            "Light pastel theme for light terminals",
+        ),
     ];
+    let mut themes: Vec<(ThemeName, String, String)> = builtin
+        .iter()
+        .map(|(theme, name, description)| (*theme, name.to_string(), description.to_string()))
+        .collect();
+    for (idx, custom) in app.custom_themes().iter().enumerate() {
+        let base = custom.base.as_deref().unwrap_or("OceanDark");
+        themes.push((
+            ThemeName::Custom(idx),
+            custom.name.clone(),
+            format!("Custom theme based on {}", base),
+        ));
+    }
 
     // Create centered popup area
     // Min 35 cols for theme names, min 12 rows for all themes + header
@@ -337,7 +405,7 @@ pub fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
     // Create lines for each theme
     let mut lines = vec![
         Line::from(vec![Span::styled(
-            "Select Theme (j/k: preview, Enter: save, Esc: cancel)",
+            "Select Theme (j/k: preview, Enter: save, e: copy TOML, c: code theme, Esc: cancel)",
             Style::default()
                 .fg(theme.modal_description())
                 .add_modifier(Modifier::ITALIC),
@@ -347,8 +415,7 @@ pub fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
 
     for (idx, (theme_name, name, description)) in themes.iter().enumerate() {
         let is_selected = idx == app.theme_picker_selected;
-        // Show ✓ next to the saved theme (original), not the preview
-        let saved_theme = app.theme_picker_original.unwrap_or(app.current_theme);
+        // Show ✓ next to the saved theme, not the preview
         let is_saved = *theme_name == saved_theme;
 
         let (prefix, style) = if is_selected {
@@ -379,6 +446,10 @@ pub fn render_theme_picker(frame: &mut Frame, app: &App, area: Rect) {
     }
 
     lines.push(Line::from(""));
+    lines.push(Line::from(vec![Span::styled(
+        format!("Code theme: {}", app.highlighter.theme_name()),
+        Style::default().fg(theme.modal_description()),
+    )]));
 
     let paragraph = Paragraph::new(lines)
         .block(
@@ -502,8 +573,56 @@ pub fn render_file_create_confirm(frame: &mut Frame, message: &str, theme: &Them
     frame.render_widget(paragraph, area);
 }
 
+/// Ask before an export replaces the existing file at `path`.
+pub fn render_overwrite_confirm(frame: &mut Frame, path: &Path, theme: &Theme) {
+    let area = popup_area(frame.area(), 50, 20, 30, 7);
+
+    frame.render_widget(Clear, area);
+
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+    let text = vec![
+        Line::from(vec![Span::styled(
+            "Overwrite File?",
+            Style::default()
+                .fg(theme.modal_title())
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        Line::from(vec![Span::styled(
+            format!("'{}' already exists.", name),
+            Style::default().fg(theme.modal_text()),
+        )]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[y]", Style::default().fg(theme.modal_key_fg())),
+            Span::styled(
+                " Overwrite  ",
+                Style::default().fg(theme.modal_description()),
+            ),
+            Span::styled("[n/Esc]", Style::default().fg(theme.modal_key_fg())),
+            Span::styled(" Cancel", Style::default().fg(theme.modal_description())),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Confirm ")
+                .title_style(Style::default().fg(theme.modal_title()))
+                .border_style(Style::default().fg(theme.modal_border()))
+                .style(Style::default().bg(theme.modal_bg())),
+        );
+
+    frame.render_widget(paragraph, area);
+}
+
 /// Render the save width confirmation modal
-pub fn render_save_width_confirm(frame: &mut Frame, width: u16, theme: &Theme) {
+pub fn render_save_width_confirm(frame: &mut Frame, width: WidthSpec, theme: &Theme) {
     // Create a centered dialog area
     // Min 28 cols for text, min 7 rows for dialog content
     let area = popup_area(frame.area(), 45, 18, 28, 7);
@@ -521,7 +640,7 @@ pub fn render_save_width_confirm(frame: &mut Frame, width: u16, theme: &Theme) {
         )]),
         Line::from(""),
         Line::from(vec![Span::styled(
-            format!("Save width {}% to config file?", width),
+            format!("Save width {} to config file?", width),
             Style::default().fg(theme.modal_text()),
         )]),
         Line::from(""),
@@ -547,6 +666,64 @@ pub fn render_save_width_confirm(frame: &mut Frame, width: u16, theme: &Theme) {
     frame.render_widget(paragraph, area);
 }
 
+/// Render the word count and reading time of a section titled `title`
+pub fn render_section_stats(
+    frame: &mut Frame,
+    title: &str,
+    stats: TextStats,
+    wpm: usize,
+    theme: &Theme,
+) {
+    let area = popup_area(frame.area(), 45, 22, 30, 9);
+    frame.render_widget(Clear, area);
+
+    let row = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(
+                format!("{:<14}", label),
+                Style::default().fg(theme.modal_description()),
+            ),
+            Span::styled(
+                format!("{:>10}", value),
+                Style::default().fg(theme.modal_text()),
+            ),
+        ])
+    };
+    let text = vec![
+        Line::from(vec![Span::styled(
+            title.to_string(),
+            Style::default()
+                .fg(theme.modal_title())
+                .add_modifier(Modifier::BOLD),
+        )]),
+        Line::from(""),
+        row("Words", stats.words.to_string()),
+        row("Characters", stats.chars.to_string()),
+        row(
+            "Reading time",
+            format!("{} min", stats.reading_minutes(wpm)),
+        ),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled("[Esc]", Style::default().fg(theme.modal_key_fg())),
+            Span::styled(" Close", Style::default().fg(theme.modal_description())),
+        ]),
+    ];
+
+    let paragraph = Paragraph::new(text)
+        .alignment(ratatui::layout::Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Section Stats ")
+                .title_style(Style::default().fg(theme.modal_title()))
+                .border_style(Style::default().fg(theme.modal_border()))
+                .style(Style::default().bg(theme.modal_bg())),
+        );
+
+    frame.render_widget(paragraph, area);
+}
+
 /// Render the save before quit confirmation modal
 pub fn render_save_before_quit_confirm(frame: &mut Frame, edit_count: usize, theme: &Theme) {
     // Create a centered dialog area
@@ -686,7 +863,7 @@ pub fn render_save_before_nav_confirm(frame: &mut Frame, edit_count: usize, them
 
 /// Render the command palette with fuzzy search
 pub fn render_command_palette(frame: &mut Frame, app: &App, theme: &Theme) {
-    use crate::tui::app::PALETTE_COMMANDS;
+    use crate::tui::app::palette_commands;
 
     // Create a centered popup
     // Min 35 cols for command names, min 10 rows for header + a few commands
@@ -726,8 +903,19 @@ pub fn render_command_palette(frame: &mut Frame, app: &App, theme: &Theme) {
                 .add_modifier(Modifier::ITALIC),
         )]));
     } else {
-        for (display_idx, &cmd_idx) in app.command_palette.filtered.iter().enumerate() {
-            let cmd = &PALETTE_COMMANDS[cmd_idx];
+        // Scroll the list so the selection stays visible: the border, header,
+        // footer and the selected command's description take 9 rows.
+        let visible = (area.height as usize).saturating_sub(9).max(1);
+        let first = (app.command_palette.selected + 1).saturating_sub(visible);
+        for (display_idx, &cmd_idx) in app
+            .command_palette
+            .filtered
+            .iter()
+            .enumerate()
+            .skip(first)
+            .take(visible)
+        {
+            let cmd = &palette_commands()[cmd_idx];
             let is_selected = display_idx == app.command_palette.selected;
 
             let prefix = if is_selected { "▸ " } else { "  " };
@@ -756,7 +944,7 @@ pub fn render_command_palette(frame: &mut Frame, app: &App, theme: &Theme) {
             // Show description for selected item
             if is_selected {
                 lines.push(Line::from(vec![Span::styled(
-                    format!("    {}", cmd.description),
+                    format!("    {}", app.palette_description(cmd)),
                     Style::default()
                         .fg(theme.modal_description())
                         .add_modifier(Modifier::ITALIC),
@@ -67,34 +67,15 @@ fn calculate_column_widths(headers: &[String], rows: &[Vec<String>]) -> Vec<usiz
     col_widths
 }
 
-/// Render a complete table with headers, alignments, and rows
-///
-/// # Arguments
-/// * `headers` - Column headers
-/// * `alignments` - Column alignments
-/// * `rows` - Data rows
-/// * `theme` - Color theme
-/// * `is_selected` - Whether the table element is selected
-/// * `in_table_mode` - Whether we're in table cell navigation mode
-/// * `selected_cell` - Currently selected cell (row, col) if in table mode
-/// * `available_width` - Optional maximum width to constrain table to
-#[allow(clippy::too_many_arguments)]
-pub fn render_table(
+/// Final column widths (content plus padding) for a table whose lines start
+/// with `prefix_width` columns of selection marker, shrunk to fit
+/// `available_width` when given.
+fn layout_column_widths(
     headers: &[String],
-    alignments: &[Alignment],
     rows: &[Vec<String>],
-    theme: &Theme,
-    is_selected: bool,
-    in_table_mode: bool,
-    selected_cell: Option<(usize, usize)>,
+    prefix_width: usize,
     available_width: Option<u16>,
-) -> Vec<Line<'static>> {
-    let mut lines = Vec::new();
-
-    if headers.is_empty() {
-        return lines;
-    }
-
+) -> Vec<usize> {
     let col_count = headers.len();
 
     // Calculate column widths using content-weighted approach
@@ -111,7 +92,6 @@ pub fn render_table(
     // Smart table collapsing: shrink columns proportionally if table is too wide
     if let Some(max_width) = available_width {
         let max_width = max_width as usize;
-        let prefix_width = if in_table_mode || is_selected { 2 } else { 0 };
         let border_width = col_count + 1; // │ between and around columns
 
         // Try shrinking with progressively less padding
@@ -180,6 +160,55 @@ pub fn render_table(
         }
     }
 
+    col_widths
+}
+
+/// Columns `[start, end)` covered by column `col` of a table rendered in
+/// table navigation mode, counted from the start of its lines.
+pub fn table_column_span(
+    headers: &[String],
+    rows: &[Vec<String>],
+    col: usize,
+    available_width: Option<u16>,
+) -> Option<(usize, usize)> {
+    let widths = layout_column_widths(headers, rows, 2, available_width);
+    let width = *widths.get(col)?;
+    // Selection marker, then a border before every column
+    let start = 2 + widths[..col].iter().sum::<usize>() + col + 1;
+    Some((start, start + width))
+}
+
+/// Render a complete table with headers, alignments, and rows
+///
+/// # Arguments
+/// * `headers` - Column headers
+/// * `alignments` - Column alignments
+/// * `rows` - Data rows
+/// * `theme` - Color theme
+/// * `is_selected` - Whether the table element is selected
+/// * `in_table_mode` - Whether we're in table cell navigation mode
+/// * `selected_cell` - Currently selected cell (row, col) if in table mode
+/// * `available_width` - Optional maximum width to constrain table to
+#[allow(clippy::too_many_arguments)]
+pub fn render_table(
+    headers: &[String],
+    alignments: &[Alignment],
+    rows: &[Vec<String>],
+    theme: &Theme,
+    is_selected: bool,
+    in_table_mode: bool,
+    selected_cell: Option<(usize, usize)>,
+    available_width: Option<u16>,
+) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+
+    if headers.is_empty() {
+        return lines;
+    }
+
+    let prefix_width = if in_table_mode || is_selected { 2 } else { 0 };
+    let col_widths = layout_column_widths(headers, rows, prefix_width, available_width);
+
     // Top border (add selection indicator or spacing)
     let mut top_border_spans = vec![];
 
@@ -667,6 +696,41 @@ mod tests {
                 assert!(!lines.is_empty(), "Table should render at width {}", width);
             }
         }
+
+        #[test]
+        fn test_column_span_matches_rendered_columns() {
+            let theme = test_theme();
+            let headers = vec!["Name".to_string(), "Description".to_string()];
+            let rows = vec![vec!["treemd".to_string(), "x".repeat(80)]];
+            for width in [None, Some(40)] {
+                let lines = render_table(
+                    &headers,
+                    &[],
+                    &rows,
+                    &theme,
+                    true,
+                    true,
+                    Some((0, 1)),
+                    width,
+                );
+                let top: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+                let borders: Vec<usize> = top
+                    .chars()
+                    .enumerate()
+                    .filter(|(_, c)| matches!(c, '┌' | '┬' | '┐'))
+                    .map(|(i, _)| i)
+                    .collect();
+                assert_eq!(
+                    table_column_span(&headers, &rows, 0, width),
+                    Some((borders[0] + 1, borders[1]))
+                );
+                assert_eq!(
+                    table_column_span(&headers, &rows, 1, width),
+                    Some((borders[1] + 1, borders[2]))
+                );
+                assert_eq!(table_column_span(&headers, &rows, 2, width), None);
+            }
+        }
     }
 
     mod render_table_row_tests {
@@ -5,7 +5,7 @@
 use crate::parser::output::Alignment;
 use ratatui::layout::Rect;
 use ratatui::style::Style;
-use ratatui::text::{Line, Span};
+use ratatui::text::Span;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 const HALFWIDTH_KATAKANA_VOICED_SOUND_MARK: char = '\u{FF9E}';
@@ -208,47 +208,63 @@ pub fn align_text(text: &str, width: usize, alignment: &Alignment) -> String {
     }
 }
 
-/// Highlight search matches within text, returning a Line with styled spans.
+/// Byte ranges of the case-insensitive, non-overlapping occurrences of
+/// `query` in `text`.
 ///
-/// Performs case-insensitive matching and splits the text into segments,
-/// applying the highlight style to matched portions.
-///
-/// # Arguments
-/// * `text` - The text to search within
-/// * `query` - The search query (case-insensitive)
-/// * `base_style` - Style for non-matched text
-/// * `highlight_style` - Style for matched text
-///
-/// # Returns
-/// A vector of Spans with appropriate styling applied
-pub fn highlight_search_matches(
-    text: &str,
-    query: &str,
-    base_style: Style,
-    highlight_style: Style,
-) -> Vec<Span<'static>> {
+/// Characters are compared by their lowercase forms one at a time, so the
+/// ranges are always valid for `text` even where lowercasing changes byte
+/// lengths (`İ` becomes three bytes). A match never covers part of a
+/// character.
+pub fn find_ignore_case(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let query: Vec<char> = query.chars().flat_map(char::to_lowercase).collect();
     if query.is_empty() {
-        return vec![Span::styled(text.to_string(), base_style)];
+        return Vec::new();
     }
 
-    let text_lower = text.to_lowercase();
-    let query_lower = query.to_lowercase();
+    let mut found = Vec::new();
+    let mut start = 0;
+    while start < text.len() {
+        match match_len_at(&text[start..], &query) {
+            Some(len) => {
+                found.push((start, start + len));
+                start += len;
+            }
+            None => start += text[start..].chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    found
+}
+
+/// Byte length of the prefix of `text` whose lowercase form is `query`.
+fn match_len_at(text: &str, query: &[char]) -> Option<usize> {
+    let mut expected = query.iter();
+    let mut next = expected.next();
+    for (i, c) in text.char_indices() {
+        for lower in c.to_lowercase() {
+            match next {
+                Some(&q) if q == lower => next = expected.next(),
+                _ => return None,
+            }
+        }
+        if next.is_none() {
+            return Some(i + c.len_utf8());
+        }
+    }
+    None
+}
 
+/// Split `text` into spans, applying `highlight_style` to the given byte
+/// ranges (sorted, non-overlapping) and `base_style` to the rest.
+pub fn highlight_ranges(
+    text: &str,
+    ranges: &[(usize, usize)],
+    base_style: Style,
+    highlight_style: Style,
+) -> Vec<Span<'static>> {
     let mut spans = Vec::new();
     let mut last_end = 0;
 
-    // Find all matches
-    let mut search_start = 0;
-    while let Some(rel_pos) = text_lower[search_start..].find(&query_lower) {
-        let match_start = search_start + rel_pos;
-        let match_end = match_start + query.len();
-
-        // Verify char boundaries
-        if !text.is_char_boundary(match_start) || !text.is_char_boundary(match_end) {
-            search_start = match_start + 1;
-            continue;
-        }
-
+    for &(match_start, match_end) in ranges {
         // Add text before match
         if match_start > last_end {
             spans.push(Span::styled(
@@ -264,11 +280,6 @@ pub fn highlight_search_matches(
         ));
 
         last_end = match_end;
-        search_start = match_end;
-
-        if search_start >= text.len() {
-            break;
-        }
     }
 
     // Add remaining text after last match
@@ -284,39 +295,6 @@ pub fn highlight_search_matches(
     spans
 }
 
-/// Build a Line with optional search highlighting.
-///
-/// Convenience wrapper that builds a complete Line, optionally with a prefix.
-///
-/// # Arguments
-/// * `prefix` - Optional prefix spans to prepend
-/// * `text` - The main text content
-/// * `query` - Optional search query for highlighting
-/// * `base_style` - Style for non-matched text
-/// * `highlight_style` - Style for matched text
-pub fn build_highlighted_line(
-    prefix: Vec<Span<'static>>,
-    text: &str,
-    query: Option<&str>,
-    base_style: Style,
-    highlight_style: Style,
-) -> Line<'static> {
-    let mut spans = prefix;
-
-    if let Some(q) = query {
-        spans.extend(highlight_search_matches(
-            text,
-            q,
-            base_style,
-            highlight_style,
-        ));
-    } else {
-        spans.push(Span::styled(text.to_string(), base_style));
-    }
-
-    Line::from(spans)
-}
-
 /// Strip YAML frontmatter from the beginning of a document.
 ///
 /// Frontmatter must:
@@ -827,6 +805,114 @@ pub fn filter_content(
     result
 }
 
+/// 1-based line in `source` of each line of `text`, a copy of `source`
+/// from line `first_line` on with lines trimmed, dropped or edited (section
+/// extraction, hidden front matter and LaTeX).
+///
+/// Lines are matched in order, ignoring surrounding whitespace. Lines with no
+/// match (edited) are placed just before the next matched line.
+pub fn source_line_map(source: &str, first_line: usize, text: &str) -> Vec<usize> {
+    let source_lines: Vec<&str> = source
+        .lines()
+        .skip(first_line.saturating_sub(1))
+        .map(str::trim)
+        .collect();
+    let mut next = 0;
+    let mut indices: Vec<Option<usize>> = text
+        .lines()
+        .map(|line| {
+            let line = line.trim();
+            let found = source_lines[next.min(source_lines.len())..]
+                .iter()
+                .position(|&s| s == line)
+                .map(|i| next + i);
+            if let Some(index) = found {
+                next = index + 1;
+            }
+            found
+        })
+        .collect();
+
+    let mut following = None;
+    for slot in indices.iter_mut().rev() {
+        if slot.is_none() {
+            *slot = following.map(|f: usize| f.saturating_sub(1));
+        }
+        following = *slot;
+    }
+    let mut previous: Option<usize> = None;
+    indices
+        .into_iter()
+        .map(|slot| {
+            let index = slot.unwrap_or_else(|| previous.map_or(0, |p| p + 1));
+            previous = Some(index);
+            first_line.max(1) + index
+        })
+        .collect()
+}
+
+/// `parts` joined by ` › `, dropping parts from the left (marked by `…`) so
+/// the trail fits in `width` columns. The last part is cut from its start if
+/// even it alone does not fit.
+pub fn breadcrumb(parts: &[&str], width: usize) -> String {
+    const SEP: &str = " › ";
+    for skip in 0..parts.len() {
+        let mut trail = parts[skip..].join(SEP);
+        if skip > 0 {
+            trail = format!("…{}{}", SEP, trail);
+        }
+        if terminal_width(&trail) <= width {
+            return trail;
+        }
+    }
+    let Some(last) = parts.last() else {
+        return String::new();
+    };
+    if width == 0 {
+        return String::new();
+    }
+    let mut tail: Vec<char> = Vec::new();
+    let mut used = 1; // the leading …
+    for c in last.chars().rev() {
+        let w = terminal_width(c.encode_utf8(&mut [0; 4]));
+        if used + w > width {
+            break;
+        }
+        used += w;
+        tail.push(c);
+    }
+    std::iter::once('…').chain(tail.into_iter().rev()).collect()
+}
+
+/// Vim-style position of a view scrolled to `scroll` out of `max_scroll`:
+/// `All` when everything fits, `Top`, `Bot`, or the percentage in between.
+pub fn scroll_position_label(scroll: u16, max_scroll: u16) -> String {
+    if max_scroll == 0 {
+        "All".to_string()
+    } else if scroll == 0 {
+        "Top".to_string()
+    } else if scroll >= max_scroll {
+        "Bot".to_string()
+    } else {
+        format!("{}%", scroll as usize * 100 / max_scroll as usize)
+    }
+}
+
+/// Scroll offset that brings columns `[start, end)` into a `width`-wide
+/// view scrolled to `scroll`, moving as little as possible. When the range is
+/// wider than the view, its start wins.
+pub fn scroll_to_reveal(scroll: u16, start: usize, end: usize, width: u16) -> u16 {
+    let start = start.min(u16::MAX as usize) as u16;
+    let end = end.min(u16::MAX as usize) as u16;
+    if start < scroll {
+        start
+    } else if end > scroll.saturating_add(width) {
+        end.saturating_sub(width).min(start)
+    } else {
+        scroll
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1312,6 +1398,20 @@ mod tests {
         use super::*;
         use ratatui::style::Color;
 
+        fn highlight_search_matches(
+            text: &str,
+            query: &str,
+            base_style: Style,
+            highlight_style: Style,
+        ) -> Vec<Span<'static>> {
+            highlight_ranges(
+                text,
+                &find_ignore_case(text, query),
+                base_style,
+                highlight_style,
+            )
+        }
+
         #[test]
         fn test_no_match() {
             let base = Style::default().fg(Color::White);
@@ -1371,5 +1471,114 @@ mod tests {
             assert_eq!(spans[0].style, highlight);
             assert_eq!(spans[1].content.as_ref(), " World");
         }
+
+        #[test]
+        fn test_match_after_char_that_grows_when_lowercased() {
+            // `İ` is two bytes but lowercases to three; searching the
+            // lowercased copy used to slice the original mid-character
+            let base = Style::default().fg(Color::White);
+            let highlight = Style::default().fg(Color::Yellow);
+            let spans = highlight_search_matches("İé and É", "é", base, highlight);
+            let highlighted: Vec<&str> = spans
+                .iter()
+                .filter(|s| s.style == highlight)
+                .map(|s| s.content.as_ref())
+                .collect();
+            assert_eq!(highlighted, ["é", "É"]);
+        }
+
+        #[test]
+        fn test_find_ignore_case_ranges_index_original_text() {
+            let text = "İstanbul ISTANBUL ẞtraße";
+            let found = find_ignore_case(text, "stan");
+            let words: Vec<&str> = found.iter().map(|&(s, e)| &text[s..e]).collect();
+            assert_eq!(words, ["stan", "STAN"]);
+            // Multi-char query matched against a char's full lowercase form
+            assert_eq!(find_ignore_case("İ", "i\u{307}"), [(0, 2)]);
+            // Never half a character
+            assert!(find_ignore_case("İ", "i").is_empty());
+            assert!(find_ignore_case("abc", "").is_empty());
+            assert_eq!(find_ignore_case("aaaa", "aa"), [(0, 2), (2, 4)]);
+        }
+    }
+
+    mod scroll_to_reveal_tests {
+        use super::*;
+
+        #[test]
+        fn test_visible_range_keeps_scroll() {
+            assert_eq!(scroll_to_reveal(10, 12, 20, 20), 10);
+        }
+
+        #[test]
+        fn test_moves_just_enough_either_way() {
+            assert_eq!(scroll_to_reveal(10, 4, 8, 20), 4);
+            assert_eq!(scroll_to_reveal(0, 30, 38, 20), 18);
+        }
+
+        #[test]
+        fn test_range_wider_than_view_shows_its_start() {
+            assert_eq!(scroll_to_reveal(0, 30, 80, 20), 30);
+        }
+    }
+
+    mod source_line_map_tests {
+        use super::*;
+
+        #[test]
+        fn test_section_lines_map_past_trimmed_blank_lines() {
+            let source = "# A\n\n\nIntro\n\n```\ncode\n```\n";
+            let section = "Intro\n\n```\ncode\n```";
+            assert_eq!(source_line_map(source, 2, section), [4, 5, 6, 7, 8]);
+        }
+
+        #[test]
+        fn test_dropped_and_edited_lines_keep_later_numbers() {
+            let source = "---\ntitle: x\n---\nText $x$ here\n\nEnd\n";
+            let shown = "Text  here\n\nEnd";
+            assert_eq!(source_line_map(source, 1, shown), [4, 5, 6]);
+
+            let shown = "End\nappended";
+            assert_eq!(source_line_map(source, 1, shown), [6, 7]);
+        }
+    }
+
+    mod breadcrumb_tests {
+        use super::*;
+
+        #[test]
+        fn test_fits_whole_path() {
+            assert_eq!(
+                breadcrumb(&["Intro", "Setup", "Config"], 80),
+                "Intro › Setup › Config"
+            );
+            assert_eq!(breadcrumb(&[], 80), "");
+        }
+
+        #[test]
+        fn test_drops_ancestors_from_the_left() {
+            let parts = ["Introduction", "Setup", "Config"];
+            assert_eq!(breadcrumb(&parts, 20), "… › Setup › Config");
+            assert_eq!(breadcrumb(&parts, 12), "… › Config");
+        }
+
+        #[test]
+        fn test_cuts_a_lone_heading_from_its_start() {
+            assert_eq!(breadcrumb(&["Installation"], 6), "…ation");
+            assert_eq!(terminal_width(&breadcrumb(&["日本語の見出し"], 7)), 7);
+        }
+    }
+
+    mod scroll_position_label_tests {
+        use super::*;
+
+        #[test]
+        fn test_labels_like_vim() {
+            assert_eq!(scroll_position_label(0, 0), "All");
+            assert_eq!(scroll_position_label(0, 50), "Top");
+            assert_eq!(scroll_position_label(50, 50), "Bot");
+            assert_eq!(scroll_position_label(60, 50), "Bot");
+            assert_eq!(scroll_position_label(6, 50), "12%");
+        }
     }
 }
@@ -0,0 +1,197 @@
+//! Where each file was last left, so it reopens at the same heading.
+//!
+//! Positions are kept in `<config dir>/treemd/view_state.json`, newest last.
+//! A heading is stored as the path of heading texts leading to it rather
+//! than an index, and found again by anchor, so a remembered position
+//! survives edits elsewhere in the file.
+
+use crate::parser::HeadingNode;
+use crate::parser::content::{anchor_matches, slugify};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// How many files keep a remembered position; the oldest are forgotten.
+pub const VIEW_STATE_LIMIT: usize = 500;
+
+/// Where one file was left.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileView {
+    /// Canonical path of the file
+    pub path: PathBuf,
+    /// Texts of the selected heading and its ancestors, outermost first
+    /// (empty for the document overview)
+    #[serde(default)]
+    pub heading_path: Vec<String>,
+    /// Content scroll within the heading's section
+    #[serde(default)]
+    pub content_scroll: u16,
+}
+
+/// Remembered positions, least recently left first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ViewState {
+    #[serde(default)]
+    files: Vec<FileView>,
+}
+
+impl ViewState {
+    /// Default state file (`<config dir>/treemd/view_state.json`).
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::config_dir().map(|p| p.join("treemd").join("view_state.json"))
+    }
+
+    /// Load positions from `path`; a missing or unreadable file remembers nothing.
+    pub fn load(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the positions to `path`, replacing the file in one step so two
+    /// instances exiting together can't leave it half-written.
+    pub fn save(&self, path: &Path) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let contents = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        crate::tui::edits::write_atomically(path, &contents).map_err(std::io::Error::other)
+    }
+
+    /// Where `file` was last left.
+    pub fn get(&self, file: &Path) -> Option<&FileView> {
+        self.files.iter().find(|view| view.path == file)
+    }
+
+    /// Record `view` as its file's position, keeping at most `limit` files.
+    pub fn remember(&mut self, view: FileView, limit: usize) {
+        self.files.retain(|existing| existing.path != view.path);
+        self.files.push(view);
+        let excess = self.files.len().saturating_sub(limit);
+        self.files.drain(..excess);
+    }
+}
+
+/// The heading `heading_path` names in `tree`, and whether the whole path
+/// still matched.
+///
+/// Each step matches a heading's anchor, so changes in case or punctuation
+/// don't lose it. If the path is gone, a heading with the same text
+/// elsewhere (moved under another parent) is next best, then the deepest
+/// ancestor still there.
+pub fn locate(tree: &[HeadingNode], heading_path: &[String]) -> Option<(usize, bool)> {
+    let mut level = tree;
+    let mut deepest = None;
+    for text in heading_path {
+        let anchor = slugify(text);
+        match level
+            .iter()
+            .find(|node| anchor_matches(&node.heading.text, &anchor))
+        {
+            Some(node) => {
+                deepest = Some(node.index);
+                level = &node.children;
+            }
+            None => {
+                let moved = heading_path
+                    .last()
+                    .and_then(|last| find_anywhere(tree, &slugify(last)));
+                return moved.or(deepest).map(|index| (index, false));
+            }
+        }
+    }
+    deepest.map(|index| (index, true))
+}
+
+/// First heading in `tree` (depth-first) whose anchor is `anchor`.
+fn find_anywhere(tree: &[HeadingNode], anchor: &str) -> Option<usize> {
+    tree.iter().find_map(|node| {
+        if anchor_matches(&node.heading.text, anchor) {
+            Some(node.index)
+        } else {
+            find_anywhere(&node.children, anchor)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_markdown;
+
+    fn path(texts: &[&str]) -> Vec<String> {
+        texts.iter().map(|t| t.to_string()).collect()
+    }
+
+    fn tree(markdown: &str) -> Vec<HeadingNode> {
+        parse_markdown(markdown).build_tree()
+    }
+
+    #[test]
+    fn locates_the_exact_path() {
+        let tree = tree("# Guide\n## Setup\n# Reference\n## Setup\n");
+        assert_eq!(
+            locate(&tree, &path(&["Reference", "Setup"])),
+            Some((3, true))
+        );
+        assert_eq!(locate(&tree, &path(&["Guide", "Setup"])), Some((1, true)));
+        assert_eq!(locate(&tree, &[]), None);
+    }
+
+    #[test]
+    fn small_edits_still_match() {
+        let tree = tree("# Guide\n## Setup!\n");
+        assert_eq!(locate(&tree, &path(&["guide", "Setup"])), Some((1, true)));
+    }
+
+    #[test]
+    fn falls_back_to_a_moved_heading_then_an_ancestor() {
+        let tree = tree("# Guide\n## Intro\n# Appendix\n## Setup\n");
+        assert_eq!(locate(&tree, &path(&["Guide", "Setup"])), Some((3, false)));
+        assert_eq!(locate(&tree, &path(&["Guide", "Gone"])), Some((0, false)));
+        assert_eq!(locate(&tree, &path(&["Gone", "Also gone"])), None);
+    }
+
+    #[test]
+    fn remembers_the_newest_files() {
+        let view = |name: &str, scroll| FileView {
+            path: PathBuf::from(name),
+            heading_path: path(&["Intro"]),
+            content_scroll: scroll,
+        };
+        let mut state = ViewState::default();
+        state.remember(view("a.md", 1), 2);
+        state.remember(view("b.md", 2), 2);
+        state.remember(view("a.md", 3), 2);
+        state.remember(view("c.md", 4), 2);
+        assert_eq!(state.get(Path::new("b.md")), None);
+        assert_eq!(
+            state.get(Path::new("a.md")).map(|v| v.content_scroll),
+            Some(3)
+        );
+        assert_eq!(
+            state.get(Path::new("c.md")).map(|v| v.content_scroll),
+            Some(4)
+        );
+    }
+
+    #[test]
+    fn round_trips_through_a_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("nested").join("view_state.json");
+        assert_eq!(ViewState::load(&file), ViewState::default());
+
+        let mut state = ViewState::default();
+        state.remember(
+            FileView {
+                path: PathBuf::from("/docs/README.md"),
+                heading_path: path(&["Install", "From source"]),
+                content_scroll: 12,
+            },
+            VIEW_STATE_LIMIT,
+        );
+        state.save(&file).unwrap();
+        assert_eq!(ViewState::load(&file), state);
+    }
+}
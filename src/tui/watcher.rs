@@ -11,6 +11,13 @@ use std::path::PathBuf;
 use std::sync::mpsc::{self, Receiver, TryRecvError};
 use std::time::{Duration, Instant};
 
+/// How long the file must stay quiet after a write before it is reloaded,
+/// so an editor's burst of writes for one save reloads once.
+const QUIET_PERIOD: Duration = Duration::from_millis(100);
+
+/// Longest a reload waits while writes keep arriving.
+const MAX_DEBOUNCE: Duration = Duration::from_secs(1);
+
 /// Manages file watching for live reload.
 pub struct FileWatcher {
     watcher: RecommendedWatcher,
@@ -18,9 +25,8 @@ pub struct FileWatcher {
     current_path: Option<PathBuf>,
     /// The directory path actually being watched (parent of current_path)
     watched_dir: Option<PathBuf>,
-    /// Timestamp of the first relevant event in the current debounce window
-    debounce_start: Option<Instant>,
-    debounce_duration: Duration,
+    /// First and latest relevant events of the pending reload
+    pending: Option<(Instant, Instant)>,
 }
 
 impl FileWatcher {
@@ -34,8 +40,7 @@ impl FileWatcher {
             receiver: rx,
             current_path: None,
             watched_dir: None,
-            debounce_start: None,
-            debounce_duration: Duration::from_millis(100),
+            pending: None,
         })
     }
 
@@ -54,7 +59,7 @@ impl FileWatcher {
         self.watcher.watch(dir_path, RecursiveMode::NonRecursive)?;
         self.current_path = Some(path.to_path_buf());
         self.watched_dir = Some(dir_path.to_path_buf());
-        self.debounce_start = None;
+        self.pending = None;
 
         Ok(())
     }
@@ -67,15 +72,16 @@ impl FileWatcher {
         }
         self.current_path = None;
         self.watched_dir = None;
-        self.debounce_start = None;
+        self.pending = None;
     }
 
     /// Check if the watched file has been modified.
     /// Returns true if a reload should be triggered.
     ///
-    /// Uses non-blocking drain with time-based debouncing: the first relevant
-    /// event starts a debounce window, and we only signal a reload once the
-    /// window has elapsed on a subsequent poll. This avoids blocking the event loop.
+    /// Uses non-blocking drain with time-based debouncing: a reload is only
+    /// signalled once no relevant event has arrived for [`QUIET_PERIOD`] (or
+    /// after [`MAX_DEBOUNCE`] of continuous writes). This avoids blocking the
+    /// event loop and reloading halfway through a save.
     pub fn check_for_changes(&mut self) -> bool {
         // Drain all pending events (non-blocking)
         let mut saw_relevant = false;
@@ -95,16 +101,17 @@ impl FileWatcher {
             }
         }
 
+        let now = Instant::now();
         if saw_relevant {
-            // Start debounce window if not already running
-            self.debounce_start.get_or_insert_with(Instant::now);
+            // Each write restarts the quiet period
+            let first = self.pending.map_or(now, |(first, _)| first);
+            self.pending = Some((first, now));
         }
 
-        // Check if debounce window has elapsed
-        if let Some(start) = self.debounce_start
-            && start.elapsed() >= self.debounce_duration
+        if let Some((first, last)) = self.pending
+            && settled(first, last, now)
         {
-            self.debounce_start = None;
+            self.pending = None;
             return true;
         }
 
@@ -186,6 +193,11 @@ impl FileWatcher {
     }
 }
 
+/// Whether writes seen from `first` to `last` are done by `now`.
+fn settled(first: Instant, last: Instant, now: Instant) -> bool {
+    now.duration_since(last) >= QUIET_PERIOD || now.duration_since(first) >= MAX_DEBOUNCE
+}
+
 impl Default for FileWatcher {
     fn default() -> Self {
         Self::new().expect("Failed to create file watcher")
@@ -201,4 +213,16 @@ mod tests {
         let watcher = FileWatcher::new();
         assert!(watcher.is_ok());
     }
+
+    #[test]
+    fn reload_waits_for_writes_to_stop() {
+        let start = Instant::now();
+        let ms = |n| start + Duration::from_millis(n);
+        // A save that writes at 0, 40 and 80 ms reloads 100 ms after the last
+        assert!(!settled(start, ms(80), ms(150)));
+        assert!(settled(start, ms(80), ms(180)));
+        // Writes that never stop still reload after a second
+        assert!(!settled(start, ms(950), ms(990)));
+        assert!(settled(start, ms(990), ms(1000)));
+    }
 }
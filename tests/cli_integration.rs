@@ -61,12 +61,19 @@ fn fixture_file() -> PathBuf {
     path
 }
 
+/// The binary, with its query history kept out of the user's data dir.
+fn treemd() -> Command {
+    let mut command = Command::new(bin());
+    command.env(
+        "TREEMD_QUERY_HISTORY",
+        std::env::temp_dir().join(format!("treemd-it-history-{}", std::process::id())),
+    );
+    command
+}
+
 /// Run treemd with args, return (stdout, stderr, exit code).
 fn run(args: &[&str]) -> (String, String, i32) {
-    let out = Command::new(bin())
-        .args(args)
-        .output()
-        .expect("spawn treemd");
+    let out = treemd().args(args).output().expect("spawn treemd");
     (
         String::from_utf8_lossy(&out.stdout).into_owned(),
         String::from_utf8_lossy(&out.stderr).into_owned(),
@@ -76,7 +83,7 @@ fn run(args: &[&str]) -> (String, String, i32) {
 
 /// Run treemd with stdin piped from `input` and the given args.
 fn run_with_stdin(args: &[&str], input: &str) -> (String, String, i32) {
-    let mut child = Command::new(bin())
+    let mut child = treemd()
         .args(args)
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
@@ -98,7 +105,7 @@ fn run_with_stdin(args: &[&str], input: &str) -> (String, String, i32) {
 }
 
 // ------------------------------------------------------------------
-// --version / --help — basic CLI plumbing
+// --version / --help / --list-keys — basic CLI plumbing
 // ------------------------------------------------------------------
 
 #[test]
@@ -129,6 +136,23 @@ fn query_help_prints_query_docs_and_exits_zero() {
     assert!(stdout.contains("Query Language") || stdout.contains("ELEMENT SELECTORS"));
 }
 
+#[test]
+fn list_keys_prints_bindings_without_input() {
+    let (stdout, _, code) = run(&["--list-keys"]);
+    assert_eq!(code, 0);
+    assert!(stdout.starts_with("Normal\n"));
+    assert!(stdout.contains("Move to next item"));
+
+    let (stdout, _, code) = run(&["--list-keys=toml"]);
+    assert_eq!(code, 0);
+    assert!(stdout.starts_with("[keybindings]\nsequence_timeout_ms = "));
+    assert!(stdout.contains("\n[keybindings.Normal]\n"));
+
+    let (_, stderr, code) = run(&["--list-keys=yaml"]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("yaml"));
+}
+
 // ------------------------------------------------------------------
 // --list (plain / json) and filtering
 // ------------------------------------------------------------------
@@ -262,10 +286,149 @@ fn count_reports_per_level_and_total() {
     assert!(stdout.contains("Total: 5"));
 }
 
+// ------------------------------------------------------------------
+// --tts
+// ------------------------------------------------------------------
+
+#[test]
+fn tts_reads_plain_sentences() {
+    let f = fixture_file();
+    let (stdout, _, code) = run(&["--tts", f.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(stdout.starts_with("Heading level 1: Title\nIntro paragraph.\n"));
+    assert!(stdout.contains("Heading level 3: Advanced\n"));
+    assert!(stdout.contains("Code block in Rust\n"));
+    assert!(!stdout.contains('#'), "no markup in output: {stdout}");
+
+    let (stdout, _, code) = run(&["--tts", "--tts-skip-code", f.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(!stdout.contains("Code block"));
+}
+
+// ------------------------------------------------------------------
+// --export html
+// ------------------------------------------------------------------
+
+#[test]
+fn export_html_writes_a_standalone_page() {
+    let f = fixture_file();
+    let (stdout, stderr, code) = run(&["--export", "html", f.to_str().unwrap()]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(stdout.starts_with("<!DOCTYPE html>"));
+    assert!(stdout.contains("<title>Title</title>"));
+    assert!(stdout.contains("<style>"));
+    assert!(stdout.contains("<h2 id=\"installation\">Installation</h2>"));
+    assert!(stdout.contains("<code class=\"language-rust\">"));
+
+    let (stdout, _, code) = run(&["--export", "html", "-s", "Usage", f.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("<h2 id=\"usage\">Usage</h2>"));
+    assert!(stdout.contains("<h3 id=\"advanced\">Advanced</h3>"));
+    assert!(!stdout.contains("Installation"));
+    assert!(!stdout.contains("Conclusion"));
+}
+
+// ------------------------------------------------------------------
+// --dump-theme
+// ------------------------------------------------------------------
+
+#[test]
+fn dump_theme_prints_a_custom_theme_block() {
+    let (stdout, stderr, code) = run(&["--dump-theme", "Gruvbox"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(stdout.starts_with("[[theme.custom]]\nname = \"GruvboxCustom\"\nbase = \"Gruvbox\"\n"));
+    assert!(stdout.contains("\nbackground = { rgb = ["));
+
+    let (_, stderr, code) = run(&["--dump-theme", "NoSuchTheme"]);
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("unknown theme 'NoSuchTheme'"),
+        "stderr: {stderr}"
+    );
+}
+
+#[test]
+fn print_renders_plain_text_when_piped() {
+    let path = fixture_file();
+    let (stdout, stderr, code) = run(&["--print", path.to_str().unwrap()]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(!stdout.contains('\x1b'), "stdout: {stdout}");
+    assert!(!stdout.contains("## Usage"));
+    assert!(stdout.contains("Usage\n"));
+    assert!(stdout.contains("let x = 1;"));
+
+    let (stdout, _, code) = run(&["--print", "-s", "Usage", path.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert!(stdout.contains("Deep section."));
+    assert!(!stdout.contains("Install steps here."));
+}
+
+#[test]
+fn completions_offer_output_formats_and_theme_names() {
+    let (stdout, stderr, code) = run(&["--completions", "fish"]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert!(stdout.contains("complete -c treemd"), "stdout: {stdout}");
+    assert!(stdout.contains("TokyoNight"));
+    assert!(stdout.contains("jsonl"));
+
+    let (_, _, code) = run(&["--completions", "tcsh"]);
+    assert_ne!(code, 0);
+}
+
+// ------------------------------------------------------------------
+// --json-tree
+// ------------------------------------------------------------------
+
+#[test]
+fn json_tree_exports_sections_with_typed_blocks() {
+    let dir = std::env::temp_dir().join(format!("treemd-it-jsontree-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("doc.md");
+    std::fs::write(&path, "# Intro\n\nHello world.\n\n```sh\necho hi\n```\n").unwrap();
+
+    let (stdout, stderr, code) = run(&["--json-tree", path.to_str().unwrap()]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let v: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(v["schema"], "treemd.json-tree/1");
+    assert_eq!(v["blocks"], serde_json::json!([]));
+
+    let section = &v["sections"][0];
+    assert_eq!(section["type"], "section");
+    assert_eq!(section["level"], 1);
+    assert_eq!(section["title"], "Intro");
+    assert_eq!(section["heading_span"]["start_line"], 1);
+    assert_eq!(section["children"], serde_json::json!([]));
+
+    let blocks = section["blocks"].as_array().expect("blocks array");
+    assert_eq!(blocks.len(), 2);
+    assert_eq!(blocks[0]["type"], "paragraph");
+    assert_eq!(blocks[0]["span"]["start_line"], 3);
+    assert_eq!(blocks[1]["type"], "code");
+    assert_eq!(blocks[1]["language"], "sh");
+    assert_eq!(blocks[1]["content"], "echo hi");
+    assert_eq!(blocks[1]["span"]["start_line"], 5);
+    assert_eq!(blocks[1]["span"]["end_line"], 7);
+}
+
 // ------------------------------------------------------------------
 // -q / query mode
 // ------------------------------------------------------------------
 
+#[test]
+fn piped_queries_recall_history_without_adding_to_it() {
+    let f = fixture_file();
+    let history = f.with_file_name("query_history");
+    std::fs::write(&history, ".h3\n").unwrap();
+    let out = Command::new(bin())
+        .env("TREEMD_QUERY_HISTORY", &history)
+        .args(["-q", "!!", f.to_str().unwrap()])
+        .output()
+        .expect("spawn treemd");
+    assert!(out.status.success());
+    assert!(String::from_utf8_lossy(&out.stdout).contains("Advanced"));
+    assert_eq!(std::fs::read_to_string(&history).unwrap(), ".h3\n");
+}
+
 #[test]
 fn query_h2_returns_only_h2_headings() {
     let f = fixture_file();
@@ -286,6 +449,142 @@ fn query_invalid_syntax_exits_nonzero() {
     assert!(!stderr.is_empty(), "expected an error message on stderr");
 }
 
+#[test]
+fn query_over_several_files_prints_each_in_argument_order() {
+    let f = fixture_file();
+    let other = f.with_file_name("other.md");
+    std::fs::write(&other, "# Other\n\n## Only\n").expect("write other");
+    let (stdout, _, code) = run(&[
+        "-q",
+        ".h2 | text",
+        other.to_str().unwrap(),
+        f.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0, "stdout: {stdout}");
+    let expected = format!(
+        "==> {} <==\nOnly\n\n==> {} <==\nInstallation\nUsage\nConclusion\n",
+        other.display(),
+        f.display()
+    );
+    assert_eq!(stdout, expected);
+}
+
+#[test]
+fn query_over_several_files_as_jsonl_prints_an_object_per_file() {
+    let f = fixture_file();
+    let other = f.with_file_name("other-jsonl.md");
+    std::fs::write(&other, "# Other\n\n## Only\n").expect("write other");
+    let (stdout, _, code) = run(&[
+        "-q",
+        ".h2[0] | text",
+        "--query-output",
+        "jsonl",
+        other.to_str().unwrap(),
+        f.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0, "stdout: {stdout}");
+    let lines: Vec<serde_json::Value> = stdout
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("each line is JSON"))
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            serde_json::json!({"file": other.display().to_string(), "results": ["Only"]}),
+            serde_json::json!({"file": f.display().to_string(), "results": ["Installation"]}),
+        ]
+    );
+}
+
+#[test]
+fn query_over_several_files_as_json_prints_one_array() {
+    let f = fixture_file();
+    let other = f.with_file_name("other-json.md");
+    std::fs::write(&other, "# Other\n\n## Only\n").expect("write other");
+    let expected = serde_json::json!([
+        {"file": other.display().to_string(), "results": ["Only"]},
+        {"file": f.display().to_string(), "results": ["Installation"]},
+    ]);
+    for format in ["json", "json-pretty"] {
+        let (stdout, _, code) = run(&[
+            "-q",
+            ".h2[0] | text",
+            "--query-output",
+            format,
+            other.to_str().unwrap(),
+            f.to_str().unwrap(),
+        ]);
+        assert_eq!(code, 0, "stdout: {stdout}");
+        let parsed: serde_json::Value = serde_json::from_str(&stdout).expect("one JSON document");
+        assert_eq!(parsed, expected, "{format}");
+    }
+}
+
+#[test]
+fn query_over_several_files_exits_nonzero_when_one_fails() {
+    let f = fixture_file();
+    let missing = f.with_file_name("missing.md");
+    let (stdout, stderr, code) = run(&[
+        "-q",
+        ".h2[0] | text",
+        missing.to_str().unwrap(),
+        f.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 1, "stderr: {stderr}");
+    assert!(stderr.contains("missing.md"), "stderr: {stderr}");
+    assert!(
+        stdout.contains("Installation"),
+        "the other file still prints"
+    );
+}
+
+#[test]
+fn raw_output_drops_json_quotes_from_strings() {
+    let f = fixture_file();
+    let query = ["-q", ".h2[0:2] | text", "--query-output", "jsonl"];
+    let (quoted, _, code) = run(&[&query[..], &[f.to_str().unwrap()]].concat());
+    assert_eq!(code, 0);
+    assert_eq!(quoted, "\"Installation\"\n\"Usage\"\n");
+
+    let (raw, _, code) = run(&[&query[..], &["-r", f.to_str().unwrap()]].concat());
+    assert_eq!(code, 0);
+    assert_eq!(raw, "Installation\nUsage\n");
+}
+
+#[test]
+fn query_file_runs_the_saved_query() {
+    let f = fixture_file();
+    let saved = f.with_file_name("headings.tql");
+    std::fs::write(&saved, ".h2\n  | select(contains(\"Usage\"))\n  | text\n")
+        .expect("write query");
+    let (stdout, _, code) = run(&["--query-file", saved.to_str().unwrap(), f.to_str().unwrap()]);
+    assert_eq!(code, 0, "stdout: {stdout}");
+    assert_eq!(stdout, "Usage\n");
+
+    // An unreadable file is reported as such, not as a query error
+    let missing = f.with_file_name("missing.tql");
+    let (_, stderr, code) = run(&[
+        "--query-file",
+        missing.to_str().unwrap(),
+        f.to_str().unwrap(),
+    ]);
+    assert_ne!(code, 0);
+    assert!(
+        stderr.contains("cannot read query file"),
+        "stderr: {stderr}"
+    );
+
+    let (_, stderr, code) = run(&[
+        "--query-file",
+        saved.to_str().unwrap(),
+        "-q",
+        ".h2",
+        f.to_str().unwrap(),
+    ]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("cannot be used with"), "stderr: {stderr}");
+}
+
 // ------------------------------------------------------------------
 // stdin piping
 // ------------------------------------------------------------------
@@ -354,6 +653,131 @@ fn at_line_zero_is_rejected() {
     assert!(stderr.contains(">= 1"));
 }
 
+// ------------------------------------------------------------------
+// --resolve-anchor
+// ------------------------------------------------------------------
+
+#[test]
+fn resolve_anchor_prints_heading_line_and_offset() {
+    let f = fixture_file();
+    let line = FIXTURE
+        .lines()
+        .position(|l| l.starts_with("## Installation"))
+        .expect("Installation heading present")
+        + 1;
+    let offset = FIXTURE.find("## Installation").unwrap();
+
+    let (stdout, stderr, code) = run(&["--resolve-anchor", "installation", f.to_str().unwrap()]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    assert_eq!(stdout.trim(), format!("{line} {offset}"));
+
+    let (stdout, _, code) = run(&[
+        "--resolve-anchor",
+        "#installation",
+        "--format",
+        "json",
+        f.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0);
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(json["line"], line);
+    assert_eq!(json["offset"], offset);
+    assert_eq!(json["heading"], "Installation");
+    assert_eq!(json["end_offset"], FIXTURE.find("## Usage").unwrap());
+}
+
+#[test]
+fn resolved_repeated_headings_report_their_own_anchor() {
+    let f = fixture_file().with_file_name("repeated.md");
+    std::fs::write(&f, "# Log\n\n## Notes\n\na\n\n## Notes\n\nb\n").unwrap();
+    let (stdout, stderr, code) = run(&[
+        "--resolve-anchor",
+        "notes-1",
+        "--format",
+        "json",
+        f.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0, "stderr: {stderr}");
+    let json: serde_json::Value = serde_json::from_str(&stdout).expect("valid JSON");
+    assert_eq!(json["anchor"], "notes-1");
+    assert_eq!(json["line"], 7);
+}
+
+#[test]
+fn resolve_missing_anchor_exits_nonzero() {
+    let f = fixture_file();
+    let (stdout, stderr, code) = run(&["--resolve-anchor", "no-such-section", f.to_str().unwrap()]);
+    assert_ne!(code, 0);
+    assert!(stdout.is_empty());
+    assert!(stderr.contains("no-such-section"));
+}
+
+#[test]
+fn toc_prints_linked_nested_list() {
+    let f = fixture_file();
+    let (stdout, _, code) = run(&["--toc", f.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert_eq!(
+        stdout,
+        "\
+- [Title](#title)
+  - [Installation](#installation)
+  - [Usage](#usage)
+    - [Advanced](#advanced)
+  - [Conclusion](#conclusion)
+"
+    );
+
+    let (stdout, _, code) = run(&[
+        "--toc",
+        "--max-level",
+        "2",
+        "--numbered",
+        f.to_str().unwrap(),
+    ]);
+    assert_eq!(code, 0);
+    assert_eq!(
+        stdout,
+        "1. [Title](#title)\n   1. [Installation](#installation)\n   2. [Usage](#usage)\n   3. [Conclusion](#conclusion)\n"
+    );
+
+    let (_, stderr, code) = run(&["--numbered", f.to_str().unwrap()]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--toc"), "stderr: {stderr}");
+
+    let (_, stderr, code) = run(&["--toc", "--max-level", "0", f.to_str().unwrap()]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--max-level"), "stderr: {stderr}");
+}
+
+#[test]
+fn toc_of_plain_text_is_empty() {
+    let f = fixture_file().with_file_name("notes.txt");
+    std::fs::write(&f, "just notes\n").expect("write notes");
+    let (stdout, _, code) = run(&["--toc", f.to_str().unwrap()]);
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "");
+}
+
+#[test]
+fn interactive_requires_a_query_and_excludes_query_output() {
+    let f = fixture_file();
+    let (_, stderr, code) = run(&["--interactive", f.to_str().unwrap()]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("--query"), "stderr: {stderr}");
+
+    let (_, stderr, code) = run(&[
+        "-q",
+        ".h2",
+        "--interactive",
+        "--query-output",
+        "json",
+        f.to_str().unwrap(),
+    ]);
+    assert_ne!(code, 0);
+    assert!(stderr.contains("cannot be used with"), "stderr: {stderr}");
+}
+
 // ------------------------------------------------------------------
 // -s with formatted heading text (regression: previously broken because
 // main.rs::extract_section did string-search on heading.text, which is
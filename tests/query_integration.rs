@@ -88,6 +88,144 @@ fn pipe_inside_object_restores_current() {
     }
 }
 
+#[test]
+fn object_construction_renames_and_shorthand_keys() {
+    let md = "# Doc\n\n## Install\n\n## Usage\n";
+    let doc = parse_markdown(md);
+    let fields = |q: &str| -> Vec<Vec<(String, String)>> {
+        query::execute(&doc, q)
+            .unwrap_or_else(|e| panic!("query {q:?} failed: {e}"))
+            .iter()
+            .map(|v| match v {
+                Value::Object(o) => o.iter().map(|(k, v)| (k.clone(), v.to_text())).collect(),
+                other => panic!("expected object, got {other:?}"),
+            })
+            .collect()
+    };
+    let pair = |k: &str, v: &str| (k.to_string(), v.to_string());
+
+    assert_eq!(
+        fields(".h2 | {title: .text, depth: .level}"),
+        vec![
+            vec![pair("title", "Install"), pair("depth", "2")],
+            vec![pair("title", "Usage"), pair("depth", "2")],
+        ]
+    );
+    // `{text}` is `{text: .text}`, and can be mixed with explicit pairs
+    assert_eq!(
+        fields(".h2[0] | {text, level, slug: (.text | slugify)}"),
+        vec![vec![
+            pair("text", "Install"),
+            pair("level", "2"),
+            pair("slug", "install")
+        ]]
+    );
+}
+
+/// Run a query expected to yield a single array; return its items as text.
+fn run_array(md: &str, q: &str) -> Vec<String> {
+    let doc = parse_markdown(md);
+    let out = query::execute(&doc, q).unwrap_or_else(|e| panic!("query {q:?} failed: {e}"));
+    match out.as_slice() {
+        [Value::Array(items)] => items.iter().map(|v| v.to_text()).collect(),
+        other => panic!("expected one array from {q:?}, got {other:?}"),
+    }
+}
+
+#[test]
+fn string_functions_split_join_and_case() {
+    let md = "# Getting Started\n## API Reference\n";
+    assert_eq!(
+        run_array(md, ".h1 | text | split(\" \")"),
+        vec!["Getting", "Started"]
+    );
+    assert_eq!(
+        run(md, "[.h | text] | join(\", \")"),
+        vec!["Getting Started, API Reference"]
+    );
+    assert_eq!(
+        run(md, ".h1 | text | split(\" \") | join(\"-\") | lower"),
+        vec!["getting-started"]
+    );
+    assert_eq!(run(md, ".h2 | text | upper"), vec!["API REFERENCE"]);
+    assert_eq!(run(md, "\"  padded \" | trim"), vec!["padded"]);
+}
+
+#[test]
+fn join_rejects_non_array() {
+    let doc = parse_markdown("# Title\n");
+    let err = query::execute(&doc, ".h1 | text | join(\", \")").unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected an array for join, found string")
+    );
+}
+
+#[test]
+fn substring_predicates_are_case_sensitive_by_default() {
+    let md = "# Error Codes\n## error handling\n## Overview\n";
+    assert_eq!(
+        run(md, ".h | select(.text | contains(\"Error\")) | text"),
+        vec!["Error Codes"]
+    );
+    assert_eq!(
+        run(md, ".h | select(.text | contains(\"error\", true)) | text"),
+        vec!["Error Codes", "error handling"]
+    );
+    assert_eq!(
+        run(md, ".h | select(startswith(\"err\")) | text"),
+        vec!["error handling"]
+    );
+    assert_eq!(
+        run(md, ".h | select(endswith(\"VIEW\", true)) | text"),
+        vec!["Overview"]
+    );
+}
+
+#[test]
+fn substring_predicates_handle_unicode() {
+    let md = "# Café Straße 🚀\n## ÉCOLE\n";
+    assert_eq!(run(md, ".h1 | contains(\"é S\")"), vec!["true"]);
+    assert_eq!(run(md, ".h1 | startswith(\"Caf\")"), vec!["true"]);
+    assert_eq!(run(md, ".h1 | startswith(\"Cafe\")"), vec!["false"]);
+    assert_eq!(run(md, ".h1 | endswith(\"ße 🚀\")"), vec!["true"]);
+    assert_eq!(run(md, ".h1 | endswith(\"\u{1F680}\")"), vec!["true"]);
+    assert_eq!(run(md, ".h2 | startswith(\"éc\")"), vec!["false"]);
+    assert_eq!(run(md, ".h2 | startswith(\"éc\", true)"), vec!["true"]);
+    assert_eq!(run(md, ".h2 | endswith(\"école\", true)"), vec!["true"]);
+}
+
+#[test]
+fn map_applies_expression_per_element() {
+    let md = "# T\n\n| a | b | c |\n|---|---|---|\n| 1 | 2 | 3 |\n| 4 | 5 |  |\n";
+    assert_eq!(
+        run_array(md, ".tables[0] | .rows | map(length)"),
+        vec!["3", "3"]
+    );
+    assert_eq!(
+        run_array("# A\n## B\n", "[.h] | map(.text | upper)"),
+        vec!["A", "B"]
+    );
+    // Elements for which the expression yields nothing are dropped
+    assert_eq!(
+        run_array("# X\n", "[1, 2, 3] | map(select(. > 1))"),
+        vec!["2", "3"]
+    );
+}
+
+#[test]
+fn map_flattens_multiple_outputs_per_element() {
+    assert_eq!(
+        run_array("# X\n", r#"["a,b", "c"] | map(split(",")[])"#),
+        vec!["a", "b", "c"]
+    );
+    assert_eq!(
+        run_array("# X\n", "[[1, 2], [3]] | map((.)[])"),
+        vec!["1", "2", "3"]
+    );
+    run_err("# X\n", "[1] | map()");
+}
+
 // ---------------------------------------------------------------------------
 // Arithmetic / lexer (item 3)
 // ---------------------------------------------------------------------------
@@ -190,27 +328,63 @@ fn sort_by_level() {
 }
 
 #[test]
-fn group_by_level() {
-    let md = "# A\n# B\n## C\n";
+fn sort_by_and_unique_consume_the_whole_stream() {
+    let md = "# Beta\n## Alpha\n### Gamma\n\n[x](u1) [y](u2) [z](u1) [w](1)\n";
+    assert_eq!(
+        run(md, ".h | sort_by(.text) | .text"),
+        vec!["Alpha", "Beta", "Gamma"]
+    );
+    assert_eq!(
+        run(md, ".h | sort_by(.level) | .text"),
+        vec!["Beta", "Alpha", "Gamma"]
+    );
+    // First occurrence wins; duplicates need not be adjacent
+    assert_eq!(run(md, ".link | .url | unique"), vec!["u1", "u2", "1"]);
+    // A number and the same digits as a string are different values
+    assert_eq!(run_array("# X\n", "[1, \"1\", 1] | unique").len(), 2);
+}
+
+#[test]
+fn sort_by_mixed_key_kinds_is_an_error() {
+    let md = "# Beta\n## Alpha\n";
+    run_err(md, ".h | sort_by(if .level > 1 then .text else .level end)");
+    run_err("# X\n", "[1, \"a\"] | sort_by(.)");
+}
+
+/// Run a group_by query and return each group's heading texts.
+fn groups(md: &str, q: &str) -> Vec<Vec<String>> {
     let doc = parse_markdown(md);
-    let out = query::execute(&doc, "[.h] | group_by(.level)").unwrap();
-    assert_eq!(out.len(), 1);
-    if let Value::Object(o) = &out[0] {
-        // Two groups: level 1 (A, B) and level 2 (C).
-        assert_eq!(o.len(), 2);
-        if let Some(Value::Array(g1)) = o.get("1") {
-            assert_eq!(g1.len(), 2);
-        } else {
-            panic!("missing level-1 group");
-        }
-        if let Some(Value::Array(g2)) = o.get("2") {
-            assert_eq!(g2.len(), 1);
-        } else {
-            panic!("missing level-2 group");
-        }
-    } else {
-        panic!("expected object, got {:?}", out[0]);
-    }
+    query::execute(&doc, q)
+        .unwrap_or_else(|e| panic!("query {q:?} failed: {e}"))
+        .iter()
+        .map(|group| match group {
+            Value::Array(items) => items
+                .iter()
+                .filter_map(|v| v.get_property("text").map(|t| t.to_text()))
+                .collect(),
+            other => panic!("expected one array per group, got {other:?}"),
+        })
+        .collect()
+}
+
+#[test]
+fn group_by_level() {
+    let md = "# A\n## C\n# B\n";
+    // Two groups in first-seen order: level 1 (A, B) and level 2 (C).
+    let expected = vec![vec!["A", "B"], vec!["C"]];
+    assert_eq!(groups(md, "[.h] | group_by(.level)"), expected);
+    assert_eq!(groups(md, ".h | group_by(.level)"), expected);
+    assert_eq!(groups(md, ".h | group(.level)"), expected);
+}
+
+#[test]
+fn group_by_empty_and_single_key() {
+    assert!(groups("# A\n", ".h2 | group_by(.level)").is_empty());
+    assert!(groups("# A\n", "[] | group_by(.level)").is_empty());
+    assert_eq!(
+        groups("## A\n## B\n## C\n", ".h | group_by(.level)"),
+        vec![vec!["A", "B", "C"]]
+    );
 }
 
 // ---------------------------------------------------------------------------
@@ -264,6 +438,213 @@ fn frontmatter_is_parsed() {
     assert_eq!(run(md, ".frontmatter.title"), vec!["Hi".to_string()]);
 }
 
+#[test]
+fn frontmatter_nested_and_only_at_document_start() {
+    let md = "\
+---
+title: Hello
+author:
+  name: Ann
+  tags: [a, b]
+---
+# H
+";
+    assert_eq!(run(md, ".frontmatter | .title"), vec!["Hello"]);
+    assert_eq!(run(md, ".frontmatter.author.name"), vec!["Ann"]);
+    assert_eq!(run(md, ".frontmatter.author.tags[1]"), vec!["b"]);
+
+    // A later `---` pair is a thematic break, not frontmatter
+    let md = "# Doc\n\ntext\n\n---\ntitle: x\n---\n";
+    assert!(run(md, ".frontmatter").is_empty());
+    assert!(run("# Doc\n", ".frontmatter | .title").is_empty());
+}
+
+#[test]
+fn has_and_keys_on_nested_objects() {
+    let md = "---\ntitle: Hi\nauthor:\n  name: Ann\n  links:\n    web: x\n    git: y\n---\n# Doc\n";
+    assert_eq!(run(md, ".frontmatter | has(\"title\")"), vec!["true"]);
+    assert_eq!(run(md, ".frontmatter | has(\"name\")"), vec!["false"]);
+    assert_eq!(run(md, ".frontmatter.author | has(\"name\")"), vec!["true"]);
+    assert_eq!(
+        run_array(md, ".frontmatter | keys"),
+        vec!["author", "title"]
+    );
+    assert_eq!(
+        run_array(md, ".frontmatter.author.links | keys"),
+        vec!["git", "web"]
+    );
+    assert_eq!(
+        run_array(md, "{y: 1, x: {d: 2, c: 3}} | .x | keys"),
+        vec!["c", "d"]
+    );
+}
+
+#[test]
+fn to_entries_and_from_entries_round_trip() {
+    let md = "---\ntitle: Hi\ndraft: false\ntags: [a, b]\n---\n# Doc\n";
+    assert_eq!(
+        run_array(md, ".frontmatter | to_entries | map(.key)"),
+        vec!["draft", "tags", "title"]
+    );
+    assert_eq!(
+        run_array(md, ".frontmatter | to_entries | map(.value | type)"),
+        vec!["boolean", "array", "string"]
+    );
+    assert_eq!(
+        run(md, ".frontmatter | to_entries | from_entries | .title"),
+        vec!["Hi"]
+    );
+    assert_eq!(
+        run_array(md, ".frontmatter | to_entries | from_entries | keys"),
+        vec!["draft", "tags", "title"]
+    );
+    // Missing values are null; later keys win
+    assert_eq!(
+        run(
+            md,
+            "[{key: \"x\"}, {key: \"y\", value: 1}, {key: \"y\", value: 2}] | from_entries | .y"
+        ),
+        vec!["2"]
+    );
+    assert_eq!(
+        run(md, "[{key: \"x\"}] | from_entries | .x | type"),
+        vec!["null"]
+    );
+
+    run_err(md, "[{value: 1}] | from_entries");
+    run_err(md, "[\"x\"] | from_entries");
+    run_err(md, "{x: 1} | from_entries");
+    run_err(md, "\"x\" | to_entries");
+}
+
+#[test]
+fn format_filters_encode_strings_and_rows() {
+    let md = "# Doc\n\nSee [the guide](<docs/a b.md?x=1&y=é>).\n";
+    assert_eq!(
+        run(md, ".link | .url | @uri"),
+        vec!["docs%2Fa%20b.md%3Fx%3D1%26y%3D%C3%A9"]
+    );
+    assert_eq!(
+        run(md, "\"hello world\" | @base64"),
+        vec!["aGVsbG8gd29ybGQ="]
+    );
+    assert_eq!(run(md, "\"ab\" | @base64"), vec!["YWI="]);
+    assert_eq!(run(md, "\"\" | @base64"), vec![""]);
+    assert_eq!(
+        run(md, "[1, \"say \\\"hi\\\"\", null, true] | @csv"),
+        vec!["1,\"say \"\"hi\"\"\",,true"]
+    );
+    assert_eq!(run(md, ".h1 | [.text, .level] | @csv"), vec!["\"Doc\",1"]);
+
+    run_err(md, "\"x\" | @csv");
+    run_err(md, "[[1]] | @csv");
+    run_err(md, "\"x\" | @html");
+}
+
+#[test]
+fn missing_object_key_yields_nothing() {
+    let md = "---\nauthor:\n  name: Ann\n---\n# Doc\n";
+    assert!(run(md, ".frontmatter.missing").is_empty());
+    assert!(run(md, ".frontmatter.author.missing.deeper").is_empty());
+    assert!(run(md, "{x: {y: 1}} | .x.z").is_empty());
+    assert_eq!(run(md, "{x: {y: 1}} | .x.y"), vec!["1"]);
+    // Unknown element properties are still reported
+    run_err(md, ".h1 | .nope");
+}
+
+#[test]
+fn links_report_line_and_autolink() {
+    let md = "\
+# A
+
+See [inline](https://a.example) and [ref][r].
+<https://auto.example>
+
+[r]: ./ref.md
+";
+    assert_eq!(
+        run(md, ".links | .url"),
+        vec!["https://a.example", "./ref.md", "https://auto.example"]
+    );
+    assert_eq!(run(md, ".links | .line"), vec!["3", "3", "4"]);
+    assert_eq!(
+        run(md, ".links | select(.autolink) | .text"),
+        vec!["https://auto.example"]
+    );
+}
+
+#[test]
+fn images_include_inline_reference_and_data_uri() {
+    let md = "\
+# A
+
+![logo](assets/logo.png \"The logo\")
+
+Text with ![icon][i] inline and ![dot](data:image/png;base64,iVBORw0KGgo=).
+
+- item ![badge](badge.svg)
+
+[i]: icons/icon.png
+";
+    assert_eq!(
+        run(md, ".images | .src"),
+        vec![
+            "assets/logo.png",
+            "icons/icon.png",
+            "data:image/png;base64,iVBORw0KGgo=",
+            "badge.svg",
+        ]
+    );
+    assert_eq!(run(md, ".images[0] | .title"), vec!["The logo"]);
+    assert_eq!(
+        run(md, ".images | .alt"),
+        vec!["logo", "icon", "dot", "badge"]
+    );
+}
+
+#[test]
+fn codeblocks_filters_by_language_case_insensitively() {
+    let md = "\
+# A
+
+```Bash
+  echo one
+```
+
+```rust
+fn main() {}
+```
+
+```bash
+echo two
+```
+";
+    assert_eq!(
+        run(md, ".codeblocks(\"bash\")"),
+        vec!["  echo one", "echo two"]
+    );
+    assert_eq!(run(md, "[.codeblocks(\"BASH\")] | count"), vec!["2"]);
+    assert_eq!(run(md, ".codeblocks | .lang"), vec!["Bash", "rust", "bash"]);
+    assert_eq!(run(md, ".codeblocks[1] | .\"code\""), vec!["fn main() {}"]);
+}
+
+#[test]
+fn quoted_property_reads_a_key_named_like_an_element() {
+    let md = "# A\n\n```rust\nfn a() {}\n```\n\n```sh\nls\n```\n";
+    assert_eq!(run(md, ".codeblocks | .\"code\""), vec!["fn a() {}", "ls"]);
+    assert_eq!(run(md, "{code: \"x\"} | .\"code\""), vec!["x"]);
+    // Unquoted, `.code` reads the piped block's source too
+    assert_eq!(run(md, ".codeblocks[0] | .code"), vec!["fn a() {}"]);
+    assert_eq!(run(md, "{code: \"x\"} | .code"), vec!["x"]);
+    // and selects the document's code blocks anywhere else
+    assert_eq!(run(md, "[.h1 | .code] | length"), vec!["2"]);
+
+    let doc = parse_markdown(md);
+    let values = query::execute(&doc, ".codeblocks[1]").unwrap();
+    let json = query::format_output(&values, query::OutputFormat::Json);
+    assert!(json.contains("\"code\":\"ls\""), "{json}");
+}
+
 // ---------------------------------------------------------------------------
 // Heading-scoped code blocks (item 5d)
 // ---------------------------------------------------------------------------
@@ -310,3 +691,275 @@ fn deeply_nested_parens_error_not_overflow() {
         .join()
         .unwrap();
 }
+
+#[test]
+fn tables_expose_plain_headers_rows_and_align() {
+    let md = "\
+# T
+
+| Name | Desc | N |
+|:-----|:----:|--:|
+| **bold** | a \\| b | |
+| `code` [link](http://x) | | 3 |
+";
+    assert_eq!(run(md, ".tables[0].headers"), vec!["Name\nDesc\nN"]);
+    assert_eq!(run(md, ".tables[0].rows[0]"), vec!["bold\na | b\n"]);
+    assert_eq!(run(md, ".tables[0].rows[1][0]"), vec!["code link"]);
+    assert_eq!(run(md, ".tables[0].rows[1][1]"), vec![""]);
+    assert_eq!(run(md, ".tables[0] | .align"), vec!["left\ncenter\nright"]);
+}
+
+#[test]
+fn tasks_match_only_genuine_markers() {
+    let md = "\
+# TODO
+
+- [ ] write docs
+- [x] ship **parser**
+* [X] uppercase done
+1. [ ] numbered
+
+- [link](http://example.com) is not a task
+[x] not a list item
+- [ab] not a marker
+
+```md
+- [ ] inside a fence
+```
+";
+    assert_eq!(
+        run(md, ".tasks | .text"),
+        vec!["write docs", "ship parser", "uppercase done", "numbered"]
+    );
+    assert_eq!(
+        run(md, ".tasks | select(.checked | not) | .text"),
+        vec!["write docs", "numbered"]
+    );
+    assert_eq!(run(md, ".tasks | .line"), vec!["3", "4", "5", "6"]);
+}
+
+#[test]
+fn csv_output_quotes_fields_and_unions_keys() {
+    use treemd::query::{OutputFormat, format_output};
+
+    let md = "# A\n\n## Install, \"quick\"\n\n## Usage\n";
+    let doc = parse_markdown(md);
+    let csv = |q: &str| {
+        let values = query::execute(&doc, q).unwrap();
+        format_output(&values, OutputFormat::Csv)
+    };
+
+    assert_eq!(
+        csv(".h2 | {title: .text, level: .level}"),
+        "title,level\n\"Install, \"\"quick\"\"\",2\nUsage,2"
+    );
+    // Keys missing from some objects become empty cells
+    assert_eq!(csv("{a: 1}, {b: \"x\ny\"}"), "a,b\n1,\n,\"x\ny\"");
+    // Scalars are a single column
+    assert_eq!(csv(".h2 | .level"), "2\n2");
+    assert!("csv".parse::<OutputFormat>().is_ok());
+}
+
+// ---------------------------------------------------------------------------
+// Source lines on extracted values
+// ---------------------------------------------------------------------------
+
+#[test]
+fn extracted_values_carry_source_lines() {
+    let md = "\
+---
+title: x
+---
+# Top
+
+Para one
+continues.
+
+```rust
+fn a() {}
+```
+
+## Sub
+
+| A | B |
+|---|---|
+| 1 | 2 |
+
+- item ![i](x.png)
+- two
+
+> quote
+
+![solo](y.png)
+
+- list
+
+  ```sh
+  nested
+  ```
+";
+    assert_eq!(run(md, ".h2 | .line"), vec!["13"]);
+    assert_eq!(
+        run(md, ".code | select(.lang == \"rust\") | .line"),
+        vec!["9"]
+    );
+    assert_eq!(
+        run(md, ".code | select(.lang == \"rust\") | .end_line"),
+        vec!["11"]
+    );
+    assert_eq!(run(md, ".table | line"), vec!["15"]);
+    assert_eq!(run(md, ".para | line"), vec!["6", "24"]);
+    assert_eq!(run(md, ".list | line"), vec!["19", "26"]);
+    assert_eq!(run(md, ".blockquote | .line"), vec!["22"]);
+    // An image inside a list item is placed on its list's line
+    assert_eq!(run(md, ".img | .line"), vec!["19", "24"]);
+    assert_eq!(run(md, ".h1 > .code | .line"), vec!["9"]);
+
+    // Code nested in a list has its own fence lines
+    assert_eq!(
+        run(md, ".code | select(.lang == \"sh\") | [.line, .end_line]"),
+        vec!["28\n30"]
+    );
+    // Plain values have no line: empty, not an error
+    assert_eq!(run(md, "\"x\" | .line"), Vec::<String>::new());
+    assert_eq!(run(md, "\"x\" | line"), Vec::<String>::new());
+
+    assert_eq!(run(md, ".table | {at: .line} | .at"), vec!["15"]);
+}
+
+#[test]
+fn a_quote_interrupting_a_paragraph_keeps_every_line() {
+    let md = "# T\n\nText\n> quoted\n\n```sh\nls\n```\n";
+    assert_eq!(run(md, ".para | .line"), vec!["3"]);
+    assert_eq!(run(md, ".blockquote | .line"), vec!["4"]);
+    assert_eq!(run(md, ".code | [.line, .end_line]"), vec!["6\n8"]);
+}
+
+// ---------------------------------------------------------------------------
+// Batch queries over many files
+// ---------------------------------------------------------------------------
+
+#[test]
+fn execute_many_keeps_input_order_and_reports_bad_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut paths = Vec::new();
+    for i in (0..12).rev() {
+        let path = dir.path().join(format!("doc{i}.md"));
+        let body: String = (0..=i).map(|n| format!("## {i}.{n}\n\n")).collect();
+        std::fs::write(&path, format!("# Doc {i}\n\n{body}")).unwrap();
+        paths.push(path);
+    }
+    paths.insert(3, dir.path().join("missing.md"));
+
+    for _ in 0..3 {
+        let results =
+            query::execute_many(&paths, ".h2 | text", query::OutputFormat::Plain, false).unwrap();
+        assert_eq!(results.len(), paths.len());
+        for (file, path) in results.iter().zip(&paths) {
+            assert_eq!(&file.path, path);
+        }
+        assert!(results[3].output.is_err());
+        assert_eq!(results[0].count, 12);
+        assert_eq!(
+            results[0].output.as_deref().unwrap().lines().next(),
+            Some("11.0")
+        );
+        assert_eq!(results[12].count, 1);
+        assert_eq!(results[12].output.as_deref().unwrap(), "0.0");
+    }
+
+    assert!(query::execute_many(&paths, ".h2 |", query::OutputFormat::Plain, false).is_err());
+}
+
+// ---------------------------------------------------------------------------
+// Callouts
+// ---------------------------------------------------------------------------
+
+#[test]
+fn callouts_expose_kind_title_and_text() {
+    let md = "# Doc\n\n> [!NOTE]\n> Read *this*\n> twice\n\n> just a quote\n\n> [!warning]- Mind the gap\n> Step **carefully**\n";
+    assert_eq!(run(md, ".callouts | .kind"), vec!["note", "warning"]);
+    assert_eq!(run(md, ".alerts | .title"), vec!["Note", "Mind the gap"]);
+    assert_eq!(
+        run(md, ".callout | .text"),
+        vec!["Read this\ntwice", "Step carefully"]
+    );
+    // Callouts are still blockquotes
+    assert_eq!(run(md, "[.blockquote] | length"), vec!["3"]);
+    assert_eq!(
+        run(md, ".callouts | select(.kind == \"warning\") | type"),
+        vec!["callout"]
+    );
+}
+
+// ---------------------------------------------------------------------------
+// Footnotes
+// ---------------------------------------------------------------------------
+
+#[test]
+fn footnotes_expose_label_and_text() {
+    let md = "# Doc\n\nA claim[^1] and another[^src].\n\n```\n[^x]: in code\n```\n\n[^1]: First *note*.\n[^src]: The source,\n    continued.\n";
+    assert_eq!(run(md, ".footnotes | .label"), vec!["1", "src"]);
+    assert_eq!(
+        run(md, ".footnote | .text"),
+        vec!["First note.", "The source, continued."]
+    );
+    assert_eq!(run(md, ".footnotes | .line"), vec!["9", "10"]);
+    assert_eq!(run(md, ".footnotes | type"), vec!["footnote", "footnote"]);
+}
+
+// ---------------------------------------------------------------------------
+// Definition lists
+// ---------------------------------------------------------------------------
+
+#[test]
+fn definitions_pair_each_term_with_its_definitions() {
+    let md = "# Glossary\n\nApple\n: A *fruit*\n: A company\n\nKiwi\n: Green\n  and fuzzy\n\nNot: a definition list.\n";
+    assert_eq!(
+        run(md, ".definitions | .term"),
+        vec!["Apple", "Apple", "Kiwi"]
+    );
+    assert_eq!(
+        run(md, ".dl | .definition"),
+        vec!["A fruit", "A company", "Green and fuzzy"]
+    );
+    assert_eq!(run(md, ".definitions | .line"), vec!["3", "3", "7"]);
+    assert_eq!(
+        run(md, ".definitions | select(.term == \"Kiwi\") | type"),
+        vec!["definition"]
+    );
+    // Definition lists are still paragraphs
+    assert_eq!(run(md, "[.para] | length"), vec!["3"]);
+}
+
+#[test]
+fn outline_nests_headings_in_document_order() {
+    let md = "# Guide\n## Install\n#### Deep\n## Usage\n# Appendix\n";
+    assert_eq!(
+        run(md, ".outline | .text"),
+        vec!["Guide", "Install", "Deep", "Usage", "Appendix"]
+    );
+    // Depth follows the tree, so a skipped level doesn't add one
+    assert_eq!(run(md, ".outline | .depth"), vec!["0", "1", "2", "1", "0"]);
+    assert_eq!(run(md, ".outline | .level"), vec!["1", "2", "4", "2", "1"]);
+    assert_eq!(
+        run(md, ".outline | .children_count"),
+        vec!["2", "1", "0", "0", "0"]
+    );
+    assert_eq!(
+        run(md, ".outline | select(.depth == 1) | .line"),
+        vec!["2", "4"]
+    );
+    assert_eq!(run(md, ".toc[-1] | .text"), vec!["Appendix"]);
+}
+
+#[test]
+fn wordcount_counts_section_prose_without_code() {
+    let md = "# Guide\n\nRead **this** first.\n\n```sh\nmake install\n```\n\n## Notes\n\nOne more line.\n\n# Appendix\n\nEnd.\n";
+    // Guide's section includes its Notes subsection
+    assert_eq!(run(md, ".h1 | wordcount"), vec!["8", "2"]);
+    assert_eq!(run(md, ".h1[Guide] | wordcount(true)"), vec!["10"]);
+    assert_eq!(run(md, ".h2 | text | wordcount"), vec!["1"]);
+    // A quote's lines are separate words, not run together
+    assert_eq!(run("# T\n\n> a b\n> c d\n", ".h1 | wordcount"), vec!["5"]);
+}